@@ -51,8 +51,8 @@ use mz_sql::ast::Expr;
 use mz_sql::catalog::{
     CatalogComputeInstance, CatalogDatabase, CatalogError as SqlCatalogError,
     CatalogItem as SqlCatalogItem, CatalogItemType as SqlCatalogItemType, CatalogItemType,
-    CatalogSchema, CatalogType, CatalogTypeDetails, IdReference, NameReference, SessionCatalog,
-    TypeReference,
+    CatalogSchema, CatalogType, CatalogTypeDetails, IdReference, NameReference, Privilege,
+    PrivilegeObject, SessionCatalog, TypeReference,
 };
 use mz_sql::names::{
     Aug, DatabaseId, FullObjectName, ObjectQualifiers, PartialObjectName, QualifiedObjectName,
@@ -180,6 +180,8 @@ pub struct CatalogState {
     compute_instances_by_id: HashMap<ComputeInstanceId, ComputeInstance>,
     compute_instances_by_name: HashMap<String, ComputeInstanceId>,
     roles: HashMap<String, Role>,
+    privileges: BTreeMap<PrivilegeObject, BTreeMap<RoleId, BTreeSet<Privilege>>>,
+    owners: BTreeMap<PrivilegeObject, RoleId>,
     config: mz_sql::catalog::CatalogConfig,
     oid_counter: u32,
     cluster_replica_sizes: ClusterReplicaSizeMap,
@@ -201,6 +203,79 @@ impl CatalogState {
         Ok(oid)
     }
 
+    /// Gets a role by its ID.
+    ///
+    /// Panics if `id` does not specify a valid role.
+    pub fn get_role(&self, id: &RoleId) -> &Role {
+        self.roles
+            .values()
+            .find(|role| role.id == *id)
+            .expect("role must exist")
+    }
+
+    /// Returns the IDs of all roles of which role `id` is a member, directly
+    /// or transitively. Does not include `id` itself or `PUBLIC`.
+    pub fn role_memberships(&self, id: RoleId) -> BTreeSet<RoleId> {
+        let mut memberships = BTreeSet::new();
+        let mut queue = VecDeque::from([id]);
+        while let Some(id) = queue.pop_front() {
+            let role = match self.roles.values().find(|role| role.id == id) {
+                Some(role) => role,
+                None => continue,
+            };
+            for parent in &role.membership {
+                if memberships.insert(*parent) {
+                    queue.push_back(*parent);
+                }
+            }
+        }
+        memberships
+    }
+
+    /// Returns the privileges that were explicitly granted to `grantee` on
+    /// `object`.
+    pub fn grantee_privileges(
+        &self,
+        object: &PrivilegeObject,
+        grantee: &RoleId,
+    ) -> BTreeSet<Privilege> {
+        self.privileges
+            .get(object)
+            .and_then(|grantees| grantees.get(grantee))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the role that owns `object`, if any. Objects created before
+    /// ownership was recorded, or whose owner was dropped, have no owner.
+    pub fn owner(&self, object: &PrivilegeObject) -> Option<RoleId> {
+        self.owners.get(object).copied()
+    }
+
+    /// Reports whether role `id` holds `privilege` on `object`, either
+    /// directly, through one of the roles it is a member of, or through
+    /// `PUBLIC`. Privileges of superusers are not considered.
+    pub fn has_privilege(
+        &self,
+        id: RoleId,
+        object: &PrivilegeObject,
+        privilege: Privilege,
+    ) -> bool {
+        let grantees = match self.privileges.get(object) {
+            Some(grantees) => grantees,
+            None => return false,
+        };
+        let mut roles = self.role_memberships(id);
+        roles.insert(id);
+        roles.insert(RoleId::Public);
+        roles.iter().any(|role| {
+            grantees
+                .get(role)
+                .map(|privileges| privileges.contains(&privilege))
+                .unwrap_or(false)
+        })
+    }
+
     /// Computes the IDs of any indexes that transitively depend on this catalog
     /// entry.
     pub fn dependent_indexes(&self, id: GlobalId) -> Vec<GlobalId> {
@@ -1362,6 +1437,16 @@ pub struct Role {
     pub id: RoleId,
     #[serde(skip)]
     pub oid: u32,
+    /// The SCRAM-SHA-256 verifier for the role's password, if any. See
+    /// [`crate::scram`].
+    #[serde(skip)]
+    pub password_verifier: Option<String>,
+    /// Whether the role bypasses all privilege checks.
+    pub super_user: bool,
+    /// Whether the role may log in.
+    pub login: bool,
+    /// The roles of which this role is a direct member.
+    pub membership: BTreeSet<RoleId>,
 }
 
 impl Role {
@@ -1515,7 +1600,7 @@ pub struct Sink {
     // TODO(benesch): this field duplicates information that could be derived
     // from the connection ID. Too hard to fix at the moment.
     pub connection: StorageSinkConnectionState,
-    pub envelope: SinkEnvelope,
+    pub envelope: Option<SinkEnvelope>,
     pub with_snapshot: bool,
     pub depends_on: Vec<GlobalId>,
     pub host_config: StorageHostConfig,
@@ -2081,6 +2166,8 @@ impl<S: Append> Catalog<S> {
                 compute_instances_by_id: HashMap::new(),
                 compute_instances_by_name: HashMap::new(),
                 roles: HashMap::new(),
+                privileges: BTreeMap::new(),
+                owners: BTreeMap::new(),
                 config: mz_sql::catalog::CatalogConfig {
                     start_time: to_datetime((config.now)()),
                     start_instant: Instant::now(),
@@ -2166,18 +2253,35 @@ impl<S: Append> Catalog<S> {
         }
 
         let roles = catalog.storage().await.load_roles().await?;
-        for (id, name) in roles {
+        for (id, role) in roles {
             let oid = catalog.allocate_oid()?;
             catalog.state.roles.insert(
-                name.clone(),
+                role.name.clone(),
                 Role {
-                    name: name.clone(),
+                    name: role.name,
                     id,
                     oid,
+                    password_verifier: role.password_verifier,
+                    super_user: role.super_user,
+                    login: role.login,
+                    membership: role.membership,
                 },
             );
         }
 
+        let privileges = catalog.storage().await.load_privileges().await?;
+        for (object, grantee, privileges) in privileges {
+            catalog
+                .state
+                .privileges
+                .entry(object)
+                .or_default()
+                .insert(grantee, privileges);
+        }
+
+        let owners = catalog.storage().await.load_owners().await?;
+        catalog.state.owners.extend(owners);
+
         catalog.load_builtin_types().await?;
 
         let persisted_builtin_ids = catalog.storage().await.load_system_gids().await?;
@@ -2682,6 +2786,9 @@ impl<S: Append> Catalog<S> {
                 key_reference: name_to_id_map[key_reference],
                 value_reference: name_to_id_map[value_reference],
             },
+            CatalogType::Range { element_reference } => CatalogType::Range {
+                element_reference: name_to_id_map[element_reference],
+            },
             CatalogType::Record { fields } => CatalogType::Record {
                 fields: fields
                     .into_iter()
@@ -2705,6 +2812,7 @@ impl<S: Append> Catalog<S> {
             CatalogType::MzTimestamp => CatalogType::MzTimestamp,
             CatalogType::Interval => CatalogType::Interval,
             CatalogType::Jsonb => CatalogType::Jsonb,
+            CatalogType::JsonPath => CatalogType::JsonPath,
             CatalogType::Numeric => CatalogType::Numeric,
             CatalogType::Oid => CatalogType::Oid,
             CatalogType::PgLegacyChar => CatalogType::PgLegacyChar,
@@ -3689,6 +3797,9 @@ impl<S: Append> Catalog<S> {
                 id: RoleId,
                 oid: u32,
                 name: String,
+                password_verifier: Option<String>,
+                super_user: bool,
+                login: bool,
             },
             CreateComputeInstance {
                 id: ComputeInstanceId,
@@ -3728,6 +3839,19 @@ impl<S: Append> Catalog<S> {
                 compute_id: ComputeInstanceId,
             },
             DropItem(GlobalId),
+            UpdatePrivileges {
+                object: PrivilegeObject,
+                grantee: RoleId,
+                privileges: BTreeSet<Privilege>,
+            },
+            UpdateOwner {
+                object: PrivilegeObject,
+                owner: RoleId,
+            },
+            UpdateRoleMembership {
+                id: RoleId,
+                membership: BTreeSet<RoleId>,
+            },
             UpdateItem {
                 id: GlobalId,
                 to_name: QualifiedObjectName,
@@ -4052,6 +4176,20 @@ impl<S: Append> Catalog<S> {
                             schema_name: DEFAULT_SCHEMA.to_string(),
                         },
                     )?;
+                    // Like the default schema of the default database, the
+                    // default schema of every database is usable by all roles.
+                    let object = PrivilegeObject::Schema(schema_id);
+                    let privileges = BTreeSet::from([Privilege::Usage]);
+                    tx.set_privileges(object, RoleId::Public, privileges.clone())?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdatePrivileges {
+                            object,
+                            grantee: RoleId::Public,
+                            privileges,
+                        },
+                    )?;
                 }
                 Op::CreateSchema {
                     database_id,
@@ -4095,14 +4233,28 @@ impl<S: Append> Catalog<S> {
                             schema_name,
                         },
                     )?;
+                    grant_to_creator(
+                        state,
+                        builtin_table_updates,
+                        tx,
+                        session,
+                        PrivilegeObject::Schema(schema_id),
+                    )?;
                 }
-                Op::CreateRole { name, oid } => {
+                Op::CreateRole {
+                    name,
+                    oid,
+                    password_verifier,
+                    super_user,
+                    login,
+                } => {
                     if is_reserved_name(&name) {
                         return Err(AdapterError::Catalog(Error::new(
                             ErrorKind::ReservedRoleName(name),
                         )));
                     }
-                    let role_id = tx.insert_user_role(&name)?;
+                    let role_id =
+                        tx.insert_user_role(&name, password_verifier.clone(), super_user, login)?;
                     state.add_to_audit_log(
                         session,
                         tx,
@@ -4122,6 +4274,9 @@ impl<S: Append> Catalog<S> {
                             id: role_id,
                             oid,
                             name,
+                            password_verifier,
+                            super_user,
+                            login,
                         },
                     )?;
                 }
@@ -4304,6 +4459,9 @@ impl<S: Append> Catalog<S> {
                         )?;
                     }
 
+                    // Temporary items aren't durable, and are only visible to
+                    // the session that created them anyway.
+                    let is_temporary = item.is_temporary();
                     catalog_action(
                         state,
                         builtin_table_updates,
@@ -4314,6 +4472,15 @@ impl<S: Append> Catalog<S> {
                             item,
                         },
                     )?;
+                    if !is_temporary {
+                        grant_to_creator(
+                            state,
+                            builtin_table_updates,
+                            tx,
+                            session,
+                            PrivilegeObject::Item(id),
+                        )?;
+                    }
                 }
                 Op::DropDatabase { id } => {
                     let database = &state.database_by_id[&id];
@@ -4510,6 +4677,81 @@ impl<S: Append> Catalog<S> {
                 Op::DropTimeline(timeline) => {
                     tx.remove_timestamp(timeline);
                 }
+                Op::GrantPrivileges {
+                    object,
+                    grantee,
+                    privileges,
+                } => {
+                    let mut new_privileges = state.grantee_privileges(&object, &grantee);
+                    new_privileges.extend(privileges);
+                    tx.set_privileges(object, grantee, new_privileges.clone())?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdatePrivileges {
+                            object,
+                            grantee,
+                            privileges: new_privileges,
+                        },
+                    )?;
+                }
+                Op::RevokePrivileges {
+                    object,
+                    grantee,
+                    privileges,
+                } => {
+                    let mut new_privileges = state.grantee_privileges(&object, &grantee);
+                    for privilege in &privileges {
+                        new_privileges.remove(privilege);
+                    }
+                    tx.set_privileges(object, grantee, new_privileges.clone())?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdatePrivileges {
+                            object,
+                            grantee,
+                            privileges: new_privileges,
+                        },
+                    )?;
+                }
+                Op::GrantRole { role_id, member_id } => {
+                    // Reject memberships that would make a role a member of
+                    // itself.
+                    if role_id == member_id || state.role_memberships(role_id).contains(&member_id)
+                    {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::CircularRoleMembership {
+                                role_name: state.get_role(&role_id).name.clone(),
+                                member_name: state.get_role(&member_id).name.clone(),
+                            },
+                        )));
+                    }
+                    let mut membership = state.get_role(&member_id).membership.clone();
+                    membership.insert(role_id);
+                    tx.update_role_membership(member_id, membership.clone())?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateRoleMembership {
+                            id: member_id,
+                            membership,
+                        },
+                    )?;
+                }
+                Op::RevokeRole { role_id, member_id } => {
+                    let mut membership = state.get_role(&member_id).membership.clone();
+                    membership.remove(&role_id);
+                    tx.update_role_membership(member_id, membership.clone())?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateRoleMembership {
+                            id: member_id,
+                            membership,
+                        },
+                    )?;
+                }
                 Op::RenameItem {
                     id,
                     to_name,
@@ -4723,6 +4965,41 @@ impl<S: Append> Catalog<S> {
             };
         }
 
+        /// Makes the role of the session's user, if it has one, the owner of
+        /// the newly created `object` and grants it every privilege on it.
+        fn grant_to_creator<S: Append>(
+            state: &mut CatalogState,
+            builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+            tx: &mut Transaction<'_, S>,
+            session: Option<&Session>,
+            object: PrivilegeObject,
+        ) -> Result<(), AdapterError> {
+            let role_id = match session.and_then(|session| state.roles.get(&session.user().name)) {
+                Some(role) => role.id,
+                None => return Ok(()),
+            };
+            tx.set_owner(object, Some(role_id))?;
+            catalog_action(
+                state,
+                builtin_table_updates,
+                Action::UpdateOwner {
+                    object,
+                    owner: role_id,
+                },
+            )?;
+            let privileges: BTreeSet<_> = Privilege::all_for(&object).iter().copied().collect();
+            tx.set_privileges(object, role_id, privileges.clone())?;
+            catalog_action(
+                state,
+                builtin_table_updates,
+                Action::UpdatePrivileges {
+                    object,
+                    grantee: role_id,
+                    privileges,
+                },
+            )
+        }
+
         fn catalog_action(
             state: &mut CatalogState,
             builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
@@ -4779,7 +5056,14 @@ impl<S: Append> Catalog<S> {
                     ));
                 }
 
-                Action::CreateRole { id, oid, name } => {
+                Action::CreateRole {
+                    id,
+                    oid,
+                    name,
+                    password_verifier,
+                    super_user,
+                    login,
+                } => {
                     info!("create role {}", name);
                     state.roles.insert(
                         name.clone(),
@@ -4787,6 +5071,10 @@ impl<S: Append> Catalog<S> {
                             name: name.clone(),
                             id,
                             oid,
+                            password_verifier,
+                            super_user,
+                            login,
+                            membership: BTreeSet::new(),
                         },
                     );
                     let role = &state.roles[&name];
@@ -4853,6 +5141,8 @@ impl<S: Append> Catalog<S> {
                     let db = state.database_by_id.get(&id).unwrap();
                     state.database_by_name.remove(db.name());
                     state.database_by_id.remove(&id);
+                    state.privileges.remove(&PrivilegeObject::Database(id));
+                    state.owners.remove(&PrivilegeObject::Database(id));
                 }
 
                 Action::DropSchema {
@@ -4863,11 +5153,21 @@ impl<S: Append> Catalog<S> {
                     let schema = db.schemas_by_id.get(&schema_id).unwrap();
                     db.schemas_by_name.remove(&schema.name.schema);
                     db.schemas_by_id.remove(&schema_id);
+                    state.privileges.remove(&PrivilegeObject::Schema(schema_id));
+                    state.owners.remove(&PrivilegeObject::Schema(schema_id));
                 }
 
                 Action::DropRole { name } => {
-                    if state.roles.remove(&name).is_some() {
+                    if let Some(role) = state.roles.remove(&name) {
                         info!("drop role {}", name);
+                        for grantees in state.privileges.values_mut() {
+                            grantees.remove(&role.id);
+                        }
+                        state.privileges.retain(|_, grantees| !grantees.is_empty());
+                        state.owners.retain(|_, owner| *owner != role.id);
+                        for other in state.roles.values_mut() {
+                            other.membership.remove(&role.id);
+                        }
                     }
                 }
 
@@ -4893,6 +5193,10 @@ impl<S: Append> Catalog<S> {
                         instance.exports.is_empty() && instance.replicas_by_id.is_empty(),
                         "not all items dropped before compute instance"
                     );
+                    state
+                        .privileges
+                        .remove(&PrivilegeObject::ComputeInstance(id));
+                    state.owners.remove(&PrivilegeObject::ComputeInstance(id));
                 }
 
                 Action::DropComputeReplica { name, compute_id } => {
@@ -4913,6 +5217,37 @@ impl<S: Append> Catalog<S> {
 
                 Action::DropItem(id) => {
                     state.drop_item(id);
+                    state.privileges.remove(&PrivilegeObject::Item(id));
+                    state.owners.remove(&PrivilegeObject::Item(id));
+                }
+
+                Action::UpdatePrivileges {
+                    object,
+                    grantee,
+                    privileges,
+                } => {
+                    let grantees = state.privileges.entry(object).or_default();
+                    if privileges.is_empty() {
+                        grantees.remove(&grantee);
+                    } else {
+                        grantees.insert(grantee, privileges);
+                    }
+                    if grantees.is_empty() {
+                        state.privileges.remove(&object);
+                    }
+                }
+
+                Action::UpdateOwner { object, owner } => {
+                    state.owners.insert(object, owner);
+                }
+
+                Action::UpdateRoleMembership { id, membership } => {
+                    let role = state
+                        .roles
+                        .values_mut()
+                        .find(|role| role.id == id)
+                        .expect("can only update known roles");
+                    role.membership = membership;
                 }
 
                 Action::UpdateItem {
@@ -5236,6 +5571,10 @@ impl<S: Append> Catalog<S> {
         self.state.compute_instances_by_id.get(&instance_id)
     }
 
+    pub fn try_get_role(&self, role_name: &str) -> Option<&Role> {
+        self.state.roles.get(role_name)
+    }
+
     pub fn user_compute_instances(&self) -> impl Iterator<Item = &ComputeInstance> {
         self.compute_instances()
             .filter(|compute_instance| compute_instance.id.is_user())
@@ -5391,6 +5730,9 @@ pub enum Op {
     CreateRole {
         name: String,
         oid: u32,
+        password_verifier: Option<String>,
+        super_user: bool,
+        login: bool,
     },
     CreateComputeInstance {
         name: String,
@@ -5429,6 +5771,24 @@ pub enum Op {
     /// may be violated.
     DropItem(GlobalId),
     DropTimeline(Timeline),
+    GrantPrivileges {
+        object: PrivilegeObject,
+        grantee: RoleId,
+        privileges: Vec<Privilege>,
+    },
+    RevokePrivileges {
+        object: PrivilegeObject,
+        grantee: RoleId,
+        privileges: Vec<Privilege>,
+    },
+    GrantRole {
+        role_id: RoleId,
+        member_id: RoleId,
+    },
+    RevokeRole {
+        role_id: RoleId,
+        member_id: RoleId,
+    },
     RenameItem {
         id: GlobalId,
         current_full_name: FullObjectName,
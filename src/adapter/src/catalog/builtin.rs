@@ -635,6 +635,28 @@ pub const TYPE_JSONB_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_JSONPATH: BuiltinType<NameReference> = BuiltinType {
+    name: "jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_JSONPATH_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::JsonPath,
+        array_id: None,
+    },
+};
+
+pub const TYPE_JSONPATH_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_JSONPATH_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_JSONPATH.name,
+        },
+        array_id: None,
+    },
+};
+
 pub const TYPE_ANY: BuiltinType<NameReference> = BuiltinType {
     name: "any",
     schema: PG_CATALOG_SCHEMA,
@@ -675,6 +697,16 @@ pub const TYPE_ANYNONARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_ANYRANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "anyrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_ANYRANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Pseudo,
+        array_id: None,
+    },
+};
+
 pub const TYPE_CHAR: BuiltinType<NameReference> = BuiltinType {
     name: "char",
     schema: PG_CATALOG_SCHEMA,
@@ -1009,6 +1041,150 @@ pub const TYPE_MZ_TIMESTAMP_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_INT4RANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "int4range",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INT4RANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_INT4.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_INT4RANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_int4range",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INT4RANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INT4RANGE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_INT8RANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "int8range",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INT8RANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_INT8.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_INT8RANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_int8range",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INT8RANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INT8RANGE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_NUMRANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "numrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_NUMRANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_NUMERIC.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_NUMRANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_numrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_NUMRANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_NUMRANGE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_DATERANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "daterange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_DATERANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_DATE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_DATERANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_daterange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_DATERANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_DATERANGE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_TSRANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "tsrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSRANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_TIMESTAMP.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_TSRANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tsrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSRANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TSRANGE.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_TSTZRANGE: BuiltinType<NameReference> = BuiltinType {
+    name: "tstzrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSTZRANGE_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Range {
+            element_reference: TYPE_TIMESTAMPTZ.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_TSTZRANGE_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tstzrange",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSTZRANGE_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TSTZRANGE.name,
+        },
+        array_id: None,
+    },
+};
+
 pub const MZ_DATAFLOW_OPERATORS: BuiltinLog = BuiltinLog {
     name: "mz_dataflow_operators",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1869,8 +2045,13 @@ pub const PG_TYPE: BuiltinView = BuiltinView {
     mz_schemas.oid AS typnamespace,
     NULL::pg_catalog.int2 AS typlen,
     -- 'a' is used internally to denote an array type, but in postgres they show up
-    -- as 'b'.
-    (CASE mztype WHEN 'a' THEN 'b' ELSE mztype END)::pg_catalog.char AS typtype,
+    -- as 'b'. Range types are stored as base types, but show up in postgres as
+    -- 'r'.
+    (CASE
+        WHEN mztype = 'a' THEN 'b'
+        WHEN category = 'range' THEN 'r'
+        ELSE mztype
+    END)::pg_catalog.char AS typtype,
     (CASE category
         WHEN 'array' THEN 'A'
         WHEN 'bit-string' THEN 'V'
@@ -1883,6 +2064,7 @@ pub const PG_TYPE: BuiltinView = BuiltinView {
         WHEN 'network-address' THEN 'I'
         WHEN 'numeric' THEN 'N'
         WHEN 'pseudo' THEN 'P'
+        WHEN 'range' THEN 'R'
         WHEN 'string' THEN 'S'
         WHEN 'timespan' THEN 'T'
         WHEN 'user-defined' THEN 'U'
@@ -2642,6 +2824,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_ANYARRAY),
         Builtin::Type(&TYPE_ANYELEMENT),
         Builtin::Type(&TYPE_ANYNONARRAY),
+        Builtin::Type(&TYPE_ANYRANGE),
         Builtin::Type(&TYPE_BOOL),
         Builtin::Type(&TYPE_BOOL_ARRAY),
         Builtin::Type(&TYPE_BYTEA),
@@ -2707,6 +2890,20 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_UINT8_ARRAY),
         Builtin::Type(&TYPE_MZ_TIMESTAMP),
         Builtin::Type(&TYPE_MZ_TIMESTAMP_ARRAY),
+        Builtin::Type(&TYPE_INT4RANGE),
+        Builtin::Type(&TYPE_INT4RANGE_ARRAY),
+        Builtin::Type(&TYPE_INT8RANGE),
+        Builtin::Type(&TYPE_INT8RANGE_ARRAY),
+        Builtin::Type(&TYPE_NUMRANGE),
+        Builtin::Type(&TYPE_NUMRANGE_ARRAY),
+        Builtin::Type(&TYPE_DATERANGE),
+        Builtin::Type(&TYPE_DATERANGE_ARRAY),
+        Builtin::Type(&TYPE_TSRANGE),
+        Builtin::Type(&TYPE_TSRANGE_ARRAY),
+        Builtin::Type(&TYPE_TSTZRANGE),
+        Builtin::Type(&TYPE_TSTZRANGE_ARRAY),
+        Builtin::Type(&TYPE_JSONPATH),
+        Builtin::Type(&TYPE_JSONPATH_ARRAY),
     ];
     for (schema, funcs) in &[
         (PG_CATALOG_SCHEMA, &*mz_sql::func::PG_CATALOG_BUILTINS),
@@ -3086,6 +3283,13 @@ mod tests {
                                 ty.name
                             )
                         }
+                        CatalogType::Range { .. } => {
+                            assert_eq!(
+                                pg_ty.ty, "r",
+                                "type {} is not a range type as expected",
+                                ty.name
+                            )
+                        }
                         _ => {
                             assert_eq!(
                                 pg_ty.ty, "b",
@@ -3249,6 +3453,7 @@ mod tests {
                     | ScalarType::Char { .. }
                     | ScalarType::VarChar { .. }
                     | ScalarType::Jsonb
+                    | ScalarType::JsonPath
                     | ScalarType::Uuid
                     | ScalarType::Array(_)
                     | ScalarType::Record { .. }
@@ -3256,7 +3461,8 @@ mod tests {
                     | ScalarType::RegProc
                     | ScalarType::RegType
                     | ScalarType::RegClass
-                    | ScalarType::Int2Vector => {}
+                    | ScalarType::Int2Vector
+                    | ScalarType::Range { .. } => {}
                 }
             }
         }
@@ -523,6 +523,7 @@ impl CatalogState {
                         diff,
                     });
                 }
+                StorageSinkConnection::S3(_) => {}
             };
             updates.push(BuiltinTableUpdate {
                 id: self.resolve_builtin_table(&MZ_SINKS),
@@ -56,6 +56,11 @@ pub enum ErrorKind {
     ReadOnlySystemSchema(String),
     #[error("system item '{0}' cannot be modified")]
     ReadOnlyItem(String),
+    #[error("role {} is a member of role {}", .role_name.quoted(), .member_name.quoted())]
+    CircularRoleMembership {
+        role_name: String,
+        member_name: String,
+    },
     #[error("cannot drop non-empty schema '{0}'")]
     SchemaNotEmpty(String),
     #[error("non-temporary items cannot depend on temporary item '{0}'")]
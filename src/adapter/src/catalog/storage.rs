@@ -24,7 +24,9 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::now::EpochMillis;
 use mz_repr::GlobalId;
-use mz_sql::catalog::{CatalogError as SqlCatalogError, CatalogItemType};
+use mz_sql::catalog::{
+    CatalogError as SqlCatalogError, CatalogItemType, Privilege, PrivilegeObject,
+};
 use mz_sql::names::{
     DatabaseId, ObjectQualifiers, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId, SchemaId,
     SchemaSpecifier,
@@ -258,6 +260,10 @@ async fn migrate<S: Append>(
                 },
                 RoleValue {
                     name: "materialize".into(),
+                    password_verifier: None,
+                    super_user: true,
+                    login: true,
+                    membership: BTreeSet::new(),
                 },
             )?;
             let id = txn.get_and_increment_id(AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
@@ -357,6 +363,43 @@ async fn migrate<S: Append>(
         |_, _| Ok(()),
         |_, _| Ok(()),
         |_, _| Ok(()),
+        // > Grant PUBLIC usage of the default schema and cluster.
+        // >
+        // > Introduced in v0.34.0.
+        // >
+        // > Roles that are not superusers must be able to use the objects
+        // > that sessions start out with. If the default schema or cluster
+        // > have since been dropped, there is nothing to grant.
+        |txn: &mut Transaction<'_, S>, _bootstrap_args| {
+            let usage = BTreeSet::from([Privilege::Usage]);
+            if txn
+                .schemas
+                .get(&SchemaKey {
+                    id: PUBLIC_SCHEMA_ID,
+                })
+                .is_some()
+            {
+                txn.set_privileges(
+                    PrivilegeObject::Schema(SchemaId::new(PUBLIC_SCHEMA_ID)),
+                    RoleId::Public,
+                    usage.clone(),
+                )?;
+            }
+            if txn
+                .compute_instances
+                .get(&ComputeInstanceKey {
+                    id: DEFAULT_USER_COMPUTE_INSTANCE_ID,
+                })
+                .is_some()
+            {
+                txn.set_privileges(
+                    PrivilegeObject::ComputeInstance(DEFAULT_USER_COMPUTE_INSTANCE_ID),
+                    RoleId::Public,
+                    usage,
+                )?;
+            }
+            Ok(())
+        },
         // Add new migrations above.
         //
         // Migrations should be preceded with a comment of the following form:
@@ -634,12 +677,32 @@ impl<S: Append> Connection<S> {
             .collect())
     }
 
-    pub async fn load_roles(&mut self) -> Result<Vec<(RoleId, String)>, Error> {
+    pub async fn load_roles(&mut self) -> Result<Vec<(RoleId, RoleValue)>, Error> {
         Ok(COLLECTION_ROLE
             .peek_one(&mut self.stash)
             .await?
             .into_iter()
-            .map(|(k, v)| (k.id, v.name))
+            .map(|(k, v)| (k.id, v))
+            .collect())
+    }
+
+    pub async fn load_privileges(
+        &mut self,
+    ) -> Result<Vec<(PrivilegeObject, RoleId, BTreeSet<Privilege>)>, Error> {
+        Ok(COLLECTION_PRIVILEGE
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .map(|(k, v)| (k.object, k.grantee, v.privileges))
+            .collect())
+    }
+
+    pub async fn load_owners(&mut self) -> Result<Vec<(PrivilegeObject, RoleId)>, Error> {
+        Ok(COLLECTION_OWNER
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .map(|(k, v)| (k.object, v.owner))
             .collect())
     }
 
@@ -966,6 +1029,8 @@ pub async fn transaction<'a, S: Append>(stash: &'a mut S) -> Result<Transaction<
     let timestamps = COLLECTION_TIMESTAMP.peek_one(stash).await?;
     let system_gid_mapping = COLLECTION_SYSTEM_GID_MAPPING.peek_one(stash).await?;
     let system_configurations = COLLECTION_SYSTEM_CONFIGURATION.peek_one(stash).await?;
+    let privileges = COLLECTION_PRIVILEGE.peek_one(stash).await?;
+    let owners = COLLECTION_OWNER.peek_one(stash).await?;
 
     Ok(Transaction {
         stash,
@@ -986,6 +1051,8 @@ pub async fn transaction<'a, S: Append>(stash: &'a mut S) -> Result<Transaction<
         timestamps: TableTransaction::new(timestamps, |_a, _b| false),
         system_gid_mapping: TableTransaction::new(system_gid_mapping, |_a, _b| false),
         system_configurations: TableTransaction::new(system_configurations, |_a, _b| false),
+        privileges: TableTransaction::new(privileges, |_a, _b| false),
+        owners: TableTransaction::new(owners, |_a, _b| false),
         audit_log_updates: Vec::new(),
         storage_usage_updates: Vec::new(),
     })
@@ -1007,6 +1074,8 @@ pub struct Transaction<'a, S> {
     timestamps: TableTransaction<TimestampKey, TimestampValue>,
     system_gid_mapping: TableTransaction<GidMappingKey, GidMappingValue>,
     system_configurations: TableTransaction<ServerConfigurationKey, ServerConfigurationValue>,
+    privileges: TableTransaction<PrivilegeKey, PrivilegeValue>,
+    owners: TableTransaction<OwnerKey, OwnerValue>,
     // Don't make this a table transaction so that it's not read into the stash
     // memory cache.
     audit_log_updates: Vec<(AuditLogKey, (), i64)>,
@@ -1099,17 +1168,43 @@ impl<'a, S: Append> Transaction<'a, S> {
         }
     }
 
-    pub fn insert_user_role(&mut self, role_name: &str) -> Result<RoleId, Error> {
-        self.insert_role(role_name, USER_ROLE_ID_ALLOC_KEY, RoleId::User)
+    pub fn insert_user_role(
+        &mut self,
+        role_name: &str,
+        password_verifier: Option<String>,
+        super_user: bool,
+        login: bool,
+    ) -> Result<RoleId, Error> {
+        self.insert_role(
+            RoleValue {
+                name: role_name.to_string(),
+                password_verifier,
+                super_user,
+                login,
+                membership: BTreeSet::new(),
+            },
+            USER_ROLE_ID_ALLOC_KEY,
+            RoleId::User,
+        )
     }
 
     fn insert_system_role(&mut self, role_name: &str) -> Result<RoleId, Error> {
-        self.insert_role(role_name, SYSTEM_ROLE_ID_ALLOC_KEY, RoleId::System)
+        self.insert_role(
+            RoleValue {
+                name: role_name.to_string(),
+                password_verifier: None,
+                super_user: true,
+                login: true,
+                membership: BTreeSet::new(),
+            },
+            SYSTEM_ROLE_ID_ALLOC_KEY,
+            RoleId::System,
+        )
     }
 
     fn insert_role<F>(
         &mut self,
-        role_name: &str,
+        value: RoleValue,
         id_alloc_key: &str,
         role_id_variant: F,
     ) -> Result<RoleId, Error>
@@ -1118,12 +1213,8 @@ impl<'a, S: Append> Transaction<'a, S> {
     {
         let id = self.get_and_increment_id(id_alloc_key.to_string())?;
         let id = role_id_variant(id);
-        match self.roles.insert(
-            RoleKey { id },
-            RoleValue {
-                name: role_name.to_string(),
-            },
-        ) {
+        let role_name = value.name.clone();
+        match self.roles.insert(RoleKey { id }, value) {
             Ok(_) => Ok(id),
             Err(_) => Err(Error::new(ErrorKind::RoleAlreadyExists(
                 role_name.to_owned(),
@@ -1307,6 +1398,7 @@ impl<'a, S: Append> Transaction<'a, S> {
     pub fn remove_database(&mut self, id: &DatabaseId) -> Result<(), Error> {
         let prev = self.databases.set(DatabaseKey { id: id.0 }, None)?;
         if prev.is_some() {
+            self.remove_privileges_on(PrivilegeObject::Database(*id));
             Ok(())
         } else {
             Err(SqlCatalogError::UnknownDatabase(id.to_string()).into())
@@ -1320,6 +1412,7 @@ impl<'a, S: Append> Transaction<'a, S> {
     ) -> Result<(), Error> {
         let prev = self.schemas.set(SchemaKey { id: schema_id.0 }, None)?;
         if prev.is_some() {
+            self.remove_privileges_on(PrivilegeObject::Schema(*schema_id));
             Ok(())
         } else {
             Err(SqlCatalogError::UnknownSchema(format!("{}.{}", database_id.0, schema_id.0)).into())
@@ -1327,15 +1420,88 @@ impl<'a, S: Append> Transaction<'a, S> {
     }
 
     pub fn remove_role(&mut self, name: &str) -> Result<(), Error> {
-        let n = self.roles.delete(|_k, v| v.name == name).len();
+        let deleted = self.roles.delete(|_k, v| v.name == name);
+        if deleted.is_empty() {
+            Err(SqlCatalogError::UnknownRole(name.to_owned()).into())
+        } else {
+            assert_eq!(deleted.len(), 1);
+            // Cascade delete the role's privileges, ownerships, and
+            // memberships. Objects without an owner can only be dropped or
+            // altered by superusers.
+            let id = deleted.into_element().0.id;
+            self.privileges.delete(|k, _v| k.grantee == id);
+            self.owners.delete(|_k, v| v.owner == id);
+            self.roles.update(|_k, v| {
+                if v.membership.contains(&id) {
+                    let mut v = v.clone();
+                    v.membership.remove(&id);
+                    Some(v)
+                } else {
+                    None
+                }
+            })?;
+            Ok(())
+        }
+    }
+
+    /// Sets the roles of which role `id` is a member.
+    pub fn update_role_membership(
+        &mut self,
+        id: RoleId,
+        membership: BTreeSet<RoleId>,
+    ) -> Result<(), Error> {
+        let n = self.roles.update(|k, v| {
+            if k.id == id {
+                let mut v = v.clone();
+                v.membership = membership.clone();
+                Some(v)
+            } else {
+                None
+            }
+        })?;
         assert!(n <= 1);
         if n == 1 {
             Ok(())
         } else {
-            Err(SqlCatalogError::UnknownRole(name.to_owned()).into())
+            Err(SqlCatalogError::UnknownRole(id.to_string()).into())
         }
     }
 
+    /// Sets the privileges that `grantee` holds on `object`, replacing any
+    /// previously held privileges.
+    pub fn set_privileges(
+        &mut self,
+        object: PrivilegeObject,
+        grantee: RoleId,
+        privileges: BTreeSet<Privilege>,
+    ) -> Result<(), Error> {
+        let value = if privileges.is_empty() {
+            None
+        } else {
+            Some(PrivilegeValue { privileges })
+        };
+        self.privileges
+            .set(PrivilegeKey { object, grantee }, value)?;
+        Ok(())
+    }
+
+    /// Sets the owner of `object`, or removes its owner if `owner` is `None`.
+    pub fn set_owner(
+        &mut self,
+        object: PrivilegeObject,
+        owner: Option<RoleId>,
+    ) -> Result<(), Error> {
+        self.owners
+            .set(OwnerKey { object }, owner.map(|owner| OwnerValue { owner }))?;
+        Ok(())
+    }
+
+    /// Removes the privileges on and the owner of `object`.
+    fn remove_privileges_on(&mut self, object: PrivilegeObject) {
+        self.privileges.delete(|k, _v| k.object == object);
+        self.owners.delete(|k, _v| k.object == object);
+    }
+
     pub fn remove_compute_instance(
         &mut self,
         name: &str,
@@ -1345,8 +1511,10 @@ impl<'a, S: Append> Transaction<'a, S> {
             Err(SqlCatalogError::UnknownComputeInstance(name.to_owned()).into())
         } else {
             assert_eq!(deleted.len(), 1);
-            // Cascade delete introsepction sources and cluster replicas.
+            // Cascade delete introsepction sources, cluster replicas, and
+            // privileges.
             let id = deleted.into_element().0.id;
+            self.remove_privileges_on(PrivilegeObject::ComputeInstance(id));
             self.compute_replicas
                 .delete(|_k, v| v.compute_instance_id == id);
             let introspection_source_indexes = self
@@ -1387,6 +1555,7 @@ impl<'a, S: Append> Transaction<'a, S> {
     pub fn remove_item(&mut self, id: GlobalId) -> Result<(), Error> {
         let prev = self.items.set(ItemKey { gid: id }, None)?;
         if prev.is_some() {
+            self.remove_privileges_on(PrivilegeObject::Item(id));
             Ok(())
         } else {
             Err(SqlCatalogError::UnknownItem(id.to_string()).into())
@@ -1402,6 +1571,12 @@ impl<'a, S: Append> Transaction<'a, S> {
     pub fn remove_items(&mut self, ids: BTreeSet<GlobalId>) -> Result<(), Error> {
         let n = self.items.delete(|k, _v| ids.contains(&k.gid)).len();
         if n == ids.len() {
+            let on_items = |object: &PrivilegeObject| match object {
+                PrivilegeObject::Item(id) => ids.contains(id),
+                _ => false,
+            };
+            self.privileges.delete(|k, _v| on_items(&k.object));
+            self.owners.delete(|k, _v| on_items(&k.object));
             Ok(())
         } else {
             let item_gids = self.items.items().keys().map(|k| k.gid).collect();
@@ -1678,6 +1853,20 @@ impl<'a, S: Append> Transaction<'a, S> {
             self.system_configurations.pending(),
         )
         .await?;
+        add_batch(
+            self.stash,
+            &mut batches,
+            &COLLECTION_PRIVILEGE,
+            self.privileges.pending(),
+        )
+        .await?;
+        add_batch(
+            self.stash,
+            &mut batches,
+            &COLLECTION_OWNER,
+            self.owners.pending(),
+        )
+        .await?;
         add_batch(
             self.stash,
             &mut batches,
@@ -1760,6 +1949,8 @@ pub async fn initialize_stash<S: Append>(stash: &mut S) -> Result<(), Error> {
     add_batch(stash, &mut batches, &COLLECTION_SYSTEM_CONFIGURATION).await?;
     add_batch(stash, &mut batches, &COLLECTION_AUDIT_LOG).await?;
     add_batch(stash, &mut batches, &COLLECTION_STORAGE_USAGE).await?;
+    add_batch(stash, &mut batches, &COLLECTION_PRIVILEGE).await?;
+    add_batch(stash, &mut batches, &COLLECTION_OWNER).await?;
     stash.append(&batches).await.map_err(|e| e.into())
 }
 
@@ -1869,7 +2060,47 @@ pub struct RoleKey {
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
 pub struct RoleValue {
-    name: String,
+    pub name: String,
+    /// The SCRAM-SHA-256 verifier for the role's password, if the role has a
+    /// password.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_verifier: Option<String>,
+    /// Whether the role bypasses all privilege checks. Roles created before
+    /// privileges existed were required to be superusers.
+    #[serde(default = "default_true")]
+    pub super_user: bool,
+    /// Whether the role may log in. Roles created before privileges existed
+    /// were required to be able to log in.
+    #[serde(default = "default_true")]
+    pub login: bool,
+    /// The roles of which this role is a direct member.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub membership: BTreeSet<RoleId>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct PrivilegeKey {
+    object: PrivilegeObject,
+    grantee: RoleId,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
+pub struct PrivilegeValue {
+    privileges: BTreeSet<Privilege>,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct OwnerKey {
+    object: PrivilegeObject,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
+pub struct OwnerValue {
+    owner: RoleId,
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
@@ -1938,6 +2169,9 @@ pub static COLLECTION_AUDIT_LOG: TypedCollection<AuditLogKey, ()> =
     TypedCollection::new("audit_log");
 pub static COLLECTION_STORAGE_USAGE: TypedCollection<StorageUsageKey, ()> =
     TypedCollection::new("storage_usage");
+pub static COLLECTION_PRIVILEGE: TypedCollection<PrivilegeKey, PrivilegeValue> =
+    TypedCollection::new("privilege");
+pub static COLLECTION_OWNER: TypedCollection<OwnerKey, OwnerValue> = TypedCollection::new("owner");
 
 pub static ALL_COLLECTIONS: &[&str] = &[
     COLLECTION_CONFIG.name(),
@@ -1955,4 +2189,6 @@ pub static ALL_COLLECTIONS: &[&str] = &[
     COLLECTION_SYSTEM_CONFIGURATION.name(),
     COLLECTION_AUDIT_LOG.name(),
     COLLECTION_STORAGE_USAGE.name(),
+    COLLECTION_PRIVILEGE.name(),
+    COLLECTION_OWNER.name(),
 ];
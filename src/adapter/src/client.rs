@@ -213,6 +213,16 @@ impl ConnClient {
         });
     }
 
+    /// Returns the SCRAM-SHA-256 verifier for the password of the named
+    /// role, or `None` if the role does not exist or does not have a
+    /// password.
+    ///
+    /// See [`crate::scram`] for details on the format of the verifier.
+    pub async fn role_password_verifier(&mut self, role_name: String) -> Option<String> {
+        self.send(|tx| Command::RolePasswordVerifier { role_name, tx })
+            .await
+    }
+
     async fn send<T, F>(&mut self, f: F) -> T
     where
         F: FnOnce(oneshot::Sender<T>) -> Command,
@@ -448,6 +458,7 @@ impl SessionClient {
                     | Command::StartTransaction { .. }
                     | Command::Commit { .. }
                     | Command::CancelRequest { .. }
+                    | Command::RolePasswordVerifier { .. }
                     | Command::DumpCatalog { .. }
                     | Command::CopyRows { .. }
                     | Command::Terminate { .. } => {}
@@ -102,7 +102,9 @@ use mz_persist_client::usage::StorageUsageClient;
 use mz_persist_client::ShardId;
 use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
 use mz_secrets::SecretsController;
-use mz_sql::ast::{CreateSourceStatement, CreateSubsourceStatement, Raw, Statement};
+use mz_sql::ast::{
+    CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement, Raw, Statement,
+};
 use mz_sql::names::Aug;
 use mz_sql::plan::{MutationKind, Params};
 use mz_stash::Append;
@@ -163,6 +165,7 @@ pub enum Message<T = mz_repr::Timestamp> {
     Command(Command),
     ControllerReady,
     CreateSourceStatementReady(CreateSourceStatementReady),
+    CreateSinkStatementReady(CreateSinkStatementReady),
     SinkConnectionReady(SinkConnectionReady),
     SendDiffs(SendDiffs),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
@@ -219,6 +222,19 @@ pub struct CreateSourceStatementReady {
     pub otel_ctx: OpenTelemetryContext,
 }
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct CreateSinkStatementReady {
+    pub session: Session,
+    #[derivative(Debug = "ignore")]
+    pub tx: ClientTransmitter<ExecuteResponse>,
+    pub result: Result<CreateSinkStatement<Aug>, AdapterError>,
+    pub params: Params,
+    pub depends_on: Vec<GlobalId>,
+    pub original_stmt: Statement<Raw>,
+    pub otel_ctx: OpenTelemetryContext,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct SinkConnectionReady {
@@ -18,6 +18,7 @@ use tokio::sync::{oneshot, watch};
 use tracing::Instrument;
 
 use mz_compute_client::response::PeekResponse;
+use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::ScalarType;
 use mz_sql::ast::{InsertSource, Query, Raw, SetExpr, Statement};
@@ -30,8 +31,11 @@ use crate::command::{
     Canceled, Command, ExecuteResponse, Response, StartupMessage, StartupResponse,
 };
 use crate::coord::appends::{Deferred, PendingWriteTxn};
-use crate::coord::peek::PendingPeek;
-use crate::coord::{ConnMeta, Coordinator, CreateSourceStatementReady, Message, PendingTxn};
+use crate::coord::peek::{PeekResponseUnary, PendingPeek};
+use crate::coord::{
+    ConnMeta, Coordinator, CreateSinkStatementReady, CreateSourceStatementReady, Message,
+    PendingTxn,
+};
 use crate::error::AdapterError;
 use crate::metrics;
 use crate::session::{PreparedStatement, Session, TransactionStatus};
@@ -93,6 +97,14 @@ impl<S: Append + 'static> Coordinator<S> {
                 self.handle_cancel(conn_id, secret_key);
             }
 
+            Command::RolePasswordVerifier { role_name, tx } => {
+                let verifier = self
+                    .catalog
+                    .try_get_role(&role_name)
+                    .and_then(|role| role.password_verifier.clone());
+                let _ = tx.send(verifier);
+            }
+
             Command::DumpCatalog { session, tx } => {
                 // TODO(benesch): when we have RBAC, dumping the catalog should
                 // require superuser permissions.
@@ -185,8 +197,14 @@ impl<S: Append + 'static> Coordinator<S> {
                 });
                 return;
             }
+            // Roles that are created on demand can log in, but hold no
+            // privileges beyond those of `PUBLIC` until they are granted
+            // some. Only the bootstrap and system roles are superusers.
             let plan = CreateRolePlan {
                 name: session.user().name.to_string(),
+                password: None,
+                super_user: false,
+                login: true,
             };
             if let Err(err) = self.sequence_create_role(&session, plan).await {
                 let _ = tx.send(Response {
@@ -197,6 +215,19 @@ impl<S: Append + 'static> Coordinator<S> {
             }
         }
 
+        if let Some(role) = self.catalog.try_get_role(&session.user().name) {
+            if !role.login {
+                let _ = tx.send(Response {
+                    result: Err(AdapterError::Unauthorized(format!(
+                        "role {} is not permitted to log in",
+                        role.name.quoted()
+                    ))),
+                    session,
+                });
+                return;
+            }
+        }
+
         let mut messages = vec![];
         let catalog = self.catalog.for_session(&session);
         if catalog.active_database().is_none() {
@@ -391,6 +422,10 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::DropRoles(_)
                     | Statement::DropClusters(_)
                     | Statement::DropClusterReplicas(_)
+                    | Statement::GrantPrivileges(_)
+                    | Statement::RevokePrivileges(_)
+                    | Statement::GrantRole(_)
+                    | Statement::RevokeRole(_)
                     | Statement::Insert(_)
                     | Statement::Update(_) => {
                         return tx.send(
@@ -415,6 +450,7 @@ impl<S: Append + 'static> Coordinator<S> {
         // purification.  This should be done back on the main thread.
         // We do the validation:
         //   - In the handler for `Message::CreateSourceStatementReady`, before we handle the purified statement.
+        //   - In the handler for `Message::CreateSinkStatementReady`, likewise.
         // If we add special handling for more types of `Statement`s, we'll need to ensure similar verification
         // occurs.
         match stmt {
@@ -450,6 +486,37 @@ impl<S: Append + 'static> Coordinator<S> {
                 });
             }
 
+            // `CREATE SINK` statements may need to fetch schemas from a schema
+            // registry, so they are also purified off the main thread.
+            Statement::CreateSink(stmt) => {
+                let internal_cmd_tx = self.internal_cmd_tx.clone();
+                let conn_id = session.conn_id();
+                let purify_fut = mz_sql::pure::purify_create_sink(
+                    Box::new(catalog.into_owned()),
+                    stmt,
+                    self.connection_context.clone(),
+                );
+                let otel_ctx = OpenTelemetryContext::obtain();
+                task::spawn(|| format!("purify:{conn_id}"), async move {
+                    let result = purify_fut.await.map_err(|e| e.into());
+                    // It is not an error for purification to complete after `internal_cmd_rx` is dropped.
+                    let result = internal_cmd_tx.send(Message::CreateSinkStatementReady(
+                        CreateSinkStatementReady {
+                            session,
+                            tx,
+                            result,
+                            params,
+                            depends_on,
+                            original_stmt,
+                            otel_ctx,
+                        },
+                    ));
+                    if let Err(e) = result {
+                        tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
+                });
+            }
+
             // `CREATE SUBSOURCE` statements are disallowed for users and are only generated
             // automatically as part of purification
             Statement::CreateSubsource(_) => tx.send(
@@ -537,9 +604,61 @@ impl<S: Append + 'static> Coordinator<S> {
                 // So we ignore errors when sending the response.
                 let _ = rows_tx.send(PeekResponse::Canceled);
             }
+
+            self.cancel_pending_subscribes(conn_id);
         }
     }
 
+    /// Cancels the subscribes that the connection has in flight.
+    ///
+    /// The subscribes' dataflows are dropped immediately, rather than when the
+    /// connection's transaction ends, as the canceled statement has failed the
+    /// transaction in any case.
+    fn cancel_pending_subscribes(&mut self, conn_id: ConnectionId) {
+        let sink_ids: Vec<_> = self
+            .pending_subscribes
+            .iter()
+            .filter(|(_, pending_subscribe)| pending_subscribe.conn_id == conn_id)
+            .map(|(sink_id, _)| *sink_id)
+            .collect();
+        if sink_ids.is_empty() {
+            return;
+        }
+        for sink_id in &sink_ids {
+            let pending_subscribe = self
+                .pending_subscribes
+                .remove(sink_id)
+                .expect("known to exist");
+            // The connection may have already hung up.
+            let _ = pending_subscribe.channel.send(PeekResponseUnary::Canceled);
+            self.metrics
+                .active_subscribes
+                .with_label_values(&[pending_subscribe.session_type])
+                .dec();
+        }
+        let conn_meta = self
+            .active_conns
+            .get_mut(&conn_id)
+            .expect("must exist for active connections");
+        let (drop_sinks, retained_sinks) = conn_meta
+            .drop_sinks
+            .drain(..)
+            .partition(|sink| sink_ids.contains(&sink.global_id));
+        conn_meta.drop_sinks = retained_sinks;
+        self.drop_compute_sinks(drop_sinks);
+    }
+
+    /// Reports whether a cancellation request has arrived for the statement
+    /// that the connection is currently executing.
+    ///
+    /// Statements that wait on asynchronous work before they are sequenced
+    /// use this to bail out once that work completes.
+    pub(crate) fn is_canceled(&self, conn_id: ConnectionId) -> bool {
+        self.active_conns.get(&conn_id).map_or(false, |conn_meta| {
+            matches!(*conn_meta.cancel_tx.borrow(), Canceled::Canceled)
+        })
+    }
+
     /// Handle termination of a client session.
     ///
     /// This cleans up any state in the coordinator associated with the session.
@@ -482,7 +482,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 .unwrap()
                 .into_owned(),
             connection,
-            envelope: Some(sink.envelope),
+            envelope: sink.envelope,
             as_of,
             status_id,
             from_storage_metadata: (),
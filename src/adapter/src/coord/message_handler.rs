@@ -31,8 +31,8 @@ use crate::util::ResultExt;
 use crate::{catalog, AdapterNotice};
 
 use crate::coord::{
-    Coordinator, CreateSourceStatementReady, Message, PendingReadTxn, SendDiffs,
-    SinkConnectionReady,
+    Coordinator, CreateSinkStatementReady, CreateSourceStatementReady, Message, PendingReadTxn,
+    SendDiffs, SinkConnectionReady,
 };
 
 impl<S: Append + 'static> Coordinator<S> {
@@ -47,6 +47,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Message::CreateSourceStatementReady(ready) => {
                 self.message_create_source_statement_ready(ready).await
             }
+            Message::CreateSinkStatementReady(ready) => {
+                self.message_create_sink_statement_ready(ready).await
+            }
             Message::SinkConnectionReady(ready) => self.message_sink_connection_ready(ready).await,
             Message::WriteLockGrant(write_lock_guard) => {
                 self.message_write_lock_grant(write_lock_guard).await;
@@ -271,6 +274,12 @@ impl<S: Append + 'static> Coordinator<S> {
     ) {
         otel_ctx.attach_as_parent();
 
+        // Purification may take a while, during which the client may have
+        // given up on the statement.
+        if self.is_canceled(session.conn_id()) {
+            return tx.send(Ok(ExecuteResponse::Canceled), session);
+        }
+
         // Ensure that all dependencies still exist after purification, as a
         // `DROP CONNECTION` may have sneaked in. If any have gone missing, we
         // repurify the original statement. This will either produce a nice
@@ -343,6 +352,46 @@ impl<S: Append + 'static> Coordinator<S> {
         tx.send(result, session);
     }
 
+    #[tracing::instrument(level = "debug", skip(self, tx, session))]
+    async fn message_create_sink_statement_ready(
+        &mut self,
+        CreateSinkStatementReady {
+            mut session,
+            tx,
+            result,
+            params,
+            depends_on,
+            original_stmt,
+            otel_ctx,
+        }: CreateSinkStatementReady,
+    ) {
+        otel_ctx.attach_as_parent();
+
+        // See `message_create_source_statement_ready` for details on these
+        // checks.
+        if self.is_canceled(session.conn_id()) {
+            return tx.send(Ok(ExecuteResponse::Canceled), session);
+        }
+        if !depends_on
+            .iter()
+            .all(|id| self.catalog.try_get_entry(id).is_some())
+        {
+            self.handle_execute_inner(original_stmt, params, session, tx)
+                .await;
+            return;
+        }
+
+        let stmt = match result {
+            Ok(stmt) => stmt,
+            Err(e) => return tx.send(Err(e), session),
+        };
+
+        match self.plan_statement(&mut session, Statement::CreateSink(stmt), &params) {
+            Ok(plan) => self.sequence_plan(tx, session, plan, depends_on).await,
+            Err(e) => tx.send(Err(e), session),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip(self, session_and_tx))]
     async fn message_sink_connection_ready(
         &mut self,
@@ -524,7 +573,15 @@ impl<S: Append + 'static> Coordinator<S> {
         let mut ready_txns = Vec::new();
         let mut deferred_txns = Vec::new();
 
-        for read_txn in pending_read_txns {
+        for mut read_txn in pending_read_txns {
+            // Don't make canceled reads wait to be linearized.
+            if let PendingReadTxn::Read { txn, .. } = &mut read_txn {
+                if self.is_canceled(txn.session.conn_id()) {
+                    txn.response = Ok(ExecuteResponse::Canceled);
+                    ready_txns.push(read_txn);
+                    continue;
+                }
+            }
             if let Some((timestamp, Some(timeline))) = &read_txn.timestamp() {
                 let timestamp_oracle = self.get_timestamp_oracle_mut(timeline);
                 let read_ts = timestamp_oracle.read_ts();
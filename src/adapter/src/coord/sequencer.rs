@@ -48,14 +48,14 @@ use mz_sql::plan::{
     CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
     CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan, DropComputeReplicasPlan,
     DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
-    FetchPlan, IndexOption, InsertPlan, MaterializedView, MutationKind, OptimizerConfig, PeekPlan,
-    Plan, PlanKind, QueryWhen, RaisePlan, ReadThenWritePlan, ResetVariablePlan, RotateKeysPlan,
+    FetchPlan, GrantPrivilegesPlan, GrantRolePlan, IndexOption, InsertPlan, MaterializedView,
+    MutationKind, OptimizerConfig, PeekPlan, Plan, PlanKind, QueryWhen, RaisePlan,
+    ReadThenWritePlan, ResetVariablePlan, RevokePrivilegesPlan, RevokeRolePlan, RotateKeysPlan,
     SendDiffsPlan, SetVariablePlan, ShowVariablePlan, SubscribeFrom, SubscribePlan, View,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_stash::Append;
 use mz_storage_client::controller::{CollectionDescription, DataSource, ReadPolicy, StorageError};
-use mz_storage_client::types::sinks::StorageSinkConnectionBuilder;
 use mz_storage_client::types::sources::{IngestionDescription, SourceExport, Timeline};
 
 use crate::catalog::builtin::{
@@ -77,6 +77,8 @@ use crate::error::AdapterError;
 use crate::explain_new::optimizer_trace::OptimizerTrace;
 use crate::metrics;
 use crate::notice::AdapterNotice;
+use crate::rbac;
+use crate::scram::ScramSha256Verifier;
 use crate::session::vars::{IsolationLevel, CLUSTER_VAR_NAME, DATABASE_VAR_NAME};
 use crate::session::{
     EndTransactionAction, PreparedStatement, Session, TransactionOps, TransactionStatus, Var,
@@ -111,6 +113,10 @@ impl<S: Append + 'static> Coordinator<S> {
             }
         }
 
+        if let Err(e) = rbac::check_plan(&self.catalog, &session, &plan, &depends_on) {
+            return tx.send(Err(e), session);
+        }
+
         match plan {
             Plan::CreateSource(plan) => {
                 let source_id = match self.catalog.allocate_user_id().await {
@@ -213,6 +219,24 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::DropRoles(plan) => {
                 tx.send(self.sequence_drop_roles(&session, plan).await, session);
             }
+            Plan::GrantPrivileges(plan) => {
+                tx.send(
+                    self.sequence_grant_privileges(&session, plan).await,
+                    session,
+                );
+            }
+            Plan::RevokePrivileges(plan) => {
+                tx.send(
+                    self.sequence_revoke_privileges(&session, plan).await,
+                    session,
+                );
+            }
+            Plan::GrantRole(plan) => {
+                tx.send(self.sequence_grant_role(&session, plan).await, session);
+            }
+            Plan::RevokeRole(plan) => {
+                tx.send(self.sequence_revoke_role(&session, plan).await, session);
+            }
             Plan::DropComputeInstances(plan) => {
                 tx.send(
                     self.sequence_drop_compute_instances(&mut session, plan)
@@ -742,10 +766,26 @@ impl<S: Append + 'static> Coordinator<S> {
         session: &Session,
         plan: CreateRolePlan,
     ) -> Result<ExecuteResponse, AdapterError> {
+        // As in PostgreSQL, a password that is already a SCRAM-SHA-256
+        // verifier is stored as is, which allows dumped roles to be restored.
+        let password_verifier = match plan.password {
+            None => None,
+            Some(password) => match password.parse::<ScramSha256Verifier>() {
+                Ok(verifier) => Some(verifier.to_string()),
+                Err(_) => Some(
+                    ScramSha256Verifier::new(&password)
+                        .map_err(|e| AdapterError::Unstructured(e.into()))?
+                        .to_string(),
+                ),
+            },
+        };
         let oid = self.catalog.allocate_oid()?;
         let op = catalog::Op::CreateRole {
             name: plan.name,
             oid,
+            password_verifier,
+            super_user: plan.super_user,
+            login: plan.login,
         };
         self.catalog_transact(Some(session), vec![op])
             .await
@@ -1269,10 +1309,6 @@ impl<S: Append + 'static> Coordinator<S> {
             }
         };
 
-        // Knowing that we're only handling kafka sinks here helps us simplify.
-        let StorageSinkConnectionBuilder::Kafka(connection_builder) =
-            sink.connection_builder.clone();
-
         // Then try to create a placeholder catalog item with an unknown
         // connection. If that fails, we're done, though if the client specified
         // `if_not_exists` we'll tell the client we succeeded.
@@ -1283,9 +1319,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let catalog_sink = catalog::Sink {
             create_sql: sink.create_sql,
             from: sink.from,
-            connection: StorageSinkConnectionState::Pending(StorageSinkConnectionBuilder::Kafka(
-                connection_builder,
-            )),
+            connection: StorageSinkConnectionState::Pending(sink.connection_builder.clone()),
             envelope: sink.envelope,
             with_snapshot,
             depends_on,
@@ -1706,6 +1740,80 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::DroppedRole)
     }
 
+    async fn sequence_grant_privileges(
+        &mut self,
+        session: &Session,
+        plan: GrantPrivilegesPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let mut ops = Vec::new();
+        for object in &plan.objects {
+            for grantee in &plan.grantees {
+                ops.push(catalog::Op::GrantPrivileges {
+                    object: *object,
+                    grantee: *grantee,
+                    privileges: plan.privileges.clone(),
+                });
+            }
+        }
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::GrantedPrivilege)
+    }
+
+    async fn sequence_revoke_privileges(
+        &mut self,
+        session: &Session,
+        plan: RevokePrivilegesPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let mut ops = Vec::new();
+        for object in &plan.objects {
+            for grantee in &plan.grantees {
+                ops.push(catalog::Op::RevokePrivileges {
+                    object: *object,
+                    grantee: *grantee,
+                    privileges: plan.privileges.clone(),
+                });
+            }
+        }
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::RevokedPrivilege)
+    }
+
+    async fn sequence_grant_role(
+        &mut self,
+        session: &Session,
+        plan: GrantRolePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let mut ops = Vec::new();
+        for role_id in &plan.role_ids {
+            for member_id in &plan.member_ids {
+                ops.push(catalog::Op::GrantRole {
+                    role_id: *role_id,
+                    member_id: *member_id,
+                });
+            }
+        }
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::GrantedRole)
+    }
+
+    async fn sequence_revoke_role(
+        &mut self,
+        session: &Session,
+        plan: RevokeRolePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let mut ops = Vec::new();
+        for role_id in &plan.role_ids {
+            for member_id in &plan.member_ids {
+                ops.push(catalog::Op::RevokeRole {
+                    role_id: *role_id,
+                    member_id: *member_id,
+                });
+            }
+        }
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::RevokedRole)
+    }
+
     async fn sequence_drop_compute_instances(
         &mut self,
         session: &mut Session,
@@ -1976,6 +2084,7 @@ impl<S: Append + 'static> Coordinator<S> {
             }
             None => vars.reset(&name, local)?,
         }
+        session.refresh_pcx_timezone();
 
         Ok(ExecuteResponse::SetVariable { name, reset: false })
     }
@@ -1986,6 +2095,7 @@ impl<S: Append + 'static> Coordinator<S> {
         plan: ResetVariablePlan,
     ) -> Result<ExecuteResponse, AdapterError> {
         session.vars_mut().reset(&plan.name, false)?;
+        session.refresh_pcx_timezone();
         Ok(ExecuteResponse::SetVariable {
             name: plan.name,
             reset: true,
@@ -2491,6 +2601,7 @@ impl<S: Append + 'static> Coordinator<S> {
         self.pending_subscribes.insert(
             *sink_id,
             PendingSubscribe {
+                conn_id: session.conn_id(),
                 session_type,
                 channel: tx,
                 emit_progress,
@@ -3633,6 +3744,10 @@ impl<S: Append + 'static> Coordinator<S> {
             | Plan::DropDatabase(_)
             | Plan::DropSchema(_)
             | Plan::DropRoles(_)
+            | Plan::GrantPrivileges(_)
+            | Plan::RevokePrivileges(_)
+            | Plan::GrantRole(_)
+            | Plan::RevokeRole(_)
             | Plan::DropComputeInstances(_)
             | Plan::DropComputeReplicas(_)
             | Plan::DropItems(_)
@@ -160,6 +160,11 @@ fn id_gen(expr: &HirRelationExpr) -> Result<impl Iterator<Item = LocalId>, Recur
     expr.visit_post(&mut |expr| {
         match expr {
             HirRelationExpr::Let { id, .. } => max_id = std::cmp::max(max_id, id.into()),
+            HirRelationExpr::LetRec { bindings, .. } => {
+                for (_, id, _, _) in bindings {
+                    max_id = std::cmp::max(max_id, id.into());
+                }
+            }
             _ => (),
         };
     })?;
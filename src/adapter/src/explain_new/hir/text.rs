@@ -121,6 +121,19 @@ impl<'a> Displayable<'a, HirRelationExpr> {
                     Ok(())
                 })?;
             }
+            LetRec { bindings, body } => {
+                writeln!(f, "{}Return", ctx.indent)?;
+                ctx.indented(|ctx| Displayable::from(body.as_ref()).fmt_text(f, ctx))?;
+                writeln!(f, "{}With Recursive", ctx.indent)?;
+                ctx.indented(|ctx| {
+                    for (_, id, value, _) in bindings.iter() {
+                        // TODO: print the name and not the id
+                        writeln!(f, "{}cte {} =", ctx.indent, *id)?;
+                        ctx.indented(|ctx| Displayable::from(value).fmt_text(f, ctx))?;
+                    }
+                    Ok(())
+                })?;
+            }
             Get { id, .. } => match id {
                 Id::Local(id) => {
                     // TODO: resolve local id to the human-readable name from the context
@@ -343,6 +356,12 @@ impl<'a> DisplayText for Displayable<'a, HirScalarExpr> {
                         Displayable::from(scalar.expr.as_ref()).fmt_text(f, ctx)?;
                         write!(f, ")")?
                     }
+                    WindowExprType::Aggregate(aggregate) => {
+                        write!(f, "{}(", aggregate.aggregate_expr.func.clone().into_expr())?;
+                        Displayable::from(aggregate.aggregate_expr.expr.as_ref())
+                            .fmt_text(f, ctx)?;
+                        write!(f, ")")?
+                    }
                 }
                 write!(f, " over (")?;
                 for (i, e) in expr.partition.iter().enumerate() {
@@ -130,6 +130,18 @@ impl<'a> DisplayText<PlanRenderingContext<'_, Plan>> for Displayable<'a, Plan> {
                     Ok(())
                 })?;
             }
+            LetRec { ids, values, body } => {
+                writeln!(f, "{}Return", ctx.indent)?;
+                ctx.indented(|ctx| Displayable::from(body.as_ref()).fmt_text(f, ctx))?;
+                writeln!(f, "{}With Recursive", ctx.indent)?;
+                ctx.indented(|ctx| {
+                    for (id, value) in ids.iter().zip(values.iter()) {
+                        writeln!(f, "{}cte {} =", ctx.indent, *id)?;
+                        ctx.indented(|ctx| Displayable::from(value).fmt_text(f, ctx))?;
+                    }
+                    Ok(())
+                })?;
+            }
             Mfp {
                 input,
                 mfp,
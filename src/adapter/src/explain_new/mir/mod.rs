@@ -303,6 +303,11 @@ fn id_gen(expr: &MirRelationExpr) -> Result<impl Iterator<Item = LocalId>, Recur
     expr.visit_post(&mut |expr| {
         match expr {
             MirRelationExpr::Let { id, .. } => max_id = std::cmp::max(max_id, id.into()),
+            MirRelationExpr::LetRec { ids, .. } => {
+                for id in ids {
+                    max_id = std::cmp::max(max_id, id.into());
+                }
+            }
             _ => (),
         };
     })?;
@@ -323,6 +328,14 @@ fn enforce_let_id_sequence(expr: &mut MirRelationExpr) -> Result<(), RecursionLi
                 id_map.insert(id_old, LocalId::new(id_new));
                 std::mem::swap(id, &mut LocalId::new(id_new));
             }
+            MirRelationExpr::LetRec { ids, .. } => {
+                for id in ids {
+                    let id_old = id.clone();
+                    let id_new = u64::cast_from(id_map.len());
+                    id_map.insert(id_old, LocalId::new(id_new));
+                    std::mem::swap(id, &mut LocalId::new(id_new));
+                }
+            }
             MirRelationExpr::Get {
                 id: Id::Local(id_old),
                 ..
@@ -423,6 +436,9 @@ fn normalize_lets_at_root(expr: &mut MirRelationExpr) {
     let normalized_expr = match expr {
         Constant { rows: _, typ: _ } => None,
         Get { id: _, typ: _ } => None,
+        // Bindings in a `LetRec` block may not be hoisted out of it, as they
+        // may refer to the recursive bindings.
+        LetRec { .. } => None,
         Let {
             id: id_1,
             value: value_1,
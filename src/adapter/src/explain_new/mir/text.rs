@@ -125,6 +125,19 @@ impl<'a> Displayable<'a, MirRelationExpr> {
                     })?;
                 }
             }
+            LetRec { ids, values, body } => {
+                write!(f, "{}Return", ctx.indent)?;
+                self.fmt_attributes(f, ctx)?;
+                ctx.indented(|ctx| Displayable::from(body.as_ref()).fmt_text(f, ctx))?;
+                writeln!(f, "{}With Recursive", ctx.indent)?;
+                ctx.indented(|ctx| {
+                    for (id, value) in ids.iter().zip(values.iter()) {
+                        writeln!(f, "{}cte {} =", ctx.indent, *id)?;
+                        ctx.indented(|ctx| Displayable::from(value).fmt_text(f, ctx))?;
+                    }
+                    Ok(())
+                })?;
+            }
             Get { id, .. } => {
                 match id {
                     Id::Local(id) => {
@@ -37,6 +37,7 @@ mod coord;
 mod error;
 mod explain_new;
 mod notice;
+mod rbac;
 mod subscribe;
 mod util;
 
@@ -44,6 +45,7 @@ pub mod catalog;
 pub mod client;
 pub mod config;
 pub mod metrics;
+pub mod scram;
 pub mod session;
 
 pub use crate::client::{Client, ConnClient, Handle, SessionClient};
@@ -110,6 +110,10 @@ where
         StatementKind::DropRoles => "drop_roles",
         StatementKind::DropClusters => "drop_clusters",
         StatementKind::DropClusterReplicas => "drop_cluster_replicas",
+        StatementKind::GrantPrivileges => "grant_privileges",
+        StatementKind::RevokePrivileges => "revoke_privileges",
+        StatementKind::GrantRole => "grant_role",
+        StatementKind::RevokeRole => "revoke_role",
         StatementKind::SetVariable => "set_variable",
         StatementKind::ResetVariable => "reset_variable",
         StatementKind::Show => "show",
@@ -0,0 +1,378 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Role-based access control.
+//!
+//! Privileges are granted to roles on databases, schemas, clusters and
+//! relations with `GRANT` and revoked with `REVOKE`, and are stored in the
+//! catalog. The role that creates a schema or item owns it and is granted
+//! every privilege on it, and only its owner may drop or alter it. The checks
+//! in this module run in the sequencer before a plan is executed. Internal
+//! users and superusers bypass them entirely.
+
+use mz_compute_client::controller::ComputeInstanceId;
+use mz_ore::str::StrExt;
+use mz_repr::GlobalId;
+use mz_sql::catalog::{CatalogItemType, Privilege, PrivilegeObject};
+use mz_sql::names::{
+    DatabaseId, ObjectQualifiers, ResolvedDatabaseSpecifier, RoleId, SchemaSpecifier,
+};
+use mz_sql::plan::{MutationKind, Plan};
+use mz_stash::Append;
+
+use crate::catalog::Catalog;
+use crate::error::AdapterError;
+use crate::session::Session;
+
+/// Verifies that the role of the session's user holds every privilege that is
+/// required to execute `plan`.
+///
+/// `depends_on` are the items that `plan` refers to.
+pub fn check_plan<S: Append>(
+    catalog: &Catalog<S>,
+    session: &Session,
+    plan: &Plan,
+    depends_on: &[GlobalId],
+) -> Result<(), AdapterError> {
+    if session.user().is_internal() {
+        return Ok(());
+    }
+    // Users without a role in the catalog only hold the privileges of `PUBLIC`.
+    let role_id = match catalog.try_get_role(&session.user().name) {
+        Some(role) if role.super_user => return Ok(()),
+        Some(role) => role.id,
+        None => RoleId::Public,
+    };
+    let checker = Checker {
+        catalog,
+        session,
+        role_id,
+    };
+
+    // The item being written to, which requires a write privilege rather than
+    // `SELECT`.
+    let mut target = None;
+
+    match plan {
+        Plan::CreateDatabase(_)
+        | Plan::DropDatabase(_)
+        | Plan::CreateRole(_)
+        | Plan::DropRoles(_)
+        | Plan::GrantPrivileges(_)
+        | Plan::RevokePrivileges(_)
+        | Plan::GrantRole(_)
+        | Plan::RevokeRole(_)
+        | Plan::CreateComputeInstance(_)
+        | Plan::DropComputeInstances(_)
+        | Plan::CreateComputeReplica(_)
+        | Plan::DropComputeReplicas(_)
+        | Plan::AlterSystemSet(_)
+        | Plan::AlterSystemReset(_)
+        | Plan::AlterSystemResetAll(_) => {
+            return Err(AdapterError::Unauthorized(
+                "permission denied: must be superuser to perform this action".into(),
+            ));
+        }
+
+        Plan::CreateSchema(plan) => {
+            if let ResolvedDatabaseSpecifier::Id(id) = &plan.database_spec {
+                checker.require_database(*id, Privilege::Create)?;
+            }
+        }
+        Plan::DropSchema(plan) => {
+            if let Some((database_id, schema_id)) = &plan.id {
+                checker.require_owner(PrivilegeObject::Schema(*schema_id), || {
+                    let database = catalog.get_database(database_id);
+                    format!(
+                        "schema {}",
+                        database.schemas_by_id[schema_id].name.schema.quoted()
+                    )
+                })?;
+            }
+            return Ok(());
+        }
+
+        Plan::CreateConnection(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateSource(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateSecret(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateSink(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateTable(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateView(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateType(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+        }
+        Plan::CreateMaterializedView(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+            checker.require_compute_instance(
+                plan.materialized_view.compute_instance,
+                Privilege::Create,
+            )?;
+        }
+        Plan::CreateIndex(plan) => {
+            checker.require_schema(&plan.name.qualifiers, Privilege::Create)?;
+            checker.require_compute_instance(plan.index.compute_instance, Privilege::Create)?;
+        }
+
+        Plan::DropItems(plan) => {
+            for id in &plan.items {
+                checker.require_item_owner(*id)?;
+            }
+            return Ok(());
+        }
+        Plan::AlterItemRename(plan) => {
+            checker.require_item_owner(plan.id)?;
+            checker.require_item_schema(plan.id, Privilege::Create)?;
+            return Ok(());
+        }
+        Plan::AlterIndexSetOptions(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+        Plan::AlterIndexResetOptions(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+        Plan::AlterSink(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+        Plan::AlterSource(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+        Plan::AlterSecret(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+        Plan::RotateKeys(plan) => {
+            checker.require_item_owner(plan.id)?;
+            return Ok(());
+        }
+
+        Plan::Peek(_) | Plan::Subscribe(_) => {
+            if !depends_on.is_empty() {
+                if let Ok(compute_instance) = catalog.active_compute_instance(session) {
+                    checker.require_compute_instance(compute_instance.id, Privilege::Usage)?;
+                }
+            }
+        }
+        Plan::Insert(plan) => {
+            checker.require_item(plan.id, Privilege::Insert)?;
+            target = Some(plan.id);
+        }
+        Plan::CopyFrom(plan) => {
+            checker.require_item(plan.id, Privilege::Insert)?;
+            target = Some(plan.id);
+        }
+        Plan::ReadThenWrite(plan) => {
+            checker.require_item(plan.id, mutation_privilege(&plan.kind))?;
+            target = Some(plan.id);
+        }
+        Plan::SendDiffs(plan) => {
+            checker.require_item(plan.id, mutation_privilege(&plan.kind))?;
+            target = Some(plan.id);
+        }
+
+        Plan::Explain(_)
+        | Plan::SendRows(_)
+        | Plan::DiscardTemp
+        | Plan::DiscardAll
+        | Plan::EmptyQuery
+        | Plan::ShowAllVariables
+        | Plan::ShowVariable(_)
+        | Plan::SetVariable(_)
+        | Plan::ResetVariable(_)
+        | Plan::StartTransaction(_)
+        | Plan::CommitTransaction
+        | Plan::AbortTransaction
+        | Plan::AlterNoop(_)
+        | Plan::Declare(_)
+        | Plan::Fetch(_)
+        | Plan::Close(_)
+        | Plan::Prepare(_)
+        | Plan::Execute(_)
+        | Plan::Deallocate(_)
+        | Plan::Raise(_) => {}
+    }
+
+    // Every item that the plan refers to must be visible to the role, and
+    // relations must be readable.
+    for id in depends_on {
+        checker.require_item_schema(*id, Privilege::Usage)?;
+        if Some(*id) == target {
+            continue;
+        }
+        match catalog.get_entry(id).item_type() {
+            CatalogItemType::Table
+            | CatalogItemType::Source
+            | CatalogItemType::View
+            | CatalogItemType::MaterializedView => {
+                checker.require_item(*id, Privilege::Select)?;
+            }
+            CatalogItemType::Sink
+            | CatalogItemType::Index
+            | CatalogItemType::Type
+            | CatalogItemType::Func
+            | CatalogItemType::Secret
+            | CatalogItemType::Connection => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn mutation_privilege(kind: &MutationKind) -> Privilege {
+    match kind {
+        MutationKind::Insert => Privilege::Insert,
+        MutationKind::Update => Privilege::Update,
+        MutationKind::Delete => Privilege::Delete,
+    }
+}
+
+struct Checker<'a, S> {
+    catalog: &'a Catalog<S>,
+    session: &'a Session,
+    role_id: RoleId,
+}
+
+impl<'a, S: Append> Checker<'a, S> {
+    fn require(
+        &self,
+        object: PrivilegeObject,
+        privilege: Privilege,
+        describe: impl FnOnce() -> String,
+    ) -> Result<(), AdapterError> {
+        if self
+            .catalog
+            .state()
+            .has_privilege(self.role_id, &object, privilege)
+        {
+            Ok(())
+        } else {
+            Err(AdapterError::Unauthorized(format!(
+                "permission denied for {}",
+                describe()
+            )))
+        }
+    }
+
+    /// Requires the role to own `object`, directly or through one of the
+    /// roles it is a member of.
+    fn require_owner(
+        &self,
+        object: PrivilegeObject,
+        describe: impl FnOnce() -> String,
+    ) -> Result<(), AdapterError> {
+        let state = self.catalog.state();
+        match state.owner(&object) {
+            Some(owner)
+                if owner == self.role_id
+                    || state.role_memberships(self.role_id).contains(&owner) =>
+            {
+                Ok(())
+            }
+            _ => Err(AdapterError::Unauthorized(format!(
+                "permission denied: must be owner of {}",
+                describe()
+            ))),
+        }
+    }
+
+    fn require_database(&self, id: DatabaseId, privilege: Privilege) -> Result<(), AdapterError> {
+        self.require(PrivilegeObject::Database(id), privilege, || {
+            format!("database {}", self.catalog.get_database(&id).name.quoted())
+        })
+    }
+
+    /// Requires `privilege` on the schema described by `qualifiers`. The
+    /// ambient schemas, which include the system and temporary schemas, are
+    /// always accessible.
+    fn require_schema(
+        &self,
+        qualifiers: &ObjectQualifiers,
+        privilege: Privilege,
+    ) -> Result<(), AdapterError> {
+        let schema_id = match (&qualifiers.database_spec, &qualifiers.schema_spec) {
+            (ResolvedDatabaseSpecifier::Id(_), SchemaSpecifier::Id(id)) => *id,
+            _ => return Ok(()),
+        };
+        self.require(PrivilegeObject::Schema(schema_id), privilege, || {
+            let schema = self.catalog.get_schema(
+                &qualifiers.database_spec,
+                &qualifiers.schema_spec,
+                self.session.conn_id(),
+            );
+            format!("schema {}", schema.name.schema.quoted())
+        })
+    }
+
+    fn require_compute_instance(
+        &self,
+        id: ComputeInstanceId,
+        privilege: Privilege,
+    ) -> Result<(), AdapterError> {
+        self.require(PrivilegeObject::ComputeInstance(id), privilege, || {
+            let name = self
+                .catalog
+                .try_get_compute_instance(id)
+                .map(|compute_instance| compute_instance.name.clone())
+                .unwrap_or_else(|| id.to_string());
+            format!("cluster {}", name.quoted())
+        })
+    }
+
+    /// Requires `privilege` on the schema that contains item `id`.
+    fn require_item_schema(&self, id: GlobalId, privilege: Privilege) -> Result<(), AdapterError> {
+        self.require_schema(&self.catalog.get_entry(&id).name().qualifiers, privilege)
+    }
+
+    /// Requires the role to own item `id`. Items in the ambient schemas, which
+    /// include the temporary schema, are exempt.
+    fn require_item_owner(&self, id: GlobalId) -> Result<(), AdapterError> {
+        let entry = self.catalog.get_entry(&id);
+        if entry.name().qualifiers.database_spec == ResolvedDatabaseSpecifier::Ambient {
+            return Ok(());
+        }
+        self.require_owner(PrivilegeObject::Item(id), || {
+            let full_name = self
+                .catalog
+                .resolve_full_name(entry.name(), Some(self.session.conn_id()));
+            format!("{} {}", entry.item_type(), full_name.to_string().quoted())
+        })
+    }
+
+    /// Requires `privilege` on item `id`. System items and items in the
+    /// ambient schemas are always accessible.
+    fn require_item(&self, id: GlobalId, privilege: Privilege) -> Result<(), AdapterError> {
+        let entry = self.catalog.get_entry(&id);
+        if id.is_system()
+            || entry.name().qualifiers.database_spec == ResolvedDatabaseSpecifier::Ambient
+        {
+            return Ok(());
+        }
+        self.require(PrivilegeObject::Item(id), privilege, || {
+            let full_name = self
+                .catalog
+                .resolve_full_name(entry.name(), Some(self.session.conn_id()));
+            format!("{} {}", entry.item_type(), full_name.to_string().quoted())
+        })
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SCRAM-SHA-256 password verifiers.
+//!
+//! Role passwords are never stored in the catalog. Instead we store a verifier
+//! as described in [RFC 5802] and [RFC 7677], which is sufficient to check a
+//! client's proof of knowledge of the password during a SCRAM exchange, but
+//! not to recover the password itself. The textual format of the verifier
+//! matches PostgreSQL's:
+//!
+//! ```text
+//! SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>
+//! ```
+//!
+//! where the salt and keys are base64 encoded.
+//!
+//! Unlike PostgreSQL, passwords are not normalized with SASLprep before they
+//! are hashed. PostgreSQL uses the raw password when SASLprep fails, so this
+//! only affects passwords that contain non-ASCII characters.
+//!
+//! [RFC 5802]: https://datatracker.ietf.org/doc/html/rfc5802
+//! [RFC 7677]: https://datatracker.ietf.org/doc/html/rfc7677
+
+use std::fmt;
+use std::str::FromStr;
+
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkcs5;
+use openssl::pkey::PKey;
+use openssl::rand;
+use openssl::sha;
+use openssl::sign::Signer;
+
+use mz_ore::cast::CastFrom;
+
+/// The name of the SASL mechanism implemented by this module.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The number of PBKDF2 iterations used for new verifiers. This matches
+/// PostgreSQL's default.
+const ITERATIONS: u32 = 4096;
+
+/// The length of the salt used for new verifiers, in bytes.
+const SALT_LEN: usize = 16;
+
+/// A SCRAM-SHA-256 verifier for a password.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ScramSha256Verifier {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramSha256Verifier {
+    /// Computes a verifier for `password` using a random salt.
+    pub fn new(password: &str) -> Result<ScramSha256Verifier, ErrorStack> {
+        let mut salt = vec![0; SALT_LEN];
+        rand::rand_bytes(&mut salt)?;
+        ScramSha256Verifier::with_salt(password, salt, ITERATIONS)
+    }
+
+    fn with_salt(
+        password: &str,
+        salt: Vec<u8>,
+        iterations: u32,
+    ) -> Result<ScramSha256Verifier, ErrorStack> {
+        let mut salted_password = [0; 32];
+        pkcs5::pbkdf2_hmac(
+            password.as_bytes(),
+            &salt,
+            usize::cast_from(iterations),
+            MessageDigest::sha256(),
+            &mut salted_password,
+        )?;
+        let client_key = hmac(&salted_password, b"Client Key")?;
+        let server_key = hmac(&salted_password, b"Server Key")?;
+        Ok(ScramSha256Verifier {
+            iterations,
+            salt,
+            stored_key: sha::sha256(&client_key).to_vec(),
+            server_key,
+        })
+    }
+
+    /// Returns the number of PBKDF2 iterations used to salt the password.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the salt used to salt the password.
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// Reports whether `client_proof` proves that the client knows the
+    /// password for the exchange described by `auth_message`.
+    pub fn verify_client_proof(
+        &self,
+        auth_message: &[u8],
+        client_proof: &[u8],
+    ) -> Result<bool, ErrorStack> {
+        let client_signature = hmac(&self.stored_key, auth_message)?;
+        if client_proof.len() != client_signature.len() {
+            return Ok(false);
+        }
+        let client_key: Vec<u8> = client_proof
+            .iter()
+            .zip(client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        Ok(memcmp::eq(&sha::sha256(&client_key), &self.stored_key))
+    }
+
+    /// Computes the signature that proves to the client that the server
+    /// knows the verifier for the exchange described by `auth_message`.
+    pub fn server_signature(&self, auth_message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+        hmac(&self.server_key, auth_message)
+    }
+}
+
+impl fmt::Debug for ScramSha256Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Avoid leaking the keys into logs.
+        f.debug_struct("ScramSha256Verifier")
+            .field("iterations", &self.iterations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for ScramSha256Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode_block(&self.salt),
+            base64::encode_block(&self.stored_key),
+            base64::encode_block(&self.server_key),
+        )
+    }
+}
+
+impl FromStr for ScramSha256Verifier {
+    type Err = InvalidVerifier;
+
+    fn from_str(s: &str) -> Result<ScramSha256Verifier, InvalidVerifier> {
+        let mut parts = s.split('$');
+        let (mechanism, params, keys) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(mechanism), Some(params), Some(keys)) if parts.next().is_none() => {
+                (mechanism, params, keys)
+            }
+            _ => return Err(InvalidVerifier),
+        };
+        if mechanism != SCRAM_SHA_256 {
+            return Err(InvalidVerifier);
+        }
+        let (iterations, salt) = params.split_once(':').ok_or(InvalidVerifier)?;
+        let (stored_key, server_key) = keys.split_once(':').ok_or(InvalidVerifier)?;
+        let decode = |s| base64::decode_block(s).map_err(|_| InvalidVerifier);
+        let verifier = ScramSha256Verifier {
+            iterations: iterations.parse().map_err(|_| InvalidVerifier)?,
+            salt: decode(salt)?,
+            stored_key: decode(stored_key)?,
+            server_key: decode(server_key)?,
+        };
+        if verifier.iterations == 0
+            || verifier.stored_key.len() != 32
+            || verifier.server_key.len() != 32
+        {
+            return Err(InvalidVerifier);
+        }
+        Ok(verifier)
+    }
+}
+
+/// The error returned when parsing a malformed [`ScramSha256Verifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidVerifier;
+
+impl fmt::Display for InvalidVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid SCRAM-SHA-256 verifier")
+    }
+}
+
+impl std::error::Error for InvalidVerifier {}
+
+/// Computes the HMAC-SHA-256 of `data` under `key`.
+fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    signer.sign_to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The test vector from RFC 7677, section 3.
+    const PASSWORD: &str = "pencil";
+    const SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+    const AUTH_MESSAGE: &str = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+        r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+        s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+        c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const CLIENT_PROOF: &str = "dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_SIGNATURE: &str = "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    #[test]
+    fn test_rfc_7677() {
+        let salt = base64::decode_block(SALT).unwrap();
+        let verifier = ScramSha256Verifier::with_salt(PASSWORD, salt, 4096).unwrap();
+        let proof = base64::decode_block(CLIENT_PROOF).unwrap();
+        assert!(verifier
+            .verify_client_proof(AUTH_MESSAGE.as_bytes(), &proof)
+            .unwrap());
+        assert!(!verifier
+            .verify_client_proof(AUTH_MESSAGE.as_bytes(), &proof[1..])
+            .unwrap());
+        assert!(!verifier
+            .verify_client_proof(b"n=user,r=other", &proof)
+            .unwrap());
+        assert_eq!(
+            base64::encode_block(&verifier.server_signature(AUTH_MESSAGE.as_bytes()).unwrap()),
+            SERVER_SIGNATURE,
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let verifier = ScramSha256Verifier::new("hunter2").unwrap();
+        let s = verifier.to_string();
+        assert!(s.starts_with("SCRAM-SHA-256$4096:"));
+        assert_eq!(s.parse::<ScramSha256Verifier>().unwrap(), verifier);
+
+        for invalid in [
+            "",
+            "hunter2",
+            "md5$4096:c2FsdA==$a2V5:a2V5",
+            "SCRAM-SHA-256$4096:c2FsdA==",
+            "SCRAM-SHA-256$0:c2FsdA==$a2V5:a2V5",
+            "SCRAM-SHA-256$4096:c2FsdA==$a2V5:a2V5",
+        ] {
+            assert_eq!(invalid.parse::<ScramSha256Verifier>(), Err(InvalidVerifier));
+        }
+    }
+}
@@ -137,6 +137,19 @@ impl<T: TimestampManipulation> Session<T> {
         &self.transaction().inner().unwrap().pcx
     }
 
+    /// Updates the current transaction's PlanContext, if any, to use the time
+    /// zone in the `TimeZone` session variable.
+    ///
+    /// Changes to the time zone must be visible to the statements that follow
+    /// them in the same transaction, so this must be called whenever the
+    /// variable changes.
+    pub fn refresh_pcx_timezone(&mut self) {
+        let timezone = self.vars.timezone().to_timezone();
+        if let Some(txn) = self.transaction.inner_mut() {
+            txn.pcx.timezone = timezone;
+        }
+    }
+
     /// Reports whether the session is a system session.
     pub fn is_system(&self) -> bool {
         crate::catalog::is_reserved_name(&self.user().name)
@@ -172,7 +185,11 @@ impl<T: TimestampManipulation> Session<T> {
                 let id = self.next_transaction_id;
                 self.next_transaction_id = self.next_transaction_id.wrapping_add(1);
                 self.transaction = TransactionStatus::InTransaction(Transaction {
-                    pcx: PlanContext::new(wall_time, self.vars.qgm_optimizations()),
+                    pcx: PlanContext::new(
+                        wall_time,
+                        self.vars.qgm_optimizations(),
+                        self.vars.timezone().to_timezone(),
+                    ),
                     ops: TransactionOps::None,
                     write_lock_guard: None,
                     access,
@@ -206,7 +223,11 @@ impl<T: TimestampManipulation> Session<T> {
             let id = self.next_transaction_id;
             self.next_transaction_id = self.next_transaction_id.wrapping_add(1);
             let txn = Transaction {
-                pcx: PlanContext::new(wall_time, self.vars.qgm_optimizations()),
+                pcx: PlanContext::new(
+                    wall_time,
+                    self.vars.qgm_optimizations(),
+                    self.vars.timezone().to_timezone(),
+                ),
                 ops: TransactionOps::None,
                 write_lock_guard: None,
                 access: None,
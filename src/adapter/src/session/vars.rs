@@ -11,6 +11,7 @@ use std::borrow::Borrow;
 use std::fmt;
 use std::time::Duration;
 
+use chrono_tz::Tz;
 use const_format::concatcp;
 use mz_sql::ast::{Ident, SetVariableValue, Value as AstValue};
 use once_cell::sync::Lazy;
@@ -18,6 +19,7 @@ use serde::Serialize;
 use uncased::UncasedStr;
 
 use mz_ore::cast;
+use mz_repr::adt::datetime::Timezone;
 use mz_sql::DEFAULT_SCHEMA;
 use mz_sql_parser::ast::TransactionIsolationLevel;
 use mz_sql_parser::parser::parse_set_variable_value;
@@ -1693,8 +1695,8 @@ impl Value for ClientSeverity {
 
 /// List of valid time zones.
 ///
-/// Names are following the tz database, but only time zones equivalent
-/// to UTC±00:00 are supported.
+/// Names follow the tz database. Besides the named time zones, the fixed
+/// offset `+00:00` is accepted for compatibility with PostgreSQL.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TimeZone {
     /// UTC
@@ -1702,6 +1704,8 @@ pub enum TimeZone {
     /// Fixed offset from UTC, currently only "+00:00" is supported.
     /// A string representation is kept here for compatibility with Postgres.
     FixedOffset(&'static str),
+    /// A time zone from the tz database, e.g. `America/New_York`.
+    Named(Tz),
 }
 
 impl TimeZone {
@@ -1709,6 +1713,15 @@ impl TimeZone {
         match self {
             TimeZone::UTC => "UTC",
             TimeZone::FixedOffset(s) => s,
+            TimeZone::Named(tz) => tz.name(),
+        }
+    }
+
+    /// Returns the time zone in the representation used by the SQL layer.
+    pub fn to_timezone(&self) -> Timezone {
+        match self {
+            TimeZone::UTC | TimeZone::FixedOffset(_) => Timezone::default(),
+            TimeZone::Named(tz) => Timezone::Tz(*tz),
         }
     }
 }
@@ -1724,7 +1737,12 @@ impl Value for TimeZone {
         } else if s == "+00:00" {
             Ok(TimeZone::FixedOffset("+00:00"))
         } else {
-            Err(())
+            // Parse names the same way as the `timezone` function does, so
+            // that the session time zone can be passed to it by name.
+            match s.as_str().parse() {
+                Ok(Timezone::Tz(tz)) => Ok(TimeZone::Named(tz)),
+                _ => Err(()),
+            }
         }
     }
 
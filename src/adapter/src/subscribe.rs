@@ -15,10 +15,13 @@ use mz_compute_client::response::{SubscribeBatch, SubscribeResponse};
 use mz_repr::adt::numeric;
 use mz_repr::{Datum, Row};
 
+use crate::client::ConnectionId;
 use crate::coord::peek::PeekResponseUnary;
 
 /// A description of a pending subscribe from coord's perspective
 pub struct PendingSubscribe {
+    /// The ID of the connection that created the subscribe.
+    pub conn_id: ConnectionId,
     /// The type of the session that created the subscribe.
     pub session_type: &'static str,
     /// Channel to send responses to the client
@@ -280,6 +280,7 @@ impl<T> ComputeController<T> {
         orchestrator: Arc<dyn NamespacedOrchestrator>,
         computed_image: String,
         init_container_image: Option<String>,
+        persist_write_structured_keys: bool,
         envd_epoch: NonZeroI64,
     ) -> Self {
         Self {
@@ -289,6 +290,7 @@ impl<T> ComputeController<T> {
                 orchestrator,
                 computed_image,
                 init_container_image,
+                persist_write_structured_keys,
             ),
             initialized: false,
             stashed_response: None,
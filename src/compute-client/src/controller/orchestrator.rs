@@ -32,6 +32,7 @@ pub(super) struct ComputeOrchestrator {
     inner: Arc<dyn NamespacedOrchestrator>,
     computed_image: String,
     init_container_image: Option<String>,
+    persist_write_structured_keys: bool,
 }
 
 impl ComputeOrchestrator {
@@ -39,11 +40,13 @@ impl ComputeOrchestrator {
         inner: Arc<dyn NamespacedOrchestrator>,
         computed_image: String,
         init_container_image: Option<String>,
+        persist_write_structured_keys: bool,
     ) -> Self {
         Self {
             inner,
             computed_image,
             init_container_image,
+            persist_write_structured_keys,
         }
     }
     pub(super) async fn ensure_replica_location(
@@ -121,6 +124,9 @@ impl ComputeOrchestrator {
                             compute_opts
                                 .push(format!("--opentelemetry-resource=replica_index={}", index));
                         }
+                        if self.persist_write_structured_keys {
+                            compute_opts.push("--persist-write-structured-keys".into());
+                        }
                         compute_opts
                     },
                     ports: vec![
@@ -214,6 +214,22 @@ pub enum Plan<T = mz_repr::Timestamp> {
         /// that reference `Id::Local(id)`.
         body: Box<Plan<T>>,
     },
+    /// Binds `values` to `ids`, iterates them to a fixed point, and then
+    /// results in `body` with those bindings.
+    ///
+    /// Each of `values` may reference any of `ids`, and the bindings start out
+    /// as empty collections. The bindings are not available outside of `values`
+    /// and `body`.
+    LetRec {
+        /// The local identifiers to be used, available to `values` and `body`
+        /// as `Id::Local(id)`.
+        ids: Vec<LocalId>,
+        /// The collections that should be bound to `ids`.
+        values: Vec<Plan<T>>,
+        /// The collection that results, which is allowed to contain `Get` stages
+        /// that reference any of `ids`.
+        body: Box<Plan<T>>,
+    },
     /// Map, Filter, and Project operators.
     ///
     /// This stage contains work that we would ideally like to fuse to other plan
@@ -346,11 +362,51 @@ pub enum Plan<T = mz_repr::Timestamp> {
 }
 
 impl<T> Plan<T> {
+    /// Iterates through references to child expressions.
+    pub fn children(&self) -> impl Iterator<Item = &Self> {
+        let mut first = None;
+        let mut second = None;
+        let mut rest = None;
+        let mut last = None;
+
+        use Plan::*;
+        match self {
+            Constant { .. } | Get { .. } => (),
+            Let { value, body, .. } => {
+                first = Some(&**value);
+                second = Some(&**body);
+            }
+            LetRec { values, body, .. } => {
+                rest = Some(values);
+                last = Some(&**body);
+            }
+            Mfp { input, .. }
+            | FlatMap { input, .. }
+            | Reduce { input, .. }
+            | TopK { input, .. }
+            | Negate { input }
+            | Threshold { input, .. }
+            | ArrangeBy { input, .. } => {
+                first = Some(&**input);
+            }
+            Join { inputs, .. } | Union { inputs } => {
+                rest = Some(inputs);
+            }
+        }
+
+        first
+            .into_iter()
+            .chain(second)
+            .chain(rest.into_iter().flatten())
+            .chain(last)
+    }
+
     /// Iterates through mutable references to child expressions.
     pub fn children_mut(&mut self) -> impl Iterator<Item = &mut Self> {
         let mut first = None;
         let mut second = None;
         let mut rest = None;
+        let mut last = None;
 
         use Plan::*;
         match self {
@@ -359,6 +415,10 @@ impl<T> Plan<T> {
                 first = Some(&mut **value);
                 second = Some(&mut **body);
             }
+            LetRec { values, body, .. } => {
+                rest = Some(values);
+                last = Some(&mut **body);
+            }
             Mfp { input, .. }
             | FlatMap { input, .. }
             | Reduce { input, .. }
@@ -377,6 +437,20 @@ impl<T> Plan<T> {
             .into_iter()
             .chain(second)
             .chain(rest.into_iter().flatten())
+            .chain(last)
+    }
+
+    /// Indicates whether the plan contains a `LetRec` stage, and must
+    /// therefore be rendered in an iterative scope.
+    pub fn is_recursive(&self) -> bool {
+        let mut todo = vec![self];
+        while let Some(plan) = todo.pop() {
+            if let Plan::LetRec { .. } = plan {
+                return true;
+            }
+            todo.extend(plan.children());
+        }
+        false
     }
 }
 
@@ -411,6 +485,20 @@ impl Arbitrary for Plan {
                         body: body.into(),
                     })
                     .boxed(),
+                //Plan::LetRec
+                (
+                    prop::collection::vec((any::<LocalId>(), inner.clone()), 0..2),
+                    inner.clone(),
+                )
+                    .prop_map(|(bindings, body)| {
+                        let (ids, values) = bindings.into_iter().unzip();
+                        Plan::LetRec {
+                            ids,
+                            values,
+                            body: body.into(),
+                        }
+                    })
+                    .boxed(),
                 //Plan::Mfp
                 (
                     inner.clone(),
@@ -564,6 +652,14 @@ impl RustType<ProtoPlan> for Plan {
                     body: Some(body.into_proto()),
                 }
                 .into()),
+                Plan::LetRec { ids, values, body } => LetRec(
+                    ProtoPlanLetRec {
+                        ids: ids.into_proto(),
+                        values: values.into_proto(),
+                        body: Some(body.into_proto()),
+                    }
+                    .into(),
+                ),
                 Plan::Mfp {
                     input,
                     mfp,
@@ -695,6 +791,11 @@ impl RustType<ProtoPlan> for Plan {
                 value: proto.value.into_rust_if_some("ProtoPlanLet::value")?,
                 body: proto.body.into_rust_if_some("ProtoPlanLet::body")?,
             },
+            LetRec(proto) => Plan::LetRec {
+                ids: proto.ids.into_rust()?,
+                values: proto.values.into_rust()?,
+                body: proto.body.into_rust_if_some("ProtoPlanLetRec::body")?,
+            },
             Mfp(proto) => Plan::Mfp {
                 input: proto.input.into_rust_if_some("ProtoPlanMfp::input")?,
                 input_key_val: input_kv_try_into(proto.input_key_val)?,
@@ -1056,6 +1157,42 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                     b_keys,
                 )
             }
+            MirRelationExpr::LetRec { ids, values, body } => {
+                // The bindings may be referenced before they are defined, and so
+                // we cannot rely on any arrangements they might produce. Each is
+                // introduced as a raw collection, visible to all values and the body.
+                for id in ids.iter() {
+                    let pre_existing =
+                        arrangements.insert(Id::Local(*id), AvailableCollections::new_raw());
+                    assert!(pre_existing.is_none());
+                }
+                let mut lir_values = Vec::with_capacity(values.len());
+                for value in values.iter() {
+                    let arity = value.arity();
+                    let (value, v_keys) = Plan::from_mir_inner(value, arrangements, debug_info)?;
+                    // Rendering feeds the raw collection back to the binding, so
+                    // install an operator to form it, if necessary.
+                    let value = if !v_keys.raw {
+                        value.arrange_by(AvailableCollections::new_raw(), &v_keys, arity)
+                    } else {
+                        value
+                    };
+                    lir_values.push(value);
+                }
+                let (body, b_keys) = Plan::from_mir_inner(body, arrangements, debug_info)?;
+                for id in ids.iter() {
+                    arrangements.remove(&Id::Local(*id));
+                }
+                // Return the plan, and any `body` arrangements.
+                (
+                    Plan::LetRec {
+                        ids: ids.clone(),
+                        values: lir_values,
+                        body: Box::new(body),
+                    },
+                    b_keys,
+                )
+            }
             MirRelationExpr::FlatMap { input, func, exprs } => {
                 let (input, keys) = Plan::from_mir_inner(input, arrangements, debug_info)?;
                 // This stage can absorb arbitrary MFP instances.
@@ -1665,6 +1802,25 @@ This is not expected to cause incorrect results, but could indicate a performanc
                         })
                         .collect()
                 }
+                Plan::LetRec { ids, values, body } => {
+                    let mut values_parts: Vec<Vec<Self>> = vec![Vec::new(); parts];
+                    for value in values.into_iter() {
+                        for (index, value_part) in
+                            value.partition_among(parts).into_iter().enumerate()
+                        {
+                            values_parts[index].push(value_part);
+                        }
+                    }
+                    values_parts
+                        .into_iter()
+                        .zip(body.partition_among(parts))
+                        .map(|(values, body)| Plan::LetRec {
+                            ids: ids.clone(),
+                            values,
+                            body: Box::new(body),
+                        })
+                        .collect()
+                }
                 Plan::Mfp {
                     input,
                     input_key_val,
@@ -1805,6 +1961,16 @@ impl<T> CollectionPlan for Plan<T> {
                 value.depends_on_into(out);
                 body.depends_on_into(out);
             }
+            Plan::LetRec {
+                ids: _,
+                values,
+                body,
+            } => {
+                for value in values {
+                    value.depends_on_into(out);
+                }
+                body.depends_on_into(out);
+            }
             Plan::Join { inputs, plan: _ } | Plan::Union { inputs } => {
                 for input in inputs {
                     input.depends_on_into(out);
@@ -830,6 +830,11 @@ pub fn convert_indexes_to_skips(mut indexes: Vec<usize>) -> Vec<usize> {
 /// significant input data). Hierarchical aggregates can be rendered more efficiently if the
 /// input stream is append-only as then we only need to retain the "currently winning" value.
 /// Every hierarchical aggregate needs to supply a corresponding ReductionMonoid implementation.
+///
+/// Ordered-set aggregations are planned as basic aggregations, but are rendered with their own
+/// reduction trees, as they can neither be accumulated nor combined from partial results.
+/// The same holds for approximate distinct counts, whose HyperLogLog registers must support
+/// retractions.
 fn reduction_type(func: &AggregateFunc) -> ReductionType {
     match func {
         AggregateFunc::SumInt16
@@ -880,11 +885,21 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::ArrayConcat { .. }
         | AggregateFunc::ListConcat { .. }
         | AggregateFunc::StringAgg { .. }
+        | AggregateFunc::PercentileCont { .. }
+        | AggregateFunc::PercentileDisc { .. }
+        | AggregateFunc::Mode { .. }
+        | AggregateFunc::ApproxCountDistinct
         | AggregateFunc::RowNumber { .. }
+        | AggregateFunc::Rank { .. }
         | AggregateFunc::DenseRank { .. }
+        | AggregateFunc::PercentRank { .. }
+        | AggregateFunc::CumeDist { .. }
+        | AggregateFunc::Ntile { .. }
         | AggregateFunc::LagLead { .. }
         | AggregateFunc::FirstValue { .. }
-        | AggregateFunc::LastValue { .. } => ReductionType::Basic,
+        | AggregateFunc::LastValue { .. }
+        | AggregateFunc::NthValue { .. }
+        | AggregateFunc::WindowAggregate { .. } => ReductionType::Basic,
     }
 }
 
@@ -66,6 +66,11 @@ struct Args {
     )]
     internal_http_listen_addr: SocketAddr,
 
+    // === Storage options. ===
+    /// Whether to write a structured columnar encoding of keys to persist.
+    #[clap(long, env = "PERSIST_WRITE_STRUCTURED_KEYS")]
+    persist_write_structured_keys: bool,
+
     // === Process orchestrator options. ===
     /// Where to write a PID lock file.
     ///
@@ -165,6 +170,7 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         build_info: &BUILD_INFO,
         metrics_registry,
         now: SYSTEM_TIME.clone(),
+        persist_write_structured_keys: args.persist_write_structured_keys,
     };
 
     let (_server, client_builder) = mz_compute::server::serve(config)?;
@@ -110,13 +110,14 @@ use timely::dataflow::operators::to_stream::ToStream;
 use timely::dataflow::operators::InspectCore;
 use timely::dataflow::scopes::Child;
 use timely::dataflow::{Scope, Stream};
+use timely::order::Product;
 use timely::progress::Timestamp;
 use timely::worker::Worker as TimelyWorker;
 use timely::PartialOrder;
 
 use mz_compute_client::command::{BuildDesc, DataflowDescription, IndexDesc};
-use mz_compute_client::plan::Plan;
-use mz_expr::Id;
+use mz_compute_client::plan::{AvailableCollections, Plan};
+use mz_expr::{permutation_for_arrangement, Id, MapFilterProject};
 use mz_ore::collections::CollectionExt as IteratorExt;
 use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_client::controller::CollectionMetadata;
@@ -150,6 +151,11 @@ pub fn build_compute_dataflow<A: Allocate>(
 ) {
     let worker_logging = timely_worker.log_register().get("timely");
     let name = format!("Dataflow: {}", &dataflow.debug_name);
+    // Dataflows containing recursive bindings must be built in an iterative scope.
+    let recursive = dataflow
+        .objects_to_build
+        .iter()
+        .any(|object| object.plan.is_recursive());
 
     timely_worker.dataflow_core(&name, worker_logging, Box::new(()), |_, scope| {
         // The scope.clone() occurs to allow import in the region.
@@ -216,11 +222,6 @@ pub fn build_compute_dataflow<A: Allocate>(
                 tokens.insert(*source_id, token);
             }
 
-            // Import declared indexes into the rendering context.
-            for (idx_id, idx) in &dataflow.index_imports {
-                context.import_index(compute_state, &mut tokens, scope, region, *idx_id, &idx.0);
-            }
-
             // We first determine indexes and sinks to export, then build the declared object, and
             // finally export indexes and sinks. The reason for this is that we want to avoid
             // cloning the dataflow plan for `build_object`, which can be expensive.
@@ -239,9 +240,112 @@ pub fn build_compute_dataflow<A: Allocate>(
                 .map(|(sink_id, sink)| (*sink_id, dataflow.depends_on(sink.from), sink.clone()))
                 .collect::<Vec<_>>();
 
-            // Build declared objects.
-            for object in dataflow.objects_to_build {
-                context.build_object(region, object);
+            if recursive {
+                // Build declared objects in an iterative scope, from which we extract
+                // the collections that are exported. Those exported as indexes are
+                // re-arranged outside of the iterative scope.
+                let mut exports = BTreeMap::new();
+                for (idx_id, (_idx, typ)) in dataflow.index_exports.iter() {
+                    exports.insert(*idx_id, typ.arity());
+                }
+                for sink in dataflow.sink_exports.values() {
+                    exports.insert(sink.from, sink.from_desc.arity());
+                }
+                region.clone().iterative::<u64, _, _>(|inner| {
+                    let mut inner_context = Context {
+                        debug_name: context.debug_name.clone(),
+                        dataflow_id: context.dataflow_id,
+                        as_of_frontier: context.as_of_frontier.clone(),
+                        until: context.until.clone(),
+                        bindings: BTreeMap::new(),
+                    };
+                    // Enter imported sources into the iterative scope.
+                    for (id, bundle) in context.bindings.iter() {
+                        let (oks, errs) = bundle.as_specific_collection(None);
+                        inner_context.insert_id(
+                            *id,
+                            CollectionBundle::from_collections(oks.enter(inner), errs.enter(inner)),
+                        );
+                    }
+                    // Import declared indexes into the iterative scope.
+                    for (idx_id, idx) in &dataflow.index_imports {
+                        inner_context.import_index(
+                            compute_state,
+                            &mut tokens,
+                            region,
+                            inner,
+                            *idx_id,
+                            &idx.0,
+                        );
+                    }
+                    for object in dataflow.objects_to_build {
+                        inner_context.build_object(inner, object);
+                    }
+                    for (id, arity) in exports.iter() {
+                        let bundle = inner_context
+                            .lookup_id(Id::Global(*id))
+                            .unwrap_or_else(|| panic!("Export {} not built", id));
+                        let (oks, errs) = if let Some(collection) = &bundle.collection {
+                            collection.clone()
+                        } else {
+                            let key = bundle
+                                .arranged
+                                .keys()
+                                .next()
+                                .expect(
+                                    "Invariant violated: at least one collection must be present.",
+                                )
+                                .clone();
+                            let (permutation, thinning) = permutation_for_arrangement(&key, *arity);
+                            let mut mfp = MapFilterProject::new(*arity);
+                            mfp.permute(permutation, thinning.len() + key.len());
+                            bundle.as_collection_core(
+                                mfp,
+                                Some((key, None)),
+                                inner_context.until.clone(),
+                            )
+                        };
+                        context.insert_id(
+                            Id::Global(*id),
+                            CollectionBundle::from_collections(oks.leave(), errs.leave()),
+                        );
+                    }
+                });
+                for (idx_id, (idx, typ)) in dataflow.index_exports.iter() {
+                    let arity = typ.arity();
+                    let bundle = context
+                        .remove_id(Id::Global(*idx_id))
+                        .expect("Export extracted above");
+                    let (permutation, thinning) = permutation_for_arrangement(&idx.key, arity);
+                    let bundle = bundle.ensure_collections(
+                        AvailableCollections::new_arranged(vec![(
+                            idx.key.clone(),
+                            permutation,
+                            thinning,
+                        )]),
+                        None,
+                        MapFilterProject::new(arity),
+                        context.until.clone(),
+                    );
+                    context.insert_id(Id::Global(*idx_id), bundle);
+                }
+            } else {
+                // Import declared indexes into the rendering context.
+                for (idx_id, idx) in &dataflow.index_imports {
+                    context.import_index(
+                        compute_state,
+                        &mut tokens,
+                        scope,
+                        region,
+                        *idx_id,
+                        &idx.0,
+                    );
+                }
+
+                // Build declared objects.
+                for object in dataflow.objects_to_build {
+                    context.build_object(region, object);
+                }
             }
 
             // Export declared indexes.
@@ -528,6 +632,39 @@ where
                 self.remove_id(Id::Local(id));
                 body
             }
+            Plan::LetRec { ids, values, body } => {
+                use differential_dataflow::operators::iterate::Variable;
+                use differential_dataflow::operators::{Consolidate, Threshold};
+
+                // Bind each identifier to a variable, which starts out empty and in
+                // each iteration takes on the value rendered in the prior iteration.
+                let step = <G::Timestamp as RenderTimestamp>::iteration_delay()
+                    .expect("Recursive plans must be rendered in an iterative scope");
+                let mut variables = BTreeMap::new();
+                for id in ids.iter() {
+                    let oks = Variable::new(scope, step.clone());
+                    let errs = Variable::new(scope, step.clone());
+                    let bundle =
+                        CollectionBundle::from_collections((*oks).clone(), (*errs).clone());
+                    let prebound = self.insert_id(Id::Local(*id), bundle);
+                    assert!(prebound.is_none());
+                    variables.insert(*id, (oks, errs));
+                }
+                // Render each value, and feed it back to its variable. Consolidation
+                // ensures that the iteration stops once the values are unchanged.
+                for (id, value) in ids.iter().zip(values) {
+                    let bundle = self.render_plan(value, scope, worker_index);
+                    let (oks, errs) = bundle.as_specific_collection(None);
+                    let (oks_var, errs_var) = variables.remove(id).expect("Variable bound above");
+                    oks_var.set(&oks.consolidate());
+                    errs_var.set(&errs.distinct_core());
+                }
+                let body = self.render_plan(*body, scope, worker_index);
+                for id in ids.iter() {
+                    self.remove_id(Id::Local(*id));
+                }
+                body
+            }
             Plan::Mfp {
                 input,
                 mfp,
@@ -639,6 +776,9 @@ pub trait RenderTimestamp: Timestamp + Lattice + Refines<mz_repr::Timestamp> {
     /// Steps the timestamp back so that logical compaction to the output will
     /// not conflate `self` with any historical times.
     fn step_back(&self) -> Self;
+    /// Effects a delay of one iteration in terms of the timestamp summary,
+    /// or `None` if the timestamp has no iteration coordinate.
+    fn iteration_delay() -> Option<<Self as Timestamp>::Summary>;
 }
 
 impl RenderTimestamp for mz_repr::Timestamp {
@@ -657,4 +797,30 @@ impl RenderTimestamp for mz_repr::Timestamp {
     fn step_back(&self) -> Self {
         self.saturating_sub(1)
     }
+    fn iteration_delay() -> Option<<Self as Timestamp>::Summary> {
+        None
+    }
+}
+
+impl RenderTimestamp for Product<mz_repr::Timestamp, u64> {
+    fn system_time(&mut self) -> &mut mz_repr::Timestamp {
+        &mut self.outer
+    }
+    fn system_delay(delay: mz_repr::Timestamp) -> <Self as Timestamp>::Summary {
+        Product::new(delay, Default::default())
+    }
+    fn event_time(&mut self) -> &mut mz_repr::Timestamp {
+        &mut self.outer
+    }
+    fn event_delay(delay: mz_repr::Timestamp) -> <Self as Timestamp>::Summary {
+        Product::new(delay, Default::default())
+    }
+    fn step_back(&self) -> Self {
+        // Both coordinates must be stepped back, as either may be the one
+        // that distinguishes `self` from historical times.
+        Product::new(self.outer.saturating_sub(1), self.inner.saturating_sub(1))
+    }
+    fn iteration_delay() -> Option<<Self as Timestamp>::Summary> {
+        Some(Product::new(Default::default(), 1))
+    }
 }
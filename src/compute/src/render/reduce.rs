@@ -11,6 +11,10 @@
 //!
 //! Consult [ReducePlan] documentation for details.
 
+use std::cmp::Ordering;
+use std::fmt;
+
+use chrono::Timelike;
 use dec::OrderedDecimal;
 use differential_dataflow::collection::AsCollection;
 use differential_dataflow::difference::Multiply;
@@ -31,7 +35,10 @@ use mz_compute_client::plan::reduce::{
     AccumulablePlan, BasicPlan, BucketedPlan, HierarchicalPlan, KeyValPlan, MonotonicPlan,
     ReducePlan, ReductionType,
 };
-use mz_expr::{AggregateExpr, AggregateFunc};
+use mz_expr::{
+    approx_count_distinct_estimate, approx_count_distinct_register, percentile_cont_interpolate,
+    percentile_cont_positions, percentile_disc_position, AggregateExpr, AggregateFunc,
+};
 use mz_ore::soft_assert_or_log;
 use mz_repr::adt::numeric::{self, Numeric, NumericAgg};
 use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena};
@@ -475,6 +482,13 @@ where
         partial = partial.distinct_core();
     }
 
+    if func.is_ordered_set() {
+        return build_ordered_set_aggregate(partial, &func);
+    }
+    if func == AggregateFunc::ApproxCountDistinct {
+        return build_approx_count_distinct(partial);
+    }
+
     partial.reduce_abelian::<_, RowSpine<_, _, _, _>>("ReduceInaccumulable", {
         let mut row_buf = Row::default();
         move |_key, source, target| {
@@ -504,6 +518,658 @@ where
     })
 }
 
+/// Build the dataflow to compute a single ordered-set aggregation.
+///
+/// Rather than sorting all values of a group on every update, ordered-set
+/// aggregations maintain reduction trees in which an update only revisits a
+/// bounded number of records per layer.
+fn build_ordered_set_aggregate<G>(
+    input: Collection<G, (Row, Row), Diff>,
+    func: &AggregateFunc,
+) -> Arrangement<G, Row>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+{
+    match func {
+        AggregateFunc::PercentileCont { order_by } => {
+            build_percentile(input, true, order_by.first().map_or(false, |o| o.desc))
+        }
+        AggregateFunc::PercentileDisc { order_by } => {
+            build_percentile(input, false, order_by.first().map_or(false, |o| o.desc))
+        }
+        AggregateFunc::Mode { order_by } => {
+            build_mode(input, order_by.first().map_or(false, |o| o.desc))
+        }
+        _ => unreachable!("{} is not an ordered-set aggregation", func),
+    }
+}
+
+/// The number of bits of an order statistic code that each layer of a
+/// percentile's reduction tree resolves.
+const ORDER_STATISTIC_DIGIT_BITS: u32 = 8;
+
+/// Build the dataflow to compute `percentile_cont` or `percentile_disc`.
+///
+/// Each non-null value is assigned an order statistic code (see
+/// [`order_statistic_code`]), whose bytes are the digits of a path through a
+/// 256-ary search tree. The root determines the positions of the values to
+/// select from the number of values in each group, and each following layer
+/// descends one digit by counting the values below the current prefix. The
+/// leaves order the values that share a code.
+fn build_percentile<G>(
+    input: Collection<G, (Row, Row), Diff>,
+    cont: bool,
+    desc: bool,
+) -> Arrangement<G, Row>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+{
+    use differential_dataflow::operators::Join;
+
+    /// The first `digits` digits of `code`.
+    fn prefix(code: u128, digits: u32) -> u128 {
+        code >> (u128::BITS - digits * ORDER_STATISTIC_DIGIT_BITS)
+    }
+
+    input.scope().region_named("ReducePercentile", |inner| {
+        let input = input.enter(inner);
+
+        // Extract the non-null values, along with their codes and the fraction.
+        let values = input.flat_map(move |(key, row)| {
+            let record = row.unpack_first().unwrap_list().iter().next().unwrap();
+            // The record is null if the input was excluded by a FILTER clause.
+            if record.is_null() {
+                return None;
+            }
+            let mut value_fraction = record.unwrap_list().iter();
+            let value = value_fraction.next().unwrap();
+            let fraction = value_fraction.next().unwrap();
+            if value.is_null() {
+                return None;
+            }
+            let code = order_statistic_code(value, desc);
+            Some((
+                key,
+                code,
+                Row::pack_slice(&[value]),
+                Row::pack_slice(&[fraction]),
+            ))
+        });
+
+        // Determine the positions of the values to select, and the first digit
+        // of their codes. The planner ensures that the fraction is the same for
+        // all values of a group.
+        let mut targets = values
+            .map(|(key, code, _value, fraction)| (key, (prefix(code, 1), fraction)))
+            .reduce_named("PercentilePositions", {
+                move |key, source, target: &mut Vec<((u8, u128, u64, u64), Diff)>| {
+                    if report_non_positive("PercentilePositions", key, source) {
+                        return;
+                    }
+                    let fraction = source[0].0 .1.unpack_first();
+                    if fraction.is_null() {
+                        return;
+                    }
+                    let fraction = fraction.unwrap_float64();
+                    let count = u64::try_from(source.iter().map(|(_val, cnt)| *cnt).sum::<Diff>())
+                        .expect("counts are positive");
+                    let positions = if cont {
+                        let (lower, upper, weight) = percentile_cont_positions(fraction, count);
+                        vec![(0, lower, weight), (1, upper, weight)]
+                    } else {
+                        vec![(0, percentile_disc_position(fraction, count), 0.0)]
+                    };
+                    for (index, position, weight) in positions {
+                        let digits = source
+                            .iter()
+                            .map(|((digit, _fraction), cnt)| (*digit, *cnt));
+                        if let Some((digit, position)) = select_position(digits, position) {
+                            target.push(((index, digit, position, weight.to_bits()), 1));
+                        }
+                    }
+                }
+            })
+            .map(|(key, (index, digit, position, weight))| {
+                ((key, digit), (index, position, weight))
+            });
+
+        // Descend the tree one digit at a time, until the targets have
+        // determined the full codes of the values they select.
+        for digits in 2..=u128::BITS / ORDER_STATISTIC_DIGIT_BITS {
+            let children = values.map(move |(key, code, _value, _fraction)| {
+                ((key, prefix(code, digits - 1)), prefix(code, digits))
+            });
+            targets = targets
+                .join_map(&children, |(key, parent), target, child| {
+                    ((key.clone(), *parent, *target), *child)
+                })
+                .reduce_named("PercentileDigits", {
+                    move |(_key, _parent, (_index, position, _weight)), source, target| {
+                        let digits = source.iter().map(|(child, cnt)| (**child, *cnt));
+                        if let Some(selected) = select_position(digits, *position) {
+                            target.push((selected, 1));
+                        }
+                    }
+                })
+                .map(
+                    |((key, _parent, (index, _position, weight)), (child, position))| {
+                        ((key, child), (index, position, weight))
+                    },
+                );
+        }
+
+        // Select the values among those that share a code.
+        let leaves = values.map(|(key, code, value, _fraction)| ((key, code), value));
+        let selected = targets
+            .join_map(&leaves, |(key, code), target, value| {
+                ((key.clone(), *code, *target), value.clone())
+            })
+            .reduce_named("PercentileLeaves", {
+                move |(_key, _code, (_index, position, _weight)), source, target| {
+                    let mut leaves = source
+                        .iter()
+                        .map(|(value, cnt)| (value.unpack_first(), *cnt))
+                        .collect::<Vec<_>>();
+                    if desc {
+                        leaves.sort_by(|(a, _), (b, _)| b.cmp(a));
+                    } else {
+                        leaves.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    }
+                    if let Some((value, _)) = select_position(leaves, *position) {
+                        target.push((Row::pack_slice(&[value]), 1));
+                    }
+                }
+            })
+            .map(|((key, _code, (index, _position, weight)), value)| {
+                (key, Some((index, weight, value)))
+            });
+
+        // Combine the selected values, and produce `NULL` for groups without
+        // any.
+        input
+            .map(|(key, _row)| (key, None))
+            .concat(&selected)
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("ReducePercentile", {
+                let mut row_buf = Row::default();
+                move |key, source, target| {
+                    if report_non_positive("ReducePercentile", key, source) {
+                        return;
+                    }
+                    let mut selected = [None, None];
+                    for (val, _cnt) in source.iter() {
+                        if let Some((index, weight, value)) = val {
+                            selected[usize::from(*index)] = Some((*weight, value.unpack_first()));
+                        }
+                    }
+                    let result = match selected {
+                        [Some((weight, lower)), upper] if cont => {
+                            let lower = lower.unwrap_float64();
+                            let upper = upper.map_or(lower, |(_, upper)| upper.unwrap_float64());
+                            Datum::from(percentile_cont_interpolate(
+                                lower,
+                                upper,
+                                f64::from_bits(weight),
+                            ))
+                        }
+                        [Some((_weight, value)), _] => value,
+                        _ => Datum::Null,
+                    };
+                    row_buf.packer().push(result);
+                    target.push((row_buf.clone(), 1));
+                }
+            })
+            .leave_region()
+    })
+}
+
+/// Reports the non-positive accumulations among the values of the group `key`
+/// in the reduction named `name`, and returns whether there were any.
+///
+/// The ordered-set aggregations cannot make sense of such groups, and produce
+/// no output for them.
+fn report_non_positive<K, V>(name: &str, key: &K, source: &[(V, Diff)]) -> bool
+where
+    K: fmt::Debug + ?Sized,
+    V: fmt::Debug,
+{
+    if source.iter().all(|(_val, cnt)| *cnt > 0) {
+        return false;
+    }
+    for (val, cnt) in source {
+        // XXX: This reports user data, which we perhaps should not do!
+        soft_assert_or_log!(
+            *cnt > 0,
+            "[customer-data] Non-positive accumulation in {}: key: {:?}\tvalue: {:?}\tcount: {:?}",
+            name,
+            key,
+            val,
+            cnt,
+        );
+    }
+    true
+}
+
+/// Returns the element at the zero-based `position` among the elements of
+/// `counts`, if any, along with the element's position among the elements
+/// equal to it.
+///
+/// `counts` must be sorted, and contain the number of occurrences of each
+/// element.
+fn select_position<T, I>(counts: I, mut position: u64) -> Option<(T, u64)>
+where
+    I: IntoIterator<Item = (T, Diff)>,
+{
+    for (element, cnt) in counts {
+        let cnt = u64::try_from(cnt).unwrap_or(0);
+        if position < cnt {
+            return Some((element, position));
+        }
+        position -= cnt;
+    }
+    None
+}
+
+/// The number of bytes of a value's order-preserving encoding that its order
+/// statistic code retains.
+const ORDER_STATISTIC_CODE_BYTES: usize = 16;
+
+/// Maps `datum` to a code such that the codes of values are ordered like the
+/// values, or in reverse if `desc` is set.
+///
+/// The code consists of the leading bytes of the value's order-preserving
+/// encoding (see [`encode_order_statistic_key`]), so the codes of distinct
+/// values may be equal, e.g. for strings that share a long prefix.
+fn order_statistic_code(datum: Datum, desc: bool) -> u128 {
+    let mut key = Vec::new();
+    encode_order_statistic_key(datum, &mut key);
+    let mut code = [0; ORDER_STATISTIC_CODE_BYTES];
+    let len = key.len().min(code.len());
+    code[..len].copy_from_slice(&key[..len]);
+    let code = u128::from_be_bytes(code);
+    if desc {
+        !code
+    } else {
+        code
+    }
+}
+
+/// Appends to `buf` an encoding of `datum` whose byte-wise order agrees with
+/// the order of [`Datum`]s.
+///
+/// The encoding starts with a tag for the datum's variant, so that datums of
+/// different variants, like the elements of a `jsonb` array, are ordered as by
+/// `Datum::cmp`. It is self-delimiting, so that the encodings of the elements
+/// of lists and of the bounds of ranges can be concatenated. Maps are ordered
+/// by their internal representation rather than their entries, and are encoded
+/// by their tag alone.
+fn encode_order_statistic_key(datum: Datum, buf: &mut Vec<u8>) {
+    fn signed32(i: i32) -> [u8; 4] {
+        ((i as u32) ^ (1 << 31)).to_be_bytes()
+    }
+    fn signed64(i: i64) -> [u8; 8] {
+        ((i as u64) ^ (1 << 63)).to_be_bytes()
+    }
+    fn float(f: f64) -> [u8; 8] {
+        let bits = if f.is_nan() {
+            u64::MAX
+        } else if f == 0.0 {
+            // Positive and negative zero are equal.
+            1 << 63
+        } else if f.is_sign_negative() {
+            !f.to_bits()
+        } else {
+            f.to_bits() | (1 << 63)
+        };
+        bits.to_be_bytes()
+    }
+    // Escapes zero bytes as `0x00 0xff`, and terminates with `0x00 0x00`, so
+    // that a prefix sorts before the values that extend it.
+    fn bytes(b: &[u8], buf: &mut Vec<u8>) {
+        for byte in b {
+            buf.push(*byte);
+            if *byte == 0 {
+                buf.push(0xff);
+            }
+        }
+        buf.extend([0, 0]);
+    }
+    // Encodes each element after a non-zero tag, and terminates with a zero
+    // byte, so that a prefix sorts before the lists that extend it.
+    fn list<'a>(elements: impl Iterator<Item = Datum<'a>>, buf: &mut Vec<u8>) {
+        for element in elements {
+            encode_order_statistic_key(element, buf);
+        }
+        buf.push(0);
+    }
+
+    // The tags follow the order of the variants of `Datum`, starting at one.
+    match datum {
+        Datum::False => buf.push(1),
+        Datum::True => buf.push(2),
+        Datum::Int16(i) => {
+            buf.push(3);
+            buf.extend(signed32(i.into()));
+        }
+        Datum::Int32(i) => {
+            buf.push(4);
+            buf.extend(signed32(i));
+        }
+        Datum::Int64(i) => {
+            buf.push(5);
+            buf.extend(signed64(i));
+        }
+        Datum::UInt8(u) => buf.extend([6, u]),
+        Datum::UInt16(u) => {
+            buf.push(7);
+            buf.extend(u.to_be_bytes());
+        }
+        Datum::UInt32(u) => {
+            buf.push(8);
+            buf.extend(u.to_be_bytes());
+        }
+        Datum::UInt64(u) => {
+            buf.push(9);
+            buf.extend(u.to_be_bytes());
+        }
+        Datum::Float32(f) => {
+            buf.push(10);
+            buf.extend(float(f.into_inner().into()));
+        }
+        Datum::Float64(f) => {
+            buf.push(11);
+            buf.extend(float(f.into_inner()));
+        }
+        Datum::Date(d) => {
+            buf.push(12);
+            buf.extend(signed32(d.pg_epoch_days()));
+        }
+        Datum::Time(t) => {
+            buf.push(13);
+            buf.extend(t.num_seconds_from_midnight().to_be_bytes());
+            buf.extend(t.nanosecond().to_be_bytes());
+        }
+        Datum::Timestamp(ts) => {
+            buf.push(14);
+            buf.extend(signed64(ts.timestamp()));
+            buf.extend(ts.timestamp_subsec_nanos().to_be_bytes());
+        }
+        Datum::TimestampTz(ts) => {
+            buf.push(15);
+            buf.extend(signed64(ts.timestamp()));
+            buf.extend(ts.timestamp_subsec_nanos().to_be_bytes());
+        }
+        Datum::Interval(iv) => {
+            buf.push(16);
+            buf.extend(signed32(iv.months));
+            buf.extend(signed32(iv.days));
+            buf.extend(signed64(iv.micros));
+        }
+        Datum::Bytes(b) => {
+            buf.push(17);
+            bytes(b, buf);
+        }
+        Datum::String(s) => {
+            buf.push(18);
+            bytes(s.as_bytes(), buf);
+        }
+        Datum::Array(a) => {
+            // Arrays are ordered by their elements before their dimensions.
+            buf.push(19);
+            list(a.elements().iter(), buf);
+        }
+        Datum::List(l) => {
+            buf.push(20);
+            list(l.iter(), buf);
+        }
+        Datum::Map(_) => buf.push(21),
+        Datum::Numeric(n) => {
+            buf.push(22);
+            let n = n.0;
+            if n.is_nan() {
+                buf.push(5);
+            } else if n.is_infinite() {
+                buf.push(if n.is_negative() { 0 } else { 4 });
+            } else if n.is_zero() {
+                buf.push(2);
+            } else {
+                // Order by the exponent of the leading digit, and then by the
+                // digits, ignoring trailing zeros so that e.g. `1.0` and `1`
+                // are equal. The encoding of negative values is inverted.
+                buf.push(if n.is_negative() { 1 } else { 3 });
+                let start = buf.len();
+                let mut digits = n.coefficient_digits();
+                while digits.last() == Some(&0) {
+                    digits.pop();
+                }
+                let digit_count = i32::try_from(n.digits()).expect("numeric digits fit in i32");
+                buf.extend(signed32(n.exponent() + digit_count - 1));
+                buf.extend(digits.iter().map(|digit| digit + 1));
+                buf.push(0);
+                if n.is_negative() {
+                    for byte in &mut buf[start..] {
+                        *byte = !*byte;
+                    }
+                }
+            }
+        }
+        Datum::JsonNull => buf.push(23),
+        Datum::Uuid(u) => {
+            buf.push(24);
+            buf.extend(u.as_bytes());
+        }
+        Datum::MzTimestamp(t) => {
+            buf.push(25);
+            buf.extend(u64::from(t).to_be_bytes());
+        }
+        Datum::Range(r) => {
+            // Empty ranges sort first. Infinite lower bounds sort before finite
+            // ones, and inclusive lower bounds before exclusive ones at the
+            // same value; upper bounds are ordered the other way around.
+            buf.push(26);
+            match r.inner {
+                None => buf.push(0),
+                Some(inner) => {
+                    buf.push(1);
+                    match inner.lower.bound {
+                        None => buf.push(0),
+                        Some(bound) => {
+                            buf.push(1);
+                            encode_order_statistic_key(bound.datum(), buf);
+                            buf.push(if inner.lower.inclusive { 0 } else { 1 });
+                        }
+                    }
+                    match inner.upper.bound {
+                        None => buf.push(1),
+                        Some(bound) => {
+                            buf.push(0);
+                            encode_order_statistic_key(bound.datum(), buf);
+                            buf.push(if inner.upper.inclusive { 1 } else { 0 });
+                        }
+                    }
+                }
+            }
+        }
+        Datum::Dummy => buf.push(27),
+        Datum::Null => buf.push(28),
+    }
+}
+
+/// The number of buckets in each layer of a mode's reduction tree.
+const MODE_BUCKETS: [u64; 7] = [1 << 28, 1 << 24, 1 << 20, 1 << 16, 1 << 12, 1 << 8, 1 << 4];
+
+/// Build the dataflow to compute `mode`.
+///
+/// The first layer counts the occurrences of each distinct value, after which
+/// each layer of the reduction tree selects the most frequent value among the
+/// values that fall into each of its buckets.
+fn build_mode<G>(input: Collection<G, (Row, Row), Diff>, desc: bool) -> Arrangement<G, Row>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+{
+    /// Returns the most frequent of the `(count, value)` pairs in `source`,
+    /// breaking ties in favor of the first value in the aggregation's order.
+    fn most_frequent<'a, I>(source: I, desc: bool) -> Option<&'a (Diff, Row)>
+    where
+        I: IntoIterator<Item = &'a (Diff, Row)>,
+    {
+        source.into_iter().fold(None, |best, candidate| match best {
+            Some(best) => {
+                let better = match candidate.0.cmp(&best.0) {
+                    Ordering::Greater => true,
+                    Ordering::Less => false,
+                    Ordering::Equal => {
+                        let order = candidate.1.unpack_first().cmp(&best.1.unpack_first());
+                        let order = if desc { order.reverse() } else { order };
+                        order == Ordering::Less
+                    }
+                };
+                Some(if better { candidate } else { best })
+            }
+            None => Some(candidate),
+        })
+    }
+
+    input.scope().region_named("ReduceMode", |inner| {
+        let input = input.enter(inner);
+
+        // Count the occurrences of each non-null value.
+        let mut stage = input
+            .flat_map(|(key, row)| {
+                let value = row.unpack_first().unwrap_list().iter().next().unwrap();
+                if value.is_null() {
+                    None
+                } else {
+                    let value = Row::pack_slice(&[value]);
+                    let hash = value.hashed();
+                    Some(((key, hash), value))
+                }
+            })
+            .reduce_named("ModeCounts", |key, source, target| {
+                if report_non_positive("ModeCounts", key, source) {
+                    return;
+                }
+                for (value, cnt) in source.iter() {
+                    target.push(((*cnt, (*value).clone()), 1));
+                }
+            });
+
+        // Repeatedly select the most frequent values with a progressively
+        // coarser key.
+        for buckets in MODE_BUCKETS {
+            stage = stage
+                .map(move |((key, hash), value)| ((key, hash % buckets), value))
+                .reduce_named("ModeHierarchical", move |_key, source, target| {
+                    if let Some(best) = most_frequent(source.iter().map(|(val, _cnt)| *val), desc) {
+                        target.push((best.clone(), 1));
+                    }
+                });
+        }
+
+        // Select the most frequent value, and produce `NULL` for groups without
+        // any.
+        input
+            .map(|(key, _row)| (key, None))
+            .concat(&stage.map(|((key, _hash), value)| (key, Some(value))))
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("ReduceMode", {
+                let mut row_buf = Row::default();
+                move |key, source, target| {
+                    if report_non_positive("ReduceMode", key, source) {
+                        return;
+                    }
+                    let candidates = source.iter().filter_map(|(val, _cnt)| val.as_ref());
+                    let result = match most_frequent(candidates, desc) {
+                        Some((_count, value)) => value.unpack_first(),
+                        None => Datum::Null,
+                    };
+                    row_buf.packer().push(result);
+                    target.push((row_buf.clone(), 1));
+                }
+            })
+            .leave_region()
+    })
+}
+
+/// Build the dataflow to compute `approx_count_distinct`.
+///
+/// Each non-null value records a rank in one of the registers of a HyperLogLog
+/// sketch (see [`approx_count_distinct_register`]), and each register holds the
+/// greatest rank recorded in it. Rather than the distinct values themselves,
+/// the first layer maintains the number of values that record each rank in
+/// each register, which is bounded by the number of registers and ranks, so
+/// that a retraction can restore the register's previous rank. The second layer
+/// maintains the number of registers that hold each rank, from which the final
+/// layer computes the estimate.
+fn build_approx_count_distinct<G>(input: Collection<G, (Row, Row), Diff>) -> Arrangement<G, Row>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+{
+    input.scope().region_named("ReduceApproxCountDistinct", |inner| {
+        let input = input.enter(inner);
+
+        let ranks = input
+            .flat_map(|(key, row)| {
+                let datum = row.unpack_first();
+                if datum.is_null() {
+                    None
+                } else {
+                    let (register, rank) = approx_count_distinct_register(datum);
+                    Some(((key, register), rank))
+                }
+            })
+            .reduce_named("ApproxCountDistinctRegisters", |key, source, target| {
+                if source.iter().any(|(_val, cnt)| cnt <= &0) {
+                    for (val, cnt) in source.iter() {
+                        // XXX: This reports user data, which we perhaps should not do!
+                        soft_assert_or_log!(
+                            *cnt > 0,
+                            "[customer-data] Non-positive accumulation in ApproxCountDistinctRegisters: key: {:?}\tvalue: {:?}\tcount: {:?}",
+                            key, val, cnt,
+                        );
+                    }
+                    return;
+                }
+                // The ranks are sorted, so the register holds the last one.
+                if let Some((rank, _cnt)) = source.last() {
+                    target.push((**rank, 1));
+                }
+            })
+            .map(|((key, _register), rank)| (key, Some(rank)));
+
+        // Produce an estimate of zero for groups without non-null values.
+        input
+            .map(|(key, _row)| (key, None))
+            .concat(&ranks)
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("ReduceApproxCountDistinct", {
+                let mut row_buf = Row::default();
+                move |key, source, target| {
+                    if source.iter().any(|(_val, cnt)| cnt <= &0) {
+                        for (val, cnt) in source.iter() {
+                            // XXX: This reports user data, which we perhaps should not do!
+                            soft_assert_or_log!(
+                                *cnt > 0,
+                                "[customer-data] Non-positive accumulation in ReduceApproxCountDistinct: key: {:?}\tvalue: {:?}\tcount: {:?}",
+                                key, val, cnt,
+                            );
+                        }
+                        return;
+                    }
+                    let ranks = source.iter().filter_map(|(rank, cnt)| {
+                        rank.map(|rank| (rank, u64::try_from(*cnt).expect("count is positive")))
+                    });
+                    row_buf
+                        .packer()
+                        .push(Datum::Int64(approx_count_distinct_estimate(ranks)));
+                    target.push((row_buf.clone(), 1));
+                }
+            })
+            .leave_region()
+    })
+}
+
 /// Build the dataflow to compute and arrange multiple hierarchical aggregations
 /// on non-monotonic inputs.
 ///
@@ -1547,11 +2213,21 @@ pub mod monoids {
             | AggregateFunc::ArrayConcat { .. }
             | AggregateFunc::ListConcat { .. }
             | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
             | AggregateFunc::RowNumber { .. }
+            | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
-            | AggregateFunc::LastValue { .. } => None,
+            | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
+            | AggregateFunc::WindowAggregate { .. } => None,
         }
     }
 }
@@ -63,6 +63,8 @@ pub struct Config {
     pub now: NowFn,
     /// Metrics registry through which dataflow metrics will be reported.
     pub metrics_registry: MetricsRegistry,
+    /// Whether to write a structured columnar encoding of keys to persist.
+    pub persist_write_structured_keys: bool,
 }
 
 /// A client managing access to the local portion of a Timely cluster
@@ -108,10 +110,9 @@ pub fn serve(
     let trace_metrics = TraceMetrics::register_with(&config.metrics_registry);
     let compute_metrics = ComputeMetrics::register_with(&config.metrics_registry);
 
-    let persist_clients = PersistClientCache::new(
-        PersistConfig::new(config.build_info, config.now.clone()),
-        &config.metrics_registry,
-    );
+    let mut persist_config = PersistConfig::new(config.build_info, config.now.clone());
+    persist_config.write_structured_keys = config.persist_write_structured_keys;
+    let persist_clients = PersistClientCache::new(persist_config, &config.metrics_registry);
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
     let tokio_executor = tokio::runtime::Handle::current();
     let timely_container = Arc::new(tokio::sync::Mutex::new(None));
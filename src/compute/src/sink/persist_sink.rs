@@ -512,6 +512,7 @@ where
 {
     let persist_location = target.persist_location.clone();
     let shard_id = target.data_shard;
+    let relation_desc = target.relation_desc.clone();
 
     let scope = desired_stream.scope();
     let worker_index = scope.index();
@@ -580,6 +581,7 @@ where
             )
             .await
             .expect("could not open persist shard");
+        write.set_key_schema(Arc::new(relation_desc));
 
         // The current input frontiers.
         let mut batch_descriptions_frontier = Antichain::from_elem(TimelyTimestamp::minimum());
@@ -830,6 +832,7 @@ where
 
     let persist_location = target.persist_location.clone();
     let shard_id = target.data_shard;
+    let relation_desc = target.relation_desc.clone();
 
     let operator_name = format!("{} append_batches", operator_name);
     let mut append_op = AsyncOperatorBuilder::new(operator_name, scope.clone());
@@ -907,6 +910,8 @@ where
             )
             .await
             .expect("could not open persist shard");
+        // This handle performs the compactions triggered by its appends.
+        write.set_key_schema(Arc::new(relation_desc));
 
         // The current input frontiers.
         let mut batch_description_frontier = Antichain::from_elem(TimelyTimestamp::minimum());
@@ -234,6 +234,14 @@ where
 {
     /// Creates a new controller.
     pub async fn new(config: ControllerConfig, envd_epoch: NonZeroI64) -> Self {
+        // Clusters write to persist too, so they must agree with this process
+        // about whether to write structured keys.
+        let persist_write_structured_keys = config
+            .persist_clients
+            .lock()
+            .await
+            .cfg()
+            .write_structured_keys;
         let storage_controller = mz_storage_client::controller::Controller::new(
             config.build_info,
             config.storage_stash_url,
@@ -242,6 +250,7 @@ where
             config.orchestrator.namespace("storage"),
             config.storaged_image,
             config.init_container_image.clone(),
+            persist_write_structured_keys,
             config.now,
             &config.postgres_factory,
             envd_epoch,
@@ -253,6 +262,7 @@ where
             config.orchestrator.namespace("compute"),
             config.computed_image,
             config.init_container_image,
+            persist_write_structured_keys,
             envd_epoch,
         );
 
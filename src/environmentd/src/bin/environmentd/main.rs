@@ -59,6 +59,7 @@ use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistConfig, PersistLocation};
+use mz_secrets::envelope::{EnvelopeSecretsController, Keyring};
 use mz_secrets::SecretsController;
 use mz_stash::PostgresFactory;
 use mz_storage_client::types::connections::ConnectionContext;
@@ -307,6 +308,39 @@ pub struct Args {
         default_value = "mzdata"
     )]
     orchestrator_process_data_directory: PathBuf,
+    /// A cgroup v2 directory in which the process orchestrator should create
+    /// a cgroup per process, to enforce the memory and CPU limits of
+    /// replicas.
+    ///
+    /// The directory must be writable, contain no processes, and have the
+    /// `memory` and `cpu` controllers enabled in its `cgroup.subtree_control`.
+    /// Without it, only memory limits are enforced, approximately, via
+    /// resource limits.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_CGROUP_ROOT", value_name = "PATH")]
+    orchestrator_process_cgroup_root: Option<PathBuf>,
+    /// A file containing the master keys with which the process orchestrator
+    /// encrypts secrets at rest, as whitespace-separated hex-encoded 256-bit
+    /// keys.
+    ///
+    /// New secrets are encrypted with the first key. The remaining keys are
+    /// only used to decrypt secrets that have not yet been rotated to the
+    /// first key with `secrets-admin rotate`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SECRETS_MASTER_KEY_FILE",
+        value_name = "PATH",
+        conflicts_with = "orchestrator-process-secrets-master-key"
+    )]
+    orchestrator_process_secrets_master_key_file: Option<PathBuf>,
+    /// Like `--orchestrator-process-secrets-master-key-file`, but specifies
+    /// the keys directly.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SECRETS_MASTER_KEY",
+        value_name = "KEYS",
+        hide_env_values = true
+    )]
+    orchestrator_process_secrets_master_key: Option<Keyring>,
 
     /// The init container to use for computed and storaged when using the
     /// kubernetes orchestrator.
@@ -320,6 +354,12 @@ pub struct Args {
     /// Where the persist library should perform consensus.
     #[clap(long, env = "PERSIST_CONSENSUS_URL")]
     persist_consensus_url: Url,
+    /// Whether to write a structured columnar encoding of keys to persist,
+    /// which allows readers to decode only the columns they need.
+    ///
+    /// The flag is forwarded to the storaged and computed processes.
+    #[clap(long, env = "PERSIST_WRITE_STRUCTURED_KEYS")]
+    persist_write_structured_keys: bool,
     /// The PostgreSQL URL for the storage stash.
     #[clap(long, env = "STORAGE_STASH_URL", value_name = "POSTGRES_URL")]
     storage_stash_url: String,
@@ -591,6 +631,10 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
             )
         }
         OrchestratorKind::Process => {
+            let secrets_master_key = match args.orchestrator_process_secrets_master_key_file {
+                Some(path) => Some(Keyring::from_file(&path)?),
+                None => args.orchestrator_process_secrets_master_key,
+            };
             let orchestrator = Arc::new(
                 runtime
                     .block_on(ProcessOrchestrator::new(ProcessOrchestratorConfig {
@@ -611,22 +655,31 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                         command_wrapper: args
                             .orchestrator_process_wrapper
                             .map_or(Ok(vec![]), |s| shell_words::split(&s))?,
+                        cgroup_root: args.orchestrator_process_cgroup_root,
+                        secrets_master_key: secrets_master_key.clone(),
                     }))
                     .context("creating process orchestrator")?,
             );
+            let secrets_controller = Arc::clone(&orchestrator) as Arc<dyn SecretsController>;
+            let secrets_controller = match secrets_master_key {
+                Some(keyring) => {
+                    Arc::new(EnvelopeSecretsController::new(secrets_controller, keyring))
+                        as Arc<dyn SecretsController>
+                }
+                None => secrets_controller,
+            };
             (
-                Arc::clone(&orchestrator) as Arc<dyn Orchestrator>,
-                orchestrator as Arc<dyn SecretsController>,
+                orchestrator as Arc<dyn Orchestrator>,
+                secrets_controller,
                 None,
             )
         }
     };
     let secrets_reader = secrets_controller.reader();
     let now = SYSTEM_TIME.clone();
-    let persist_clients = PersistClientCache::new(
-        PersistConfig::new(&mz_environmentd::BUILD_INFO, now.clone()),
-        &metrics_registry,
-    );
+    let mut persist_config = PersistConfig::new(&mz_environmentd::BUILD_INFO, now.clone());
+    persist_config.write_structured_keys = args.persist_write_structured_keys;
+    let persist_clients = PersistClientCache::new(persist_config, &metrics_registry);
     let persist_clients = Arc::new(Mutex::new(persist_clients));
     let orchestrator = Arc::new(TracingOrchestrator::new(orchestrator, args.tracing.clone()));
     let controller = ControllerConfig {
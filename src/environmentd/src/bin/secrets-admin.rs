@@ -0,0 +1,93 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Administration of the secrets stored by the process orchestrator.
+//!
+//! To rotate the master key with which secrets are encrypted:
+//!
+//!   1. Generate a new key with `secrets-admin generate-key`.
+//!   2. Add the new key to the start of the master key file and restart
+//!      environmentd, keeping the old key in the file.
+//!   3. Rewrap all secrets with the new key with `secrets-admin rotate`.
+//!   4. Remove the old key from the master key file and restart environmentd.
+
+use std::path::PathBuf;
+use std::process;
+
+use clap::Parser;
+
+use mz_orchestrator_process::secrets::rotate_secrets;
+use mz_ore::cli::{self, CliConfig};
+use mz_secrets::envelope::{Keyring, MasterKey};
+
+#[derive(Parser, Debug)]
+#[clap(name = "secrets-admin", next_line_help = true)]
+pub struct Args {
+    #[clap(subcommand)]
+    action: Action,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Action {
+    /// Prints a new random master key.
+    GenerateKey,
+    /// Rewraps all secrets with the first key in the master key file.
+    ///
+    /// Secrets that are not yet encrypted are encrypted. Every secret must be
+    /// decryptable with one of the keys in the master key file. Can be used
+    /// while environmentd is running.
+    Rotate {
+        /// The data directory of the process orchestrator.
+        #[clap(
+            long,
+            env = "ORCHESTRATOR_PROCESS_DATA_DIRECTORY",
+            value_name = "PATH",
+            default_value = "mzdata"
+        )]
+        orchestrator_process_data_directory: PathBuf,
+        /// The file containing the master keys.
+        #[clap(long, env = "MASTER_KEY_FILE", value_name = "PATH")]
+        master_key_file: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let args = cli::parse_args(CliConfig {
+        env_prefix: Some("MZ_SECRETS_ADMIN_"),
+        enable_version_flag: false,
+    });
+    if let Err(err) = run(args).await {
+        eprintln!("secrets-admin: {:#}", err);
+        process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<(), anyhow::Error> {
+    match args.action {
+        Action::GenerateKey => {
+            let key = MasterKey::generate()?;
+            println!("{}", key.to_hex());
+        }
+        Action::Rotate {
+            orchestrator_process_data_directory,
+            master_key_file,
+        } => {
+            let keyring = Keyring::from_file(&master_key_file)?;
+            let secrets_dir = orchestrator_process_data_directory.join("secrets");
+            let rewritten = rotate_secrets(&secrets_dir, &keyring).await?;
+            println!(
+                "rewrapped {} secrets with master key {}",
+                rewritten,
+                keyring.current().id()
+            );
+        }
+    }
+    Ok(())
+}
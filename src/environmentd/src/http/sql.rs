@@ -8,16 +8,19 @@
 // by the Apache License, Version 2.0.
 
 use anyhow::anyhow;
+use async_trait::async_trait;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::response::IntoResponse;
 use axum::Json;
 use http::StatusCode;
 use itertools::izip;
 use serde::{Deserialize, Serialize};
 
-use mz_adapter::session::{EndTransactionAction, TransactionStatus};
+use mz_adapter::session::{EndTransactionAction, RowBatchStream, TransactionStatus};
 use mz_adapter::{ExecuteResponse, ExecuteResponseKind, PeekResponseUnary, SessionClient};
 use mz_pgwire::Severity;
-use mz_repr::{Datum, RowArena};
+use mz_repr::adt::datetime::Timezone;
+use mz_repr::{strconv, Datum, Row, RowArena};
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{Raw, Statement, StatementKind};
 use mz_sql::plan::Plan;
@@ -28,12 +31,60 @@ pub async fn handle_sql(
     mut client: AuthedClient,
     Json(request): Json<SqlRequest>,
 ) -> impl IntoResponse {
-    match execute_request(&mut client.0, request).await {
-        Ok(res) => Ok(Json(res)),
+    let mut res = SqlResponse { results: vec![] };
+    match execute_request(&mut client.0, request, &mut res).await {
+        Ok(()) => Ok(Json(res)),
         Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
     }
 }
 
+pub async fn handle_sql_ws(client: AuthedClient, ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(|ws| async move { run_ws(client.0, ws).await })
+}
+
+/// Serves [`SqlRequest`]s received over a WebSocket until the client closes
+/// the socket.
+///
+/// Each text or binary message from the client must contain one
+/// `SqlRequest`. The results of each statement are streamed back as
+/// [`WebSocketResponse`]s, and every request is terminated by a
+/// [`WebSocketResponse::ReadyForQuery`] message. Unlike `/api/sql`, `SUBSCRIBE`
+/// is permitted and streams each batch of updates as it arrives. Requests
+/// received while a `SUBSCRIBE` is streaming are answered with a
+/// [`WebSocketResponse::Error`] and are not executed.
+async fn run_ws(mut client: SessionClient, mut ws: WebSocket) {
+    while let Some(msg) = ws.recv().await {
+        let request = match msg {
+            Ok(Message::Text(text)) => serde_json::from_str(&text),
+            Ok(Message::Binary(data)) => serde_json::from_slice(&data),
+            Ok(Message::Ping(_) | Message::Pong(_)) => continue,
+            Ok(Message::Close(_)) | Err(_) => break,
+        };
+        let res = match request {
+            Ok(request) => execute_request(&mut client, request, &mut ws).await,
+            Err(e) => Err(anyhow!("invalid request: {}", e)),
+        };
+        if let Err(e) = res {
+            // If the socket was closed while streaming results, this send
+            // fails too and we stop serving requests.
+            if send_ws_response(&mut ws, WebSocketResponse::Error(e.to_string()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        if send_ws_response(&mut ws, WebSocketResponse::ReadyForQuery)
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+    // Terminating the session tears down any in-progress `SUBSCRIBE`.
+    client.terminate().await;
+}
+
 /// A request to execute SQL over HTTP.
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
@@ -68,6 +119,152 @@ struct SqlResponse {
     results: Vec<SqlResult>,
 }
 
+/// A message sent to the client of a WebSocket SQL session.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "payload")]
+enum WebSocketResponse {
+    /// The result of a statement that does not stream its results.
+    Result(SqlResult),
+    /// A `SUBSCRIBE` has started and will stream updates with the given
+    /// columns.
+    Subscribe { col_names: Vec<String> },
+    /// A batch of updates from the in-progress `SUBSCRIBE`, including any
+    /// progress rows if the `PROGRESS` option was specified.
+    Updates(Vec<Vec<serde_json::Value>>),
+    /// The in-progress `SUBSCRIBE` completed.
+    SubscribeComplete,
+    /// The request could not be executed.
+    Error(String),
+    /// The request has been fully executed.
+    ReadyForQuery,
+}
+
+async fn send_ws_response(ws: &mut WebSocket, res: WebSocketResponse) -> Result<(), anyhow::Error> {
+    let msg = serde_json::to_string(&res)?;
+    ws.send(Message::Text(msg)).await?;
+    Ok(())
+}
+
+/// The result of executing a single statement, before it has been delivered
+/// to the client.
+enum StatementResult {
+    /// A result that can be delivered in its entirety.
+    SqlResult(SqlResult),
+    /// A `SUBSCRIBE` whose updates must be streamed to the client.
+    Subscribe {
+        col_names: Vec<String>,
+        rx: RowBatchStream,
+    },
+}
+
+impl From<SqlResult> for StatementResult {
+    fn from(res: SqlResult) -> StatementResult {
+        StatementResult::SqlResult(res)
+    }
+}
+
+/// A destination for the results of a [`SqlRequest`].
+#[async_trait]
+trait ResultSender: Send {
+    /// Whether this sender can stream the results of a `SUBSCRIBE`.
+    const SUPPORTS_SUBSCRIBE: bool;
+
+    /// Delivers the result of a statement.
+    ///
+    /// Returning an error aborts execution of the remainder of the request.
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> Result<(), anyhow::Error>;
+}
+
+#[async_trait]
+impl ResultSender for SqlResponse {
+    const SUPPORTS_SUBSCRIBE: bool = false;
+
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> Result<(), anyhow::Error> {
+        let res = match res {
+            StatementResult::SqlResult(res) => res,
+            StatementResult::Subscribe { .. } => SqlResult::err(
+                client,
+                "internal error: encountered prohibited ExecuteResponse Subscribing",
+            ),
+        };
+        self.results.push(res);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResultSender for WebSocket {
+    const SUPPORTS_SUBSCRIBE: bool = true;
+
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> Result<(), anyhow::Error> {
+        let (col_names, mut rx) = match res {
+            StatementResult::SqlResult(res) => {
+                return send_ws_response(self, WebSocketResponse::Result(res)).await;
+            }
+            StatementResult::Subscribe { col_names, rx } => (col_names, rx),
+        };
+        send_ws_response(self, WebSocketResponse::Subscribe { col_names }).await?;
+        loop {
+            tokio::select! {
+                batch = rx.recv() => {
+                    let res = match batch {
+                        Some(PeekResponseUnary::Rows(rows)) => {
+                            WebSocketResponse::Updates(rows_to_json(client, rows))
+                        }
+                        Some(PeekResponseUnary::Error(e)) => {
+                            client.fail_transaction();
+                            WebSocketResponse::Result(SqlResult::err(client, e))
+                        }
+                        Some(PeekResponseUnary::Canceled) => {
+                            client.fail_transaction();
+                            WebSocketResponse::Result(SqlResult::err(
+                                client,
+                                "statement canceled due to user request",
+                            ))
+                        }
+                        None => WebSocketResponse::SubscribeComplete,
+                    };
+                    let done = !matches!(res, WebSocketResponse::Updates(_));
+                    send_ws_response(self, res).await?;
+                    if done {
+                        return Ok(());
+                    }
+                }
+                msg = self.recv() => match msg {
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                        anyhow::bail!("websocket closed");
+                    }
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => {}
+                    // Requests cannot be executed while a `SUBSCRIBE` is in
+                    // progress. Reject them rather than silently dropping
+                    // them, but keep the `SUBSCRIBE` running.
+                    Some(Ok(Message::Text(_) | Message::Binary(_))) => {
+                        send_ws_response(
+                            self,
+                            WebSocketResponse::Error(
+                                "request rejected: a SUBSCRIBE is in progress".into(),
+                            ),
+                        )
+                        .await?;
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// The result of a single query in a [`SqlResponse`].
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
@@ -134,13 +331,14 @@ pub struct Notice {
 ///
 /// See the user-facing documentation about the HTTP API for a description of
 /// the semantics of this function.
-async fn execute_request(
+async fn execute_request<S: ResultSender>(
     client: &mut SessionClient,
     request: SqlRequest,
-) -> Result<SqlResponse, anyhow::Error> {
+    sender: &mut S,
+) -> Result<(), anyhow::Error> {
     // This API prohibits executing statements with responses whose
     // semantics are at odds with an HTTP response.
-    fn check_prohibited_stmts(stmt: &Statement<Raw>) -> Result<(), anyhow::Error> {
+    fn check_prohibited_stmts<S: ResultSender>(stmt: &Statement<Raw>) -> Result<(), anyhow::Error> {
         let kind: StatementKind = stmt.into();
         let execute_responses = Plan::generated_from(kind)
             .into_iter()
@@ -151,8 +349,8 @@ async fn execute_request(
         if execute_responses.iter().any(|execute_response| {
             // Returns true if a statement or execute response are unsupported.
             match execute_response {
+                ExecuteResponseKind::Subscribing => !S::SUPPORTS_SUBSCRIBE,
                 ExecuteResponseKind::Fetch
-                | ExecuteResponseKind::Subscribing
                 | ExecuteResponseKind::CopyFrom
                 | ExecuteResponseKind::DeclaredCursor
                 | ExecuteResponseKind::ClosedCursor => true,
@@ -170,14 +368,13 @@ async fn execute_request(
     }
 
     let mut stmt_groups = vec![];
-    let mut results = vec![];
 
     match request {
         SqlRequest::Simple { query } => {
             let stmts = mz_sql::parse::parse(&query).map_err(|e| anyhow!(e))?;
             let mut stmt_group = Vec::with_capacity(stmts.len());
             for stmt in stmts {
-                check_prohibited_stmts(&stmt)?;
+                check_prohibited_stmts::<S>(&stmt)?;
                 stmt_group.push((stmt, vec![]));
             }
             stmt_groups.push(stmt_group);
@@ -194,7 +391,7 @@ async fn execute_request(
                 }
 
                 let stmt = stmts.pop().unwrap();
-                check_prohibited_stmts(&stmt)?;
+                check_prohibited_stmts::<S>(&stmt)?;
 
                 stmt_groups.push(vec![(stmt, params)]);
             }
@@ -214,14 +411,15 @@ async fn execute_request(
             // Mirror the behavior of the PostgreSQL simple query protocol.
             // See the pgwire::protocol::StateMachine::query method for details.
             if let Err(e) = client.start_transaction(Some(num_stmts)).await {
-                results.push(SqlResult::err(client, e));
+                let res = SqlResult::err(client, e);
+                sender.add_result(client, res.into()).await?;
                 break;
             }
             let res = execute_stmt(client, stmt, params).await;
-            if matches!(res, SqlResult::Err { .. }) {
+            if matches!(res, StatementResult::SqlResult(SqlResult::Err { .. })) {
                 client.fail_transaction();
             }
-            results.push(res);
+            sender.add_result(client, res).await?;
         }
     }
 
@@ -229,7 +427,7 @@ async fn execute_request(
         client.end_transaction(EndTransactionAction::Commit).await?;
     }
 
-    Ok(SqlResponse { results })
+    Ok(())
 }
 
 /// Executes a single statement in a [`SqlRequest`].
@@ -237,19 +435,19 @@ async fn execute_stmt(
     client: &mut SessionClient,
     stmt: Statement<Raw>,
     raw_params: Vec<Option<String>>,
-) -> SqlResult {
+) -> StatementResult {
     const EMPTY_PORTAL: &str = "";
     if let Err(e) = client
         .describe(EMPTY_PORTAL.into(), Some(stmt.clone()), vec![])
         .await
     {
-        return SqlResult::err(client, e);
+        return SqlResult::err(client, e).into();
     }
 
     let prep_stmt = match client.get_prepared_statement(EMPTY_PORTAL).await {
         Ok(stmt) => stmt,
         Err(err) => {
-            return SqlResult::err(client, err);
+            return SqlResult::err(client, err).into();
         }
     };
 
@@ -262,7 +460,7 @@ async fn execute_stmt(
             actual = raw_params.len(),
             expected = param_types.len()
         );
-        return SqlResult::err(client, message);
+        return SqlResult::err(client, message).into();
     }
 
     let buf = RowArena::new();
@@ -280,7 +478,7 @@ async fn execute_stmt(
                     Ok(param) => param.into_datum(&buf, &pg_typ),
                     Err(err) => {
                         let msg = format!("unable to decode parameter: {}", err);
-                        return SqlResult::err(client, msg);
+                        return SqlResult::err(client, msg).into();
                     }
                 }
             }
@@ -309,7 +507,7 @@ async fn execute_stmt(
         result_formats,
         revision,
     ) {
-        return SqlResult::err(client, err.to_string());
+        return SqlResult::err(client, err.to_string()).into();
     }
 
     let desc = client
@@ -322,13 +520,13 @@ async fn execute_stmt(
     let res = match client.execute(EMPTY_PORTAL.into()).await {
         Ok(res) => res,
         Err(e) => {
-            return SqlResult::err(client, e);
+            return SqlResult::err(client, e).into();
         }
     };
 
     match res {
         ExecuteResponse::Canceled => {
-            SqlResult::err(client, "statement canceled due to user request")
+            SqlResult::err(client, "statement canceled due to user request").into()
         }
         res @ (ExecuteResponse::CreatedConnection { .. }
         | ExecuteResponse::CreatedDatabase { .. }
@@ -364,8 +562,12 @@ async fn execute_stmt(
         | ExecuteResponse::DroppedSecret
         | ExecuteResponse::DroppedConnection
         | ExecuteResponse::EmptyQuery
+        | ExecuteResponse::GrantedPrivilege
+        | ExecuteResponse::GrantedRole
         | ExecuteResponse::Inserted(_)
         | ExecuteResponse::Raised
+        | ExecuteResponse::RevokedPrivilege
+        | ExecuteResponse::RevokedRole
         | ExecuteResponse::SetVariable { .. }
         | ExecuteResponse::StartedTransaction { .. }
         | ExecuteResponse::TransactionCommitted
@@ -375,7 +577,7 @@ async fn execute_stmt(
         | ExecuteResponse::AlteredIndexLogicalCompaction
         | ExecuteResponse::AlteredSystemConfiguraion
         | ExecuteResponse::Deallocate { .. }
-        | ExecuteResponse::Prepare) => SqlResult::ok(client, res),
+        | ExecuteResponse::Prepare) => SqlResult::ok(client, res).into(),
         ExecuteResponse::SendingRows {
             future: rows,
             span: _,
@@ -383,26 +585,27 @@ async fn execute_stmt(
             let rows = match rows.await {
                 PeekResponseUnary::Rows(rows) => rows,
                 PeekResponseUnary::Error(e) => {
-                    return SqlResult::err(client, e);
+                    return SqlResult::err(client, e).into();
                 }
                 PeekResponseUnary::Canceled => {
-                    return SqlResult::err(client, "statement canceled due to user request");
+                    return SqlResult::err(client, "statement canceled due to user request").into();
                 }
             };
-            let mut sql_rows: Vec<Vec<serde_json::Value>> = vec![];
             let col_names = match desc.relation_desc {
                 Some(desc) => desc.iter_names().map(|name| name.to_string()).collect(),
                 None => vec![],
             };
-            let mut datum_vec = mz_repr::DatumVec::new();
-            for row in rows {
-                let datums = datum_vec.borrow_with(&row);
-                sql_rows.push(datums.iter().map(From::from).collect());
-            }
-            SqlResult::rows(client, sql_rows, col_names)
+            let rows = rows_to_json(client, rows);
+            SqlResult::rows(client, rows, col_names).into()
+        }
+        ExecuteResponse::Subscribing { rx } => {
+            let col_names = match desc.relation_desc {
+                Some(desc) => desc.iter_names().map(|name| name.to_string()).collect(),
+                None => vec![],
+            };
+            StatementResult::Subscribe { col_names, rx }
         }
         res @ (ExecuteResponse::Fetch { .. }
-        | ExecuteResponse::Subscribing { .. }
         | ExecuteResponse::CopyTo { .. }
         | ExecuteResponse::CopyFrom { .. }
         | ExecuteResponse::DeclaredCursor
@@ -412,8 +615,57 @@ async fn execute_stmt(
                 format!("internal error: encountered prohibited ExecuteResponse {:?}.\n\n
 This is a bug. Can you please file an issue letting us know?\n
 https://github.com/MaterializeInc/materialize/issues/new?assignees=&labels=C-bug%2CC-triage&template=01-bug.yml",
-            ExecuteResponseKind::from(res)))
+            ExecuteResponseKind::from(res))).into()
+        }
+    }
+}
+
+/// Converts rows to their JSON representation.
+///
+/// Timestamps with time zones are rendered in the session's time zone.
+fn rows_to_json(client: &mut SessionClient, rows: Vec<Row>) -> Vec<Vec<serde_json::Value>> {
+    let tz = client.session().vars().timezone().to_timezone();
+    let mut datum_vec = mz_repr::DatumVec::new();
+    rows.iter()
+        .map(|row| {
+            let datums = datum_vec.borrow_with(row);
+            datums
+                .iter()
+                .map(|datum| datum_to_json(datum, &tz))
+                .collect()
+        })
+        .collect()
+}
+
+/// Converts a datum to its JSON representation, rendering timestamps with time
+/// zones, including those nested in lists, arrays and maps, in the time zone
+/// `tz`.
+fn datum_to_json(datum: &Datum, tz: &Timezone) -> serde_json::Value {
+    if *tz == Timezone::default() {
+        return datum.into();
+    }
+    match datum {
+        Datum::TimestampTz(ts) => {
+            let mut buf = String::new();
+            strconv::format_timestamptz_in(&mut buf, ts, tz);
+            serde_json::Value::String(buf)
+        }
+        Datum::List(list) => {
+            serde_json::Value::Array(list.iter().map(|datum| datum_to_json(&datum, tz)).collect())
         }
+        Datum::Array(array) => serde_json::Value::Array(
+            array
+                .elements()
+                .iter()
+                .map(|datum| datum_to_json(&datum, tz))
+                .collect(),
+        ),
+        Datum::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.to_owned(), datum_to_json(&v, tz)))
+                .collect(),
+        ),
+        _ => datum.into(),
     }
 }
 
@@ -1650,3 +1650,64 @@ fn test_auth_intermediate_ca() {
         ],
     );
 }
+
+#[test]
+fn test_auth_scram() {
+    let server = util::start_server(util::Config::default()).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE ROLE alice LOGIN SUPERUSER PASSWORD 'hunter2'")
+        .unwrap();
+    client
+        .batch_execute("CREATE ROLE bob LOGIN SUPERUSER")
+        .unwrap();
+
+    run_tests(
+        "SCRAM-SHA-256",
+        &server,
+        &[
+            // The correct password should succeed.
+            TestCase::Pgwire {
+                user: "alice",
+                password: Some("hunter2"),
+                ssl_mode: SslMode::Disable,
+                configure: Box::new(|_| Ok(())),
+                assert: Assert::Success,
+            },
+            // A wrong password should fail.
+            TestCase::Pgwire {
+                user: "alice",
+                password: Some("hunter3"),
+                ssl_mode: SslMode::Disable,
+                configure: Box::new(|_| Ok(())),
+                assert: Assert::Err(Box::new(|err| {
+                    let err = err.unwrap_db_error();
+                    assert_eq!(
+                        err.message(),
+                        "password authentication failed for user \"alice\""
+                    );
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            // An empty password should fail.
+            TestCase::Pgwire {
+                user: "alice",
+                password: None,
+                ssl_mode: SslMode::Disable,
+                configure: Box::new(|_| Ok(())),
+                assert: Assert::Err(Box::new(|err| {
+                    let err = err.unwrap_db_error();
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            // Roles without a password do not require authentication.
+            TestCase::Pgwire {
+                user: "bob",
+                password: None,
+                ssl_mode: SslMode::Disable,
+                configure: Box::new(|_| Ok(())),
+                assert: Assert::Success,
+            },
+        ],
+    );
+}
@@ -205,6 +205,12 @@ fn test_http_sql() {
             status: StatusCode::OK,
             body: r#"{"results":[{"rows":[[[1],[2]]],"col_names":["array","list"],"notices":[]}]}"#,
         },
+        // Timestamps with time zones are rendered in the session's time zone.
+        TestCaseSimple {
+            query: "set timezone = 'America/New_York'; select timestamptz '2020-07-01 12:00:00+00' as a, array[timestamptz '2020-12-01 12:00:00+00'] as b",
+            status: StatusCode::OK,
+            body: r#"{"results":[{"ok":"SET","notices":[]},{"rows":[["2020-07-01 08:00:00-04",["2020-12-01 07:00:00-05"]]],"col_names":["a","b"],"notices":[]}]}"#,
+        },
         // Succeeding and failing queries can mix and match.
         TestCaseSimple {
             query: "select 1; select * from noexist;",
@@ -717,6 +723,182 @@ fn test_cancel_dataflow_removal() {
         .unwrap();
 }
 
+/// Returns the number of dataflows installed for `SUBSCRIBE`s, as reported by
+/// the introspection sources.
+///
+/// Counting only these dataflows keeps tests robust against other dataflows,
+/// like those for system indexes, coming and going.
+fn subscribe_dataflow_count(client: &mut postgres::Client) -> i64 {
+    client
+        .query_one(
+            "SELECT count(*) FROM mz_internal.mz_dataflows
+             WHERE name LIKE 'Dataflow: subscribe-%'",
+            &[],
+        )
+        .unwrap()
+        .get(0)
+}
+
+// Test that canceling a SUBSCRIBE uninstalls its dataflow without waiting for
+// the transaction to end.
+#[test]
+fn test_cancel_subscribe() {
+    let config = util::Config::default();
+    let server = util::start_server(config).unwrap();
+
+    let mut client1 = server.connect(postgres::NoTls).unwrap();
+    let mut client2 = server.connect(postgres::NoTls).unwrap();
+    let cancel_token = client1.cancel_token();
+
+    client1.batch_execute("CREATE TABLE t (i INT)").unwrap();
+
+    let handle = thread::spawn(move || {
+        // Wait until we see the subscribe's dataflow.
+        Retry::default()
+            .retry(|_state| {
+                if subscribe_dataflow_count(&mut client2) == 0 {
+                    Err(())
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap();
+        cancel_token.cancel_query(postgres::NoTls).unwrap();
+        client2
+    });
+
+    client1
+        .batch_execute("BEGIN; DECLARE c CURSOR FOR SUBSCRIBE t")
+        .unwrap();
+    match client1.simple_query("FETCH ALL c") {
+        Err(e) if e.code() == Some(&postgres::error::SqlState::QUERY_CANCELED) => {}
+        Err(e) => panic!("expected error SqlState::QUERY_CANCELED, but got {:?}", e),
+        Ok(_) => panic!("expected error SqlState::QUERY_CANCELED, but query succeeded"),
+    }
+
+    // Expect the dataflow to shut down while the transaction is still open.
+    let mut client2 = handle.join().unwrap();
+    Retry::default()
+        .retry(|_state| {
+            if subscribe_dataflow_count(&mut client2) == 0 {
+                Ok(())
+            } else {
+                Err(())
+            }
+        })
+        .unwrap();
+    client1.batch_execute("ROLLBACK").unwrap();
+}
+
+// Test that the WebSocket SQL endpoint streams SUBSCRIBE updates and tears
+// down the SUBSCRIBE when the socket is closed.
+#[test]
+fn test_http_sql_ws() {
+    let server = util::start_server(util::Config::default()).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE t (i INT); INSERT INTO t VALUES (1)")
+        .unwrap();
+
+    let url = format!(
+        "ws://{}/api/experimental/sql",
+        server.inner.http_local_addr()
+    );
+    let (mut ws, _resp) = tungstenite::connect(url).unwrap();
+    fn recv<S: std::io::Read + std::io::Write>(
+        ws: &mut tungstenite::WebSocket<S>,
+    ) -> serde_json::Value {
+        loop {
+            match ws.read_message().unwrap() {
+                tungstenite::Message::Text(msg) => return serde_json::from_str(&msg).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    // Non-streaming statements report their results as in `/api/sql`.
+    ws.write_message(tungstenite::Message::Text(
+        json!({"query": "SELECT 1 AS a"}).to_string(),
+    ))
+    .unwrap();
+    assert_eq!(
+        recv(&mut ws),
+        json!({"type": "Result", "payload": {"rows": [[1]], "col_names": ["a"], "notices": []}})
+    );
+    assert_eq!(recv(&mut ws), json!({"type": "ReadyForQuery"}));
+
+    // Invalid requests are reported, but do not close the socket.
+    ws.write_message(tungstenite::Message::Text("{}".into()))
+        .unwrap();
+    assert_eq!(recv(&mut ws)["type"], "Error");
+    assert_eq!(recv(&mut ws), json!({"type": "ReadyForQuery"}));
+
+    ws.write_message(tungstenite::Message::Text(
+        json!({"query": "SUBSCRIBE t WITH (PROGRESS)"}).to_string(),
+    ))
+    .unwrap();
+    assert_eq!(
+        recv(&mut ws),
+        json!({
+            "type": "Subscribe",
+            "payload": {"col_names": ["mz_timestamp", "mz_progressed", "mz_diff", "i"]}
+        })
+    );
+    // Returns whether a progress row preceded the update that inserts `i`.
+    let recv_update = |ws: &mut tungstenite::WebSocket<_>, i: i64| {
+        let mut saw_progress = false;
+        loop {
+            let msg = recv(ws);
+            assert_eq!(msg["type"], "Updates");
+            for row in msg["payload"].as_array().unwrap() {
+                if row[1] == json!(true) {
+                    saw_progress = true;
+                } else if row[3] == json!(i) {
+                    assert_eq!(row[2], json!(1));
+                    return saw_progress;
+                }
+            }
+        }
+    };
+    recv_update(&mut ws, 1);
+    client.batch_execute("INSERT INTO t VALUES (2)").unwrap();
+    // The snapshot and the insert happen at different timestamps, so there
+    // must be a progress message between them.
+    assert!(recv_update(&mut ws, 2));
+
+    // Requests sent while the SUBSCRIBE is in progress are rejected, but the
+    // SUBSCRIBE keeps streaming.
+    ws.write_message(tungstenite::Message::Text(
+        json!({"query": "SELECT 1"}).to_string(),
+    ))
+    .unwrap();
+    loop {
+        let msg = recv(&mut ws);
+        if msg["type"] == "Updates" {
+            continue;
+        }
+        assert_eq!(
+            msg,
+            json!({"type": "Error", "payload": "request rejected: a SUBSCRIBE is in progress"})
+        );
+        break;
+    }
+    client.batch_execute("INSERT INTO t VALUES (3)").unwrap();
+    recv_update(&mut ws, 3);
+
+    // Closing the socket uninstalls the SUBSCRIBE's dataflow.
+    ws.close(None).unwrap();
+    Retry::default()
+        .retry(|_state| {
+            if subscribe_dataflow_count(&mut client) == 0 {
+                Ok(())
+            } else {
+                Err(())
+            }
+        })
+        .unwrap();
+}
+
 #[test]
 fn test_storage_usage_collection_interval() {
     let config =
@@ -1750,6 +1750,124 @@ fn test_introspection_user_permissions() {
         .is_err());
 }
 
+#[test]
+fn test_role_privileges() {
+    let config = util::Config::default();
+    let server = util::start_server(config).unwrap();
+
+    let mut admin_client = server.connect(postgres::NoTls).unwrap();
+    admin_client
+        .batch_execute(
+            "CREATE ROLE alice LOGIN;
+             CREATE ROLE readers;
+             CREATE ROLE nologin;
+             CREATE TABLE t (a INT);
+             INSERT INTO t VALUES (1);",
+        )
+        .unwrap();
+
+    // Roles without LOGIN cannot start a session.
+    assert!(server
+        .pg_config()
+        .user("nologin")
+        .connect(postgres::NoTls)
+        .is_err());
+
+    let mut alice_client = server
+        .pg_config()
+        .user("alice")
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    // Non-superusers hold no privileges on objects created by other roles.
+    let err = alice_client.query("SELECT * FROM t", &[]).unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "permission denied for table \"materialize.public.t\""
+    );
+    assert!(alice_client
+        .batch_execute("CREATE TABLE u (a INT)")
+        .is_err());
+    assert!(alice_client.batch_execute("CREATE ROLE bob").is_err());
+    assert!(alice_client
+        .batch_execute("GRANT SELECT ON t TO alice")
+        .is_err());
+
+    // Privileges granted to a role are inherited by its members.
+    admin_client
+        .batch_execute("GRANT SELECT ON TABLE t TO readers; GRANT readers TO alice")
+        .unwrap();
+    let rows = alice_client.query("SELECT a FROM t", &[]).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(alice_client
+        .batch_execute("INSERT INTO t VALUES (2)")
+        .is_err());
+
+    admin_client
+        .batch_execute("GRANT INSERT ON TABLE t TO PUBLIC")
+        .unwrap();
+    alice_client
+        .batch_execute("INSERT INTO t VALUES (2)")
+        .unwrap();
+
+    admin_client
+        .batch_execute("GRANT CREATE ON SCHEMA materialize.public TO alice")
+        .unwrap();
+    alice_client
+        .batch_execute("CREATE TABLE u (a INT)")
+        .unwrap();
+
+    // Creators own what they create and hold every privilege on it.
+    alice_client
+        .batch_execute("INSERT INTO u VALUES (1); UPDATE u SET a = 2; DELETE FROM u")
+        .unwrap();
+    assert_eq!(alice_client.query("SELECT a FROM u", &[]).unwrap().len(), 0);
+    alice_client
+        .batch_execute("ALTER TABLE u RENAME TO v")
+        .unwrap();
+
+    // Only owners may drop or alter objects.
+    let err = alice_client.batch_execute("DROP TABLE t").unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "permission denied: must be owner of table \"materialize.public.t\""
+    );
+    assert!(alice_client
+        .batch_execute("ALTER TABLE t RENAME TO t2")
+        .is_err());
+    assert!(alice_client.batch_execute("DROP SCHEMA public").is_err());
+    admin_client
+        .batch_execute(
+            "CREATE ROLE mallory LOGIN; GRANT CREATE ON SCHEMA materialize.public TO mallory",
+        )
+        .unwrap();
+    let mut mallory_client = server
+        .pg_config()
+        .user("mallory")
+        .connect(postgres::NoTls)
+        .unwrap();
+    assert!(mallory_client.batch_execute("DROP TABLE v").is_err());
+    assert!(mallory_client
+        .batch_execute("ALTER TABLE v RENAME TO w")
+        .is_err());
+    alice_client.batch_execute("DROP TABLE v").unwrap();
+
+    // Circular memberships are rejected.
+    let err = admin_client
+        .batch_execute("GRANT alice TO readers")
+        .unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "role \"alice\" is a member of role \"readers\""
+    );
+
+    // Revoking the membership revokes the inherited privileges.
+    admin_client
+        .batch_execute("REVOKE readers FROM alice")
+        .unwrap();
+    assert!(alice_client.query("SELECT a FROM t", &[]).is_err());
+}
+
 #[test]
 fn test_idle_in_transaction_session_timeout() {
     let config = util::Config::default();
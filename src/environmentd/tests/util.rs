@@ -192,6 +192,8 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
             suppress_output: true,
             data_dir: data_directory.clone(),
             command_wrapper: vec![],
+            cgroup_root: None,
+            secrets_master_key: None,
         }))?,
     );
     // Messing with the clock causes persist to expire leases, causing hangs and
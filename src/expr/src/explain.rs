@@ -132,6 +132,21 @@ impl<'a> ViewExplanation<'a> {
                     explanation.local_id_chains.insert(*id, value_chain);
                     explanation.chain_local_ids.insert(value_chain, *id);
 
+                    walk(body, explanation);
+                }
+                LetRec { ids, values, body } => {
+                    // Each recursive definition goes in its own chain.
+                    // References to bindings that are not yet walked are
+                    // printed without their chain ID.
+                    for (id, value) in ids.iter().zip(values) {
+                        walk(value, explanation);
+                        explanation.chain += 1;
+
+                        let value_chain = explanation.expr_chain(value);
+                        explanation.local_id_chains.insert(*id, value_chain);
+                        explanation.chain_local_ids.insert(value_chain, *id);
+                    }
+
                     walk(body, explanation);
                 }
             }
@@ -153,14 +168,16 @@ impl<'a> ViewExplanation<'a> {
         {
             for expr in exprs {
                 // Elide chains that would consist only a of single Get node.
-                if let MirRelationExpr::Get {
-                    id: Id::Local(id), ..
-                } = expr
-                {
-                    explanation.expr_chains.insert(
-                        expr as *const MirRelationExpr,
-                        explanation.local_id_chains[id],
-                    );
+                let local_chain = match expr {
+                    MirRelationExpr::Get {
+                        id: Id::Local(id), ..
+                    } => explanation.local_id_chains.get(id).copied(),
+                    _ => None,
+                };
+                if let Some(chain) = local_chain {
+                    explanation
+                        .expr_chains
+                        .insert(expr as *const MirRelationExpr, chain);
                 } else {
                     walk(expr, explanation);
                     explanation.chain += 1;
@@ -183,7 +200,7 @@ impl<'a> ViewExplanation<'a> {
     /// Attach type information into the explanation.
     pub fn explain_types(&mut self) {
         for node in &mut self.nodes {
-            if let MirRelationExpr::Let { .. } = &node.expr {
+            if let MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } = &node.expr {
                 // Skip.
                 // Since we don't print out Let nodes in the explanation,
                 // types of Let nodes should not be attached to the
@@ -238,7 +255,7 @@ impl<'a> ViewExplanation<'a> {
                 )?,
             },
             // Lets are annotated on the chain ID that they correspond to.
-            Let { .. } => (),
+            Let { .. } | LetRec { .. } => (),
             Project { outputs, .. } => {
                 writeln!(f, "| Project {}", bracketed("(", ")", Indices(outputs)))?
             }
@@ -139,11 +139,13 @@ impl fmt::Display for SourceInstanceId {
 
 /// Unique identifier for each part of a whole source.
 ///     Kafka -> partition
+///     KafkaTopic -> topic and partition, for sources that read from several topics
 ///     None -> sources that have no notion of partitioning (e.g file sources)
 #[derive(Arbitrary, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum PartitionId {
     Kafka(i32),
     None,
+    KafkaTopic(String, i32),
 }
 
 impl fmt::Display for PartitionId {
@@ -151,6 +153,8 @@ impl fmt::Display for PartitionId {
         match self {
             PartitionId::Kafka(id) => write!(f, "{}", id),
             PartitionId::None => write!(f, "none"),
+            // Kafka topic names cannot contain slashes, so this is unambiguous.
+            PartitionId::KafkaTopic(topic, id) => write!(f, "{}/{}", topic, id),
         }
     }
 }
@@ -170,10 +174,16 @@ impl FromStr for PartitionId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "none" => Ok(PartitionId::None),
-            s => {
-                let val: i32 = s.parse()?;
-                Ok(PartitionId::Kafka(val))
-            }
+            s => match s.rsplit_once('/') {
+                Some((topic, partition)) => {
+                    let val: i32 = partition.parse()?;
+                    Ok(PartitionId::KafkaTopic(topic.to_string(), val))
+                }
+                None => {
+                    let val: i32 = s.parse()?;
+                    Ok(PartitionId::Kafka(val))
+                }
+            },
         }
     }
 }
@@ -185,6 +195,12 @@ impl RustType<ProtoPartitionId> for PartitionId {
             kind: Some(match self {
                 PartitionId::Kafka(x) => Kafka(*x),
                 PartitionId::None => None(()),
+                PartitionId::KafkaTopic(topic, partition) => {
+                    KafkaTopic(proto_partition_id::ProtoKafkaTopic {
+                        topic: topic.clone(),
+                        partition: *partition,
+                    })
+                }
             }),
         }
     }
@@ -194,6 +210,9 @@ impl RustType<ProtoPartitionId> for PartitionId {
         match proto.kind {
             Option::Some(Kafka(x)) => Ok(PartitionId::Kafka(x)),
             Option::Some(None(_)) => Ok(PartitionId::None),
+            Option::Some(KafkaTopic(proto_partition_id::ProtoKafkaTopic { topic, partition })) => {
+                Ok(PartitionId::KafkaTopic(topic, partition))
+            }
             Option::None => Err(TryFromProtoError::missing_field("ProtoPartitionId::kind")),
         }
     }
@@ -231,5 +250,12 @@ mod tests {
             assert!(actual.is_ok());
             assert_eq!(actual.unwrap(), expect);
         }
+
+        #[test]
+        fn partition_id_protobuf_roundtrip(expect in any::<PartitionId>()) {
+            let actual = protobuf_roundtrip::<_, ProtoPartitionId>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
     }
 }
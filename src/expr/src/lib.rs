@@ -37,6 +37,10 @@ pub use linear::{
     util::{join_permutations, permutation_for_arrangement},
     MapFilterProject, ProtoMapFilterProject, ProtoMfpPlan, ProtoSafeMfpPlan,
 };
+pub use relation::func::{
+    approx_count_distinct_estimate, approx_count_distinct_register, percentile_cont_interpolate,
+    percentile_cont_positions, percentile_disc_position, APPROX_COUNT_DISTINCT_PRECISION,
+};
 pub use relation::func::{AggregateFunc, LagLeadType, TableFunc};
 pub use relation::func::{AnalyzedRegex, CaptureGroupDesc};
 pub use relation::join_input_mapper::JoinInputMapper;
@@ -1424,7 +1424,7 @@ pub mod util {
 }
 
 pub mod plan {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::iter;
 
     use proptest::prelude::*;
@@ -1742,6 +1742,48 @@ pub mod plan {
             }
         }
 
+        /// The non-temporal portion of the plan, which is applied before the
+        /// temporal bounds are evaluated.
+        pub fn nontemporal(&self) -> &SafeMfpPlan {
+            &self.mfp
+        }
+
+        /// Expressions that, when evaluated on an input row, lower-bound the
+        /// times at which the row is valid.
+        pub fn lower_bounds(&self) -> &[MirScalarExpr] {
+            &self.lower_bounds
+        }
+
+        /// Expressions that, when evaluated on an input row, upper-bound the
+        /// times at which the row is valid.
+        pub fn upper_bounds(&self) -> &[MirScalarExpr] {
+            &self.upper_bounds
+        }
+
+        /// Lists input columns whose values are used in outputs or in the
+        /// evaluation of predicates, temporal or non-.
+        ///
+        /// Columns outside of this set may be replaced with arbitrary values
+        /// without changing the results of [MfpPlan::evaluate].
+        pub fn demand(&self) -> HashSet<usize> {
+            let mfp = &self.mfp.mfp;
+            let mut demanded = HashSet::new();
+            for (_index, pred) in mfp.predicates.iter() {
+                demanded.extend(pred.support());
+            }
+            for bound in self.lower_bounds.iter().chain(self.upper_bounds.iter()) {
+                demanded.extend(bound.support());
+            }
+            demanded.extend(mfp.projection.iter().cloned());
+            for index in (0..mfp.expressions.len()).rev() {
+                if demanded.contains(&(mfp.input_arity + index)) {
+                    demanded.extend(mfp.expressions[index].support());
+                }
+            }
+            demanded.retain(|col| col < &mfp.input_arity);
+            demanded
+        }
+
         /// Returns an iterator over mutable references to all non-temporal
         /// scalar expressions in the plan.
         ///
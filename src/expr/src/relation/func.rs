@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::iter;
 use std::ops::Deref;
@@ -31,18 +32,21 @@ use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::Interval;
+use mz_repr::adt::jsonpath::JsonPath;
 use mz_repr::adt::numeric::{self, NumericMaxScale};
 use mz_repr::adt::regex::Regex as ReprRegex;
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::adt::timestamp::TimestampLike;
-use mz_repr::{ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType};
+use mz_repr::{
+    strconv, ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType,
+};
 
 use crate::relation::{
     compare_columns, proto_aggregate_func, proto_aggregate_func::ProtoColumnOrders,
     proto_table_func, ColumnOrder, ProtoAggregateFunc, ProtoTableFunc, WindowFrame,
     WindowFrameBound, WindowFrameUnits,
 };
-use crate::scalar::func::{add_timestamp_months, jsonb_stringify};
+use crate::scalar::func::{add_timestamp_months, jsonb_path_eval, jsonb_stringify, regexp_split};
 use crate::EvalError;
 
 include!(concat!(env!("OUT_DIR"), "/mz_expr.relation.func.rs"));
@@ -544,6 +548,78 @@ where
     Datum::from(x)
 }
 
+/// The number of bits of a value's hash that select the HyperLogLog register
+/// that the value updates in `approx_count_distinct`.
+///
+/// With `2^14` registers, the relative standard error of the estimate is
+/// `1.04 / sqrt(2^14)`, or about 0.81%.
+pub const APPROX_COUNT_DISTINCT_PRECISION: u32 = 14;
+
+/// Returns the HyperLogLog register that `datum` updates in
+/// `approx_count_distinct`, along with the rank that it records there, i.e.,
+/// the one-based position of the first set bit among the remaining bits of
+/// its hash.
+///
+/// The register holds the greatest rank recorded by any value.
+pub fn approx_count_distinct_register(datum: Datum) -> (u16, u8) {
+    let hash = mz_ore::hash::hash(&datum);
+    let register = hash >> (u64::BITS - APPROX_COUNT_DISTINCT_PRECISION);
+    let rest = hash << APPROX_COUNT_DISTINCT_PRECISION;
+    let rank = rest
+        .leading_zeros()
+        .min(u64::BITS - APPROX_COUNT_DISTINCT_PRECISION)
+        + 1;
+    (
+        u16::try_from(register).expect("register fits in precision bits"),
+        u8::try_from(rank).expect("rank is at most 64"),
+    )
+}
+
+/// Estimates the number of distinct values from the number of HyperLogLog
+/// registers that hold each rank, in increasing order of rank. Registers that
+/// no value updated are omitted.
+pub fn approx_count_distinct_estimate<I>(ranks: I) -> i64
+where
+    I: IntoIterator<Item = (u8, u64)>,
+{
+    let registers = f64::from(1u32 << APPROX_COUNT_DISTINCT_PRECISION);
+    let mut empty = registers;
+    let mut sum = 0.0;
+    for (rank, count) in ranks {
+        empty -= count as f64;
+        sum += count as f64 * 2f64.powi(-i32::from(rank));
+    }
+    sum += empty;
+    let alpha = 0.7213 / (1.0 + 1.079 / registers);
+    let estimate = alpha * registers * registers / sum;
+    let estimate = if estimate <= 2.5 * registers && empty > 0.0 {
+        // Linear counting is more accurate for small cardinalities.
+        registers * (registers / empty).ln()
+    } else {
+        estimate
+    };
+    estimate.round() as i64
+}
+
+fn approx_count_distinct<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut registers = vec![0u8; 1 << APPROX_COUNT_DISTINCT_PRECISION];
+    for datum in datums {
+        if !datum.is_null() {
+            let (register, rank) = approx_count_distinct_register(datum);
+            let register = &mut registers[usize::from(register)];
+            *register = (*register).max(rank);
+        }
+    }
+    let mut ranks = BTreeMap::new();
+    for rank in registers.into_iter().filter(|rank| *rank > 0) {
+        *ranks.entry(rank).or_insert(0) += 1;
+    }
+    Datum::from(approx_count_distinct_estimate(ranks))
+}
+
 fn any<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -730,6 +806,111 @@ where
     })
 }
 
+/// Returns the zero-based position of the value that `percentile_disc` selects
+/// from `count` values sorted by the aggregation's `ORDER BY`.
+///
+/// Like PostgreSQL, this is the first value whose position in the ordering,
+/// as a fraction of `count`, is at least `fraction`.
+pub fn percentile_disc_position(fraction: f64, count: u64) -> u64 {
+    let position = (fraction * count as f64).ceil() as u64;
+    position.clamp(1, count) - 1
+}
+
+/// Returns the zero-based positions of the two values that `percentile_cont`
+/// interpolates between when applied to `count` values sorted by the
+/// aggregation's `ORDER BY`, along with the weight of the second value.
+pub fn percentile_cont_positions(fraction: f64, count: u64) -> (u64, u64, f64) {
+    let position = fraction * (count - 1) as f64;
+    let lower = position.floor();
+    (lower as u64, position.ceil() as u64, position - lower)
+}
+
+/// Linearly interpolates between the two values selected by
+/// [`percentile_cont_positions`].
+pub fn percentile_cont_interpolate(lower: f64, upper: f64, weight: f64) -> f64 {
+    if weight == 0.0 {
+        // Avoid producing NaN when interpolating between infinite values.
+        lower
+    } else {
+        lower + weight * (upper - lower)
+    }
+}
+
+// Assuming datums is a List whose first element is a `(value, fraction)`
+// record, sort them by the remaining elements corresponding to order_by, then
+// return the non-null values and the fraction.
+fn percentile_input<'a, I>(datums: I, order_by: &[ColumnOrder]) -> (Vec<Datum<'a>>, Datum<'a>)
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut fraction = Datum::Null;
+    let values = order_aggregate_datums(datums, order_by)
+        .filter_map(|d| {
+            // The record is null if the input was excluded by a FILTER clause.
+            if d.is_null() {
+                return None;
+            }
+            let mut value_fraction = d.unwrap_list().iter();
+            let value = value_fraction.next().unwrap();
+            // The planner ensures that the fraction is the same for all inputs.
+            fraction = value_fraction.next().unwrap();
+            (!value.is_null()).then_some(value)
+        })
+        .collect();
+    (values, fraction)
+}
+
+fn percentile_cont<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (values, fraction) = percentile_input(datums, order_by);
+    if values.is_empty() || fraction.is_null() {
+        return Datum::Null;
+    }
+    let (lower, upper, weight) =
+        percentile_cont_positions(fraction.unwrap_float64(), u64::cast_from(values.len()));
+    Datum::Float64(
+        percentile_cont_interpolate(
+            values[usize::cast_from(lower)].unwrap_float64(),
+            values[usize::cast_from(upper)].unwrap_float64(),
+            weight,
+        )
+        .into(),
+    )
+}
+
+fn percentile_disc<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (values, fraction) = percentile_input(datums, order_by);
+    if values.is_empty() || fraction.is_null() {
+        return Datum::Null;
+    }
+    let position =
+        percentile_disc_position(fraction.unwrap_float64(), u64::cast_from(values.len()));
+    values[usize::cast_from(position)]
+}
+
+fn mode<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // The datums are sorted, so equal values are adjacent. Only replace the
+    // mode with a strictly more frequent value, so that ties are broken in
+    // favor of the value that sorts first.
+    let mut mode = (Datum::Null, 0);
+    let values = order_aggregate_datums(datums, order_by).filter(|d| !d.is_null());
+    for (value, group) in &values.group_by(|d| *d) {
+        let count = group.count();
+        if count > mode.1 {
+            mode = (value, count);
+        }
+    }
+    mode.0
+}
+
 fn row_number<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -789,6 +970,104 @@ where
     })
 }
 
+// Computes the bounds of the peer group of each row, i.e. the range of rows with the same
+// ORDER BY value. `rows` must already be sorted by the ORDER BY expressions.
+fn peer_bounds<T, F>(rows: &[T], order_by_row: F) -> Vec<(usize, usize)>
+where
+    F: Fn(&T) -> &Row,
+{
+    let length = rows.len();
+    let mut bounds = Vec::with_capacity(length);
+    let mut peer_start = 0;
+    for idx in 1..=length {
+        if idx == length || order_by_row(&rows[idx]) != order_by_row(&rows[peer_start]) {
+            bounds.extend(iter::repeat((peer_start, idx)).take(idx - peer_start));
+            peer_start = idx;
+        }
+    }
+    bounds
+}
+
+// Sorts the datums by their ORDER BY expressions, and returns the original rows paired with the
+// bounds of their peer group.
+// The expected input is in the format of [([OriginalRow], OrderByExprs...)]
+fn peer_groups<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Vec<(Datum<'a>, (usize, usize))>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums = order_aggregate_datums_with_rank(datums, order_by)
+        .flat_map(|(d, row)| d.unwrap_list().iter().map(move |d| (d, row.clone())))
+        .collect_vec();
+    let bounds = peer_bounds(&datums, |(_, row)| row);
+    datums
+        .into_iter()
+        .map(|(original_row, _)| original_row)
+        .zip(bounds)
+        .collect()
+}
+
+fn rank<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums =
+        peer_groups(datums, order_by)
+            .into_iter()
+            .map(|(original_row, (peer_start, _))| {
+                // The rank of a row is the row number of the first row of its peer group
+                let rank = i64::try_from(peer_start + 1).expect("rank does not fit in i64");
+                temp_storage.make_datum(|packer| {
+                    packer.push_list(vec![Datum::Int64(rank), original_row]);
+                })
+            });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+fn percent_rank<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums = peer_groups(datums, order_by);
+    let length = datums.len();
+    let datums = datums.into_iter().map(|(original_row, (peer_start, _))| {
+        // (rank - 1) / (total rows - 1), or 0 if the partition has a single row
+        let percent_rank = if length > 1 {
+            peer_start as f64 / (length - 1) as f64
+        } else {
+            0.0
+        };
+        temp_storage.make_datum(|packer| {
+            packer.push_list(vec![Datum::from(percent_rank), original_row]);
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+fn cume_dist<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums = peer_groups(datums, order_by);
+    let length = datums.len();
+    let datums = datums.into_iter().map(|(original_row, (_, peer_end))| {
+        // The number of rows preceding or peer with the current row, over the total rows
+        let cume_dist = peer_end as f64 / length as f64;
+        temp_storage.make_datum(|packer| {
+            packer.push_list(vec![Datum::from(cume_dist), original_row]);
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
 // The expected input is in the format of [((OriginalRow, EncodedArgs), OrderByExprs...)]
 fn lag_lead<'a, I>(
     datums: I,
@@ -860,6 +1139,55 @@ where
     })
 }
 
+// The expected input is in the format of [((OriginalRow, NumBuckets), OrderByExprs...)]
+fn ntile<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, NumBuckets) record
+    let datums = order_aggregate_datums(datums, order_by)
+        .map(|d| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let num_buckets = iter.next().unwrap();
+
+            (num_buckets, original_row)
+        })
+        .collect_vec();
+
+    // Like in PostgreSQL, the number of buckets is taken from the first row of the partition
+    let length = datums.len();
+    let num_buckets = datums.first().map_or(Datum::Null, |(n, _)| *n);
+    let result = datums.iter().enumerate().map(|(idx, (_, original_row))| {
+        let bucket = match num_buckets {
+            Datum::Null => Datum::Null,
+            num_buckets => {
+                // Negative and zero bucket counts are rejected during planning
+                let num_buckets = usize::try_from(num_buckets.unwrap_int32())
+                    .expect("ntile bucket count must be positive");
+                // The rows are distributed as evenly as possible, with the first
+                // `length % num_buckets` buckets having one extra row
+                let per_bucket = length / num_buckets;
+                let extra = length % num_buckets;
+                let large_rows = extra * (per_bucket + 1);
+                let bucket = if idx < large_rows {
+                    idx / (per_bucket + 1)
+                } else {
+                    extra + (idx - large_rows) / per_bucket
+                };
+                Datum::Int32(i32::try_from(bucket + 1).expect("bucket is at most num_buckets"))
+            }
+        };
+        temp_storage.make_datum(|packer| {
+            packer.push_list(vec![bucket, *original_row]);
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(result);
+    })
+}
+
 // The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
 fn first_value<'a, I>(
     datums: I,
@@ -870,27 +1198,43 @@ fn first_value<'a, I>(
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, InputValue) record
-    let datums = order_aggregate_datums(datums, order_by);
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, InputValue), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
 
-    // Decode the input (OriginalRow, InputValue) into separate datums
+    // Decode the input (OriginalRow, InputValue) into separate datums, while keeping the OrderByRow
     let datums = datums
         .into_iter()
-        .map(|d| {
+        .map(|(d, order_by_row)| {
             let mut iter = d.unwrap_list().iter();
             let original_row = iter.next().unwrap();
             let input_value = iter.next().unwrap();
 
-            (input_value, original_row)
+            (input_value, original_row, order_by_row)
         })
         .collect_vec();
 
     let length = datums.len();
     let mut result: Vec<(Datum, Datum)> = Vec::with_capacity(length);
-    for (idx, (current_datum, original_row)) in datums.iter().enumerate() {
+    for (idx, (current_datum, original_row, order_by_row)) in datums.iter().enumerate() {
         let first_value = match &window_frame.start_bound {
-            // Always return the current value
-            WindowFrameBound::CurrentRow => *current_datum,
+            WindowFrameBound::CurrentRow => match &window_frame.units {
+                // Always return the current value when in ROWS mode
+                WindowFrameUnits::Rows => *current_datum,
+                WindowFrameUnits::Range => {
+                    // When in RANGE mode, return the first value of the peer group
+                    // The peer group is the group of rows with the same ORDER BY value
+                    datums[..=idx]
+                        .iter()
+                        .rev()
+                        .take_while(|(_, _, row)| row == order_by_row)
+                        .last()
+                        .unwrap()
+                        .0
+                }
+                // GROUPS is not supported, and forbidden during planning
+                WindowFrameUnits::Groups => unreachable!(),
+            },
             WindowFrameBound::UnboundedPreceding => {
                 if let WindowFrameBound::OffsetPreceding(end_offset) = &window_frame.end_bound {
                     let end_offset = usize::cast_from(*end_offset);
@@ -989,8 +1333,8 @@ where
                 WindowFrameUnits::Range => {
                     // When in RANGE mode, return the last value of the peer group
                     // The peer group is the group of rows with the same ORDER BY value
-                    // Note: Range is only supported for the default window frame (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW),
-                    // which is why it does not appear in the other branches
+                    // Note: RANGE is only supported without offsets, which is why it does not
+                    // appear in the other branches
                     datums[idx..]
                         .iter()
                         .take_while(|(_, _, row)| row == order_by_row)
@@ -1076,6 +1420,328 @@ where
     })
 }
 
+// Computes the window frame of the row at `idx` of a sorted partition of `length` rows, whose
+// peer group spans `peer_start..peer_end`. The frame is returned as the range of rows it covers,
+// and an empty frame is represented by `(0, 0)`.
+fn window_frame_bounds(
+    window_frame: &WindowFrame,
+    idx: usize,
+    length: usize,
+    (peer_start, peer_end): (usize, usize),
+) -> (usize, usize) {
+    let start = match (&window_frame.units, &window_frame.start_bound) {
+        (_, WindowFrameBound::UnboundedPreceding) => 0,
+        (WindowFrameUnits::Rows, WindowFrameBound::OffsetPreceding(offset)) => {
+            idx.saturating_sub(usize::cast_from(*offset))
+        }
+        (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => idx,
+        (WindowFrameUnits::Rows, WindowFrameBound::OffsetFollowing(offset)) => {
+            idx.saturating_add(usize::cast_from(*offset))
+        }
+        (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peer_start,
+        // Offsets in RANGE mode and GROUPS are forbidden during planning, as is
+        // UNBOUNDED FOLLOWING as the frame start
+        _ => unreachable!(),
+    };
+    let end = match (&window_frame.units, &window_frame.end_bound) {
+        (_, WindowFrameBound::UnboundedFollowing) => length,
+        (WindowFrameUnits::Rows, WindowFrameBound::OffsetPreceding(offset)) => {
+            (idx + 1).saturating_sub(usize::cast_from(*offset))
+        }
+        (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => idx + 1,
+        (WindowFrameUnits::Rows, WindowFrameBound::OffsetFollowing(offset)) => idx
+            .saturating_add(usize::cast_from(*offset))
+            .saturating_add(1),
+        (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peer_end,
+        // Offsets in RANGE mode and GROUPS are forbidden during planning, as is
+        // UNBOUNDED PRECEDING as the frame end
+        _ => unreachable!(),
+    };
+    let end = end.min(length);
+    if start < end {
+        (start, end)
+    } else {
+        (0, 0)
+    }
+}
+
+/// Describes how the results of an aggregate over two disjoint sets of rows are combined into the
+/// result of the aggregate over their union.
+#[derive(Debug, Clone, Copy)]
+enum PartialCombiner<'f> {
+    /// The aggregate, applied to its own results, computes the combined result. This holds for
+    /// `min`, `max`, `bool_or`, `bool_and`, and sums whose result type is their input type.
+    Reapply(&'f AggregateFunc),
+    /// The results are `int8`s or NULLs that are added together.
+    AddInt64,
+    /// The results are `uint8`s or NULLs that are added together.
+    AddUInt64,
+    /// The results are `numeric`s or NULLs that are added together.
+    AddNumeric,
+}
+
+impl<'f> PartialCombiner<'f> {
+    /// Returns the combiner for `func`, or `None` if the results of `func` cannot be combined.
+    fn for_aggregate(func: &'f AggregateFunc) -> Option<Self> {
+        use AggregateFunc::*;
+        match func {
+            MaxNumeric | MaxInt16 | MaxInt32 | MaxInt64 | MaxUInt16 | MaxUInt32 | MaxUInt64
+            | MaxMzTimestamp | MaxFloat32 | MaxFloat64 | MaxBool | MaxString | MaxDate
+            | MaxTimestamp | MaxTimestampTz | MinNumeric | MinInt16 | MinInt32 | MinInt64
+            | MinUInt16 | MinUInt32 | MinUInt64 | MinMzTimestamp | MinFloat32 | MinFloat64
+            | MinBool | MinString | MinDate | MinTimestamp | MinTimestampTz | SumFloat32
+            | SumFloat64 | SumNumeric | Any | All => Some(PartialCombiner::Reapply(func)),
+            SumInt16 | SumInt32 | Count => Some(PartialCombiner::AddInt64),
+            SumUInt16 | SumUInt32 => Some(PartialCombiner::AddUInt64),
+            SumInt64 | SumUInt64 => Some(PartialCombiner::AddNumeric),
+            _ => None,
+        }
+    }
+
+    fn combine<'a>(&self, a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+        match (self, a, b) {
+            (PartialCombiner::Reapply(func), a, b) => func.eval([a, b], temp_storage),
+            (_, Datum::Null, d) | (_, d, Datum::Null) => d,
+            (PartialCombiner::AddInt64, a, b) => Datum::from(a.unwrap_int64() + b.unwrap_int64()),
+            (PartialCombiner::AddUInt64, a, b) => {
+                Datum::from(a.unwrap_uint64() + b.unwrap_uint64())
+            }
+            (PartialCombiner::AddNumeric, a, b) => {
+                let mut cx = numeric::cx_datum();
+                let mut sum = a.unwrap_numeric().0;
+                cx.add(&mut sum, &b.unwrap_numeric().0);
+                Datum::from(sum)
+            }
+        }
+    }
+}
+
+/// A hierarchical reduction tree over the partial results of an aggregate.
+///
+/// Each leaf holds the result of the aggregate over one row, and each inner node holds the
+/// combination of its children. The result of the aggregate over any contiguous range of rows is
+/// assembled from `O(log n)` nodes.
+///
+/// The tree only lives for one evaluation of a window aggregate over a partition, so it is built
+/// anew whenever any row of the partition changes.
+#[derive(Debug)]
+pub struct ReductionTree<'a, 'f> {
+    combiner: PartialCombiner<'f>,
+    /// The result of the aggregate over no rows, which is the identity of `combiner`.
+    identity: Datum<'a>,
+    /// The nodes of the tree, in the layout of a binary heap. The leaves occupy the second half.
+    nodes: Vec<Datum<'a>>,
+}
+
+impl<'a, 'f> ReductionTree<'a, 'f> {
+    /// Builds a tree for `func` over `values`, or returns `None` if the results of `func` cannot
+    /// be combined.
+    pub fn new<I>(func: &'f AggregateFunc, values: I, temp_storage: &'a RowArena) -> Option<Self>
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let combiner = PartialCombiner::for_aggregate(func)?;
+        let values = values.into_iter();
+        let len = values.len();
+        let identity = func.eval(iter::empty(), temp_storage);
+        let mut nodes = vec![identity; len];
+        nodes.extend(values.map(|value| func.eval([value], temp_storage)));
+        for i in (1..len).rev() {
+            nodes[i] = combiner.combine(nodes[2 * i], nodes[2 * i + 1], temp_storage);
+        }
+        Some(ReductionTree {
+            combiner,
+            identity,
+            nodes,
+        })
+    }
+
+    /// The number of rows in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len() / 2
+    }
+
+    /// Whether the tree holds no rows.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the result of the aggregate over the rows in `start..end`.
+    pub fn query(&self, start: usize, end: usize, temp_storage: &'a RowArena) -> Datum<'a> {
+        let mut result = self.identity;
+        let (mut lo, mut hi) = (start + self.len(), end + self.len());
+        while lo < hi {
+            if lo % 2 == 1 {
+                result = self.combiner.combine(result, self.nodes[lo], temp_storage);
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                result = self.combiner.combine(result, self.nodes[hi], temp_storage);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        result
+    }
+
+    /// Returns the result of the aggregate over the rows in `start..end`, given the result
+    /// `prefix` of the aggregate over the rows in `start..prev_end`.
+    ///
+    /// This extends a running aggregate by the rows in `prev_end..end` without revisiting the
+    /// rows before them.
+    fn extend(
+        &self,
+        prefix: Datum<'a>,
+        prev_end: usize,
+        end: usize,
+        temp_storage: &'a RowArena,
+    ) -> Datum<'a> {
+        let leaves = &self.nodes[self.len()..];
+        leaves[prev_end..end].iter().fold(prefix, |acc, leaf| {
+            self.combiner.combine(acc, *leaf, temp_storage)
+        })
+    }
+}
+
+// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
+fn window_aggregate<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    wrapped_aggregate: &AggregateFunc,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, InputValue), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    // Decode the input (OriginalRow, InputValue) into separate datums, while keeping the OrderByRow
+    let datums = datums
+        .into_iter()
+        .map(|(d, order_by_row)| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let input_value = iter.next().unwrap();
+
+            (input_value, original_row, order_by_row)
+        })
+        .collect_vec();
+
+    let length = datums.len();
+    let peer_bounds = peer_bounds(&datums, |(_, _, order_by_row)| order_by_row);
+
+    // Aggregates whose partial results can be combined are evaluated over a reduction tree, so
+    // that every frame costs `O(log n)` rather than `O(n)`. Others are evaluated directly over the
+    // rows of every frame. Either way, the whole partition is evaluated again whenever one of its
+    // rows changes.
+    let tree = ReductionTree::new(
+        wrapped_aggregate,
+        datums.iter().map(|(value, _, _)| *value),
+        temp_storage,
+    );
+
+    let mut result: Vec<(Datum, Datum)> = Vec::with_capacity(length);
+    // The frame of the previous row and the aggregate computed over it. Consecutive rows often
+    // share the same frame (e.g. peers in RANGE mode), in which case the aggregate is reused.
+    // Frames that only grow at their end (e.g. running aggregates) extend the previous aggregate.
+    let mut prev: Option<((usize, usize), Datum)> = None;
+    for (idx, (_, original_row, _)) in datums.iter().enumerate() {
+        let frame = window_frame_bounds(window_frame, idx, length, peer_bounds[idx]);
+
+        let value = match (&tree, prev) {
+            (_, Some((prev_frame, prev_value))) if prev_frame == frame => prev_value,
+            (Some(tree), Some(((prev_start, prev_end), prev_value)))
+                if prev_start == frame.0 && prev_end <= frame.1 =>
+            {
+                tree.extend(prev_value, prev_end, frame.1, temp_storage)
+            }
+            (Some(tree), _) => tree.query(frame.0, frame.1, temp_storage),
+            (None, _) => wrapped_aggregate.eval(
+                datums[frame.0..frame.1].iter().map(|(value, _, _)| *value),
+                temp_storage,
+            ),
+        };
+        prev = Some((frame, value));
+
+        result.push((value, *original_row));
+    }
+
+    let result = result.into_iter().map(|(value, original_row)| {
+        temp_storage.make_datum(|packer| {
+            packer.push_list(vec![value, original_row]);
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(result);
+    })
+}
+
+// The expected input is in the format of [((OriginalRow, (InputValue, N)), OrderByExprs...)]
+fn nth_value<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, EncodedArgs), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    // Decode the input (OriginalRow, (InputValue, N)) into separate datums, while keeping the OrderByRow
+    let datums = datums
+        .into_iter()
+        .map(|(d, order_by_row)| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let mut encoded_args = iter.next().unwrap().unwrap_list().iter();
+            let (input_value, n) = (encoded_args.next().unwrap(), encoded_args.next().unwrap());
+
+            (input_value, n, original_row, order_by_row)
+        })
+        .collect_vec();
+
+    let length = datums.len();
+    let peer_bounds = peer_bounds(&datums, |(_, _, _, order_by_row)| order_by_row);
+
+    let mut result: Vec<(Datum, Datum)> = Vec::with_capacity(length);
+    for (idx, (_, n, original_row, _)) in datums.iter().enumerate() {
+        let value = match n {
+            Datum::Null => Datum::Null,
+            n => {
+                // Non-positive values of N are rejected during planning
+                let n = usize::try_from(n.unwrap_int32()).expect("N must be positive");
+                let (start, end) = window_frame_bounds(window_frame, idx, length, peer_bounds[idx]);
+                // Return null if the frame has fewer than N rows
+                match start.checked_add(n - 1) {
+                    Some(nth) if nth < end => datums[nth].0,
+                    _ => Datum::Null,
+                }
+            }
+        };
+
+        result.push((value, *original_row));
+    }
+
+    let result = result.into_iter().map(|(value, original_row)| {
+        temp_storage.make_datum(|packer| {
+            packer.push_list(vec![value, original_row]);
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(result);
+    })
+}
+
 /// Identify whether the given aggregate function is Lag or Lead, since they share
 /// implementations.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
@@ -1126,6 +1792,9 @@ pub enum AggregateFunc {
     SumFloat64,
     SumNumeric,
     Count,
+    /// Estimates the number of distinct non-null values with a HyperLogLog
+    /// sketch. See [`APPROX_COUNT_DISTINCT_PRECISION`] for its error bound.
+    ApproxCountDistinct,
     Any,
     All,
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
@@ -1159,12 +1828,43 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes a continuous percentile of `Datum::List`s whose first element
+    /// is a `(value, fraction)` record of `Datum::Float64`s, interpolating
+    /// between adjacent values if needed. The other elements are columns used
+    /// by `order_by`.
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `PercentileCont`, but selects the first value whose position in
+    /// the ordering reaches the fraction, rather than interpolating. The value
+    /// may be of any type.
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Selects the most frequent first element of `Datum::List`s, breaking ties
+    /// in favor of the value that sorts first. The other elements are columns
+    /// used by `order_by`.
+    Mode {
+        order_by: Vec<ColumnOrder>,
+    },
     RowNumber {
         order_by: Vec<ColumnOrder>,
     },
+    Rank {
+        order_by: Vec<ColumnOrder>,
+    },
     DenseRank {
         order_by: Vec<ColumnOrder>,
     },
+    PercentRank {
+        order_by: Vec<ColumnOrder>,
+    },
+    CumeDist {
+        order_by: Vec<ColumnOrder>,
+    },
+    Ntile {
+        order_by: Vec<ColumnOrder>,
+    },
     LagLead {
         order_by: Vec<ColumnOrder>,
         lag_lead: LagLeadType,
@@ -1177,6 +1877,19 @@ pub enum AggregateFunc {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    NthValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    /// Evaluates `wrapped_aggregate` over the window frame of each row of a
+    /// partition. Like the other window functions, the input is a list of
+    /// `((OriginalRow, InputValue), OrderByExprs...)` records, and the output
+    /// is a list of `(Result, OriginalRow)` records.
+    WindowAggregate {
+        wrapped_aggregate: Box<AggregateFunc>,
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -1239,6 +1952,7 @@ impl Arbitrary for AggregateFunc {
             Just(AggregateFunc::SumFloat64),
             Just(AggregateFunc::SumNumeric),
             Just(AggregateFunc::Count),
+            Just(AggregateFunc::ApproxCountDistinct),
             Just(AggregateFunc::Any),
             Just(AggregateFunc::All),
             vec(proptest_any::<ColumnOrder>(), 1..4)
@@ -1251,10 +1965,24 @@ impl Arbitrary for AggregateFunc {
                 .prop_map(|order_by| AggregateFunc::ListConcat { order_by }),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::StringAgg { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentileCont { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentileDisc { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Mode { order_by }),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::RowNumber { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Rank { order_by }),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::DenseRank { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentRank { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::CumeDist { order_by }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Ntile { order_by }),
             (
                 vec(proptest_any::<ColumnOrder>(), 1..4),
                 proptest_any::<LagLeadType>()
@@ -1276,6 +2004,30 @@ impl Arbitrary for AggregateFunc {
                     order_by,
                     window_frame,
                 }),
+            (
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<WindowFrame>()
+            )
+                .prop_map(|(order_by, window_frame)| AggregateFunc::NthValue {
+                    order_by,
+                    window_frame,
+                }),
+            (
+                prop_oneof![
+                    Just(AggregateFunc::SumInt64),
+                    Just(AggregateFunc::MaxInt64),
+                    Just(AggregateFunc::Count),
+                ],
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<WindowFrame>()
+            )
+                .prop_map(|(wrapped_aggregate, order_by, window_frame)| {
+                    AggregateFunc::WindowAggregate {
+                        wrapped_aggregate: Box::new(wrapped_aggregate),
+                        order_by,
+                        window_frame,
+                    }
+                }),
             Just(AggregateFunc::Dummy)
         ]
     }
@@ -1338,6 +2090,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::SumFloat64 => Kind::SumFloat64(()),
                 AggregateFunc::SumNumeric => Kind::SumNumeric(()),
                 AggregateFunc::Count => Kind::Count(()),
+                AggregateFunc::ApproxCountDistinct => Kind::ApproxCountDistinct(()),
                 AggregateFunc::Any => Kind::Any(()),
                 AggregateFunc::All => Kind::All(()),
                 AggregateFunc::JsonbAgg { order_by } => Kind::JsonbAgg(order_by.into_proto()),
@@ -1347,8 +2100,19 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::ArrayConcat { order_by } => Kind::ArrayConcat(order_by.into_proto()),
                 AggregateFunc::ListConcat { order_by } => Kind::ListConcat(order_by.into_proto()),
                 AggregateFunc::StringAgg { order_by } => Kind::StringAgg(order_by.into_proto()),
+                AggregateFunc::PercentileCont { order_by } => {
+                    Kind::PercentileCont(order_by.into_proto())
+                }
+                AggregateFunc::PercentileDisc { order_by } => {
+                    Kind::PercentileDisc(order_by.into_proto())
+                }
+                AggregateFunc::Mode { order_by } => Kind::Mode(order_by.into_proto()),
                 AggregateFunc::RowNumber { order_by } => Kind::RowNumber(order_by.into_proto()),
+                AggregateFunc::Rank { order_by } => Kind::Rank(order_by.into_proto()),
                 AggregateFunc::DenseRank { order_by } => Kind::DenseRank(order_by.into_proto()),
+                AggregateFunc::PercentRank { order_by } => Kind::PercentRank(order_by.into_proto()),
+                AggregateFunc::CumeDist { order_by } => Kind::CumeDist(order_by.into_proto()),
+                AggregateFunc::Ntile { order_by } => Kind::Ntile(order_by.into_proto()),
                 AggregateFunc::LagLead { order_by, lag_lead } => {
                     Kind::LagLead(proto_aggregate_func::ProtoLagLead {
                         order_by: Some(order_by.into_proto()),
@@ -1376,6 +2140,22 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::NthValue {
+                    order_by,
+                    window_frame,
+                } => Kind::NthValue(proto_aggregate_func::ProtoWindowFrame {
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                }),
+                AggregateFunc::WindowAggregate {
+                    wrapped_aggregate,
+                    order_by,
+                    window_frame,
+                } => Kind::WindowAggregate(Box::new(proto_aggregate_func::ProtoWindowAggregate {
+                    wrapped_aggregate: Some(wrapped_aggregate.into_proto()),
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                })),
                 AggregateFunc::Dummy => Kind::Dummy(()),
             }),
         }
@@ -1427,6 +2207,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::SumFloat64(()) => AggregateFunc::SumFloat64,
             Kind::SumNumeric(()) => AggregateFunc::SumNumeric,
             Kind::Count(()) => AggregateFunc::Count,
+            Kind::ApproxCountDistinct(()) => AggregateFunc::ApproxCountDistinct,
             Kind::Any(()) => AggregateFunc::Any,
             Kind::All(()) => AggregateFunc::All,
             Kind::JsonbAgg(order_by) => AggregateFunc::JsonbAgg {
@@ -1444,12 +2225,33 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::StringAgg(order_by) => AggregateFunc::StringAgg {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentileCont(order_by) => AggregateFunc::PercentileCont {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::PercentileDisc(order_by) => AggregateFunc::PercentileDisc {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Mode(order_by) => AggregateFunc::Mode {
+                order_by: order_by.into_rust()?,
+            },
             Kind::RowNumber(order_by) => AggregateFunc::RowNumber {
                 order_by: order_by.into_rust()?,
             },
+            Kind::Rank(order_by) => AggregateFunc::Rank {
+                order_by: order_by.into_rust()?,
+            },
             Kind::DenseRank(order_by) => AggregateFunc::DenseRank {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentRank(order_by) => AggregateFunc::PercentRank {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::CumeDist(order_by) => AggregateFunc::CumeDist {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Ntile(order_by) => AggregateFunc::Ntile {
+                order_by: order_by.into_rust()?,
+            },
             Kind::LagLead(pll) => AggregateFunc::LagLead {
                 order_by: pll.order_by.into_rust_if_some("ProtoLagLead::order_by")?,
                 lag_lead: match pll.lag_lead {
@@ -1482,6 +2284,25 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoWindowFrame::window_frame")?,
             },
+            Kind::NthValue(pnv) => AggregateFunc::NthValue {
+                order_by: pnv
+                    .order_by
+                    .into_rust_if_some("ProtoWindowFrame::order_by")?,
+                window_frame: pnv
+                    .window_frame
+                    .into_rust_if_some("ProtoWindowFrame::window_frame")?,
+            },
+            Kind::WindowAggregate(pwa) => AggregateFunc::WindowAggregate {
+                wrapped_aggregate: pwa
+                    .wrapped_aggregate
+                    .into_rust_if_some("ProtoWindowAggregate::wrapped_aggregate")?,
+                order_by: pwa
+                    .order_by
+                    .into_rust_if_some("ProtoWindowAggregate::order_by")?,
+                window_frame: pwa
+                    .window_frame
+                    .into_rust_if_some("ProtoWindowAggregate::window_frame")?,
+            },
             Kind::Dummy(()) => AggregateFunc::Dummy,
         })
     }
@@ -1533,6 +2354,7 @@ impl AggregateFunc {
             AggregateFunc::SumFloat64 => sum_float64(datums),
             AggregateFunc::SumNumeric => sum_numeric(datums),
             AggregateFunc::Count => count(datums),
+            AggregateFunc::ApproxCountDistinct => approx_count_distinct(datums),
             AggregateFunc::Any => any(datums),
             AggregateFunc::All => all(datums),
             AggregateFunc::JsonbAgg { order_by } => jsonb_agg(datums, temp_storage, order_by),
@@ -1542,8 +2364,15 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => array_concat(datums, temp_storage, order_by),
             AggregateFunc::ListConcat { order_by } => list_concat(datums, temp_storage, order_by),
             AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::PercentileCont { order_by } => percentile_cont(datums, order_by),
+            AggregateFunc::PercentileDisc { order_by } => percentile_disc(datums, order_by),
+            AggregateFunc::Mode { order_by } => mode(datums, order_by),
             AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
+            AggregateFunc::Rank { order_by } => rank(datums, temp_storage, order_by),
             AggregateFunc::DenseRank { order_by } => dense_rank(datums, temp_storage, order_by),
+            AggregateFunc::PercentRank { order_by } => percent_rank(datums, temp_storage, order_by),
+            AggregateFunc::CumeDist { order_by } => cume_dist(datums, temp_storage, order_by),
+            AggregateFunc::Ntile { order_by } => ntile(datums, temp_storage, order_by),
             AggregateFunc::LagLead {
                 order_by,
                 lag_lead: lag_lead_type,
@@ -1556,6 +2385,21 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+            } => nth_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => window_aggregate(
+                datums,
+                temp_storage,
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            ),
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -1564,7 +2408,7 @@ impl AggregateFunc {
     /// input relation.
     pub fn default(&self) -> Datum<'static> {
         match self {
-            AggregateFunc::Count => Datum::Int64(0),
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => Datum::Int64(0),
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
@@ -1582,10 +2426,16 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { .. } => Datum::empty_array(),
             AggregateFunc::ListConcat { .. } => Datum::empty_list(),
             AggregateFunc::RowNumber { .. } => Datum::empty_list(),
+            AggregateFunc::Rank { .. } => Datum::empty_list(),
             AggregateFunc::DenseRank { .. } => Datum::empty_list(),
+            AggregateFunc::PercentRank { .. } => Datum::empty_list(),
+            AggregateFunc::CumeDist { .. } => Datum::empty_list(),
+            AggregateFunc::Ntile { .. } => Datum::empty_list(),
             AggregateFunc::LagLead { .. } => Datum::empty_list(),
             AggregateFunc::FirstValue { .. } => Datum::empty_list(),
             AggregateFunc::LastValue { .. } => Datum::empty_list(),
+            AggregateFunc::NthValue { .. } => Datum::empty_list(),
+            AggregateFunc::WindowAggregate { .. } => Datum::empty_list(),
             _ => Datum::Null,
         }
     }
@@ -1597,7 +2447,7 @@ impl AggregateFunc {
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
@@ -1620,6 +2470,21 @@ impl AggregateFunc {
                 }
             }
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } => ScalarType::Float64,
+            AggregateFunc::PercentileDisc { .. } => match input_type.scalar_type {
+                // The input is wrapped in a Record with the ORDER BY, so extract the value out of
+                // the inner (value, fraction) Record.
+                ScalarType::Record { ref fields, .. } => match &fields[0].1.scalar_type {
+                    ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            AggregateFunc::Mode { .. } => match input_type.scalar_type {
+                // The input is wrapped in a Record with the ORDER BY, so extract it out.
+                ScalarType::Record { ref fields, .. } => fields[0].1.scalar_type.clone(),
+                _ => unreachable!(),
+            },
             AggregateFunc::RowNumber { .. } => match input_type.scalar_type {
                 ScalarType::Record { ref fields, .. } => ScalarType::List {
                     element_type: Box::new(ScalarType::Record {
@@ -1642,28 +2507,62 @@ impl AggregateFunc {
                 },
                 _ => unreachable!(),
             },
-            AggregateFunc::DenseRank { .. } => match input_type.scalar_type {
-                ScalarType::Record { ref fields, .. } => ScalarType::List {
+            AggregateFunc::Rank { .. }
+            | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. } => match input_type.scalar_type {
+                ScalarType::Record { ref fields, .. } => {
+                    let (column_name, value_type) = match self {
+                        AggregateFunc::Rank { .. } => ("?rank?", ScalarType::Int64),
+                        AggregateFunc::DenseRank { .. } => ("?dense_rank?", ScalarType::Int64),
+                        AggregateFunc::PercentRank { .. } => {
+                            ("?percent_rank?", ScalarType::Float64)
+                        }
+                        AggregateFunc::CumeDist { .. } => ("?cume_dist?", ScalarType::Float64),
+                        _ => unreachable!(),
+                    };
+                    ScalarType::List {
+                        element_type: Box::new(ScalarType::Record {
+                            fields: vec![
+                                (ColumnName::from(column_name), value_type.nullable(false)),
+                                (ColumnName::from("?record?"), {
+                                    let inner = match &fields[0].1.scalar_type {
+                                        ScalarType::List { element_type, .. } => {
+                                            element_type.clone()
+                                        }
+                                        _ => unreachable!(),
+                                    };
+                                    inner.nullable(false)
+                                }),
+                            ],
+                            custom_id: None,
+                        }),
+                        custom_id: None,
+                    }
+                }
+                _ => unreachable!(),
+            },
+            AggregateFunc::Ntile { .. } => {
+                // The input type for Ntile is ((OriginalRow, NumBuckets), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+
+                ScalarType::List {
                     element_type: Box::new(ScalarType::Record {
                         fields: vec![
                             (
-                                ColumnName::from("?dense_rank?"),
-                                ScalarType::Int64.nullable(false),
+                                ColumnName::from("?ntile?"),
+                                ScalarType::Int32.nullable(true),
                             ),
-                            (ColumnName::from("?record?"), {
-                                let inner = match &fields[0].1.scalar_type {
-                                    ScalarType::List { element_type, .. } => element_type.clone(),
-                                    _ => unreachable!(),
-                                };
-                                inner.nullable(false)
-                            }),
+                            (ColumnName::from("?record?"), original_row_type),
                         ],
                         custom_id: None,
                     }),
                     custom_id: None,
-                },
-                _ => unreachable!(),
-            },
+                }
+            }
             AggregateFunc::LagLead { lag_lead, .. } => {
                 // The input type for Lag is a ((OriginalRow, EncodedArgs), OrderByExprs...)
                 let fields = input_type.scalar_type.unwrap_record_element_type();
@@ -1732,15 +2631,67 @@ impl AggregateFunc {
                     custom_id: None,
                 }
             }
+            AggregateFunc::NthValue { .. } => {
+                // The input type for NthValue is ((OriginalRow, (InputValue, N)), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let value_type = fields[0].unwrap_record_element_type()[1]
+                    .unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(true);
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: vec![
+                            (ColumnName::from("?nth_value?"), value_type),
+                            (ColumnName::from("?record?"), original_row_type),
+                        ],
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => {
+                // The input type for a window aggregate is ((OriginalRow, InputValue), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let input_value_type = fields[0].unwrap_record_element_type()[1]
+                    .clone()
+                    .nullable(true);
+                // The window frame of a row might be empty, in which case the result is the
+                // default value of the wrapped aggregate
+                let value_type = wrapped_aggregate.output_type(input_value_type);
+                let nullable = value_type.nullable || wrapped_aggregate.default().is_null();
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: vec![
+                            (
+                                ColumnName::from("?window_agg?"),
+                                value_type.scalar_type.nullable(nullable),
+                            ),
+                            (ColumnName::from("?record?"), original_row_type),
+                        ],
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
             // Note AggregateFunc::MaxString, MinString rely on returning input
             // type as output type to support the proper return type for
             // character input.
             _ => input_type.scalar_type.clone(),
         };
-        // Count never produces null, and other aggregations only produce
-        // null in the presence of null inputs.
+        // Count and ApproxCountDistinct never produce null, and other
+        // aggregations only produce null in the presence of null inputs.
         let nullable = match self {
-            AggregateFunc::Count => false,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => false,
             // Use the nullability of the underlying column being aggregated, not the Records wrapping it
             AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
                 // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
@@ -1751,6 +2702,27 @@ impl AggregateFunc {
                 },
                 _ => unreachable!(),
             },
+            // Percentiles are null if there are no non-null values, or if the fraction is null
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                match input_type.scalar_type {
+                    ScalarType::Record { fields, .. } => {
+                        let inner = &fields[0].1;
+                        match &inner.scalar_type {
+                            // The inner Record is a (value, fraction) tuple, and is itself null
+                            // if the input was excluded by a FILTER clause
+                            ScalarType::Record { fields, .. } => {
+                                inner.nullable || fields.iter().any(|(_, typ)| typ.nullable)
+                            }
+                            _ => unreachable!(),
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            AggregateFunc::Mode { .. } => match input_type.scalar_type {
+                ScalarType::Record { fields, .. } => fields[0].1.nullable,
+                _ => unreachable!(),
+            },
             _ => input_type.nullable,
         };
         scalar_type.nullable(nullable)
@@ -1807,6 +2779,17 @@ impl AggregateFunc {
             _ => false,
         }
     }
+
+    /// Returns true if this is an ordered-set aggregate, like `percentile_cont`,
+    /// whose input values are ordered by a `WITHIN GROUP (ORDER BY ...)` clause.
+    pub fn is_ordered_set(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunc::PercentileCont { .. }
+                | AggregateFunc::PercentileDisc { .. }
+                | AggregateFunc::Mode { .. }
+        )
+    }
 }
 
 fn jsonb_each<'a>(
@@ -1866,6 +2849,49 @@ fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     Some((Row::pack(datums), 1))
 }
 
+fn regexp_matches<'a>(
+    a: Datum<'a>,
+    r: &'a Regex,
+    global: bool,
+) -> impl Iterator<Item = (Row, Diff)> + 'a {
+    let limit = if global { usize::MAX } else { 1 };
+    r.captures_iter(a.unwrap_str()).take(limit).map(|captures| {
+        // Like `regexp_match`, produce the text of each capture group, or
+        // the text of the entire match if the regex has no capture groups.
+        let datums: Vec<_> = if captures.len() > 1 {
+            captures
+                .iter()
+                .skip(1)
+                .map(|m| Datum::from(m.map(|m| m.as_str())))
+                .collect()
+        } else {
+            vec![Datum::String(captures.get(0).unwrap().as_str())]
+        };
+        let dims = ArrayDimension {
+            lower_bound: 1,
+            length: datums.len(),
+        };
+        let mut row = Row::default();
+        row.packer().push_array(&[dims], datums).unwrap();
+        (row, 1)
+    })
+}
+
+fn jsonb_path_query<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let items = jsonb_path_eval(datums, path, temp_storage)?.unwrap_or_default();
+    Ok(items.into_iter().map(|item| (Row::pack_slice(&[item]), 1)))
+}
+
+fn regexp_split_to_table<'a>(a: Datum<'a>, r: &Regex) -> impl Iterator<Item = (Row, Diff)> + 'a {
+    regexp_split(a.unwrap_str(), r)
+        .into_iter()
+        .map(|part| (Row::pack_slice(&[Datum::String(part)]), 1))
+}
+
 fn generate_series<N>(
     start: N,
     stop: N,
@@ -2034,6 +3060,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::SumFloat64 => f.write_str("sum"),
             AggregateFunc::SumNumeric => f.write_str("sum"),
             AggregateFunc::Count => f.write_str("count"),
+            AggregateFunc::ApproxCountDistinct => f.write_str("approx_count_distinct"),
             AggregateFunc::Any => f.write_str("any"),
             AggregateFunc::All => f.write_str("all"),
             AggregateFunc::JsonbAgg { .. } => f.write_str("jsonb_agg"),
@@ -2041,8 +3068,15 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::ArrayConcat { .. } => f.write_str("array_agg"),
             AggregateFunc::ListConcat { .. } => f.write_str("list_agg"),
             AggregateFunc::StringAgg { .. } => f.write_str("string_agg"),
+            AggregateFunc::PercentileCont { .. } => f.write_str("percentile_cont"),
+            AggregateFunc::PercentileDisc { .. } => f.write_str("percentile_disc"),
+            AggregateFunc::Mode { .. } => f.write_str("mode"),
             AggregateFunc::RowNumber { .. } => f.write_str("row_number"),
+            AggregateFunc::Rank { .. } => f.write_str("rank"),
             AggregateFunc::DenseRank { .. } => f.write_str("dense_rank"),
+            AggregateFunc::PercentRank { .. } => f.write_str("percent_rank"),
+            AggregateFunc::CumeDist { .. } => f.write_str("cume_dist"),
+            AggregateFunc::Ntile { .. } => f.write_str("ntile"),
             AggregateFunc::LagLead {
                 lag_lead: LagLeadType::Lag,
                 ..
@@ -2053,6 +3087,10 @@ impl fmt::Display for AggregateFunc {
             } => f.write_str("lead"),
             AggregateFunc::FirstValue { .. } => f.write_str("first_value"),
             AggregateFunc::LastValue { .. } => f.write_str("last_value"),
+            AggregateFunc::NthValue { .. } => f.write_str("nth_value"),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => write!(f, "window_agg[{}]", wrapped_aggregate),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -2174,7 +3212,22 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery,
+    /// Like `JsonbPathQuery`, but with the path, usually the second argument,
+    /// parsed ahead of time.
+    JsonbPathQueryCompiled {
+        #[proptest(strategy = "mz_repr::adt::jsonpath::any_jsonpath()")]
+        path: JsonPath,
+    },
     RegexpExtract(AnalyzedRegex),
+    /// Produces the captures of the first match of `regex`, or of every match
+    /// if `global` is set, as text arrays.
+    RegexpMatches {
+        #[proptest(strategy = "mz_repr::adt::regex::any_regex()")]
+        regex: ReprRegex,
+        global: bool,
+    },
+    RegexpSplitToTable(#[proptest(strategy = "mz_repr::adt::regex::any_regex()")] ReprRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
     GenerateSeriesInt64,
@@ -2202,14 +3255,25 @@ pub enum TableFunc {
 impl RustType<ProtoTableFunc> for TableFunc {
     fn into_proto(&self) -> ProtoTableFunc {
         use proto_table_func::Kind;
-        use proto_table_func::ProtoWrap;
+        use proto_table_func::{ProtoRegexpMatches, ProtoWrap};
 
         ProtoTableFunc {
             kind: Some(match self {
                 TableFunc::JsonbEach { stringify } => Kind::JsonbEach(*stringify),
                 TableFunc::JsonbObjectKeys => Kind::JsonbObjectKeys(()),
                 TableFunc::JsonbArrayElements { stringify } => Kind::JsonbArrayElements(*stringify),
+                TableFunc::JsonbPathQuery => Kind::JsonbPathQuery(()),
+                TableFunc::JsonbPathQueryCompiled { path } => {
+                    Kind::JsonbPathQueryCompiled(path.into_proto())
+                }
                 TableFunc::RegexpExtract(x) => Kind::RegexpExtract(x.into_proto()),
+                TableFunc::RegexpMatches { regex, global } => {
+                    Kind::RegexpMatches(ProtoRegexpMatches {
+                        regex: Some(regex.into_proto()),
+                        global: *global,
+                    })
+                }
+                TableFunc::RegexpSplitToTable(x) => Kind::RegexpSplitToTable(x.into_proto()),
                 TableFunc::CsvExtract(x) => Kind::CsvExtract(x.into_proto()),
                 TableFunc::GenerateSeriesInt32 => Kind::GenerateSeriesInt32(()),
                 TableFunc::GenerateSeriesInt64 => Kind::GenerateSeriesInt64(()),
@@ -2238,7 +3302,16 @@ impl RustType<ProtoTableFunc> for TableFunc {
             Kind::JsonbEach(stringify) => TableFunc::JsonbEach { stringify },
             Kind::JsonbObjectKeys(()) => TableFunc::JsonbObjectKeys,
             Kind::JsonbArrayElements(stringify) => TableFunc::JsonbArrayElements { stringify },
+            Kind::JsonbPathQuery(()) => TableFunc::JsonbPathQuery,
+            Kind::JsonbPathQueryCompiled(path) => TableFunc::JsonbPathQueryCompiled {
+                path: path.into_rust()?,
+            },
             Kind::RegexpExtract(x) => TableFunc::RegexpExtract(x.into_rust()?),
+            Kind::RegexpMatches(x) => TableFunc::RegexpMatches {
+                regex: x.regex.into_rust_if_some("ProtoRegexpMatches::regex")?,
+                global: x.global,
+            },
+            Kind::RegexpSplitToTable(x) => TableFunc::RegexpSplitToTable(x.into_rust()?),
             Kind::CsvExtract(x) => TableFunc::CsvExtract(x.into_rust()?),
             Kind::GenerateSeriesInt32(()) => TableFunc::GenerateSeriesInt32,
             Kind::GenerateSeriesInt64(()) => TableFunc::GenerateSeriesInt64,
@@ -2279,7 +3352,22 @@ impl TableFunc {
                 temp_storage,
                 *stringify,
             ))),
+            TableFunc::JsonbPathQuery => {
+                let path = strconv::parse_jsonpath(datums[1].unwrap_str())?;
+                let mut args = vec![datums[0]];
+                args.extend(&datums[2..]);
+                Ok(Box::new(jsonb_path_query(&args, &path, temp_storage)?))
+            }
+            TableFunc::JsonbPathQueryCompiled { path } => {
+                Ok(Box::new(jsonb_path_query(datums, path, temp_storage)?))
+            }
             TableFunc::RegexpExtract(a) => Ok(Box::new(regexp_extract(datums[0], a).into_iter())),
+            TableFunc::RegexpMatches { regex, global } => {
+                Ok(Box::new(regexp_matches(datums[0], regex, *global)))
+            }
+            TableFunc::RegexpSplitToTable(regex) => {
+                Ok(Box::new(regexp_split_to_table(datums[0], regex)))
+            }
             TableFunc::CsvExtract(n_cols) => Ok(Box::new(csv_extract(datums[0], *n_cols))),
             TableFunc::GenerateSeriesInt32 => {
                 let res = generate_series(
@@ -2364,6 +3452,11 @@ impl TableFunc {
                 let keys = vec![];
                 (column_types, keys)
             }
+            TableFunc::JsonbPathQuery | TableFunc::JsonbPathQueryCompiled { .. } => {
+                let column_types = vec![ScalarType::Jsonb.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
             TableFunc::RegexpExtract(a) => {
                 let column_types = a
                     .capture_groups_iter()
@@ -2372,6 +3465,17 @@ impl TableFunc {
                 let keys = vec![];
                 (column_types, keys)
             }
+            TableFunc::RegexpMatches { .. } => {
+                let column_types =
+                    vec![ScalarType::Array(Box::new(ScalarType::String)).nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
+            TableFunc::RegexpSplitToTable(_) => {
+                let column_types = vec![ScalarType::String.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
             TableFunc::CsvExtract(n_cols) => {
                 let column_types = iter::repeat(ScalarType::String.nullable(false))
                     .take(*n_cols)
@@ -2438,7 +3542,11 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery => 1,
+            TableFunc::JsonbPathQueryCompiled { .. } => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
+            TableFunc::RegexpMatches { .. } => 1,
+            TableFunc::RegexpSplitToTable(_) => 1,
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
             TableFunc::GenerateSeriesInt64 => 1,
@@ -2457,12 +3565,16 @@ impl TableFunc {
             TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery
+            | TableFunc::JsonbPathQueryCompiled { .. }
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
             | TableFunc::GenerateSeriesTimestamp
             | TableFunc::GenerateSeriesTimestampTz
             | TableFunc::GenerateSubscriptsArray
             | TableFunc::RegexpExtract(_)
+            | TableFunc::RegexpMatches { .. }
+            | TableFunc::RegexpSplitToTable(_)
             | TableFunc::CsvExtract(_)
             | TableFunc::Repeat
             | TableFunc::UnnestArray { .. }
@@ -2479,7 +3591,11 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery => true,
+            TableFunc::JsonbPathQueryCompiled { .. } => true,
             TableFunc::RegexpExtract(_) => true,
+            TableFunc::RegexpMatches { .. } => true,
+            TableFunc::RegexpSplitToTable(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
             TableFunc::GenerateSeriesInt64 => true,
@@ -2500,7 +3616,22 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery => f.write_str("jsonb_path_query"),
+            TableFunc::JsonbPathQueryCompiled { path } => {
+                write!(f, "jsonb_path_query[{}]", path)
+            }
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
+            TableFunc::RegexpMatches { regex, global } => {
+                write!(
+                    f,
+                    "regexp_matches(_, {:?}, global={})",
+                    regex.as_str(),
+                    global
+                )
+            }
+            TableFunc::RegexpSplitToTable(regex) => {
+                write!(f, "regexp_split_to_table(_, {:?})", regex.as_str())
+            }
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
             TableFunc::GenerateSeriesInt64 => f.write_str("generate_series"),
@@ -2517,10 +3648,64 @@ impl fmt::Display for TableFunc {
 
 #[cfg(test)]
 mod tests {
-    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc};
+    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, ReductionTree, TableFunc};
     use mz_proto::protobuf_roundtrip;
+    use mz_repr::{Datum, RowArena};
     use proptest::prelude::*;
 
+    #[test]
+    fn reduction_tree_answers_range_queries() {
+        let temp_storage = RowArena::new();
+        let mut values: Vec<Datum> = (0..1000)
+            .map(|i| {
+                if i % 7 == 0 {
+                    Datum::Null
+                } else {
+                    Datum::Int32(i)
+                }
+            })
+            .collect();
+        let ranges = [
+            (0, 0),
+            (0, 1),
+            (0, 1000),
+            (10, 11),
+            (3, 517),
+            (999, 1000),
+            (512, 999),
+        ];
+
+        for func in [
+            AggregateFunc::SumInt32,
+            AggregateFunc::Count,
+            AggregateFunc::MaxInt32,
+            AggregateFunc::MinInt32,
+        ] {
+            let tree = ReductionTree::new(&func, values.iter().copied(), &temp_storage)
+                .expect("aggregate is combinable");
+            assert_eq!(tree.len(), values.len());
+            for (start, end) in ranges {
+                assert_eq!(
+                    tree.query(start, end, &temp_storage),
+                    func.eval(values[start..end].iter().copied(), &temp_storage),
+                    "{} over {}..{}",
+                    func,
+                    start,
+                    end,
+                );
+            }
+        }
+
+        // Aggregates whose results cannot be combined are not supported.
+        values.truncate(3);
+        assert!(ReductionTree::new(
+            &AggregateFunc::StringAgg { order_by: vec![] },
+            values.iter().copied(),
+            &temp_storage
+        )
+        .is_none());
+    }
+
     proptest! {
        #[test]
         fn aggregate_func_protobuf_roundtrip(expect in any::<AggregateFunc>() ) {
@@ -114,6 +114,26 @@ pub enum MirRelationExpr {
         /// The result of the `Let`, evaluated with `id` bound to `value`.
         body: Box<MirRelationExpr>,
     },
+    /// Introduce a set of mutually recursive dataflows.
+    ///
+    /// Each identifier in `ids` is initially bound to an empty collection of
+    /// the type of its corresponding expression in `values`. The `values` are
+    /// then repeatedly evaluated against the current contents of all bindings,
+    /// and the bindings updated with the results, until no binding changes.
+    /// The result is `body` evaluated against the final contents of the
+    /// bindings.
+    ///
+    /// The runtime memory footprint of this operator is proportional to the
+    /// sizes of the bound collections.
+    LetRec {
+        /// The identifiers to be used in `Get` variants to retrieve each `value`.
+        #[mzreflect(ignore)]
+        ids: Vec<LocalId>,
+        /// The collections to be bound to each `id`, which may refer to any `id`.
+        values: Vec<MirRelationExpr>,
+        /// The result of the `LetRec`, evaluated with all `ids` bound.
+        body: Box<MirRelationExpr>,
+    },
     /// Project out some columns from a dataflow
     ///
     /// The runtime memory footprint of this operator is zero.
@@ -271,12 +291,13 @@ impl MirRelationExpr {
         #[allow(deprecated)]
         self.visit_pre_post_nolimit(
             &mut |e: &MirRelationExpr| -> Option<Vec<&MirRelationExpr>> {
-                if let MirRelationExpr::Let { body, .. } = &e {
-                    // Do not traverse the value sub-graph, since it's not relevant for
-                    // determining the relation type of Let operators.
-                    Some(vec![&*body])
-                } else {
-                    None
+                match &e {
+                    // Do not traverse the value sub-graphs, since they're not relevant for
+                    // determining the relation type of Let and LetRec operators.
+                    MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
+                        Some(vec![&*body])
+                    }
+                    _ => None,
                 }
             },
             &mut |e: &MirRelationExpr| {
@@ -287,6 +308,12 @@ impl MirRelationExpr {
                     type_stack.push(RelationType::empty());
                     type_stack.push(body_typ);
                 }
+                if let MirRelationExpr::LetRec { values, .. } = &e {
+                    let body_typ = type_stack.pop().unwrap();
+                    // Insert dummy relation types for the values, for the same reason.
+                    type_stack.extend(values.iter().map(|_| RelationType::empty()));
+                    type_stack.push(body_typ);
+                }
                 let num_inputs = e.num_inputs();
                 let relation_type =
                     e.typ_with_input_types(&type_stack[type_stack.len() - num_inputs..]);
@@ -410,6 +437,10 @@ impl MirRelationExpr {
                 input_types.next();
                 input_types.next().unwrap().clone()
             }
+            LetRec { .. } => {
+                // skip over the unique keys for the values
+                input_types.last().unwrap().clone()
+            }
             Union { .. } => {
                 let mut result = input_types.next().unwrap().clone();
                 for input_col_types in input_types {
@@ -493,6 +524,10 @@ impl MirRelationExpr {
                 input_keys.next();
                 input_keys.next().unwrap().clone()
             }
+            LetRec { .. } => {
+                // skip over the unique keys for the values
+                input_keys.last().unwrap().clone()
+            }
             Project { outputs, .. } => {
                 let input = input_keys.next().unwrap();
                 input
@@ -800,12 +835,13 @@ impl MirRelationExpr {
         #[allow(deprecated)]
         self.visit_pre_post_nolimit(
             &mut |e: &MirRelationExpr| -> Option<Vec<&MirRelationExpr>> {
-                if let MirRelationExpr::Let { body, .. } = &e {
-                    // Do not traverse the value sub-graph, since it's not relevant for
-                    // determining the arity of Let operators.
-                    Some(vec![&*body])
-                } else {
-                    None
+                match &e {
+                    // Do not traverse the value sub-graphs, since they're not relevant for
+                    // determining the arity of Let and LetRec operators.
+                    MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
+                        Some(vec![&*body])
+                    }
+                    _ => None,
                 }
             },
             &mut |e: &MirRelationExpr| {
@@ -814,6 +850,11 @@ impl MirRelationExpr {
                     arity_stack.push(0);
                     arity_stack.push(body_arity);
                 }
+                if let MirRelationExpr::LetRec { values, .. } = &e {
+                    let body_arity = arity_stack.pop().unwrap();
+                    arity_stack.extend(values.iter().map(|_| 0));
+                    arity_stack.push(body_arity);
+                }
                 let num_inputs = e.num_inputs();
                 let arity = e
                     .arity_with_input_arities(arity_stack[arity_stack.len() - num_inputs..].iter());
@@ -849,6 +890,7 @@ impl MirRelationExpr {
                 input_arities.next();
                 *input_arities.next().unwrap()
             }
+            LetRec { .. } => *input_arities.last().unwrap(),
             Project { outputs, .. } => outputs.len(),
             Map { scalars, .. } => *input_arities.next().unwrap() + scalars.len(),
             FlatMap { func, .. } => {
@@ -1283,6 +1325,18 @@ impl MirRelationExpr {
         None
     }
 
+    /// Indicates if the expression contains a `LetRec` node.
+    pub fn is_recursive(&self) -> bool {
+        let mut is_recursive = false;
+        #[allow(deprecated)]
+        self.visit_pre_nolimit(&mut |e| {
+            if let MirRelationExpr::LetRec { .. } = e {
+                is_recursive = true;
+            }
+        });
+        is_recursive
+    }
+
     /// Pretty-print this MirRelationExpr to a string.
     ///
     /// This method allows an additional `ExprHumanizer` which can annotate
@@ -1488,6 +1542,7 @@ impl MirRelationExpr {
             Constant { .. }
             | Get { .. }
             | Let { .. }
+            | LetRec { .. }
             | Project { .. }
             | TopK { .. }
             | Negate { .. }
@@ -1569,6 +1624,7 @@ impl MirRelationExpr {
         let mut first = None;
         let mut second = None;
         let mut rest = None;
+        let mut last = None;
 
         use MirRelationExpr::*;
         match self {
@@ -1577,6 +1633,10 @@ impl MirRelationExpr {
                 first = Some(&**value);
                 second = Some(&**body);
             }
+            LetRec { values, body, .. } => {
+                rest = Some(values);
+                last = Some(&**body);
+            }
             Project { input, .. }
             | Map { input, .. }
             | FlatMap { input, .. }
@@ -1601,6 +1661,7 @@ impl MirRelationExpr {
             .into_iter()
             .chain(second)
             .chain(rest.into_iter().flatten())
+            .chain(last)
     }
 
     /// Iterates through mutable references to child expressions.
@@ -1608,6 +1669,7 @@ impl MirRelationExpr {
         let mut first = None;
         let mut second = None;
         let mut rest = None;
+        let mut last = None;
 
         use MirRelationExpr::*;
         match self {
@@ -1616,6 +1678,10 @@ impl MirRelationExpr {
                 first = Some(&mut **value);
                 second = Some(&mut **body);
             }
+            LetRec { values, body, .. } => {
+                rest = Some(values);
+                last = Some(&mut **body);
+            }
             Project { input, .. }
             | Map { input, .. }
             | FlatMap { input, .. }
@@ -1640,6 +1706,7 @@ impl MirRelationExpr {
             .into_iter()
             .chain(second)
             .chain(rest.into_iter().flatten())
+            .chain(last)
     }
 
     /// Return a vector of references to the subtrees of this expression
@@ -1815,7 +1882,9 @@ impl AggregateExpr {
             | AggregateFunc::Any
             | AggregateFunc::All
             | AggregateFunc::Dummy => self.expr.is_literal(),
-            AggregateFunc::Count => self.expr.is_literal_null(),
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => {
+                self.expr.is_literal_null()
+            }
             _ => self.expr.is_literal_err(),
         }
     }
@@ -1823,8 +1892,9 @@ impl AggregateExpr {
     /// Extracts unique input from aggregate type
     pub fn on_unique(&self, input_type: &[ColumnType]) -> MirScalarExpr {
         match &self.func {
-            // Count is one if non-null, and zero if null.
-            AggregateFunc::Count => self
+            // Count is one if non-null, and zero if null. So is the estimate of
+            // ApproxCountDistinct.
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => self
                 .expr
                 .clone()
                 .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
@@ -1901,6 +1971,31 @@ impl AggregateExpr {
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)))
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
+            // PercentileCont and PercentileDisc take nested records of (value, fraction), and output
+            // the value unless the fraction is null
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                let record = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let value = record
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let fraction = record.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+                fraction
+                    .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
+                    .if_then_else(
+                        MirScalarExpr::literal_null(self.typ(input_type).scalar_type),
+                        value,
+                    )
+            }
+
+            // Mode takes a single level of records and outputs the value
+            AggregateFunc::Mode { .. } => self
+                .expr
+                .clone()
+                .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
+
             // ListConcat and ArrayConcat take a single level of records and output a list containing exactly 1 element
             AggregateFunc::ListConcat { .. } | AggregateFunc::ArrayConcat { .. } => self
                 .expr
@@ -1947,8 +2042,12 @@ impl AggregateExpr {
                 }
             }
 
-            // DenseRank takes a list of records and outputs a list containing exactly 1 element
-            AggregateFunc::DenseRank { .. } => {
+            // The ranking functions take a list of records and output a list containing exactly
+            // 1 element, which is ranked first in its partition
+            AggregateFunc::Rank { .. }
+            | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. } => {
                 let list = self
                     .expr
                     .clone()
@@ -1964,6 +2063,26 @@ impl AggregateExpr {
                     ],
                 };
 
+                let (column_name, value) = match &self.func {
+                    AggregateFunc::Rank { .. } => (
+                        "?rank?",
+                        MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+                    ),
+                    AggregateFunc::DenseRank { .. } => (
+                        "?dense_rank?",
+                        MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+                    ),
+                    AggregateFunc::PercentRank { .. } => (
+                        "?percent_rank?",
+                        MirScalarExpr::literal_ok(Datum::from(0.0f64), ScalarType::Float64),
+                    ),
+                    AggregateFunc::CumeDist { .. } => (
+                        "?cume_dist?",
+                        MirScalarExpr::literal_ok(Datum::from(1.0f64), ScalarType::Float64),
+                    ),
+                    _ => unreachable!(),
+                };
+
                 MirScalarExpr::CallVariadic {
                     func: VariadicFunc::ListCreate {
                         elem_type: self
@@ -1975,14 +2094,58 @@ impl AggregateExpr {
                     exprs: vec![MirScalarExpr::CallVariadic {
                         func: VariadicFunc::RecordCreate {
                             field_names: vec![
-                                ColumnName::from("?dense_rank?"),
+                                ColumnName::from(column_name),
                                 ColumnName::from("?record?"),
                             ],
                         },
-                        exprs: vec![
-                            MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
-                            record,
-                        ],
+                        exprs: vec![value, record],
+                    }],
+                }
+            }
+
+            // The input type for Ntile is a ((OriginalRow, NumBuckets), OrderByExprs...)
+            AggregateFunc::Ntile { .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let ntile_return_type = return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the number of buckets
+                let num_buckets = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                // A single row always lands in the first bucket, unless the number of buckets is null
+                let value = num_buckets
+                    .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
+                    .if_then_else(
+                        MirScalarExpr::literal_null(ntile_return_type),
+                        MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+                    );
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?ntile?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
                     }],
                 }
             }
@@ -2145,6 +2308,118 @@ impl AggregateExpr {
                 }
             }
 
+            // The input type for NthValue is a ((OriginalRow, (InputValue, N)), OrderByExprs...)
+            AggregateFunc::NthValue { window_frame, .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let nth_value_return_type = return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the encoded args
+                let encoded_args =
+                    tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+                let expr = encoded_args
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let n = encoded_args.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                // If the window frame includes the current (single) row, it is the first and only
+                // row of the frame, so return its value if N is 1 and null otherwise
+                let value = if window_frame.includes_current_row() {
+                    n.call_binary(
+                        MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+                        crate::BinaryFunc::Eq,
+                    )
+                    .if_then_else(expr, MirScalarExpr::literal_null(nth_value_return_type))
+                } else {
+                    MirScalarExpr::literal_null(nth_value_return_type)
+                };
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?nth_value?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
+                    }],
+                }
+            }
+
+            // The input type for a window aggregate is a ((OriginalRow, InputValue), OrderByExprs...)
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                window_frame,
+                ..
+            } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let window_agg_return_type = return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the input value
+                let expr = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                // If the window frame includes the current (single) row, apply the wrapped
+                // aggregation to its value, otherwise return the default of the wrapped aggregation
+                let value = if window_frame.includes_current_row() {
+                    AggregateExpr {
+                        func: (**wrapped_aggregate).clone(),
+                        expr,
+                        distinct: false,
+                    }
+                    .on_unique(input_type)
+                } else {
+                    MirScalarExpr::literal_ok(wrapped_aggregate.default(), window_agg_return_type)
+                };
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?window_agg?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
+                    }],
+                }
+            }
+
             // All other variants should return the argument to the aggregation.
             AggregateFunc::MaxNumeric
             | AggregateFunc::MaxInt16
@@ -32,7 +32,7 @@ use sha1::Sha1;
 use sha2::{Sha224, Sha256, Sha384, Sha512};
 
 use mz_lowertest::MzReflect;
-use mz_ore::cast;
+use mz_ore::cast::{self, CastFrom};
 use mz_ore::fmt::FormatBuffer;
 use mz_ore::option::OptionExt;
 use mz_pgrepr::Type;
@@ -41,10 +41,14 @@ use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::datetime::Timezone;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::jsonpath::{any_jsonpath, JsonPath, JsonPathError};
 use mz_repr::adt::numeric::{self, DecimalLike, Numeric, NumericMaxScale};
+use mz_repr::adt::range::{self, Range, RangeBound};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::chrono::any_naive_datetime;
-use mz_repr::{strconv, ColumnName, ColumnType, Datum, DatumType, Row, RowArena, ScalarType};
+use mz_repr::{
+    strconv, ColumnName, ColumnType, Datum, DatumMap, DatumType, Row, RowArena, ScalarType,
+};
 
 use crate::scalar::func::format::DateTimeFormat;
 use crate::scalar::{
@@ -1860,6 +1864,10 @@ pub enum BinaryFunc {
     ListElementConcat,
     ElementListConcat,
     ListRemove,
+    RangeContainsElem { rev: bool },
+    RangeContainsRange { rev: bool },
+    RangeOverlaps,
+    RangeAdjacent,
     DigestString,
     DigestBytes,
     MzRenderTypmod,
@@ -2158,6 +2166,10 @@ impl BinaryFunc {
             BinaryFunc::ListElementConcat => Ok(eager!(list_element_concat, temp_storage)),
             BinaryFunc::ElementListConcat => Ok(eager!(element_list_concat, temp_storage)),
             BinaryFunc::ListRemove => Ok(eager!(list_remove, temp_storage)),
+            BinaryFunc::RangeContainsElem { rev } => Ok(eager!(range_contains_elem, *rev)),
+            BinaryFunc::RangeContainsRange { rev } => Ok(eager!(range_contains_range, *rev)),
+            BinaryFunc::RangeOverlaps => Ok(eager!(range_overlaps)),
+            BinaryFunc::RangeAdjacent => Ok(eager!(range_adjacent)),
             BinaryFunc::DigestString => eager!(digest_string, temp_storage),
             BinaryFunc::DigestBytes => eager!(digest_bytes, temp_storage),
             BinaryFunc::MzRenderTypmod => eager!(mz_render_typmod, temp_storage),
@@ -2284,6 +2296,11 @@ impl BinaryFunc {
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
 
+            RangeContainsElem { .. }
+            | RangeContainsRange { .. }
+            | RangeOverlaps
+            | RangeAdjacent => ScalarType::Bool.nullable(in_nullable),
+
             MapGetValue => input1_type
                 .scalar_type
                 .unwrap_map_value_type()
@@ -2569,7 +2586,11 @@ impl BinaryFunc {
             | ArrayArrayConcat
             | ListListConcat
             | ListElementConcat
-            | ElementListConcat => true,
+            | ElementListConcat
+            | RangeContainsElem { .. }
+            | RangeContainsRange { .. }
+            | RangeOverlaps
+            | RangeAdjacent => true,
             ToCharTimestamp
             | ToCharTimestampTz
             | DateBinTimestamp
@@ -2816,6 +2837,12 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ListElementConcat => f.write_str("||"),
             BinaryFunc::ElementListConcat => f.write_str("||"),
             BinaryFunc::ListRemove => f.write_str("list_remove"),
+            BinaryFunc::RangeContainsElem { rev: false } => f.write_str("@>"),
+            BinaryFunc::RangeContainsElem { rev: true } => f.write_str("<@"),
+            BinaryFunc::RangeContainsRange { rev: false } => f.write_str("@>"),
+            BinaryFunc::RangeContainsRange { rev: true } => f.write_str("<@"),
+            BinaryFunc::RangeOverlaps => f.write_str("&&"),
+            BinaryFunc::RangeAdjacent => f.write_str("-|-"),
             BinaryFunc::DigestString | BinaryFunc::DigestBytes => f.write_str("digest"),
             BinaryFunc::MzRenderTypmod => f.write_str("mz_render_typmod"),
             BinaryFunc::Encode => f.write_str("encode"),
@@ -3003,6 +3030,10 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::ListElementConcat),
             Just(BinaryFunc::ElementListConcat),
             Just(BinaryFunc::ListRemove),
+            bool::arbitrary().prop_map(|rev| BinaryFunc::RangeContainsElem { rev }),
+            bool::arbitrary().prop_map(|rev| BinaryFunc::RangeContainsRange { rev }),
+            Just(BinaryFunc::RangeOverlaps),
+            Just(BinaryFunc::RangeAdjacent),
             Just(BinaryFunc::DigestString),
             Just(BinaryFunc::DigestBytes),
             Just(BinaryFunc::MzRenderTypmod),
@@ -3178,6 +3209,10 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::ListElementConcat => ListElementConcat(()),
             BinaryFunc::ElementListConcat => ElementListConcat(()),
             BinaryFunc::ListRemove => ListRemove(()),
+            BinaryFunc::RangeContainsElem { rev } => RangeContainsElem(*rev),
+            BinaryFunc::RangeContainsRange { rev } => RangeContainsRange(*rev),
+            BinaryFunc::RangeOverlaps => RangeOverlaps(()),
+            BinaryFunc::RangeAdjacent => RangeAdjacent(()),
             BinaryFunc::DigestString => DigestString(()),
             BinaryFunc::DigestBytes => DigestBytes(()),
             BinaryFunc::MzRenderTypmod => MzRenderTypmod(()),
@@ -3360,6 +3395,10 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 ListElementConcat(()) => Ok(BinaryFunc::ListElementConcat),
                 ElementListConcat(()) => Ok(BinaryFunc::ElementListConcat),
                 ListRemove(()) => Ok(BinaryFunc::ListRemove),
+                RangeContainsElem(rev) => Ok(BinaryFunc::RangeContainsElem { rev }),
+                RangeContainsRange(rev) => Ok(BinaryFunc::RangeContainsRange { rev }),
+                RangeOverlaps(()) => Ok(BinaryFunc::RangeOverlaps),
+                RangeAdjacent(()) => Ok(BinaryFunc::RangeAdjacent),
                 DigestString(()) => Ok(BinaryFunc::DigestString),
                 DigestBytes(()) => Ok(BinaryFunc::DigestBytes),
                 MzRenderTypmod(()) => Ok(BinaryFunc::MzRenderTypmod),
@@ -3689,12 +3728,15 @@ derive_unary!(
     CastTimestampTzToDate,
     CastTimestampTzToTimestamp,
     CastTimestampTzToString,
+    CastTimestampTzToStringInTimezone,
     CastTimestampTzToTime,
     CastPgLegacyCharToString,
     CastPgLegacyCharToInt32,
     CastBytesToString,
     CastStringToJsonb,
     CastJsonbToString,
+    CastStringToJsonPath,
+    CastJsonPathToString,
     CastJsonbOrNullToJsonb,
     CastJsonbToInt16,
     CastJsonbToInt32,
@@ -3728,6 +3770,8 @@ derive_unary!(
     IsLikeMatch,
     IsRegexpMatch,
     RegexpMatch,
+    RegexpReplace,
+    RegexpSplitToArray,
     ExtractInterval,
     ExtractTime,
     ExtractTimestamp,
@@ -3791,7 +3835,16 @@ derive_unary!(
     PgColumnSize,
     MzRowSize,
     MzTypeName,
-    StepMzTimestamp
+    StepMzTimestamp,
+    CastStringToRange,
+    CastRangeToString,
+    RangeLower,
+    RangeUpper,
+    RangeEmpty,
+    RangeLowerInc,
+    RangeUpperInc,
+    RangeLowerInf,
+    RangeUpperInf
 );
 
 impl UnaryFunc {
@@ -4018,12 +4071,15 @@ impl Arbitrary for UnaryFunc {
             CastTimestampTzToDate::arbitrary().prop_map_into(),
             CastTimestampTzToTimestamp::arbitrary().prop_map_into(),
             CastTimestampTzToString::arbitrary().prop_map_into(),
+            CastTimestampTzToStringInTimezone::arbitrary().prop_map_into(),
             CastTimestampTzToTime::arbitrary().prop_map_into(),
             CastPgLegacyCharToString::arbitrary().prop_map_into(),
             CastPgLegacyCharToInt32::arbitrary().prop_map_into(),
             CastBytesToString::arbitrary().prop_map_into(),
             CastStringToJsonb::arbitrary().prop_map_into(),
             CastJsonbToString::arbitrary().prop_map_into(),
+            CastStringToJsonPath::arbitrary().prop_map_into(),
+            CastJsonPathToString::arbitrary().prop_map_into(),
             CastJsonbOrNullToJsonb::arbitrary().prop_map_into(),
             CastJsonbToInt16::arbitrary().prop_map_into(),
             CastJsonbToInt32::arbitrary().prop_map_into(),
@@ -4072,6 +4128,14 @@ impl Arbitrary for UnaryFunc {
                 .prop_map(|matcher| UnaryFunc::IsLikeMatch(IsLikeMatch(matcher))),
             any_regex().prop_map(|regex| UnaryFunc::IsRegexpMatch(IsRegexpMatch(regex))),
             any_regex().prop_map(|regex| UnaryFunc::RegexpMatch(RegexpMatch(regex))),
+            (any_regex(), any::<String>(), any::<usize>()).prop_map(
+                |(regex, replacement, limit)| UnaryFunc::RegexpReplace(RegexpReplace {
+                    regex,
+                    replacement,
+                    limit,
+                })
+            ),
+            any_regex().prop_map(|regex| UnaryFunc::RegexpSplitToArray(RegexpSplitToArray(regex))),
             ExtractInterval::arbitrary().prop_map_into(),
             ExtractTime::arbitrary().prop_map_into(),
             ExtractTimestamp::arbitrary().prop_map_into(),
@@ -4135,6 +4199,20 @@ impl Arbitrary for UnaryFunc {
             PgColumnSize::arbitrary().prop_map_into(),
             MzRowSize::arbitrary().prop_map_into(),
             MzTypeName::arbitrary().prop_map_into(),
+            (any::<ScalarType>(), any::<MirScalarExpr>()).prop_map(|(return_ty, expr)| {
+                UnaryFunc::CastStringToRange(CastStringToRange {
+                    return_ty,
+                    cast_expr: Box::new(expr),
+                })
+            }),
+            CastRangeToString::arbitrary().prop_map_into(),
+            RangeLower::arbitrary().prop_map_into(),
+            RangeUpper::arbitrary().prop_map_into(),
+            RangeEmpty::arbitrary().prop_map_into(),
+            RangeLowerInc::arbitrary().prop_map_into(),
+            RangeUpperInc::arbitrary().prop_map_into(),
+            RangeLowerInf::arbitrary().prop_map_into(),
+            RangeUpperInf::arbitrary().prop_map_into(),
         ]
     }
 }
@@ -4335,12 +4413,17 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastTimestampTzToDate(_) => CastTimestampTzToDate(()),
             UnaryFunc::CastTimestampTzToTimestamp(_) => CastTimestampTzToTimestamp(()),
             UnaryFunc::CastTimestampTzToString(_) => CastTimestampTzToString(()),
+            UnaryFunc::CastTimestampTzToStringInTimezone(func) => {
+                CastTimestampTzToStringInTimezone(func.0.into_proto())
+            }
             UnaryFunc::CastTimestampTzToTime(_) => CastTimestampTzToTime(()),
             UnaryFunc::CastPgLegacyCharToString(_) => CastPgLegacyCharToString(()),
             UnaryFunc::CastPgLegacyCharToInt32(_) => CastPgLegacyCharToInt32(()),
             UnaryFunc::CastBytesToString(_) => CastBytesToString(()),
             UnaryFunc::CastStringToJsonb(_) => CastStringToJsonb(()),
             UnaryFunc::CastJsonbToString(_) => CastJsonbToString(()),
+            UnaryFunc::CastStringToJsonPath(_) => CastStringToJsonPath(()),
+            UnaryFunc::CastJsonPathToString(_) => CastJsonPathToString(()),
             UnaryFunc::CastJsonbOrNullToJsonb(_) => CastJsonbOrNullToJsonb(()),
             UnaryFunc::CastJsonbToInt16(_) => CastJsonbToInt16(()),
             UnaryFunc::CastJsonbToInt32(_) => CastJsonbToInt32(()),
@@ -4384,6 +4467,12 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::IsLikeMatch(pattern) => IsLikeMatch(pattern.0.into_proto()),
             UnaryFunc::IsRegexpMatch(regex) => IsRegexpMatch(regex.0.into_proto()),
             UnaryFunc::RegexpMatch(regex) => RegexpMatch(regex.0.into_proto()),
+            UnaryFunc::RegexpReplace(func) => RegexpReplace(ProtoRegexpReplace {
+                regex: Some(func.regex.into_proto()),
+                replacement: func.replacement.clone(),
+                limit: func.limit.into_proto(),
+            }),
+            UnaryFunc::RegexpSplitToArray(regex) => RegexpSplitToArray(regex.0.into_proto()),
             UnaryFunc::ExtractInterval(func) => ExtractInterval(func.0.into_proto()),
             UnaryFunc::ExtractTime(func) => ExtractTime(func.0.into_proto()),
             UnaryFunc::ExtractTimestamp(func) => ExtractTimestamp(func.0.into_proto()),
@@ -4460,6 +4549,20 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastTimestampToMzTimestamp(_) => CastTimestampToMzTimestamp(()),
             UnaryFunc::CastTimestampTzToMzTimestamp(_) => CastTimestampTzToMzTimestamp(()),
             UnaryFunc::StepMzTimestamp(_) => StepMzTimestamp(()),
+            UnaryFunc::CastStringToRange(inner) => {
+                CastStringToRange(Box::new(ProtoCastToVariableType {
+                    return_ty: Some(inner.return_ty.into_proto()),
+                    cast_expr: Some(inner.cast_expr.into_proto()),
+                }))
+            }
+            UnaryFunc::CastRangeToString(func) => CastRangeToString(func.ty.into_proto()),
+            UnaryFunc::RangeLower(_) => RangeLower(()),
+            UnaryFunc::RangeUpper(_) => RangeUpper(()),
+            UnaryFunc::RangeEmpty(_) => RangeEmpty(()),
+            UnaryFunc::RangeLowerInc(_) => RangeLowerInc(()),
+            UnaryFunc::RangeUpperInc(_) => RangeUpperInc(()),
+            UnaryFunc::RangeLowerInf(_) => RangeLowerInf(()),
+            UnaryFunc::RangeUpperInf(_) => RangeUpperInf(()),
         };
         ProtoUnaryFunc { kind: Some(kind) }
     }
@@ -4689,12 +4792,17 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 CastTimestampTzToDate(()) => Ok(impls::CastTimestampTzToDate.into()),
                 CastTimestampTzToTimestamp(()) => Ok(impls::CastTimestampTzToTimestamp.into()),
                 CastTimestampTzToString(()) => Ok(impls::CastTimestampTzToString.into()),
+                CastTimestampTzToStringInTimezone(tz) => {
+                    Ok(impls::CastTimestampTzToStringInTimezone(tz.into_rust()?).into())
+                }
                 CastTimestampTzToTime(()) => Ok(impls::CastTimestampTzToTime.into()),
                 CastPgLegacyCharToString(()) => Ok(impls::CastPgLegacyCharToString.into()),
                 CastPgLegacyCharToInt32(()) => Ok(impls::CastPgLegacyCharToInt32.into()),
                 CastBytesToString(()) => Ok(impls::CastBytesToString.into()),
                 CastStringToJsonb(()) => Ok(impls::CastStringToJsonb.into()),
                 CastJsonbToString(()) => Ok(impls::CastJsonbToString.into()),
+                CastStringToJsonPath(()) => Ok(impls::CastStringToJsonPath.into()),
+                CastJsonPathToString(()) => Ok(impls::CastJsonPathToString.into()),
                 CastJsonbOrNullToJsonb(()) => Ok(impls::CastJsonbOrNullToJsonb.into()),
                 CastJsonbToInt16(()) => Ok(impls::CastJsonbToInt16.into()),
                 CastJsonbToInt32(()) => Ok(impls::CastJsonbToInt32.into()),
@@ -4756,6 +4864,15 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 IsLikeMatch(pattern) => Ok(impls::IsLikeMatch(pattern.into_rust()?).into()),
                 IsRegexpMatch(regex) => Ok(impls::IsRegexpMatch(regex.into_rust()?).into()),
                 RegexpMatch(regex) => Ok(impls::RegexpMatch(regex.into_rust()?).into()),
+                RegexpReplace(func) => Ok(impls::RegexpReplace {
+                    regex: func.regex.into_rust_if_some("ProtoRegexpReplace::regex")?,
+                    replacement: func.replacement,
+                    limit: func.limit.into_rust()?,
+                }
+                .into()),
+                RegexpSplitToArray(regex) => {
+                    Ok(impls::RegexpSplitToArray(regex.into_rust()?).into())
+                }
                 ExtractInterval(units) => Ok(impls::ExtractInterval(units.into_rust()?).into()),
                 ExtractTime(units) => Ok(impls::ExtractTime(units.into_rust()?).into()),
                 ExtractTimestamp(units) => Ok(impls::ExtractTimestamp(units.into_rust()?).into()),
@@ -4846,6 +4963,26 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 CastTimestampToMzTimestamp(()) => Ok(impls::CastTimestampToMzTimestamp.into()),
                 CastTimestampTzToMzTimestamp(()) => Ok(impls::CastTimestampTzToMzTimestamp.into()),
                 StepMzTimestamp(()) => Ok(impls::StepMzTimestamp.into()),
+                CastStringToRange(inner) => Ok(impls::CastStringToRange {
+                    return_ty: inner
+                        .return_ty
+                        .into_rust_if_some("ProtoCastStringToRange::return_ty")?,
+                    cast_expr: inner
+                        .cast_expr
+                        .into_rust_if_some("ProtoCastStringToRange::cast_expr")?,
+                }
+                .into()),
+                CastRangeToString(ty) => Ok(impls::CastRangeToString {
+                    ty: ty.into_rust()?,
+                }
+                .into()),
+                RangeLower(()) => Ok(impls::RangeLower.into()),
+                RangeUpper(()) => Ok(impls::RangeUpper.into()),
+                RangeEmpty(()) => Ok(impls::RangeEmpty.into()),
+                RangeLowerInc(()) => Ok(impls::RangeLowerInc.into()),
+                RangeUpperInc(()) => Ok(impls::RangeUpperInc.into()),
+                RangeLowerInf(()) => Ok(impls::RangeLowerInf.into()),
+                RangeUpperInf(()) => Ok(impls::RangeUpperInf.into()),
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoUnaryFunc::kind"))
@@ -5139,6 +5276,84 @@ fn regexp_match_static<'a>(
     Ok(temp_storage.push_unary_row(row))
 }
 
+fn regexp_replace_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let source = datums[0].unwrap_str();
+    let needle = datums[1].unwrap_str();
+    let replacement = datums[2].unwrap_str();
+    let flags = match datums.get(3) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let (flags, global) = split_global_regex_flag(flags);
+    let needle = build_regex(needle, &flags)?;
+    let replacement = build_regex_replacement(replacement);
+    let limit = if global { 0 } else { 1 };
+    let replaced = needle.replacen(source, limit, replacement.as_str());
+    Ok(Datum::String(
+        temp_storage.push_string(replaced.into_owned()),
+    ))
+}
+
+fn regexp_split_to_array_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let haystack = datums[0].unwrap_str();
+    let needle = datums[1].unwrap_str();
+    let flags = match datums.get(2) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let needle = build_split_regex(needle, flags)?;
+    regexp_split_to_array_static(haystack, temp_storage, &needle)
+}
+
+pub fn regexp_split_to_array_static<'a>(
+    haystack: &'a str,
+    temp_storage: &'a RowArena,
+    needle: &regex::Regex,
+) -> Result<Datum<'a>, EvalError> {
+    let parts = regexp_split(haystack, needle);
+    temp_storage
+        .try_make_datum(|packer| {
+            packer.push_array(
+                &[ArrayDimension {
+                    lower_bound: 1,
+                    length: parts.len(),
+                }],
+                parts.into_iter().map(Datum::String),
+            )
+        })
+        .err_into()
+}
+
+/// Splits `haystack` at each match of `needle`.
+///
+/// Like PostgreSQL, this ignores empty matches at the beginning or end of
+/// `haystack` and empty matches that immediately follow the previous match.
+pub fn regexp_split<'a>(haystack: &'a str, needle: &regex::Regex) -> Vec<&'a str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    for m in needle.find_iter(haystack) {
+        if m.start() == m.end()
+            && (m.start() == 0 || m.start() == haystack.len() || m.start() == start)
+        {
+            continue;
+        }
+        parts.push(&haystack[start..m.start()]);
+        start = m.end();
+    }
+    parts.push(&haystack[start..]);
+    parts
+}
+
+/// Builds a regex from a pattern and a string of PostgreSQL regex flags.
+///
+/// The `g` flag is not accepted here, as its meaning depends on the function
+/// in question; see [`split_global_regex_flag`].
 pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError> {
     let mut regex = RegexBuilder::new(needle);
     for f in flags.chars() {
@@ -5149,12 +5364,86 @@ pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError>
             'c' => {
                 regex.case_insensitive(false);
             }
+            // Newline-sensitive matching: `.` does not match newlines, and `^`
+            // and `$` match at line boundaries. `m` is a historical synonym.
+            'n' | 'm' => {
+                regex.dot_matches_new_line(false).multi_line(true);
+            }
+            's' => {
+                regex.dot_matches_new_line(true).multi_line(false);
+            }
+            // Partial newline-sensitive matching.
+            'p' => {
+                regex.dot_matches_new_line(false).multi_line(false);
+            }
+            // Inverse partial newline-sensitive matching.
+            'w' => {
+                regex.dot_matches_new_line(true).multi_line(true);
+            }
+            'x' => {
+                regex.ignore_whitespace(true);
+            }
+            't' => {
+                regex.ignore_whitespace(false);
+            }
             _ => return Err(EvalError::InvalidRegexFlag(f)),
         }
     }
     Ok(regex.build()?)
 }
 
+/// Builds the regex for `regexp_split_to_array`, which, like PostgreSQL,
+/// rejects the `g` flag rather than treating it as an unknown flag.
+pub fn build_split_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError> {
+    if flags.contains('g') {
+        return Err(EvalError::InvalidParameterValue(
+            "regexp_split_to_array() does not support the \"global\" option".into(),
+        ));
+    }
+    build_regex(needle, flags)
+}
+
+/// Removes the `g` flag from a string of PostgreSQL regex flags, returning the
+/// remaining flags and whether `g` was present.
+///
+/// The `g` flag requests that every match, rather than only the first, be
+/// processed, and so is only meaningful to functions like `regexp_replace` and
+/// `regexp_matches`.
+pub fn split_global_regex_flag(flags: &str) -> (String, bool) {
+    (flags.replace('g', ""), flags.contains('g'))
+}
+
+/// Translates a PostgreSQL regex replacement string into the syntax expected
+/// by [`regex::Regex::replacen`].
+///
+/// PostgreSQL refers to the `n`th capture group as `\n` and to the entire
+/// match as `\&`, while `\\` produces a literal backslash.
+pub fn build_regex_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&d @ '1'..='9')) => {
+                out.push_str("${");
+                out.push(d);
+                out.push('}');
+                chars.next();
+            }
+            ('\\', Some(&'&')) => {
+                out.push_str("${0}");
+                chars.next();
+            }
+            ('\\', Some(&'\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            ('$', _) => out.push_str("$$"),
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
 pub fn hmac_string<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
@@ -5264,6 +5553,268 @@ fn jsonb_build_object<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> D
     }
 }
 
+/// Evaluates `path` against the jsonb value in `datums[0]`, as the
+/// `jsonb_path_*` functions do.
+///
+/// The optional second and third arguments are the `vars` object and the
+/// `silent` flag. Returns `None` if evaluation failed with an error that
+/// `silent` suppresses.
+pub(crate) fn jsonb_path_eval<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<Option<Vec<Datum<'a>>>, EvalError> {
+    let vars = match datums.get(1) {
+        Some(d) => *d,
+        None => Datum::Map(DatumMap::empty()),
+    };
+    let silent = match datums.get(2) {
+        Some(d) => d.unwrap_bool(),
+        None => false,
+    };
+    match path.evaluate(datums[0], vars, temp_storage) {
+        Ok(items) => Ok(Some(items)),
+        Err(e) if silent && e.is_silenceable() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Calls the `jsonb_path_*` function `f` with the jsonpath parsed from
+/// `datums[1]`, for paths that were not known when the expression was
+/// reduced.
+fn jsonb_path_dynamic<'a, F>(
+    datums: &[Datum<'a>],
+    f: F,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError>
+where
+    F: FnOnce(&[Datum<'a>], &JsonPath, &'a RowArena) -> Result<Datum<'a>, EvalError>,
+{
+    let path = strconv::parse_jsonpath(datums[1].unwrap_str())?;
+    let mut args = vec![datums[0]];
+    args.extend(&datums[2..]);
+    f(&args, &path, temp_storage)
+}
+
+fn jsonb_path_exists<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    Ok(match jsonb_path_eval(datums, path, temp_storage)? {
+        Some(items) => Datum::from(!items.is_empty()),
+        None => Datum::Null,
+    })
+}
+
+fn jsonb_path_match<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let silent = match datums.get(2) {
+        Some(d) => d.unwrap_bool(),
+        None => false,
+    };
+    match jsonb_path_eval(datums, path, temp_storage)?.as_deref() {
+        Some([Datum::True]) => Ok(Datum::True),
+        Some([Datum::False]) => Ok(Datum::False),
+        Some([Datum::JsonNull]) | None => Ok(Datum::Null),
+        Some(_) if silent => Ok(Datum::Null),
+        Some(_) => Err(JsonPathError::SingleBooleanExpected.into()),
+    }
+}
+
+fn jsonb_path_query_array<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_eval(datums, path, temp_storage)?.unwrap_or_default();
+    Ok(temp_storage.make_datum(|packer| packer.push_list(items)))
+}
+
+fn jsonb_path_query_first<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    Ok(jsonb_path_eval(datums, path, temp_storage)?
+        .and_then(|items| items.into_iter().next())
+        .unwrap_or(Datum::Null))
+}
+
+/// How [`jsonb_set_path`] places the new value at the end of the path.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JsonbSetMode {
+    /// Replace an existing value, if there is one.
+    Replace,
+    /// Replace an existing value, or add the value if there is none.
+    Create,
+    /// Insert the value before an existing array element.
+    InsertBefore,
+    /// Insert the value after an existing array element.
+    InsertAfter,
+}
+
+impl JsonbSetMode {
+    fn creates(&self) -> bool {
+        !matches!(self, JsonbSetMode::Replace)
+    }
+
+    fn inserts(&self) -> bool {
+        matches!(self, JsonbSetMode::InsertBefore | JsonbSetMode::InsertAfter)
+    }
+}
+
+fn jsonb_set<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let create_if_missing = match datums.get(3) {
+        Some(d) => d.unwrap_bool(),
+        None => true,
+    };
+    let mode = if create_if_missing {
+        JsonbSetMode::Create
+    } else {
+        JsonbSetMode::Replace
+    };
+    jsonb_set_inner(datums[0], datums[1], datums[2], mode, temp_storage)
+}
+
+fn jsonb_insert<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let insert_after = match datums.get(3) {
+        Some(d) => d.unwrap_bool(),
+        None => false,
+    };
+    let mode = if insert_after {
+        JsonbSetMode::InsertAfter
+    } else {
+        JsonbSetMode::InsertBefore
+    };
+    jsonb_set_inner(datums[0], datums[1], datums[2], mode, temp_storage)
+}
+
+fn jsonb_set_inner<'a>(
+    target: Datum<'a>,
+    path: Datum<'a>,
+    new_value: Datum<'a>,
+    mode: JsonbSetMode,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if !matches!(target, Datum::List(_) | Datum::Map(_)) {
+        return Err(EvalError::InvalidParameterValue(
+            "cannot set path in scalar".into(),
+        ));
+    }
+    let path = path.unwrap_array().elements().iter().collect::<Vec<_>>();
+    if path.is_empty() {
+        return Ok(target);
+    }
+    jsonb_set_path(target, &path, 0, new_value, mode, temp_storage)
+}
+
+/// Places `new_value` at the end of `path[level..]` within `target`, following
+/// PostgreSQL's `jsonb_set` and `jsonb_insert`.
+///
+/// Path elements that do not exist are not created, except for the last, and
+/// path elements that address scalars leave `target` unchanged.
+fn jsonb_set_path<'a>(
+    target: Datum<'a>,
+    path: &[Datum<'a>],
+    level: usize,
+    new_value: Datum<'a>,
+    mode: JsonbSetMode,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let key = match path[level] {
+        Datum::Null => {
+            return Err(EvalError::InvalidParameterValue(format!(
+                "path element at position {} is null",
+                level + 1
+            )))
+        }
+        key => key.unwrap_str(),
+    };
+    let is_last = level == path.len() - 1;
+    match target {
+        Datum::Map(map) => {
+            let mut pairs = Vec::new();
+            let mut found = false;
+            for (k, v) in map.iter() {
+                if k != key {
+                    pairs.push((k, v));
+                    continue;
+                }
+                found = true;
+                if !is_last {
+                    let v = jsonb_set_path(v, path, level + 1, new_value, mode, temp_storage)?;
+                    pairs.push((k, v));
+                } else if mode.inserts() {
+                    return Err(EvalError::InvalidParameterValue(
+                        "cannot replace existing key".into(),
+                    ));
+                } else {
+                    pairs.push((k, new_value));
+                }
+            }
+            if !found && is_last && mode.creates() {
+                pairs.push((key, new_value));
+                pairs.sort_by(|(k1, _v1), (k2, _v2)| k1.cmp(k2));
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_dict(pairs)))
+        }
+        Datum::List(list) => {
+            let index = strconv::parse_int32(key).map_err(|_| {
+                EvalError::InvalidParameterValue(format!(
+                    "path element at position {} is not an integer: \"{}\"",
+                    level + 1,
+                    key
+                ))
+            })?;
+            let elems = list.iter().collect::<Vec<_>>();
+            // Negative indexes count back from the end of the array. Indexes
+            // that remain negative address the position before the start.
+            let index = if index < 0 {
+                elems
+                    .len()
+                    .checked_sub(usize::cast_from(index.unsigned_abs()))
+            } else {
+                Some(usize::cast_from(index.unsigned_abs()))
+            };
+            let mut new_elems = Vec::with_capacity(elems.len() + 1);
+            let mut done = false;
+            if is_last && mode.creates() && (index.is_none() || elems.is_empty()) {
+                new_elems.push(new_value);
+                done = true;
+            }
+            for (i, elem) in elems.into_iter().enumerate() {
+                if index != Some(i) {
+                    new_elems.push(elem);
+                } else if !is_last {
+                    let elem =
+                        jsonb_set_path(elem, path, level + 1, new_value, mode, temp_storage)?;
+                    new_elems.push(elem);
+                } else {
+                    match mode {
+                        JsonbSetMode::Replace | JsonbSetMode::Create => new_elems.push(new_value),
+                        JsonbSetMode::InsertBefore => new_elems.extend([new_value, elem]),
+                        JsonbSetMode::InsertAfter => new_elems.extend([elem, new_value]),
+                    }
+                    done = true;
+                }
+            }
+            // Indexes past the end of the array append to it.
+            if is_last && mode.creates() && !done {
+                new_elems.push(new_value);
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_list(new_elems)))
+        }
+        _ => Ok(target),
+    }
+}
+
 /// Constructs a new multidimensional array out of an arbitrary number of
 /// lower-dimensional arrays.
 ///
@@ -5404,6 +5955,7 @@ where
             Ok(strconv::Nestable::MayNeedEscaping)
         }
         Jsonb => Ok(strconv::format_jsonb(buf, JsonbRef::from_datum(d))),
+        JsonPath => Ok(strconv::format_jsonpath(buf, d.unwrap_str())),
         Uuid => Ok(strconv::format_uuid(buf, d.unwrap_uuid())),
         Record { fields, .. } => {
             let mut fields = fields.iter();
@@ -5446,9 +5998,45 @@ where
             stringify_datum(buf.nonnull_buffer(), d, &ScalarType::Int16)
         }),
         MzTimestamp { .. } => Ok(strconv::format_mz_timestamp(buf, d.unwrap_mz_timestamp())),
+        ScalarType::Range { element_type } => {
+            strconv::format_range(buf, &d.unwrap_range(), |buf, d| {
+                stringify_datum(buf.nonnull_buffer(), d.datum(), element_type)
+            })
+        }
     }
 }
 
+fn range_create<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let flags = match datums.get(2) {
+        None => "[)",
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "range constructor flags argument must not be null".into(),
+            ))
+        }
+        Some(flags) => flags.unwrap_str(),
+    };
+    let (lower_inclusive, upper_inclusive) = range::parse_range_bound_flags(flags)?;
+    // NULL bounds are infinite.
+    let bound = |d: Datum<'a>| if d.is_null() { None } else { Some(d) };
+    let range = Range::new(Some((
+        RangeBound {
+            inclusive: lower_inclusive,
+            bound: bound(datums[0]),
+        },
+        RangeBound {
+            inclusive: upper_inclusive,
+            bound: bound(datums[1]),
+        },
+    )));
+    temp_storage
+        .try_make_datum(|packer| packer.push_range(range))
+        .err_into()
+}
+
 fn array_index<'a>(datums: &[Datum<'a>], offset: usize) -> Datum<'a> {
     let array = datums[0].unwrap_array();
     let dims = array.dims();
@@ -5807,6 +6395,25 @@ fn array_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(array.elements().iter().any(|e| e == a))
 }
 
+fn range_contains_elem<'a>(a: Datum<'a>, b: Datum<'a>, rev: bool) -> Datum<'a> {
+    let (range, elem) = if rev { (b, a) } else { (a, b) };
+    let range = range.unwrap_range().map(|bound| bound.datum());
+    Datum::from(range.contains_elem(&elem))
+}
+
+fn range_contains_range<'a>(a: Datum<'a>, b: Datum<'a>, rev: bool) -> Datum<'a> {
+    let (outer, inner) = if rev { (b, a) } else { (a, b) };
+    Datum::from(outer.unwrap_range().contains_range(&inner.unwrap_range()))
+}
+
+fn range_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().overlaps(&b.unwrap_range()))
+}
+
+fn range_adjacent<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().adjacent(&b.unwrap_range()))
+}
+
 fn array_array_concat<'a>(
     a: Datum<'a>,
     b: Datum<'a>,
@@ -6047,10 +6654,38 @@ pub enum VariadicFunc {
     RecordCreate {
         field_names: Vec<ColumnName>,
     },
+    RangeCreate {
+        // We need to know the element type to type the range when both bounds
+        // are NULL.
+        elem_type: ScalarType,
+    },
     ListIndex,
     ListSliceLinear,
     SplitPart,
     RegexpMatch,
+    RegexpReplace,
+    RegexpSplitToArray,
+    JsonbPathExists,
+    JsonbPathMatch,
+    JsonbPathQueryArray,
+    JsonbPathQueryFirst,
+    /// Like [`VariadicFunc::JsonbPathExists`], but with a literal path that
+    /// was parsed when the expression was reduced, and that is omitted from
+    /// the arguments.
+    JsonbPathExistsCompiled {
+        path: JsonPath,
+    },
+    JsonbPathMatchCompiled {
+        path: JsonPath,
+    },
+    JsonbPathQueryArrayCompiled {
+        path: JsonPath,
+    },
+    JsonbPathQueryFirstCompiled {
+        path: JsonPath,
+    },
+    JsonbSet,
+    JsonbInsert,
     HmacString,
     HmacBytes,
     ErrorIfNull,
@@ -6102,10 +6737,41 @@ impl VariadicFunc {
             VariadicFunc::ListCreate { .. } | VariadicFunc::RecordCreate { .. } => {
                 Ok(eager!(list_create, temp_storage))
             }
+            VariadicFunc::RangeCreate { .. } => eager!(range_create, temp_storage),
             VariadicFunc::ListIndex => Ok(eager!(list_index)),
             VariadicFunc::ListSliceLinear => Ok(eager!(list_slice_linear, temp_storage)),
             VariadicFunc::SplitPart => eager!(split_part),
             VariadicFunc::RegexpMatch => eager!(regexp_match_dynamic, temp_storage),
+            VariadicFunc::RegexpReplace => eager!(regexp_replace_dynamic, temp_storage),
+            VariadicFunc::RegexpSplitToArray => {
+                eager!(regexp_split_to_array_dynamic, temp_storage)
+            }
+            VariadicFunc::JsonbPathExists => {
+                eager!(jsonb_path_dynamic, jsonb_path_exists, temp_storage)
+            }
+            VariadicFunc::JsonbPathMatch => {
+                eager!(jsonb_path_dynamic, jsonb_path_match, temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryArray => {
+                eager!(jsonb_path_dynamic, jsonb_path_query_array, temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryFirst => {
+                eager!(jsonb_path_dynamic, jsonb_path_query_first, temp_storage)
+            }
+            VariadicFunc::JsonbPathExistsCompiled { path } => {
+                eager!(jsonb_path_exists, path, temp_storage)
+            }
+            VariadicFunc::JsonbPathMatchCompiled { path } => {
+                eager!(jsonb_path_match, path, temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryArrayCompiled { path } => {
+                eager!(jsonb_path_query_array, path, temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryFirstCompiled { path } => {
+                eager!(jsonb_path_query_first, path, temp_storage)
+            }
+            VariadicFunc::JsonbSet => eager!(jsonb_set, temp_storage),
+            VariadicFunc::JsonbInsert => eager!(jsonb_insert, temp_storage),
             VariadicFunc::HmacString => eager!(hmac_string, temp_storage),
             VariadicFunc::HmacBytes => eager!(hmac_bytes, temp_storage),
             VariadicFunc::ErrorIfNull => error_if_null(datums, temp_storage, exprs),
@@ -6144,10 +6810,23 @@ impl VariadicFunc {
             | VariadicFunc::ArrayIndex { offset: _ }
             | VariadicFunc::ListCreate { elem_type: _ }
             | VariadicFunc::RecordCreate { field_names: _ }
+            | VariadicFunc::RangeCreate { elem_type: _ }
             | VariadicFunc::ListIndex
             | VariadicFunc::ListSliceLinear
             | VariadicFunc::SplitPart
             | VariadicFunc::RegexpMatch
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::RegexpSplitToArray
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst
+            | VariadicFunc::JsonbPathExistsCompiled { path: _ }
+            | VariadicFunc::JsonbPathMatchCompiled { path: _ }
+            | VariadicFunc::JsonbPathQueryArrayCompiled { path: _ }
+            | VariadicFunc::JsonbPathQueryFirstCompiled { path: _ }
+            | VariadicFunc::JsonbSet
+            | VariadicFunc::JsonbInsert
             | VariadicFunc::HmacString
             | VariadicFunc::HmacBytes
             | VariadicFunc::ErrorIfNull
@@ -6214,8 +6893,27 @@ impl VariadicFunc {
                 custom_id: None,
             }
             .nullable(false),
+            RangeCreate { elem_type } => ScalarType::Range {
+                element_type: Box::new(elem_type.clone()),
+            }
+            .nullable(false),
             SplitPart => ScalarType::String.nullable(in_nullable),
             RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpReplace => ScalarType::String.nullable(in_nullable),
+            RegexpSplitToArray => {
+                ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable)
+            }
+            JsonbPathExists
+            | JsonbPathMatch
+            | JsonbPathExistsCompiled { .. }
+            | JsonbPathMatchCompiled { .. } => ScalarType::Bool.nullable(true),
+            JsonbPathQueryArray | JsonbPathQueryArrayCompiled { .. } => {
+                ScalarType::Jsonb.nullable(in_nullable)
+            }
+            JsonbPathQueryFirst | JsonbPathQueryFirstCompiled { .. } => {
+                ScalarType::Jsonb.nullable(true)
+            }
+            JsonbSet | JsonbInsert => ScalarType::Jsonb.nullable(in_nullable),
             HmacString | HmacBytes => ScalarType::Bytes.nullable(true),
             ErrorIfNull => input_types[0].scalar_type.clone().nullable(false),
             DateBinTimestamp => ScalarType::Timestamp.nullable(true),
@@ -6240,6 +6938,7 @@ impl VariadicFunc {
                 | VariadicFunc::JsonbBuildObject
                 | VariadicFunc::ListCreate { .. }
                 | VariadicFunc::RecordCreate { .. }
+                | VariadicFunc::RangeCreate { .. }
                 | VariadicFunc::ArrayCreate { .. }
                 | VariadicFunc::ArrayToString { .. }
                 | VariadicFunc::ErrorIfNull
@@ -6260,6 +6959,28 @@ impl VariadicFunc {
         )
     }
 
+    /// Reports whether this is a `jsonb_path_*` function whose path, the
+    /// second argument, is parsed for every row.
+    pub fn is_jsonb_path(&self) -> bool {
+        use VariadicFunc::*;
+        matches!(
+            self,
+            JsonbPathExists | JsonbPathMatch | JsonbPathQueryArray | JsonbPathQueryFirst
+        )
+    }
+
+    /// Returns the variant of this `jsonb_path_*` function that evaluates the
+    /// already parsed `path`, in place of its second argument.
+    pub fn compile_jsonb_path(&self, path: JsonPath) -> Self {
+        match self {
+            VariadicFunc::JsonbPathExists => VariadicFunc::JsonbPathExistsCompiled { path },
+            VariadicFunc::JsonbPathMatch => VariadicFunc::JsonbPathMatchCompiled { path },
+            VariadicFunc::JsonbPathQueryArray => VariadicFunc::JsonbPathQueryArrayCompiled { path },
+            VariadicFunc::JsonbPathQueryFirst => VariadicFunc::JsonbPathQueryFirstCompiled { path },
+            _ => unreachable!(),
+        }
+    }
+
     pub fn switch_and_or(&self) -> Self {
         match self {
             VariadicFunc::And => VariadicFunc::Or,
@@ -6320,10 +7041,31 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::ArrayIndex { .. } => f.write_str("array_index"),
             VariadicFunc::ListCreate { .. } => f.write_str("list_create"),
             VariadicFunc::RecordCreate { .. } => f.write_str("record_create"),
+            VariadicFunc::RangeCreate { .. } => f.write_str("range_create"),
             VariadicFunc::ListIndex => f.write_str("list_index"),
             VariadicFunc::ListSliceLinear => f.write_str("list_slice_linear"),
             VariadicFunc::SplitPart => f.write_str("split_string"),
             VariadicFunc::RegexpMatch => f.write_str("regexp_match"),
+            VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
+            VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
+            VariadicFunc::JsonbPathExists => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathMatch => f.write_str("jsonb_path_match"),
+            VariadicFunc::JsonbPathQueryArray => f.write_str("jsonb_path_query_array"),
+            VariadicFunc::JsonbPathQueryFirst => f.write_str("jsonb_path_query_first"),
+            VariadicFunc::JsonbPathExistsCompiled { path } => {
+                write!(f, "jsonb_path_exists[{}]", path)
+            }
+            VariadicFunc::JsonbPathMatchCompiled { path } => {
+                write!(f, "jsonb_path_match[{}]", path)
+            }
+            VariadicFunc::JsonbPathQueryArrayCompiled { path } => {
+                write!(f, "jsonb_path_query_array[{}]", path)
+            }
+            VariadicFunc::JsonbPathQueryFirstCompiled { path } => {
+                write!(f, "jsonb_path_query_first[{}]", path)
+            }
+            VariadicFunc::JsonbSet => f.write_str("jsonb_set"),
+            VariadicFunc::JsonbInsert => f.write_str("jsonb_insert"),
             VariadicFunc::HmacString | VariadicFunc::HmacBytes => f.write_str("hmac"),
             VariadicFunc::ErrorIfNull => f.write_str("error_if_null"),
             VariadicFunc::DateBinTimestamp => f.write_str("timestamp_bin"),
@@ -6363,10 +7105,23 @@ impl Arbitrary for VariadicFunc {
             ScalarType::arbitrary().prop_map(|elem_type| VariadicFunc::ListCreate { elem_type }),
             Vec::<ColumnName>::arbitrary()
                 .prop_map(|field_names| VariadicFunc::RecordCreate { field_names }),
+            ScalarType::arbitrary().prop_map(|elem_type| VariadicFunc::RangeCreate { elem_type }),
             Just(VariadicFunc::ListIndex),
             Just(VariadicFunc::ListSliceLinear),
             Just(VariadicFunc::SplitPart),
             Just(VariadicFunc::RegexpMatch),
+            Just(VariadicFunc::RegexpReplace),
+            Just(VariadicFunc::RegexpSplitToArray),
+            Just(VariadicFunc::JsonbPathExists),
+            Just(VariadicFunc::JsonbPathMatch),
+            Just(VariadicFunc::JsonbPathQueryArray),
+            Just(VariadicFunc::JsonbPathQueryFirst),
+            any_jsonpath().prop_map(|path| VariadicFunc::JsonbPathExistsCompiled { path }),
+            any_jsonpath().prop_map(|path| VariadicFunc::JsonbPathMatchCompiled { path }),
+            any_jsonpath().prop_map(|path| VariadicFunc::JsonbPathQueryArrayCompiled { path }),
+            any_jsonpath().prop_map(|path| VariadicFunc::JsonbPathQueryFirstCompiled { path }),
+            Just(VariadicFunc::JsonbSet),
+            Just(VariadicFunc::JsonbInsert),
             Just(VariadicFunc::HmacString),
             Just(VariadicFunc::HmacBytes),
             Just(VariadicFunc::ErrorIfNull),
@@ -6400,10 +7155,31 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::RecordCreate { field_names } => RecordCreate(ProtoRecordCreate {
                 field_names: field_names.into_proto(),
             }),
+            VariadicFunc::RangeCreate { elem_type } => RangeCreate(elem_type.into_proto()),
             VariadicFunc::ListIndex => ListIndex(()),
             VariadicFunc::ListSliceLinear => ListSliceLinear(()),
             VariadicFunc::SplitPart => SplitPart(()),
             VariadicFunc::RegexpMatch => RegexpMatch(()),
+            VariadicFunc::RegexpReplace => RegexpReplace(()),
+            VariadicFunc::RegexpSplitToArray => RegexpSplitToArray(()),
+            VariadicFunc::JsonbPathExists => JsonbPathExists(()),
+            VariadicFunc::JsonbPathMatch => JsonbPathMatch(()),
+            VariadicFunc::JsonbPathQueryArray => JsonbPathQueryArray(()),
+            VariadicFunc::JsonbPathQueryFirst => JsonbPathQueryFirst(()),
+            VariadicFunc::JsonbPathExistsCompiled { path } => {
+                JsonbPathExistsCompiled(path.into_proto())
+            }
+            VariadicFunc::JsonbPathMatchCompiled { path } => {
+                JsonbPathMatchCompiled(path.into_proto())
+            }
+            VariadicFunc::JsonbPathQueryArrayCompiled { path } => {
+                JsonbPathQueryArrayCompiled(path.into_proto())
+            }
+            VariadicFunc::JsonbPathQueryFirstCompiled { path } => {
+                JsonbPathQueryFirstCompiled(path.into_proto())
+            }
+            VariadicFunc::JsonbSet => JsonbSet(()),
+            VariadicFunc::JsonbInsert => JsonbInsert(()),
             VariadicFunc::HmacString => HmacString(()),
             VariadicFunc::HmacBytes => HmacBytes(()),
             VariadicFunc::ErrorIfNull => ErrorIfNull(()),
@@ -6445,10 +7221,37 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 RecordCreate(ProtoRecordCreate { field_names }) => Ok(VariadicFunc::RecordCreate {
                     field_names: field_names.into_rust()?,
                 }),
+                RangeCreate(elem_type) => Ok(VariadicFunc::RangeCreate {
+                    elem_type: elem_type.into_rust()?,
+                }),
                 ListIndex(()) => Ok(VariadicFunc::ListIndex),
                 ListSliceLinear(()) => Ok(VariadicFunc::ListSliceLinear),
                 SplitPart(()) => Ok(VariadicFunc::SplitPart),
                 RegexpMatch(()) => Ok(VariadicFunc::RegexpMatch),
+                RegexpReplace(()) => Ok(VariadicFunc::RegexpReplace),
+                RegexpSplitToArray(()) => Ok(VariadicFunc::RegexpSplitToArray),
+                JsonbPathExists(()) => Ok(VariadicFunc::JsonbPathExists),
+                JsonbPathMatch(()) => Ok(VariadicFunc::JsonbPathMatch),
+                JsonbPathQueryArray(()) => Ok(VariadicFunc::JsonbPathQueryArray),
+                JsonbPathQueryFirst(()) => Ok(VariadicFunc::JsonbPathQueryFirst),
+                JsonbPathExistsCompiled(path) => Ok(VariadicFunc::JsonbPathExistsCompiled {
+                    path: path.into_rust()?,
+                }),
+                JsonbPathMatchCompiled(path) => Ok(VariadicFunc::JsonbPathMatchCompiled {
+                    path: path.into_rust()?,
+                }),
+                JsonbPathQueryArrayCompiled(path) => {
+                    Ok(VariadicFunc::JsonbPathQueryArrayCompiled {
+                        path: path.into_rust()?,
+                    })
+                }
+                JsonbPathQueryFirstCompiled(path) => {
+                    Ok(VariadicFunc::JsonbPathQueryFirstCompiled {
+                        path: path.into_rust()?,
+                    })
+                }
+                JsonbSet(()) => Ok(VariadicFunc::JsonbSet),
+                JsonbInsert(()) => Ok(VariadicFunc::JsonbInsert),
                 HmacString(()) => Ok(VariadicFunc::HmacString),
                 HmacBytes(()) => Ok(VariadicFunc::HmacBytes),
                 ErrorIfNull(()) => Ok(VariadicFunc::ErrorIfNull),
@@ -21,12 +21,14 @@ mod int32;
 mod int64;
 mod interval;
 mod jsonb;
+mod jsonpath;
 mod list;
 mod map;
 mod mz_timestamp;
 mod numeric;
 mod oid;
 mod pg_legacy_char;
+mod range;
 mod record;
 mod regproc;
 mod string;
@@ -53,12 +55,14 @@ pub use int32::*;
 pub use int64::*;
 pub use interval::*;
 pub use jsonb::*;
+pub use jsonpath::*;
 pub use list::*;
 pub use map::*;
 pub use mz_timestamp::*;
 pub use numeric::*;
 pub use oid::*;
 pub use pg_legacy_char::*;
+pub use range::*;
 pub use record::*;
 pub use regproc::*;
 pub use string::*;
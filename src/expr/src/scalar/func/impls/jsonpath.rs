@@ -0,0 +1,61 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_lowertest::MzReflect;
+use mz_repr::{strconv, ColumnType, ScalarType};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToJsonPath;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToJsonPath {
+    type Input = &'a str;
+    type Output = Result<String, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<String, EvalError> {
+        // Paths are stored in their canonical form, so that equivalent paths
+        // compare equal.
+        let path = strconv::parse_jsonpath(a)?;
+        Ok(path.to_string())
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::JsonPath.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastJsonPathToString)
+    }
+}
+
+impl fmt::Display for CastStringToJsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_jsonpath")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "jsonpath_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToJsonPath)]
+    fn cast_jsonpath_to_string<'a>(a: &'a str) -> String {
+        let mut buf = String::new();
+        strconv::format_jsonpath(&mut buf, a);
+        buf
+    }
+);
@@ -0,0 +1,285 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_lowertest::MzReflect;
+use mz_ore::result::ResultExt;
+use mz_repr::adt::range::Range;
+use mz_repr::{strconv, ColumnType, Datum, DatumNested, RowArena, ScalarType};
+
+use crate::scalar::func::{stringify_datum, LazyUnaryFunc};
+use crate::{EvalError, MirScalarExpr};
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct CastStringToRange {
+    // Target range's type.
+    pub return_ty: ScalarType,
+    // The expression to cast the discovered range bounds to the range's
+    // element type.
+    pub cast_expr: Box<MirScalarExpr>,
+}
+
+impl LazyUnaryFunc for CastStringToRange {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        let range = strconv::parse_range(a.unwrap_str(), |elem_text| {
+            let elem_text = match elem_text {
+                Cow::Owned(s) => temp_storage.push_string(s),
+                Cow::Borrowed(s) => s,
+            };
+            self.cast_expr
+                .eval(&[Datum::String(elem_text)], temp_storage)
+        })?;
+        temp_storage
+            .try_make_datum(|packer| packer.push_range(range))
+            .err_into()
+    }
+
+    /// The output ColumnType of this function
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        self.return_ty
+            .without_modifiers()
+            .nullable(input_type.nullable)
+    }
+
+    /// Whether this function will produce NULL on NULL input
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    /// Whether this function will produce NULL on non-NULL input
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    /// Whether this function preserves uniqueness
+    fn preserves_uniqueness(&self) -> bool {
+        false
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastRangeToString {
+            ty: self.return_ty.clone(),
+        })
+    }
+}
+
+impl fmt::Display for CastStringToRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("strtorange")
+    }
+}
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastRangeToString {
+    pub ty: ScalarType,
+}
+
+impl LazyUnaryFunc for CastRangeToString {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        let mut buf = String::new();
+        stringify_datum(&mut buf, a, &self.ty)?;
+        Ok(Datum::String(temp_storage.push_string(buf)))
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        ScalarType::String.nullable(input_type.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        // TODO? If we moved typeconv into `expr` we could determine the right
+        // inverse of this.
+        None
+    }
+}
+
+impl fmt::Display for CastRangeToString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("rangetostr")
+    }
+}
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct RangeLower;
+
+impl LazyUnaryFunc for RangeLower {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        Ok(match a.unwrap_range().lower() {
+            Some(bound) => bound.datum(),
+            None => Datum::Null,
+        })
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        input_type
+            .scalar_type
+            .unwrap_range_element_type()
+            .clone()
+            .nullable(true)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        // Empty ranges and ranges with an infinite lower bound return NULL.
+        true
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        false
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        None
+    }
+}
+
+impl fmt::Display for RangeLower {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("range_lower")
+    }
+}
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct RangeUpper;
+
+impl LazyUnaryFunc for RangeUpper {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        Ok(match a.unwrap_range().upper() {
+            Some(bound) => bound.datum(),
+            None => Datum::Null,
+        })
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        input_type
+            .scalar_type
+            .unwrap_range_element_type()
+            .clone()
+            .nullable(true)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        // Empty ranges and ranges with an infinite upper bound return NULL.
+        true
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        false
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        None
+    }
+}
+
+impl fmt::Display for RangeUpper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("range_upper")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "range_empty"]
+    fn range_empty<'a>(a: Range<DatumNested<'a>>) -> bool {
+        a.is_empty()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "range_lower_inc"]
+    fn range_lower_inc<'a>(a: Range<DatumNested<'a>>) -> bool {
+        a.lower_inclusive()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "range_upper_inc"]
+    fn range_upper_inc<'a>(a: Range<DatumNested<'a>>) -> bool {
+        a.upper_inclusive()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "range_lower_inf"]
+    fn range_lower_inf<'a>(a: Range<DatumNested<'a>>) -> bool {
+        a.lower_infinite()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "range_upper_inf"]
+    fn range_upper_inf<'a>(a: Range<DatumNested<'a>>) -> bool {
+        a.upper_infinite()
+    }
+);
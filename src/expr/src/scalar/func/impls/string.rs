@@ -32,7 +32,9 @@ use mz_repr::adt::system::{Oid, PgLegacyChar};
 use mz_repr::adt::varchar::{VarChar, VarCharMaxLength};
 use mz_repr::{strconv, ColumnType, Datum, Row, RowArena, ScalarType};
 
-use crate::scalar::func::{array_create_scalar, EagerUnaryFunc, LazyUnaryFunc};
+use crate::scalar::func::{
+    array_create_scalar, regexp_split_to_array_static, EagerUnaryFunc, LazyUnaryFunc,
+};
 use crate::{like_pattern, EvalError, MirScalarExpr, UnaryFunc};
 
 sqlfunc!(
@@ -813,6 +815,88 @@ impl fmt::Display for RegexpMatch {
     }
 }
 
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct RegexpReplace {
+    pub regex: Regex,
+    // The replacement string, in the syntax expected by
+    // `regex::Regex::replacen`.
+    pub replacement: String,
+    // The maximum number of matches to replace, or zero to replace all
+    // matches.
+    pub limit: usize,
+}
+
+impl<'a> EagerUnaryFunc<'a> for RegexpReplace {
+    type Input = &'a str;
+    type Output = String;
+
+    fn call(&self, source: &'a str) -> String {
+        self.regex
+            .replacen(source, self.limit, self.replacement.as_str())
+            .into_owned()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::String.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for RegexpReplace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "regexp_replace[{}, {}, limit={}]",
+            self.regex.as_str(),
+            self.replacement.quoted(),
+            self.limit
+        )
+    }
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct RegexpSplitToArray(pub Regex);
+
+impl LazyUnaryFunc for RegexpSplitToArray {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let haystack = a.eval(datums, temp_storage)?;
+        if haystack.is_null() {
+            return Ok(Datum::Null);
+        }
+        regexp_split_to_array_static(haystack.unwrap_str(), temp_storage, &self.0)
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        ScalarType::Array(Box::new(ScalarType::String)).nullable(input_type.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        false
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        None
+    }
+}
+
+impl fmt::Display for RegexpSplitToArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "regexp_split_to_array[{}]", self.0.as_str())
+    }
+}
+
 sqlfunc!(
     #[sqlname = "mz_panic"]
     fn panic<'a>(a: &'a str) -> String {
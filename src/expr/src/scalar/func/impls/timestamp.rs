@@ -47,6 +47,38 @@ sqlfunc!(
     }
 );
 
+/// Like [`CastTimestampTzToString`], but renders the timestamp in the time
+/// zone of the session that planned the cast, rather than in UTC.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastTimestampTzToStringInTimezone(pub Timezone);
+
+impl<'a> EagerUnaryFunc<'a> for CastTimestampTzToStringInTimezone {
+    type Input = CheckedTimestamp<DateTime<Utc>>;
+    type Output = String;
+
+    fn call(&self, a: CheckedTimestamp<DateTime<Utc>>) -> String {
+        let mut buf = String::new();
+        strconv::format_timestamptz_in(&mut buf, &a, &self.0);
+        buf
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::String.nullable(input.nullable)
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for CastTimestampTzToStringInTimezone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timestamp_with_time_zone_to_text_{}", self.0)
+    }
+}
+
 sqlfunc!(
     #[sqlname = "timestamp_to_date"]
     #[preserves_uniqueness = false]
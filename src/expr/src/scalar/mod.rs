@@ -30,9 +30,11 @@ use mz_pgrepr::TypeFromOidError;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::InvalidArrayError;
 use mz_repr::adt::datetime::DateTimeUnits;
+use mz_repr::adt::jsonpath::JsonPathError;
+use mz_repr::adt::range::InvalidRangeError;
 use mz_repr::adt::regex::Regex;
 use mz_repr::arb_datum;
-use mz_repr::strconv::{ParseError, ParseHexError};
+use mz_repr::strconv::{self, ParseError, ParseHexError};
 use mz_repr::{ColumnType, Datum, Row, RowArena, ScalarType};
 
 use self::func::{BinaryFunc, UnaryFunc, UnmaterializableFunc, VariadicFunc};
@@ -1138,6 +1140,66 @@ impl MirScalarExpr {
                                     e.typ(column_types).scalar_type,
                                 ),
                             };
+                        } else if *func == VariadicFunc::RegexpReplace
+                            && exprs[1..].iter().all(|e| e.is_literal())
+                        {
+                            let needle = exprs[1].as_literal_str().unwrap();
+                            let replacement =
+                                func::build_regex_replacement(exprs[2].as_literal_str().unwrap());
+                            let flags = match exprs.len() {
+                                4 => exprs[3].as_literal_str().unwrap(),
+                                _ => "",
+                            };
+                            let (flags, global) = func::split_global_regex_flag(flags);
+                            *e = match func::build_regex(needle, &flags) {
+                                Ok(regex) => mem::take(exprs).into_first().call_unary(
+                                    UnaryFunc::RegexpReplace(func::RegexpReplace {
+                                        regex: Regex(regex),
+                                        replacement,
+                                        limit: if global { 0 } else { 1 },
+                                    }),
+                                ),
+                                Err(err) => MirScalarExpr::literal(
+                                    Err(err),
+                                    e.typ(column_types).scalar_type,
+                                ),
+                            };
+                        } else if *func == VariadicFunc::RegexpSplitToArray
+                            && exprs[1..].iter().all(|e| e.is_literal())
+                        {
+                            let needle = exprs[1].as_literal_str().unwrap();
+                            let flags = match exprs.len() {
+                                3 => exprs[2].as_literal_str().unwrap(),
+                                _ => "",
+                            };
+                            *e = match func::build_split_regex(needle, flags) {
+                                Ok(regex) => mem::take(exprs).into_first().call_unary(
+                                    UnaryFunc::RegexpSplitToArray(func::RegexpSplitToArray(Regex(
+                                        regex,
+                                    ))),
+                                ),
+                                Err(err) => MirScalarExpr::literal(
+                                    Err(err),
+                                    e.typ(column_types).scalar_type,
+                                ),
+                            };
+                        } else if func.is_jsonb_path() && exprs[1].is_literal() {
+                            // We can at least parse the path once, rather than
+                            // for every row.
+                            let path = exprs[1].as_literal_str().unwrap();
+                            *e = match strconv::parse_jsonpath(path) {
+                                Ok(path) => {
+                                    exprs.remove(1);
+                                    MirScalarExpr::CallVariadic {
+                                        func: func.compile_jsonb_path(path),
+                                        exprs: mem::take(exprs),
+                                    }
+                                }
+                                Err(err) => MirScalarExpr::literal(
+                                    Err(err.into()),
+                                    e.typ(column_types).scalar_type,
+                                ),
+                            };
                         } else if *func == VariadicFunc::ListIndex && is_list_create_call(&exprs[0])
                         {
                             // We are looking for ListIndex(ListCreate, literal), and eliminate
@@ -2210,6 +2272,7 @@ pub enum EvalError {
         val: i64,
     },
     InvalidArray(InvalidArrayError),
+    InvalidRange(InvalidRangeError),
     InvalidEncodingName(String),
     InvalidHashAlgorithm(String),
     InvalidByteSequence {
@@ -2223,6 +2286,7 @@ pub enum EvalError {
     InvalidRegex(String),
     InvalidRegexFlag(char),
     InvalidParameterValue(String),
+    JsonPath(String),
     NegSqrt,
     NullCharacterNotPermitted,
     UnknownUnits(String),
@@ -2314,6 +2378,7 @@ impl fmt::Display for EvalError {
                 val, max_layer
             ),
             EvalError::InvalidArray(e) => e.fmt(f),
+            EvalError::InvalidRange(e) => e.fmt(f),
             EvalError::InvalidEncodingName(name) => write!(f, "invalid encoding name '{}'", name),
             EvalError::InvalidHashAlgorithm(alg) => write!(f, "invalid hash algorithm '{}'", alg),
             EvalError::InvalidByteSequence {
@@ -2329,6 +2394,7 @@ impl fmt::Display for EvalError {
             EvalError::InvalidRegex(e) => write!(f, "invalid regular expression: {}", e),
             EvalError::InvalidRegexFlag(c) => write!(f, "invalid regular expression flag: {}", c),
             EvalError::InvalidParameterValue(s) => f.write_str(s),
+            EvalError::JsonPath(s) => f.write_str(s),
             EvalError::UnknownUnits(units) => write!(f, "unit '{}' not recognized", units),
             EvalError::UnsupportedUnits(units, typ) => {
                 write!(f, "unit '{}' not supported for type {}", units, typ)
@@ -2433,6 +2499,7 @@ impl EvalError {
                 converted to mz_timestamp."
                     .into(),
             ),
+            EvalError::InvalidRange(e) => e.hint(),
             _ => None,
         }
     }
@@ -2458,12 +2525,24 @@ impl From<InvalidArrayError> for EvalError {
     }
 }
 
+impl From<InvalidRangeError> for EvalError {
+    fn from(e: InvalidRangeError) -> EvalError {
+        EvalError::InvalidRange(e)
+    }
+}
+
 impl From<regex::Error> for EvalError {
     fn from(e: regex::Error) -> EvalError {
         EvalError::InvalidRegex(e.to_string())
     }
 }
 
+impl From<JsonPathError> for EvalError {
+    fn from(e: JsonPathError) -> EvalError {
+        EvalError::JsonPath(e.to_string())
+    }
+}
+
 impl From<TypeFromOidError> for EvalError {
     fn from(e: TypeFromOidError) -> EvalError {
         EvalError::TypeFromOid(e.to_string())
@@ -2535,6 +2614,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 val: *val,
             }),
             EvalError::InvalidArray(error) => InvalidArray(error.into_proto()),
+            EvalError::InvalidRange(error) => InvalidRange(error.into_proto()),
             EvalError::InvalidEncodingName(v) => InvalidEncodingName(v.clone()),
             EvalError::InvalidHashAlgorithm(v) => InvalidHashAlgorithm(v.clone()),
             EvalError::InvalidByteSequence {
@@ -2551,6 +2631,7 @@ impl RustType<ProtoEvalError> for EvalError {
             EvalError::InvalidRegex(v) => InvalidRegex(v.clone()),
             EvalError::InvalidRegexFlag(v) => InvalidRegexFlag(v.into_proto()),
             EvalError::InvalidParameterValue(v) => InvalidParameterValue(v.clone()),
+            EvalError::JsonPath(v) => JsonPath(v.clone()),
             EvalError::NegSqrt => NegSqrt(()),
             EvalError::NullCharacterNotPermitted => NullCharacterNotPermitted(()),
             EvalError::UnknownUnits(v) => UnknownUnits(v.clone()),
@@ -2640,6 +2721,7 @@ impl RustType<ProtoEvalError> for EvalError {
                     val: v.val,
                 }),
                 InvalidArray(error) => Ok(EvalError::InvalidArray(error.into_rust()?)),
+                InvalidRange(error) => Ok(EvalError::InvalidRange(error.into_rust()?)),
                 InvalidEncodingName(v) => Ok(EvalError::InvalidEncodingName(v)),
                 InvalidHashAlgorithm(v) => Ok(EvalError::InvalidHashAlgorithm(v)),
                 InvalidByteSequence(v) => Ok(EvalError::InvalidByteSequence {
@@ -2653,6 +2735,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 InvalidRegex(v) => Ok(EvalError::InvalidRegex(v)),
                 InvalidRegexFlag(v) => Ok(EvalError::InvalidRegexFlag(char::from_proto(v)?)),
                 InvalidParameterValue(v) => Ok(EvalError::InvalidParameterValue(v)),
+                JsonPath(v) => Ok(EvalError::JsonPath(v)),
                 NegSqrt(()) => Ok(EvalError::NegSqrt),
                 NullCharacterNotPermitted(()) => Ok(EvalError::NullCharacterNotPermitted),
                 UnknownUnits(v) => Ok(EvalError::UnknownUnits(v)),
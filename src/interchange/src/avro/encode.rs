@@ -225,12 +225,12 @@ impl Encode for AvroEncoder {
         "avro"
     }
 
-    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
-        self.encode_key_unchecked(self.key_schema_id.unwrap(), row)
+    fn encode_key_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_key_unchecked(self.key_schema_id.unwrap(), row))
     }
 
-    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
-        self.encode_value_unchecked(self.value_schema_id, row)
+    fn encode_value_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_value_unchecked(self.value_schema_id, row))
     }
 }
 
@@ -331,7 +331,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     buf
                 }),
                 ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
-                ScalarType::String | ScalarType::VarChar { .. } => {
+                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::JsonPath => {
                     Value::String(datum.unwrap_str().to_owned())
                 }
                 ScalarType::Char { length } => {
@@ -397,6 +397,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     Value::Record(fields)
                 }
                 ScalarType::MzTimestamp => Value::String(datum.unwrap_mz_timestamp().to_string()),
+                ScalarType::Range { .. } => Value::String(datum.unwrap_range().to_string()),
             };
             if typ.nullable {
                 val = Value::Union {
@@ -14,9 +14,17 @@ use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row};
 pub trait Encode {
     fn get_format_name(&self) -> &str;
 
-    fn encode_key_unchecked(&self, row: Row) -> Vec<u8>;
+    /// Encodes a key row.
+    ///
+    /// Returns an error if the row holds a value that the format cannot
+    /// represent.
+    fn encode_key_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error>;
 
-    fn encode_value_unchecked(&self, row: Row) -> Vec<u8>;
+    /// Encodes a value row.
+    ///
+    /// Returns an error if the row holds a value that the format cannot
+    /// represent.
+    fn encode_value_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error>;
 }
 
 /// Bundled information sufficient to encode Datums.
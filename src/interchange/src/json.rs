@@ -57,15 +57,15 @@ impl Encode for JsonEncoder {
         "json"
     }
 
-    fn encode_key_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(
+    fn encode_key_unchecked(&self, row: mz_repr::Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_row(
             row,
             self.key_columns.as_ref().expect("key schema must exist"),
-        )
+        ))
     }
 
-    fn encode_value_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(row, &self.value_columns)
+    fn encode_value_unchecked(&self, row: mz_repr::Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_row(row, &self.value_columns))
     }
 }
 
@@ -145,7 +145,9 @@ impl ToJson for TypedDatum<'_> {
                     serde_json::Value::String(format!("{}", datum.unwrap_interval()))
                 }
                 ScalarType::Bytes => json!(datum.unwrap_bytes()),
-                ScalarType::String | ScalarType::VarChar { .. } => json!(datum.unwrap_str()),
+                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::JsonPath => {
+                    json!(datum.unwrap_str())
+                }
                 ScalarType::Char { length } => {
                     let s = char::format_str_pad(datum.unwrap_str(), *length);
                     serde_json::Value::String(s)
@@ -208,6 +210,7 @@ impl ToJson for TypedDatum<'_> {
                     serde_json::Value::Object(elements)
                 }
                 ScalarType::MzTimestamp => json!(datum.unwrap_mz_timestamp().to_string()),
+                ScalarType::Range { .. } => json!(datum.unwrap_range().to_string()),
             }
         }
     }
@@ -264,7 +267,10 @@ fn build_row_schema_field<F: FnMut() -> String>(
             }
         }
         ScalarType::Bytes => json!("bytes"),
-        ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+        ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::JsonPath => {
             json!("string")
         }
         ScalarType::Jsonb => json!({
@@ -337,6 +343,7 @@ fn build_row_schema_field<F: FnMut() -> String>(
             })
         }
         ScalarType::MzTimestamp => json!("string"),
+        ScalarType::Range { .. } => json!("string"),
     };
     if typ.nullable {
         field_type = json!(["null", field_type]);
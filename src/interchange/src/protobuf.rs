@@ -10,14 +10,19 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, bail, Context};
-
+use byteorder::{NetworkEndian, WriteBytesExt};
+use prost::Message;
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
 };
 
 use mz_ore::str::StrExt;
-use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
 
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
@@ -113,6 +118,324 @@ impl Decoder {
     }
 }
 
+/// Encodes rows as a particular Protobuf message.
+#[derive(Debug)]
+struct MessageEncoder {
+    message_descriptor: MessageDescriptor,
+    fields: Vec<FieldEncoder>,
+    /// The Confluent schema registry ID of the message's schema, if messages
+    /// are to be written in the Confluent wire format.
+    schema_id: Option<i32>,
+}
+
+impl MessageEncoder {
+    fn new(
+        descriptors: DecodedDescriptors,
+        columns: Vec<(ColumnName, ColumnType)>,
+        schema_id: Option<i32>,
+    ) -> Result<Self, anyhow::Error> {
+        let message_descriptor = descriptors.message_descriptor;
+        let fields = field_encoders(&message_descriptor, &columns)?;
+        Ok(MessageEncoder {
+            message_descriptor,
+            fields,
+            schema_id,
+        })
+    }
+
+    fn encode(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let mut buf = vec![];
+        if let Some(schema_id) = self.schema_id {
+            // The Confluent wire format for Protobuf follows the magic byte
+            // and schema ID with the path of message indexes that identifies
+            // the message within the schema. The path `[0]`, which refers to
+            // the first message in the schema, is encoded as a single zero.
+            //
+            // https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+            buf.write_u8(0).expect("writing to vec cannot fail");
+            buf.write_i32::<NetworkEndian>(schema_id)
+                .expect("writing to vec cannot fail");
+            buf.write_u8(0).expect("writing to vec cannot fail");
+        }
+        let message = encode_message(&self.message_descriptor, &self.fields, row.iter())?;
+        message
+            .encode(&mut buf)
+            .expect("writing to vec cannot fail");
+        Ok(buf)
+    }
+}
+
+/// Describes how to encode a column into a message field.
+#[derive(Debug)]
+struct FieldEncoder {
+    field: FieldDescriptor,
+    /// The type of the column.
+    column_type: ScalarType,
+    /// If the field is a message, the encoders for the fields of the message.
+    fields: Vec<FieldEncoder>,
+}
+
+/// Encodes rows as Protobuf messages.
+///
+/// Each column of the encoded relation is written to the message field of the
+/// same name. Message fields that do not correspond to a column are left
+/// unset, as are fields whose column is null.
+#[derive(Debug)]
+pub struct Encoder {
+    key: Option<MessageEncoder>,
+    value: MessageEncoder,
+}
+
+impl Encoder {
+    /// Constructs an encoder that writes keys described by `key_desc` as
+    /// the message described by `key_descriptors`, and values described by
+    /// `value_desc` as the message described by `value_descriptors`.
+    ///
+    /// If a schema ID is provided for the key or value, the corresponding
+    /// messages are written in the Confluent wire format.
+    ///
+    /// If `debezium` is set, values are wrapped in a Debezium-style envelope,
+    /// and the value message must consist of `before` and `after` fields of a
+    /// message type that describes `value_desc`.
+    ///
+    /// Returns an error if the columns of either relation cannot be mapped
+    /// onto the fields of the corresponding message.
+    pub fn new(
+        key: Option<(RelationDesc, DecodedDescriptors, Option<i32>)>,
+        value_desc: RelationDesc,
+        value_descriptors: DecodedDescriptors,
+        value_schema_id: Option<i32>,
+        debezium: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let key = key
+            .map(|(key_desc, key_descriptors, key_schema_id)| {
+                MessageEncoder::new(
+                    key_descriptors,
+                    column_names_and_types(key_desc),
+                    key_schema_id,
+                )
+                .context("validating protobuf key message")
+            })
+            .transpose()?;
+        let mut value_columns = column_names_and_types(value_desc);
+        if debezium {
+            value_columns = envelopes::dbz_envelope(value_columns);
+        }
+        let value = MessageEncoder::new(value_descriptors, value_columns, value_schema_id)
+            .context("validating protobuf value message")?;
+        Ok(Encoder { key, value })
+    }
+}
+
+impl Encode for Encoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        self.key
+            .as_ref()
+            .expect("key message must exist")
+            .encode(row)
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        self.value.encode(row)
+    }
+}
+
+/// Maps each of `columns` onto the field of the same name in `message`,
+/// verifying that the column's type can be encoded as the field's type.
+fn field_encoders(
+    message: &MessageDescriptor,
+    columns: &[(ColumnName, ColumnType)],
+) -> Result<Vec<FieldEncoder>, anyhow::Error> {
+    let mut fields = Vec::with_capacity(columns.len());
+    for (name, typ) in columns {
+        let field = message.get_field_by_name(name.as_str()).ok_or_else(|| {
+            anyhow!(
+                "protobuf message {} has no field named {}",
+                message.full_name().quoted(),
+                name.as_str().quoted()
+            )
+        })?;
+        fields.push(field_encoder(field, &typ.scalar_type)?);
+    }
+    Ok(fields)
+}
+
+fn field_encoder(field: FieldDescriptor, typ: &ScalarType) -> Result<FieldEncoder, anyhow::Error> {
+    if field.is_map() {
+        bail!("Protobuf map fields are not supported");
+    }
+    let element_type = match typ {
+        ScalarType::List { element_type, .. } if field.is_list() => element_type,
+        ScalarType::Array(element_type) if field.is_list() => element_type,
+        _ if field.is_list() => bail!(
+            "cannot encode column of type {:?} as repeated field {}",
+            typ,
+            field.name().quoted()
+        ),
+        _ => typ,
+    };
+    let ok = match (field.kind(), element_type) {
+        (Kind::Bool, ScalarType::Bool) => true,
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, ScalarType::Int16 | ScalarType::Int32) => {
+            true
+        }
+        (
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64,
+            ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64,
+        ) => true,
+        (Kind::Uint32 | Kind::Fixed32, ScalarType::UInt16 | ScalarType::UInt32) => true,
+        (
+            Kind::Uint64 | Kind::Fixed64,
+            ScalarType::UInt16 | ScalarType::UInt32 | ScalarType::UInt64,
+        ) => true,
+        (Kind::Float, ScalarType::Float32) => true,
+        (Kind::Double, ScalarType::Float32 | ScalarType::Float64) => true,
+        (
+            Kind::String,
+            ScalarType::String
+            | ScalarType::VarChar { .. }
+            | ScalarType::Char { .. }
+            | ScalarType::Jsonb
+            | ScalarType::Uuid
+            | ScalarType::Numeric { .. }
+            | ScalarType::Date
+            | ScalarType::Time
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz
+            | ScalarType::Interval,
+        ) => true,
+        (Kind::Bytes, ScalarType::Bytes) => true,
+        (Kind::Enum(_), ScalarType::String) => true,
+        (Kind::Message(_), ScalarType::Record { .. }) => true,
+        _ => false,
+    };
+    if !ok {
+        bail!(
+            "cannot encode column of type {:?} as field {} of type {:?}",
+            typ,
+            field.name().quoted(),
+            field.kind()
+        );
+    }
+    let fields = match (field.kind(), element_type) {
+        (Kind::Message(m), ScalarType::Record { fields, .. }) => field_encoders(&m, fields)?,
+        _ => vec![],
+    };
+    Ok(FieldEncoder {
+        field,
+        column_type: typ.clone(),
+        fields,
+    })
+}
+
+fn encode_message<'a, I>(
+    message: &MessageDescriptor,
+    fields: &[FieldEncoder],
+    datums: I,
+) -> Result<DynamicMessage, anyhow::Error>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut encoded = DynamicMessage::new(message.clone());
+    for (encoder, datum) in fields.iter().zip(datums) {
+        if datum.is_null() {
+            continue;
+        }
+        let value = if encoder.field.is_list() {
+            let element_type = encoder.column_type.unwrap_collection_element_type();
+            let elements = match &encoder.column_type {
+                ScalarType::Array(_) => datum.unwrap_array().elements(),
+                _ => datum.unwrap_list(),
+            };
+            // Protobuf has no notion of a null list element, so null elements
+            // are omitted.
+            Value::List(
+                elements
+                    .iter()
+                    .filter(|datum| !datum.is_null())
+                    .map(|datum| encode_value(encoder, element_type, datum))
+                    .collect::<Result<_, _>>()?,
+            )
+        } else {
+            encode_value(encoder, &encoder.column_type, datum)?
+        };
+        encoded.set_field(&encoder.field, value);
+    }
+    Ok(encoded)
+}
+
+fn encode_value(
+    encoder: &FieldEncoder,
+    typ: &ScalarType,
+    datum: Datum,
+) -> Result<Value, anyhow::Error> {
+    let value = match (encoder.field.kind(), typ) {
+        (Kind::Bool, _) => Value::Bool(datum.unwrap_bool()),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, ScalarType::Int16) => {
+            Value::I32(i32::from(datum.unwrap_int16()))
+        }
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, _) => Value::I32(datum.unwrap_int32()),
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, ScalarType::Int16) => {
+            Value::I64(i64::from(datum.unwrap_int16()))
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, ScalarType::Int32) => {
+            Value::I64(i64::from(datum.unwrap_int32()))
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, _) => Value::I64(datum.unwrap_int64()),
+        (Kind::Uint32 | Kind::Fixed32, ScalarType::UInt16) => {
+            Value::U32(u32::from(datum.unwrap_uint16()))
+        }
+        (Kind::Uint32 | Kind::Fixed32, _) => Value::U32(datum.unwrap_uint32()),
+        (Kind::Uint64 | Kind::Fixed64, ScalarType::UInt16) => {
+            Value::U64(u64::from(datum.unwrap_uint16()))
+        }
+        (Kind::Uint64 | Kind::Fixed64, ScalarType::UInt32) => {
+            Value::U64(u64::from(datum.unwrap_uint32()))
+        }
+        (Kind::Uint64 | Kind::Fixed64, _) => Value::U64(datum.unwrap_uint64()),
+        (Kind::Float, _) => Value::F32(datum.unwrap_float32()),
+        (Kind::Double, ScalarType::Float32) => Value::F64(f64::from(datum.unwrap_float32())),
+        (Kind::Double, _) => Value::F64(datum.unwrap_float64()),
+        (Kind::String, ScalarType::Jsonb) => Value::String(JsonbRef::from_datum(datum).to_string()),
+        (Kind::String, ScalarType::Uuid) => Value::String(datum.unwrap_uuid().to_string()),
+        (Kind::String, ScalarType::Numeric { .. }) => {
+            Value::String(datum.unwrap_numeric().0.to_standard_notation_string())
+        }
+        (Kind::String, ScalarType::Date) => Value::String(datum.unwrap_date().to_string()),
+        (Kind::String, ScalarType::Time) => Value::String(datum.unwrap_time().to_string()),
+        (Kind::String, ScalarType::Timestamp) => {
+            Value::String(datum.unwrap_timestamp().to_string())
+        }
+        (Kind::String, ScalarType::TimestampTz) => {
+            Value::String(datum.unwrap_timestamptz().to_string())
+        }
+        (Kind::String, ScalarType::Interval) => Value::String(datum.unwrap_interval().to_string()),
+        (Kind::String, _) => Value::String(datum.unwrap_str().to_owned()),
+        (Kind::Bytes, _) => Value::Bytes(datum.unwrap_bytes().to_vec().into()),
+        (Kind::Enum(e), _) => {
+            let name = datum.unwrap_str();
+            let value = e.get_value_by_name(name).ok_or_else(|| {
+                anyhow!(
+                    "protobuf enum {} has no value named {}",
+                    e.full_name().quoted(),
+                    name.quoted()
+                )
+            })?;
+            Value::EnumNumber(value.number())
+        }
+        (Kind::Message(m), _) => Value::Message(encode_message(
+            &m,
+            &encoder.fields,
+            datum.unwrap_list().iter(),
+        )?),
+    };
+    Ok(value)
+}
+
 fn derive_column_type(
     seen_messages: &mut HashSet<String>,
     field: &FieldDescriptor,
@@ -8,10 +8,12 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::{HashMap, HashSet};
-use std::ffi::OsStr;
+use std::ffi::{CString, OsStr};
 use std::fmt::Debug;
 use std::fs::Permissions;
+use std::io::ErrorKind;
 use std::net::{IpAddr, Ipv4Addr};
+use std::os::unix::ffi::OsStrExt;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
@@ -25,18 +27,20 @@ use itertools::Itertools;
 use scopeguard::defer;
 use sysinfo::{ProcessExt, ProcessStatus, RefreshKind, SystemExt};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 use tokio::process::{Child, Command};
 use tokio::task::JoinHandle;
 use tokio::time::{self, Duration};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use mz_orchestrator::{
-    NamespacedOrchestrator, Orchestrator, Service, ServiceAssignments, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics, ServiceStatus,
+    CpuLimit, MemoryLimit, NamespacedOrchestrator, Orchestrator, Service, ServiceAssignments,
+    ServiceConfig, ServiceEvent, ServiceProcessMetrics, ServiceStatus,
 };
 use mz_ore::cast::CastFrom;
 use mz_ore::id_gen::PortAllocator;
 use mz_pid_file::PidFile;
+use mz_secrets::envelope::Keyring;
 
 use crate::port_metadata_file::PortMetadataFile;
 
@@ -58,6 +62,25 @@ pub struct ProcessOrchestratorConfig {
     pub data_dir: PathBuf,
     /// A command to wrap the child command invocation
     pub command_wrapper: Vec<String>,
+    /// A cgroup v2 directory in which to create a cgroup for each process,
+    /// through which to enforce the memory and CPU limits of its service.
+    ///
+    /// The directory must be writable, must not contain any processes itself,
+    /// and must have the `memory` and `cpu` controllers enabled in its
+    /// `cgroup.subtree_control`. If it is not specified, memory limits are
+    /// instead enforced by limiting the size of each process's data segment,
+    /// and CPU limits are not enforced.
+    pub cgroup_root: Option<PathBuf>,
+    /// The master keys with which secrets are encrypted, if any.
+    ///
+    /// The orchestrator does not encrypt secrets itself; wrap it in an
+    /// [`EnvelopeSecretsController`] for that. The keys are written to a file
+    /// in the data directory that only the current user can read, which is
+    /// passed to each process via `--secrets-reader-master-key-file` so that
+    /// it can decrypt the secrets it reads.
+    ///
+    /// [`EnvelopeSecretsController`]: mz_secrets::envelope::EnvelopeSecretsController
+    pub secrets_master_key: Option<Keyring>,
 }
 
 /// An orchestrator backed by processes on the local machine.
@@ -78,6 +101,8 @@ pub struct ProcessOrchestrator {
     data_dir: PathBuf,
     secrets_dir: PathBuf,
     command_wrapper: Vec<String>,
+    cgroup_root: Option<PathBuf>,
+    secrets_master_key_file: Option<PathBuf>,
 }
 
 impl ProcessOrchestrator {
@@ -89,6 +114,8 @@ impl ProcessOrchestrator {
             suppress_output,
             data_dir,
             command_wrapper,
+            cgroup_root,
+            secrets_master_key,
         }: ProcessOrchestratorConfig,
     ) -> Result<ProcessOrchestrator, anyhow::Error> {
         let secrets_dir = data_dir.join("secrets");
@@ -98,6 +125,16 @@ impl ProcessOrchestrator {
         fs::set_permissions(&secrets_dir, Permissions::from_mode(0o700))
             .await
             .context("setting secrets directory permissions")?;
+        let secrets_master_key_file = match secrets_master_key {
+            Some(keyring) => {
+                let path = data_dir.join("secrets-master-key");
+                write_private_file(&path, keyring.encode().as_bytes())
+                    .await
+                    .context("writing secrets master key file")?;
+                Some(fs::canonicalize(path).await?)
+            }
+            None => None,
+        };
         Ok(ProcessOrchestrator {
             image_dir: fs::canonicalize(image_dir).await?,
             port_allocator,
@@ -106,6 +143,8 @@ impl ProcessOrchestrator {
             data_dir: fs::canonicalize(data_dir).await?,
             secrets_dir: fs::canonicalize(secrets_dir).await?,
             command_wrapper,
+            cgroup_root,
+            secrets_master_key_file,
         })
     }
 }
@@ -123,6 +162,8 @@ impl Orchestrator for ProcessOrchestrator {
                 data_dir: self.data_dir.clone(),
                 secrets_dir: self.secrets_dir.clone(),
                 command_wrapper: self.command_wrapper.clone(),
+                cgroup_root: self.cgroup_root.clone(),
+                secrets_master_key_file: self.secrets_master_key_file.clone(),
             })
         }))
     }
@@ -138,16 +179,64 @@ struct NamespacedProcessOrchestrator {
     data_dir: PathBuf,
     secrets_dir: PathBuf,
     command_wrapper: Vec<String>,
+    cgroup_root: Option<PathBuf>,
+    secrets_master_key_file: Option<PathBuf>,
+}
+
+impl NamespacedProcessOrchestrator {
+    fn pid_file_location(&self, id: &str, i: usize) -> PathBuf {
+        self.data_dir
+            .join(format!("{}-{}-{}.pid", self.namespace, id, i))
+    }
 }
 
 #[async_trait]
 impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
     async fn fetch_service_metrics(
         &self,
-        _id: &str,
+        id: &str,
     ) -> Result<Vec<ServiceProcessMetrics>, anyhow::Error> {
-        // Metrics are not currently supported on the process orchestrator
-        futures::future::pending().await
+        let scale = {
+            let supervisors = self.supervisors.lock().expect("lock poisoned");
+            match supervisors.get(id) {
+                Some(handles) => handles.len(),
+                None => anyhow::bail!("Failed to get scale for {id}"),
+            }
+        };
+        // A process that can't be found, e.g. because it is restarting, gets
+        // empty metrics rather than failing the whole request.
+        let pids = (0..scale)
+            .map(|i| PidFile::read(self.pid_file_location(id, i)).ok())
+            .collect::<Vec<_>>();
+
+        // CPU usage is the rate at which CPU time accumulates, so sample it
+        // twice.
+        let start = pids
+            .iter()
+            .map(|pid| pid.and_then(proc_cpu_time))
+            .collect::<Vec<_>>();
+        let start_time = time::Instant::now();
+        time::sleep(CPU_SAMPLE_INTERVAL).await;
+        let elapsed = start_time.elapsed();
+
+        let metrics = pids
+            .iter()
+            .zip(start)
+            .map(|(pid, start)| {
+                let Some(pid) = *pid else {
+                    return ServiceProcessMetrics::default();
+                };
+                let cpu_nano_cores = start.zip(proc_cpu_time(pid)).and_then(|(start, end)| {
+                    let cpu = end.checked_sub(start)?;
+                    u64::try_from(cpu.as_nanos() * 1_000_000_000 / elapsed.as_nanos()).ok()
+                });
+                ServiceProcessMetrics {
+                    cpu_nano_cores,
+                    memory_bytes: proc_memory_bytes(pid),
+                }
+            })
+            .collect();
+        Ok(metrics)
     }
 
     async fn ensure_service(
@@ -158,8 +247,8 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
             init_container_image: _,
             args,
             ports: ports_in,
-            memory_limit: _,
-            cpu_limit: _,
+            memory_limit,
+            cpu_limit,
             scale: scale_in,
             labels: _,
             availability_zone: _,
@@ -167,6 +256,22 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
         }: ServiceConfig<'_>,
     ) -> Result<Box<dyn Service>, anyhow::Error> {
         let full_id = format!("{}-{}", self.namespace, id);
+        let limits = |i| ProcessLimits {
+            memory: memory_limit,
+            cpu: cpu_limit,
+            cgroup: self
+                .cgroup_root
+                .as_ref()
+                .map(|root| root.join(format!("{full_id}-{i}"))),
+        };
+
+        // Configure the cgroups up front, so that limits that can't be
+        // enforced fail the request. This also updates the limits of any
+        // processes that are already running.
+        for i in 0..scale_in.get() {
+            limits(i).create_cgroup().await?;
+        }
+
         let mut supervisors = self.supervisors.lock().expect("lock poisoned");
         let handles = supervisors.entry(id.to_string()).or_default();
 
@@ -185,7 +290,7 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
         let mut port_metadata_file_locations = vec![None; scale_in.get()];
         for i in 0..(scale_in.get()) {
             let process_file_name = format!("{}-{}-{}", self.namespace, id, i);
-            let pid_file_location = self.data_dir.join(format!("{}.pid", process_file_name));
+            let pid_file_location = self.pid_file_location(id, i);
             pid_file_locations[i] = Some(pid_file_location.clone());
             let port_metadata_file_location =
                 self.data_dir.join(format!("{}.ports", process_file_name));
@@ -266,6 +371,10 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
             processes.push(ports.clone());
         }
 
+        if cpu_limit.is_some() && self.cgroup_root.is_none() {
+            warn!("{full_id}: CPU limits are only enforced when a cgroup root is configured");
+        }
+
         // Now create all the processes that weren't detected as being still alive
         let peers = processes
             .iter()
@@ -288,6 +397,12 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
                     "--secrets-reader-process-dir={}",
                     self.secrets_dir.display()
                 ));
+                if let Some(path) = &self.secrets_master_key_file {
+                    args.push(format!(
+                        "--secrets-reader-master-key-file={}",
+                        path.display()
+                    ));
+                }
 
                 let command_wrapper = self.command_wrapper.clone();
                 handles.push(AbortOnDrop(Box::new(mz_ore::task::spawn(
@@ -301,6 +416,7 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
                         processes[i].clone(),
                         self.suppress_output,
                         std::mem::take(port_metadata_file_locations[i].as_mut().unwrap()),
+                        limits(i),
                     ),
                 ))));
             }
@@ -369,6 +485,7 @@ async fn supervise(
     ports: HashMap<String, u16>,
     suppress_output: bool,
     port_metadata_file_location: PathBuf,
+    limits: ProcessLimits,
 ) {
     fn interpolate_command(
         command_part: &str,
@@ -423,19 +540,260 @@ async fn supervise(
             cmd.stdout(Stdio::null());
             cmd.stderr(Stdio::null());
         }
+        // Declared before the process, so that the process is killed before
+        // its cgroup is removed.
+        let cgroup = match limits.apply(&mut cmd).await {
+            Ok(cgroup) => cgroup,
+            Err(e) => {
+                error!(
+                    "{} failed to enforce resource limits: {:#}; relaunching in 5s",
+                    full_id, e
+                );
+                time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
         match cmd.spawn() {
             Ok(process) => {
-                let status = KillOnDropChild(process).0.wait().await;
+                let mut process = KillOnDropChild(process);
+                let status = process.0.wait().await;
                 error!("{} exited: {:?}; relaunching in 5s", full_id, status);
             }
             Err(e) => {
                 error!("{} failed to launch: {}; relaunching in 5s", full_id, e);
             }
         };
+        drop(cgroup);
         time::sleep(Duration::from_secs(5)).await;
     }
 }
 
+/// The resource limits of a process.
+#[derive(Debug)]
+struct ProcessLimits {
+    memory: Option<MemoryLimit>,
+    cpu: Option<CpuLimit>,
+    /// The cgroup through which to enforce the limits, if any.
+    cgroup: Option<PathBuf>,
+}
+
+impl ProcessLimits {
+    /// The period over which the CPU limit is enforced, in microseconds.
+    const CPU_PERIOD_MICROS: usize = 100_000;
+    /// The smallest CPU quota the kernel accepts, in microseconds.
+    const CPU_MIN_QUOTA_MICROS: usize = 1_000;
+
+    /// Arranges for the processes spawned by `cmd` to be subject to the
+    /// limits.
+    ///
+    /// If there is a cgroup, it is created and configured, and the processes
+    /// join it before they exec. The cgroup is removed when the returned
+    /// guard is dropped.
+    async fn apply(&self, cmd: &mut Command) -> Result<Option<CgroupGuard>, anyhow::Error> {
+        let Some(cgroup) = &self.cgroup else {
+            if let Some(memory) = self.memory {
+                limit_data_segment(cmd, memory);
+            }
+            return Ok(None);
+        };
+        self.create_cgroup().await?;
+        let guard = CgroupGuard(cgroup.clone());
+        join_cgroup(cmd, cgroup)?;
+        Ok(Some(guard))
+    }
+
+    /// Creates the cgroup, if there is one, and configures it with the
+    /// limits.
+    async fn create_cgroup(&self) -> Result<(), anyhow::Error> {
+        let Some(cgroup) = &self.cgroup else {
+            return Ok(());
+        };
+        fs::create_dir_all(cgroup)
+            .await
+            .with_context(|| format!("creating cgroup {}", cgroup.display()))?;
+        let memory_max = match &self.memory {
+            Some(limit) => limit.0.as_u64().to_string(),
+            None => "max".into(),
+        };
+        fs::write(cgroup.join("memory.max"), memory_max)
+            .await
+            .context("setting memory limit")?;
+        let cpu_max = match &self.cpu {
+            Some(limit) => {
+                // Limits of less than 10 millicpus are rounded up to the
+                // smallest quota, rather than failing to be applied.
+                let quota = (limit.as_millicpus() * Self::CPU_PERIOD_MICROS / 1000)
+                    .max(Self::CPU_MIN_QUOTA_MICROS);
+                format!("{} {}", quota, Self::CPU_PERIOD_MICROS)
+            }
+            None => format!("max {}", Self::CPU_PERIOD_MICROS),
+        };
+        fs::write(cgroup.join("cpu.max"), cpu_max)
+            .await
+            .context("setting cpu limit")?;
+        Ok(())
+    }
+}
+
+/// Makes processes spawned by `cmd` move themselves into `cgroup` before they
+/// exec, so that they are never running outside of it.
+///
+/// If a process can't join the cgroup, spawning it fails.
+fn join_cgroup(cmd: &mut Command, cgroup: &Path) -> Result<(), anyhow::Error> {
+    let procs = CString::new(cgroup.join("cgroup.procs").as_os_str().as_bytes())
+        .context("cgroup path contains a nul byte")?;
+    // SAFETY: `open`, `write`, and `close` are async-signal-safe, and the
+    // closure doesn't allocate, so it may be called between `fork` and
+    // `exec`.
+    unsafe {
+        cmd.pre_exec(move || {
+            let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+            if fd < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // Writing 0 moves the writing process.
+            let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+            let err = std::io::Error::last_os_error();
+            libc::close(fd);
+            if written != 1 {
+                return Err(err);
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+/// Removes a cgroup when dropped.
+///
+/// A cgroup can only be removed once all of its processes have exited. If
+/// they have only just been killed, removal is retried in the background for
+/// a few seconds.
+#[derive(Debug)]
+struct CgroupGuard(PathBuf);
+
+impl CgroupGuard {
+    const RETRIES: usize = 50;
+    const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Tries to remove the cgroup, returning whether to try again.
+    fn try_remove(path: &Path) -> bool {
+        match std::fs::remove_dir(path) {
+            Ok(()) => false,
+            Err(e) if e.kind() == ErrorKind::NotFound => false,
+            Err(e) if e.raw_os_error() == Some(libc::EBUSY) => true,
+            Err(e) => {
+                warn!("failed to remove cgroup {}: {}", path.display(), e);
+                false
+            }
+        }
+    }
+}
+
+impl Drop for CgroupGuard {
+    fn drop(&mut self) {
+        if !Self::try_remove(&self.0) {
+            return;
+        }
+        let path = self.0.clone();
+        std::thread::spawn(move || {
+            for _ in 0..Self::RETRIES {
+                std::thread::sleep(Self::RETRY_INTERVAL);
+                if !Self::try_remove(&path) {
+                    return;
+                }
+            }
+            warn!(
+                "failed to remove cgroup {}: its processes did not exit",
+                path.display()
+            );
+        });
+    }
+}
+
+/// Durably replaces the contents of the file at `path` with `contents`,
+/// making it readable and writable only by the current user.
+async fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    // Write to a new file and then move it into place, so that the contents
+    // are never readable with the wrong permissions or only partially
+    // written.
+    let tmp = path.with_extension("tmp");
+    match fs::remove_file(&tmp).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&tmp)
+        .await?;
+    file.write_all(contents).await?;
+    file.sync_all().await?;
+    fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+/// Approximates a memory limit for processes spawned by `cmd` by limiting the
+/// size of their data segments, which includes their heaps.
+fn limit_data_segment(cmd: &mut Command, limit: MemoryLimit) {
+    let limit = libc::rlim_t::try_from(limit.0.as_u64()).unwrap_or(libc::RLIM_INFINITY);
+    // SAFETY: `setrlimit` is async-signal-safe, so it may be called between
+    // `fork` and `exec`.
+    unsafe {
+        cmd.pre_exec(move || {
+            let rlimit = libc::rlimit {
+                rlim_cur: limit,
+                rlim_max: limit,
+            };
+            if libc::setrlimit(libc::RLIMIT_DATA, &rlimit) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// How long to measure the CPU usage of a process for when fetching metrics.
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the total CPU time used by the process with the given pid, as
+/// reported by `/proc/<pid>/stat`.
+fn proc_cpu_time(pid: i32) -> Option<Duration> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The second field is the command name in parentheses, which may itself
+    // contain spaces and parentheses, so skip past the last closing one.
+    let fields = stat[stat.rfind(')')? + 1..]
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    // `utime` and `stime` are the 14th and 15th fields overall, measured in
+    // clock ticks.
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    // SAFETY: `sysconf` is always safe to call.
+    let ticks_per_sec = u64::try_from(unsafe { libc::sysconf(libc::_SC_CLK_TCK) }).ok()?;
+    if ticks_per_sec == 0 {
+        return None;
+    }
+    let ticks = utime + stime;
+    Some(
+        Duration::from_secs(ticks / ticks_per_sec)
+            + Duration::from_nanos((ticks % ticks_per_sec) * 1_000_000_000 / ticks_per_sec),
+    )
+}
+
+/// Returns the resident memory of the process with the given pid, as reported
+/// by `/proc/<pid>/status`.
+fn proc_memory_bytes(pid: i32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let rss = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?;
+    let kb = rss.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
 struct KillOnDropChild(Child);
 
 impl Drop for KillOnDropChild {
@@ -498,3 +856,112 @@ impl Service for ProcessService {
             .collect()
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use bytesize::ByteSize;
+
+    use super::*;
+
+    #[test]
+    fn proc_metrics() {
+        let pid = i32::try_from(std::process::id()).expect("pid fits in i32");
+        assert!(proc_memory_bytes(pid).expect("resident memory") > 0);
+        let start = proc_cpu_time(pid).expect("cpu time");
+        // Burn some CPU so that the CPU time increases.
+        let deadline = std::time::Instant::now() + Duration::from_millis(100);
+        while std::time::Instant::now() < deadline {}
+        assert!(proc_cpu_time(pid).expect("cpu time") > start);
+        assert_eq!(proc_cpu_time(-1), None);
+    }
+
+    #[tokio::test]
+    async fn private_file() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let path = temp_dir.path().join("key");
+        std::fs::write(&path, "old").expect("file written");
+        std::fs::set_permissions(&path, Permissions::from_mode(0o644)).expect("permissions set");
+
+        write_private_file(&path, b"new")
+            .await
+            .expect("file written");
+        assert_eq!(std::fs::read_to_string(&path).expect("file read"), "new");
+        let metadata = std::fs::metadata(&path).expect("file exists");
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn cgroup_limits() {
+        // A plain directory stands in for the cgroup, which is enough to
+        // check which files are written. Unlike in a real cgroup,
+        // `cgroup.procs` has to be created up front.
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let cgroup = temp_dir.path().join("cluster-u1-0");
+        std::fs::create_dir(&cgroup).expect("cgroup created");
+        std::fs::write(cgroup.join("cgroup.procs"), "").expect("file written");
+        let limits = ProcessLimits {
+            memory: Some(MemoryLimit(ByteSize::mib(1))),
+            cpu: Some(CpuLimit::from_millicpus(500)),
+            cgroup: Some(cgroup.clone()),
+        };
+
+        // The process joins the cgroup before it execs.
+        let mut cmd = Command::new("true");
+        let guard = limits.apply(&mut cmd).await.expect("valid cgroup");
+        let status = cmd.status().await.expect("process spawned");
+        assert!(status.success());
+        let read = |file| std::fs::read_to_string(cgroup.join(file)).expect("file written");
+        assert_eq!(read("memory.max"), "1048576");
+        assert_eq!(read("cpu.max"), "50000 100000");
+        assert_eq!(read("cgroup.procs"), "0");
+
+        // A process that can't join the cgroup isn't spawned.
+        std::fs::remove_dir_all(&cgroup).expect("removed cgroup");
+        assert!(cmd.status().await.is_err());
+        drop(guard);
+
+        // The cgroup is removed once its processes have exited.
+        let mut cmd = Command::new("true");
+        let guard = limits.apply(&mut cmd).await.expect("valid cgroup");
+        assert!(cgroup.exists());
+        for file in ["memory.max", "cpu.max"] {
+            std::fs::remove_file(cgroup.join(file)).expect("removed file");
+        }
+        drop(guard);
+        assert!(!cgroup.exists());
+
+        // Tiny CPU limits are rounded up to the smallest quota.
+        let tiny = ProcessLimits {
+            cpu: Some(CpuLimit::from_millicpus(1)),
+            ..limits
+        };
+        tiny.create_cgroup().await.expect("valid cgroup");
+        assert_eq!(read("cpu.max"), "1000 100000");
+        std::fs::remove_dir_all(&cgroup).expect("removed cgroup");
+        let limits = ProcessLimits {
+            cpu: Some(CpuLimit::from_millicpus(500)),
+            ..tiny
+        };
+
+        // Limits that can't be enforced are an error.
+        let limits = ProcessLimits {
+            cgroup: Some(temp_dir.path().join("missing").join("cgroup")),
+            ..limits
+        };
+        std::fs::write(temp_dir.path().join("missing"), "").expect("file written");
+        assert!(limits.create_cgroup().await.is_err());
+        assert!(limits.apply(&mut Command::new("true")).await.is_err());
+
+        // Without a cgroup, there is nothing to clean up.
+        let limits = ProcessLimits {
+            cgroup: None,
+            ..limits
+        };
+        assert!(limits
+            .apply(&mut Command::new("true"))
+            .await
+            .expect("no cgroup")
+            .is_none());
+    }
+}
@@ -9,14 +9,17 @@
 
 //! Management of user secrets via the local file system.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use anyhow::Context;
 use async_trait::async_trait;
-use tokio::fs::{self, OpenOptions};
+use tokio::fs::{self, File, OpenOptions};
 use tokio::io::AsyncWriteExt;
+use tracing::info;
 
 use mz_repr::GlobalId;
+use mz_secrets::envelope::{self, Keyring};
 use mz_secrets::{SecretsController, SecretsReader};
 
 use crate::ProcessOrchestrator;
@@ -70,3 +73,114 @@ impl SecretsReader for ProcessSecretsReader {
         Ok(contents)
     }
 }
+
+/// Re-encrypts the secrets stored in `secrets_dir` so that they can be read
+/// with only the current key of `keyring`.
+///
+/// Secrets whose data keys are wrapped with one of the previous keys of
+/// `keyring` are rewrapped with the current key. Unencrypted secrets, as
+/// written by a [`ProcessOrchestrator`] that is not wrapped in an
+/// [`EnvelopeSecretsController`], are encrypted. Each secret is replaced
+/// atomically, so readers observe either the old or the new version of each
+/// secret, and both can be decrypted by readers that know both keys.
+///
+/// Returns the number of secrets that were rewritten.
+///
+/// [`EnvelopeSecretsController`]: mz_secrets::envelope::EnvelopeSecretsController
+pub async fn rotate_secrets(secrets_dir: &Path, keyring: &Keyring) -> Result<usize, anyhow::Error> {
+    let mut rewritten = 0;
+    let mut entries = fs::read_dir(secrets_dir)
+        .await
+        .with_context(|| format!("reading secrets directory {}", secrets_dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        // Skip anything that isn't a secret, like the temporary files left
+        // behind by an interrupted rotation.
+        let id: GlobalId = match entry.file_name().to_str().and_then(|n| n.parse().ok()) {
+            Some(id) => id,
+            None => continue,
+        };
+        let contents = fs::read(entry.path()).await?;
+        let new_contents = if envelope::is_envelope(&contents) {
+            keyring
+                .rewrap(&contents)
+                .with_context(|| format!("rewrapping secret {}", id))?
+        } else {
+            Some(keyring.seal(id, &contents)?)
+        };
+        if let Some(new_contents) = new_contents {
+            let tmp_path = secrets_dir.join(format!(".{}.tmp", id));
+            let mut file = OpenOptions::new()
+                .mode(0o600)
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&tmp_path)
+                .await?;
+            file.write_all(&new_contents).await?;
+            file.sync_all().await?;
+            fs::rename(&tmp_path, entry.path()).await?;
+            rewritten += 1;
+        }
+    }
+    File::open(secrets_dir).await?.sync_all().await?;
+    info!(
+        "rotated {} secrets to master key {}",
+        rewritten,
+        keyring.current().id()
+    );
+    Ok(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_secrets::envelope::MasterKey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn rotate() {
+        let secrets_dir = tempfile::tempdir().unwrap();
+        let old_key = MasterKey::generate().unwrap();
+        let new_key = MasterKey::generate().unwrap();
+        let old = Keyring::new(old_key.clone(), vec![]);
+        let new = Keyring::new(new_key.clone(), vec![]);
+
+        let (u1, u2) = (GlobalId::User(1), GlobalId::User(2));
+        fs::write(secrets_dir.path().join(u1.to_string()), b"legacy")
+            .await
+            .unwrap();
+        fs::write(
+            secrets_dir.path().join(u2.to_string()),
+            old.seal(u2, b"encrypted").unwrap(),
+        )
+        .await
+        .unwrap();
+        fs::write(secrets_dir.path().join("not-a-secret"), b"ignored")
+            .await
+            .unwrap();
+
+        let rotating = Keyring::new(new_key, vec![old_key]);
+        assert_eq!(
+            rotate_secrets(secrets_dir.path(), &rotating).await.unwrap(),
+            2
+        );
+        assert_eq!(
+            rotate_secrets(secrets_dir.path(), &rotating).await.unwrap(),
+            0
+        );
+
+        // The old key is no longer needed to read any of the secrets.
+        let reader = ProcessSecretsReader::new(secrets_dir.path().to_path_buf());
+        for (id, expected) in [(u1, &b"legacy"[..]), (u2, &b"encrypted"[..])] {
+            let contents = reader.read(id).await.unwrap();
+            assert_eq!(new.open(id, &contents).unwrap(), expected);
+            assert!(old.open(id, &contents).is_err());
+        }
+        assert_eq!(
+            fs::read(secrets_dir.path().join("not-a-secret"))
+                .await
+                .unwrap(),
+            b"ignored"
+        );
+    }
+}
@@ -144,6 +144,7 @@ pub async fn force_compaction(
                 Arc::new(CpuHeavyRuntime::new()),
                 req,
                 writer_id.clone(),
+                None,
             )
             .await?;
             info!(
@@ -23,6 +23,7 @@ use mz_ore::cast::CastFrom;
 use mz_persist::indexed::columnar::{ColumnarRecords, ColumnarRecordsVecBuilder};
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::{Atomicity, Blob};
+use mz_persist_types::columnar::{Part, PartStats, Schema};
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
@@ -220,7 +221,11 @@ where
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         shard_id: ShardId,
         writer_id: WriterId,
+        key_schema: Option<Arc<dyn Schema<K>>>,
     ) -> Self {
+        let key_structured = key_schema
+            .filter(|_| cfg.write_structured_keys)
+            .map(KeyStructuredEncoder::new);
         let parts = BatchParts::new(
             cfg.batch_builder_max_outstanding_parts,
             Arc::clone(&metrics),
@@ -230,6 +235,7 @@ where
             Arc::clone(&blob),
             cpu_heavy_runtime,
             &metrics.user,
+            key_structured,
         );
         Self {
             size_hint,
@@ -371,6 +377,64 @@ where
     }
 }
 
+/// Computes the structured columnar encoding of the keys in a part, along with
+/// statistics about them.
+///
+/// The [Codec] encoded keys are only kept if the structured ones don't
+/// round-trip back to exactly the same bytes through [Codec::structured_decoder],
+/// so that keys aren't stored twice.
+#[derive(Clone)]
+pub(crate) struct KeyStructuredEncoder(
+    Arc<dyn Fn(&ColumnarRecords) -> Result<KeyStructured, String> + Send + Sync>,
+);
+
+/// The output of a [KeyStructuredEncoder].
+pub(crate) struct KeyStructured {
+    part: Part,
+    stats: PartStats,
+    /// Whether the [Codec] encoded keys can be recovered from `part`.
+    roundtrips: bool,
+}
+
+impl Debug for KeyStructuredEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("KeyStructuredEncoder").finish()
+    }
+}
+
+impl KeyStructuredEncoder {
+    pub(crate) fn new<K: Codec>(schema: Arc<dyn Schema<K>>) -> Self {
+        KeyStructuredEncoder(Arc::new(move |updates: &ColumnarRecords| {
+            let mut encoder = schema.encoder();
+            for ((k, _), _, _) in updates.iter() {
+                encoder.encode(&K::decode(k)?);
+            }
+            let part = encoder.finish()?;
+            let stats = schema.stats(&part)?;
+            let roundtrips = match K::structured_decoder(&part) {
+                Ok(decoder) => {
+                    let mut buf = Vec::new();
+                    updates.iter().enumerate().all(|(idx, ((k, _), _, _))| {
+                        buf.clear();
+                        decoder.decode(idx).encode(&mut buf);
+                        buf == k
+                    })
+                }
+                Err(_) => false,
+            };
+            Ok(KeyStructured {
+                part,
+                stats,
+                roundtrips,
+            })
+        }))
+    }
+
+    fn encode(&self, updates: &ColumnarRecords) -> Result<KeyStructured, String> {
+        (self.0)(updates)
+    }
+}
+
 // TODO: If this is dropped, cancel (and delete?) any writing parts and delete
 // any finished ones.
 #[derive(Debug)]
@@ -382,9 +446,10 @@ pub(crate) struct BatchParts<T> {
     lower: Antichain<T>,
     blob: Arc<dyn Blob + Send + Sync>,
     cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
-    writing_parts: VecDeque<(PartialBatchKey, JoinHandle<usize>)>,
+    writing_parts: VecDeque<(PartialBatchKey, JoinHandle<(usize, Option<PartStats>)>)>,
     finished_parts: Vec<HollowBatchPart>,
     batch_metrics: BatchWriteMetrics,
+    key_structured: Option<KeyStructuredEncoder>,
 }
 
 impl<T: Timestamp + Codec64> BatchParts<T> {
@@ -397,6 +462,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
         blob: Arc<dyn Blob + Send + Sync>,
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         batch_metrics: &BatchWriteMetrics,
+        key_structured: Option<KeyStructuredEncoder>,
     ) -> Self {
         BatchParts {
             max_outstanding,
@@ -409,12 +475,13 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
             writing_parts: VecDeque::new(),
             finished_parts: Vec::new(),
             batch_metrics: batch_metrics.clone(),
+            key_structured,
         }
     }
 
     pub(crate) async fn write(
         &mut self,
-        updates: ColumnarRecords,
+        mut updates: ColumnarRecords,
         upper: Antichain<T>,
         since: Antichain<T>,
     ) {
@@ -426,27 +493,44 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
         let partial_key = PartialBatchKey::new(&self.writer_id, &PartId::new());
         let key = partial_key.complete(&self.shard_id);
         let index = u64::cast_from(self.finished_parts.len() + self.writing_parts.len());
+        let key_structured = self.key_structured.clone();
 
         let write_span = debug_span!("batch::write_part", shard = %self.shard_id).or_current();
         let handle = mz_ore::task::spawn(
             || "batch::write_part",
             async move {
                 let goodbytes = updates.goodbytes();
-                let batch = BlobTraceBatchPart {
-                    desc,
-                    updates: vec![updates],
-                    index,
-                };
 
                 let start = Instant::now();
-                let buf = cpu_heavy_runtime
+                let (buf, stats) = cpu_heavy_runtime
                     .spawn_named(|| "batch::encode_part", async move {
+                        // The structured keys are an optimization for readers,
+                        // so failing to compute them doesn't fail the write.
+                        let mut stats = None;
+                        if let Some(key_structured) = key_structured {
+                            let res = key_structured.encode(&updates).and_then(|x| {
+                                updates.set_key_structured(x.part)?;
+                                if x.roundtrips {
+                                    updates.elide_key_codec()?;
+                                }
+                                Ok(x.stats)
+                            });
+                            match res {
+                                Ok(x) => stats = Some(x),
+                                Err(err) => warn!("failed to encode structured keys: {}", err),
+                            }
+                        }
+                        let batch = BlobTraceBatchPart {
+                            desc,
+                            updates: vec![updates],
+                            index,
+                        };
                         let mut buf = Vec::new();
                         batch.encode(&mut buf);
 
                         // Drop batch as soon as we can to reclaim its memory.
                         drop(batch);
-                        Bytes::from(buf)
+                        (Bytes::from(buf), stats)
                     })
                     .instrument(debug_span!("batch::encode_part"))
                     .await
@@ -470,7 +554,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 batch_metrics.seconds.inc_by(start.elapsed().as_secs_f64());
                 batch_metrics.bytes.inc_by(u64::cast_from(payload_len));
                 batch_metrics.goodbytes.inc_by(u64::cast_from(goodbytes));
-                payload_len
+                (payload_len, stats)
             }
             .instrument(write_span),
         );
@@ -482,17 +566,18 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 .writing_parts
                 .pop_front()
                 .expect("pop failed when len was just > some usize");
-            let encoded_size_bytes = match handle
+            let (encoded_size_bytes, stats) = match handle
                 .instrument(debug_span!("batch::max_outstanding"))
                 .await
             {
                 Ok(x) => x,
-                Err(err) if err.is_cancelled() => 0,
+                Err(err) if err.is_cancelled() => (0, None),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
             self.finished_parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                stats,
             });
         }
     }
@@ -501,14 +586,15 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
     pub(crate) async fn finish(self) -> Vec<HollowBatchPart> {
         let mut parts = self.finished_parts;
         for (key, handle) in self.writing_parts {
-            let encoded_size_bytes = match handle.await {
+            let (encoded_size_bytes, stats) = match handle.await {
                 Ok(x) => x,
-                Err(err) if err.is_cancelled() => 0,
+                Err(err) if err.is_cancelled() => (0, None),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
             parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                stats,
             });
         }
         parts
@@ -533,10 +619,18 @@ pub(crate) fn validate_truncate_batch<T: Timestamp>(
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+
+    use arrow2::array::{Array, MutableArray, MutableUtf8Array, Utf8Array};
+    use arrow2::bitmap::MutableBitmap;
+    use arrow2::datatypes::{DataType, Field};
+    use mz_persist_types::columnar::{ColumnStats, PartDecoder, PartEncoder};
+
     use crate::cache::PersistClientCache;
+    use crate::fetch::fetch_leased_part;
     use crate::internal::paths::{BlobKey, PartialBlobKey};
-    use crate::tests::all_ok;
+    use crate::tests::{all_ok, expect_fetch_part};
     use crate::PersistLocation;
 
     use super::*;
@@ -656,4 +750,143 @@ mod tests {
             }
         }
     }
+
+    #[derive(Debug)]
+    pub(crate) struct StringSchema;
+
+    impl Schema<String> for StringSchema {
+        fn columns(&self) -> Vec<Field> {
+            vec![Field::new("s", DataType::Utf8, false)]
+        }
+
+        fn encoder(&self) -> Box<dyn PartEncoder<String>> {
+            Box::new(StringEncoder(MutableUtf8Array::new()))
+        }
+
+        fn decoder(&self, part: &Part) -> Result<Box<dyn PartDecoder<String>>, String> {
+            let array = part
+                .column("s")
+                .and_then(|x| x.as_any().downcast_ref::<Utf8Array<i32>>())
+                .ok_or_else(|| "missing column s".to_owned())?;
+            Ok(Box::new(StringDecoder(array.clone())))
+        }
+
+        fn stats(&self, part: &Part) -> Result<PartStats, String> {
+            let array = part
+                .column("s")
+                .and_then(|x| x.as_any().downcast_ref::<Utf8Array<i32>>())
+                .ok_or_else(|| "missing column s".to_owned())?;
+            let stats = ColumnStats {
+                null_count: array.null_count(),
+                lower: array.values_iter().min().map(|x| x.as_bytes().to_vec()),
+                upper: array.values_iter().max().map(|x| x.as_bytes().to_vec()),
+            };
+            Ok(PartStats {
+                len: part.len(),
+                columns: BTreeMap::from([("s".to_owned(), stats)]),
+            })
+        }
+    }
+
+    struct StringEncoder(MutableUtf8Array<i32>);
+
+    impl PartEncoder<String> for StringEncoder {
+        fn encode(&mut self, val: &String) {
+            self.0.push(Some(val));
+        }
+
+        fn finish(mut self: Box<Self>) -> Result<Part, String> {
+            Part::new(self.0.len(), StringSchema.columns(), vec![self.0.as_box()])
+        }
+    }
+
+    struct StringDecoder(Utf8Array<i32>);
+
+    impl PartDecoder<String> for StringDecoder {
+        fn decode(&self, idx: usize) -> String {
+            self.0.value(idx).to_owned()
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_builder_structured_keys() {
+        mz_ore::test::init_logging();
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let mut cache = PersistClientCache::new_no_metrics();
+        cache.cfg.write_structured_keys = true;
+        let client = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed");
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+        write.set_key_schema(Arc::new(StringSchema));
+        write.expect_compare_and_append(&data, 0, 4).await;
+
+        // Push down a filter on the key and check that only the matching
+        // updates are returned.
+        let snap = read
+            .snapshot(Antichain::from_elem(3))
+            .await
+            .expect("cannot serve requested as_of");
+        let mut actual = Vec::new();
+        for part in snap {
+            // The stats computed at write time are available before fetching.
+            let stats = part.stats().expect("stats were written");
+            assert_eq!(stats.len, 3);
+            assert_eq!(
+                stats.columns.get("s"),
+                Some(&ColumnStats {
+                    null_count: 0,
+                    lower: Some(b"1".to_vec()),
+                    upper: Some(b"3".to_vec()),
+                })
+            );
+            let (part, mut fetched_part) = fetch_leased_part::<String, String, u64, i64>(
+                part,
+                read.blob.as_ref(),
+                Arc::clone(&read.metrics),
+                &read.metrics.read.snapshot,
+                Some(&read.reader_id),
+            )
+            .await;
+            read.process_returned_leased_part(part);
+            fetched_part.set_structured_key_decoder(Box::new(|part| {
+                let decoder = StringSchema.decoder(part)?;
+                let mask = part
+                    .column("s")
+                    .and_then(|x| x.as_any().downcast_ref::<Utf8Array<i32>>())
+                    .expect("column was validated by decoder")
+                    .values_iter()
+                    .map(|x| x >= "2")
+                    .collect::<MutableBitmap>();
+                Ok((decoder, Some(mask.into())))
+            }));
+            actual.extend(fetched_part);
+        }
+        actual.sort();
+        assert_eq!(actual, all_ok(&data[1..], 3));
+
+        // The structured keys round-trip, so the Codec encoded ones weren't
+        // stored, but readers without a structured decoder still get them.
+        let batches = write.machine.snapshot(&Antichain::from_elem(3)).await;
+        for part in batches.expect("as_of is valid")[0].parts.iter() {
+            let (part, _) = expect_fetch_part::<String, String, u64, i64>(
+                write.blob.as_ref(),
+                &part.key.complete(&write.machine.shard_id()),
+            )
+            .await;
+            assert!(part.updates.iter().all(|x| x.key_codec_elided()));
+        }
+        assert_eq!(read.expect_snapshot_and_fetch(3).await, all_ok(&data, 3));
+    }
 }
@@ -53,6 +53,11 @@ impl PersistClientCache {
         }
     }
 
+    /// The configuration of the clients returned by this cache.
+    pub fn cfg(&self) -> &PersistConfig {
+        &self.cfg
+    }
+
     /// A test helper that returns a [PersistClientCache] disconnected from
     /// metrics.
     #[cfg(test)]
@@ -15,6 +15,7 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::anyhow;
+use arrow2::bitmap::Bitmap;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
@@ -22,10 +23,12 @@ use mz_ore::cast::CastFrom;
 use serde::{Deserialize, Serialize};
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
-use tracing::{debug_span, trace_span, Instrument};
+use tracing::{debug_span, trace_span, warn, Instrument};
 
+use mz_persist::indexed::columnar::ColumnarRecords;
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::{Blob, SeqNo};
+use mz_persist_types::columnar::{Part, PartDecoder, PartStats};
 use mz_persist_types::{Codec, Codec64};
 
 use crate::error::InvalidUsage;
@@ -214,6 +217,7 @@ where
         metrics,
         ts_filter,
         part: encoded_part,
+        structured_keys: StructuredKeys::default(),
         _phantom: PhantomData,
     };
 
@@ -336,6 +340,7 @@ where
     pub(crate) desc: Description<T>,
     pub(crate) key: PartialBatchKey,
     pub(crate) encoded_size_bytes: usize,
+    pub(crate) stats: Option<PartStats>,
     /// The `SeqNo` from which this part originated; we track this value as
     /// long as necessary to ensure the `SeqNo` isn't garbage collected while a
     /// read still depends on it.
@@ -364,6 +369,7 @@ where
             since: self.desc.since().iter().map(T::encode).collect(),
             key: self.key.clone(),
             encoded_size_bytes: self.encoded_size_bytes,
+            stats: self.stats.clone(),
             leased_seqno: self.leased_seqno,
             reader_id: self.reader_id.clone(),
         };
@@ -394,6 +400,15 @@ where
     pub fn encoded_size_bytes(&self) -> usize {
         self.encoded_size_bytes
     }
+
+    /// Statistics about the structured keys in this part, if they were
+    /// written.
+    ///
+    /// These describe every update in the blob, including any that will be
+    /// filtered out by time when the part is fetched.
+    pub fn stats(&self) -> Option<&PartStats> {
+        self.stats.as_ref()
+    }
 }
 
 impl<T> Drop for LeasedBatchPart<T>
@@ -412,10 +427,95 @@ pub struct FetchedPart<K, V, T, D> {
     metrics: Arc<Metrics>,
     ts_filter: FetchBatchFilter<T>,
     part: EncodedPart<T>,
+    structured_keys: StructuredKeys<K>,
 
     _phantom: PhantomData<fn() -> (K, V, D)>,
 }
 
+/// Returns a decoder for the structured keys in a [Part] along with an
+/// optional mask of the keys that might be of interest to the reader.
+pub type StructuredKeyDecoderFn<K> =
+    Box<dyn FnMut(&Part) -> Result<(Box<dyn PartDecoder<K>>, Option<Bitmap>), String> + Send>;
+
+/// The state of decoding structured keys from a [FetchedPart].
+struct StructuredKeys<K> {
+    decoder_fn: Option<StructuredKeyDecoderFn<K>>,
+    /// The index of the chunk of updates the decoder was last created for,
+    /// along with the decoder and mask, if that chunk had structured keys.
+    current: Option<(usize, Option<(Box<dyn PartDecoder<K>>, Option<Bitmap>)>)>,
+}
+
+impl<K> Default for StructuredKeys<K> {
+    fn default() -> Self {
+        StructuredKeys {
+            decoder_fn: None,
+            current: None,
+        }
+    }
+}
+
+impl<K> Debug for StructuredKeys<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StructuredKeys")
+            .field("current", &self.current.as_ref().map(|(idx, _)| idx))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: Codec> StructuredKeys<K> {
+    /// Returns the decoder to use for the keys in the chunk of updates at
+    /// `part_idx`, if they should be decoded from the structured keys.
+    ///
+    /// Chunks whose [Codec] encoded keys were elided are always decoded from
+    /// the structured keys, falling back to [Codec::structured_decoder] if the
+    /// reader didn't set a decoder or it failed.
+    fn get(
+        &mut self,
+        part_idx: usize,
+        updates: &ColumnarRecords,
+    ) -> Result<Option<&(Box<dyn PartDecoder<K>>, Option<Bitmap>)>, String> {
+        if self.current.as_ref().map(|(idx, _)| *idx) != Some(part_idx) {
+            let mut decoder = None;
+            if let (Some(decoder_fn), Some(part)) =
+                (self.decoder_fn.as_mut(), updates.key_structured())
+            {
+                match decoder_fn(part) {
+                    Ok(x) => decoder = Some(x),
+                    // The Codec encoded keys are usually available, so fall
+                    // back to them.
+                    Err(err) => warn!("failed to decode structured keys: {}", err),
+                }
+            }
+            if decoder.is_none() && updates.key_codec_elided() {
+                let part = updates
+                    .key_structured()
+                    .expect("elided keys have structured keys");
+                decoder = Some((K::structured_decoder(part)?, None));
+            }
+            self.current = Some((part_idx, decoder));
+        }
+        Ok(self.current.as_ref().and_then(|(_, x)| x.as_ref()))
+    }
+}
+
+impl<K, V, T, D> FetchedPart<K, V, T, D> {
+    /// Decodes keys from the structured columnar encoding written by
+    /// [crate::write::WriteHandle::set_key_schema], where available, instead
+    /// of with [Codec].
+    ///
+    /// `decoder_fn` is called once for each chunk of updates in the part that
+    /// has structured keys. If it returns a mask, updates whose bit is unset
+    /// are skipped entirely, which lets readers push simple filters down into
+    /// the fetch. Updates without structured keys are decoded as usual and
+    /// are never skipped.
+    pub fn set_structured_key_decoder(&mut self, decoder_fn: StructuredKeyDecoderFn<K>) {
+        self.structured_keys = StructuredKeys {
+            decoder_fn: Some(decoder_fn),
+            current: None,
+        };
+    }
+}
+
 /// A [Blob] object that has been fetched, but has no associated decoding
 /// logic.
 #[derive(Debug)]
@@ -438,12 +538,22 @@ where
     type Item = ((Result<K, String>, Result<V, String>), T, D);
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some((k, v, mut t, d)) = self.part.next() {
+        while let Some(((part_idx, updates, idx), k, v, mut t, d)) = self.part.next_with_position()
+        {
             if !self.ts_filter.filter_ts(&mut t) {
                 continue;
             }
 
-            let k = self.metrics.codecs.key.decode(|| K::decode(k));
+            let k = match self.structured_keys.get(part_idx, updates) {
+                Ok(Some((decoder, mask))) => {
+                    if mask.as_ref().map_or(false, |mask| !mask.get_bit(idx)) {
+                        continue;
+                    }
+                    Ok(decoder.decode(idx))
+                }
+                Ok(None) => self.metrics.codecs.key.decode(|| K::decode(k)),
+                Err(err) => Err(err),
+            };
             let v = self.metrics.codecs.val.decode(|| V::decode(v));
             let d = D::decode(d);
             return Some(((k, v), t, d));
@@ -525,9 +635,44 @@ where
         }
     }
 
+    /// Restores any [Codec] encoded keys that were elided in favor of the
+    /// structured ones, for users that operate directly on the encoded keys.
+    pub(crate) fn restore_key_codec<K: Codec>(&mut self) -> Result<(), String> {
+        for updates in self.part.updates.iter_mut() {
+            if !updates.key_codec_elided() {
+                continue;
+            }
+            let part = updates
+                .key_structured()
+                .expect("elided keys have structured keys");
+            let decoder = K::structured_decoder(part)?;
+            updates.restore_key_codec(|idx, buf| {
+                decoder.decode(idx).encode(buf);
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
     pub fn next<'a>(&'a mut self) -> Option<(&'a [u8], &'a [u8], T, [u8; 8])> {
+        self.next_with_position()
+            .map(|(_, k, v, t, d)| (k, v, t, d))
+    }
+
+    /// Like [Self::next], but also returns the position of the update: the
+    /// index of its chunk, the chunk itself, and its index in the chunk.
+    pub(crate) fn next_with_position<'a>(
+        &'a mut self,
+    ) -> Option<(
+        (usize, &'a ColumnarRecords, usize),
+        &'a [u8],
+        &'a [u8],
+        T,
+        [u8; 8],
+    )> {
         while let Some(part) = self.part.updates.get(self.part_idx) {
-            let ((k, v), t, d) = match part.get(self.idx) {
+            let idx = self.idx;
+            let ((k, v), t, d) = match part.get(idx) {
                 Some(x) => {
                     self.idx += 1;
                     x
@@ -551,7 +696,7 @@ where
                     continue;
                 }
             }
-            return Some((k, v, t, d));
+            return Some(((self.part_idx, part, idx), k, v, t, d));
         }
         None
     }
@@ -574,6 +719,7 @@ pub struct SerdeLeasedBatchPart {
     since: Vec<[u8; 8]>,
     key: PartialBatchKey,
     encoded_size_bytes: usize,
+    stats: Option<PartStats>,
     leased_seqno: Option<SeqNo>,
     reader_id: LeasedReaderId,
 }
@@ -600,6 +746,7 @@ impl<T: Timestamp + Codec64> LeasedBatchPart<T> {
             ),
             key: x.key,
             encoded_size_bytes: x.encoded_size_bytes,
+            stats: x.stats,
             leased_seqno: x.leased_seqno,
             reader_id: x.reader_id,
         }
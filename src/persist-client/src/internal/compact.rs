@@ -28,6 +28,7 @@ use mz_persist::indexed::columnar::{
     ColumnarRecordsBuilder, ColumnarRecordsVecBuilder, KEY_VAL_DATA_MAX_LEN,
 };
 use mz_persist::location::Blob;
+use mz_persist_types::columnar::Schema;
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::Timestamp;
 use timely::PartialOrder;
@@ -38,7 +39,7 @@ use tracing::log::warn;
 use tracing::{debug, debug_span, trace, Instrument, Span};
 
 use crate::async_runtime::CpuHeavyRuntime;
-use crate::batch::BatchParts;
+use crate::batch::{BatchParts, KeyStructuredEncoder};
 use crate::fetch::{fetch_batch_part, EncodedPart};
 use crate::internal::machine::{retry_external, Machine};
 use crate::internal::state::{HollowBatch, HollowBatchPart};
@@ -77,7 +78,13 @@ pub struct CompactRes<T> {
 pub struct Compactor<K, V, T, D> {
     cfg: PersistConfig,
     metrics: Arc<Metrics>,
-    sender: Sender<(CompactReq<T>, Machine<K, V, T, D>, oneshot::Sender<()>)>,
+    sender: Sender<(
+        CompactReq<T>,
+        Machine<K, V, T, D>,
+        Option<KeyStructuredEncoder>,
+        oneshot::Sender<()>,
+    )>,
+    key_structured: Option<KeyStructuredEncoder>,
     _phantom: PhantomData<fn() -> D>,
 }
 
@@ -94,10 +101,12 @@ where
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         writer_id: WriterId,
     ) -> Self {
-        let (compact_req_sender, mut compact_req_receiver) =
-            mpsc::channel::<(CompactReq<T>, Machine<K, V, T, D>, oneshot::Sender<()>)>(
-                cfg.compaction_queue_size,
-            );
+        let (compact_req_sender, mut compact_req_receiver) = mpsc::channel::<(
+            CompactReq<T>,
+            Machine<K, V, T, D>,
+            Option<KeyStructuredEncoder>,
+            oneshot::Sender<()>,
+        )>(cfg.compaction_queue_size);
         let concurrency_limit = Arc::new(tokio::sync::Semaphore::new(
             cfg.compaction_concurrency_limit,
         ));
@@ -105,7 +114,9 @@ where
         // spin off a single task responsible for executing compaction requests.
         // work is enqueued into the task through a channel
         let _worker_handle = mz_ore::task::spawn(|| "PersistCompactionWorker", async move {
-            while let Some((req, mut machine, completer)) = compact_req_receiver.recv().await {
+            while let Some((req, mut machine, key_structured, completer)) =
+                compact_req_receiver.recv().await
+            {
                 assert_eq!(req.shard_id, machine.shard_id());
 
                 let cfg = machine.cfg.clone();
@@ -178,6 +189,7 @@ where
                                     Arc::clone(&cpu_heavy_runtime),
                                     req,
                                     writer_id,
+                                    key_structured,
                                 )
                                 .instrument(compact_span),
                             )
@@ -255,10 +267,20 @@ where
             cfg,
             metrics,
             sender: compact_req_sender,
+            key_structured: None,
             _phantom: PhantomData,
         }
     }
 
+    /// Sets the schema used to write structured keys into the output of
+    /// compaction, see [crate::write::WriteHandle::set_key_schema].
+    pub fn set_key_schema(&mut self, schema: Arc<dyn Schema<K>>) {
+        self.key_structured = self
+            .cfg
+            .write_structured_keys
+            .then(|| KeyStructuredEncoder::new(schema));
+    }
+
     /// Enqueues a [CompactReq] to be consumed by the compaction background task when available.
     ///
     /// Returns a receiver that indicates when compaction has completed. The receiver can be
@@ -292,8 +314,12 @@ where
         // spine structure that generated the request, so it has a much better chance of
         // merging and committing the result than a machine kept up-to-date through state
         // diffs, which may have a different spine structure less amendable to merging.
-        let send =
-            new_compaction_sender.try_send((req, machine.clone(), compaction_completed_sender));
+        let send = new_compaction_sender.try_send((
+            req,
+            machine.clone(),
+            self.key_structured.clone(),
+            compaction_completed_sender,
+        ));
         if let Err(_) = send {
             self.metrics.compaction.dropped.inc();
             return None;
@@ -325,13 +351,17 @@ where
     ///
     /// 3. If there is excess memory after accounting for (1) and (2), we increase the
     ///    number of outstanding parts we can keep in-flight to Blob.
-    pub async fn compact(
+    ///
+    /// If `key_structured` is set, the output has structured keys, even if the
+    /// inputs don't.
+    pub(crate) async fn compact(
         cfg: PersistConfig,
         blob: Arc<dyn Blob + Send + Sync>,
         metrics: Arc<Metrics>,
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
         req: CompactReq<T>,
         writer_id: WriterId,
+        key_structured: Option<KeyStructuredEncoder>,
     ) -> Result<CompactRes<T>, anyhow::Error> {
         let () = Self::validate_req(&req)?;
         // compaction needs memory enough for at least 2 runs and 2 in-progress parts
@@ -371,6 +401,7 @@ where
                 Arc::clone(&blob),
                 Arc::clone(&cpu_heavy_runtime),
                 &metrics.compaction.batch,
+                key_structured.clone(),
             );
 
             let (parts, runs, updates) = Self::compact_runs(
@@ -587,6 +618,8 @@ where
                 let mut part = part
                     .join(shard_id, blob.as_ref(), &metrics, part_desc)
                     .await?;
+                // Compaction consolidates the Codec encoded keys.
+                part.restore_key_codec::<K>().map_err(|err| anyhow!(err))?;
                 // Ideally we'd hook into start_prefetches here, too, but runs
                 // is mutable borrowed. Not the end of the world. Instead do it
                 // once after this initial heap population.
@@ -620,6 +653,7 @@ where
                     let mut part = part
                         .join(shard_id, blob.as_ref(), &metrics, part_desc)
                         .await?;
+                    part.restore_key_codec::<K>().map_err(|err| anyhow!(err))?;
                     // start_prefetches is O(n) so calling it here is O(n^2). N
                     // is the number of things we're about to fetch over the
                     // network, so if it's big enough for N^2 to matter, we've
@@ -1001,6 +1035,7 @@ mod tests {
     use crate::PersistLocation;
     use timely::progress::Antichain;
 
+    use crate::batch::tests::StringSchema;
     use crate::tests::{all_ok, expect_fetch_part, new_test_client, new_test_client_cache};
 
     use super::*;
@@ -1054,6 +1089,7 @@ mod tests {
             Arc::new(CpuHeavyRuntime::new()),
             req.clone(),
             write.writer_id.clone(),
+            None,
         )
         .await
         .expect("compaction failed");
@@ -1071,6 +1107,82 @@ mod tests {
         assert_eq!(updates, all_ok(&data, 10));
     }
 
+    #[tokio::test]
+    async fn structured_keys() {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("0".to_owned(), "zero".to_owned()), 0, 1),
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+        ];
+
+        // The inputs are written with and without structured keys, and the
+        // output has them regardless.
+        let mut cache = new_test_client_cache();
+        cache.cfg.write_structured_keys = true;
+        let (mut write, _) = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed")
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+        let b0 = write
+            .expect_batch(&data[..1], 0, 1)
+            .await
+            .into_hollow_batch();
+        write.set_key_schema(Arc::new(StringSchema));
+        let b1 = write
+            .expect_batch(&data[1..], 1, 2)
+            .await
+            .into_hollow_batch();
+        assert!(b0.parts[0].stats.is_none());
+        assert!(b1.parts[0].stats.is_some());
+
+        let req = CompactReq {
+            shard_id: write.machine.shard_id(),
+            desc: Description::new(
+                b0.desc.lower().clone(),
+                b1.desc.upper().clone(),
+                Antichain::from_elem(0u64),
+            ),
+            inputs: vec![b0, b1],
+        };
+        let res = Compactor::<String, String, u64, i64>::compact(
+            write.cfg.clone(),
+            Arc::clone(&write.blob),
+            Arc::clone(&write.metrics),
+            Arc::new(CpuHeavyRuntime::new()),
+            req.clone(),
+            write.writer_id.clone(),
+            Some(KeyStructuredEncoder::new(Arc::new(StringSchema))),
+        )
+        .await
+        .expect("compaction failed");
+
+        assert_eq!(res.output.parts.len(), 1);
+        let part = &res.output.parts[0];
+        assert_eq!(part.stats.as_ref().map(|x| x.len), Some(2));
+        let (part, _) = expect_fetch_part::<String, String, u64, i64>(
+            write.blob.as_ref(),
+            &part.key.complete(&write.machine.shard_id()),
+        )
+        .await;
+        let updates = &part.updates[0];
+        assert!(updates.key_structured().is_some());
+        assert!(updates.key_codec_elided());
+
+        let mut part = EncodedPart::new("", res.output.desc.clone(), part);
+        part.restore_key_codec::<String>().expect("keys round-trip");
+        let mut keys = Vec::new();
+        while let Some((k, _, _, _)) = part.next() {
+            keys.push(String::decode(k).expect("valid key"));
+        }
+        assert_eq!(keys, vec!["0".to_owned(), "1".to_owned()]);
+    }
+
     #[tokio::test]
     async fn prefetches() {
         let desc = Description::new(
@@ -1082,6 +1194,7 @@ mod tests {
             .map(|encoded_size_bytes| HollowBatchPart {
                 key: PartialBatchKey("".into()),
                 encoded_size_bytes,
+                stats: None,
             })
             .collect::<Vec<_>>();
         let parse = |x: &str| {
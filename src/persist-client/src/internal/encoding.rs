@@ -12,6 +12,7 @@ use std::marker::PhantomData;
 
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
+use mz_persist_types::columnar::{ColumnStats, PartStats};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use prost::Message;
@@ -28,10 +29,11 @@ use crate::error::CodecMismatch;
 use crate::internal::paths::{PartialBatchKey, PartialRollupKey};
 use crate::internal::state::{
     CriticalReaderState, HandleDebugState, HollowBatch, HollowBatchPart, IdempotencyToken,
-    LeasedReaderState, OpaqueState, ProtoCriticalReaderState, ProtoHandleDebugState,
-    ProtoHollowBatch, ProtoHollowBatchPart, ProtoLeasedReaderState, ProtoStateDiff,
-    ProtoStateField, ProtoStateFieldDiffType, ProtoStateFieldDiffs, ProtoStateRollup, ProtoTrace,
-    ProtoU64Antichain, ProtoU64Description, ProtoWriterState, State, StateCollections, WriterState,
+    LeasedReaderState, OpaqueState, ProtoColumnStats, ProtoCriticalReaderState,
+    ProtoHandleDebugState, ProtoHollowBatch, ProtoHollowBatchPart, ProtoLeasedReaderState,
+    ProtoPartStats, ProtoStateDiff, ProtoStateField, ProtoStateFieldDiffType, ProtoStateFieldDiffs,
+    ProtoStateRollup, ProtoTrace, ProtoU64Antichain, ProtoU64Description, ProtoWriterState, State,
+    StateCollections, WriterState,
 };
 use crate::internal::state_diff::{
     ProtoStateFieldDiff, StateDiff, StateFieldDiff, StateFieldValDiff,
@@ -810,6 +812,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatch> for HollowBatch<T> {
                 .map(|key| HollowBatchPart {
                     key: PartialBatchKey(key),
                     encoded_size_bytes: 0,
+                    stats: None,
                 }),
         );
         Ok(HollowBatch {
@@ -826,6 +829,7 @@ impl RustType<ProtoHollowBatchPart> for HollowBatchPart {
         ProtoHollowBatchPart {
             key: self.key.into_proto(),
             encoded_size_bytes: self.encoded_size_bytes.into_proto(),
+            stats: self.stats.into_proto(),
         }
     }
 
@@ -833,6 +837,49 @@ impl RustType<ProtoHollowBatchPart> for HollowBatchPart {
         Ok(HollowBatchPart {
             key: proto.key.into_rust()?,
             encoded_size_bytes: proto.encoded_size_bytes.into_rust()?,
+            stats: proto.stats.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoPartStats> for PartStats {
+    fn into_proto(&self) -> ProtoPartStats {
+        ProtoPartStats {
+            len: self.len.into_proto(),
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, stats)| (name.clone(), stats.into_proto()))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoPartStats) -> Result<Self, TryFromProtoError> {
+        let mut columns = BTreeMap::new();
+        for (name, stats) in proto.columns {
+            columns.insert(name, stats.into_rust()?);
+        }
+        Ok(PartStats {
+            len: proto.len.into_rust()?,
+            columns,
+        })
+    }
+}
+
+impl RustType<ProtoColumnStats> for ColumnStats {
+    fn into_proto(&self) -> ProtoColumnStats {
+        ProtoColumnStats {
+            null_count: self.null_count.into_proto(),
+            lower: self.lower.clone(),
+            upper: self.upper.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoColumnStats) -> Result<Self, TryFromProtoError> {
+        Ok(ColumnStats {
+            null_count: proto.null_count.into_rust()?,
+            lower: proto.lower,
+            upper: proto.upper,
         })
     }
 }
@@ -982,6 +1029,7 @@ mod tests {
             parts: vec![HollowBatchPart {
                 key: PartialBatchKey("a".into()),
                 encoded_size_bytes: 5,
+                stats: None,
             }],
             runs: vec![],
         };
@@ -999,6 +1047,7 @@ mod tests {
         expected.parts.push(HollowBatchPart {
             key: PartialBatchKey("b".into()),
             encoded_size_bytes: 0,
+            stats: None,
         });
         assert_eq!(<HollowBatch<u64>>::from_proto(old).unwrap(), expected);
     }
@@ -1308,6 +1308,7 @@ pub mod datadriven {
             Arc::clone(&datadriven.client.cpu_heavy_runtime),
             datadriven.shard_id.clone(),
             WriterId::new(),
+            None,
         );
         for ((k, ()), t, d) in updates {
             builder.add(&k, &(), &t, &d).await.expect("invalid batch");
@@ -1462,6 +1463,7 @@ pub mod datadriven {
             Arc::clone(&datadriven.client.cpu_heavy_runtime),
             req,
             WriterId::new(),
+            None,
         )
         .await?;
 
@@ -209,6 +209,10 @@ pub struct PersistConfig {
     pub batch_builder_max_outstanding_parts: usize,
     /// Whether to physically and logically compact batches in blob storage.
     pub compaction_enabled: bool,
+    /// Whether to write a structured columnar encoding of keys, for writers
+    /// that have set a key schema with
+    /// [crate::write::WriteHandle::set_key_schema].
+    pub write_structured_keys: bool,
     /// The upper bound on compaction's memory consumption. The value must be at
     /// least 4*`blob_target_size`. Increasing this value beyond the minimum allows
     /// compaction to merge together more runs at once, providing greater
@@ -316,6 +320,8 @@ impl PersistConfig {
             blob_target_size: 128 * MB,
             batch_builder_max_outstanding_parts: 2,
             compaction_enabled: !compaction_disabled,
+            // Structured keys are opt-in while we gain confidence in them.
+            write_structured_keys: false,
             compaction_memory_bound_bytes: 1024 * MB,
             compaction_heuristic_min_inputs: 8,
             compaction_heuristic_min_parts: 8,
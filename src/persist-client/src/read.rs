@@ -634,6 +634,7 @@ where
             desc: batch.desc.clone(),
             key: part.key,
             encoded_size_bytes: part.encoded_size_bytes,
+            stats: part.stats,
             leased_seqno: Some(self.lease_seqno()),
         })
     }
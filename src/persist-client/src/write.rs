@@ -21,6 +21,7 @@ use mz_ore::now::EpochMillis;
 use mz_ore::task::RuntimeExt;
 use mz_persist::location::{Blob, Indeterminate};
 use mz_persist::retry::Retry;
+use mz_persist_types::columnar::Schema;
 use mz_persist_types::{Codec, Codec64};
 use serde::{Deserialize, Serialize};
 use timely::progress::{Antichain, Timestamp};
@@ -134,6 +135,7 @@ where
     pub(crate) blob: Arc<dyn Blob + Send + Sync>,
     pub(crate) cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
     pub(crate) writer_id: WriterId,
+    pub(crate) key_schema: Option<Arc<dyn Schema<K>>>,
 
     pub(crate) upper: Antichain<T>,
     pub(crate) last_heartbeat: EpochMillis,
@@ -170,6 +172,7 @@ where
             blob,
             cpu_heavy_runtime,
             writer_id: writer_id.clone(),
+            key_schema: None,
             upper,
             last_heartbeat,
             explicitly_expired: false,
@@ -567,6 +570,22 @@ where
         }
     }
 
+    /// Sets the schema used to write a structured columnar encoding of keys,
+    /// which allows readers to decode only the parts of the keys they need.
+    /// The schema is used both for batches written by this handle and for the
+    /// output of the compactions it performs.
+    ///
+    /// Structured keys are only written if enabled by
+    /// [PersistConfig::write_structured_keys]. When the [Codec] encoding of
+    /// the keys can be recovered from the structured one, only the latter is
+    /// stored.
+    pub fn set_key_schema(&mut self, schema: Arc<dyn Schema<K>>) {
+        if let Some(compact) = self.compact.as_mut() {
+            compact.set_key_schema(Arc::clone(&schema));
+        }
+        self.key_schema = Some(schema);
+    }
+
     /// Returns a [BatchBuilder] that can be used to write a batch of updates to
     /// blob storage which can then be appended to this shard using
     /// [Self::compare_and_append_batch] or [Self::append_batch].
@@ -589,6 +608,7 @@ where
             Arc::clone(&self.cpu_heavy_runtime),
             self.machine.shard_id().clone(),
             self.writer_id.clone(),
+            self.key_schema.clone(),
         )
     }
 
@@ -9,8 +9,10 @@
 
 //! Implementations of [Codec] for stdlib types.
 
+use arrow2::array::{Array, Utf8Array};
 use bytes::BufMut;
 
+use crate::columnar::{Part, PartDecoder};
 use crate::{Codec, Codec64, Opaque};
 
 impl Codec for () {
@@ -48,6 +50,28 @@ impl Codec for String {
     fn decode<'a>(buf: &'a [u8]) -> Result<Self, String> {
         String::from_utf8(buf.to_owned()).map_err(|err| err.to_string())
     }
+
+    fn structured_decoder(part: &Part) -> Result<Box<dyn PartDecoder<Self>>, String> {
+        // The only sensible structured encoding of a String is a single
+        // column of non-null strings.
+        let array = match part.columns() {
+            [column] => column.as_any().downcast_ref::<Utf8Array<i32>>(),
+            _ => None,
+        };
+        match array {
+            Some(array) if array.null_count() == 0 => Ok(Box::new(StringDecoder(array.clone()))),
+            _ => Err("expected a single column of non-null strings".into()),
+        }
+    }
+}
+
+/// Decodes the structured encoding of a String.
+struct StringDecoder(Utf8Array<i32>);
+
+impl PartDecoder<String> for StringDecoder {
+    fn decode(&self, idx: usize) -> String {
+        self.0.value(idx).to_owned()
+    }
 }
 
 impl Codec for Vec<u8> {
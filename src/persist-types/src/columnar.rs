@@ -0,0 +1,387 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Structured columnar encodings of persisted keys and values.
+//!
+//! [Codec] encodes each key and value into an opaque blob of bytes, so every
+//! read has to decode whole values. A [Schema] additionally describes how to
+//! encode a batch of values column by column into Arrow arrays, which lets
+//! readers decode only the columns they need and evaluate simple predicates
+//! directly on the columns.
+//!
+//! [Codec]: crate::Codec
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use arrow2::array::Array;
+use arrow2::datatypes::Field;
+use serde::{Deserialize, Serialize};
+
+/// A description of the structured columnar encoding of a type `T`.
+pub trait Schema<T>: Debug + Send + Sync {
+    /// The columns used to store values of `T`.
+    ///
+    /// The names of the columns are unique.
+    fn columns(&self) -> Vec<Field>;
+
+    /// Returns an encoder that accumulates values of `T` into columns
+    /// matching [Schema::columns].
+    fn encoder(&self) -> Box<dyn PartEncoder<T>>;
+
+    /// Returns a decoder for the values stored in `part`.
+    ///
+    /// `part` may be missing any of the columns in [Schema::columns], in which
+    /// case the decoder fills the corresponding pieces of the decoded values
+    /// with defaults. This is how readers skip columns they don't need.
+    fn decoder(&self, part: &Part) -> Result<Box<dyn PartDecoder<T>>, String>;
+
+    /// Returns statistics about the values stored in `part`, which must have
+    /// been built by an encoder returned from [Schema::encoder].
+    fn stats(&self, part: &Part) -> Result<PartStats, String>;
+}
+
+/// Accumulates values of some type into a [Part].
+pub trait PartEncoder<T>: Send {
+    /// Appends `val` to the columns being built.
+    fn encode(&mut self, val: &T);
+
+    /// Finishes building the columns.
+    ///
+    /// Returns an error if any of the encoded values did not match the schema
+    /// that created this encoder.
+    fn finish(self: Box<Self>) -> Result<Part, String>;
+}
+
+/// Decodes values of some type from a [Part].
+pub trait PartDecoder<T>: Send {
+    /// Decodes the value at `idx`.
+    ///
+    /// Panics if `idx` is not less than the length of the part.
+    fn decode(&self, idx: usize) -> T;
+}
+
+/// A batch of values stored column by column.
+///
+/// Invariants:
+/// - fields.len() == columns.len()
+/// - The names of the fields are unique.
+/// - Each column has length `len` and the data type of its field.
+#[derive(Clone)]
+pub struct Part {
+    len: usize,
+    fields: Vec<Field>,
+    columns: Vec<Box<dyn Array>>,
+}
+
+impl Debug for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Part")
+            .field("len", &self.len)
+            .field("fields", &self.fields)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Part {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self.fields == other.fields
+            && self
+                .columns
+                .iter()
+                .zip(other.columns.iter())
+                .all(|(a, b)| a.as_ref() == b.as_ref())
+    }
+}
+
+impl Part {
+    /// Returns a new part made up of the given columns.
+    pub fn new(
+        len: usize,
+        fields: Vec<Field>,
+        columns: Vec<Box<dyn Array>>,
+    ) -> Result<Self, String> {
+        if fields.len() != columns.len() {
+            return Err(format!(
+                "expected {} columns got {}",
+                fields.len(),
+                columns.len()
+            ));
+        }
+        for (idx, (field, column)) in fields.iter().zip(columns.iter()).enumerate() {
+            if fields[..idx].iter().any(|x| x.name == field.name) {
+                return Err(format!("duplicate column {}", field.name));
+            }
+            if column.len() != len {
+                return Err(format!(
+                    "expected column {} to have length {} got {}",
+                    field.name,
+                    len,
+                    column.len()
+                ));
+            }
+            if column.data_type() != field.data_type() {
+                return Err(format!(
+                    "expected column {} to have type {:?} got {:?}",
+                    field.name,
+                    field.data_type(),
+                    column.data_type()
+                ));
+            }
+        }
+        Ok(Part {
+            len,
+            fields,
+            columns,
+        })
+    }
+
+    /// The number of values stored in the part.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the part stores no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The fields describing the columns of the part.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// The columns of the part, in the same order as [Part::fields].
+    pub fn columns(&self) -> &[Box<dyn Array>] {
+        &self.columns
+    }
+
+    /// Returns the column with the given name, if the part has one.
+    pub fn column(&self, name: &str) -> Option<&dyn Array> {
+        self.fields
+            .iter()
+            .position(|x| x.name == name)
+            .map(|idx| self.columns[idx].as_ref())
+    }
+
+    /// Returns a new part with only the columns for which `keep` returns true.
+    pub fn project<F: Fn(&Field) -> bool>(&self, keep: F) -> Part {
+        let (fields, columns) = self
+            .fields
+            .iter()
+            .zip(self.columns.iter())
+            .filter(|(field, _)| keep(field))
+            .map(|(field, column)| (field.clone(), column.clone()))
+            .unzip();
+        Part {
+            len: self.len,
+            fields,
+            columns,
+        }
+    }
+}
+
+/// Statistics about the values stored in a [Part].
+///
+/// These let readers skip parts that provably contain no values of interest
+/// without fetching them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PartStats {
+    /// The number of values in the part.
+    pub len: usize,
+    /// Statistics about each column of the part, keyed by column name.
+    /// Columns without statistics are omitted.
+    pub columns: BTreeMap<String, ColumnStats>,
+}
+
+/// Statistics about the values stored in a column of a [Part].
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ColumnStats {
+    /// The number of null values in the column.
+    pub null_count: usize,
+    /// A value less than or equal to every non-null value in the column, in
+    /// an encoding chosen by the [Schema] that computed the statistics, if
+    /// known.
+    ///
+    /// This is usually the smallest value, but a [Schema] may truncate it
+    /// (see [STATS_MAX_BOUND_LEN]) to keep the statistics small.
+    pub lower: Option<Vec<u8>>,
+    /// A value greater than or equal to every non-null value in the column,
+    /// in an encoding chosen by the [Schema] that computed the statistics, if
+    /// known.
+    ///
+    /// This is usually the largest value, but a [Schema] may truncate it
+    /// (see [STATS_MAX_BOUND_LEN]) to keep the statistics small.
+    pub upper: Option<Vec<u8>>,
+}
+
+/// The maximum length, in bytes, of the strings and byte strings that bound
+/// a column in its [ColumnStats].
+///
+/// Longer bounds are truncated with [truncate_bytes] or [truncate_string] so
+/// that the statistics of a part stay small no matter what it holds.
+pub const STATS_MAX_BOUND_LEN: usize = 100;
+
+/// The side of a range of values that a truncated bound must stay on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateBound {
+    /// The truncated value must be less than or equal to the original.
+    Lower,
+    /// The truncated value must be greater than or equal to the original.
+    Upper,
+}
+
+/// Returns a byte string of at most `max_len` bytes that bounds `x` from the
+/// given side, or None if there is no such byte string.
+///
+/// A lower bound is a prefix of `x`. An upper bound is a prefix of `x` with
+/// its last byte incremented, which only fails if the prefix is all
+/// [u8::MAX].
+pub fn truncate_bytes(x: &[u8], max_len: usize, bound: TruncateBound) -> Option<Vec<u8>> {
+    if x.len() <= max_len {
+        return Some(x.to_owned());
+    }
+    let mut prefix = x[..max_len].to_owned();
+    match bound {
+        TruncateBound::Lower => Some(prefix),
+        TruncateBound::Upper => {
+            while let Some(last) = prefix.pop() {
+                if last < u8::MAX {
+                    prefix.push(last + 1);
+                    return Some(prefix);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Returns a string of at most `max_len` bytes that bounds `x` from the given
+/// side, or None if there is no such string.
+///
+/// This is [truncate_bytes] for strings: the result is cut at a char boundary
+/// and an upper bound increments its last char, so it is always valid UTF-8.
+/// Strings compare bytewise, which for UTF-8 is the order of their chars.
+pub fn truncate_string(x: &str, max_len: usize, bound: TruncateBound) -> Option<String> {
+    if x.len() <= max_len {
+        return Some(x.to_owned());
+    }
+    let mut end = max_len;
+    while !x.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut prefix = x[..end].to_owned();
+    match bound {
+        TruncateBound::Lower => Some(prefix),
+        TruncateBound::Upper => {
+            while let Some(last) = prefix.pop() {
+                // The next char, skipping over the surrogates.
+                let next = (u32::from(last) + 1..=u32::from(char::MAX)).find_map(char::from_u32);
+                if let Some(next) = next {
+                    // Incrementing can lengthen the char's encoding.
+                    if prefix.len() + next.len_utf8() <= max_len {
+                        prefix.push(next);
+                        return Some(prefix);
+                    }
+                }
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow2::array::{BooleanArray, PrimitiveArray};
+    use arrow2::datatypes::DataType;
+
+    use super::*;
+
+    #[test]
+    fn part() {
+        let fields = vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Boolean, true),
+        ];
+        let a: Box<dyn Array> = Box::new(PrimitiveArray::<i64>::from_slice([1, 2, 3]));
+        let b: Box<dyn Array> = Box::new(BooleanArray::from([Some(true), None, Some(false)]));
+
+        let part = Part::new(3, fields.clone(), vec![a.clone(), b.clone()]).unwrap();
+        assert_eq!(part.len(), 3);
+        assert!(part.column("b") == Some(b.as_ref()));
+        assert!(part.column("c").is_none());
+
+        let projected = part.project(|field| field.name == "b");
+        assert_eq!(projected.fields(), &fields[1..]);
+        assert!(projected.column("a").is_none());
+        assert_eq!(projected.len(), 3);
+
+        // Mismatched lengths, types, and names are rejected.
+        assert!(Part::new(2, fields.clone(), vec![a.clone(), b.clone()]).is_err());
+        assert!(Part::new(3, fields.clone(), vec![b.clone(), a.clone()]).is_err());
+        assert!(Part::new(
+            3,
+            vec![fields[0].clone(), fields[0].clone()],
+            vec![a.clone(), a]
+        )
+        .is_err());
+        assert!(Part::new(3, fields, vec![b]).is_err());
+    }
+
+    #[test]
+    fn truncate() {
+        use TruncateBound::*;
+
+        assert_eq!(truncate_bytes(b"abc", 3, Lower), Some(b"abc".to_vec()));
+        assert_eq!(truncate_bytes(b"abc", 3, Upper), Some(b"abc".to_vec()));
+        assert_eq!(truncate_bytes(b"abcd", 3, Lower), Some(b"abc".to_vec()));
+        assert_eq!(truncate_bytes(b"abcd", 3, Upper), Some(b"abd".to_vec()));
+        assert_eq!(truncate_bytes(&[1, 0xff, 0xff, 2], 3, Upper), Some(vec![2]));
+        assert_eq!(truncate_bytes(&[0xff, 0xff, 0xff, 0], 3, Upper), None);
+        assert_eq!(truncate_bytes(b"abcd", 0, Lower), Some(vec![]));
+
+        assert_eq!(truncate_string("abc", 3, Upper), Some("abc".to_owned()));
+        assert_eq!(truncate_string("abcd", 3, Lower), Some("abc".to_owned()));
+        assert_eq!(truncate_string("abcd", 3, Upper), Some("abd".to_owned()));
+        // Truncation happens at a char boundary.
+        assert_eq!(truncate_string("aé", 2, Lower), Some("a".to_owned()));
+        assert_eq!(truncate_string("aé", 2, Upper), Some("b".to_owned()));
+        // Incrementing skips the surrogates and doesn't exceed the length.
+        assert_eq!(
+            truncate_string("a\u{d7ff}b", 4, Upper),
+            Some("a\u{e000}".to_owned())
+        );
+        assert_eq!(truncate_string("a\u{7f}b", 2, Upper), Some("b".to_owned()));
+        assert_eq!(truncate_string("\u{10ffff}a", 4, Upper), None);
+
+        // The bounds hold for arbitrary strings.
+        let strings = [
+            "",
+            "a",
+            "aa",
+            "ab",
+            "a\u{7f}",
+            "é",
+            "éé",
+            "\u{10ffff}\u{10ffff}",
+            "zz",
+        ];
+        for x in strings {
+            for max_len in 0..6 {
+                if let Some(lower) = truncate_string(x, max_len, Lower) {
+                    assert!(lower.len() <= max_len && lower.as_str() <= x);
+                }
+                if let Some(upper) = truncate_string(x, max_len, Upper) {
+                    assert!(upper.len() <= max_len.max(x.len()) && upper.as_str() >= x);
+                }
+            }
+        }
+    }
+}
@@ -18,7 +18,10 @@
 
 use bytes::BufMut;
 
+use crate::columnar::{Part, PartDecoder};
+
 mod codec_impls;
+pub mod columnar;
 
 /// Encoding and decoding operations for a type usable as a persisted key or
 /// value.
@@ -49,6 +52,21 @@ pub trait Codec: Sized + 'static {
     // TODO: Mechanically, this could return a ref to the original bytes
     // without any copies, see if we can make the types work out for that.
     fn decode<'a>(buf: &'a [u8]) -> Result<Self, String>;
+
+    /// Returns a decoder for values stored in `part` by the encoder of some
+    /// [columnar::Schema] for this type, without access to that schema.
+    ///
+    /// Persist uses this to recover values whose [Codec] encoding was dropped
+    /// because it was redundant with their structured encoding, so it must
+    /// decode every column written by the encoder. Types that can't decode
+    /// their structured encoding without a schema return an error, which is
+    /// the default.
+    fn structured_decoder(_part: &Part) -> Result<Box<dyn PartDecoder<Self>>, String> {
+        Err(format!(
+            "{} cannot decode structured data without a schema",
+            Self::codec_name()
+        ))
+    }
 }
 
 /// Encoding and decoding operations for a type usable as a persisted timestamp
@@ -17,7 +17,7 @@ use anyhow::anyhow;
 use tracing::warn;
 use url::Url;
 
-use crate::file::{FileBlob, FileBlobConfig};
+use crate::file::{FileBlob, FileBlobConfig, FileConsensus, FileConsensusConfig};
 use crate::location::{Blob, Consensus, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::metrics::PostgresConsensusMetrics;
@@ -122,6 +122,8 @@ impl BlobConfig {
 /// Config for an implementation of [Consensus].
 #[derive(Debug, Clone)]
 pub enum ConsensusConfig {
+    /// Config for [FileConsensus].
+    File(FileConsensusConfig),
     /// Config for [PostgresConsensus].
     Postgres(PostgresConsensusConfig),
     /// Config for [MemConsensus], only available in testing.
@@ -144,6 +146,9 @@ impl ConsensusConfig {
     /// Opens the associated implementation of [Consensus].
     pub async fn open(self) -> Result<Arc<dyn Consensus + Send + Sync>, ExternalError> {
         match self {
+            ConsensusConfig::File(config) => FileConsensus::open(config)
+                .await
+                .map(|x| Arc::new(x) as Arc<dyn Consensus + Send + Sync>),
             ConsensusConfig::Postgres(config) => PostgresConsensus::open(config)
                 .await
                 .map(|x| Arc::new(x) as Arc<dyn Consensus + Send + Sync>),
@@ -168,6 +173,7 @@ impl ConsensusConfig {
         })?;
 
         let config = match url.scheme() {
+            "file" => Ok(ConsensusConfig::File(FileConsensusConfig::from(url.path()))),
             "postgres" | "postgresql" => Ok(ConsensusConfig::Postgres(
                 PostgresConsensusConfig::new(value, knobs, metrics)?,
             )),
@@ -7,10 +7,15 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! File backed implementations for testing and benchmarking.
+//! File backed implementations for testing, benchmarking, and single-node
+//! deployments.
 
-use std::io::ErrorKind;
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -19,9 +24,12 @@ use fail::fail_point;
 use mz_ore::cast::CastFrom;
 use tokio::fs::{self, File, OpenOptions};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::warn;
 
 use crate::error::Error;
-use crate::location::{Atomicity, Blob, BlobMetadata, ExternalError};
+use crate::location::{
+    Atomicity, Blob, BlobMetadata, Consensus, ExternalError, SeqNo, VersionedData,
+};
 
 /// Configuration for opening a [FileBlob].
 #[derive(Debug, Clone)]
@@ -238,9 +246,378 @@ impl Blob for FileBlob {
     }
 }
 
+/// Configuration for opening a [FileConsensus].
+#[derive(Debug, Clone)]
+pub struct FileConsensusConfig {
+    base_dir: PathBuf,
+}
+
+impl<P: AsRef<Path>> From<P> for FileConsensusConfig {
+    fn from(base_dir: P) -> Self {
+        FileConsensusConfig {
+            base_dir: base_dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// Implementation of [Consensus] backed by files.
+///
+/// The versions of each key are stored in an append-only log, which is
+/// fsync'd before a write is acknowledged. All access to a key is serialized
+/// by an advisory lock, so a directory can be shared by every process on a
+/// single machine. It must not be on a network filesystem.
+#[derive(Debug)]
+pub struct FileConsensus {
+    base_dir: PathBuf,
+    /// The parsed contents of each key's log, so that a read only has to
+    /// parse what was appended since the last one.
+    cache: Arc<Mutex<BTreeMap<String, CachedLog>>>,
+}
+
+impl FileConsensus {
+    /// Opens the given location for non-exclusive read-write access.
+    pub async fn open(config: FileConsensusConfig) -> Result<Self, ExternalError> {
+        let base_dir = config.base_dir;
+        fs::create_dir_all(&base_dir).await.map_err(Error::from)?;
+        Ok(FileConsensus {
+            base_dir,
+            cache: Arc::default(),
+        })
+    }
+
+    /// Runs `f` on the log for `key` while holding its lock.
+    ///
+    /// The lock is shared with other readers unless `exclusive` is true. The
+    /// filesystem calls are all blocking, so this happens off of the async
+    /// runtime.
+    async fn with_log<R, F>(&self, key: &str, exclusive: bool, f: F) -> Result<R, ExternalError>
+    where
+        R: Send + 'static,
+        F: FnOnce(&ConsensusLog) -> Result<R, ExternalError> + Send + 'static,
+    {
+        let log = ConsensusLog::new(&self.base_dir, key, Arc::clone(&self.cache));
+        mz_ore::task::spawn_blocking(
+            || "persist::file_consensus",
+            move || {
+                let _lock = log.lock(exclusive)?;
+                f(&log)
+            },
+        )
+        .await?
+    }
+}
+
+#[async_trait]
+impl Consensus for FileConsensus {
+    async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+        self.with_log(key, false, |log| {
+            let (mut versions, _) = log.read(false)?;
+            Ok(versions.pop())
+        })
+        .await
+    }
+
+    async fn compare_and_set(
+        &self,
+        key: &str,
+        expected: Option<SeqNo>,
+        new: VersionedData,
+    ) -> Result<Result<(), Vec<VersionedData>>, ExternalError> {
+        if let Some(expected) = expected {
+            if new.seqno <= expected {
+                return Err(ExternalError::from(anyhow!(
+                    "new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                    new.seqno,
+                    expected
+                )));
+            }
+        }
+        if new.seqno.0 > i64::MAX.try_into().expect("i64::MAX known to fit in u64") {
+            return Err(ExternalError::from(anyhow!(
+                "sequence numbers must fit within [0, i64::MAX], received: {:?}",
+                new.seqno
+            )));
+        }
+
+        self.with_log(key, true, move |log| {
+            let (versions, valid_len) = log.read(true)?;
+            let current = versions.last().map(|x| x.seqno);
+            if current != expected {
+                let from = expected.map_or_else(SeqNo::minimum, |x| x.next());
+                let versions = versions.into_iter().filter(|x| x.seqno >= from).collect();
+                return Ok(Err(versions));
+            }
+            log.append(valid_len, &new)?;
+            Ok(Ok(()))
+        })
+        .await
+    }
+
+    async fn scan(&self, key: &str, from: SeqNo) -> Result<Vec<VersionedData>, ExternalError> {
+        self.with_log(key, false, move |log| {
+            let (versions, _) = log.read(false)?;
+            Ok(versions.into_iter().filter(|x| x.seqno >= from).collect())
+        })
+        .await
+    }
+
+    async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError> {
+        self.with_log(key, true, move |log| {
+            let (versions, _) = log.read(true)?;
+            if versions.last().map_or(true, |x| x.seqno < seqno) {
+                return Err(ExternalError::from(anyhow!(
+                    "upper bound too high for truncate: {:?}",
+                    seqno
+                )));
+            }
+            let count_before = versions.len();
+            let versions = versions
+                .into_iter()
+                .filter(|x| x.seqno >= seqno)
+                .collect::<Vec<_>>();
+            let deleted = count_before - versions.len();
+            if deleted > 0 {
+                log.rewrite(&versions)?;
+            }
+            Ok(deleted)
+        })
+        .await
+    }
+}
+
+/// The files storing the versions of a single key of a [FileConsensus].
+///
+/// The log is a sequence of records, each made up of a version's seqno and the
+/// length of its data as big-endian u64s, a big-endian CRC-32 of those and the
+/// data, and then the data itself. The seqnos of the records are strictly
+/// increasing.
+///
+/// A write that was interrupted by a crash can leave a partial or corrupt
+/// record at the end. It was never acknowledged, so it and everything after
+/// it are ignored, and removed by the next writer.
+#[derive(Debug)]
+struct ConsensusLog {
+    base_dir: PathBuf,
+    key: String,
+    log: PathBuf,
+    /// Truncating the log replaces it, so it can't be used to hold the lock.
+    lock: PathBuf,
+    cache: Arc<Mutex<BTreeMap<String, CachedLog>>>,
+}
+
+/// The versions in a prefix of a [ConsensusLog].
+#[derive(Debug)]
+struct CachedLog {
+    /// The log file that the versions were read from.
+    ///
+    /// Holding it open keeps its inode from being reused, so the log is the
+    /// same file if and only if it has the same inode. The log is only ever
+    /// appended to or replaced by a new file, and a writer only truncates
+    /// it to the end of its complete records, so the cached prefix of the
+    /// same file never changes.
+    file: std::fs::File,
+    versions: Vec<VersionedData>,
+    /// The length of the prefix of the log made up of complete records.
+    valid_len: u64,
+}
+
+impl ConsensusLog {
+    const HEADER_LEN: usize = 2 * std::mem::size_of::<u64>() + std::mem::size_of::<u32>();
+
+    fn new(base_dir: &Path, key: &str, cache: Arc<Mutex<BTreeMap<String, CachedLog>>>) -> Self {
+        let file_key = FileBlob::replace_forward_slashes(key);
+        ConsensusLog {
+            base_dir: base_dir.to_path_buf(),
+            key: key.to_owned(),
+            log: base_dir.join(format!("{}.log", file_key)),
+            lock: base_dir.join(format!("{}.lock", file_key)),
+            cache,
+        }
+    }
+
+    /// Takes the lock for this key, which is held until the returned file is
+    /// closed.
+    fn lock(&self, exclusive: bool) -> Result<std::fs::File, ExternalError> {
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .open(&self.lock)?;
+        let op = if exclusive {
+            libc::LOCK_EX
+        } else {
+            libc::LOCK_SH
+        };
+        loop {
+            // SAFETY: `file` is open for the duration of the call.
+            if unsafe { libc::flock(file.as_raw_fd(), op) } == 0 {
+                return Ok(file);
+            }
+            let err = std::io::Error::last_os_error();
+            if err.kind() != ErrorKind::Interrupted {
+                return Err(err.into());
+            }
+        }
+    }
+
+    /// Returns the versions in the log, along with the length of the prefix of
+    /// the log made up of complete records.
+    ///
+    /// Only the part of the log appended since the last read is parsed. If the
+    /// lock is held `exclusive`ly, any invalid tail of the log is removed.
+    fn read(&self, exclusive: bool) -> Result<(Vec<VersionedData>, u64), ExternalError> {
+        let mut file = match std::fs::OpenOptions::new()
+            .read(true)
+            .write(exclusive)
+            .open(&self.log)
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                self.cache.lock().expect("lock poisoned").remove(&self.key);
+                return Ok((Vec::new(), 0));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let cached = self.cache.lock().expect("lock poisoned").remove(&self.key);
+        let metadata = file.metadata()?;
+        let (mut versions, mut valid_len) = match cached {
+            Some(cached) => {
+                let cached_metadata = cached.file.metadata()?;
+                if (cached_metadata.dev(), cached_metadata.ino())
+                    == (metadata.dev(), metadata.ino())
+                {
+                    (cached.versions, cached.valid_len)
+                } else {
+                    (Vec::new(), 0)
+                }
+            }
+            None => (Vec::new(), 0),
+        };
+
+        let mut buf = Vec::new();
+        file.seek(SeekFrom::Start(valid_len))?;
+        file.read_to_end(&mut buf)?;
+        let mut remaining = &buf[..];
+        while let Some((version, len)) = Self::decode(remaining) {
+            if let Some(prev) = versions.last() {
+                if version.seqno <= prev.seqno {
+                    return Err(ExternalError::from(anyhow!(
+                        "consensus log {} has seqno {:?} after {:?}",
+                        self.log.display(),
+                        version.seqno,
+                        prev.seqno
+                    )));
+                }
+            }
+            versions.push(version);
+            valid_len += u64::cast_from(len);
+            remaining = &remaining[len..];
+        }
+
+        if !remaining.is_empty() {
+            warn!(
+                "consensus log {} has {} bytes of invalid records after offset {}",
+                self.log.display(),
+                remaining.len(),
+                valid_len
+            );
+            if exclusive {
+                file.set_len(valid_len)?;
+                file.sync_all()?;
+            }
+        }
+        self.cache.lock().expect("lock poisoned").insert(
+            self.key.clone(),
+            CachedLog {
+                file,
+                versions: versions.clone(),
+                valid_len,
+            },
+        );
+        Ok((versions, valid_len))
+    }
+
+    /// Appends `new` to the first `valid_len` bytes of the log, durably.
+    fn append(&self, valid_len: u64, new: &VersionedData) -> Result<(), ExternalError> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .open(&self.log)?;
+        // Drop any partial record left behind by a crash.
+        file.set_len(valid_len)?;
+        file.seek(SeekFrom::Start(valid_len))?;
+        file.write_all(&Self::encode(std::slice::from_ref(new)))?;
+        file.sync_all()?;
+        if valid_len == 0 {
+            // The log may have just been created, so make sure its directory
+            // entry is durable too.
+            std::fs::File::open(&self.base_dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Durably replaces the contents of the log with `versions`.
+    fn rewrite(&self, versions: &[VersionedData]) -> Result<(), ExternalError> {
+        let tmp = self.log.with_extension("log.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&Self::encode(versions))?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.log)?;
+        std::fs::File::open(&self.base_dir)?.sync_all()?;
+        Ok(())
+    }
+
+    fn encode(versions: &[VersionedData]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for version in versions {
+            let seqno = version.seqno.0.to_be_bytes();
+            let len = u64::cast_from(version.data.len()).to_be_bytes();
+            buf.extend_from_slice(&seqno);
+            buf.extend_from_slice(&len);
+            buf.extend_from_slice(&Self::checksum(&seqno, &len, &version.data).to_be_bytes());
+            buf.extend_from_slice(&version.data);
+        }
+        buf
+    }
+
+    /// Decodes the record at the start of `buf`, returning it along with its
+    /// encoded length, or None if `buf` doesn't start with a complete record
+    /// with a matching checksum.
+    fn decode(buf: &[u8]) -> Option<(VersionedData, usize)> {
+        if buf.len() < Self::HEADER_LEN {
+            return None;
+        }
+        let (header, rest) = buf.split_at(Self::HEADER_LEN);
+        let (seqno, header) = header.split_at(8);
+        let (len, checksum) = header.split_at(8);
+        let data_len = u64::from_be_bytes(len.try_into().expect("8 bytes"));
+        let data = match usize::try_from(data_len) {
+            Ok(data_len) if data_len <= rest.len() => &rest[..data_len],
+            _ => return None,
+        };
+        let checksum = u32::from_be_bytes(checksum.try_into().expect("4 bytes"));
+        if checksum != Self::checksum(seqno, len, data) {
+            return None;
+        }
+        let version = VersionedData {
+            seqno: SeqNo(u64::from_be_bytes(seqno.try_into().expect("8 bytes"))),
+            data: Bytes::copy_from_slice(data),
+        };
+        Some((version, Self::HEADER_LEN + data.len()))
+    }
+
+    fn checksum(seqno: &[u8], len: &[u8], data: &[u8]) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(seqno);
+        hasher.update(len);
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::blob_impl_test;
+    use crate::location::tests::{blob_impl_test, consensus_impl_test};
 
     use super::*;
 
@@ -253,4 +630,132 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn file_consensus() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let config = FileConsensusConfig::from(temp_dir.path());
+        consensus_impl_test(|| FileConsensus::open(config.clone())).await
+    }
+
+    #[tokio::test]
+    async fn file_consensus_durability() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let config = FileConsensusConfig::from(temp_dir.path());
+        let version = |seqno| VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(format!("v{}", seqno)),
+        };
+
+        let consensus = FileConsensus::open(config.clone()).await?;
+        assert_eq!(
+            consensus.compare_and_set("a/b", None, version(1)).await?,
+            Ok(())
+        );
+        assert_eq!(
+            consensus
+                .compare_and_set("a/b", Some(SeqNo(1)), version(2))
+                .await?,
+            Ok(())
+        );
+        drop(consensus);
+
+        // A reopened instance sees the previous writes.
+        let consensus = FileConsensus::open(config.clone()).await?;
+        assert_eq!(
+            consensus.scan("a/b", SeqNo(0)).await?,
+            vec![version(1), version(2)]
+        );
+
+        // A partial record, as left behind by a crash mid-write, is ignored
+        // and then overwritten by the next write.
+        let log = ConsensusLog::new(temp_dir.path(), "a/b", Arc::default());
+        let append = |bytes: &[u8]| -> Result<(), ExternalError> {
+            let mut file = std::fs::OpenOptions::new().append(true).open(&log.log)?;
+            file.write_all(bytes)?;
+            Ok(())
+        };
+        append(&ConsensusLog::encode(&[version(3)])[..10])?;
+        assert_eq!(consensus.head("a/b").await?, Some(version(2)));
+        assert_eq!(
+            consensus
+                .compare_and_set("a/b", Some(SeqNo(2)), version(4))
+                .await?,
+            Ok(())
+        );
+        assert_eq!(consensus.truncate("a/b", SeqNo(2)).await?, 1);
+        assert_eq!(
+            consensus.scan("a/b", SeqNo(0)).await?,
+            vec![version(2), version(4)]
+        );
+
+        // So is a complete record with a bad checksum, which is removed as
+        // soon as the lock is held exclusively.
+        let valid_len = std::fs::metadata(&log.log)?.len();
+        let mut corrupt = ConsensusLog::encode(&[version(5)]);
+        *corrupt.last_mut().expect("non-empty") ^= 1;
+        append(&corrupt)?;
+        assert_eq!(consensus.head("a/b").await?, Some(version(4)));
+        assert_eq!(
+            std::fs::metadata(&log.log)?.len(),
+            valid_len + u64::cast_from(corrupt.len())
+        );
+        assert_eq!(
+            consensus
+                .compare_and_set("a/b", Some(SeqNo(3)), version(5))
+                .await?,
+            Err(vec![version(4)])
+        );
+        assert_eq!(std::fs::metadata(&log.log)?.len(), valid_len);
+        assert_eq!(
+            consensus.scan("a/b", SeqNo(0)).await?,
+            vec![version(2), version(4)]
+        );
+
+        // A record whose seqno doesn't increase is an error.
+        append(&ConsensusLog::encode(&[version(4)]))?;
+        assert!(consensus.head("a/b").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_consensus_cache() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let config = FileConsensusConfig::from(temp_dir.path());
+        let version = |seqno| VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(format!("v{}", seqno)),
+        };
+
+        // Each instance caches the logs it reads, which stay up to date with
+        // the writes of the other.
+        let a = FileConsensus::open(config.clone()).await?;
+        let b = FileConsensus::open(config).await?;
+        assert_eq!(b.head("k").await?, None);
+        assert_eq!(a.compare_and_set("k", None, version(1)).await?, Ok(()));
+        assert_eq!(b.head("k").await?, Some(version(1)));
+        assert_eq!(
+            a.compare_and_set("k", Some(SeqNo(1)), version(2)).await?,
+            Ok(())
+        );
+        assert_eq!(b.head("k").await?, Some(version(2)));
+        assert_eq!(
+            b.compare_and_set("k", Some(SeqNo(2)), version(3)).await?,
+            Ok(())
+        );
+        assert_eq!(
+            a.scan("k", SeqNo(0)).await?,
+            vec![version(1), version(2), version(3)]
+        );
+
+        // Truncation replaces the log, which invalidates the caches.
+        assert_eq!(b.truncate("k", SeqNo(3)).await?, 2);
+        assert_eq!(a.scan("k", SeqNo(0)).await?, vec![version(3)]);
+        assert_eq!(
+            a.compare_and_set("k", Some(SeqNo(3)), version(4)).await?,
+            Ok(())
+        );
+        assert_eq!(b.scan("k", SeqNo(0)).await?, vec![version(3), version(4)]);
+        Ok(())
+    }
 }
@@ -16,6 +16,7 @@ use std::{cmp, fmt};
 
 use arrow2::buffer::Buffer;
 use arrow2::types::Index;
+use mz_persist_types::columnar::Part;
 use mz_persist_types::Codec64;
 
 pub mod arrow;
@@ -80,6 +81,9 @@ const BYTES_PER_KEY_VAL_OFFSET: usize = 4;
 /// - val_offsets.last().unwrap() == val_data.len()
 /// - timestamps.len() == len
 /// - diffs.len() == len
+/// - key_structured.len() == len, if present
+/// - key_structured is present, if key_codec_elided
+/// - key_data is empty, if key_codec_elided
 #[derive(Clone, PartialEq)]
 pub struct ColumnarRecords {
     len: usize,
@@ -89,6 +93,12 @@ pub struct ColumnarRecords {
     val_offsets: Buffer<i32>,
     timestamps: Buffer<i64>,
     diffs: Buffer<i64>,
+    /// An optional structured columnar encoding of the keys, stored
+    /// alongside the [mz_persist_types::Codec] encoded ones.
+    key_structured: Option<Part>,
+    /// Whether the [mz_persist_types::Codec] encoded keys were dropped in
+    /// favor of the structured ones, in which case every key reads as empty.
+    key_codec_elided: bool,
 }
 
 impl fmt::Debug for ColumnarRecords {
@@ -134,6 +144,82 @@ impl ColumnarRecords {
     pub fn iter<'a>(&'a self) -> ColumnarRecordsIter<'a> {
         self.borrow().iter()
     }
+
+    /// The structured columnar encoding of the keys, if there is one.
+    pub fn key_structured(&self) -> Option<&Part> {
+        self.key_structured.as_ref()
+    }
+
+    /// Attaches a structured columnar encoding of the keys in Self.
+    ///
+    /// Returns an error if `part` doesn't have one value per record.
+    pub fn set_key_structured(&mut self, part: Part) -> Result<(), String> {
+        if part.len() != self.len {
+            return Err(format!(
+                "expected structured keys with length {} got {}",
+                self.len,
+                part.len()
+            ));
+        }
+        self.key_structured = Some(part);
+        Ok(())
+    }
+
+    /// Whether the [mz_persist_types::Codec] encoded keys were dropped by
+    /// [Self::elide_key_codec] and must be recovered from the structured ones.
+    pub fn key_codec_elided(&self) -> bool {
+        self.key_codec_elided
+    }
+
+    /// Drops the [mz_persist_types::Codec] encoded keys, which must be
+    /// redundant with the structured ones, so that they aren't stored twice.
+    ///
+    /// Afterward, every key in Self reads as empty until restored with
+    /// [Self::restore_key_codec]. Returns an error if Self has no structured
+    /// keys.
+    pub fn elide_key_codec(&mut self) -> Result<(), String> {
+        if self.key_structured.is_none() {
+            return Err("cannot elide keys without structured keys".into());
+        }
+        self.key_data = Buffer::from(Vec::new());
+        self.key_offsets = Buffer::from(vec![0; self.len + 1]);
+        self.key_codec_elided = true;
+        Ok(())
+    }
+
+    /// Restores the [mz_persist_types::Codec] encoded keys dropped by
+    /// [Self::elide_key_codec], by calling `encode` with the index of each
+    /// record and a buffer to fill with its key.
+    ///
+    /// This is a no-op if the keys weren't elided.
+    pub fn restore_key_codec<F>(&mut self, mut encode: F) -> Result<(), String>
+    where
+        F: FnMut(usize, &mut Vec<u8>) -> Result<(), String>,
+    {
+        if !self.key_codec_elided {
+            return Ok(());
+        }
+        let mut key_data = Vec::new();
+        let mut key_offsets = Vec::with_capacity(self.len + 1);
+        key_offsets.push(0);
+        for idx in 0..self.len {
+            encode(idx, &mut key_data)?;
+            let offset =
+                i32::try_from(key_data.len()).map_err(|_| "restored keys exceed 2GB".to_owned())?;
+            key_offsets.push(offset);
+        }
+        let key_data_size = key_offsets.len() * BYTES_PER_KEY_VAL_OFFSET + key_data.len();
+        if key_data_size > KEY_VAL_DATA_MAX_LEN {
+            return Err(format!(
+                "restored keys are {} bytes, more than the max of {}",
+                key_data_size, KEY_VAL_DATA_MAX_LEN
+            ));
+        }
+        self.key_data = Buffer::from(key_data);
+        self.key_offsets = Buffer::from(key_offsets);
+        self.key_codec_elided = false;
+        Ok(())
+    }
 }
 
 // TODO: deduplicate this with the other FromIterator implementation.
@@ -494,6 +580,8 @@ impl ColumnarRecordsBuilder {
             val_offsets: Buffer::from(self.val_offsets),
             timestamps: Buffer::from(self.timestamps),
             diffs: Buffer::from(self.diffs),
+            key_structured: None,
+            key_codec_elided: false,
         };
         debug_assert_eq!(ret.borrow().validate(), Ok(()));
         ret
@@ -236,6 +236,8 @@ pub fn decode_arrow_batch_kvtd(x: &Chunk<Box<dyn Array>>) -> Result<ColumnarReco
         val_offsets,
         timestamps,
         diffs,
+        key_structured: None,
+        key_codec_elided: false,
     };
     ret.borrow().validate()?;
     Ok(ret)
@@ -11,11 +11,14 @@
 
 use std::io::{Read, Seek, Write};
 
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::Field;
 use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
 use arrow2::io::parquet::write::{
     CompressionOptions, Encoding, FileWriter, KeyValue, RowGroupIterator, Version, WriteOptions,
 };
 use differential_dataflow::trace::Description;
+use mz_persist_types::columnar::Part;
 use mz_persist_types::Codec64;
 use timely::progress::{Antichain, Timestamp};
 
@@ -31,6 +34,13 @@ use crate::indexed::encoding::{
 
 const INLINE_METADATA_KEY: &str = "MZ:inline";
 
+/// Present when the [mz_persist_types::Codec] encoded keys were elided in
+/// favor of the structured ones.
+const KEY_CODEC_ELIDED_METADATA_KEY: &str = "MZ:key_codec_elided";
+
+/// The prefix of the names of the columns storing structured keys.
+const KEY_STRUCTURED_PREFIX: &str = "k_s.";
+
 /// Encodes an BlobTraceBatchPart into the Parquet format.
 pub fn encode_trace_parquet<W: Write, T: Timestamp + Codec64>(
     w: &mut W,
@@ -50,10 +60,11 @@ pub fn decode_trace_parquet<R: Read + Seek, T: Timestamp + Codec64>(
     r: &mut R,
 ) -> Result<BlobTraceBatchPart<T>, Error> {
     let metadata = read_metadata(r).map_err(|err| err.to_string())?;
-    let metadata = metadata
-        .key_value_metadata()
-        .as_ref()
-        .and_then(|x| x.iter().find(|x| x.key == INLINE_METADATA_KEY));
+    let metadata = metadata.key_value_metadata().as_deref().unwrap_or_default();
+    let key_codec_elided = metadata
+        .iter()
+        .any(|x| x.key == KEY_CODEC_ELIDED_METADATA_KEY);
+    let metadata = metadata.iter().find(|x| x.key == INLINE_METADATA_KEY);
     let (format, meta) = decode_trace_inline_meta(metadata.and_then(|x| x.value.as_ref()))?;
 
     let updates = match format {
@@ -61,7 +72,7 @@ pub fn decode_trace_parquet<R: Read + Seek, T: Timestamp + Codec64>(
         ProtoBatchFormat::ArrowKvtd => {
             return Err("ArrowKVTD format not supported in parquet".into())
         }
-        ProtoBatchFormat::ParquetKvtd => decode_parquet_file_kvtd(r)?,
+        ProtoBatchFormat::ParquetKvtd => decode_parquet_file_kvtd(r, key_codec_elided)?,
     };
 
     let ret = BlobTraceBatchPart {
@@ -87,30 +98,57 @@ fn encode_parquet_kvtd<W: Write>(
     inline_base64: String,
     iter: &[ColumnarRecords],
 ) -> Result<(), Error> {
-    let iter = iter.into_iter().map(|x| Ok(encode_arrow_batch_kvtd(x)));
+    // Structured keys are only written if every chunk has them with the same
+    // columns, because all row groups in a file share one schema.
+    let key_structured_fields = key_structured_fields(iter);
+    // The same goes for eliding the Codec encoded keys, which is only possible
+    // when the structured ones are written.
+    let key_codec_elided = iter.iter().any(|x| x.key_codec_elided());
+    if key_codec_elided
+        && (key_structured_fields.is_none() || !iter.iter().all(|x| x.key_codec_elided()))
+    {
+        return Err("cannot write elided keys without structured keys".into());
+    }
+    let mut schema = (**SCHEMA_ARROW_KVTD).clone();
+    if let Some(fields) = key_structured_fields.as_ref() {
+        schema.fields.extend(fields.iter().cloned());
+    }
+
+    let iter = iter.into_iter().map(|x| {
+        let chunk = encode_arrow_batch_kvtd(x);
+        match (key_structured_fields.as_ref(), x.key_structured()) {
+            (Some(_), Some(part)) => {
+                let mut columns = chunk.into_arrays();
+                columns.extend(part.columns().iter().cloned());
+                Ok(Chunk::new(columns))
+            }
+            _ => Ok(chunk),
+        }
+    });
 
     let options = WriteOptions {
         write_statistics: false,
         compression: CompressionOptions::Uncompressed,
         version: Version::V2,
     };
-    let row_groups = RowGroupIterator::try_new(
-        iter,
-        &SCHEMA_ARROW_KVTD,
-        options,
-        vec![
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-        ],
-    )?;
-
-    let metadata = vec![KeyValue {
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|_| vec![Encoding::Plain])
+        .collect();
+    let row_groups = RowGroupIterator::try_new(iter, &schema, options, encodings)?;
+
+    let mut metadata = vec![KeyValue {
         key: INLINE_METADATA_KEY.into(),
         value: Some(inline_base64),
     }];
-    let mut writer = FileWriter::try_new(w, (**SCHEMA_ARROW_KVTD).clone(), options)?;
+    if key_codec_elided {
+        metadata.push(KeyValue {
+            key: KEY_CODEC_ELIDED_METADATA_KEY.into(),
+            value: None,
+        });
+    }
+    let mut writer = FileWriter::try_new(w, schema, options)?;
     for group in row_groups {
         writer.write(group?).map_err(|err| err.to_string())?;
     }
@@ -119,24 +157,74 @@ fn encode_parquet_kvtd<W: Write>(
     Ok(())
 }
 
-fn decode_parquet_file_kvtd<R: Read + Seek>(r: &mut R) -> Result<Vec<ColumnarRecords>, Error> {
+/// Returns the fields, prefixed with [KEY_STRUCTURED_PREFIX], of the
+/// structured keys shared by every chunk in `iter`, if there are any.
+fn key_structured_fields(iter: &[ColumnarRecords]) -> Option<Vec<Field>> {
+    let (first, rest) = iter.split_first()?;
+    let fields = first.key_structured()?.fields();
+    let all_match = rest
+        .iter()
+        .all(|x| x.key_structured().map(|x| x.fields()) == Some(fields));
+    if !all_match {
+        return None;
+    }
+    let fields = fields
+        .iter()
+        .map(|x| {
+            let mut x = x.clone();
+            x.name = format!("{}{}", KEY_STRUCTURED_PREFIX, x.name);
+            x
+        })
+        .collect();
+    Some(fields)
+}
+
+fn decode_parquet_file_kvtd<R: Read + Seek>(
+    r: &mut R,
+    key_codec_elided: bool,
+) -> Result<Vec<ColumnarRecords>, Error> {
     let metadata = read_metadata(r)?;
     let schema = infer_schema(&metadata)?;
     let reader = FileReader::new(r, metadata.row_groups, schema, None, None, None);
 
     let file_schema = reader.schema().fields.as_slice();
     // We're not trying to accept any sort of user created data, so be strict.
-    if file_schema != SCHEMA_ARROW_KVTD.fields {
+    // The only thing allowed after the KVTD columns are structured keys.
+    let kvtd_len = SCHEMA_ARROW_KVTD.fields.len();
+    if file_schema.len() < kvtd_len
+        || file_schema[..kvtd_len] != SCHEMA_ARROW_KVTD.fields
+        || file_schema[kvtd_len..]
+            .iter()
+            .any(|x| !x.name.starts_with(KEY_STRUCTURED_PREFIX))
+    {
         return Err(format!(
             "expected arrow schema {:?} got: {:?}",
             SCHEMA_ARROW_KVTD.fields, file_schema
         )
         .into());
     }
+    let key_structured_fields = file_schema[kvtd_len..]
+        .iter()
+        .map(|x| {
+            let mut x = x.clone();
+            x.name = x.name[KEY_STRUCTURED_PREFIX.len()..].to_owned();
+            x
+        })
+        .collect::<Vec<_>>();
 
     let mut ret = Vec::new();
     for batch in reader {
-        ret.push(decode_arrow_batch_kvtd(&batch?)?);
+        let mut columns = batch?.into_arrays();
+        let key_structured = columns.split_off(kvtd_len);
+        let mut records = decode_arrow_batch_kvtd(&Chunk::new(columns))?;
+        if !key_structured_fields.is_empty() {
+            let part = Part::new(records.len(), key_structured_fields.clone(), key_structured)?;
+            records.set_key_structured(part)?;
+        }
+        if key_codec_elided {
+            records.elide_key_codec()?;
+        }
+        ret.push(records);
     }
     Ok(ret)
 }
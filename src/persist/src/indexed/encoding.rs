@@ -348,7 +348,10 @@ pub fn decode_trace_inline_meta(
 mod tests {
     use std::sync::Arc;
 
+    use arrow2::array::{Array, PrimitiveArray};
+    use arrow2::datatypes::{DataType, Field};
     use bytes::Bytes;
+    use mz_persist_types::columnar::Part;
 
     use crate::error::Error;
     use crate::indexed::columnar::ColumnarRecordsVec;
@@ -639,4 +642,58 @@ mod tests {
             "1/1=1027 25/1=2778 1000/1=73022 1000/100=113067"
         );
     }
+
+    #[test]
+    fn trace_batch_key_structured_roundtrip() {
+        fn with_key_structured(mut records: ColumnarRecords) -> ColumnarRecords {
+            let keys = records
+                .iter()
+                .map(|((k, _), _, _)| i64::try_from(k.len()).expect("key len fits in i64"))
+                .collect::<Vec<_>>();
+            let part = Part::new(
+                keys.len(),
+                vec![Field::new("len", DataType::Int64, false)],
+                vec![Box::new(PrimitiveArray::<i64>::from_vec(keys)) as Box<dyn Array>],
+            )
+            .expect("valid part");
+            records.set_key_structured(part).expect("valid len");
+            records
+        }
+        fn roundtrip(updates: Vec<ColumnarRecords>) -> Vec<ColumnarRecords> {
+            let batch = BlobTraceBatchPart {
+                desc: u64_desc(0, 3),
+                index: 0,
+                updates,
+            };
+            let mut buf = Vec::new();
+            batch.encode(&mut buf);
+            BlobTraceBatchPart::<u64>::decode(&buf)
+                .expect("valid batch")
+                .updates
+        }
+
+        let chunks = || {
+            vec![
+                columnar_records(vec![update_with_key(0, "a"), update_with_key(1, "bb")]),
+                columnar_records(vec![update_with_key(2, "ccc")]),
+            ]
+        };
+
+        // Structured keys are written when every chunk has them.
+        let updates = chunks()
+            .into_iter()
+            .map(with_key_structured)
+            .collect::<Vec<_>>();
+        assert_eq!(roundtrip(updates.clone()), updates);
+
+        // Otherwise, they're dropped.
+        let mut updates = chunks();
+        updates[0] = with_key_structured(updates[0].clone());
+        assert_eq!(roundtrip(updates), chunks());
+
+        // Structured keys must have one value per record.
+        let mut records = chunks().remove(0);
+        let part = Part::new(0, vec![], vec![]).expect("valid part");
+        assert!(records.set_key_structured(part).is_err());
+    }
 }
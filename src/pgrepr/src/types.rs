@@ -83,6 +83,8 @@ pub enum Type {
     Json,
     /// A binary JSON blob.
     Jsonb,
+    /// A SQL/JSON path expression.
+    JsonPath,
     /// A sequence of homogeneous values.
     List(Box<Type>),
     /// A map with text keys and homogeneous values.
@@ -145,6 +147,11 @@ pub enum Type {
     Int2Vector,
     /// A Materialize timestamp.
     MzTimestamp,
+    /// A range of values of the inner type.
+    Range {
+        /// The type of the range's bounds.
+        element_type: Box<Type>,
+    },
 }
 
 /// An unpacked [`typmod`](Type::typmod) for a [`Type`].
@@ -494,6 +501,7 @@ impl Type {
             postgres_types::Type::INTERVAL => Type::Interval { constraints: None },
             postgres_types::Type::JSON => Type::Json,
             postgres_types::Type::JSONB => Type::Jsonb,
+            postgres_types::Type::JSONPATH => Type::JsonPath,
             postgres_types::Type::NUMERIC => Type::Numeric { constraints: None },
             postgres_types::Type::OID => Type::Oid,
             postgres_types::Type::TEXT => Type::Text,
@@ -525,6 +533,7 @@ impl Type {
             }
             postgres_types::Type::JSON_ARRAY => Type::Array(Box::new(Type::Json)),
             postgres_types::Type::JSONB_ARRAY => Type::Array(Box::new(Type::Jsonb)),
+            postgres_types::Type::JSONPATH_ARRAY => Type::Array(Box::new(Type::JsonPath)),
             postgres_types::Type::NUMERIC_ARRAY => {
                 Type::Array(Box::new(Type::Numeric { constraints: None }))
             }
@@ -551,6 +560,42 @@ impl Type {
             postgres_types::Type::REGTYPE_ARRAY => Type::Array(Box::new(Type::RegType)),
             postgres_types::Type::INT2_VECTOR => Type::Int2Vector,
             postgres_types::Type::INT2_VECTOR_ARRAY => Type::Array(Box::new(Type::Int2Vector)),
+            postgres_types::Type::INT4_RANGE => Type::Range {
+                element_type: Box::new(Type::Int4),
+            },
+            postgres_types::Type::INT8_RANGE => Type::Range {
+                element_type: Box::new(Type::Int8),
+            },
+            postgres_types::Type::NUM_RANGE => Type::Range {
+                element_type: Box::new(Type::Numeric { constraints: None }),
+            },
+            postgres_types::Type::DATE_RANGE => Type::Range {
+                element_type: Box::new(Type::Date),
+            },
+            postgres_types::Type::TS_RANGE => Type::Range {
+                element_type: Box::new(Type::Timestamp { precision: None }),
+            },
+            postgres_types::Type::TSTZ_RANGE => Type::Range {
+                element_type: Box::new(Type::TimestampTz { precision: None }),
+            },
+            postgres_types::Type::INT4_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::Int4),
+            })),
+            postgres_types::Type::INT8_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::Int8),
+            })),
+            postgres_types::Type::NUM_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::Numeric { constraints: None }),
+            })),
+            postgres_types::Type::DATE_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::Date),
+            })),
+            postgres_types::Type::TS_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::Timestamp { precision: None }),
+            })),
+            postgres_types::Type::TSTZ_RANGE_ARRAY => Type::Array(Box::new(Type::Range {
+                element_type: Box::new(Type::TimestampTz { precision: None }),
+            })),
             _ => return Err(TypeFromOidError::UnknownOid(oid)),
         };
 
@@ -616,6 +661,7 @@ impl Type {
                 Type::Interval { .. } => &postgres_types::Type::INTERVAL_ARRAY,
                 Type::Json => &postgres_types::Type::JSON_ARRAY,
                 Type::Jsonb => &postgres_types::Type::JSONB_ARRAY,
+                Type::JsonPath => &postgres_types::Type::JSONPATH_ARRAY,
                 Type::List(_) => unreachable!(),
                 Type::Map { .. } => unreachable!(),
                 Type::Numeric { .. } => &postgres_types::Type::NUMERIC_ARRAY,
@@ -634,6 +680,15 @@ impl Type {
                 Type::RegType => &postgres_types::Type::REGTYPE_ARRAY,
                 Type::Int2Vector => &postgres_types::Type::INT2_VECTOR_ARRAY,
                 Type::MzTimestamp => &MZ_TIMESTAMP_ARRAY,
+                Type::Range { element_type } => match **element_type {
+                    Type::Int4 => &postgres_types::Type::INT4_RANGE_ARRAY,
+                    Type::Int8 => &postgres_types::Type::INT8_RANGE_ARRAY,
+                    Type::Numeric { .. } => &postgres_types::Type::NUM_RANGE_ARRAY,
+                    Type::Date => &postgres_types::Type::DATE_RANGE_ARRAY,
+                    Type::Timestamp { .. } => &postgres_types::Type::TS_RANGE_ARRAY,
+                    Type::TimestampTz { .. } => &postgres_types::Type::TSTZ_RANGE_ARRAY,
+                    _ => unreachable!(),
+                },
             },
             Type::Bool => &postgres_types::Type::BOOL,
             Type::Bytea => &postgres_types::Type::BYTEA,
@@ -650,6 +705,7 @@ impl Type {
             Type::Interval { .. } => &postgres_types::Type::INTERVAL,
             Type::Json => &postgres_types::Type::JSON,
             Type::Jsonb => &postgres_types::Type::JSONB,
+            Type::JsonPath => &postgres_types::Type::JSONPATH,
             Type::List(_) => &LIST,
             Type::Map { .. } => &MAP,
             Type::Numeric { .. } => &postgres_types::Type::NUMERIC,
@@ -668,6 +724,15 @@ impl Type {
             Type::RegType => &postgres_types::Type::REGTYPE,
             Type::Int2Vector => &postgres_types::Type::INT2_VECTOR,
             Type::MzTimestamp => &MZ_TIMESTAMP,
+            Type::Range { element_type } => match **element_type {
+                Type::Int4 => &postgres_types::Type::INT4_RANGE,
+                Type::Int8 => &postgres_types::Type::INT8_RANGE,
+                Type::Numeric { .. } => &postgres_types::Type::NUM_RANGE,
+                Type::Date => &postgres_types::Type::DATE_RANGE,
+                Type::Timestamp { .. } => &postgres_types::Type::TS_RANGE,
+                Type::TimestampTz { .. } => &postgres_types::Type::TSTZ_RANGE,
+                _ => unreachable!(),
+            },
         }
     }
 
@@ -693,6 +758,7 @@ impl Type {
             &postgres_types::Type::INT8_ARRAY => "bigint[]",
             &postgres_types::Type::INTERVAL_ARRAY => "interval[]",
             &postgres_types::Type::JSONB_ARRAY => "jsonb[]",
+            &postgres_types::Type::JSONPATH_ARRAY => "jsonpath[]",
             &postgres_types::Type::NUMERIC_ARRAY => "numeric[]",
             &postgres_types::Type::OID_ARRAY => "oid[]",
             &postgres_types::Type::RECORD_ARRAY => "record[]",
@@ -770,6 +836,7 @@ impl Type {
             | Type::Interval { constraints: None }
             | Type::Json
             | Type::Jsonb
+            | Type::JsonPath
             | Type::List(_)
             | Type::Map { .. }
             | Type::Numeric { constraints: None }
@@ -786,6 +853,7 @@ impl Type {
             | Type::TimestampTz { precision: None }
             | Type::Uuid
             | Type::MzTimestamp
+            | Type::Range { .. }
             | Type::VarChar { max_length: None } => None,
         }
     }
@@ -810,6 +878,7 @@ impl Type {
             Type::Interval { .. } => 16,
             Type::Json => -1,
             Type::Jsonb => -1,
+            Type::JsonPath => -1,
             Type::List(_) => -1,
             Type::Map { .. } => -1,
             Type::Numeric { .. } => -1,
@@ -832,6 +901,7 @@ impl Type {
             Type::MzTimestamp => size_of::<mz_repr::Timestamp>()
                 .try_into()
                 .expect("must fit"),
+            Type::Range { .. } => -1,
         }
     }
 
@@ -882,6 +952,7 @@ impl TryFrom<&Type> for ScalarType {
             Type::Interval { .. } => Ok(ScalarType::Interval),
             Type::Json => Err(TypeConversionError::UnsupportedType(Type::Json)),
             Type::Jsonb => Ok(ScalarType::Jsonb),
+            Type::JsonPath => Ok(ScalarType::JsonPath),
             Type::List(t) => Ok(ScalarType::List {
                 element_type: Box::new(TryFrom::try_from(&**t)?),
                 custom_id: None,
@@ -950,6 +1021,9 @@ impl TryFrom<&Type> for ScalarType {
             Type::RegType => Ok(ScalarType::RegType),
             Type::Int2Vector => Ok(ScalarType::Int2Vector),
             Type::MzTimestamp => Ok(ScalarType::MzTimestamp),
+            Type::Range { element_type } => Ok(ScalarType::Range {
+                element_type: Box::new(TryFrom::try_from(&**element_type)?),
+            }),
         }
     }
 }
@@ -1060,6 +1134,7 @@ impl From<&ScalarType> for Type {
             ScalarType::UInt64 => Type::UInt8,
             ScalarType::Interval => Type::Interval { constraints: None },
             ScalarType::Jsonb => Type::Jsonb,
+            ScalarType::JsonPath => Type::JsonPath,
             ScalarType::List { element_type, .. } => {
                 Type::List(Box::new(From::from(&**element_type)))
             }
@@ -1098,6 +1173,9 @@ impl From<&ScalarType> for Type {
             ScalarType::RegType => Type::RegType,
             ScalarType::Int2Vector => Type::Int2Vector,
             ScalarType::MzTimestamp => Type::MzTimestamp,
+            ScalarType::Range { element_type } => Type::Range {
+                element_type: Box::new(From::from(&**element_type)),
+            },
         }
     }
 }
@@ -21,7 +21,9 @@ use uuid::Uuid;
 
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
+use mz_repr::adt::datetime::Timezone;
 use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::range::{self, Range, RangeBound};
 use mz_repr::strconv::{self, Nestable};
 use mz_repr::{Datum, RelationType, Row, RowArena, ScalarType};
 
@@ -70,6 +72,8 @@ pub enum Value {
     Interval(Interval),
     /// A binary JSON blob.
     Jsonb(Jsonb),
+    /// A SQL/JSON path expression, in its canonical form.
+    JsonPath(String),
     /// A sequence of homogeneous values.
     List(Vec<Option<Value>>),
     /// A map of string keys and homogeneous values.
@@ -101,6 +105,8 @@ pub enum Value {
     },
     /// A Materialize timestamp.
     MzTimestamp(mz_repr::Timestamp),
+    /// A contiguous range of values along a domain.
+    Range(Range<Box<Value>>),
 }
 
 impl Value {
@@ -142,6 +148,7 @@ impl Value {
             (_, ScalarType::Jsonb) => {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
+            (Datum::String(s), ScalarType::JsonPath) => Some(Value::JsonPath(s.to_owned())),
             (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
             (Datum::Array(array), ScalarType::Array(elem_type)) => {
                 let dims = array.dims().into_iter().collect();
@@ -184,6 +191,14 @@ impl Value {
                     .collect();
                 Some(Value::Map(entries))
             }
+            (Datum::Range(range), ScalarType::Range { element_type }) => {
+                Some(Value::Range(range.map(|bound| {
+                    Box::new(
+                        Value::from_datum(bound.datum(), element_type)
+                            .expect("range bounds cannot be NULL"),
+                    )
+                })))
+            }
             _ => panic!("can't serialize {}::{:?}", datum, typ),
         }
     }
@@ -215,6 +230,7 @@ impl Value {
             Value::UInt4(u) => Datum::UInt32(u),
             Value::UInt8(u) => Datum::UInt64(u),
             Value::Jsonb(js) => buf.push_unary_row(js.0.into_row()),
+            Value::JsonPath(s) => Datum::String(buf.push_string(s)),
             Value::List(elems) => {
                 let elem_pg_type = match typ {
                     Type::List(t) => &*t,
@@ -260,14 +276,35 @@ impl Value {
             Value::Uuid(u) => Datum::Uuid(u),
             Value::Numeric(n) => Datum::Numeric(n.0),
             Value::MzTimestamp(t) => Datum::MzTimestamp(t),
+            Value::Range(range) => {
+                let elem_pg_type = match typ {
+                    Type::Range { element_type } => &*element_type,
+                    _ => panic!("Value::Range should have type Type::Range. Found {:?}", typ),
+                };
+                let range = range.map(|bound| bound.into_datum(buf, elem_pg_type));
+                buf.make_datum(|packer| {
+                    packer
+                        .push_range(range)
+                        .expect("Value::Range is always valid")
+                })
+            }
         }
     }
 
     /// Serializes this value to `buf` in the specified `format`.
-    pub fn encode(&self, ty: &Type, format: Format, buf: &mut BytesMut) -> Result<(), io::Error> {
+    ///
+    /// Timestamps with time zones are rendered in the time zone `tz` when
+    /// using the text format.
+    pub fn encode(
+        &self,
+        ty: &Type,
+        format: Format,
+        tz: &Timezone,
+        buf: &mut BytesMut,
+    ) -> Result<(), io::Error> {
         match format {
             Format::Text => {
-                self.encode_text(buf);
+                self.encode_text_in(buf, tz);
                 Ok(())
             }
             Format::Binary => self.encode_binary(ty, buf),
@@ -277,11 +314,17 @@ impl Value {
     /// Serializes this value to `buf` using the [text encoding
     /// format](Format::Text).
     pub fn encode_text(&self, buf: &mut BytesMut) -> Nestable {
+        self.encode_text_in(buf, &Timezone::default())
+    }
+
+    /// Like [`Value::encode_text`], but renders timestamps with time zones in
+    /// the time zone `tz` rather than in UTC.
+    pub fn encode_text_in(&self, buf: &mut BytesMut, tz: &Timezone) -> Nestable {
         match self {
             Value::Array { dims, elements } => {
                 strconv::format_array(buf, dims, elements, |buf, elem| match elem {
                     None => Ok::<_, ()>(buf.write_null()),
-                    Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
+                    Some(elem) => Ok(elem.encode_text_in(buf.nonnull_buffer(), tz)),
                 })
                 .expect("provided closure never fails")
             }
@@ -290,7 +333,7 @@ impl Value {
                     Ok::<_, ()>(
                         elem.as_ref()
                             .expect("Int2Vector does not support NULL values")
-                            .encode_text(buf.nonnull_buffer()),
+                            .encode_text_in(buf.nonnull_buffer(), tz),
                     )
                 })
                 .expect("provided closure never fails")
@@ -312,29 +355,34 @@ impl Value {
             Value::Float4(f) => strconv::format_float32(buf, *f),
             Value::Float8(f) => strconv::format_float64(buf, *f),
             Value::Jsonb(js) => strconv::format_jsonb(buf, js.0.as_ref()),
+            Value::JsonPath(s) => strconv::format_jsonpath(buf, s),
             Value::List(elems) => strconv::format_list(buf, elems, |buf, elem| match elem {
                 None => Ok::<_, ()>(buf.write_null()),
-                Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
+                Some(elem) => Ok(elem.encode_text_in(buf.nonnull_buffer(), tz)),
             })
             .expect("provided closure never fails"),
             Value::Map(elems) => strconv::format_map(buf, elems, |buf, value| match value {
                 None => Ok::<_, ()>(buf.write_null()),
-                Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
+                Some(elem) => Ok(elem.encode_text_in(buf.nonnull_buffer(), tz)),
             })
             .expect("provided closure never fails"),
             Value::Oid(oid) => strconv::format_uint32(buf, *oid),
             Value::Record(elems) => strconv::format_record(buf, elems, |buf, elem| match elem {
                 None => Ok::<_, ()>(buf.write_null()),
-                Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
+                Some(elem) => Ok(elem.encode_text_in(buf.nonnull_buffer(), tz)),
             })
             .expect("provided closure never fails"),
             Value::Text(s) | Value::VarChar(s) | Value::BpChar(s) => strconv::format_string(buf, s),
             Value::Time(t) => strconv::format_time(buf, *t),
             Value::Timestamp(ts) => strconv::format_timestamp(buf, ts),
-            Value::TimestampTz(ts) => strconv::format_timestamptz(buf, ts),
+            Value::TimestampTz(ts) => strconv::format_timestamptz_in(buf, ts, tz),
             Value::Uuid(u) => strconv::format_uuid(buf, *u),
             Value::Numeric(d) => strconv::format_numeric(buf, &d.0),
             Value::MzTimestamp(t) => strconv::format_mz_timestamp(buf, *t),
+            Value::Range(range) => strconv::format_range(buf, range, |buf, elem| {
+                Ok::<_, ()>(elem.encode_text_in(buf.nonnull_buffer(), tz))
+            })
+            .expect("provided closure never fails"),
         }
     }
 
@@ -388,6 +436,11 @@ impl Value {
             }
             Value::Interval(iv) => iv.to_sql(&PgType::INTERVAL, buf),
             Value::Jsonb(js) => js.to_sql(&PgType::JSONB, buf),
+            Value::JsonPath(s) => {
+                buf.put_u8(1); // version
+                buf.put_slice(s.as_bytes());
+                Ok(IsNull::No)
+            }
             Value::List(_) => {
                 // A binary encoding for list is tricky. We only get one OID to
                 // describe the type of this list to the client. And we can't
@@ -450,6 +503,17 @@ impl Value {
             Value::Uuid(u) => u.to_sql(&PgType::UUID, buf),
             Value::Numeric(a) => a.to_sql(&PgType::NUMERIC, buf),
             Value::MzTimestamp(t) => t.to_string().to_sql(&PgType::TEXT, buf),
+            Value::Range(range) => {
+                let elem_type = match ty {
+                    Type::Range { element_type } => element_type,
+                    _ => unreachable!(),
+                };
+                buf.put_u8(range.flags());
+                for bound in [range.lower(), range.upper()].into_iter().flatten() {
+                    encode_element(buf, Some(bound), elem_type)?;
+                }
+                Ok(IsNull::No)
+            }
         }
         .expect("encode_binary should never trigger a to_sql failure");
         if let IsNull::Yes = is_null {
@@ -495,6 +559,7 @@ impl Value {
             Type::Interval { .. } => Value::Interval(Interval(strconv::parse_interval(s)?)),
             Type::Json => return Err("input of json types is not implemented".into()),
             Type::Jsonb => Value::Jsonb(Jsonb(strconv::parse_jsonb(s)?)),
+            Type::JsonPath => Value::JsonPath(strconv::parse_jsonpath(s)?.to_string()),
             Type::List(elem_type) => Value::List(strconv::parse_list(
                 s,
                 matches!(**elem_type, Type::List(..)),
@@ -522,6 +587,12 @@ impl Value {
             Type::TimestampTz { .. } => Value::TimestampTz(strconv::parse_timestamptz(s)?),
             Type::Uuid => Value::Uuid(Uuid::parse_str(s)?),
             Type::MzTimestamp => Value::MzTimestamp(strconv::parse_mz_timestamp(s)?),
+            Type::Range { element_type } => {
+                let range = strconv::parse_range(s, |elem_text| {
+                    Value::decode_text(element_type, elem_text.as_bytes()).map(Box::new)
+                })?;
+                return canonicalize_range(ty, range);
+            }
         })
     }
 
@@ -568,6 +639,13 @@ impl Value {
             Type::Interval { .. } => Interval::from_sql(ty.inner(), raw).map(Value::Interval),
             Type::Json => Err("input of json types is not implemented".into()),
             Type::Jsonb => Jsonb::from_sql(ty.inner(), raw).map(Value::Jsonb),
+            Type::JsonPath => {
+                if raw.first() != Some(&1) {
+                    return Err("unsupported jsonpath version number".into());
+                }
+                let s = str::from_utf8(&raw[1..])?;
+                Ok(Value::JsonPath(strconv::parse_jsonpath(s)?.to_string()))
+            }
             Type::List(_) => Err("binary decoding of list types is not implemented".into()),
             Type::Map { .. } => Err("binary decoding of map types is not implemented".into()),
             Type::Numeric { .. } => Numeric::from_sql(ty.inner(), raw).map(Value::Numeric),
@@ -596,10 +674,66 @@ impl Value {
                 let t: mz_repr::Timestamp = s.parse()?;
                 Ok(Value::MzTimestamp(t))
             }
+            Type::Range { element_type } => {
+                if !raw.has_remaining() {
+                    return Err("invalid buffer size".into());
+                }
+                let flags = raw.get_u8();
+                let range = if flags & range::flags::EMPTY != 0 {
+                    Range::empty()
+                } else {
+                    let mut decode_bound = |infinite_flag| {
+                        if flags & infinite_flag != 0 {
+                            return Ok(None);
+                        }
+                        if raw.remaining() < 4 {
+                            return Err("invalid buffer size".into());
+                        }
+                        let len = usize::try_from(raw.get_i32())
+                            .map_err(|_| "range bounds cannot be NULL")?;
+                        if raw.remaining() < len {
+                            return Err("invalid buffer size".into());
+                        }
+                        let (elem, rest) = raw.split_at(len);
+                        raw = rest;
+                        Value::decode_binary(element_type, elem).map(|v| Some(Box::new(v)))
+                    };
+                    let lower = RangeBound {
+                        inclusive: flags & range::flags::LB_INCLUSIVE != 0,
+                        bound: decode_bound(range::flags::LB_INFINITE)?,
+                    };
+                    let upper = RangeBound {
+                        inclusive: flags & range::flags::UB_INCLUSIVE != 0,
+                        bound: decode_bound(range::flags::UB_INFINITE)?,
+                    };
+                    Range::new(Some((lower, upper)))
+                };
+                if raw.has_remaining() {
+                    return Err("invalid buffer size".into());
+                }
+                canonicalize_range(ty, range)
+            }
         }
     }
 }
 
+/// Validates and canonicalizes a range of type `ty` received from a client.
+fn canonicalize_range(
+    ty: &Type,
+    range: Range<Box<Value>>,
+) -> Result<Value, Box<dyn Error + Sync + Send>> {
+    let scalar_type = ScalarType::try_from(ty)?;
+    let element_type = match ty {
+        Type::Range { element_type } => element_type,
+        _ => unreachable!(),
+    };
+    let arena = RowArena::new();
+    let range = range.map(|bound| bound.into_datum(&arena, element_type));
+    let mut row = Row::default();
+    row.packer().push_range(range)?;
+    Ok(Value::from_datum(row.unpack_first(), &scalar_type).expect("ranges are never NULL"))
+}
+
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
     match elem {
         None => buf.put_i32(-1),
@@ -31,6 +31,7 @@ use tracing::trace;
 use mz_ore::cast::CastFrom;
 use mz_ore::future::OreSinkExt;
 use mz_ore::netio::{self, AsyncReady};
+use mz_repr::adt::datetime::Timezone;
 
 use crate::message::{
     BackendMessage, ErrorResponse, FrontendMessage, FrontendStartupMessage, TransactionStatus,
@@ -156,8 +157,26 @@ where
     /// type information in the codec before sending any data row messages. This
     /// violates the abstraction boundary a bit but results in much better
     /// performance.
-    pub fn set_encode_state(&mut self, encode_state: Vec<(mz_pgrepr::Type, mz_pgrepr::Format)>) {
-        self.inner.get_mut().codec_mut().encode_state = encode_state;
+    ///
+    /// Text-encoded timestamps with time zones are rendered in `timezone`.
+    pub fn set_encode_state(
+        &mut self,
+        encode_state: Vec<(mz_pgrepr::Type, mz_pgrepr::Format)>,
+        timezone: Timezone,
+    ) {
+        let codec = self.inner.get_mut().codec_mut();
+        codec.encode_state = encode_state;
+        codec.timezone = timezone;
+    }
+
+    /// Injects state that affects how password messages are decoded.
+    ///
+    /// Password messages from the client are not self-describing; their
+    /// interpretation depends upon the authentication request that the server
+    /// most recently sent. Callers must install the appropriate kind before
+    /// receiving the client's response to an authentication request.
+    pub fn set_password_message_kind(&mut self, kind: PasswordMessageKind) {
+        self.inner.get_mut().codec_mut().password_message_kind = kind;
     }
 
     /// Waits for the connection to be closed.
@@ -210,9 +229,28 @@ where
     }
 }
 
+/// Describes how to interpret a password message (`p`) from the client.
+///
+/// The `p` message type is shared by several messages whose contents depend on
+/// the authentication method that the server requested. The message itself
+/// does not indicate which of these it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordMessageKind {
+    /// A `PasswordMessage`, in response to a request for a cleartext password.
+    Password,
+    /// A `SASLInitialResponse`, in response to an `AuthenticationSASL`
+    /// request.
+    SaslInitialResponse,
+    /// A `SASLResponse`, in response to an `AuthenticationSASLContinue`
+    /// request.
+    SaslResponse,
+}
+
 struct Codec {
     decode_state: DecodeState,
     encode_state: Vec<(mz_pgrepr::Type, mz_pgrepr::Format)>,
+    timezone: Timezone,
+    password_message_kind: PasswordMessageKind,
 }
 
 impl Codec {
@@ -221,6 +259,8 @@ impl Codec {
         Codec {
             decode_state: DecodeState::Head,
             encode_state: vec![],
+            timezone: Timezone::default(),
+            password_message_kind: PasswordMessageKind::Password,
         }
     }
 }
@@ -239,6 +279,9 @@ impl Encoder<BackendMessage> for Codec {
         let byte = match &msg {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
+            BackendMessage::AuthenticationSasl { .. } => b'R',
+            BackendMessage::AuthenticationSaslContinue(_) => b'R',
+            BackendMessage::AuthenticationSaslFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -296,6 +339,21 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationCleartextPassword => {
                 dst.put_u32(3);
             }
+            BackendMessage::AuthenticationSasl { mechanisms } => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_length_i16(fields.len())?;
                 for f in &fields {
@@ -315,7 +373,7 @@ impl Encoder<BackendMessage> for Codec {
                     if let Some(f) = f {
                         let base = dst.len();
                         dst.put_u32(0);
-                        f.encode(ty, *format, dst)?;
+                        f.encode(ty, *format, &self.timezone, dst)?;
                         let len = dst.len() - base - 4;
                         let len = i32::try_from(len).map_err(|_| {
                             io::Error::new(
@@ -547,7 +605,13 @@ impl Decoder for Codec {
                         b'X' => decode_terminate(buf)?,
 
                         // Authentication.
-                        b'p' => decode_password(buf)?,
+                        b'p' => match self.password_message_kind {
+                            PasswordMessageKind::Password => decode_password(buf)?,
+                            PasswordMessageKind::SaslInitialResponse => {
+                                decode_sasl_initial_response(buf)?
+                            }
+                            PasswordMessageKind::SaslResponse => decode_sasl_response(buf)?,
+                        },
 
                         // Copy from flow.
                         b'f' => decode_copy_fail(buf)?,
@@ -582,6 +646,28 @@ fn decode_password(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
     })
 }
 
+fn decode_sasl_initial_response(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    let mechanism = buf.read_cstr()?.to_owned();
+    let initial_response = match buf.read_i32()? {
+        -1 => vec![],
+        len => {
+            let len = usize::try_from(len).map_err(input_err)?;
+            if buf.buf.len() < len {
+                return Err(input_err("not enough buffer for SASL initial response"));
+            }
+            buf.buf[..len].to_vec()
+        }
+    };
+    Ok(FrontendMessage::SaslInitialResponse {
+        mechanism,
+        initial_response,
+    })
+}
+
+fn decode_sasl_response(buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    Ok(FrontendMessage::SaslResponse(buf.buf.to_vec()))
+}
+
 fn decode_query(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::Query {
         sql: buf.read_cstr()?.to_string(),
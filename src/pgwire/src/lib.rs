@@ -26,6 +26,7 @@ mod codec;
 mod message;
 mod metrics;
 mod protocol;
+mod scram;
 mod server;
 
 pub use message::Severity;
@@ -191,6 +191,17 @@ pub enum FrontendMessage {
     Password {
         password: String,
     },
+
+    /// The first message of a SASL authentication exchange.
+    SaslInitialResponse {
+        /// The name of the SASL mechanism selected by the client.
+        mechanism: String,
+        /// The mechanism-specific initial response.
+        initial_response: Vec<u8>,
+    },
+
+    /// A subsequent message of a SASL authentication exchange.
+    SaslResponse(Vec<u8>),
 }
 
 impl FrontendMessage {
@@ -211,6 +222,8 @@ impl FrontendMessage {
             FrontendMessage::CopyDone => "copy_done",
             FrontendMessage::CopyFail(_) => "copy_fail",
             FrontendMessage::Password { .. } => "password",
+            FrontendMessage::SaslInitialResponse { .. } => "sasl_initial_response",
+            FrontendMessage::SaslResponse(_) => "sasl_response",
         }
     }
 }
@@ -222,6 +235,11 @@ impl FrontendMessage {
 pub enum BackendMessage {
     AuthenticationOk,
     AuthenticationCleartextPassword,
+    AuthenticationSasl {
+        mechanisms: Vec<&'static str>,
+    },
+    AuthenticationSaslContinue(Vec<u8>),
+    AuthenticationSaslFinal(Vec<u8>),
     CommandComplete {
         tag: String,
     },
@@ -26,6 +26,7 @@ use tokio::time::{self, Duration, Instant};
 use tracing::{debug, warn, Instrument};
 
 use mz_adapter::catalog::INTERNAL_USER_NAMES;
+use mz_adapter::scram::ScramSha256Verifier;
 use mz_adapter::session::User;
 use mz_adapter::session::{
     EndTransactionAction, ExternalUserMetadata, InProgressRows, Portal, PortalState,
@@ -47,6 +48,7 @@ use crate::codec::FramedConn;
 use crate::message::{
     self, BackendMessage, ErrorResponse, FrontendMessage, Severity, VERSIONS, VERSION_3,
 };
+use crate::scram::{self, ScramError};
 use crate::server::{Conn, TlsMode};
 
 /// Reports whether the given stream begins with a pgwire handshake.
@@ -101,7 +103,7 @@ pub struct RunParams<'a, A> {
 pub async fn run<'a, A>(
     RunParams {
         tls_mode,
-        adapter_client,
+        mut adapter_client,
         conn,
         version,
         mut params,
@@ -219,6 +221,32 @@ where
             }
         }
     } else {
+        // Without frontegg, roles that have a password must authenticate with
+        // it. Roles without a password are trusted.
+        if let Some(verifier) = adapter_client.role_password_verifier(user.clone()).await {
+            let result = match verifier.parse::<ScramSha256Verifier>() {
+                Ok(verifier) => scram::authenticate(conn, &verifier).await,
+                Err(e) => {
+                    warn!("role {} has an invalid password verifier: {}", user, e);
+                    Err(ScramError::InvalidPassword)
+                }
+            };
+            match result {
+                Ok(()) => (),
+                Err(ScramError::Io(e)) => return Err(e),
+                Err(ScramError::Protocol(msg)) => {
+                    return conn
+                        .send(ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, msg))
+                        .await;
+                }
+                Err(ScramError::InvalidPassword) => {
+                    let msg = format!("password authentication failed for user {}", user.quoted());
+                    return conn
+                        .send(ErrorResponse::fatal(SqlState::INVALID_PASSWORD, msg))
+                        .await;
+                }
+            }
+        }
         // No frontegg check, so is_expired never resolves.
         (None, pending().right_future())
     };
@@ -419,7 +447,9 @@ where
             Some(FrontendMessage::CopyData(_))
             | Some(FrontendMessage::CopyDone)
             | Some(FrontendMessage::CopyFail(_))
-            | Some(FrontendMessage::Password { .. }) => State::Drain,
+            | Some(FrontendMessage::Password { .. })
+            | Some(FrontendMessage::SaslInitialResponse { .. })
+            | Some(FrontendMessage::SaslResponse(_)) => State::Drain,
             None => State::Done,
         };
 
@@ -1308,9 +1338,13 @@ where
             | ExecuteResponse::DroppedTable
             | ExecuteResponse::DroppedType
             | ExecuteResponse::DroppedView
+            | ExecuteResponse::GrantedPrivilege
+            | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
+            | ExecuteResponse::RevokedPrivilege
+            | ExecuteResponse::RevokedRole
             | ExecuteResponse::StartedTransaction { .. }
             | ExecuteResponse::TransactionCommitted
             | ExecuteResponse::TransactionRolledBack
@@ -1367,6 +1401,11 @@ where
                 .map(|ty| mz_pgrepr::Type::from(&ty.scalar_type))
                 .zip(result_formats)
                 .collect(),
+            self.adapter_client
+                .session()
+                .vars()
+                .timezone()
+                .to_timezone(),
         );
 
         let mut total_sent_rows = 0;
@@ -0,0 +1,328 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The server side of SCRAM-SHA-256 authentication.
+//!
+//! See "[SASL Authentication][1]" in the PostgreSQL reference for how the
+//! mechanism is embedded in pgwire, and [RFC 5802] for the mechanism itself.
+//! Channel binding (`SCRAM-SHA-256-PLUS`) is not supported.
+//!
+//! [1]: https://www.postgresql.org/docs/current/sasl-authentication.html
+//! [RFC 5802]: https://datatracker.ietf.org/doc/html/rfc5802
+
+use std::fmt;
+
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::rand;
+use tokio::io::{self, AsyncRead, AsyncWrite};
+
+use mz_adapter::scram::{ScramSha256Verifier, SCRAM_SHA_256};
+
+use crate::codec::{FramedConn, PasswordMessageKind};
+use crate::message::{BackendMessage, FrontendMessage};
+
+/// The number of random bytes in the server's portion of the nonce.
+const SERVER_NONCE_LEN: usize = 18;
+
+/// An error that occurs during a SCRAM exchange.
+#[derive(Debug)]
+pub enum ScramError {
+    /// The underlying connection failed.
+    Io(io::Error),
+    /// The client violated the SCRAM protocol.
+    Protocol(String),
+    /// The client failed to prove knowledge of the password.
+    InvalidPassword,
+}
+
+impl From<io::Error> for ScramError {
+    fn from(e: io::Error) -> ScramError {
+        ScramError::Io(e)
+    }
+}
+
+impl From<ErrorStack> for ScramError {
+    fn from(e: ErrorStack) -> ScramError {
+        ScramError::Io(io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl fmt::Display for ScramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScramError::Io(e) => e.fmt(f),
+            ScramError::Protocol(msg) => f.write_str(msg),
+            ScramError::InvalidPassword => f.write_str("invalid password"),
+        }
+    }
+}
+
+fn protocol_err(msg: impl Into<String>) -> ScramError {
+    ScramError::Protocol(msg.into())
+}
+
+/// Authenticates the client on `conn` against `verifier` via a SCRAM-SHA-256
+/// exchange.
+///
+/// On success, the caller is responsible for sending `AuthenticationOk`.
+pub async fn authenticate<A>(
+    conn: &mut FramedConn<A>,
+    verifier: &ScramSha256Verifier,
+) -> Result<(), ScramError>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    conn.send(BackendMessage::AuthenticationSasl {
+        mechanisms: vec![SCRAM_SHA_256],
+    })
+    .await?;
+    conn.flush().await?;
+    conn.set_password_message_kind(PasswordMessageKind::SaslInitialResponse);
+    let client_first = match conn.recv().await? {
+        Some(FrontendMessage::SaslInitialResponse {
+            mechanism,
+            initial_response,
+        }) => {
+            if mechanism != SCRAM_SHA_256 {
+                return Err(protocol_err(format!(
+                    "unsupported SASL mechanism {}",
+                    mechanism
+                )));
+            }
+            initial_response
+        }
+        _ => return Err(protocol_err("expected SASLInitialResponse message")),
+    };
+
+    let mut server_nonce = [0; SERVER_NONCE_LEN];
+    rand::rand_bytes(&mut server_nonce)?;
+    let server_first = ServerFirst::new(
+        &client_first,
+        &base64::encode_block(&server_nonce),
+        verifier,
+    )?;
+    conn.send(BackendMessage::AuthenticationSaslContinue(
+        server_first.message.clone().into_bytes(),
+    ))
+    .await?;
+    conn.flush().await?;
+    conn.set_password_message_kind(PasswordMessageKind::SaslResponse);
+    let client_final = match conn.recv().await? {
+        Some(FrontendMessage::SaslResponse(data)) => data,
+        _ => return Err(protocol_err("expected SASLResponse message")),
+    };
+    conn.set_password_message_kind(PasswordMessageKind::Password);
+
+    let server_final = server_first.server_final(&client_final, verifier)?;
+    conn.send(BackendMessage::AuthenticationSaslFinal(
+        server_final.into_bytes(),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// The state of the exchange after the server has processed the
+/// client-first-message.
+#[derive(Debug)]
+struct ServerFirst {
+    /// The GS2 header that prefixed the client-first-message.
+    gs2_header: String,
+    /// The client-first-message, without the GS2 header.
+    client_first_bare: String,
+    /// The combined client and server nonce.
+    nonce: String,
+    /// The server-first-message.
+    message: String,
+}
+
+impl ServerFirst {
+    /// Processes the client-first-message, appending `server_nonce` to the
+    /// client's nonce.
+    fn new(
+        client_first: &[u8],
+        server_nonce: &str,
+        verifier: &ScramSha256Verifier,
+    ) -> Result<ServerFirst, ScramError> {
+        let client_first = std::str::from_utf8(client_first)
+            .map_err(|_| protocol_err("malformed SCRAM message: invalid UTF-8"))?;
+
+        // The GS2 header consists of a channel binding flag and an optional
+        // authorization identity, each followed by a comma.
+        let mut parts = client_first.splitn(3, ',');
+        let (cbind_flag, authzid, client_first_bare) =
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(cbind_flag), Some(authzid), Some(bare)) => (cbind_flag, authzid, bare),
+                _ => return Err(protocol_err("malformed SCRAM message: missing GS2 header")),
+            };
+        match cbind_flag {
+            // The client either doesn't support channel binding, or thinks
+            // that we don't. Both are correct, as we only advertise
+            // SCRAM-SHA-256 and not SCRAM-SHA-256-PLUS.
+            "n" | "y" => (),
+            _ if cbind_flag.starts_with("p=") => {
+                return Err(protocol_err("channel binding is not supported"));
+            }
+            _ => return Err(protocol_err("malformed SCRAM message: invalid GS2 header")),
+        }
+        if !authzid.is_empty() {
+            return Err(protocol_err("authorization identities are not supported"));
+        }
+
+        let mut attrs = client_first_bare.split(',');
+        match attrs.next() {
+            Some(a) if a.starts_with("m=") => {
+                return Err(protocol_err("SCRAM extensions are not supported"));
+            }
+            // The user name is ignored in favor of the user name in the
+            // startup message, as in PostgreSQL.
+            Some(a) if a.starts_with("n=") => (),
+            _ => return Err(protocol_err("malformed SCRAM message: expected user name")),
+        }
+        let client_nonce = match attrs.next().and_then(|a| a.strip_prefix("r=")) {
+            Some(nonce) if !nonce.is_empty() && nonce.chars().all(is_printable) => nonce,
+            _ => return Err(protocol_err("malformed SCRAM message: expected nonce")),
+        };
+
+        let nonce = format!("{}{}", client_nonce, server_nonce);
+        let message = format!(
+            "r={},s={},i={}",
+            nonce,
+            base64::encode_block(verifier.salt()),
+            verifier.iterations()
+        );
+        Ok(ServerFirst {
+            gs2_header: format!("{},{},", cbind_flag, authzid),
+            client_first_bare: client_first_bare.into(),
+            nonce,
+            message,
+        })
+    }
+
+    /// Processes the client-final-message, verifying the client's proof and
+    /// returning the server-final-message.
+    fn server_final(
+        &self,
+        client_final: &[u8],
+        verifier: &ScramSha256Verifier,
+    ) -> Result<String, ScramError> {
+        let client_final = std::str::from_utf8(client_final)
+            .map_err(|_| protocol_err("malformed SCRAM message: invalid UTF-8"))?;
+
+        // The proof must be the last attribute, and it is not included in the
+        // authentication message.
+        let (without_proof, proof) = client_final
+            .rsplit_once(",p=")
+            .ok_or_else(|| protocol_err("malformed SCRAM message: expected proof"))?;
+        let proof = base64::decode_block(proof)
+            .map_err(|_| protocol_err("malformed SCRAM message: invalid proof"))?;
+
+        let mut attrs = without_proof.split(',');
+        let channel_binding = attrs
+            .next()
+            .and_then(|a| a.strip_prefix("c="))
+            .ok_or_else(|| protocol_err("malformed SCRAM message: expected channel binding"))?;
+        if channel_binding != base64::encode_block(self.gs2_header.as_bytes()) {
+            return Err(protocol_err("SCRAM channel binding check failed"));
+        }
+        let nonce = attrs
+            .next()
+            .and_then(|a| a.strip_prefix("r="))
+            .ok_or_else(|| protocol_err("malformed SCRAM message: expected nonce"))?;
+        if nonce != self.nonce {
+            return Err(protocol_err("SCRAM nonce mismatch"));
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, self.message, without_proof
+        );
+        if !verifier.verify_client_proof(auth_message.as_bytes(), &proof)? {
+            return Err(ScramError::InvalidPassword);
+        }
+        let signature = verifier.server_signature(auth_message.as_bytes())?;
+        Ok(format!("v={}", base64::encode_block(&signature)))
+    }
+}
+
+/// Reports whether `c` is a valid nonce character, as defined by the
+/// `printable` production in RFC 5802.
+fn is_printable(c: char) -> bool {
+    matches!(c, '\x21'..='\x2b' | '\x2d'..='\x7e')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The test vector from RFC 7677, section 3, for the password "pencil".
+    const VERIFIER: &str = "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$\
+        WG5d8oPm3OtcPnkdi4Uo7BkeZkBFzpcXkuLmtbsT4qY=:\
+        wfPLwcE6nTWhTAmQ7tl2KeoiWGPlZqQxSrmfPwDl2dU=";
+    const CLIENT_FIRST: &str = "n,,n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const SERVER_NONCE: &str = "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const SERVER_FIRST: &str =
+        "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+        p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    #[test]
+    fn test_exchange() {
+        let verifier: ScramSha256Verifier = VERIFIER.parse().unwrap();
+        let server_first =
+            ServerFirst::new(CLIENT_FIRST.as_bytes(), SERVER_NONCE, &verifier).unwrap();
+        assert_eq!(server_first.message, SERVER_FIRST);
+        let server_final = server_first
+            .server_final(CLIENT_FINAL.as_bytes(), &verifier)
+            .unwrap();
+        assert_eq!(server_final, SERVER_FINAL);
+    }
+
+    #[test]
+    fn test_exchange_failures() {
+        let verifier: ScramSha256Verifier = VERIFIER.parse().unwrap();
+
+        for client_first in [
+            "",
+            "n=user,r=abc",
+            "p=tls-server-end-point,,n=user,r=abc",
+            "n,a=admin,n=user,r=abc",
+            "n,,m=ext,n=user,r=abc",
+            "n,,n=user",
+            "n,,n=user,r=",
+        ] {
+            assert!(matches!(
+                ServerFirst::new(client_first.as_bytes(), SERVER_NONCE, &verifier),
+                Err(ScramError::Protocol(_))
+            ));
+        }
+
+        let server_first =
+            ServerFirst::new(CLIENT_FIRST.as_bytes(), SERVER_NONCE, &verifier).unwrap();
+        for (client_final, password_error) in [
+            ("c=biws,r=rOprNGfwEbeRWgbNEkqO", false),
+            (
+                "c=eSws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=AAAA",
+                false,
+            ),
+            ("c=biws,r=rOprNGfwEbeRWgbNEkqO,p=AAAA", false),
+            (
+                "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=AAAA",
+                true,
+            ),
+        ] {
+            match server_first.server_final(client_final.as_bytes(), &verifier) {
+                Err(ScramError::InvalidPassword) => assert!(password_error, "{}", client_final),
+                Err(ScramError::Protocol(_)) => assert!(!password_error, "{}", client_final),
+                res => panic!("unexpected result for {}: {:?}", client_final, res),
+            }
+        }
+    }
+}
@@ -59,6 +59,8 @@ pub enum TryFromProtoError {
     GlobError(globset::Error),
     /// Failed to parse a serialized URL
     InvalidUrl(url::ParseError),
+    /// Failed to parse a serialized jsonpath
+    InvalidJsonPath(String),
 }
 
 impl TryFromProtoError {
@@ -147,6 +149,7 @@ impl std::fmt::Display for TryFromProtoError {
             InvalidUri(error) => error.fmt(f),
             GlobError(error) => error.fmt(f),
             InvalidUrl(error) => error.fmt(f),
+            InvalidJsonPath(error) => error.fmt(f),
         }
     }
 }
@@ -178,6 +181,7 @@ impl std::error::Error for TryFromProtoError {
             InvalidUri(error) => Some(error),
             GlobError(error) => Some(error),
             InvalidUrl(error) => Some(error),
+            InvalidJsonPath(_) => None,
         }
     }
 }
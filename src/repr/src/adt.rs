@@ -23,7 +23,9 @@ pub mod date;
 pub mod datetime;
 pub mod interval;
 pub mod jsonb;
+pub mod jsonpath;
 pub mod numeric;
+pub mod range;
 pub mod regex;
 pub mod system;
 pub mod timestamp;
@@ -0,0 +1,1873 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL/JSON path expressions.
+//!
+//! The `jsonpath` type describes a query over a JSON document in the SQL/JSON
+//! path language, as implemented by [PostgreSQL]. A [`JsonPath`] is parsed
+//! from text with its [`FromStr`] implementation, and its [`fmt::Display`]
+//! implementation produces the canonical form in which paths are stored.
+//!
+//! Paths are evaluated against JSON datums (see [`crate::adt::jsonb`]) with
+//! [`JsonPath::evaluate`], which produces the sequence of items the path
+//! selects.
+//!
+//! The `.keyvalue()` and `.datetime()` item methods are not supported.
+//!
+//! [PostgreSQL]: https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-SQLJSON-PATH
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use dec::Rounding;
+use mz_lowertest::{MzReflect, ReflectedTypeInfo};
+use mz_ore::cast::CastFrom;
+use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
+use mz_proto::{RustType, TryFromProtoError};
+use proptest::prelude::{prop, Strategy};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::adt::numeric::{self, Numeric};
+use crate::{strconv, Datum, DatumMap, RowArena};
+
+/// The maximum depth to which paths may nest while being parsed or evaluated,
+/// including the depth to which `.**` descends into the document.
+const RECURSION_LIMIT: usize = 256;
+
+/// The error reported when [`RECURSION_LIMIT`] is exceeded.
+const TOO_DEEPLY_NESTED: &str = "jsonpath too deeply nested";
+
+/// A parsed SQL/JSON path expression.
+///
+/// Like [`crate::adt::regex::Regex`], paths are compared, hashed, and
+/// serialized by their canonical form, which parses back into an identical
+/// path.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    /// Whether the path is evaluated in strict mode, in which structural
+    /// errors are reported rather than ignored.
+    strict: bool,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    /// `$`, the document being queried.
+    Root,
+    /// `@`, the item being tested by a filter.
+    Current,
+    /// `last`, the index of the last element of the array being subscripted.
+    Last,
+    /// `$name`, a value from the `vars` object.
+    Variable(String),
+    Null,
+    Bool(bool),
+    Numeric(Numeric),
+    String(String),
+    /// An accessor applied to every item produced by an expression.
+    Accessor(Box<Expr>, Accessor),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    IsUnknown(Box<Expr>),
+    Exists(Box<Expr>),
+    LikeRegex {
+        expr: Box<Expr>,
+        pattern: String,
+        flags: String,
+        regex: Regex,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Accessor {
+    /// `.key`
+    Member(String),
+    /// `.*`
+    MemberWildcard,
+    /// `.**`, optionally restricted to the specified levels of nesting. A
+    /// level of `u32::MAX` represents `last`.
+    RecursiveWildcard {
+        first: u32,
+        last: u32,
+    },
+    /// `[*]`
+    ArrayWildcard,
+    /// `[a, b to c, ...]`
+    Subscripts(Vec<(Expr, Option<Expr>)>),
+    /// `? (predicate)`
+    Filter(Box<Expr>),
+    Method(Method),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Ceiling,
+    Floor,
+    Abs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    StartsWith,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl Expr {
+    /// Reports whether the expression is a predicate, i.e., whether it
+    /// produces a true, false, or unknown result rather than a sequence of
+    /// items.
+    fn is_predicate(&self) -> bool {
+        match self {
+            Expr::Binary(op, ..) => !op.is_arithmetic(),
+            Expr::Not(_) | Expr::IsUnknown(_) | Expr::Exists(_) | Expr::LikeRegex { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The binding strength of the expression, which determines where
+    /// parentheses are required when printing it.
+    fn priority(&self) -> u8 {
+        match self {
+            Expr::Binary(op, ..) => match op {
+                BinaryOp::Or => 0,
+                BinaryOp::And => 1,
+                BinaryOp::Eq
+                | BinaryOp::NotEq
+                | BinaryOp::Lt
+                | BinaryOp::Lte
+                | BinaryOp::Gt
+                | BinaryOp::Gte
+                | BinaryOp::StartsWith => 2,
+                BinaryOp::Add | BinaryOp::Sub => 3,
+                BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 4,
+            },
+            Expr::Unary(..) => 5,
+            _ => 6,
+        }
+    }
+}
+
+impl Accessor {
+    /// Reports whether, in lax mode, the accessor is applied to the elements
+    /// of an array rather than to the array itself.
+    fn unwraps_arrays(&self) -> bool {
+        match self {
+            Accessor::Member(_) | Accessor::MemberWildcard | Accessor::Filter(_) => true,
+            Accessor::Method(method) => !matches!(method, Method::Type | Method::Size),
+            Accessor::RecursiveWildcard { .. }
+            | Accessor::ArrayWildcard
+            | Accessor::Subscripts(_) => false,
+        }
+    }
+}
+
+impl Method {
+    fn from_name(name: &str) -> Option<Method> {
+        match name {
+            "type" => Some(Method::Type),
+            "size" => Some(Method::Size),
+            "double" => Some(Method::Double),
+            "ceiling" => Some(Method::Ceiling),
+            "floor" => Some(Method::Floor),
+            "abs" => Some(Method::Abs),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Type => "type",
+            Method::Size => "size",
+            Method::Double => "double",
+            Method::Ceiling => "ceiling",
+            Method::Floor => "floor",
+            Method::Abs => "abs",
+        }
+    }
+}
+
+impl UnaryOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UnaryOp::Plus => "+",
+            UnaryOp::Minus => "-",
+        }
+    }
+}
+
+impl BinaryOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::Or => "||",
+            BinaryOp::And => "&&",
+            BinaryOp::Eq => "==",
+            BinaryOp::NotEq => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Lte => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Gte => ">=",
+            BinaryOp::StartsWith => "starts with",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+        }
+    }
+
+    fn is_arithmetic(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod
+        )
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<JsonPath, String> {
+        Parser {
+            input: s,
+            pos: 0,
+            filter_depth: 0,
+            subscript_depth: 0,
+            recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
+        }
+        .parse_path()
+    }
+}
+
+impl PartialEq for JsonPath {
+    fn eq(&self, other: &JsonPath) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for JsonPath {}
+
+impl PartialOrd for JsonPath {
+    fn partial_cmp(&self, other: &JsonPath) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonPath {
+    fn cmp(&self, other: &JsonPath) -> Ordering {
+        self.to_string().cmp(&other.to_string())
+    }
+}
+
+impl Hash for JsonPath {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.to_string().hash(hasher)
+    }
+}
+
+impl Serialize for JsonPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonPath {
+    fn deserialize<D>(deserializer: D) -> Result<JsonPath, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl MzReflect for JsonPath {
+    fn add_to_reflected_type_info(_: &mut ReflectedTypeInfo) {
+        // Paths are deserialized from their canonical form.
+    }
+}
+
+impl RustType<String> for JsonPath {
+    fn into_proto(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_proto(proto: String) -> Result<Self, TryFromProtoError> {
+        proto.parse().map_err(TryFromProtoError::InvalidJsonPath)
+    }
+}
+
+/// Generates arbitrary paths from a fixed set, covering each kind of
+/// expression and accessor.
+pub fn any_jsonpath() -> impl Strategy<Value = JsonPath> {
+    prop::sample::select(vec![
+        "$",
+        "strict $.a[*]",
+        "$.a[1, 2 to last]",
+        "$.**{1 to 2}.b",
+        "$ ? (@.a > $x && !(@.b starts with \"c\"))",
+        "$ ? (@ like_regex \"^a\" flag \"i\")",
+        "-$.a.size() * 2",
+        "$ ? ((exists (@.a)) is unknown)",
+    ])
+    .prop_map(|path| path.parse().expect("valid jsonpath"))
+}
+
+/// A recursive descent parser for the SQL/JSON path language.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The number of filters that enclose the current position, within which
+    /// `@` is permitted.
+    filter_depth: usize,
+    /// The number of array subscripts that enclose the current position,
+    /// within which `last` is permitted.
+    subscript_depth: usize,
+    recursion_guard: RecursionGuard,
+}
+
+impl CheckedRecursion for Parser<'_> {
+    fn recursion_guard(&self) -> &RecursionGuard {
+        &self.recursion_guard
+    }
+}
+
+/// Reports whether `c` may appear in an unquoted key or variable name.
+fn is_ident_char(c: char) -> bool {
+    !c.is_whitespace() && !"?%$.[]{}()|&!=<>@#,*:-+/\\\"".contains(c)
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Consumes `s`, which must not be a keyword, if it occurs next.
+    fn eat(&mut self, s: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the keyword `kw` if it occurs next.
+    fn eat_keyword(&mut self, kw: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.starts_with(kw) && !rest[kw.len()..].starts_with(is_ident_char) {
+            self.pos += kw.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), String> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<(), String> {
+        if self.eat_keyword(kw) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn syntax_error(&mut self) -> String {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let ident_len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+        let token = match (ident_len, rest.chars().next()) {
+            (_, None) => return "syntax error at end of jsonpath input".into(),
+            (0, Some(c)) => &rest[..c.len_utf8()],
+            (n, _) => &rest[..n],
+        };
+        format!("syntax error at or near \"{}\" of jsonpath input", token)
+    }
+
+    /// Calls `f`, unless the path is nested too deeply to do so safely.
+    fn recur<T, F>(&mut self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Self) -> Result<T, String>,
+    {
+        match self.checked_recur_mut(|parser| Ok::<_, RecursionLimitError>(f(parser))) {
+            Ok(result) => result,
+            Err(_) => Err(TOO_DEEPLY_NESTED.into()),
+        }
+    }
+
+    fn predicate(&self, expr: Expr) -> Result<Expr, String> {
+        if expr.is_predicate() {
+            Ok(expr)
+        } else {
+            Err(format!(
+                "{} is not a jsonpath predicate",
+                PrintExpr(&expr, false)
+            ))
+        }
+    }
+
+    fn parse_path(mut self) -> Result<JsonPath, String> {
+        let strict = if self.eat_keyword("strict") {
+            true
+        } else {
+            self.eat_keyword("lax");
+            false
+        };
+        let expr = self.parse_expr()?;
+        self.skip_whitespace();
+        if self.peek().is_some() {
+            return Err(self.syntax_error());
+        }
+        Ok(JsonPath { strict, expr })
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        self.recur(|parser| {
+            let mut left = parser.parse_and()?;
+            while parser.eat("||") {
+                let left_pred = parser.predicate(left)?;
+                let right = parser.parse_and()?;
+                let right_pred = parser.predicate(right)?;
+                left = Expr::Binary(BinaryOp::Or, Box::new(left_pred), Box::new(right_pred));
+            }
+            Ok(left)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_not()?;
+        while self.eat("&&") {
+            let left_pred = self.predicate(left)?;
+            let right = self.parse_not()?;
+            let right_pred = self.predicate(right)?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left_pred), Box::new(right_pred));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        if self.peek() == Some('!') && self.peek_nth(1) != Some('=') {
+            self.bump();
+            self.expect("(")?;
+            let expr = self.parse_expr()?;
+            let expr = self.predicate(expr)?;
+            self.expect(")")?;
+            return Ok(Expr::Not(Box::new(expr)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let left = self.parse_additive()?;
+        let op = if self.eat("==") || self.eat("=") {
+            BinaryOp::Eq
+        } else if self.eat("!=") || self.eat("<>") {
+            BinaryOp::NotEq
+        } else if self.eat("<=") {
+            BinaryOp::Lte
+        } else if self.eat(">=") {
+            BinaryOp::Gte
+        } else if self.eat("<") {
+            BinaryOp::Lt
+        } else if self.eat(">") {
+            BinaryOp::Gt
+        } else if self.eat_keyword("starts") {
+            self.expect_keyword("with")?;
+            self.skip_whitespace();
+            let right = match self.peek() {
+                Some('"') => Expr::String(self.parse_string()?),
+                Some('$')
+                    if self
+                        .peek_nth(1)
+                        .map_or(false, |c| c == '"' || is_ident_char(c)) =>
+                {
+                    self.parse_primary()?
+                }
+                _ => return Err(self.syntax_error()),
+            };
+            return Ok(Expr::Binary(
+                BinaryOp::StartsWith,
+                Box::new(left),
+                Box::new(right),
+            ));
+        } else if self.eat_keyword("like_regex") {
+            return self.parse_like_regex(left);
+        } else if self.eat_keyword("is") {
+            self.expect_keyword("unknown")?;
+            let left = self.predicate(left)?;
+            return Ok(Expr::IsUnknown(Box::new(left)));
+        } else {
+            return Ok(left);
+        };
+        let right = self.parse_additive()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn parse_like_regex(&mut self, expr: Expr) -> Result<Expr, String> {
+        self.skip_whitespace();
+        if self.peek() != Some('"') {
+            return Err(self.syntax_error());
+        }
+        let pattern = self.parse_string()?;
+        let flags = if self.eat_keyword("flag") {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.syntax_error());
+            }
+            self.parse_string()?
+        } else {
+            String::new()
+        };
+        let mut builder = if flags.contains('q') {
+            RegexBuilder::new(&regex::escape(&pattern))
+        } else {
+            RegexBuilder::new(&pattern)
+        };
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                's' => builder.dot_matches_new_line(true),
+                'm' => builder.multi_line(true),
+                'x' => builder.ignore_whitespace(true),
+                'q' => &mut builder,
+                _ => {
+                    return Err(format!(
+                        "unrecognized flag character \"{}\" in LIKE_REGEX predicate",
+                        flag
+                    ))
+                }
+            };
+        }
+        let regex = builder
+            .build()
+            .map_err(|e| format!("invalid regular expression: {}", e))?;
+        Ok(Expr::LikeRegex {
+            expr: Box::new(expr),
+            pattern,
+            flags,
+            regex,
+        })
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = if self.eat("+") {
+                BinaryOp::Add
+            } else if self.eat("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_multiplicative()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = if self.eat("*") {
+                BinaryOp::Mul
+            } else if self.eat("/") {
+                BinaryOp::Div
+            } else if self.eat("%") {
+                BinaryOp::Mod
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        let op = if self.eat("+") {
+            UnaryOp::Plus
+        } else if self.eat("-") {
+            UnaryOp::Minus
+        } else {
+            return self.parse_accessor_expr();
+        };
+        let expr = self.recur(|parser| parser.parse_unary())?;
+        // Like PostgreSQL, fold signs into numeric literals.
+        Ok(match (op, expr) {
+            (UnaryOp::Plus, Expr::Numeric(n)) => Expr::Numeric(n),
+            (UnaryOp::Minus, Expr::Numeric(mut n)) => {
+                numeric::cx_datum().neg(&mut n);
+                Expr::Numeric(n)
+            }
+            (op, expr) => Expr::Unary(op, Box::new(expr)),
+        })
+    }
+
+    fn parse_accessor_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let accessor = if self.eat(".") {
+                self.parse_dot_accessor()?
+            } else if self.eat("[") {
+                self.parse_subscripts()?
+            } else if self.eat("?") {
+                self.expect("(")?;
+                self.filter_depth += 1;
+                let predicate = self.parse_expr()?;
+                self.filter_depth -= 1;
+                let predicate = self.predicate(predicate)?;
+                self.expect(")")?;
+                Accessor::Filter(Box::new(predicate))
+            } else {
+                return Ok(expr);
+            };
+            expr = Expr::Accessor(Box::new(expr), accessor);
+        }
+    }
+
+    fn parse_dot_accessor(&mut self) -> Result<Accessor, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('*') => {
+                self.bump();
+                if self.peek() != Some('*') {
+                    return Ok(Accessor::MemberWildcard);
+                }
+                self.bump();
+                let (first, last) = if self.eat("{") {
+                    let first = self.parse_level()?;
+                    let last = if self.eat_keyword("to") {
+                        self.parse_level()?
+                    } else {
+                        first
+                    };
+                    self.expect("}")?;
+                    (first, last)
+                } else {
+                    (0, u32::MAX)
+                };
+                Ok(Accessor::RecursiveWildcard { first, last })
+            }
+            Some('"') => Ok(Accessor::Member(self.parse_string()?)),
+            Some(c) if is_ident_char(c) => {
+                let name = self.parse_ident();
+                if !self.eat("(") {
+                    return Ok(Accessor::Member(name.into()));
+                }
+                self.expect(")")?;
+                match Method::from_name(name) {
+                    Some(method) => Ok(Accessor::Method(method)),
+                    None if name == "keyvalue" || name == "datetime" => {
+                        Err(format!("jsonpath item method .{}() is not supported", name))
+                    }
+                    None => Err(format!("unrecognized jsonpath item method .{}()", name)),
+                }
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    fn parse_level(&mut self) -> Result<u32, String> {
+        if self.eat_keyword("last") {
+            return Ok(u32::MAX);
+        }
+        self.skip_whitespace();
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+        }
+        match self.input[start..self.pos].parse() {
+            Ok(level) if level != u32::MAX => Ok(level),
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    fn parse_subscripts(&mut self) -> Result<Accessor, String> {
+        if self.eat("*") {
+            self.expect("]")?;
+            return Ok(Accessor::ArrayWildcard);
+        }
+        self.subscript_depth += 1;
+        let mut subscripts = vec![];
+        loop {
+            let from = self.parse_expr()?;
+            let to = if self.eat_keyword("to") {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
+            subscripts.push((from, to));
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.subscript_depth -= 1;
+        self.expect("]")?;
+        Ok(Accessor::Subscripts(subscripts))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('$') => {
+                self.bump();
+                match self.peek() {
+                    Some('"') => Ok(Expr::Variable(self.parse_string()?)),
+                    Some(c) if is_ident_char(c) => Ok(Expr::Variable(self.parse_ident().into())),
+                    _ => Ok(Expr::Root),
+                }
+            }
+            Some('@') => {
+                if self.filter_depth == 0 {
+                    return Err("@ is not allowed in root expressions".into());
+                }
+                self.bump();
+                Ok(Expr::Current)
+            }
+            Some('(') => {
+                self.bump();
+                let expr = self.parse_expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some('"') => Ok(Expr::String(self.parse_string()?)),
+            Some(c) if c.is_ascii_digit() => self.parse_number(),
+            Some(c) if is_ident_char(c) => {
+                let start = self.pos;
+                match self.parse_ident() {
+                    "null" => Ok(Expr::Null),
+                    "true" => Ok(Expr::Bool(true)),
+                    "false" => Ok(Expr::Bool(false)),
+                    "last" if self.subscript_depth == 0 => {
+                        Err("LAST is allowed only in array subscripts".into())
+                    }
+                    "last" => Ok(Expr::Last),
+                    "exists" => {
+                        self.expect("(")?;
+                        let expr = self.parse_expr()?;
+                        self.expect(")")?;
+                        Ok(Expr::Exists(Box::new(expr)))
+                    }
+                    _ => {
+                        self.pos = start;
+                        Err(self.syntax_error())
+                    }
+                }
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    fn parse_ident(&mut self) -> &'a str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if is_ident_char(c)) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_number(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        let eat_digits = |parser: &mut Self| {
+            while matches!(parser.peek(), Some(c) if c.is_ascii_digit()) {
+                parser.bump();
+            }
+        };
+        eat_digits(self);
+        if self.peek() == Some('.') && matches!(self.peek_nth(1), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+            eat_digits(self);
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let digit_at = match self.peek_nth(1) {
+                Some('+' | '-') => 2,
+                _ => 1,
+            };
+            if matches!(self.peek_nth(digit_at), Some(c) if c.is_ascii_digit()) {
+                for _ in 0..digit_at {
+                    self.bump();
+                }
+                eat_digits(self);
+            }
+        }
+        let text = &self.input[start..self.pos];
+        if matches!(self.peek(), Some(c) if is_ident_char(c)) {
+            return Err(format!(
+                "trailing junk after numeric literal at or near \"{}{}\"",
+                text,
+                self.peek().unwrap()
+            ));
+        }
+        let n = strconv::parse_numeric(text).map_err(|e| e.to_string())?;
+        Ok(Expr::Numeric(n.0))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        const UNTERMINATED: &str = "unexpected end of quoted string";
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump().ok_or(UNTERMINATED)? {
+                '"' => return Ok(s),
+                '\\' => match self.bump().ok_or(UNTERMINATED)? {
+                    'b' => s.push('\u{08}'),
+                    'f' => s.push('\u{0c}'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'v' => s.push('\u{0b}'),
+                    'x' => {
+                        let code = self.parse_hex_digits(2, 2)?;
+                        s.push(unicode_char(code)?);
+                    }
+                    'u' => {
+                        let mut code = self.parse_unicode_escape()?;
+                        if (0xD800..0xDC00).contains(&code) {
+                            // A high surrogate must be followed by a low
+                            // surrogate, which together encode one character.
+                            if !self.rest().starts_with("\\u") {
+                                return Err("invalid Unicode surrogate pair".into());
+                            }
+                            self.pos += 2;
+                            let low = self.parse_unicode_escape()?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return Err("invalid Unicode surrogate pair".into());
+                            }
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                        }
+                        s.push(unicode_char(code)?);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Parses the body of a `\uXXXX` or `\u{X...}` escape.
+    fn parse_unicode_escape(&mut self) -> Result<u32, String> {
+        if self.peek() == Some('{') {
+            self.bump();
+            let code = self.parse_hex_digits(1, 6)?;
+            if self.bump() != Some('}') {
+                return Err("invalid Unicode escape sequence".into());
+            }
+            Ok(code)
+        } else {
+            self.parse_hex_digits(4, 4)
+        }
+    }
+
+    fn parse_hex_digits(&mut self, min: usize, max: usize) -> Result<u32, String> {
+        let start = self.pos;
+        while self.pos - start < max && matches!(self.peek(), Some(c) if c.is_ascii_hexdigit()) {
+            self.bump();
+        }
+        if self.pos - start < min {
+            return Err("invalid hexadecimal character sequence".into());
+        }
+        Ok(u32::from_str_radix(&self.input[start..self.pos], 16).expect("validated hex digits"))
+    }
+}
+
+fn unicode_char(code: u32) -> Result<char, String> {
+    match char::from_u32(code) {
+        Some('\0') => Err("unsupported Unicode escape sequence".into()),
+        Some(c) => Ok(c),
+        None => Err("invalid Unicode escape value".into()),
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.strict {
+            f.write_str("strict ")?;
+        }
+        write!(f, "{}", PrintExpr(&self.expr, true))
+    }
+}
+
+/// Prints an expression in its canonical form, parenthesizing operators if
+/// the second field is set.
+struct PrintExpr<'a>(&'a Expr, bool);
+
+impl<'a> fmt::Display for PrintExpr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let PrintExpr(expr, brackets) = *self;
+        // Operands that bind no more tightly than their operator must be
+        // parenthesized.
+        let child = |e: &'a Expr| PrintExpr(e, e.priority() <= expr.priority());
+        match expr {
+            Expr::Root => f.write_str("$"),
+            Expr::Current => f.write_str("@"),
+            Expr::Last => f.write_str("last"),
+            Expr::Variable(name) => {
+                f.write_char('$')?;
+                write_json_string(f, name)
+            }
+            Expr::Null => f.write_str("null"),
+            Expr::Bool(b) => write!(f, "{}", b),
+            Expr::Numeric(n) => f.write_str(&n.to_standard_notation_string()),
+            Expr::String(s) => write_json_string(f, s),
+            Expr::Accessor(base, accessor) => {
+                write!(f, "{}", PrintExpr(base, base.priority() < 6))?;
+                match accessor {
+                    Accessor::Member(key) => {
+                        f.write_char('.')?;
+                        write_json_string(f, key)
+                    }
+                    Accessor::MemberWildcard => f.write_str(".*"),
+                    Accessor::RecursiveWildcard { first, last } => {
+                        let level = |l: u32| match l {
+                            u32::MAX => "last".into(),
+                            l => l.to_string(),
+                        };
+                        f.write_str(".**")?;
+                        match (*first, *last) {
+                            (0, u32::MAX) => Ok(()),
+                            (first, last) if first == last => write!(f, "{{{}}}", level(first)),
+                            (first, last) => write!(f, "{{{} to {}}}", level(first), level(last)),
+                        }
+                    }
+                    Accessor::ArrayWildcard => f.write_str("[*]"),
+                    Accessor::Subscripts(subscripts) => {
+                        f.write_char('[')?;
+                        for (i, (from, to)) in subscripts.iter().enumerate() {
+                            if i > 0 {
+                                f.write_char(',')?;
+                            }
+                            write!(f, "{}", PrintExpr(from, false))?;
+                            if let Some(to) = to {
+                                write!(f, " to {}", PrintExpr(to, false))?;
+                            }
+                        }
+                        f.write_char(']')
+                    }
+                    Accessor::Filter(predicate) => write!(f, "?({})", PrintExpr(predicate, false)),
+                    Accessor::Method(method) => write!(f, ".{}()", method.name()),
+                }
+            }
+            Expr::Unary(op, e) => {
+                if brackets {
+                    f.write_char('(')?;
+                }
+                write!(f, "{}{}", op.as_str(), child(e))?;
+                if brackets {
+                    f.write_char(')')?;
+                }
+                Ok(())
+            }
+            Expr::Binary(op, left, right) => {
+                if brackets {
+                    f.write_char('(')?;
+                }
+                write!(f, "{} {} {}", child(left), op.as_str(), child(right))?;
+                if brackets {
+                    f.write_char(')')?;
+                }
+                Ok(())
+            }
+            Expr::Not(e) => write!(f, "!({})", PrintExpr(e, false)),
+            Expr::IsUnknown(e) => write!(f, "({}) is unknown", PrintExpr(e, false)),
+            Expr::Exists(e) => write!(f, "exists ({})", PrintExpr(e, false)),
+            Expr::LikeRegex {
+                expr: e,
+                pattern,
+                flags,
+                ..
+            } => {
+                if brackets {
+                    f.write_char('(')?;
+                }
+                write!(f, "{} like_regex ", child(e))?;
+                write_json_string(f, pattern)?;
+                if !flags.is_empty() {
+                    f.write_str(" flag ")?;
+                    write_json_string(f, flags)?;
+                }
+                if brackets {
+                    f.write_char(')')?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_json_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// An error encountered while evaluating a [`JsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPathError {
+    /// The `vars` argument was not a JSON object.
+    VarsNotObject,
+    /// The path referred to a variable that `vars` does not contain.
+    UndefinedVariable(String),
+    DivisionByZero,
+    NumericOverflow,
+    /// An item in the document did not have the structure or type that the
+    /// path required.
+    InvalidItem(String),
+    /// The path did not produce the single boolean that `jsonb_path_match`
+    /// requires.
+    SingleBooleanExpected,
+    /// The path or the document was nested too deeply to evaluate safely.
+    TooDeeplyNested,
+}
+
+impl JsonPathError {
+    /// Reports whether the error is suppressed when evaluating a path in
+    /// "silent" mode.
+    ///
+    /// Errors in the arguments themselves, rather than in the document being
+    /// queried, are always reported.
+    pub fn is_silenceable(&self) -> bool {
+        !matches!(
+            self,
+            JsonPathError::VarsNotObject
+                | JsonPathError::UndefinedVariable(_)
+                | JsonPathError::TooDeeplyNested
+        )
+    }
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonPathError::VarsNotObject => f.write_str("\"vars\" argument is not an object"),
+            JsonPathError::UndefinedVariable(name) => {
+                write!(f, "could not find jsonpath variable \"{}\"", name)
+            }
+            JsonPathError::DivisionByZero => f.write_str("division by zero"),
+            JsonPathError::NumericOverflow => f.write_str("value out of range: overflow"),
+            JsonPathError::InvalidItem(message) => f.write_str(message),
+            JsonPathError::SingleBooleanExpected => {
+                f.write_str("single boolean result is expected")
+            }
+            JsonPathError::TooDeeplyNested => f.write_str(TOO_DEEPLY_NESTED),
+        }
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+impl From<RecursionLimitError> for JsonPathError {
+    fn from(_: RecursionLimitError) -> JsonPathError {
+        JsonPathError::TooDeeplyNested
+    }
+}
+
+fn invalid_item<T>(message: impl Into<String>) -> Result<T, JsonPathError> {
+    Err(JsonPathError::InvalidItem(message.into()))
+}
+
+impl JsonPath {
+    /// Evaluates the path against the JSON datum `target`, returning the
+    /// sequence of items it selects.
+    ///
+    /// `vars` must be a JSON object, whose entries supply the values of the
+    /// path's variables. If the path is a predicate, e.g. `$.a > 1`, the
+    /// result is a single JSON boolean, or JSON null if the predicate's result
+    /// is unknown.
+    pub fn evaluate<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        let vars = match vars {
+            Datum::Map(vars) => vars,
+            _ => return Err(JsonPathError::VarsNotObject),
+        };
+        let executor = Executor {
+            strict: self.strict,
+            root: target,
+            vars,
+            temp_storage,
+            recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
+        };
+        let cx = EvalContext {
+            current: target,
+            last: None,
+        };
+        executor.eval(&self.expr, cx)
+    }
+}
+
+struct Executor<'a> {
+    strict: bool,
+    root: Datum<'a>,
+    vars: DatumMap<'a>,
+    temp_storage: &'a RowArena,
+    recursion_guard: RecursionGuard,
+}
+
+impl CheckedRecursion for Executor<'_> {
+    fn recursion_guard(&self) -> &RecursionGuard {
+        &self.recursion_guard
+    }
+}
+
+#[derive(Clone, Copy)]
+struct EvalContext<'a> {
+    /// The value of `@`.
+    current: Datum<'a>,
+    /// The value of `last`, if within an array subscript.
+    last: Option<i64>,
+}
+
+impl<'a> Executor<'a> {
+    fn eval(&self, expr: &Expr, cx: EvalContext<'a>) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        executor.checked_recur(|executor| {
+            Ok(match expr {
+                Expr::Root => vec![executor.root],
+                Expr::Current => vec![cx.current],
+                Expr::Last => match cx.last {
+                    Some(last) => vec![Datum::from(Numeric::from(last))],
+                    None => {
+                        return invalid_item("evaluating jsonpath LAST outside of array subscript")
+                    }
+                },
+                Expr::Variable(name) => {
+                    match executor.vars.iter().find(|(k, _)| *k == name.as_str()) {
+                        Some((_, v)) => vec![v],
+                        None => return Err(JsonPathError::UndefinedVariable(name.clone())),
+                    }
+                }
+                Expr::Null => vec![Datum::JsonNull],
+                Expr::Bool(b) => vec![Datum::from(*b)],
+                Expr::Numeric(n) => vec![Datum::from(*n)],
+                Expr::String(s) => {
+                    vec![Datum::String(executor.temp_storage.push_string(s.clone()))]
+                }
+                Expr::Accessor(base, accessor) => {
+                    let mut out = vec![];
+                    for item in executor.eval(base, cx)? {
+                        executor.apply_accessor(accessor, item, cx, true, &mut out)?;
+                    }
+                    out
+                }
+                Expr::Unary(op, e) => {
+                    let mut out = vec![];
+                    for item in executor.eval_unwrapped(e, cx)? {
+                        let mut n = match item {
+                            Datum::Numeric(n) => n.0,
+                            _ => {
+                                return invalid_item(format!(
+                                    "operand of unary jsonpath operator {} is not a numeric value",
+                                    op.as_str()
+                                ))
+                            }
+                        };
+                        if *op == UnaryOp::Minus {
+                            numeric::cx_datum().neg(&mut n);
+                        }
+                        out.push(Datum::from(n));
+                    }
+                    out
+                }
+                Expr::Binary(op, left, right) if op.is_arithmetic() => {
+                    vec![executor.eval_arithmetic(*op, left, right, cx)?]
+                }
+                _ => vec![match executor.eval_predicate(expr, cx)? {
+                    Some(b) => Datum::from(b),
+                    None => Datum::JsonNull,
+                }],
+            })
+        })
+    }
+
+    /// Like [`Executor::eval`], but in lax mode replaces any arrays in the
+    /// result with their elements.
+    fn eval_unwrapped(
+        &self,
+        expr: &Expr,
+        cx: EvalContext<'a>,
+    ) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        let items = self.eval(expr, cx)?;
+        if self.strict {
+            return Ok(items);
+        }
+        let mut out = vec![];
+        for item in items {
+            match item {
+                Datum::List(list) => out.extend(list.iter()),
+                _ => out.push(item),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Reports an error that is only raised in strict mode.
+    fn structural_error(&self, message: &str) -> Result<(), JsonPathError> {
+        if self.strict {
+            invalid_item(message)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn apply_accessor(
+        &self,
+        accessor: &Accessor,
+        item: Datum<'a>,
+        cx: EvalContext<'a>,
+        unwrap: bool,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        if let Datum::List(list) = item {
+            if unwrap && !self.strict && accessor.unwraps_arrays() {
+                for elem in list.iter() {
+                    self.apply_accessor(accessor, elem, cx, false, out)?;
+                }
+                return Ok(());
+            }
+        }
+        match accessor {
+            Accessor::Member(key) => match item {
+                Datum::Map(map) => match map.iter().find(|(k, _)| *k == key.as_str()) {
+                    Some((_, v)) => out.push(v),
+                    None => self.structural_error(&format!(
+                        "JSON object does not contain key \"{}\"",
+                        key
+                    ))?,
+                },
+                _ => self.structural_error(
+                    "jsonpath member accessor can only be applied to an object",
+                )?,
+            },
+            Accessor::MemberWildcard => match item {
+                Datum::Map(map) => out.extend(map.iter().map(|(_, v)| v)),
+                _ => self.structural_error(
+                    "jsonpath wildcard member accessor can only be applied to an object",
+                )?,
+            },
+            Accessor::RecursiveWildcard { first, last } => {
+                if *first == 0 {
+                    out.push(item);
+                }
+                self.descend(item, 1, *first, *last, out)?;
+            }
+            Accessor::ArrayWildcard => match item {
+                Datum::List(list) => out.extend(list.iter()),
+                _ if !self.strict => out.push(item),
+                _ => {
+                    return invalid_item(
+                        "jsonpath wildcard array accessor can only be applied to an array",
+                    )
+                }
+            },
+            Accessor::Subscripts(subscripts) => {
+                let elems: Vec<_> = match item {
+                    Datum::List(list) => list.iter().collect(),
+                    _ if !self.strict => vec![item],
+                    _ => {
+                        return invalid_item(
+                            "jsonpath array accessor can only be applied to an array",
+                        )
+                    }
+                };
+                let last = i64::try_from(elems.len()).expect("array too large") - 1;
+                let cx = EvalContext {
+                    current: cx.current,
+                    last: Some(last),
+                };
+                for (from, to) in subscripts {
+                    let from = self.eval_subscript(from, cx)?;
+                    let to = match to {
+                        Some(to) => self.eval_subscript(to, cx)?,
+                        None => from,
+                    };
+                    if from < 0 || from > to || to > last {
+                        self.structural_error("jsonpath array subscript is out of bounds")?;
+                    }
+                    for i in from.max(0)..=to.min(last) {
+                        out.push(elems[usize::try_from(i).expect("known non-negative")]);
+                    }
+                }
+            }
+            Accessor::Filter(predicate) => {
+                let cx = EvalContext {
+                    current: item,
+                    last: cx.last,
+                };
+                if self.eval_predicate(predicate, cx)? == Some(true) {
+                    out.push(item);
+                }
+            }
+            Accessor::Method(method) => out.push(self.apply_method(*method, item)?),
+        }
+        Ok(())
+    }
+
+    fn eval_subscript(&self, expr: &Expr, cx: EvalContext<'a>) -> Result<i64, JsonPathError> {
+        let mut n = match self.eval(expr, cx)?.as_slice() {
+            [Datum::Numeric(n)] => n.0,
+            _ => return invalid_item("jsonpath array subscript is not a single numeric value"),
+        };
+        let mut cx = numeric::cx_datum();
+        cx.set_rounding(Rounding::Down);
+        cx.round(&mut n);
+        cx.clear_status();
+        match cx.try_into_i32(n) {
+            Ok(i) => Ok(i64::from(i)),
+            Err(_) => invalid_item("jsonpath array subscript is out of integer range"),
+        }
+    }
+
+    fn apply_method(&self, method: Method, item: Datum<'a>) -> Result<Datum<'a>, JsonPathError> {
+        match (method, item) {
+            (Method::Type, _) => Ok(Datum::String(json_type_name(item))),
+            (Method::Size, Datum::List(list)) => {
+                Ok(Datum::from(Numeric::from(u64::cast_from(list.iter().count()))))
+            }
+            (Method::Size, _) if !self.strict => Ok(Datum::from(Numeric::from(1))),
+            (Method::Size, _) => {
+                invalid_item("jsonpath item method .size() can only be applied to an array")
+            }
+            (Method::Double, Datum::Numeric(n)) => {
+                let f = n.0.to_string().parse::<f64>().unwrap_or(f64::INFINITY);
+                if !f.is_finite() {
+                    return invalid_item(
+                        "numeric argument of jsonpath item method .double() is out of range for type double precision",
+                    );
+                }
+                float_to_datum(f)
+            }
+            (Method::Double, Datum::String(s)) => match strconv::parse_float64(s) {
+                Ok(f) if f.is_finite() => float_to_datum(f),
+                Ok(_) => invalid_item(
+                    "NaN or Infinity is not allowed for jsonpath item method .double()",
+                ),
+                Err(_) => invalid_item(
+                    "string argument of jsonpath item method .double() is not a valid representation of a double precision number",
+                ),
+            },
+            (Method::Double, _) => invalid_item(
+                "jsonpath item method .double() can only be applied to a string or numeric value",
+            ),
+            (Method::Ceiling | Method::Floor | Method::Abs, Datum::Numeric(n)) => {
+                let mut n = n.0;
+                let mut cx = numeric::cx_datum();
+                match method {
+                    Method::Abs => cx.abs(&mut n),
+                    // Rounding is a no-op if there are no fractional digits.
+                    _ if n.exponent() >= 0 => (),
+                    _ => {
+                        cx.set_rounding(if method == Method::Ceiling {
+                            Rounding::Ceiling
+                        } else {
+                            Rounding::Floor
+                        });
+                        cx.round(&mut n);
+                    }
+                }
+                numeric::munge_numeric(&mut n).map_err(|_| JsonPathError::NumericOverflow)?;
+                Ok(Datum::from(n))
+            }
+            (Method::Ceiling | Method::Floor | Method::Abs, _) => invalid_item(format!(
+                "jsonpath item method .{}() can only be applied to a numeric value",
+                method.name()
+            )),
+        }
+    }
+
+    fn eval_arithmetic(
+        &self,
+        op: BinaryOp,
+        left: &Expr,
+        right: &Expr,
+        cx: EvalContext<'a>,
+    ) -> Result<Datum<'a>, JsonPathError> {
+        let operand = |expr: &Expr, side: &str| -> Result<Numeric, JsonPathError> {
+            match self.eval_unwrapped(expr, cx)?.as_slice() {
+                [Datum::Numeric(n)] => Ok(n.0),
+                _ => invalid_item(format!(
+                    "{} operand of jsonpath operator {} is not a single numeric value",
+                    side,
+                    op.as_str()
+                )),
+            }
+        };
+        let mut a = operand(left, "left")?;
+        let b = operand(right, "right")?;
+        if matches!(op, BinaryOp::Div | BinaryOp::Mod) && b.is_zero() {
+            return Err(JsonPathError::DivisionByZero);
+        }
+        let mut cx = numeric::cx_datum();
+        match op {
+            BinaryOp::Add => cx.add(&mut a, &b),
+            BinaryOp::Sub => cx.sub(&mut a, &b),
+            BinaryOp::Mul => cx.mul(&mut a, &b),
+            BinaryOp::Div => cx.div(&mut a, &b),
+            BinaryOp::Mod => cx.rem(&mut a, &b),
+            _ => unreachable!("{:?} is not an arithmetic operator", op),
+        }
+        if cx.status().overflow() {
+            return Err(JsonPathError::NumericOverflow);
+        }
+        numeric::munge_numeric(&mut a).map_err(|_| JsonPathError::NumericOverflow)?;
+        Ok(Datum::from(a))
+    }
+
+    /// Evaluates an expression whose errors, if they may be suppressed, make
+    /// the enclosing predicate unknown.
+    fn eval_in_predicate(
+        &self,
+        expr: &Expr,
+        cx: EvalContext<'a>,
+    ) -> Result<Option<Vec<Datum<'a>>>, JsonPathError> {
+        match self.eval_unwrapped(expr, cx) {
+            Ok(items) => Ok(Some(items)),
+            Err(e) if e.is_silenceable() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Combines the results of testing a predicate against several items.
+    ///
+    /// In lax mode, the predicate is true if it is true for any item, even if
+    /// it is unknown for others. In strict mode, the predicate is unknown if
+    /// it is unknown for any item.
+    fn any_item(&self, results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+        let mut found = false;
+        let mut unknown = false;
+        for result in results {
+            match result {
+                None if self.strict => return None,
+                None => unknown = true,
+                Some(true) if !self.strict => return Some(true),
+                Some(true) => found = true,
+                Some(false) => (),
+            }
+        }
+        if found {
+            Some(true)
+        } else if unknown {
+            None
+        } else {
+            Some(false)
+        }
+    }
+
+    /// Collects the descendants of `item` that are between `first` and `last`
+    /// levels deep, inclusive.
+    fn descend(
+        &self,
+        item: Datum<'a>,
+        level: u32,
+        first: u32,
+        last: u32,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        self.checked_recur(|executor| {
+            let children: Vec<_> = match item {
+                Datum::List(list) => list.iter().collect(),
+                Datum::Map(map) => map.iter().map(|(_, v)| v).collect(),
+                _ => return Ok(()),
+            };
+            for child in children {
+                let is_container = matches!(child, Datum::List(_) | Datum::Map(_));
+                // `.**{last}` selects only leaves.
+                if level >= first || (first == u32::MAX && last == u32::MAX && !is_container) {
+                    out.push(child);
+                }
+                if level < last && is_container {
+                    executor.descend(child, level + 1, first, last, out)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Evaluates a predicate, returning `None` if its result is unknown.
+    fn eval_predicate(
+        &self,
+        expr: &Expr,
+        cx: EvalContext<'a>,
+    ) -> Result<Option<bool>, JsonPathError> {
+        executor.checked_recur(|executor| match expr {
+            Expr::Binary(BinaryOp::And, left, right) => {
+                let left = executor.eval_predicate(left, cx)?;
+                if left == Some(false) {
+                    return Ok(Some(false));
+                }
+                let right = executor.eval_predicate(right, cx)?;
+                Ok(match (left, right) {
+                    (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                })
+            }
+            Expr::Binary(BinaryOp::Or, left, right) => {
+                let left = executor.eval_predicate(left, cx)?;
+                if left == Some(true) {
+                    return Ok(Some(true));
+                }
+                let right = executor.eval_predicate(right, cx)?;
+                Ok(match (left, right) {
+                    (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                })
+            }
+            Expr::Binary(BinaryOp::StartsWith, left, right) => {
+                let (left, right) = match (
+                    executor.eval_in_predicate(left, cx)?,
+                    executor.eval_in_predicate(right, cx)?,
+                ) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(None),
+                };
+                let prefix = match right.as_slice() {
+                    [Datum::String(prefix)] => *prefix,
+                    _ => return Ok(None),
+                };
+                Ok(executor.any_item(left.into_iter().map(|item| match item {
+                    Datum::String(s) => Some(s.starts_with(prefix)),
+                    _ => None,
+                })))
+            }
+            Expr::Binary(op, left, right) if !op.is_arithmetic() => {
+                let (left, right) = match (
+                    executor.eval_in_predicate(left, cx)?,
+                    executor.eval_in_predicate(right, cx)?,
+                ) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(None),
+                };
+                Ok(executor.any_item(
+                    left.iter()
+                        .flat_map(|l| right.iter().map(move |r| compare(*op, *l, *r))),
+                ))
+            }
+            Expr::Not(e) => Ok(executor.eval_predicate(e, cx)?.map(|b| !b)),
+            Expr::IsUnknown(e) => Ok(Some(executor.eval_predicate(e, cx)?.is_none())),
+            Expr::Exists(e) => match executor.eval(e, cx) {
+                Ok(items) => Ok(Some(!items.is_empty())),
+                Err(e) if e.is_silenceable() => Ok(None),
+                Err(e) => Err(e),
+            },
+            Expr::LikeRegex { expr, regex, .. } => {
+                let items = match executor.eval_in_predicate(expr, cx)? {
+                    Some(items) => items,
+                    None => return Ok(None),
+                };
+                Ok(executor.any_item(items.into_iter().map(|item| match item {
+                    Datum::String(s) => Some(regex.is_match(s)),
+                    _ => None,
+                })))
+            }
+            // Other expressions cannot appear where a predicate is expected.
+            _ => Ok(None),
+        })
+    }
+}
+
+/// Compares two JSON items, returning `None` if they are not comparable.
+fn compare(op: BinaryOp, a: Datum, b: Datum) -> Option<bool> {
+    let ordering = match (a, b) {
+        (Datum::JsonNull, Datum::JsonNull) => Ordering::Equal,
+        (Datum::JsonNull, _) | (_, Datum::JsonNull) => return Some(op == BinaryOp::NotEq),
+        (Datum::True | Datum::False, Datum::True | Datum::False) => {
+            (a == Datum::True).cmp(&(b == Datum::True))
+        }
+        (Datum::Numeric(a), Datum::Numeric(b)) => a.cmp(&b),
+        (Datum::String(a), Datum::String(b)) => a.cmp(b),
+        _ => return None,
+    };
+    Some(match op {
+        BinaryOp::Eq => ordering == Ordering::Equal,
+        BinaryOp::NotEq => ordering != Ordering::Equal,
+        BinaryOp::Lt => ordering == Ordering::Less,
+        BinaryOp::Lte => ordering != Ordering::Greater,
+        BinaryOp::Gt => ordering == Ordering::Greater,
+        BinaryOp::Gte => ordering != Ordering::Less,
+        _ => unreachable!("{:?} is not a comparison operator", op),
+    })
+}
+
+fn json_type_name(item: Datum) -> &'static str {
+    match item {
+        Datum::JsonNull => "null",
+        Datum::True | Datum::False => "boolean",
+        Datum::Numeric(_) => "number",
+        Datum::String(_) => "string",
+        Datum::List(_) => "array",
+        Datum::Map(_) => "object",
+        _ => "unknown",
+    }
+}
+
+fn float_to_datum<'a>(f: f64) -> Result<Datum<'a>, JsonPathError> {
+    let mut n = Numeric::from(f);
+    numeric::munge_numeric(&mut n).map_err(|_| JsonPathError::NumericOverflow)?;
+    Ok(Datum::from(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_proto::protobuf_roundtrip;
+    use proptest::prelude::*;
+
+    use crate::adt::jsonb::{Jsonb, JsonbRef};
+    use crate::{Datum, RowArena};
+
+    use super::{any_jsonpath, JsonPath, JsonPathError};
+
+    #[test]
+    fn test_canonical_form() {
+        for (input, expected) in [
+            ("$", "$"),
+            ("lax $.a", "$.\"a\""),
+            ("strict $.a.b", "strict $.\"a\".\"b\""),
+            ("$.\"a b\"[*]", "$.\"a b\"[*]"),
+            ("$.a[1,2, 3 to last]", "$.\"a\"[1,2,3 to last]"),
+            ("$.a ? (@.b > 1)", "$.\"a\"?(@.\"b\" > 1)"),
+            ("$.a/+-1", "($.\"a\" / -1)"),
+            ("1 * 2 + 4 % -3 != false", "(1 * 2 + 4 % -3 != false)"),
+            ("$.a + $.b + 1", "(($.\"a\" + $.\"b\") + 1)"),
+            ("$.a + ($.b + 1)", "($.\"a\" + ($.\"b\" + 1))"),
+            ("-$.a[*]", "(-$.\"a\"[*])"),
+            ("$.**{2 to last}", "$.**{2 to last}"),
+            ("$.**{1}.x", "$.**{1}.\"x\""),
+            ("$var", "$\"var\""),
+            ("$.a.type()", "$.\"a\".type()"),
+            ("$ ? (exists (@.x))", "$?(exists (@.\"x\"))"),
+            ("$ ? (!(@ < 1) && @ <> 2)", "$?(!(@ < 1) && @ != 2)"),
+            ("$ ? ((@ == 1) is unknown)", "$?((@ == 1) is unknown)"),
+            (
+                "$ ? (@ like_regex \"^a\" flag \"i\")",
+                "$?(@ like_regex \"^a\" flag \"i\")",
+            ),
+            ("$ ? (@ starts with $x)", "$?(@ starts with $\"x\")"),
+            ("\"a\\u0041\\n\"", "\"aA\\n\""),
+            ("1.50e1", "15.0"),
+        ] {
+            let path: JsonPath = input.parse().unwrap();
+            assert_eq!(path.to_string(), expected, "input: {}", input);
+            let reparsed: JsonPath = expected.parse().unwrap();
+            assert_eq!(reparsed.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (input, expected) in [
+            ("", "syntax error at end of jsonpath input"),
+            ("$.", "syntax error at end of jsonpath input"),
+            ("$ $", "syntax error at or near \"$\" of jsonpath input"),
+            ("@.a", "@ is not allowed in root expressions"),
+            ("last", "LAST is allowed only in array subscripts"),
+            ("$ ? (@.a)", "@.\"a\" is not a jsonpath predicate"),
+            ("$.a.foo()", "unrecognized jsonpath item method .foo()"),
+            (
+                "$.keyvalue()",
+                "jsonpath item method .keyvalue() is not supported",
+            ),
+            (
+                "1a",
+                "trailing junk after numeric literal at or near \"1a\"",
+            ),
+            ("\"abc", "unexpected end of quoted string"),
+            (
+                "$ ? (@ like_regex \"a\" flag \"z\")",
+                "unrecognized flag character \"z\" in LIKE_REGEX predicate",
+            ),
+        ] {
+            assert_eq!(
+                input.parse::<JsonPath>().unwrap_err(),
+                expected,
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    fn eval(path: &str, json: &str) -> Result<Vec<String>, JsonPathError> {
+        let path: JsonPath = path.parse().unwrap();
+        let json: Jsonb = json.parse().unwrap();
+        let vars: Jsonb = r#"{"x": 2, "s": "ab"}"#.parse().unwrap();
+        let arena = RowArena::new();
+        let items = path.evaluate(
+            json.as_ref().into_datum(),
+            vars.as_ref().into_datum(),
+            &arena,
+        )?;
+        Ok(items
+            .into_iter()
+            .map(|d| JsonbRef::from_datum(d).to_string())
+            .collect())
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let doc = r#"{"a": [1, 2, 3, {"b": 4}], "c": {"d": "abc", "e": null}}"#;
+        for (path, expected) in [
+            ("$.a[*]", vec!["1", "2", "3", "{\"b\":4}"]),
+            ("$.a[1 to 2]", vec!["2", "3"]),
+            ("$.a[last]", vec!["{\"b\":4}"]),
+            ("$.a[10]", vec![]),
+            ("$.a.b", vec!["4"]),
+            ("$.missing", vec![]),
+            ("$.a ? (@ >= 2)", vec!["2", "3"]),
+            ("$.a[*] ? (@ > $x)", vec!["3"]),
+            ("$.c.*", vec!["\"abc\"", "null"]),
+            ("$.** ? (@ == 4)", vec!["4"]),
+            ("$.c.d ? (@ starts with $s)", vec!["\"abc\""]),
+            ("$.c.d ? (@ like_regex \"B\" flag \"i\")", vec!["\"abc\""]),
+            ("$.c ? (@.e == null).d", vec!["\"abc\""]),
+            ("$.a.size()", vec!["4"]),
+            (
+                "$.a[0 to 2].type()",
+                vec!["\"number\"", "\"number\"", "\"number\""],
+            ),
+            ("$.a[0] + $.a[1] * 2", vec!["5"]),
+            ("$.a[0 to 1].double()", vec!["1", "2"]),
+            ("-$.a[0 to 1]", vec!["-1", "-2"]),
+            ("$.a[0] > 0", vec!["true"]),
+            ("$.c.d > 0", vec!["null"]),
+            ("exists($.c.x)", vec!["false"]),
+        ] {
+            assert_eq!(eval(path, doc).unwrap(), expected, "path: {}", path);
+        }
+
+        assert_eq!(
+            eval("strict $.missing", doc),
+            Err(JsonPathError::InvalidItem(
+                "JSON object does not contain key \"missing\"".into()
+            ))
+        );
+        assert_eq!(
+            eval("strict $.a[10]", doc),
+            Err(JsonPathError::InvalidItem(
+                "jsonpath array subscript is out of bounds".into()
+            ))
+        );
+        assert_eq!(eval("$.a[0] / 0", doc), Err(JsonPathError::DivisionByZero));
+        assert_eq!(
+            eval("$y", doc),
+            Err(JsonPathError::UndefinedVariable("y".into()))
+        );
+        assert_eq!(
+            eval("$.a + 1", doc),
+            Err(JsonPathError::InvalidItem(
+                "left operand of jsonpath operator + is not a single numeric value".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_vars_not_object() {
+        let path: JsonPath = "$".parse().unwrap();
+        let arena = RowArena::new();
+        assert_eq!(
+            path.evaluate(Datum::JsonNull, Datum::JsonNull, &arena),
+            Err(JsonPathError::VarsNotObject)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn jsonpath_protobuf_roundtrip(expect in any_jsonpath()) {
+            let actual = protobuf_roundtrip::<_, String>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
+    }
+
+    #[test]
+    fn test_recursion_limit() {
+        for input in [
+            format!("{}1{}", "(".repeat(1000), ")".repeat(1000)),
+            format!("{}1", "-".repeat(1000)),
+            format!("$ ? ({}@ == 1{})", "!(".repeat(1000), ")".repeat(1000)),
+        ] {
+            assert_eq!(
+                input.parse::<JsonPath>().unwrap_err(),
+                "jsonpath too deeply nested"
+            );
+        }
+
+        // Accessor chains are parsed iteratively, but evaluated recursively.
+        let path = format!("${}", ".a".repeat(1000));
+        assert_eq!(eval(&path, "{}"), Err(JsonPathError::TooDeeplyNested));
+    }
+}
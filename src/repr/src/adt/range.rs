@@ -0,0 +1,616 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A range data type.
+//!
+//! Ranges are modeled after the [PostgreSQL range types]. A range is either
+//! empty, or it has a lower and an upper bound, each of which may be
+//! inclusive, exclusive, or infinite.
+//!
+//! [PostgreSQL range types]: https://www.postgresql.org/docs/current/rangetypes.html
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_lowertest::MzReflect;
+use mz_proto::{RustType, TryFromProtoError};
+
+use crate::Datum;
+
+include!(concat!(env!("OUT_DIR"), "/mz_repr.adt.range.rs"));
+
+/// Flags that describe the shape of a range.
+///
+/// These match the flags used by PostgreSQL's binary encoding of ranges, and
+/// are also used by the in-memory `Row` encoding.
+pub mod flags {
+    /// The range is empty.
+    pub const EMPTY: u8 = 0x01;
+    /// The lower bound is inclusive.
+    pub const LB_INCLUSIVE: u8 = 0x02;
+    /// The upper bound is inclusive.
+    pub const UB_INCLUSIVE: u8 = 0x04;
+    /// The lower bound is infinite.
+    pub const LB_INFINITE: u8 = 0x08;
+    /// The upper bound is infinite.
+    pub const UB_INFINITE: u8 = 0x10;
+}
+
+/// A range of values of type `D`.
+///
+/// Ranges are ordered as in PostgreSQL: the empty range sorts before all other
+/// ranges, and non-empty ranges are ordered by their lower bound and then by
+/// their upper bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Range<D> {
+    /// The bounds of the range, or `None` if the range is empty.
+    pub inner: Option<RangeInner<D>>,
+}
+
+/// The bounds of a non-empty [`Range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RangeInner<D> {
+    pub lower: RangeLowerBound<D>,
+    pub upper: RangeUpperBound<D>,
+}
+
+/// One bound of a [`Range`].
+///
+/// `UPPER` determines whether this is the upper or the lower bound of the
+/// range, which affects how bounds are ordered: an infinite lower bound sorts
+/// before all other lower bounds, while an infinite upper bound sorts after all
+/// other upper bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RangeBound<D, const UPPER: bool> {
+    /// Whether the bound includes its value. Always `false` for infinite
+    /// bounds.
+    pub inclusive: bool,
+    /// The value of the bound, or `None` if the bound is infinite.
+    pub bound: Option<D>,
+}
+
+/// The lower bound of a [`Range`].
+pub type RangeLowerBound<D> = RangeBound<D, false>;
+
+/// The upper bound of a [`Range`].
+pub type RangeUpperBound<D> = RangeBound<D, true>;
+
+impl<D, const UPPER: bool> RangeBound<D, UPPER> {
+    /// Constructs a new finite bound.
+    pub fn new(bound: D, inclusive: bool) -> RangeBound<D, UPPER> {
+        RangeBound {
+            inclusive,
+            bound: Some(bound),
+        }
+    }
+
+    /// Constructs a new infinite bound.
+    pub fn infinite() -> RangeBound<D, UPPER> {
+        RangeBound {
+            inclusive: false,
+            bound: None,
+        }
+    }
+
+    /// Reports whether the bound is infinite.
+    pub fn is_infinite(&self) -> bool {
+        self.bound.is_none()
+    }
+
+    fn map<E, F>(self, f: F) -> RangeBound<E, UPPER>
+    where
+        F: FnOnce(D) -> E,
+    {
+        RangeBound {
+            inclusive: self.inclusive,
+            bound: self.bound.map(f),
+        }
+    }
+}
+
+impl<D: Ord, const UPPER: bool> Ord for RangeBound<D, UPPER> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // When `UPPER` is false, "before" means "less than"; when it is true,
+        // infinite and inclusive upper bounds extend further and therefore
+        // sort later.
+        let before = if UPPER {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+        match (&self.bound, &other.bound) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => before,
+            (Some(_), None) => before.reverse(),
+            (Some(a), Some(b)) => a
+                .cmp(b)
+                .then_with(|| match (self.inclusive, other.inclusive) {
+                    (true, false) => before,
+                    (false, true) => before.reverse(),
+                    _ => Ordering::Equal,
+                }),
+        }
+    }
+}
+
+impl<D: Ord, const UPPER: bool> PartialOrd for RangeBound<D, UPPER> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Ord> Ord for RangeInner<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.lower
+            .cmp(&other.lower)
+            .then_with(|| self.upper.cmp(&other.upper))
+    }
+}
+
+impl<D: Ord> PartialOrd for RangeInner<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Ord> Ord for Range<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `None` sorts before `Some`, so empty ranges sort first.
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<D: Ord> PartialOrd for Range<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Range<D> {
+    /// Constructs a new range from its bounds, or an empty range if `inner` is
+    /// `None`.
+    ///
+    /// The range is not canonicalized. See [`Range::canonicalize`].
+    pub fn new(inner: Option<(RangeLowerBound<D>, RangeUpperBound<D>)>) -> Range<D> {
+        Range {
+            inner: inner.map(|(lower, upper)| RangeInner { lower, upper }),
+        }
+    }
+
+    /// Constructs an empty range.
+    pub fn empty() -> Range<D> {
+        Range { inner: None }
+    }
+
+    /// Reports whether the range is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns the value of the lower bound, or `None` if the range is empty or
+    /// its lower bound is infinite.
+    pub fn lower(&self) -> Option<&D> {
+        self.inner.as_ref().and_then(|i| i.lower.bound.as_ref())
+    }
+
+    /// Returns the value of the upper bound, or `None` if the range is empty or
+    /// its upper bound is infinite.
+    pub fn upper(&self) -> Option<&D> {
+        self.inner.as_ref().and_then(|i| i.upper.bound.as_ref())
+    }
+
+    /// Reports whether the lower bound is inclusive.
+    pub fn lower_inclusive(&self) -> bool {
+        self.inner.as_ref().map_or(false, |i| i.lower.inclusive)
+    }
+
+    /// Reports whether the upper bound is inclusive.
+    pub fn upper_inclusive(&self) -> bool {
+        self.inner.as_ref().map_or(false, |i| i.upper.inclusive)
+    }
+
+    /// Reports whether the lower bound is infinite.
+    pub fn lower_infinite(&self) -> bool {
+        self.inner.as_ref().map_or(false, |i| i.lower.is_infinite())
+    }
+
+    /// Reports whether the upper bound is infinite.
+    pub fn upper_infinite(&self) -> bool {
+        self.inner.as_ref().map_or(false, |i| i.upper.is_infinite())
+    }
+
+    /// Returns the [`flags`] that describe the shape of this range.
+    pub fn flags(&self) -> u8 {
+        match &self.inner {
+            None => flags::EMPTY,
+            Some(RangeInner { lower, upper }) => {
+                let mut f = 0;
+                if lower.is_infinite() {
+                    f |= flags::LB_INFINITE;
+                } else if lower.inclusive {
+                    f |= flags::LB_INCLUSIVE;
+                }
+                if upper.is_infinite() {
+                    f |= flags::UB_INFINITE;
+                } else if upper.inclusive {
+                    f |= flags::UB_INCLUSIVE;
+                }
+                f
+            }
+        }
+    }
+
+    /// Applies `f` to each of the bounds of the range.
+    pub fn map<E, F>(self, mut f: F) -> Range<E>
+    where
+        F: FnMut(D) -> E,
+    {
+        Range {
+            inner: self.inner.map(|RangeInner { lower, upper }| RangeInner {
+                lower: lower.map(&mut f),
+                upper: upper.map(&mut f),
+            }),
+        }
+    }
+}
+
+impl<D: Ord> Range<D> {
+    /// Reports whether `elem` lies within the range.
+    pub fn contains_elem(&self, elem: &D) -> bool {
+        match &self.inner {
+            None => false,
+            Some(RangeInner { lower, upper }) => {
+                let above_lower = match &lower.bound {
+                    None => true,
+                    Some(b) => b < elem || (lower.inclusive && b == elem),
+                };
+                let below_upper = match &upper.bound {
+                    None => true,
+                    Some(b) => elem < b || (upper.inclusive && b == elem),
+                };
+                above_lower && below_upper
+            }
+        }
+    }
+
+    /// Reports whether `other` lies entirely within the range.
+    ///
+    /// The empty range is contained by every range.
+    pub fn contains_range(&self, other: &Range<D>) -> bool {
+        match (&self.inner, &other.inner) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(a), Some(b)) => a.lower <= b.lower && a.upper >= b.upper,
+        }
+    }
+
+    /// Reports whether the range and `other` have any values in common.
+    pub fn overlaps(&self, other: &Range<D>) -> bool {
+        match (&self.inner, &other.inner) {
+            (Some(a), Some(b)) => {
+                lower_precedes_upper(&a.lower, &b.upper) && lower_precedes_upper(&b.lower, &a.upper)
+            }
+            _ => false,
+        }
+    }
+
+    /// Reports whether the range and `other` are adjacent, i.e. they do not
+    /// overlap, but there is no value between them.
+    pub fn adjacent(&self, other: &Range<D>) -> bool {
+        match (&self.inner, &other.inner) {
+            (Some(a), Some(b)) => {
+                bounds_adjacent(&a.upper, &b.lower) || bounds_adjacent(&b.upper, &a.lower)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Reports whether there is at least one value that is both at or above
+/// `lower` and at or below `upper`.
+fn lower_precedes_upper<D: Ord>(lower: &RangeLowerBound<D>, upper: &RangeUpperBound<D>) -> bool {
+    match (&lower.bound, &upper.bound) {
+        (None, _) | (_, None) => true,
+        (Some(l), Some(u)) => l < u || (l == u && lower.inclusive && upper.inclusive),
+    }
+}
+
+/// Reports whether the range ending at `upper` ends exactly where the range
+/// beginning at `lower` begins.
+fn bounds_adjacent<D: Ord>(upper: &RangeUpperBound<D>, lower: &RangeLowerBound<D>) -> bool {
+    match (&upper.bound, &lower.bound) {
+        (Some(u), Some(l)) => u == l && upper.inclusive != lower.inclusive,
+        _ => false,
+    }
+}
+
+impl<'a> Range<Datum<'a>> {
+    /// Puts the range into its canonical form.
+    ///
+    /// Ranges whose bounds describe no values become empty. Ranges over
+    /// discrete types (`int4`, `int8`, and `date`) are converted to use an
+    /// inclusive lower bound and an exclusive upper bound, so that equal ranges
+    /// have equal representations.
+    ///
+    /// Returns an error if the lower bound is greater than the upper bound, or
+    /// if a bound cannot be adjusted without overflowing.
+    pub fn canonicalize(&mut self) -> Result<(), InvalidRangeError> {
+        let inner = match &mut self.inner {
+            None => return Ok(()),
+            Some(inner) => inner,
+        };
+
+        // Infinite bounds are never inclusive.
+        if inner.lower.is_infinite() {
+            inner.lower.inclusive = false;
+        }
+        if inner.upper.is_infinite() {
+            inner.upper.inclusive = false;
+        }
+
+        if let (Some(l), Some(u)) = (&inner.lower.bound, &inner.upper.bound) {
+            match l.cmp(u) {
+                Ordering::Greater => return Err(InvalidRangeError::MisorderedRangeBounds),
+                Ordering::Equal if !(inner.lower.inclusive && inner.upper.inclusive) => {
+                    self.inner = None;
+                    return Ok(());
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(l) = inner.lower.bound {
+            if !inner.lower.inclusive {
+                if let Some(next) = successor(l)? {
+                    inner.lower = RangeBound::new(next, true);
+                }
+            }
+        }
+        if let Some(u) = inner.upper.bound {
+            if inner.upper.inclusive {
+                if let Some(next) = successor(u)? {
+                    inner.upper = RangeBound::new(next, false);
+                }
+            }
+        }
+
+        // Adjusting the bounds of a discrete range can leave it with no
+        // values, e.g. `(1,2)` becomes `[2,2)`.
+        if let (Some(l), Some(u)) = (&inner.lower.bound, &inner.upper.bound) {
+            if l >= u && !(inner.lower.inclusive && inner.upper.inclusive) {
+                self.inner = None;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the value immediately following `d`, if `d` is of a discrete type.
+fn successor(d: Datum) -> Result<Option<Datum>, InvalidRangeError> {
+    let overflow = |ty: &str| InvalidRangeError::CanonicalizationOverflow(ty.into());
+    Ok(match d {
+        Datum::Int32(i) => Some(Datum::Int32(
+            i.checked_add(1).ok_or_else(|| overflow("integer"))?,
+        )),
+        Datum::Int64(i) => Some(Datum::Int64(
+            i.checked_add(1).ok_or_else(|| overflow("bigint"))?,
+        )),
+        Datum::Date(d) if d.is_finite() => {
+            Some(Datum::Date(d.checked_add(1).map_err(|_| overflow("date"))?))
+        }
+        _ => None,
+    })
+}
+
+/// Parses the textual representation of the inclusivity of a pair of range
+/// bounds, e.g. `[)`, returning whether the lower and upper bounds are
+/// inclusive.
+pub fn parse_range_bound_flags(flags: &str) -> Result<(bool, bool), InvalidRangeError> {
+    match flags {
+        "[)" => Ok((true, false)),
+        "[]" => Ok((true, true)),
+        "()" => Ok((false, false)),
+        "(]" => Ok((false, true)),
+        _ => Err(InvalidRangeError::InvalidRangeBoundFlags),
+    }
+}
+
+impl<D: Display> Display for Range<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.inner {
+            None => f.write_str("empty"),
+            Some(RangeInner { lower, upper }) => {
+                f.write_str(if lower.inclusive { "[" } else { "(" })?;
+                if let Some(b) = &lower.bound {
+                    write!(f, "{}", b)?;
+                }
+                f.write_str(",")?;
+                if let Some(b) = &upper.bound {
+                    write!(f, "{}", b)?;
+                }
+                f.write_str(if upper.inclusive { "]" } else { ")" })
+            }
+        }
+    }
+}
+
+/// An error that can occur when constructing a range.
+#[derive(
+    Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize, MzReflect,
+)]
+pub enum InvalidRangeError {
+    /// The lower bound of the range is greater than its upper bound.
+    MisorderedRangeBounds,
+    /// Canonicalizing a bound of the range overflowed its type.
+    CanonicalizationOverflow(String),
+    /// The range bound flags were not one of `[)`, `[]`, `()`, or `(]`.
+    InvalidRangeBoundFlags,
+}
+
+impl fmt::Display for InvalidRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidRangeError::MisorderedRangeBounds => {
+                f.write_str("range lower bound must be less than or equal to range upper bound")
+            }
+            InvalidRangeError::CanonicalizationOverflow(ty) => write!(f, "{} out of range", ty),
+            InvalidRangeError::InvalidRangeBoundFlags => f.write_str("invalid range bound flags"),
+        }
+    }
+}
+
+impl Error for InvalidRangeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl InvalidRangeError {
+    /// Returns a hint for the error, if any.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            InvalidRangeError::InvalidRangeBoundFlags => {
+                Some(r#"Valid values are "[]", "[)", "(]", and "()"."#.into())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RustType<ProtoInvalidRangeError> for InvalidRangeError {
+    fn into_proto(&self) -> ProtoInvalidRangeError {
+        use proto_invalid_range_error::*;
+        use Kind::*;
+        let kind = match self {
+            InvalidRangeError::MisorderedRangeBounds => MisorderedRangeBounds(()),
+            InvalidRangeError::CanonicalizationOverflow(ty) => {
+                CanonicalizationOverflow(ty.into_proto())
+            }
+            InvalidRangeError::InvalidRangeBoundFlags => InvalidRangeBoundFlags(()),
+        };
+        ProtoInvalidRangeError { kind: Some(kind) }
+    }
+
+    fn from_proto(proto: ProtoInvalidRangeError) -> Result<Self, TryFromProtoError> {
+        use proto_invalid_range_error::Kind::*;
+        match proto.kind {
+            Some(kind) => match kind {
+                MisorderedRangeBounds(()) => Ok(InvalidRangeError::MisorderedRangeBounds),
+                CanonicalizationOverflow(ty) => Ok(InvalidRangeError::CanonicalizationOverflow(ty)),
+                InvalidRangeBoundFlags(()) => Ok(InvalidRangeError::InvalidRangeBoundFlags),
+            },
+            None => Err(TryFromProtoError::missing_field(
+                "`ProtoInvalidRangeError::kind`",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use mz_proto::protobuf_roundtrip;
+
+    proptest! {
+        #[test]
+        fn invalid_range_error_protobuf_roundtrip(expect in any::<InvalidRangeError>()) {
+            let actual = protobuf_roundtrip::<_, ProtoInvalidRangeError>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
+    }
+
+    fn int_range(lower: Option<(i32, bool)>, upper: Option<(i32, bool)>) -> Range<Datum<'static>> {
+        let bound = |b: Option<(i32, bool)>| match b {
+            None => RangeBound::infinite(),
+            Some((v, inclusive)) => RangeBound::new(Datum::Int32(v), inclusive),
+        };
+        Range::new(Some((bound(lower), bound(upper))))
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let mut r = int_range(Some((1, false)), Some((5, true)));
+        r.canonicalize().unwrap();
+        assert_eq!(r, int_range(Some((2, true)), Some((6, false))));
+        assert_eq!(r.to_string(), "[2,6)");
+
+        let mut r = int_range(Some((1, false)), Some((2, false)));
+        r.canonicalize().unwrap();
+        assert!(r.is_empty());
+
+        let mut r = int_range(Some((3, true)), Some((3, false)));
+        r.canonicalize().unwrap();
+        assert!(r.is_empty());
+
+        let mut r = int_range(Some((3, true)), Some((3, true)));
+        r.canonicalize().unwrap();
+        assert_eq!(r.to_string(), "[3,4)");
+
+        let mut r = int_range(None, Some((3, true)));
+        r.canonicalize().unwrap();
+        assert_eq!(r.to_string(), "(,4)");
+
+        let mut r = int_range(Some((3, true)), Some((2, true)));
+        assert_eq!(
+            r.canonicalize(),
+            Err(InvalidRangeError::MisorderedRangeBounds)
+        );
+
+        let mut r = int_range(Some((1, true)), Some((i32::MAX, true)));
+        assert_eq!(
+            r.canonicalize(),
+            Err(InvalidRangeError::CanonicalizationOverflow(
+                "integer".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let empty = Range::<Datum>::empty();
+        let unbounded = int_range(None, None);
+        let low = int_range(Some((1, true)), Some((5, false)));
+        let high = int_range(Some((2, true)), Some((3, false)));
+        let wide = int_range(Some((1, true)), None);
+        assert!(empty < unbounded);
+        assert!(unbounded < low);
+        assert!(low < wide);
+        assert!(wide < high);
+    }
+
+    #[test]
+    fn test_operators() {
+        let r = int_range(Some((1, true)), Some((5, false)));
+        assert!(r.contains_elem(&Datum::Int32(1)));
+        assert!(r.contains_elem(&Datum::Int32(4)));
+        assert!(!r.contains_elem(&Datum::Int32(5)));
+        assert!(!Range::empty().contains_elem(&Datum::Int32(1)));
+
+        assert!(r.contains_range(&int_range(Some((2, true)), Some((5, false)))));
+        assert!(r.contains_range(&Range::empty()));
+        assert!(!r.contains_range(&int_range(Some((2, true)), None)));
+
+        assert!(r.overlaps(&int_range(Some((4, true)), Some((9, false)))));
+        assert!(!r.overlaps(&int_range(Some((5, true)), Some((9, false)))));
+        assert!(r.overlaps(&int_range(None, None)));
+        assert!(!r.overlaps(&Range::empty()));
+
+        assert!(r.adjacent(&int_range(Some((5, true)), Some((9, false)))));
+        assert!(r.adjacent(&int_range(None, Some((1, false)))));
+        assert!(!r.adjacent(&int_range(Some((4, true)), Some((9, false)))));
+    }
+}
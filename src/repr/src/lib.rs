@@ -49,8 +49,8 @@ pub use relation::{
     ProtoRelationType, RelationDesc, RelationType,
 };
 pub use row::{
-    datum_list_size, datum_size, datums_size, row_size, DatumList, DatumMap, ProtoRow, Row,
-    RowArena, RowPacker, RowRef,
+    datum_list_size, datum_size, datums_size, row_size, DatumList, DatumMap, DatumNested, ProtoRow,
+    Row, RowArena, RowColumnarDecoder, RowColumnarEncoder, RowPacker, RowRef,
 };
 pub use scalar::{
     arb_datum, AsColumnType, Datum, DatumType, PropArray, PropDatum, PropDict, PropList,
@@ -37,12 +37,15 @@ use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
+use crate::adt::range::{self, InvalidRangeError, Range, RangeBound};
 use crate::adt::timestamp::CheckedTimestamp;
 use crate::scalar::arb_datum;
 use crate::{Datum, Timestamp};
 
 mod encoding;
 
+pub use encoding::{RowColumnarDecoder, RowColumnarEncoder};
+
 include!(concat!(env!("OUT_DIR"), "/mz_repr.row.rs"));
 
 /// A packed representation for `Datum`s.
@@ -251,6 +254,47 @@ impl<'a> Serialize for DatumList<'a> {
     }
 }
 
+/// A single Datum nested within another Datum, e.g. the bound of a range.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DatumNested<'a> {
+    /// Points at the serialized datum
+    val: &'a [u8],
+}
+
+impl<'a> DatumNested<'a> {
+    /// Returns the nested datum.
+    pub fn datum(&self) -> Datum<'a> {
+        let mut offset = 0;
+        // SAFETY: `val` is always a single datum that was written by
+        // `push_datum`.
+        unsafe { read_datum(self.val, &mut offset) }
+    }
+}
+
+impl<'a> Debug for DatumNested<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.datum(), f)
+    }
+}
+
+impl<'a> fmt::Display for DatumNested<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.datum(), f)
+    }
+}
+
+impl Ord for DatumNested<'_> {
+    fn cmp(&self, other: &DatumNested) -> Ordering {
+        self.datum().cmp(&other.datum())
+    }
+}
+
+impl PartialOrd for DatumNested<'_> {
+    fn partial_cmp(&self, other: &DatumNested) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// A mapping from string keys to Datums
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct DatumMap<'a> {
@@ -309,6 +353,7 @@ enum Tag {
     UInt16,
     UInt64,
     MzTimestamp,
+    Range,
 }
 
 // --------------------------------------------------------------------------------
@@ -523,6 +568,35 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
             let t = Timestamp::decode(read_byte_array(data, offset));
             Datum::MzTimestamp(t)
         }
+        Tag::Range => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            let flag_byte = read_byte(data, offset);
+            if flag_byte & range::flags::EMPTY != 0 {
+                Datum::Range(Range::empty())
+            } else {
+                let mut read_bound = |infinite_flag| {
+                    if flag_byte & infinite_flag != 0 {
+                        None
+                    } else {
+                        let start = *offset;
+                        read_datum(data, offset);
+                        Some(DatumNested {
+                            val: &data[start..*offset],
+                        })
+                    }
+                };
+                let lower = RangeBound {
+                    inclusive: flag_byte & range::flags::LB_INCLUSIVE != 0,
+                    bound: read_bound(range::flags::LB_INFINITE),
+                };
+                let upper = RangeBound {
+                    inclusive: flag_byte & range::flags::UB_INCLUSIVE != 0,
+                    bound: read_bound(range::flags::UB_INFINITE),
+                };
+                Datum::Range(Range::new(Some((lower, upper))))
+            }
+        }
     }
 }
 
@@ -702,6 +776,17 @@ where
             data.push(Tag::MzTimestamp.into());
             data.extend_from_slice(&t.encode());
         }
+        Datum::Range(range) => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            data.push(Tag::Range.into());
+            data.push(range.flags());
+            if let Some(inner) = range.inner {
+                for bound in [inner.lower.bound, inner.upper.bound].into_iter().flatten() {
+                    data.extend_from_slice(bound.val);
+                }
+            }
+        }
         Datum::Dummy => data.push(Tag::Dummy.into()),
         Datum::Numeric(mut n) => {
             // Pseudo-canonical representation of decimal values with
@@ -819,6 +904,16 @@ pub fn datum_size(datum: &Datum) -> usize {
         Datum::Map(dict) => 1 + size_of::<u64>() + dict.data.len(),
         Datum::JsonNull => 1,
         Datum::MzTimestamp(_) => 1 + size_of::<Timestamp>(),
+        Datum::Range(range) => {
+            1 + size_of::<u8>()
+                + range.inner.map_or(0, |inner| {
+                    [inner.lower.bound, inner.upper.bound]
+                        .into_iter()
+                        .flatten()
+                        .map(|bound| bound.val.len())
+                        .sum()
+                })
+        }
         Datum::Dummy => 1,
         Datum::Numeric(d) => {
             let mut d = d.0.clone();
@@ -1164,6 +1259,29 @@ impl RowPacker<'_> {
         Ok(())
     }
 
+    /// Pushes a [`Range`] after canonicalizing it.
+    ///
+    /// Returns an error if the range is invalid. If an error occurs, the
+    /// packer's state will be unchanged.
+    pub fn push_range<'a>(&mut self, mut range: Range<Datum<'a>>) -> Result<(), InvalidRangeError> {
+        // Ranges are encoded as follows.
+        //
+        // u8    range flags (see `range::flags`)
+        // ..    lower bound datum, if the range is non-empty and the lower
+        //       bound is finite
+        // ..    upper bound datum, if the range is non-empty and the upper
+        //       bound is finite
+        range.canonicalize()?;
+        self.row.data.push(Tag::Range.into());
+        self.row.data.push(range.flags());
+        if let Some(inner) = range.inner {
+            for bound in [inner.lower.bound, inner.upper.bound].into_iter().flatten() {
+                self.push(bound);
+            }
+        }
+        Ok(())
+    }
+
     /// Convenience function to push a `DatumList` from an iter of `Datum`s
     ///
     /// See [`RowPacker::push_dict_with`] if you need to be able to handle errors
@@ -1753,6 +1871,63 @@ mod tests {
         assert!(row.data.is_empty());
     }
 
+    #[test]
+    fn test_range() {
+        // Construct a range using `Row::push_range` and verify that it is
+        // canonicalized and unpacks correctly.
+        let mut row = Row::default();
+        row.packer()
+            .push_range(Range::new(Some((
+                RangeBound::new(Datum::Int32(1), false),
+                RangeBound::new(Datum::Int32(5), true),
+            ))))
+            .unwrap();
+        let range1 = row.unpack_first().unwrap_range();
+        assert_eq!(range1.lower().map(|b| b.datum()), Some(Datum::Int32(2)));
+        assert_eq!(range1.upper().map(|b| b.datum()), Some(Datum::Int32(6)));
+        assert!(range1.lower_inclusive());
+        assert!(!range1.upper_inclusive());
+        assert_eq!(datum_size(&Datum::Range(range1)), row.data.len());
+
+        // Pack a previously-constructed `Datum::Range` and verify that it
+        // unpacks correctly.
+        let row = Row::pack_slice(&[Datum::Range(range1)]);
+        let range2 = row.unpack_first().unwrap_range();
+        assert_eq!(range1, range2);
+
+        // Infinite bounds are omitted from the encoding.
+        let mut row = Row::default();
+        row.packer()
+            .push_range(Range::new(Some((
+                RangeBound::infinite(),
+                RangeBound::new(Datum::Int64(7), false),
+            ))))
+            .unwrap();
+        let range = row.unpack_first().unwrap_range();
+        assert!(range.lower_infinite());
+        assert_eq!(range.upper().map(|b| b.datum()), Some(Datum::Int64(7)));
+        assert_eq!(datum_size(&Datum::Range(range)), row.data.len());
+
+        // Ranges that contain no values are empty.
+        let mut row = Row::default();
+        row.packer()
+            .push_range(Range::new(Some((
+                RangeBound::new(Datum::Int32(1), false),
+                RangeBound::new(Datum::Int32(2), false),
+            ))))
+            .unwrap();
+        assert!(row.unpack_first().unwrap_range().is_empty());
+
+        // Misordered bounds are rejected without modifying the row.
+        let mut row = Row::default();
+        let res = row.packer().push_range(Range::new(Some((
+            RangeBound::new(Datum::Int32(2), true),
+            RangeBound::new(Datum::Int32(1), true),
+        ))));
+        assert_eq!(res, Err(InvalidRangeError::MisorderedRangeBounds));
+        assert!(row.data.is_empty());
+    }
+
     #[test]
     fn test_nesting() {
         let mut row = Row::default();
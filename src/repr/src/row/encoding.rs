@@ -11,6 +11,15 @@
 //!
 //! See row.proto for details.
 
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use arrow2::array::{
+    Array, BinaryArray, BooleanArray, MutableArray, MutableBinaryArray, MutableBooleanArray,
+    MutablePrimitiveArray, MutableUtf8Array, PrimitiveArray, Utf8Array,
+};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::datatypes::{DataType, Field};
 use bytes::BufMut;
 use chrono::Timelike;
 use dec::Decimal;
@@ -18,18 +27,23 @@ use prost::Message;
 use uuid::Uuid;
 
 use mz_ore::cast::CastFrom;
+use mz_persist_types::columnar::{
+    truncate_bytes, truncate_string, ColumnStats, Part, PartDecoder, PartEncoder, PartStats,
+    Schema, TruncateBound, STATS_MAX_BOUND_LEN,
+};
 use mz_persist_types::Codec;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
 use crate::adt::array::ArrayDimension;
 use crate::adt::numeric::Numeric;
+use crate::adt::range::{self, Range, RangeBound};
 use crate::chrono::ProtoNaiveTime;
 use crate::row::proto_datum::DatumType;
 use crate::row::{
     ProtoArray, ProtoArrayDimension, ProtoDatum, ProtoDatumOther, ProtoDict, ProtoDictElement,
-    ProtoNumeric, ProtoRow,
+    ProtoNumeric, ProtoRange, ProtoRow,
 };
-use crate::{Datum, Row, RowPacker};
+use crate::{Datum, RelationDesc, Row, RowPacker, ScalarType};
 
 impl Codec for Row {
     fn codec_name() -> String {
@@ -135,6 +149,16 @@ impl<'a> From<Datum<'a>> for ProtoDatum {
             Datum::JsonNull => DatumType::Other(ProtoDatumOther::JsonNull.into()),
             Datum::Uuid(x) => DatumType::Uuid(x.as_bytes().to_vec()),
             Datum::MzTimestamp(x) => DatumType::MzTimestamp(x.into()),
+            Datum::Range(x) => DatumType::Range(ProtoRange {
+                flags: x.flags().into(),
+                bounds: Some(ProtoRow {
+                    datums: [x.lower(), x.upper()]
+                        .into_iter()
+                        .flatten()
+                        .map(|b| b.datum().into())
+                        .collect(),
+                }),
+            }),
             Datum::Dummy => DatumType::Other(ProtoDatumOther::Dummy.into()),
             Datum::Null => DatumType::Other(ProtoDatumOther::Null.into()),
         };
@@ -247,6 +271,41 @@ impl RowPacker<'_> {
                 self.push(Datum::from(n))
             }
             Some(DatumType::MzTimestamp(x)) => self.push(Datum::MzTimestamp((*x).into())),
+            Some(DatumType::Range(x)) => {
+                let flags = u8::try_from(x.flags)
+                    .map_err(|_| format!("invalid range flags: {}", x.flags))?;
+                let range = if flags & range::flags::EMPTY != 0 {
+                    Range::empty()
+                } else {
+                    // TODO: Could we avoid this Row alloc if we made a
+                    // push_range_with?
+                    let bounds_row = match x.bounds.as_ref() {
+                        None => Row::default(),
+                        Some(bounds) => Row::try_from(bounds)?,
+                    };
+                    let mut bounds = bounds_row.iter();
+                    let mut next_bound = |infinite_flag| {
+                        if flags & infinite_flag != 0 {
+                            Ok(None)
+                        } else {
+                            bounds
+                                .next()
+                                .map(Some)
+                                .ok_or_else(|| "missing range bound".to_string())
+                        }
+                    };
+                    let lower = RangeBound {
+                        inclusive: flags & range::flags::LB_INCLUSIVE != 0,
+                        bound: next_bound(range::flags::LB_INFINITE)?,
+                    };
+                    let upper = RangeBound {
+                        inclusive: flags & range::flags::UB_INCLUSIVE != 0,
+                        bound: next_bound(range::flags::UB_INFINITE)?,
+                    };
+                    Range::new(Some((lower, upper)))
+                };
+                self.push_range(range).map_err(|err| err.to_string())?
+            }
             None => return Err("unknown datum type".into()),
         };
         Ok(())
@@ -289,9 +348,673 @@ impl RustType<ProtoRow> for Row {
     }
 }
 
+/// The Arrow field metadata key marking columns that store each datum in its
+/// [ProtoDatum] encoding.
+const PROTO_DATUM_METADATA_KEY: &str = "mz:proto_datum";
+
+/// How a column of a [RelationDesc] is stored in a [Part].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnFormat {
+    Bool,
+    Int16,
+    Int32,
+    Int64,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Bytes,
+    String,
+    /// Types without a natural Arrow equivalent, stored as [ProtoDatum]s.
+    Proto,
+}
+
+impl ColumnFormat {
+    fn for_type(typ: &ScalarType) -> Self {
+        match typ {
+            ScalarType::Bool => ColumnFormat::Bool,
+            ScalarType::Int16 => ColumnFormat::Int16,
+            ScalarType::Int32 => ColumnFormat::Int32,
+            ScalarType::Int64 => ColumnFormat::Int64,
+            ScalarType::UInt16 => ColumnFormat::UInt16,
+            ScalarType::UInt32 => ColumnFormat::UInt32,
+            ScalarType::UInt64 => ColumnFormat::UInt64,
+            ScalarType::Float32 => ColumnFormat::Float32,
+            ScalarType::Float64 => ColumnFormat::Float64,
+            ScalarType::Bytes => ColumnFormat::Bytes,
+            ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                ColumnFormat::String
+            }
+            _ => ColumnFormat::Proto,
+        }
+    }
+
+    /// Returns the format of a column with the given field, if any.
+    fn for_field(field: &Field) -> Option<Self> {
+        let format = match field.data_type() {
+            DataType::Boolean => ColumnFormat::Bool,
+            DataType::Int16 => ColumnFormat::Int16,
+            DataType::Int32 => ColumnFormat::Int32,
+            DataType::Int64 => ColumnFormat::Int64,
+            DataType::UInt16 => ColumnFormat::UInt16,
+            DataType::UInt32 => ColumnFormat::UInt32,
+            DataType::UInt64 => ColumnFormat::UInt64,
+            DataType::Float32 => ColumnFormat::Float32,
+            DataType::Float64 => ColumnFormat::Float64,
+            DataType::Utf8 => ColumnFormat::String,
+            DataType::Binary if field.metadata.contains_key(PROTO_DATUM_METADATA_KEY) => {
+                ColumnFormat::Proto
+            }
+            DataType::Binary => ColumnFormat::Bytes,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    fn field(&self, name: String, nullable: bool) -> Field {
+        let data_type = match self {
+            ColumnFormat::Bool => DataType::Boolean,
+            ColumnFormat::Int16 => DataType::Int16,
+            ColumnFormat::Int32 => DataType::Int32,
+            ColumnFormat::Int64 => DataType::Int64,
+            ColumnFormat::UInt16 => DataType::UInt16,
+            ColumnFormat::UInt32 => DataType::UInt32,
+            ColumnFormat::UInt64 => DataType::UInt64,
+            ColumnFormat::Float32 => DataType::Float32,
+            ColumnFormat::Float64 => DataType::Float64,
+            ColumnFormat::Bytes | ColumnFormat::Proto => DataType::Binary,
+            ColumnFormat::String => DataType::Utf8,
+        };
+        let field = Field::new(name, data_type, nullable);
+        if *self == ColumnFormat::Proto {
+            let mut metadata = BTreeMap::new();
+            metadata.insert(PROTO_DATUM_METADATA_KEY.to_owned(), "true".to_owned());
+            field.with_metadata(metadata)
+        } else {
+            field
+        }
+    }
+}
+
+/// The fields used to store the columns of `desc`.
+///
+/// Columns are named by their position rather than by their names in `desc`,
+/// which need not be unique.
+fn columnar_fields(desc: &RelationDesc) -> Vec<Field> {
+    desc.typ()
+        .column_types
+        .iter()
+        .enumerate()
+        .map(|(idx, typ)| {
+            ColumnFormat::for_type(&typ.scalar_type).field(idx.to_string(), typ.nullable)
+        })
+        .collect()
+}
+
+impl Schema<Row> for RelationDesc {
+    fn columns(&self) -> Vec<Field> {
+        columnar_fields(self)
+    }
+
+    fn encoder(&self) -> Box<dyn PartEncoder<Row>> {
+        Box::new(RowColumnarEncoder::new(self))
+    }
+
+    fn decoder(&self, part: &Part) -> Result<Box<dyn PartDecoder<Row>>, String> {
+        Ok(Box::new(RowColumnarDecoder::new(self, part)?))
+    }
+
+    fn stats(&self, part: &Part) -> Result<PartStats, String> {
+        RowColumnarDecoder::new(self, part)?.stats()
+    }
+}
+
+/// A column of datums under construction.
+#[derive(Debug)]
+enum DatumColumnBuilder {
+    Bool(MutableBooleanArray),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    UInt16(MutablePrimitiveArray<u16>),
+    UInt32(MutablePrimitiveArray<u32>),
+    UInt64(MutablePrimitiveArray<u64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Bytes(MutableBinaryArray<i32>),
+    String(MutableUtf8Array<i32>),
+    Proto(MutableBinaryArray<i32>),
+}
+
+impl DatumColumnBuilder {
+    fn new(format: ColumnFormat) -> Self {
+        match format {
+            ColumnFormat::Bool => DatumColumnBuilder::Bool(MutableBooleanArray::new()),
+            ColumnFormat::Int16 => DatumColumnBuilder::Int16(MutablePrimitiveArray::new()),
+            ColumnFormat::Int32 => DatumColumnBuilder::Int32(MutablePrimitiveArray::new()),
+            ColumnFormat::Int64 => DatumColumnBuilder::Int64(MutablePrimitiveArray::new()),
+            ColumnFormat::UInt16 => DatumColumnBuilder::UInt16(MutablePrimitiveArray::new()),
+            ColumnFormat::UInt32 => DatumColumnBuilder::UInt32(MutablePrimitiveArray::new()),
+            ColumnFormat::UInt64 => DatumColumnBuilder::UInt64(MutablePrimitiveArray::new()),
+            ColumnFormat::Float32 => DatumColumnBuilder::Float32(MutablePrimitiveArray::new()),
+            ColumnFormat::Float64 => DatumColumnBuilder::Float64(MutablePrimitiveArray::new()),
+            ColumnFormat::Bytes => DatumColumnBuilder::Bytes(MutableBinaryArray::new()),
+            ColumnFormat::String => DatumColumnBuilder::String(MutableUtf8Array::new()),
+            ColumnFormat::Proto => DatumColumnBuilder::Proto(MutableBinaryArray::new()),
+        }
+    }
+
+    /// Appends `datum` to the column, returning an error if its type doesn't
+    /// match the column's.
+    fn push(&mut self, datum: Datum, buf: &mut Vec<u8>) -> Result<(), String> {
+        match (self, datum) {
+            (DatumColumnBuilder::Proto(array), datum) => {
+                buf.clear();
+                ProtoDatum::from(datum)
+                    .encode(buf)
+                    .expect("no required fields means no initialization errors");
+                array.push(Some(buf.as_slice()));
+            }
+            (DatumColumnBuilder::Bool(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Int16(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Int32(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Int64(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::UInt16(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::UInt32(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::UInt64(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Float32(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Float64(array), Datum::Null) => array.push(None),
+            (DatumColumnBuilder::Bytes(array), Datum::Null) => array.push::<&[u8]>(None),
+            (DatumColumnBuilder::String(array), Datum::Null) => array.push::<&str>(None),
+            (DatumColumnBuilder::Bool(array), Datum::False) => array.push(Some(false)),
+            (DatumColumnBuilder::Bool(array), Datum::True) => array.push(Some(true)),
+            (DatumColumnBuilder::Int16(array), Datum::Int16(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::Int32(array), Datum::Int32(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::Int64(array), Datum::Int64(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::UInt16(array), Datum::UInt16(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::UInt32(array), Datum::UInt32(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::UInt64(array), Datum::UInt64(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::Float32(array), Datum::Float32(x)) => {
+                array.push(Some(x.into_inner()))
+            }
+            (DatumColumnBuilder::Float64(array), Datum::Float64(x)) => {
+                array.push(Some(x.into_inner()))
+            }
+            (DatumColumnBuilder::Bytes(array), Datum::Bytes(x)) => array.push(Some(x)),
+            (DatumColumnBuilder::String(array), Datum::String(x)) => array.push(Some(x)),
+            (builder, datum) => {
+                return Err(format!(
+                    "datum {:?} does not match column {:?}",
+                    datum,
+                    builder.format()
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn format(&self) -> ColumnFormat {
+        match self {
+            DatumColumnBuilder::Bool(_) => ColumnFormat::Bool,
+            DatumColumnBuilder::Int16(_) => ColumnFormat::Int16,
+            DatumColumnBuilder::Int32(_) => ColumnFormat::Int32,
+            DatumColumnBuilder::Int64(_) => ColumnFormat::Int64,
+            DatumColumnBuilder::UInt16(_) => ColumnFormat::UInt16,
+            DatumColumnBuilder::UInt32(_) => ColumnFormat::UInt32,
+            DatumColumnBuilder::UInt64(_) => ColumnFormat::UInt64,
+            DatumColumnBuilder::Float32(_) => ColumnFormat::Float32,
+            DatumColumnBuilder::Float64(_) => ColumnFormat::Float64,
+            DatumColumnBuilder::Bytes(_) => ColumnFormat::Bytes,
+            DatumColumnBuilder::String(_) => ColumnFormat::String,
+            DatumColumnBuilder::Proto(_) => ColumnFormat::Proto,
+        }
+    }
+
+    fn finish(self) -> Box<dyn Array> {
+        match self {
+            DatumColumnBuilder::Bool(mut array) => array.as_box(),
+            DatumColumnBuilder::Int16(mut array) => array.as_box(),
+            DatumColumnBuilder::Int32(mut array) => array.as_box(),
+            DatumColumnBuilder::Int64(mut array) => array.as_box(),
+            DatumColumnBuilder::UInt16(mut array) => array.as_box(),
+            DatumColumnBuilder::UInt32(mut array) => array.as_box(),
+            DatumColumnBuilder::UInt64(mut array) => array.as_box(),
+            DatumColumnBuilder::Float32(mut array) => array.as_box(),
+            DatumColumnBuilder::Float64(mut array) => array.as_box(),
+            DatumColumnBuilder::Bytes(mut array) | DatumColumnBuilder::Proto(mut array) => {
+                array.as_box()
+            }
+            DatumColumnBuilder::String(mut array) => array.as_box(),
+        }
+    }
+}
+
+/// Encodes [Row]s with the columns of a [RelationDesc] into a [Part].
+#[derive(Debug)]
+pub struct RowColumnarEncoder {
+    len: usize,
+    fields: Vec<Field>,
+    columns: Vec<DatumColumnBuilder>,
+    buf: Vec<u8>,
+    error: Option<String>,
+}
+
+impl RowColumnarEncoder {
+    /// Returns an encoder for rows matching `desc`.
+    pub fn new(desc: &RelationDesc) -> Self {
+        let columns = desc
+            .typ()
+            .column_types
+            .iter()
+            .map(|typ| DatumColumnBuilder::new(ColumnFormat::for_type(&typ.scalar_type)))
+            .collect();
+        RowColumnarEncoder {
+            len: 0,
+            fields: columnar_fields(desc),
+            columns,
+            buf: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Appends `row` to the columns being built.
+    ///
+    /// If `row` doesn't match the encoder's [RelationDesc], the error is
+    /// reported by [RowColumnarEncoder::finish].
+    pub fn append(&mut self, row: &Row) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut datums = row.iter();
+        for (field, column) in self.fields.iter().zip(self.columns.iter_mut()) {
+            let datum = match datums.next() {
+                Some(datum) => datum,
+                None => {
+                    self.error = Some(format!("row {:?} has too few columns", row));
+                    return;
+                }
+            };
+            if datum.is_null() && !field.is_nullable {
+                self.error = Some(format!("null value in non-nullable column {}", field.name));
+                return;
+            }
+            if let Err(err) = column.push(datum, &mut self.buf) {
+                self.error = Some(err);
+                return;
+            }
+        }
+        if datums.next().is_some() {
+            self.error = Some(format!("row {:?} has too many columns", row));
+            return;
+        }
+        self.len += 1;
+    }
+
+    /// Finishes building the columns.
+    pub fn finish(self) -> Result<Part, String> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let columns = self.columns.into_iter().map(|x| x.finish()).collect();
+        Part::new(self.len, self.fields, columns)
+    }
+}
+
+impl PartEncoder<Row> for RowColumnarEncoder {
+    fn encode(&mut self, val: &Row) {
+        self.append(val)
+    }
+
+    fn finish(self: Box<Self>) -> Result<Part, String> {
+        RowColumnarEncoder::finish(*self)
+    }
+}
+
+/// A column of datums read from a [Part].
+#[derive(Debug, Clone)]
+enum DatumColumn {
+    Bool(BooleanArray),
+    Int16(PrimitiveArray<i16>),
+    Int32(PrimitiveArray<i32>),
+    Int64(PrimitiveArray<i64>),
+    UInt16(PrimitiveArray<u16>),
+    UInt32(PrimitiveArray<u32>),
+    UInt64(PrimitiveArray<u64>),
+    Float32(PrimitiveArray<f32>),
+    Float64(PrimitiveArray<f64>),
+    Bytes(BinaryArray<i32>),
+    String(Utf8Array<i32>),
+    Proto(BinaryArray<i32>),
+}
+
+impl DatumColumn {
+    fn new(format: ColumnFormat, array: &dyn Array) -> Option<Self> {
+        fn downcast<A: Clone + 'static>(array: &dyn Array) -> Option<A> {
+            array.as_any().downcast_ref::<A>().cloned()
+        }
+        let column = match format {
+            ColumnFormat::Bool => DatumColumn::Bool(downcast(array)?),
+            ColumnFormat::Int16 => DatumColumn::Int16(downcast(array)?),
+            ColumnFormat::Int32 => DatumColumn::Int32(downcast(array)?),
+            ColumnFormat::Int64 => DatumColumn::Int64(downcast(array)?),
+            ColumnFormat::UInt16 => DatumColumn::UInt16(downcast(array)?),
+            ColumnFormat::UInt32 => DatumColumn::UInt32(downcast(array)?),
+            ColumnFormat::UInt64 => DatumColumn::UInt64(downcast(array)?),
+            ColumnFormat::Float32 => DatumColumn::Float32(downcast(array)?),
+            ColumnFormat::Float64 => DatumColumn::Float64(downcast(array)?),
+            ColumnFormat::Bytes => DatumColumn::Bytes(downcast(array)?),
+            ColumnFormat::String => DatumColumn::String(downcast(array)?),
+            ColumnFormat::Proto => DatumColumn::Proto(downcast(array)?),
+        };
+        Some(column)
+    }
+
+    fn is_null(&self, idx: usize) -> bool {
+        match self {
+            DatumColumn::Bool(array) => array.is_null(idx),
+            DatumColumn::Int16(array) => array.is_null(idx),
+            DatumColumn::Int32(array) => array.is_null(idx),
+            DatumColumn::Int64(array) => array.is_null(idx),
+            DatumColumn::UInt16(array) => array.is_null(idx),
+            DatumColumn::UInt32(array) => array.is_null(idx),
+            DatumColumn::UInt64(array) => array.is_null(idx),
+            DatumColumn::Float32(array) => array.is_null(idx),
+            DatumColumn::Float64(array) => array.is_null(idx),
+            DatumColumn::Bytes(array) | DatumColumn::Proto(array) => array.is_null(idx),
+            DatumColumn::String(array) => array.is_null(idx),
+        }
+    }
+
+    fn push_into(&self, idx: usize, packer: &mut RowPacker) -> Result<(), String> {
+        if self.is_null(idx) {
+            packer.push(Datum::Null);
+            return Ok(());
+        }
+        match self {
+            DatumColumn::Bool(array) => packer.push(Datum::from(array.value(idx))),
+            DatumColumn::Int16(array) => packer.push(Datum::Int16(array.value(idx))),
+            DatumColumn::Int32(array) => packer.push(Datum::Int32(array.value(idx))),
+            DatumColumn::Int64(array) => packer.push(Datum::Int64(array.value(idx))),
+            DatumColumn::UInt16(array) => packer.push(Datum::UInt16(array.value(idx))),
+            DatumColumn::UInt32(array) => packer.push(Datum::UInt32(array.value(idx))),
+            DatumColumn::UInt64(array) => packer.push(Datum::UInt64(array.value(idx))),
+            DatumColumn::Float32(array) => packer.push(Datum::Float32(array.value(idx).into())),
+            DatumColumn::Float64(array) => packer.push(Datum::Float64(array.value(idx).into())),
+            DatumColumn::Bytes(array) => packer.push(Datum::Bytes(array.value(idx))),
+            DatumColumn::String(array) => packer.push(Datum::String(array.value(idx))),
+            DatumColumn::Proto(array) => {
+                let proto = ProtoDatum::decode(array.value(idx)).map_err(|err| err.to_string())?;
+                packer.try_push_proto(&proto)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the null count and the smallest and largest non-null values
+    /// of the first `len` values in the column.
+    fn stats(&self, len: usize) -> Result<ColumnStats, String> {
+        let mut null_count = 0;
+        let mut lower: Option<Row> = None;
+        let mut upper: Option<Row> = None;
+        let mut scratch = Row::default();
+        for idx in 0..len {
+            if self.is_null(idx) {
+                null_count += 1;
+                continue;
+            }
+            self.push_into(idx, &mut scratch.packer())?;
+            let datum = scratch.unpack_first();
+            if lower.as_ref().map_or(true, |x| datum < x.unpack_first()) {
+                lower = Some(scratch.clone());
+            }
+            if upper.as_ref().map_or(true, |x| datum > x.unpack_first()) {
+                upper = Some(scratch.clone());
+            }
+        }
+        Ok(ColumnStats {
+            null_count,
+            lower: lower.and_then(|x| Self::encode_bound(&x, TruncateBound::Lower)),
+            upper: upper.and_then(|x| Self::encode_bound(&x, TruncateBound::Upper)),
+        })
+    }
+
+    /// Encodes a single-datum row as a bound of a column's values, truncating
+    /// it to about [STATS_MAX_BOUND_LEN] bytes.
+    ///
+    /// Strings and byte strings are truncated to a prefix that still bounds
+    /// the datum from the given side. Other datums that are too large are
+    /// dropped, leaving that side of the column unbounded.
+    fn encode_bound(row: &Row, bound: TruncateBound) -> Option<Vec<u8>> {
+        let truncated = match row.unpack_first() {
+            Datum::String(x) => {
+                let x = truncate_string(x, STATS_MAX_BOUND_LEN, bound)?;
+                Some(Row::pack_slice(&[Datum::String(&x)]))
+            }
+            Datum::Bytes(x) => {
+                let x = truncate_bytes(x, STATS_MAX_BOUND_LEN, bound)?;
+                Some(Row::pack_slice(&[Datum::Bytes(&x)]))
+            }
+            _ => None,
+        };
+        let mut buf = Vec::new();
+        match truncated {
+            Some(truncated) => truncated.encode(&mut buf),
+            None => {
+                row.encode(&mut buf);
+                if buf.len() > STATS_MAX_BOUND_LEN {
+                    return None;
+                }
+            }
+        }
+        Some(buf)
+    }
+
+    /// See [RowColumnarDecoder::filter].
+    fn filter<F: Fn(Ordering) -> bool>(&self, literal: Datum, pred: F) -> Option<Bitmap> {
+        fn mask<T, F>(
+            len: usize,
+            is_null: impl Fn(usize) -> bool,
+            value: impl Fn(usize) -> T,
+            literal: T,
+            pred: F,
+        ) -> Bitmap
+        where
+            T: Ord,
+            F: Fn(Ordering) -> bool,
+        {
+            (0..len)
+                .map(|idx| !is_null(idx) && pred(value(idx).cmp(&literal)))
+                .collect::<MutableBitmap>()
+                .into()
+        }
+        let ret = match (self, literal) {
+            (DatumColumn::Bool(a), Datum::True | Datum::False) => mask(
+                a.len(),
+                |i| a.is_null(i),
+                |i| a.value(i),
+                literal == Datum::True,
+                pred,
+            ),
+            (DatumColumn::Int16(a), Datum::Int16(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::Int32(a), Datum::Int32(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::Int64(a), Datum::Int64(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::UInt16(a), Datum::UInt16(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::UInt32(a), Datum::UInt32(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::UInt64(a), Datum::UInt64(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::Bytes(a), Datum::Bytes(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            (DatumColumn::String(a), Datum::String(x)) => {
+                mask(a.len(), |i| a.is_null(i), |i| a.value(i), x, pred)
+            }
+            // Floats are ordered differently by Datum than by f32 and f64, so
+            // we leave them, along with everything else, to the caller.
+            _ => return None,
+        };
+        Some(ret)
+    }
+}
+
+/// Decodes [Row]s with the columns of a [RelationDesc] from a [Part].
+///
+/// Columns of the [RelationDesc] that are missing from the [Part] are
+/// decoded as [Datum::Null].
+#[derive(Debug, Clone)]
+pub struct RowColumnarDecoder {
+    len: usize,
+    columns: Vec<Option<DatumColumn>>,
+}
+
+impl RowColumnarDecoder {
+    /// Returns a decoder for the rows matching `desc` stored in `part`.
+    pub fn new(desc: &RelationDesc, part: &Part) -> Result<Self, String> {
+        let mut columns = Vec::new();
+        for (typ, expected) in desc.typ().column_types.iter().zip(columnar_fields(desc)) {
+            let format = ColumnFormat::for_type(&typ.scalar_type);
+            let idx = match part.fields().iter().position(|x| x.name == expected.name) {
+                Some(idx) => idx,
+                None => {
+                    columns.push(None);
+                    continue;
+                }
+            };
+            let field = &part.fields()[idx];
+            // A column that was written as nullable can't be read as
+            // non-nullable, but the reverse is fine.
+            if field.data_type != expected.data_type
+                || field.metadata != expected.metadata
+                || (field.is_nullable && !expected.is_nullable)
+            {
+                return Err(format!("expected column {:?} got {:?}", expected, field));
+            }
+            let column = DatumColumn::new(format, part.columns()[idx].as_ref())
+                .ok_or_else(|| format!("column {} doesn't match its field", field.name))?;
+            columns.push(Some(column));
+        }
+        Ok(RowColumnarDecoder {
+            len: part.len(),
+            columns,
+        })
+    }
+
+    /// Returns a decoder for rows with `arity` columns stored in `part`, using
+    /// only the column types recorded in `part` itself.
+    ///
+    /// This is for readers that know which columns they need but not the
+    /// [RelationDesc] that the rows were written with. Fields of `part` that
+    /// are not named by a column position, such as those added by wrappers
+    /// around [RowColumnarEncoder], are ignored.
+    pub fn from_part(arity: usize, part: &Part) -> Result<Self, String> {
+        let mut columns = vec![None; arity];
+        for (field, array) in part.fields().iter().zip(part.columns()) {
+            let idx = match field.name.parse::<usize>() {
+                Ok(idx) => idx,
+                Err(_) => continue,
+            };
+            if idx >= arity {
+                return Err(format!(
+                    "column {} out of range for arity {}",
+                    field.name, arity
+                ));
+            }
+            let column = ColumnFormat::for_field(field)
+                .and_then(|format| DatumColumn::new(format, array.as_ref()))
+                .ok_or_else(|| format!("unsupported column {:?}", field))?;
+            columns[idx] = Some(column);
+        }
+        Ok(RowColumnarDecoder {
+            len: part.len(),
+            columns,
+        })
+    }
+
+    /// The number of rows stored in the part.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the part stores no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns statistics about the columns read from the part.
+    ///
+    /// Columns are keyed by their position. The bounds of each column are the
+    /// [Codec] encodings of single-datum [Row]s. Long strings and byte strings
+    /// are truncated, so a bound isn't necessarily a value in the column.
+    pub fn stats(&self) -> Result<PartStats, String> {
+        let mut columns = BTreeMap::new();
+        for (idx, column) in self.columns.iter().enumerate() {
+            if let Some(column) = column {
+                columns.insert(idx.to_string(), column.stats(self.len)?);
+            }
+        }
+        Ok(PartStats {
+            len: self.len,
+            columns,
+        })
+    }
+
+    /// Packs the datums of the row at `idx` into `packer`.
+    pub fn decode_into(&self, idx: usize, packer: &mut RowPacker) -> Result<(), String> {
+        for column in self.columns.iter() {
+            match column {
+                Some(column) => column.push_into(idx, packer)?,
+                None => packer.push(Datum::Null),
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates `column <op> literal` for every row, where `pred` decides
+    /// whether the [Ordering] of a value relative to `literal` satisfies the
+    /// comparison.
+    ///
+    /// Returns a bitmap with a set bit for each row where the comparison is
+    /// true. Rows where the column is null are never set, which matches how
+    /// a filter treats a comparison that evaluates to null. Returns `None` if
+    /// the column was not read or if the comparison can't be evaluated
+    /// directly on the stored values, in which case the caller must evaluate
+    /// it on decoded rows.
+    pub fn filter<F: Fn(Ordering) -> bool>(
+        &self,
+        column: usize,
+        literal: Datum,
+        pred: F,
+    ) -> Option<Bitmap> {
+        self.columns.get(column)?.as_ref()?.filter(literal, pred)
+    }
+}
+
+impl PartDecoder<Row> for RowColumnarDecoder {
+    fn decode(&self, idx: usize) -> Row {
+        let mut row = Row::default();
+        self.decode_into(idx, &mut row.packer())
+            .expect("structured row columns were validated when written");
+        row
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+    use mz_persist_types::columnar::Schema;
     use mz_persist_types::Codec;
     use uuid::Uuid;
 
@@ -299,7 +1022,7 @@ mod tests {
     use crate::adt::interval::Interval;
     use crate::adt::numeric::Numeric;
     use crate::adt::timestamp::CheckedTimestamp;
-    use crate::{Datum, Row};
+    use crate::{Datum, RelationDesc, Row, RowColumnarDecoder, ScalarType};
 
     // TODO: datadriven golden tests for various interesting Datums and Rows to
     // catch any changes in the encoding.
@@ -389,4 +1112,219 @@ mod tests {
         row.encode(&mut encoded);
         assert_eq!(Row::decode(&encoded), Ok(row));
     }
+
+    #[test]
+    fn columnar_stats_truncation() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::String.nullable(false))
+            .with_column("b", ScalarType::Bytes.nullable(false))
+            .with_column(
+                "c",
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Int64),
+                    custom_id: None,
+                }
+                .nullable(false),
+            );
+        let long_string = "a".repeat(STATS_MAX_BOUND_LEN * 2);
+        let long_bytes = vec![0xff; STATS_MAX_BOUND_LEN * 2];
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push(Datum::String(&long_string));
+        packer.push(Datum::Bytes(&long_bytes));
+        packer.push_list((0..STATS_MAX_BOUND_LEN).map(|x| Datum::Int64(i64::cast_from(x))));
+
+        let mut encoder = desc.encoder();
+        encoder.encode(&row);
+        let part = encoder.finish().expect("valid rows");
+        let stats = desc.stats(&part).expect("valid part");
+        let bounds = |col: &str| {
+            let stats = &stats.columns[col];
+            let decode = |x: &Option<Vec<u8>>| x.as_ref().map(|x| Row::decode(x).unwrap());
+            (decode(&stats.lower), decode(&stats.upper))
+        };
+
+        // Long strings are bounded by truncated prefixes.
+        let (lower, upper) = bounds("0");
+        let prefix = &long_string[..STATS_MAX_BOUND_LEN];
+        let upper_prefix = format!("{}b", &prefix[..STATS_MAX_BOUND_LEN - 1]);
+        assert_eq!(lower, Some(Row::pack_slice(&[Datum::String(prefix)])));
+        assert_eq!(
+            upper,
+            Some(Row::pack_slice(&[Datum::String(&upper_prefix)]))
+        );
+        let datum = Datum::String(&long_string);
+        assert!(lower.unwrap().unpack_first() <= datum);
+        assert!(upper.unwrap().unpack_first() >= datum);
+
+        // There is no short upper bound for bytes that are all 0xff.
+        let (lower, upper) = bounds("1");
+        assert_eq!(
+            lower,
+            Some(Row::pack_slice(&[Datum::Bytes(
+                &long_bytes[..STATS_MAX_BOUND_LEN]
+            )]))
+        );
+        assert_eq!(upper, None);
+
+        // Other large datums are dropped.
+        assert_eq!(bounds("2"), (None, None));
+    }
+
+    #[test]
+    fn columnar_roundtrip() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true))
+            .with_column("c", ScalarType::Bool.nullable(true))
+            .with_column("d", ScalarType::Numeric { max_scale: None }.nullable(true))
+            .with_column("e", ScalarType::Float64.nullable(false));
+        let rows = vec![
+            Row::pack_slice(&[
+                Datum::Int64(1),
+                Datum::String("one"),
+                Datum::True,
+                Datum::from(Numeric::from(1)),
+                Datum::Float64(1.5f64.into()),
+            ]),
+            Row::pack_slice(&[
+                Datum::Int64(2),
+                Datum::Null,
+                Datum::Null,
+                Datum::Null,
+                Datum::Float64(f64::NAN.into()),
+            ]),
+            Row::pack_slice(&[
+                Datum::Int64(3),
+                Datum::String("three"),
+                Datum::False,
+                Datum::from(Numeric::nan()),
+                Datum::Float64((-0.0f64).into()),
+            ]),
+        ];
+
+        let mut encoder = desc.encoder();
+        for row in rows.iter() {
+            encoder.encode(row);
+        }
+        let part = encoder.finish().expect("valid rows");
+        assert_eq!(part.len(), rows.len());
+        assert_eq!(part.fields(), desc.columns().as_slice());
+
+        let decoder = desc.decoder(&part).expect("valid part");
+        let decoded = (0..part.len())
+            .map(|idx| decoder.decode(idx))
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, rows);
+
+        // Columns missing from the part decode as nulls.
+        let projected = part.project(|field| field.name != "1" && field.name != "3");
+        let decoder = desc.decoder(&projected).expect("valid part");
+        assert_eq!(
+            decoder.decode(0),
+            Row::pack_slice(&[
+                Datum::Int64(1),
+                Datum::Null,
+                Datum::True,
+                Datum::Null,
+                Datum::Float64(1.5f64.into()),
+            ])
+        );
+
+        // The decoder can also be built without the desc.
+        let decoder = RowColumnarDecoder::from_part(5, &projected).expect("valid part");
+        let mut row = Row::default();
+        decoder
+            .decode_into(2, &mut row.packer())
+            .expect("valid row");
+        assert_eq!(
+            row,
+            Row::pack_slice(&[
+                Datum::Int64(3),
+                Datum::Null,
+                Datum::False,
+                Datum::Null,
+                Datum::Float64((-0.0f64).into()),
+            ])
+        );
+        assert!(RowColumnarDecoder::from_part(4, &part).is_err());
+
+        // Comparisons against literals are evaluated on the columns. Nulls
+        // never match.
+        let decoder = RowColumnarDecoder::new(&desc, &part).expect("valid part");
+        let matches = |col: usize, literal: Datum, pred: fn(Ordering) -> bool| {
+            decoder
+                .filter(col, literal, pred)
+                .map(|mask| mask.iter().collect::<Vec<_>>())
+        };
+        assert_eq!(
+            matches(0, Datum::Int64(2), Ordering::is_ge),
+            Some(vec![false, true, true])
+        );
+        assert_eq!(
+            matches(1, Datum::String("one"), Ordering::is_ne),
+            Some(vec![false, false, true])
+        );
+        assert_eq!(
+            matches(2, Datum::True, Ordering::is_eq),
+            Some(vec![true, false, false])
+        );
+        // Mismatched literals and unsupported columns are left to the caller.
+        assert_eq!(matches(0, Datum::Int32(2), Ordering::is_eq), None);
+        assert_eq!(
+            matches(3, Datum::from(Numeric::from(1)), Ordering::is_eq),
+            None
+        );
+        assert_eq!(
+            matches(4, Datum::Float64(1.5f64.into()), Ordering::is_eq),
+            None
+        );
+
+        // Stats are computed over the non-null values of each column.
+        let stats = desc.stats(&part).expect("valid part");
+        assert_eq!(stats.len, 3);
+        let bounds = |col: &str| {
+            let stats = &stats.columns[col];
+            let decode = |x: &Option<Vec<u8>>| x.as_ref().map(|x| Row::decode(x).unwrap());
+            (stats.null_count, decode(&stats.lower), decode(&stats.upper))
+        };
+        assert_eq!(
+            bounds("0"),
+            (
+                0,
+                Some(Row::pack_slice(&[Datum::Int64(1)])),
+                Some(Row::pack_slice(&[Datum::Int64(3)]))
+            )
+        );
+        assert_eq!(
+            bounds("1"),
+            (
+                1,
+                Some(Row::pack_slice(&[Datum::String("one")])),
+                Some(Row::pack_slice(&[Datum::String("three")]))
+            )
+        );
+        assert_eq!(
+            bounds("3"),
+            (
+                1,
+                Some(Row::pack_slice(&[Datum::from(Numeric::from(1))])),
+                Some(Row::pack_slice(&[Datum::from(Numeric::nan())]))
+            )
+        );
+
+        // Rows that don't match the desc are rejected.
+        let mut encoder = desc.encoder();
+        encoder.encode(&Row::pack_slice(&[Datum::Int32(1)]));
+        assert!(encoder.finish().is_err());
+        let mut encoder = desc.encoder();
+        encoder.encode(&Row::pack_slice(&[
+            Datum::Null,
+            Datum::Null,
+            Datum::Null,
+            Datum::Null,
+            Datum::Float64(1.0f64.into()),
+        ]));
+        assert!(encoder.finish().is_err());
+    }
 }
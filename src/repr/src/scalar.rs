@@ -35,11 +35,12 @@ use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
+use crate::adt::range::Range;
 use crate::adt::system::{Oid, PgLegacyChar, RegClass, RegProc, RegType};
 use crate::adt::timestamp::{CheckedTimestamp, TimestampError};
 use crate::adt::varchar::{VarChar, VarCharMaxLength};
 use crate::GlobalId;
-use crate::{ColumnName, ColumnType, DatumList, DatumMap};
+use crate::{ColumnName, ColumnType, DatumList, DatumMap, DatumNested};
 use crate::{Row, RowArena};
 
 pub use crate::relation_and_scalar::proto_scalar_type::ProtoRecordField;
@@ -107,6 +108,8 @@ pub enum Datum<'a> {
     /// A universally unique identifier.
     Uuid(Uuid),
     MzTimestamp(crate::Timestamp),
+    /// A range of values, e.g. [-1, 1).
+    Range(Range<DatumNested<'a>>),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -168,6 +171,7 @@ impl<'a> Serialize for Datum<'a> {
             Numeric(n) => serializer.serialize_str(&n.to_string()),
             Uuid(u) => u.serialize(serializer),
             MzTimestamp(t) => serializer.serialize_str(&t.to_string()),
+            Range(r) => serializer.serialize_str(&r.to_string()),
             Dummy => serializer.serialize_str("Dummy"),
             JsonNull => serializer.serialize_str("JsonNull"),
             Null => serializer.serialize_none(),
@@ -709,6 +713,19 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the range value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Range`].
+    #[track_caller]
+    pub fn unwrap_range(&self) -> Range<DatumNested<'a>> {
+        match self {
+            Datum::Range(range) => *range,
+            _ => panic!("Datum::unwrap_range called on {:?}", self),
+        }
+    }
+
     /// Reports whether this datum is an instance of the specified column type.
     pub fn is_instance_of(self, column_type: &ColumnType) -> bool {
         fn is_instance_of_scalar(datum: Datum, scalar_type: &ScalarType) -> bool {
@@ -773,7 +790,8 @@ impl<'a> Datum<'a> {
                     (Datum::Bytes(_), _) => false,
                     (Datum::String(_), ScalarType::String)
                     | (Datum::String(_), ScalarType::VarChar { .. })
-                    | (Datum::String(_), ScalarType::Char { .. }) => true,
+                    | (Datum::String(_), ScalarType::Char { .. })
+                    | (Datum::String(_), ScalarType::JsonPath) => true,
                     (Datum::String(_), _) => false,
                     (Datum::Uuid(_), ScalarType::Uuid) => true,
                     (Datum::Uuid(_), _) => false,
@@ -809,6 +827,13 @@ impl<'a> Datum<'a> {
                     (Datum::Numeric(_), _) => false,
                     (Datum::MzTimestamp(_), ScalarType::MzTimestamp) => true,
                     (Datum::MzTimestamp(_), _) => false,
+                    (Datum::Range(range), ScalarType::Range { element_type }) => {
+                        [range.lower(), range.upper()]
+                            .into_iter()
+                            .flatten()
+                            .all(|b| is_instance_of_scalar(b.datum(), element_type))
+                    }
+                    (Datum::Range(_), _) => false,
                 }
             }
         }
@@ -1076,6 +1101,7 @@ impl fmt::Display for Datum<'_> {
             }
             Datum::Numeric(n) => write!(f, "{}", n.0.to_standard_notation_string()),
             Datum::MzTimestamp(t) => write!(f, "{}", t),
+            Datum::Range(r) => write!(f, "{}", r),
             Datum::JsonNull => f.write_str("json_null"),
             Datum::Dummy => f.write_str("dummy"),
         }
@@ -1139,6 +1165,7 @@ impl From<&Datum<'_>> for serde_json::Value {
             | Datum::Timestamp(_)
             | Datum::TimestampTz(_)
             | Datum::MzTimestamp(_)
+            | Datum::Range(_)
             | Datum::Uuid(_) => serde_json::Value::String(datum.to_string()),
         }
     }
@@ -1268,6 +1295,18 @@ pub enum ScalarType {
     Int2Vector,
     /// A Materialize timestamp.
     MzTimestamp,
+    /// The type of [`Datum::Range`].
+    ///
+    /// Bounds of the range are of the specified type, which must be one of
+    /// [`ScalarType::Int32`], [`ScalarType::Int64`], [`ScalarType::Numeric`],
+    /// [`ScalarType::Date`], [`ScalarType::Timestamp`], or
+    /// [`ScalarType::TimestampTz`].
+    Range { element_type: Box<ScalarType> },
+    /// Stored as [`Datum::String`], but expresses a SQL/JSON path expression
+    /// in its canonical form.
+    ///
+    /// See [`crate::adt::jsonpath`] for details.
+    JsonPath,
 }
 
 impl RustType<ProtoRecordField> for (ColumnName, ColumnType) {
@@ -1350,6 +1389,8 @@ impl RustType<ProtoScalarType> for ScalarType {
                     custom_id: custom_id.map(|id| id.into_proto()),
                 })),
                 ScalarType::MzTimestamp => MzTimestamp(()),
+                ScalarType::Range { element_type } => Range(element_type.into_proto()),
+                ScalarType::JsonPath => JsonPath(()),
             }),
         }
     }
@@ -1422,6 +1463,10 @@ impl RustType<ProtoScalarType> for ScalarType {
                 custom_id: x.custom_id.map(|id| id.into_rust().unwrap()),
             }),
             MzTimestamp(()) => Ok(ScalarType::MzTimestamp),
+            Range(x) => Ok(ScalarType::Range {
+                element_type: Box::new((*x).into_rust()?),
+            }),
+            JsonPath(()) => Ok(ScalarType::JsonPath),
         }
     }
 }
@@ -1588,6 +1633,23 @@ impl<'a, E> DatumType<'a, E> for DatumMap<'a> {
     }
 }
 
+impl<'a, E> DatumType<'a, E> for Range<DatumNested<'a>> {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::Range(range)) => Ok(range),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, _temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::Range(self))
+    }
+}
+
 impl AsColumnType for bool {
     fn as_column_type() -> ColumnType {
         ScalarType::Bool.nullable(false)
@@ -2048,6 +2110,9 @@ impl<'a> ScalarType {
                 }
             }
             Array(a) => Array(Box::new(a.without_modifiers())),
+            Range { element_type } => Range {
+                element_type: Box::new(element_type.without_modifiers()),
+            },
             Numeric { .. } => Numeric { max_scale: None },
             // Char's default length should not be `Some(1)`, but instead `None`
             // to support Char values of different lengths in e.g. lists.
@@ -2092,6 +2157,18 @@ impl<'a> ScalarType {
         }
     }
 
+    /// Returns the [`ScalarType`] of the bounds of a [`ScalarType::Range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on anything other than a [`ScalarType::Range`].
+    pub fn unwrap_range_element_type(&self) -> &ScalarType {
+        match self {
+            ScalarType::Range { element_type } => &**element_type,
+            _ => panic!("ScalarType::unwrap_range_element_type called on {:?}", self),
+        }
+    }
+
     /// Returns the [`ScalarType`] of values in a [`ScalarType::Map`].
     ///
     /// # Panics
@@ -2248,7 +2325,9 @@ impl<'a> ScalarType {
                     custom_id: oid_r,
                 },
             ) => l.eq_inner(r, structure_only) && (oid_l == oid_r || structure_only),
-            (Array(a), Array(b)) => a.eq_inner(b, structure_only),
+            (Array(a), Array(b)) | (Range { element_type: a }, Range { element_type: b }) => {
+                a.eq_inner(b, structure_only)
+            }
             (
                 Record {
                     fields: fields_a,
@@ -2490,6 +2569,13 @@ impl<'a> ScalarType {
         static REGTYPE: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
         static REGCLASS: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
         static INT2VECTOR: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
+        static RANGE: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
+        static JSONPATH: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::String("$"),
+                Datum::String("strict $.\"a\"[*]?(@ > 1)"),
+            ])
+        });
         static MZTIMESTAMP: Lazy<Row> = Lazy::new(|| {
             Row::pack_slice(&[
                 Datum::MzTimestamp(crate::Timestamp::MIN),
@@ -2530,6 +2616,8 @@ impl<'a> ScalarType {
             ScalarType::RegClass => (*REGCLASS).iter(),
             ScalarType::Int2Vector => (*INT2VECTOR).iter(),
             ScalarType::MzTimestamp => (*MZTIMESTAMP).iter(),
+            ScalarType::Range { .. } => (*RANGE).iter(),
+            ScalarType::JsonPath => (*JSONPATH).iter(),
         }
     }
 
@@ -2573,6 +2661,7 @@ impl<'a> ScalarType {
             ScalarType::RegClass,
             ScalarType::Int2Vector,
             ScalarType::MzTimestamp,
+            ScalarType::JsonPath,
             // TODO: Fill in some variants of these.
             /*
             ScalarType::Array(_),
@@ -2628,6 +2717,15 @@ impl Arbitrary for ScalarType {
             Just(ScalarType::RegType),
             Just(ScalarType::RegClass),
             Just(ScalarType::Int2Vector),
+            Just(ScalarType::JsonPath),
+            prop_oneof![
+                Just(ScalarType::Int32),
+                Just(ScalarType::Int64),
+                Just(ScalarType::Date),
+            ]
+            .prop_map(|element_type| ScalarType::Range {
+                element_type: Box::new(element_type)
+            }),
         ];
 
         leaf.prop_recursive(
@@ -54,7 +54,9 @@ use crate::adt::date::Date;
 use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
+use crate::adt::jsonpath::JsonPath;
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
+use crate::adt::range::{Range, RangeBound, RangeInner};
 use crate::adt::timestamp::CheckedTimestamp;
 
 include!(concat!(env!("OUT_DIR"), "/mz_repr.strconv.rs"));
@@ -500,11 +502,53 @@ pub fn parse_timestamptz(s: &str) -> Result<CheckedTimestamp<DateTime<Utc>>, Par
 pub fn format_timestamptz<F>(buf: &mut F, ts: &DateTime<Utc>) -> Nestable
 where
     F: FormatBuffer,
+{
+    format_timestamptz_in(buf, ts, &datetime::Timezone::default())
+}
+
+/// Writes a [`DateTime<Utc>`] timestamp to `buf` as local time in the time
+/// zone `tz`, followed by that time zone's offset from UTC.
+pub fn format_timestamptz_in<F>(
+    buf: &mut F,
+    ts: &DateTime<Utc>,
+    tz: &datetime::Timezone,
+) -> Nestable
+where
+    F: FormatBuffer,
+{
+    match tz {
+        datetime::Timezone::FixedOffset(offset) => {
+            format_local_timestamptz(buf, &ts.with_timezone(offset))
+        }
+        datetime::Timezone::Tz(tz) => format_local_timestamptz(buf, &ts.with_timezone(tz)),
+    }
+}
+
+fn format_local_timestamptz<F, T>(buf: &mut F, ts: &DateTime<T>) -> Nestable
+where
+    F: FormatBuffer,
+    T: TimeZone,
 {
     let (year_ad, year) = ts.year_ce();
-    write!(buf, "{:04}-{}", year, ts.format("%m-%d %H:%M:%S"));
+    write!(
+        buf,
+        "{:04}-{}",
+        year,
+        ts.naive_local().format("%m-%d %H:%M:%S")
+    );
     format_nanos_to_micros(buf, ts.timestamp_subsec_nanos());
-    write!(buf, "+00");
+    // Like PostgreSQL, only include the minutes and seconds of the offset if
+    // they are nonzero.
+    let offset = ts.offset().fix().local_minus_utc();
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    write!(buf, "{}{:02}", sign, offset / 3600);
+    if offset % 3600 != 0 {
+        write!(buf, ":{:02}", offset % 3600 / 60);
+        if offset % 60 != 0 {
+            write!(buf, ":{:02}", offset % 60);
+        }
+    }
     if !year_ad {
         write!(buf, " BC");
     }
@@ -707,6 +751,20 @@ where
     write!(buf, "{:#}", jsonb)
 }
 
+pub fn parse_jsonpath(s: &str) -> Result<JsonPath, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("jsonpath", s).with_details(e))
+}
+
+/// Writes a jsonpath, which must already be in its canonical form.
+pub fn format_jsonpath<F>(buf: &mut F, jsonpath: &str) -> Nestable
+where
+    F: FormatBuffer,
+{
+    buf.write_str(jsonpath);
+    Nestable::MayNeedEscaping
+}
+
 pub fn parse_uuid(s: &str) -> Result<Uuid, ParseError> {
     s.trim()
         .parse()
@@ -896,6 +954,114 @@ where
     Ok(elems)
 }
 
+pub fn parse_range<'a, T, E>(
+    s: &'a str,
+    gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Range<T>, ParseError>
+where
+    E: fmt::Display,
+{
+    parse_range_inner(s, gen_elem)
+        .map_err(|details| ParseError::invalid_input_syntax("range", s).with_details(details))
+}
+
+fn parse_range_inner<'a, T, E>(
+    s: &'a str,
+    mut gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Range<T>, String>
+where
+    E: fmt::Display,
+{
+    if s.trim_matches(|ch: char| ch.is_ascii_whitespace())
+        .eq_ignore_ascii_case("empty")
+    {
+        return Ok(Range::empty());
+    }
+
+    let buf = &mut LexBuf::new(s);
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    let lower_inclusive = match buf.next() {
+        Some('[') => true,
+        Some('(') => false,
+        _ => bail!("missing left parenthesis or bracket"),
+    };
+
+    let mut gen = |elem| gen_elem(elem).map_err_to_string();
+
+    let lower = match lex_range_bound(buf, |ch| ch == ',')? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+    if !buf.consume(',') {
+        bail!("missing comma after lower bound");
+    }
+    let upper = match lex_range_bound(buf, |ch| matches!(ch, ')' | ']'))? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+    let upper_inclusive = match buf.next() {
+        Some(']') => true,
+        Some(')') => false,
+        _ => bail!("missing right parenthesis or bracket"),
+    };
+
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    if buf.next().is_some() {
+        bail!("junk after right parenthesis or bracket")
+    }
+
+    Ok(Range {
+        inner: Some(RangeInner {
+            lower: RangeBound {
+                inclusive: lower_inclusive,
+                bound: lower,
+            },
+            upper: RangeBound {
+                inclusive: upper_inclusive,
+                bound: upper,
+            },
+        }),
+    })
+}
+
+// Result of `None` indicates the bound is infinite.
+fn lex_range_bound<'a>(
+    buf: &mut LexBuf<'a>,
+    is_end_of_bound: impl Fn(char) -> bool,
+) -> Result<Option<Cow<'a, str>>, String> {
+    let s = buf.take_while(|ch| !matches!(ch, '"' | '\\') && !is_end_of_bound(ch));
+
+    // `Cow::Borrowed` optimization for bounds without quotes or escapes.
+    match buf.peek() {
+        Some(c) if is_end_of_bound(c) => {
+            return Ok(if s.is_empty() { None } else { Some(s.into()) });
+        }
+        None => bail!("unexpected end of input"),
+        _ => {}
+    }
+
+    let mut s = s.to_string();
+    let mut in_quotes = false;
+    loop {
+        match buf.next() {
+            Some('\\') => match buf.next() {
+                Some(c) => s.push(c),
+                None => bail!("unexpected end of input"),
+            },
+            // Within quotes, a doubled quote represents a literal quote.
+            Some('"') if in_quotes && buf.consume('"') => s.push('"'),
+            Some('"') => in_quotes = !in_quotes,
+            Some(c) if !in_quotes && is_end_of_bound(c) => {
+                buf.prev();
+                break;
+            }
+            Some(c) => s.push(c),
+            None => bail!("unexpected end of input"),
+        }
+    }
+    Ok(Some(Cow::Owned(s)))
+}
+
 pub fn parse_legacy_vector<'a, T, E>(
     s: &'a str,
     gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
@@ -1227,6 +1393,41 @@ where
     Ok(())
 }
 
+pub fn format_range<F, T, E>(
+    buf: &mut F,
+    range: &Range<T>,
+    mut format_elem: impl FnMut(RangeElementWriter<F>, &T) -> Result<Nestable, E>,
+) -> Result<Nestable, E>
+where
+    F: FormatBuffer,
+{
+    let RangeInner { lower, upper } = match &range.inner {
+        None => {
+            buf.write_str("empty");
+            return Ok(Nestable::MayNeedEscaping);
+        }
+        Some(inner) => inner,
+    };
+
+    buf.write_char(if lower.inclusive { '[' } else { '(' });
+    if let Some(bound) = &lower.bound {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(RangeElementWriter(buf), bound)? {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(',');
+    if let Some(bound) = &upper.bound {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(RangeElementWriter(buf), bound)? {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(if upper.inclusive { ']' } else { ')' });
+
+    Ok(Nestable::MayNeedEscaping)
+}
+
 pub fn format_legacy_vector<F, T, E>(
     buf: &mut F,
     elems: impl IntoIterator<Item = T>,
@@ -1333,7 +1534,23 @@ impl ElementEscaper for RecordElementEscaper {
     }
 }
 
-/// Escapes a list, record, or map element in place.
+struct RangeElementEscaper;
+
+impl ElementEscaper for RangeElementEscaper {
+    fn needs_escaping(elem: &[u8]) -> bool {
+        elem.is_empty()
+            || elem.iter().any(|c| {
+                matches!(c, b'(' | b')' | b'[' | b']' | b',' | b'"' | b'\\')
+                    || c.is_ascii_whitespace()
+            })
+    }
+
+    fn escape_char(c: u8) -> u8 {
+        c
+    }
+}
+
+/// Escapes a list, record, map, or range element in place.
 ///
 /// The element must start at `start` and extend to the end of the buffer. The
 /// buffer will be resized if escaping is necessary to account for the
@@ -1476,6 +1693,20 @@ where
     }
 }
 
+/// A helper for `format_range` that formats a single range bound.
+#[derive(Debug)]
+pub struct RangeElementWriter<'a, F>(&'a mut F);
+
+impl<'a, F> RangeElementWriter<'a, F>
+where
+    F: FormatBuffer,
+{
+    /// Returns a [`FormatBuffer`] into which the bound can be written.
+    pub fn nonnull_buffer(self) -> &'a mut F {
+        self.0
+    }
+}
+
 /// An error while parsing an input as a type.
 #[derive(
     Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
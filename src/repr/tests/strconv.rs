@@ -10,8 +10,9 @@
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
 use mz_repr::adt::date::Date;
-use mz_repr::adt::datetime::DateTimeField;
+use mz_repr::adt::datetime::{DateTimeField, Timezone};
 use mz_repr::adt::interval::Interval;
+use mz_repr::adt::range::{Range, RangeBound};
 use mz_repr::strconv;
 
 #[test]
@@ -532,6 +533,79 @@ fn miri_test_format_list() {
     );
 }
 
+#[test]
+fn test_format_range() {
+    fn run_test_format_range(range: Range<&str>, expected: &str) {
+        let mut out = String::new();
+        strconv::format_range(&mut out, &range, |rw, el| {
+            Ok::<_, ()>(strconv::format_string(rw.nonnull_buffer(), el))
+        })
+        .unwrap();
+        assert_eq!(out, expected);
+    }
+
+    run_test_format_range(Range::empty(), "empty");
+    run_test_format_range(
+        Range::new(Some((
+            RangeBound::new("1", true),
+            RangeBound::new("2", false),
+        ))),
+        "[1,2)",
+    );
+    run_test_format_range(
+        Range::new(Some((RangeBound::infinite(), RangeBound::new("a b", true)))),
+        r#"(,"a b"]"#,
+    );
+    run_test_format_range(
+        Range::new(Some((
+            RangeBound::new(r#"a"b\c"#, false),
+            RangeBound::infinite(),
+        ))),
+        r#"("a""b\\c",)"#,
+    );
+}
+
+#[test]
+fn test_parse_range() {
+    fn run_test_parse_range(s: &str, expected: Range<&str>) {
+        let range = strconv::parse_range(s, |elem| Ok::<_, String>(elem.into_owned())).unwrap();
+        assert_eq!(range, expected.map(|b| b.to_string()));
+    }
+
+    run_test_parse_range("empty", Range::empty());
+    run_test_parse_range("  EMPTY ", Range::empty());
+    run_test_parse_range(
+        "[1,2)",
+        Range::new(Some((
+            RangeBound::new("1", true),
+            RangeBound::new("2", false),
+        ))),
+    );
+    run_test_parse_range(
+        " (,2] ",
+        Range::new(Some((RangeBound::infinite(), RangeBound::new("2", true)))),
+    );
+    run_test_parse_range(
+        "[1,)",
+        Range::new(Some((RangeBound::new("1", true), RangeBound::infinite()))),
+    );
+    run_test_parse_range(
+        r#"["a ""b"" \,c",)"#,
+        Range::new(Some((
+            RangeBound::new(r#"a "b" ,c"#, true),
+            RangeBound::infinite(),
+        ))),
+    );
+
+    for s in ["", "1,2", "[1,2", "[1)", "[1,2)x", "[1,2)empty"] {
+        assert!(
+            strconv::parse_range(s, |elem| Ok::<_, String>(elem.into_owned())).is_err(),
+            "{} should not parse",
+            s
+        );
+    }
+}
+
 #[test]
 fn test_format_date() {
     run_test_format_date(NaiveDate::from_ymd_opt(20000, 2, 3).unwrap(), "20000-02-03");
@@ -752,27 +826,63 @@ fn test_format_timestamptz() {
         "2000-02-03 04:05:06.789+00 BC",
     );
 
-    fn datetime_utc(
-        year: i32,
-        month: u32,
-        day: u32,
-        hour: u32,
-        min: u32,
-        sec: u32,
-        nano: u32,
-    ) -> DateTime<Utc> {
-        DateTime::from_utc(
-            NaiveDate::from_ymd_opt(year, month, day)
-                .unwrap()
-                .and_hms_nano_opt(hour, min, sec, nano)
-                .unwrap(),
-            Utc,
-        )
-    }
-
     fn run_test_format_timestamptz(n: DateTime<Utc>, e: &str) {
         let mut buf = String::new();
         strconv::format_timestamptz(&mut buf, &n);
         assert_eq!(buf, e);
     }
 }
+
+fn datetime_utc(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    min: u32,
+    sec: u32,
+    nano: u32,
+) -> DateTime<Utc> {
+    DateTime::from_utc(
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_nano_opt(hour, min, sec, nano)
+            .unwrap(),
+        Utc,
+    )
+}
+
+#[test]
+fn test_format_timestamptz_in() {
+    let new_york = Timezone::Tz(chrono_tz::America::New_York);
+    run_test_format_timestamptz_in(
+        datetime_utc(2000, 2, 3, 4, 5, 6, 0),
+        &new_york,
+        "2000-02-02 23:05:06-05",
+    );
+    run_test_format_timestamptz_in(
+        datetime_utc(2000, 7, 3, 4, 5, 6, 789_000_000),
+        &new_york,
+        "2000-07-03 00:05:06.789-04",
+    );
+    run_test_format_timestamptz_in(
+        datetime_utc(2000, 2, 3, 4, 5, 6, 0),
+        &Timezone::Tz(chrono_tz::Asia::Kolkata),
+        "2000-02-03 09:35:06+05:30",
+    );
+    run_test_format_timestamptz_in(
+        datetime_utc(2000, 2, 3, 4, 5, 6, 0),
+        &Timezone::FixedOffset(FixedOffset::west_opt(3600 + 30 * 60 + 15).unwrap()),
+        "2000-02-03 02:34:51-01:30:15",
+    );
+    run_test_format_timestamptz_in(
+        datetime_utc(0, 1, 1, 1, 0, 0, 0),
+        &Timezone::FixedOffset(FixedOffset::west_opt(2 * 3600).unwrap()),
+        "0002-12-31 23:00:00-02 BC",
+    );
+
+    fn run_test_format_timestamptz_in(n: DateTime<Utc>, tz: &Timezone, e: &str) {
+        let mut buf = String::new();
+        strconv::format_timestamptz_in(&mut buf, &n, tz);
+        assert_eq!(buf, e);
+    }
+}
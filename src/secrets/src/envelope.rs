@@ -0,0 +1,433 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Envelope encryption of secrets at rest.
+//!
+//! Each secret is encrypted with its own randomly generated data key, and the
+//! data key is in turn encrypted ("wrapped") with a master key that never
+//! leaves the processes that read and write secrets. Rotating the master key
+//! only requires rewrapping the data keys, not re-encrypting the secrets
+//! themselves.
+//!
+//! The layout of an encrypted secret is:
+//!
+//! ```text
+//! magic (4) | version (1) | master key id (8)
+//!     | wrapped key nonce (12) | wrapped data key (32) | wrapped key tag (16)
+//!     | data nonce (12) | data tag (16) | ciphertext
+//! ```
+//!
+//! Both the data key and the secret are encrypted with AES-256-GCM. The data
+//! key is authenticated together with the header, and the secret together with
+//! its ID, so that an encrypted secret cannot be passed off as another.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use openssl::symm::{self, Cipher};
+
+use mz_repr::GlobalId;
+
+use crate::{SecretsController, SecretsReader};
+
+/// The environment variable through which a [`Keyring`] is passed to the
+/// processes that read secrets, encoded as by [`Keyring::encode`].
+pub const MASTER_KEY_ENV_VAR: &str = "MZ_SECRETS_READER_MASTER_KEY";
+
+const MAGIC: &[u8; 4] = b"MZSE";
+const VERSION: u8 = 1;
+const KEY_LEN: usize = 32;
+const KEY_ID_LEN: usize = 8;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+const HEADER_LEN: usize = MAGIC.len() + 1 + KEY_ID_LEN;
+const WRAPPED_KEY_LEN: usize = NONCE_LEN + KEY_LEN + TAG_LEN;
+const DATA_START: usize = HEADER_LEN + WRAPPED_KEY_LEN;
+const MIN_ENVELOPE_LEN: usize = DATA_START + NONCE_LEN + TAG_LEN;
+
+/// A 256-bit key used to wrap the data keys of secrets.
+///
+/// Master keys are written as 64 hexadecimal digits. Each key is identified by
+/// a prefix of its SHA-256 hash, which is stored alongside every data key it
+/// wraps.
+#[derive(Clone)]
+pub struct MasterKey {
+    id: [u8; KEY_ID_LEN],
+    key: [u8; KEY_LEN],
+}
+
+impl MasterKey {
+    /// Generates a new random master key.
+    pub fn generate() -> Result<MasterKey, anyhow::Error> {
+        Ok(MasterKey::from_bytes(random()?))
+    }
+
+    fn from_bytes(key: [u8; KEY_LEN]) -> MasterKey {
+        let mut id = [0; KEY_ID_LEN];
+        id.copy_from_slice(&openssl::sha::sha256(&key)[..KEY_ID_LEN]);
+        MasterKey { id, key }
+    }
+
+    /// Returns the hex-encoded identifier of the key.
+    ///
+    /// The identifier does not reveal the key and is safe to log.
+    pub fn id(&self) -> String {
+        hex::encode(self.id)
+    }
+
+    /// Returns the hex-encoded key.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.key)
+    }
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MasterKey")
+            .field("id", &self.id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FromStr for MasterKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut key = [0; KEY_LEN];
+        hex::decode_to_slice(s.trim(), &mut key)
+            .map_err(|_| anyhow!("master key must be {} hexadecimal digits", KEY_LEN * 2))?;
+        Ok(MasterKey::from_bytes(key))
+    }
+}
+
+/// The set of master keys known to a process.
+///
+/// New secrets are always encrypted with the current key. Secrets encrypted
+/// with any of the previous keys can still be read, which allows rotating the
+/// master key without downtime: add the new key as the current key, keep the
+/// old key as a previous key, rewrap all secrets with [`Keyring::rewrap`], and
+/// then drop the old key.
+///
+/// A keyring is written as a whitespace-separated list of master keys, the
+/// first of which is the current key.
+#[derive(Clone)]
+pub struct Keyring {
+    current: MasterKey,
+    previous: Vec<MasterKey>,
+}
+
+impl Keyring {
+    /// Constructs a new keyring.
+    pub fn new(current: MasterKey, previous: Vec<MasterKey>) -> Keyring {
+        Keyring { current, previous }
+    }
+
+    /// Loads a keyring from the file at the specified path.
+    pub fn from_file(path: &Path) -> Result<Keyring, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading master key file {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("parsing master key file {}", path.display()))
+    }
+
+    /// Returns the key used to encrypt new secrets.
+    pub fn current(&self) -> &MasterKey {
+        &self.current
+    }
+
+    /// Encodes the keyring in the format accepted by [`Keyring::from_str`].
+    pub fn encode(&self) -> String {
+        let keys: Vec<_> = self.keys().map(|k| k.to_hex()).collect();
+        keys.join(" ")
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &MasterKey> {
+        std::iter::once(&self.current).chain(self.previous.iter())
+    }
+
+    fn find(&self, id: &[u8]) -> Result<&MasterKey, anyhow::Error> {
+        self.keys()
+            .find(|k| k.id == id)
+            .ok_or_else(|| anyhow!("unknown master key {}", hex::encode(id)))
+    }
+
+    /// Encrypts the contents of the specified secret under a new data key
+    /// wrapped with the current master key.
+    pub fn seal(&self, id: GlobalId, contents: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let data_key: [u8; KEY_LEN] = random()?;
+        let mut envelope = self.wrap(&data_key)?;
+        let nonce: [u8; NONCE_LEN] = random()?;
+        let mut tag = [0; TAG_LEN];
+        let ciphertext = symm::encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &data_key,
+            Some(&nonce),
+            id.to_string().as_bytes(),
+            contents,
+            &mut tag,
+        )?;
+        envelope.extend(nonce);
+        envelope.extend(tag);
+        envelope.extend(ciphertext);
+        Ok(envelope)
+    }
+
+    /// Decrypts the contents of the specified secret.
+    ///
+    /// Returns an error if `envelope` was not produced by [`Keyring::seal`]
+    /// for the same secret with one of the keys in this keyring.
+    pub fn open(&self, id: GlobalId, envelope: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let data_key = self.unwrap(envelope)?;
+        let data = &envelope[DATA_START..];
+        let (nonce, data) = data.split_at(NONCE_LEN);
+        let (tag, ciphertext) = data.split_at(TAG_LEN);
+        symm::decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &data_key,
+            Some(nonce),
+            id.to_string().as_bytes(),
+            ciphertext,
+            tag,
+        )
+        .map_err(|_| anyhow!("secret {} failed authentication", id))
+    }
+
+    /// Rewraps the data key of an encrypted secret with the current master
+    /// key, leaving the encrypted contents untouched.
+    ///
+    /// Returns `None` if the data key is already wrapped with the current
+    /// master key.
+    pub fn rewrap(&self, envelope: &[u8]) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        let data_key = self.unwrap(envelope)?;
+        if envelope[MAGIC.len() + 1..HEADER_LEN] == self.current.id {
+            return Ok(None);
+        }
+        let mut rewrapped = self.wrap(&data_key)?;
+        rewrapped.extend(&envelope[DATA_START..]);
+        Ok(Some(rewrapped))
+    }
+
+    /// Returns the header and wrapped data key of a new envelope.
+    fn wrap(&self, data_key: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let mut envelope = Vec::with_capacity(DATA_START);
+        envelope.extend(MAGIC);
+        envelope.push(VERSION);
+        envelope.extend(self.current.id);
+        let nonce: [u8; NONCE_LEN] = random()?;
+        let mut tag = [0; TAG_LEN];
+        let wrapped = symm::encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.current.key,
+            Some(&nonce),
+            &envelope,
+            data_key,
+            &mut tag,
+        )?;
+        envelope.extend(nonce);
+        envelope.extend(wrapped);
+        envelope.extend(tag);
+        Ok(envelope)
+    }
+
+    /// Validates the header of an envelope and returns its data key.
+    fn unwrap(&self, envelope: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        if !is_envelope(envelope) {
+            bail!("secret is not encrypted");
+        }
+        if envelope[MAGIC.len()] != VERSION {
+            bail!("unsupported secret version {}", envelope[MAGIC.len()]);
+        }
+        if envelope.len() < MIN_ENVELOPE_LEN {
+            bail!("encrypted secret is truncated");
+        }
+        let (header, rest) = envelope.split_at(HEADER_LEN);
+        let master_key = self.find(&header[MAGIC.len() + 1..])?;
+        let (nonce, rest) = rest.split_at(NONCE_LEN);
+        let (wrapped, rest) = rest.split_at(KEY_LEN);
+        let tag = &rest[..TAG_LEN];
+        symm::decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &master_key.key,
+            Some(nonce),
+            header,
+            wrapped,
+            tag,
+        )
+        .map_err(|_| anyhow!("data key failed authentication"))
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field("current", &self.current)
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
+
+impl FromStr for Keyring {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keys = s.split_whitespace().map(|k| k.parse());
+        let current = keys.next().ok_or_else(|| anyhow!("no master key"))??;
+        let previous = keys.collect::<Result<_, _>>()?;
+        Ok(Keyring { current, previous })
+    }
+}
+
+/// Reports whether `bytes` looks like a secret encrypted by a [`Keyring`].
+pub fn is_envelope(bytes: &[u8]) -> bool {
+    bytes.len() > MAGIC.len() && bytes.starts_with(MAGIC)
+}
+
+fn random<const N: usize>() -> Result<[u8; N], anyhow::Error> {
+    let mut buf = [0; N];
+    openssl::rand::rand_bytes(&mut buf)?;
+    Ok(buf)
+}
+
+/// A [`SecretsController`] that encrypts secrets before handing them to
+/// another controller for storage.
+#[derive(Debug)]
+pub struct EnvelopeSecretsController {
+    inner: Arc<dyn SecretsController>,
+    keyring: Keyring,
+}
+
+impl EnvelopeSecretsController {
+    /// Constructs a new [`EnvelopeSecretsController`] that stores secrets in
+    /// `inner`, encrypted with the current key of `keyring`.
+    pub fn new(inner: Arc<dyn SecretsController>, keyring: Keyring) -> EnvelopeSecretsController {
+        EnvelopeSecretsController { inner, keyring }
+    }
+}
+
+#[async_trait]
+impl SecretsController for EnvelopeSecretsController {
+    async fn ensure(&self, id: GlobalId, contents: &[u8]) -> Result<(), anyhow::Error> {
+        let envelope = self.keyring.seal(id, contents)?;
+        self.inner.ensure(id, &envelope).await
+    }
+
+    async fn delete(&self, id: GlobalId) -> Result<(), anyhow::Error> {
+        self.inner.delete(id).await
+    }
+
+    fn reader(&self) -> Arc<dyn SecretsReader> {
+        Arc::new(EnvelopeSecretsReader::new(
+            self.inner.reader(),
+            self.keyring.clone(),
+        ))
+    }
+}
+
+/// A [`SecretsReader`] that decrypts secrets written by an
+/// [`EnvelopeSecretsController`].
+#[derive(Debug)]
+pub struct EnvelopeSecretsReader {
+    inner: Arc<dyn SecretsReader>,
+    keyring: Keyring,
+}
+
+impl EnvelopeSecretsReader {
+    /// Constructs a new [`EnvelopeSecretsReader`] that reads encrypted secrets
+    /// from `inner` and decrypts them with the keys in `keyring`.
+    pub fn new(inner: Arc<dyn SecretsReader>, keyring: Keyring) -> EnvelopeSecretsReader {
+        EnvelopeSecretsReader { inner, keyring }
+    }
+}
+
+#[async_trait]
+impl SecretsReader for EnvelopeSecretsReader {
+    async fn read(&self, id: GlobalId) -> Result<Vec<u8>, anyhow::Error> {
+        let envelope = self.inner.read(id).await?;
+        self.keyring
+            .open(id, &envelope)
+            .with_context(|| format!("decrypting secret {}", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_open() {
+        let keyring = Keyring::new(MasterKey::generate().unwrap(), vec![]);
+        let id = GlobalId::User(1);
+        let envelope = keyring.seal(id, b"hunter2").unwrap();
+        assert!(is_envelope(&envelope));
+        assert!(!envelope.windows(7).any(|w| w == b"hunter2"));
+        assert_eq!(keyring.open(id, &envelope).unwrap(), b"hunter2");
+
+        // Data keys are not reused.
+        assert_ne!(keyring.seal(id, b"hunter2").unwrap(), envelope);
+
+        // The envelope is bound to the secret's ID.
+        assert!(keyring.open(GlobalId::User(2), &envelope).is_err());
+
+        // Tampering with any part of the envelope is detected.
+        for i in 0..envelope.len() {
+            let mut tampered = envelope.clone();
+            tampered[i] ^= 1;
+            assert!(keyring.open(id, &tampered).is_err(), "byte {}", i);
+        }
+        assert!(keyring.open(id, &envelope[..envelope.len() - 1]).is_err());
+        assert!(keyring.open(id, b"hunter2").is_err());
+
+        // Other keys cannot open the envelope.
+        let other = Keyring::new(MasterKey::generate().unwrap(), vec![]);
+        assert!(other.open(id, &envelope).is_err());
+    }
+
+    #[test]
+    fn rotate() {
+        let old_key = MasterKey::generate().unwrap();
+        let new_key = MasterKey::generate().unwrap();
+        let old = Keyring::new(old_key.clone(), vec![]);
+        let rotating = Keyring::new(new_key.clone(), vec![old_key]);
+        let new = Keyring::new(new_key, vec![]);
+
+        let id = GlobalId::User(1);
+        let envelope = old.seal(id, b"hunter2").unwrap();
+        assert!(new.open(id, &envelope).is_err());
+        assert_eq!(rotating.open(id, &envelope).unwrap(), b"hunter2");
+
+        let rewrapped = rotating.rewrap(&envelope).unwrap().unwrap();
+        assert_eq!(rewrapped[DATA_START..], envelope[DATA_START..]);
+        assert_eq!(new.open(id, &rewrapped).unwrap(), b"hunter2");
+        assert!(old.open(id, &rewrapped).is_err());
+        assert_eq!(rotating.rewrap(&rewrapped).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_keyring() {
+        let a = MasterKey::generate().unwrap();
+        let b = MasterKey::generate().unwrap();
+        let keyring: Keyring = format!("{}\n{}\n", a.to_hex(), b.to_hex()).parse().unwrap();
+        assert_eq!(keyring.current().id(), a.id());
+        assert_eq!(keyring.previous.len(), 1);
+        assert_eq!(keyring.previous[0].id(), b.id());
+        let reparsed: Keyring = keyring.encode().parse().unwrap();
+        assert_eq!(reparsed.encode(), keyring.encode());
+
+        assert!("".parse::<Keyring>().is_err());
+        assert!("abcd".parse::<Keyring>().is_err());
+        assert!(!format!("{:?}", keyring).contains(&a.to_hex()));
+    }
+}
@@ -7,13 +7,16 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::env;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use anyhow::Context;
 use clap::ArgEnum;
 
 use mz_orchestrator_kubernetes::secrets::KubernetesSecretsReader;
 use mz_orchestrator_process::secrets::ProcessSecretsReader;
+use mz_secrets::envelope::{EnvelopeSecretsReader, Keyring, MASTER_KEY_ENV_VAR};
 use mz_secrets::SecretsReader;
 
 #[derive(clap::Parser)]
@@ -29,6 +32,13 @@ pub struct SecretsReaderCliArgs {
     /// load.
     #[structopt(long, required_if_eq("secrets-reader", "kubernetes"))]
     secrets_reader_kubernetes_context: Option<String>,
+    /// A file containing the master keys with which secrets are encrypted.
+    ///
+    /// If not specified, the keys are read from the
+    /// `MZ_SECRETS_READER_MASTER_KEY` environment variable, if it is set.
+    /// Otherwise secrets are assumed to be unencrypted.
+    #[structopt(long, value_name = "PATH")]
+    secrets_reader_master_key_file: Option<PathBuf>,
 }
 
 #[derive(ArgEnum, Debug, Clone)]
@@ -40,17 +50,32 @@ enum SecretsReaderKind {
 impl SecretsReaderCliArgs {
     /// Loads the secrets reader specified by the command-line arguments.
     pub async fn load(self) -> Result<Arc<dyn SecretsReader>, anyhow::Error> {
-        match self.secrets_reader {
+        let reader: Arc<dyn SecretsReader> = match self.secrets_reader {
             SecretsReaderKind::Process => {
                 let dir = self.secrets_reader_process_dir.expect("clap enforced");
-                Ok(Arc::new(ProcessSecretsReader::new(dir)))
+                Arc::new(ProcessSecretsReader::new(dir))
             }
             SecretsReaderKind::Kubernetes => {
                 let context = self
                     .secrets_reader_kubernetes_context
                     .expect("clap enforced");
-                Ok(Arc::new(KubernetesSecretsReader::new(context).await?))
+                Arc::new(KubernetesSecretsReader::new(context).await?)
             }
+        };
+        let keyring = match (
+            self.secrets_reader_master_key_file,
+            env::var(MASTER_KEY_ENV_VAR),
+        ) {
+            (Some(path), _) => Some(Keyring::from_file(&path)?),
+            (None, Ok(keys)) => Some(
+                keys.parse()
+                    .with_context(|| format!("parsing {}", MASTER_KEY_ENV_VAR))?,
+            ),
+            (None, Err(_)) => None,
+        };
+        match keyring {
+            Some(keyring) => Ok(Arc::new(EnvelopeSecretsReader::new(reader, keyring))),
+            None => Ok(reader),
         }
     }
 }
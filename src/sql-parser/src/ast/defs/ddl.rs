@@ -285,6 +285,9 @@ pub struct CsrSeedProtobufSchema {
     // Hex encoded string.
     pub schema: String,
     pub message_name: String,
+    // The ID of the schema in the schema registry. Only recorded for sinks,
+    // which must tag the messages they write with it.
+    pub schema_id: Option<i32>,
 }
 impl AstDisplay for CsrSeedProtobufSchema {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
@@ -293,6 +296,10 @@ impl AstDisplay for CsrSeedProtobufSchema {
         f.write_str("' MESSAGE '");
         f.write_str(&self.message_name);
         f.write_str("'");
+        if let Some(schema_id) = self.schema_id {
+            f.write_str(" ID ");
+            f.write_str(schema_id);
+        }
     }
 }
 impl_display!(CsrSeedProtobufSchema);
@@ -338,7 +345,17 @@ pub enum Format<T: AstInfo> {
         columns: CsvColumns,
         delimiter: char,
     },
-    Json,
+    /// `JSON ( column_def, ... )?`: `columns` is empty if the documents
+    /// should be decoded in their entirety
+    Json {
+        columns: Vec<ColumnDef<T>>,
+    },
+    /// `PARQUET ( column_def, ... )?`: the columns to read out of each
+    /// Parquet object, matched against the object's schema by name. `columns`
+    /// is empty when writing Parquet from a sink
+    Parquet {
+        columns: Vec<ColumnDef<T>>,
+    },
     Text,
 }
 
@@ -482,7 +499,22 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str("'");
                 }
             }
-            Self::Json => f.write_str("JSON"),
+            Self::Json { columns } => {
+                f.write_str("JSON");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
+            Self::Parquet { columns } => {
+                f.write_str("PARQUET");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
             Self::Text => f.write_str("TEXT"),
         }
     }
@@ -492,6 +524,9 @@ impl_display_t!(Format);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
+    Snappy,
     None,
 }
 
@@ -499,6 +534,9 @@ impl AstDisplay for Compression {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::Gzip => f.write_str("GZIP"),
+            Self::Zstd => f.write_str("ZSTD"),
+            Self::Bzip2 => f.write_str("BZIP2"),
+            Self::Snappy => f.write_str("SNAPPY"),
             Self::None => f.write_str("NONE"),
         }
     }
@@ -875,6 +913,7 @@ pub enum KafkaConfigOptionName {
     IsolationLevel,
     Topic,
     TopicMetadataRefreshIntervalMs,
+    TopicPattern,
     TransactionTimeoutMs,
     StartTimestamp,
     StartOffset,
@@ -897,6 +936,7 @@ impl AstDisplay for KafkaConfigOptionName {
             KafkaConfigOptionName::TopicMetadataRefreshIntervalMs => {
                 "TOPIC METADATA REFRESH INTERVAL MS"
             }
+            KafkaConfigOptionName::TopicPattern => "TOPIC PATTERN",
             KafkaConfigOptionName::TransactionTimeoutMs => "TRANSACTION TIMEOUT MS",
             KafkaConfigOptionName::StartOffset => "START OFFSET",
             KafkaConfigOptionName::StartTimestamp => "START TIMESTAMP",
@@ -1152,6 +1192,11 @@ pub enum CreateSinkConnection<T: AstInfo> {
         connection: KafkaConnection<T>,
         key: Option<KafkaSinkKey>,
     },
+    S3 {
+        /// The AWS connection.
+        connection: T::ObjectName,
+        options: Vec<S3SinkOption<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
@@ -1164,11 +1209,57 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                     f.write_node(key);
                 }
             }
+            CreateSinkConnection::S3 {
+                connection,
+                options,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
 impl_display_t!(CreateSinkConnection);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum S3SinkOptionName {
+    Bucket,
+    Prefix,
+}
+
+impl AstDisplay for S3SinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            S3SinkOptionName::Bucket => "BUCKET",
+            S3SinkOptionName::Prefix => "PREFIX",
+        })
+    }
+}
+impl_display!(S3SinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO S3`.
+pub struct S3SinkOption<T: AstInfo> {
+    pub name: S3SinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for S3SinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(S3SinkOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KafkaSinkKey {
     pub key_columns: Vec<Ident>,
@@ -1337,6 +1428,7 @@ pub enum CreateSourceOptionName {
     Size,
     Timeline,
     TimestampInterval,
+    UpsertStateBackend,
 }
 
 impl AstDisplay for CreateSourceOptionName {
@@ -1347,6 +1439,7 @@ impl AstDisplay for CreateSourceOptionName {
             CreateSourceOptionName::Size => "SIZE",
             CreateSourceOptionName::Timeline => "TIMELINE",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
+            CreateSourceOptionName::UpsertStateBackend => "UPSERT STATE BACKEND",
         })
     }
 }
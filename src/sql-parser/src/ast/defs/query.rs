@@ -31,6 +31,9 @@ use crate::ast::{
 /// including `WITH`, `UNION` / other set operations, and `ORDER BY`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Query<T: AstInfo> {
+    /// Whether the CTEs were declared with `WITH RECURSIVE`, in which case
+    /// each CTE is in scope within its own definition.
+    pub ctes_recursive: bool,
     /// WITH (common table expressions, or CTEs)
     pub ctes: Vec<Cte<T>>,
     /// SELECT or UNION / EXCEPT / INTERSECT
@@ -48,6 +51,9 @@ impl<T: AstInfo> AstDisplay for Query<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         if !self.ctes.is_empty() {
             f.write_str("WITH ");
+            if self.ctes_recursive {
+                f.write_str("RECURSIVE ");
+            }
             f.write_node(&display::comma_separated(&self.ctes));
             f.write_str(" ");
         }
@@ -85,6 +91,7 @@ impl_display_t!(Query);
 impl<T: AstInfo> Query<T> {
     pub fn select(select: Select<T>) -> Query<T> {
         Query {
+            ctes_recursive: false,
             ctes: vec![],
             body: SetExpr::Select(Box::new(select)),
             order_by: vec![],
@@ -95,6 +102,7 @@ impl<T: AstInfo> Query<T> {
 
     pub fn query(query: Query<T>) -> Query<T> {
         Query {
+            ctes_recursive: false,
             ctes: vec![],
             body: SetExpr::Query(Box::new(query)),
             order_by: vec![],
@@ -107,6 +115,7 @@ impl<T: AstInfo> Query<T> {
         mem::replace(
             self,
             Query::<T> {
+                ctes_recursive: false,
                 ctes: vec![],
                 order_by: vec![],
                 body: SetExpr::Values(Values(vec![])),
@@ -76,6 +76,10 @@ pub enum Statement<T: AstInfo> {
     DropRoles(DropRolesStatement),
     DropClusters(DropClustersStatement),
     DropClusterReplicas(DropClusterReplicasStatement),
+    GrantPrivileges(GrantPrivilegesStatement),
+    RevokePrivileges(RevokePrivilegesStatement),
+    GrantRole(GrantRoleStatement),
+    RevokeRole(RevokeRoleStatement),
     SetVariable(SetVariableStatement),
     ResetVariable(ResetVariableStatement),
     Show(ShowStatement<T>),
@@ -133,6 +137,10 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::DropRoles(stmt) => f.write_node(stmt),
             Statement::DropClusters(stmt) => f.write_node(stmt),
             Statement::DropClusterReplicas(stmt) => f.write_node(stmt),
+            Statement::GrantPrivileges(stmt) => f.write_node(stmt),
+            Statement::RevokePrivileges(stmt) => f.write_node(stmt),
+            Statement::GrantRole(stmt) => f.write_node(stmt),
+            Statement::RevokeRole(stmt) => f.write_node(stmt),
             Statement::SetVariable(stmt) => f.write_node(stmt),
             Statement::ResetVariable(stmt) => f.write_node(stmt),
             Statement::Show(stmt) => f.write_node(stmt),
@@ -998,7 +1006,9 @@ impl AstDisplay for CreateRoleStatement {
 impl_display!(CreateRoleStatement);
 
 /// Options that can be attached to [`CreateRoleStatement`].
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// The `Debug` and `Display` implementations redact passwords.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub enum CreateRoleOption {
     /// The `SUPERUSER` option.
     SuperUser,
@@ -1008,6 +1018,8 @@ pub enum CreateRoleOption {
     Login,
     /// The `NOLOGIN` option.
     NoLogin,
+    /// The `PASSWORD` option. `None` indicates `PASSWORD NULL`.
+    Password(Option<String>),
 }
 
 impl AstDisplay for CreateRoleOption {
@@ -1017,11 +1029,35 @@ impl AstDisplay for CreateRoleOption {
             CreateRoleOption::NoSuperUser => f.write_str("NOSUPERUSER"),
             CreateRoleOption::Login => f.write_str("LOGIN"),
             CreateRoleOption::NoLogin => f.write_str("NOLOGIN"),
+            CreateRoleOption::Password(None) => f.write_str("PASSWORD NULL"),
+            CreateRoleOption::Password(Some(_)) if f.redacted() => {
+                f.write_str("PASSWORD '********'")
+            }
+            CreateRoleOption::Password(Some(password)) => {
+                f.write_str("PASSWORD '");
+                f.write_node(&display::escape_single_quote_string(password));
+                f.write_str("'");
+            }
         }
     }
 }
 impl_display!(CreateRoleOption);
 
+impl fmt::Debug for CreateRoleOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreateRoleOption::SuperUser => f.write_str("SuperUser"),
+            CreateRoleOption::NoSuperUser => f.write_str("NoSuperUser"),
+            CreateRoleOption::Login => f.write_str("Login"),
+            CreateRoleOption::NoLogin => f.write_str("NoLogin"),
+            CreateRoleOption::Password(password) => f
+                .debug_tuple("Password")
+                .field(&password.as_ref().map(|_| "********"))
+                .finish(),
+        }
+    }
+}
+
 /// A `CREATE SECRET` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateSecretStatement<T: AstInfo> {
@@ -1678,6 +1714,168 @@ impl AstDisplay for DropClusterReplicasStatement {
 }
 impl_display!(DropClusterReplicasStatement);
 
+/// A privilege that can be granted on an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Usage,
+    Create,
+}
+
+impl AstDisplay for Privilege {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            Privilege::Select => "SELECT",
+            Privilege::Insert => "INSERT",
+            Privilege::Update => "UPDATE",
+            Privilege::Delete => "DELETE",
+            Privilege::Usage => "USAGE",
+            Privilege::Create => "CREATE",
+        })
+    }
+}
+impl_display!(Privilege);
+
+/// The privileges named by a `GRANT` or `REVOKE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrivilegeSpecification {
+    /// `ALL [PRIVILEGES]`
+    All,
+    /// A list of privileges.
+    Privileges(Vec<Privilege>),
+}
+
+impl AstDisplay for PrivilegeSpecification {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            PrivilegeSpecification::All => f.write_str("ALL"),
+            PrivilegeSpecification::Privileges(privileges) => {
+                f.write_node(&display::comma_separated(privileges))
+            }
+        }
+    }
+}
+impl_display!(PrivilegeSpecification);
+
+/// The type of the objects named by a `GRANT` or `REVOKE` statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrantObjectType {
+    /// Tables, views, materialized views, and sources.
+    Table,
+    Database,
+    Schema,
+    Cluster,
+}
+
+impl AstDisplay for GrantObjectType {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            GrantObjectType::Table => "TABLE",
+            GrantObjectType::Database => "DATABASE",
+            GrantObjectType::Schema => "SCHEMA",
+            GrantObjectType::Cluster => "CLUSTER",
+        })
+    }
+}
+impl_display!(GrantObjectType);
+
+/// `GRANT <privileges> ON <objects> TO <roles>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrantPrivilegesStatement {
+    /// The privileges being granted.
+    pub privileges: PrivilegeSpecification,
+    /// The type of the objects.
+    pub object_type: GrantObjectType,
+    /// The objects on which the privileges are granted.
+    pub names: Vec<UnresolvedObjectName>,
+    /// The roles to which the privileges are granted. `PUBLIC` is represented
+    /// by the identifier `public`.
+    pub roles: Vec<Ident>,
+}
+
+impl AstDisplay for GrantPrivilegesStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("GRANT ");
+        f.write_node(&self.privileges);
+        f.write_str(" ON ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        f.write_node(&display::comma_separated(&self.names));
+        f.write_str(" TO ");
+        f.write_node(&display::comma_separated(&self.roles));
+    }
+}
+impl_display!(GrantPrivilegesStatement);
+
+/// `REVOKE <privileges> ON <objects> FROM <roles>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RevokePrivilegesStatement {
+    /// The privileges being revoked.
+    pub privileges: PrivilegeSpecification,
+    /// The type of the objects.
+    pub object_type: GrantObjectType,
+    /// The objects on which the privileges are revoked.
+    pub names: Vec<UnresolvedObjectName>,
+    /// The roles from which the privileges are revoked. `PUBLIC` is
+    /// represented by the identifier `public`.
+    pub roles: Vec<Ident>,
+}
+
+impl AstDisplay for RevokePrivilegesStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("REVOKE ");
+        f.write_node(&self.privileges);
+        f.write_str(" ON ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        f.write_node(&display::comma_separated(&self.names));
+        f.write_str(" FROM ");
+        f.write_node(&display::comma_separated(&self.roles));
+    }
+}
+impl_display!(RevokePrivilegesStatement);
+
+/// `GRANT <roles> TO <members>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrantRoleStatement {
+    /// The roles being granted.
+    pub role_names: Vec<Ident>,
+    /// The roles that become members of the granted roles.
+    pub member_names: Vec<Ident>,
+}
+
+impl AstDisplay for GrantRoleStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("GRANT ");
+        f.write_node(&display::comma_separated(&self.role_names));
+        f.write_str(" TO ");
+        f.write_node(&display::comma_separated(&self.member_names));
+    }
+}
+impl_display!(GrantRoleStatement);
+
+/// `REVOKE <roles> FROM <members>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RevokeRoleStatement {
+    /// The roles being revoked.
+    pub role_names: Vec<Ident>,
+    /// The roles that stop being members of the revoked roles.
+    pub member_names: Vec<Ident>,
+}
+
+impl AstDisplay for RevokeRoleStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("REVOKE ");
+        f.write_node(&display::comma_separated(&self.role_names));
+        f.write_str(" FROM ");
+        f.write_node(&display::comma_separated(&self.member_names));
+    }
+}
+impl_display!(RevokeRoleStatement);
+
 /// `SET <variable>`
 ///
 /// Note: this is not a standard SQL statement, but it is supported by at
@@ -47,7 +47,7 @@ where
     T: AstDisplay,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        AstFormatter::new(f, FormatMode::Simple).write_node(self);
+        AstFormatter::new(f, FormatMode::SimpleRedacted).write_node(self);
         Ok(())
     }
 }
@@ -72,6 +72,9 @@ pub enum FormatMode {
     // Simple is the normal way of printing for human consumption. Identifiers are quoted only if
     // necessary.
     Simple,
+    // SimpleRedacted is like Simple, but strips out sensitive values, like role passwords, that
+    // must not be shown to users or written to logs. This mode is used by fmt::Display.
+    SimpleRedacted,
     // Stable prints out the AST in a form more suitable for persistence. All identifiers are
     // quoted, even if not necessary. This mode is used when persisting table information to the
     // catalog.
@@ -103,6 +106,11 @@ where
         self.mode == FormatMode::Stable
     }
 
+    // Whether sensitive values in the AST should be redacted.
+    pub fn redacted(&self) -> bool {
+        self.mode == FormatMode::SimpleRedacted
+    }
+
     pub fn new(buf: W, mode: FormatMode) -> Self {
         AstFormatter { buf, mode }
     }
@@ -128,6 +136,13 @@ pub trait AstDisplay {
         self.fmt(&mut f);
         buf
     }
+
+    fn to_ast_string_redacted(&self) -> String {
+        let mut buf = String::new();
+        let mut f = AstFormatter::new(&mut buf, FormatMode::SimpleRedacted);
+        self.fmt(&mut f);
+        buf
+    }
 }
 
 // Derive a fmt::Display implementation for types implementing AstDisplay.
@@ -137,7 +152,7 @@ macro_rules! impl_display {
         impl std::fmt::Display for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 use $crate::ast::display::{AstFormatter, FormatMode};
-                AstFormatter::new(f, FormatMode::Simple).write_node(self);
+                AstFormatter::new(f, FormatMode::SimpleRedacted).write_node(self);
                 Ok(())
             }
         }
@@ -149,7 +164,7 @@ macro_rules! impl_display_t {
         impl<T: AstInfo> std::fmt::Display for $name<T> {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 use crate::ast::display::{AstFormatter, FormatMode};
-                AstFormatter::new(f, FormatMode::Simple).write_node(self);
+                AstFormatter::new(f, FormatMode::SimpleRedacted).write_node(self);
                 Ok(())
             }
         }
@@ -254,6 +254,8 @@ impl<'a> Parser<'a> {
                 Token::Keyword(CREATE) => Ok(self.parse_create()?),
                 Token::Keyword(DISCARD) => Ok(self.parse_discard()?),
                 Token::Keyword(DROP) => Ok(self.parse_drop()?),
+                Token::Keyword(GRANT) => Ok(self.parse_grant()?),
+                Token::Keyword(REVOKE) => Ok(self.parse_revoke()?),
                 Token::Keyword(DELETE) => Ok(self.parse_delete()?),
                 Token::Keyword(INSERT) => Ok(self.parse_insert()?),
                 Token::Keyword(UPDATE) => Ok(self.parse_update()?),
@@ -552,7 +554,7 @@ impl<'a> Parser<'a> {
                         let query = SetExpr::Query(Box::new(query));
                         let ctes = vec![];
                         let body = parser.parse_query_body_seeded(SetPrecedence::Zero, query)?;
-                        Ok(Either::Query(parser.parse_query_tail(ctes, body)?))
+                        Ok(Either::Query(parser.parse_query_tail(false, ctes, body)?))
                     }
 
                     // The next token is something else. That implies we have a
@@ -577,7 +579,7 @@ impl<'a> Parser<'a> {
             self.parse_at_most_one_keyword(&[ALL, DISTINCT], &format!("function: {}", name))?,
             Some(DISTINCT),
         );
-        let args = self.parse_optional_args(true)?;
+        let mut args = self.parse_optional_args(true)?;
 
         if distinct && matches!(args, FunctionArgs::Star) {
             return Err(self.error(
@@ -586,6 +588,33 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let pos = self.peek_pos();
+        let within_group = self.parse_keywords(&[WITHIN, GROUP]);
+        if within_group {
+            match &mut args {
+                FunctionArgs::Args { order_by, .. } if order_by.is_empty() && !distinct => {
+                    self.expect_token(&Token::LParen)?;
+                    self.expect_keywords(&[ORDER, BY])?;
+                    *order_by = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+                    self.expect_token(&Token::RParen)?;
+                }
+                FunctionArgs::Args { order_by, .. } if !order_by.is_empty() => {
+                    return Err(self.error(
+                        pos,
+                        "cannot use multiple ORDER BY clauses with WITHIN GROUP".to_string(),
+                    ));
+                }
+                FunctionArgs::Args { .. } => {
+                    return Err(
+                        self.error(pos, "cannot use DISTINCT with WITHIN GROUP".to_string())
+                    );
+                }
+                FunctionArgs::Star => {
+                    return Err(self.error(pos, "cannot use * with WITHIN GROUP".to_string()));
+                }
+            }
+        }
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -632,6 +661,7 @@ impl<'a> Parser<'a> {
             filter,
             over,
             distinct,
+            within_group,
         }))
     }
 
@@ -764,6 +794,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -827,6 +858,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -845,6 +877,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -1071,6 +1104,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: false,
                     }))
                 }
                 COLLATE => Ok(Expr::Collate {
@@ -1195,6 +1229,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -1694,7 +1729,7 @@ impl<'a> Parser<'a> {
             self.expect_keyword(WITH)?;
             let columns = if self.parse_keyword(HEADER) || self.parse_keyword(HEADERS) {
                 CsvColumns::Header {
-                    names: self.parse_parenthesized_column_list(Mandatory)?,
+                    names: self.parse_parenthesized_column_list(Optional)?,
                 }
             } else {
                 let n_cols = self.parse_literal_uint()? as usize;
@@ -1712,7 +1747,19 @@ impl<'a> Parser<'a> {
             };
             Format::Csv { columns, delimiter }
         } else if self.parse_keyword(JSON) {
-            Format::Json
+            let columns = if self.peek_token() == Some(Token::LParen) {
+                self.parse_composite_type_definition()?
+            } else {
+                vec![]
+            };
+            Format::Json { columns }
+        } else if self.parse_keyword(PARQUET) {
+            let columns = if self.peek_token() == Some(Token::LParen) {
+                self.parse_composite_type_definition()?
+            } else {
+                vec![]
+            };
+            Format::Parquet { columns }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
@@ -1720,7 +1767,7 @@ impl<'a> Parser<'a> {
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, PARQUET, TEXT, or BYTES",
                 self.peek_token(),
             );
         };
@@ -1887,28 +1934,13 @@ impl<'a> Parser<'a> {
 
         let seed = if self.parse_keyword(SEED) {
             let key = if self.parse_keyword(KEY) {
-                self.expect_keyword(SCHEMA)?;
-                let schema = self.parse_literal_string()?;
-                self.expect_keyword(MESSAGE)?;
-                let message_name = self.parse_literal_string()?;
-                Some(CsrSeedProtobufSchema {
-                    schema,
-                    message_name,
-                })
+                Some(self.parse_csr_seed_protobuf_schema()?)
             } else {
                 None
             };
-            self.expect_keywords(&[VALUE, SCHEMA])?;
-            let value_schema = self.parse_literal_string()?;
-            self.expect_keyword(MESSAGE)?;
-            let value_message_name = self.parse_literal_string()?;
-            Some(CsrSeedProtobuf {
-                value: CsrSeedProtobufSchema {
-                    schema: value_schema,
-                    message_name: value_message_name,
-                },
-                key,
-            })
+            self.expect_keyword(VALUE)?;
+            let value = self.parse_csr_seed_protobuf_schema()?;
+            Some(CsrSeedProtobuf { value, key })
         } else {
             None
         };
@@ -1916,6 +1948,28 @@ impl<'a> Parser<'a> {
         Ok(CsrConnectionProtobuf { connection, seed })
     }
 
+    fn parse_csr_seed_protobuf_schema(&mut self) -> Result<CsrSeedProtobufSchema, ParserError> {
+        self.expect_keyword(SCHEMA)?;
+        let schema = self.parse_literal_string()?;
+        self.expect_keyword(MESSAGE)?;
+        let message_name = self.parse_literal_string()?;
+        let schema_id = if self.parse_keyword(ID) {
+            let pos = self.index;
+            Some(
+                self.parse_literal_int()?
+                    .try_into()
+                    .map_err(|_| ParserError::new(pos, "Expected a 32-bit integer"))?,
+            )
+        } else {
+            None
+        };
+        Ok(CsrSeedProtobufSchema {
+            schema,
+            message_name,
+            schema_id,
+        })
+    }
+
     fn parse_envelope(&mut self) -> Result<Envelope, ParserError> {
         let envelope = if self.parse_keyword(NONE) {
             Envelope::None
@@ -1943,8 +1997,18 @@ impl<'a> Parser<'a> {
             Compression::None
         } else if self.parse_keyword(GZIP) {
             Compression::Gzip
+        } else if self.parse_keyword(ZSTD) {
+            Compression::Zstd
+        } else if self.parse_keyword(BZIP2) {
+            Compression::Bzip2
+        } else if self.parse_keyword(SNAPPY) {
+            Compression::Snappy
         } else {
-            return self.expected(self.peek_pos(), "NONE or GZIP", self.peek_token());
+            return self.expected(
+                self.peek_pos(),
+                "NONE, GZIP, ZSTD, BZIP2, or SNAPPY",
+                self.peek_token(),
+            );
         };
         Ok(compression)
     }
@@ -2201,6 +2265,8 @@ impl<'a> Parser<'a> {
                 if self.parse_keyword(METADATA) {
                     self.expect_keywords(&[REFRESH, INTERVAL, MS])?;
                     KafkaConfigOptionName::TopicMetadataRefreshIntervalMs
+                } else if self.parse_keyword(PATTERN) {
+                    KafkaConfigOptionName::TopicPattern
                 } else {
                     KafkaConfigOptionName::Topic
                 }
@@ -2492,21 +2558,26 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name =
-            match self.expect_one_of_keywords(&[IGNORE, REMOTE, SIZE, TIMELINE, TIMESTAMP])? {
-                IGNORE => {
-                    self.expect_keyword(KEYS)?;
-                    CreateSourceOptionName::IgnoreKeys
-                }
-                REMOTE => CreateSourceOptionName::Remote,
-                SIZE => CreateSourceOptionName::Size,
-                TIMELINE => CreateSourceOptionName::Timeline,
-                TIMESTAMP => {
-                    self.expect_keyword(INTERVAL)?;
-                    CreateSourceOptionName::TimestampInterval
-                }
-                _ => unreachable!(),
-            };
+        let name = match self
+            .expect_one_of_keywords(&[IGNORE, REMOTE, SIZE, TIMELINE, TIMESTAMP, UPSERT])?
+        {
+            IGNORE => {
+                self.expect_keyword(KEYS)?;
+                CreateSourceOptionName::IgnoreKeys
+            }
+            REMOTE => CreateSourceOptionName::Remote,
+            SIZE => CreateSourceOptionName::Size,
+            TIMELINE => CreateSourceOptionName::Timeline,
+            TIMESTAMP => {
+                self.expect_keyword(INTERVAL)?;
+                CreateSourceOptionName::TimestampInterval
+            }
+            UPSERT => {
+                self.expect_keywords(&[STATE, BACKEND])?;
+                CreateSourceOptionName::UpsertStateBackend
+            }
+            _ => unreachable!(),
+        };
         Ok(name)
     }
 
@@ -2758,7 +2829,25 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
-        self.expect_keyword(KAFKA)?;
+        if self.expect_one_of_keywords(&[KAFKA, S3])? == S3 {
+            // INTO S3 CONNECTION <aws CONNECTION> (BUCKET = '<bucket>', PREFIX = '<prefix>')
+            self.expect_keyword(CONNECTION)?;
+            let connection = self.parse_raw_name()?;
+
+            let options = if self.consume_token(&Token::LParen) {
+                let options = self.parse_comma_separated(Parser::parse_s3_sink_option)?;
+                self.expect_token(&Token::RParen)?;
+                options
+            } else {
+                vec![]
+            };
+
+            return Ok(CreateSinkConnection::S3 {
+                connection,
+                options,
+            });
+        }
+
         self.expect_keyword(CONNECTION)?;
 
         let connection = self.parse_kafka_connection_reference()?;
@@ -2787,6 +2876,20 @@ impl<'a> Parser<'a> {
         Ok(CreateSinkConnection::Kafka { connection, key })
     }
 
+    fn parse_s3_sink_option(&mut self) -> Result<S3SinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[BUCKET, PREFIX])? {
+            BUCKET => S3SinkOptionName::Bucket,
+            PREFIX => S3SinkOptionName::Prefix,
+            _ => unreachable!(),
+        };
+
+        let _ = self.consume_token(&Token::Eq);
+        Ok(S3SinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
         let mut if_exists = if self.parse_keyword(OR) {
             self.expect_keyword(REPLACE)?;
@@ -2959,12 +3062,20 @@ impl<'a> Parser<'a> {
         let _ = self.parse_keyword(WITH);
         let mut options = vec![];
         loop {
-            match self.parse_one_of_keywords(&[SUPERUSER, NOSUPERUSER, LOGIN, NOLOGIN]) {
+            match self.parse_one_of_keywords(&[SUPERUSER, NOSUPERUSER, LOGIN, NOLOGIN, PASSWORD]) {
                 None => break,
                 Some(SUPERUSER) => options.push(CreateRoleOption::SuperUser),
                 Some(NOSUPERUSER) => options.push(CreateRoleOption::NoSuperUser),
                 Some(LOGIN) => options.push(CreateRoleOption::Login),
                 Some(NOLOGIN) => options.push(CreateRoleOption::NoLogin),
+                Some(PASSWORD) => {
+                    let password = if self.parse_keyword(NULL) {
+                        None
+                    } else {
+                        Some(self.parse_literal_string()?)
+                    };
+                    options.push(CreateRoleOption::Password(password));
+                }
                 Some(_) => unreachable!(),
             }
         }
@@ -3179,6 +3290,104 @@ impl<'a> Parser<'a> {
         Ok(Statement::Discard(DiscardStatement { target }))
     }
 
+    fn parse_grant(&mut self) -> Result<Statement<Raw>, ParserError> {
+        match self.parse_privilege_specification()? {
+            Some(privileges) => {
+                let (object_type, names) = self.parse_grant_objects()?;
+                self.expect_keyword(TO)?;
+                let roles = self.parse_comma_separated(Parser::parse_identifier)?;
+                Ok(Statement::GrantPrivileges(GrantPrivilegesStatement {
+                    privileges,
+                    object_type,
+                    names,
+                    roles,
+                }))
+            }
+            None => {
+                let role_names = self.parse_comma_separated(Parser::parse_identifier)?;
+                self.expect_keyword(TO)?;
+                let member_names = self.parse_comma_separated(Parser::parse_identifier)?;
+                Ok(Statement::GrantRole(GrantRoleStatement {
+                    role_names,
+                    member_names,
+                }))
+            }
+        }
+    }
+
+    fn parse_revoke(&mut self) -> Result<Statement<Raw>, ParserError> {
+        match self.parse_privilege_specification()? {
+            Some(privileges) => {
+                let (object_type, names) = self.parse_grant_objects()?;
+                self.expect_keyword(FROM)?;
+                let roles = self.parse_comma_separated(Parser::parse_identifier)?;
+                Ok(Statement::RevokePrivileges(RevokePrivilegesStatement {
+                    privileges,
+                    object_type,
+                    names,
+                    roles,
+                }))
+            }
+            None => {
+                let role_names = self.parse_comma_separated(Parser::parse_identifier)?;
+                self.expect_keyword(FROM)?;
+                let member_names = self.parse_comma_separated(Parser::parse_identifier)?;
+                Ok(Statement::RevokeRole(RevokeRoleStatement {
+                    role_names,
+                    member_names,
+                }))
+            }
+        }
+    }
+
+    /// Parses the privileges named by a `GRANT` or `REVOKE` statement, or
+    /// returns `None` if the statement grants or revokes roles instead.
+    fn parse_privilege_specification(
+        &mut self,
+    ) -> Result<Option<PrivilegeSpecification>, ParserError> {
+        if self.parse_keyword(ALL) {
+            let _ = self.parse_keyword(PRIVILEGES);
+            return Ok(Some(PrivilegeSpecification::All));
+        }
+        match self.peek_token() {
+            Some(Token::Keyword(SELECT | INSERT | UPDATE | DELETE | USAGE | CREATE)) => {
+                let privileges = self.parse_comma_separated(Parser::parse_privilege)?;
+                Ok(Some(PrivilegeSpecification::Privileges(privileges)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_privilege(&mut self) -> Result<Privilege, ParserError> {
+        Ok(
+            match self.expect_one_of_keywords(&[SELECT, INSERT, UPDATE, DELETE, USAGE, CREATE])? {
+                SELECT => Privilege::Select,
+                INSERT => Privilege::Insert,
+                UPDATE => Privilege::Update,
+                DELETE => Privilege::Delete,
+                USAGE => Privilege::Usage,
+                CREATE => Privilege::Create,
+                _ => unreachable!(),
+            },
+        )
+    }
+
+    /// Parses the `ON <objects>` clause of a `GRANT` or `REVOKE` statement.
+    fn parse_grant_objects(
+        &mut self,
+    ) -> Result<(GrantObjectType, Vec<UnresolvedObjectName>), ParserError> {
+        self.expect_keyword(ON)?;
+        let object_type = match self.parse_one_of_keywords(&[TABLE, DATABASE, SCHEMA, CLUSTER]) {
+            Some(DATABASE) => GrantObjectType::Database,
+            Some(SCHEMA) => GrantObjectType::Schema,
+            Some(CLUSTER) => GrantObjectType::Cluster,
+            Some(TABLE) | None => GrantObjectType::Table,
+            Some(_) => unreachable!(),
+        };
+        let names = self.parse_comma_separated(Parser::parse_object_name)?;
+        Ok((object_type, names))
+    }
+
     fn parse_drop(&mut self) -> Result<Statement<Raw>, ParserError> {
         let object_type = match self.expect_one_of_keywords(&[
             CONNECTION,
@@ -4378,44 +4587,59 @@ impl<'a> Parser<'a> {
     /// expect the initial keyword to be already consumed
     fn parse_query(&mut self) -> Result<Query<Raw>, ParserError> {
         self.checked_recur_mut(|parser| {
-            let ctes = if parser.parse_keyword(WITH) {
-                // TODO: optional RECURSIVE
-                parser.parse_comma_separated(Parser::parse_cte)?
+            let (ctes_recursive, ctes) = if parser.parse_keyword(WITH) {
+                // `RECURSIVE` is not a reserved keyword, so it is only a
+                // modifier if it is not itself the name of the first CTE.
+                let ctes_recursive = parser.peek_keyword(RECURSIVE)
+                    && !matches!(
+                        parser.peek_nth_token(1),
+                        Some(Token::Keyword(AS)) | Some(Token::LParen)
+                    );
+                if ctes_recursive {
+                    parser.next_token();
+                }
+                (
+                    ctes_recursive,
+                    parser.parse_comma_separated(Parser::parse_cte)?,
+                )
             } else {
-                vec![]
+                (false, vec![])
             };
 
             let body = parser.parse_query_body(SetPrecedence::Zero)?;
 
-            parser.parse_query_tail(ctes, body)
+            parser.parse_query_tail(ctes_recursive, ctes, body)
         })
     }
 
     fn parse_query_tail(
         &mut self,
+        ctes_recursive: bool,
         ctes: Vec<Cte<Raw>>,
         body: SetExpr<Raw>,
     ) -> Result<Query<Raw>, ParserError> {
-        let (inner_ctes, inner_order_by, inner_limit, inner_offset, body) = match body {
-            SetExpr::Query(query) => {
-                let Query {
-                    ctes,
-                    body,
-                    order_by,
-                    limit,
-                    offset,
-                } = *query;
-                (ctes, order_by, limit, offset, body)
-            }
-            _ => (vec![], vec![], None, None, body),
-        };
+        let (inner_ctes_recursive, inner_ctes, inner_order_by, inner_limit, inner_offset, body) =
+            match body {
+                SetExpr::Query(query) => {
+                    let Query {
+                        ctes_recursive,
+                        ctes,
+                        body,
+                        order_by,
+                        limit,
+                        offset,
+                    } = *query;
+                    (ctes_recursive, ctes, order_by, limit, offset, body)
+                }
+                _ => (false, vec![], vec![], None, None, body),
+            };
 
-        let ctes = if ctes.is_empty() {
-            inner_ctes
+        let (ctes_recursive, ctes) = if ctes.is_empty() {
+            (inner_ctes_recursive, inner_ctes)
         } else if !inner_ctes.is_empty() {
             return parser_err!(self, self.peek_pos(), "multiple WITH clauses not allowed");
         } else {
-            ctes
+            (ctes_recursive, ctes)
         };
 
         let order_by = if self.parse_keywords(&[ORDER, BY]) {
@@ -4493,6 +4717,7 @@ impl<'a> Parser<'a> {
         }
 
         Ok(Query {
+            ctes_recursive,
             ctes,
             body,
             order_by,
@@ -60,7 +60,7 @@ fn datadriven() {
                     return "expected exactly one statement\n".to_string();
                 }
                 let stmt = s.into_element();
-                let parsed = match parser::parse_statements(&stmt.to_string()) {
+                let parsed = match parser::parse_statements(&stmt.to_ast_string()) {
                     Ok(parsed) => parsed.into_element(),
                     Err(err) => return format!("reparse failed: {}\n", err),
                 };
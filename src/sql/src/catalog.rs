@@ -38,7 +38,7 @@ use mz_storage_client::types::sources::SourceDesc;
 use crate::func::Func;
 use crate::names::{
     Aug, DatabaseId, FullObjectName, PartialObjectName, QualifiedObjectName, QualifiedSchemaName,
-    ResolvedDatabaseSpecifier, RoleId, SchemaSpecifier,
+    ResolvedDatabaseSpecifier, RoleId, SchemaId, SchemaSpecifier,
 };
 use crate::normalize;
 use crate::plan::statement::StatementDesc;
@@ -402,6 +402,69 @@ impl fmt::Display for CatalogItemType {
     }
 }
 
+/// A privilege that a role can hold on an object.
+#[derive(Debug, Deserialize, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Privilege {
+    /// Read from a relation.
+    Select,
+    /// Insert into a table.
+    Insert,
+    /// Update rows in a table.
+    Update,
+    /// Delete rows from a table.
+    Delete,
+    /// Look up objects in a schema, or run queries on a cluster.
+    Usage,
+    /// Create schemas in a database, objects in a schema, or indexes and
+    /// materialized views on a cluster.
+    Create,
+}
+
+impl Privilege {
+    /// Returns the privileges that are meaningful for objects of the
+    /// specified kind, i.e., the privileges that `ALL` expands to.
+    pub fn all_for(object: &PrivilegeObject) -> &'static [Privilege] {
+        match object {
+            PrivilegeObject::Database(_) => &[Privilege::Create],
+            PrivilegeObject::Schema(_) | PrivilegeObject::ComputeInstance(_) => {
+                &[Privilege::Usage, Privilege::Create]
+            }
+            PrivilegeObject::Item(_) => &[
+                Privilege::Select,
+                Privilege::Insert,
+                Privilege::Update,
+                Privilege::Delete,
+            ],
+        }
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Privilege::Select => f.write_str("SELECT"),
+            Privilege::Insert => f.write_str("INSERT"),
+            Privilege::Update => f.write_str("UPDATE"),
+            Privilege::Delete => f.write_str("DELETE"),
+            Privilege::Usage => f.write_str("USAGE"),
+            Privilege::Create => f.write_str("CREATE"),
+        }
+    }
+}
+
+/// An object on which privileges can be granted.
+#[derive(Debug, Deserialize, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum PrivilegeObject {
+    /// A database.
+    Database(DatabaseId),
+    /// A schema.
+    Schema(SchemaId),
+    /// A compute instance.
+    ComputeInstance(ComputeInstanceId),
+    /// A catalog item.
+    Item(GlobalId),
+}
+
 /// Details about a type in the catalog.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CatalogTypeDetails<T: TypeReference> {
@@ -459,6 +522,7 @@ pub enum CatalogType<T: TypeReference> {
     MzTimestamp,
     Interval,
     Jsonb,
+    JsonPath,
     List {
         element_reference: T::Reference,
     },
@@ -470,6 +534,9 @@ pub enum CatalogType<T: TypeReference> {
     Oid,
     PgLegacyChar,
     Pseudo,
+    Range {
+        element_reference: T::Reference,
+    },
     Record {
         fields: Vec<(ColumnName, T::Reference)>,
     },
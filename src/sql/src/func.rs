@@ -21,7 +21,11 @@ use once_cell::sync::Lazy;
 use mz_expr::func;
 use mz_ore::collections::CollectionExt;
 use mz_pgrepr::oid;
-use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use mz_repr::adt::datetime::Timezone;
+use mz_repr::adt::regex::Regex;
+use mz_repr::{
+    strconv, ColumnName, ColumnType, Datum, DatumMap, RelationType, Row, ScalarBaseType, ScalarType,
+};
 
 use crate::ast::{SelectStatement, Statement};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
@@ -72,7 +76,9 @@ impl TypeCategory {
         match typ {
             ScalarType::Array(..) | ScalarType::Int2Vector => Self::Array,
             ScalarType::Bool => Self::Boolean,
-            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::Uuid => Self::UserDefined,
+            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::JsonPath | ScalarType::Uuid => {
+                Self::UserDefined
+            }
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp
@@ -105,6 +111,7 @@ impl TypeCategory {
             }
             ScalarType::Map { .. } => Self::Pseudo,
             ScalarType::MzTimestamp => Self::Numeric,
+            ScalarType::Range { .. } => Self::Range,
         }
     }
 
@@ -120,6 +127,8 @@ impl TypeCategory {
             | ParamType::NonVecAny
             | ParamType::MapAny
             | ParamType::MapAnyCompatible
+            | ParamType::RangeAny
+            | ParamType::RangeElementAny
             | ParamType::RecordAny => Self::Pseudo,
             ParamType::Plain(t) => Self::from_type(t),
         }
@@ -136,7 +145,9 @@ impl TypeCategory {
         match catalog_type {
             CatalogType::Array { .. } | CatalogType::Int2Vector => Self::Array,
             CatalogType::Bool => Self::Boolean,
-            CatalogType::Bytes | CatalogType::Jsonb | CatalogType::Uuid => Self::UserDefined,
+            CatalogType::Bytes | CatalogType::Jsonb | CatalogType::JsonPath | CatalogType::Uuid => {
+                Self::UserDefined
+            }
             CatalogType::Date
             | CatalogType::Time
             | CatalogType::Timestamp
@@ -163,6 +174,7 @@ impl TypeCategory {
             CatalogType::Record { .. } => TypeCategory::Composite,
             CatalogType::Map { .. } | CatalogType::Pseudo => Self::Pseudo,
             CatalogType::MzTimestamp => Self::String,
+            CatalogType::Range { .. } => Self::Range,
         }
     }
 
@@ -704,6 +716,13 @@ pub enum ParamType {
     /// A standard parameter that accepts arguments that match its embedded
     /// `ScalarType`.
     Plain(ScalarType),
+    /// A pseudotype permitting any range type, requiring other "Any"-type
+    /// parameters to be of the same type.
+    RangeAny,
+    /// A pseudotype permitting any type, requiring other "Any"-type parameters
+    /// to be of the same type. Additionally, enforces a constraint that when
+    /// used with `RangeAny`, resolves to that argument's element type.
+    RangeElementAny,
     /// A polymorphic pseudotype permitting a `ScalarType::Record` of any type,
     /// but all records must be structurally equal.
     RecordAny,
@@ -716,12 +735,13 @@ impl ParamType {
         use ScalarType::*;
 
         match self {
-            Any | AnyCompatible | ListElementAnyCompatible => true,
+            Any | AnyCompatible | ListElementAnyCompatible | RangeElementAny => true,
             ArrayAny | ArrayAnyCompatible => matches!(t, Array(..) | Int2Vector),
             ListAny | ListAnyCompatible => matches!(t, List { .. }),
             MapAny | MapAnyCompatible => matches!(t, Map { .. }),
             NonVecAny => !t.is_vec(),
             Plain(to) => typeconv::can_cast(ecx, CastContext::Implicit, t, to),
+            RangeAny => matches!(t, Range { .. }),
             RecordAny => matches!(t, Record { .. }),
         }
     }
@@ -767,6 +787,8 @@ impl ParamType {
             | MapAny
             | MapAnyCompatible
             | NonVecAny
+            | RangeAny
+            | RangeElementAny
             // In PG, RecordAny isn't polymorphic even though it offers
             // polymorphic behavior. For more detail, see
             // `PolymorphicCompatClass::StructuralEq`.
@@ -795,6 +817,8 @@ impl ParamType {
             ParamType::MapAny => "map",
             ParamType::MapAnyCompatible => "anycompatiblemap",
             ParamType::NonVecAny => "anynonarray",
+            ParamType::RangeAny => "anyrange",
+            ParamType::RangeElementAny => "anyelement",
             ParamType::RecordAny => "record",
         }
     }
@@ -826,7 +850,7 @@ impl From<ScalarBaseType> for ParamType {
     fn from(s: ScalarBaseType) -> ParamType {
         use ScalarBaseType::*;
         let s = match s {
-            Array | List | Map | Record => {
+            Array | List | Map | Range | Record => {
                 panic!("use polymorphic parameters rather than {:?}", s);
             }
             Bool => ScalarType::Bool,
@@ -850,6 +874,7 @@ impl From<ScalarBaseType> for ParamType {
             VarChar => ScalarType::VarChar { max_length: None },
             PgLegacyChar => ScalarType::PgLegacyChar,
             Jsonb => ScalarType::Jsonb,
+            JsonPath => ScalarType::JsonPath,
             Uuid => ScalarType::Uuid,
             Oid => ScalarType::Oid,
             RegClass => ScalarType::RegClass,
@@ -1380,7 +1405,9 @@ impl TryFrom<&ParamType> for PolymorphicCompatClass {
         use ParamType::*;
 
         Ok(match param {
-            ArrayAny | ListAny | MapAny | NonVecAny => PolymorphicCompatClass::BaseEq,
+            ArrayAny | ListAny | MapAny | NonVecAny | RangeAny | RangeElementAny => {
+                PolymorphicCompatClass::BaseEq
+            }
             ArrayAnyCompatible | AnyCompatible => PolymorphicCompatClass::BestCommonAny,
             ListAnyCompatible | ListElementAnyCompatible => PolymorphicCompatClass::BestCommonList,
             MapAnyCompatible => PolymorphicCompatClass::BestCommonMap,
@@ -1449,8 +1476,11 @@ impl PolymorphicSolution {
 
         self.seen.push(match param {
             AnyCompatible | ArrayAny | ListAny | ListAnyCompatible | MapAny | MapAnyCompatible
-            | NonVecAny | RecordAny => seen,
+            | NonVecAny | RangeAny | RecordAny => seen,
             ArrayAnyCompatible => seen.map(|array| array.unwrap_array_element_type().clone()),
+            RangeElementAny => seen.map(|el| ScalarType::Range {
+                element_type: Box::new(el),
+            }),
             ListElementAnyCompatible => seen.map(|el| ScalarType::List {
                 custom_id: None,
                 element_type: Box::new(el),
@@ -1550,7 +1580,7 @@ impl PolymorphicSolution {
 
         match param {
             AnyCompatible | ArrayAny | ListAny | ListAnyCompatible | MapAny | MapAnyCompatible
-            | NonVecAny => self.key.clone(),
+            | NonVecAny | RangeAny => self.key.clone(),
             ArrayAnyCompatible => self
                 .key
                 .as_ref()
@@ -1559,6 +1589,10 @@ impl PolymorphicSolution {
                 .key
                 .as_ref()
                 .map(|key| key.unwrap_list_element_type().clone()),
+            RangeElementAny => self
+                .key
+                .as_ref()
+                .map(|key| key.unwrap_range_element_type().clone()),
             _ => unreachable!(
                 "cannot use polymorphic solution to resolve target type for param {:?}",
                 param,
@@ -1591,7 +1625,7 @@ fn coerce_args_to_types(
                 }
                 _ => cexpr.type_as_any(ecx)?,
             },
-            p @ (ArrayAny | ListAny | MapAny) => {
+            p @ (ArrayAny | ListAny | MapAny | RangeAny | RangeElementAny) => {
                 let target = polymorphic_solution
                     .target_for_param_type(p)
                     .ok_or_else(|| {
@@ -1660,7 +1694,7 @@ macro_rules! impl_def {
     // Return type can be automatically determined as a function of the
     // parameters.
     ($params:expr, $op:expr, $oid:expr) => {{
-        let pcx = crate::plan::PlanContext::new(DateTime::<Utc>::MIN_UTC, false);
+        let pcx = crate::plan::PlanContext::new(DateTime::<Utc>::MIN_UTC, false, Timezone::default());
         let scx = StatementContext::new(None, &crate::catalog::DummyCatalog);
         // This lifetime is compatible with more functions.
         let qcx = QueryContext::root(&scx, QueryLifetime::OneShot(&pcx));
@@ -1932,9 +1966,24 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         },
         "date_trunc" => Scalar {
             params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp, 2020;
-            params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz, 1217;
+            params!(String, TimestampTz) => Operation::binary(|ecx, units, ts| {
+                // Truncate in the session's time zone, as PostgreSQL does, so
+                // that e.g. days begin at local midnight.
+                Ok(match ecx.session_timezone() {
+                    None => units.call_binary(ts, BinaryFunc::DateTruncTimestampTz),
+                    Some(tz) => {
+                        let local = tz.clone().call_binary(ts, BinaryFunc::TimezoneTimestampTz);
+                        let local = units.call_binary(local, BinaryFunc::DateTruncTimestamp);
+                        tz.call_binary(local, BinaryFunc::TimezoneTimestamp)
+                    }
+                })
+            }), 1217;
             params!(String, Interval) => BinaryFunc::DateTruncInterval, 1218;
         },
+        "daterange" => Scalar {
+            params!(Date, Date) => VariadicFunc::RangeCreate { elem_type: ScalarType::Date }, 3941;
+            params!(Date, Date, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Date }, 3942;
+        },
         "degrees" => Scalar {
             params!(Float64) => UnaryFunc::Degrees(func::Degrees), 1608;
         },
@@ -1966,6 +2015,17 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(String, String, String) => VariadicFunc::HmacString, 44156;
             params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes, 44157;
         },
+        "int4range" => Scalar {
+            params!(Int32, Int32) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int32 }, 3840;
+            params!(Int32, Int32, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int32 }, 3841;
+        },
+        "int8range" => Scalar {
+            params!(Int64, Int64) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int64 }, 3945;
+            params!(Int64, Int64, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int64 }, 3946;
+        },
+        "isempty" => Scalar {
+            params!(RangeAny) => UnaryFunc::RangeEmpty(func::RangeEmpty) => Bool, 3850;
+        },
         "jsonb_array_length" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbArrayLength(func::JsonbArrayLength) => Int32, 3207;
         },
@@ -1992,9 +2052,37 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 })
             }) => Jsonb, 3273;
         },
+        "jsonb_insert" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => VariadicFunc::JsonbInsert => Jsonb, oid::FUNC_JSONB_INSERT_3_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbInsert => Jsonb, 3579;
+        },
+        "jsonb_path_exists" => Scalar {
+            params!(Jsonb, JsonPath) => VariadicFunc::JsonbPathExists => Bool, oid::FUNC_JSONB_PATH_EXISTS_2_OID;
+            params!(Jsonb, JsonPath, Jsonb) => VariadicFunc::JsonbPathExists => Bool, oid::FUNC_JSONB_PATH_EXISTS_3_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathExists => Bool, 4005;
+        },
+        "jsonb_path_match" => Scalar {
+            params!(Jsonb, JsonPath) => VariadicFunc::JsonbPathMatch => Bool, oid::FUNC_JSONB_PATH_MATCH_2_OID;
+            params!(Jsonb, JsonPath, Jsonb) => VariadicFunc::JsonbPathMatch => Bool, oid::FUNC_JSONB_PATH_MATCH_3_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathMatch => Bool, 4009;
+        },
+        "jsonb_path_query_array" => Scalar {
+            params!(Jsonb, JsonPath) => VariadicFunc::JsonbPathQueryArray => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_2_OID;
+            params!(Jsonb, JsonPath, Jsonb) => VariadicFunc::JsonbPathQueryArray => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_3_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryArray => Jsonb, 4007;
+        },
+        "jsonb_path_query_first" => Scalar {
+            params!(Jsonb, JsonPath) => VariadicFunc::JsonbPathQueryFirst => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_2_OID;
+            params!(Jsonb, JsonPath, Jsonb) => VariadicFunc::JsonbPathQueryFirst => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_3_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryFirst => Jsonb, 4008;
+        },
         "jsonb_pretty" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbPretty(func::JsonbPretty), 3306;
         },
+        "jsonb_set" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => VariadicFunc::JsonbSet => Jsonb, oid::FUNC_JSONB_SET_3_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbSet => Jsonb, 3304;
+        },
         "jsonb_strip_nulls" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbStripNulls(func::JsonbStripNulls), 3262;
         },
@@ -2037,6 +2125,13 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         },
         "lower" => Scalar {
             params!(String) => UnaryFunc::Lower(func::Lower), 870;
+            params!(RangeAny) => UnaryFunc::RangeLower(func::RangeLower) => RangeElementAny, 3848;
+        },
+        "lower_inc" => Scalar {
+            params!(RangeAny) => UnaryFunc::RangeLowerInc(func::RangeLowerInc) => Bool, 3851;
+        },
+        "lower_inf" => Scalar {
+            params!(RangeAny) => UnaryFunc::RangeLowerInf(func::RangeLowerInf) => Bool, 3853;
         },
         "lpad" => Scalar {
             params!(String, Int64) => VariadicFunc::PadLeading, 879;
@@ -2073,6 +2168,10 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "now" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp, 1299;
         },
+        "numrange" => Scalar {
+            params!(Numeric, Numeric) => VariadicFunc::RangeCreate { elem_type: ScalarType::Numeric { max_scale: None } }, 3844;
+            params!(Numeric, Numeric, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Numeric { max_scale: None } }, 3845;
+        },
         "octet_length" => Scalar {
             params!(Bytes) => UnaryFunc::ByteLengthBytes(func::ByteLengthBytes), 720;
             params!(String) => UnaryFunc::ByteLengthString(func::ByteLengthString), 1374;
@@ -2252,6 +2351,14 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3396;
             params!(String, String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3397;
         },
+        "regexp_replace" => Scalar {
+            params!(String, String, String) => VariadicFunc::RegexpReplace => String, 2284;
+            params!(String, String, String, String) => VariadicFunc::RegexpReplace => String, 2285;
+        },
+        "regexp_split_to_array" => Scalar {
+            params!(String, String) => VariadicFunc::RegexpSplitToArray => ScalarType::Array(Box::new(ScalarType::String)), 2767;
+            params!(String, String, String) => VariadicFunc::RegexpSplitToArray => ScalarType::Array(Box::new(ScalarType::String)), 2768;
+        },
         "replace" => Scalar {
             params!(String, String, String) => VariadicFunc::Replace, 2087;
         },
@@ -2399,8 +2506,23 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(Float64) => UnaryFunc::TruncFloat64(func::TruncFloat64), 1343;
             params!(Numeric) => UnaryFunc::TruncNumeric(func::TruncNumeric), 1710;
         },
+        "tsrange" => Scalar {
+            params!(Timestamp, Timestamp) => VariadicFunc::RangeCreate { elem_type: ScalarType::Timestamp }, 3933;
+            params!(Timestamp, Timestamp, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Timestamp }, 3934;
+        },
+        "tstzrange" => Scalar {
+            params!(TimestampTz, TimestampTz) => VariadicFunc::RangeCreate { elem_type: ScalarType::TimestampTz }, 3937;
+            params!(TimestampTz, TimestampTz, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::TimestampTz }, 3938;
+        },
         "upper" => Scalar {
             params!(String) => UnaryFunc::Upper(func::Upper), 871;
+            params!(RangeAny) => UnaryFunc::RangeUpper(func::RangeUpper) => RangeElementAny, 3849;
+        },
+        "upper_inc" => Scalar {
+            params!(RangeAny) => UnaryFunc::RangeUpperInc(func::RangeUpperInc) => Bool, 3852;
+        },
+        "upper_inf" => Scalar {
+            params!(RangeAny) => UnaryFunc::RangeUpperInf(func::RangeUpperInf) => Bool, 3854;
         },
         "variance" => Scalar {
             params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => Float64, 2151;
@@ -2504,6 +2626,21 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(TimestampTz) => AggregateFunc::MinTimestampTz, 2143;
             params!(Numeric) => AggregateFunc::MinNumeric, oid::FUNC_MIN_NUMERIC_OID;
         },
+        "mode" => Aggregate {
+            params!(Any) => Operation::unary_ordered(|_ecx, e, order_by| {
+                Ok((e, AggregateFunc::Mode { order_by }))
+            }) => Any, 3984;
+        },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => Operation::binary_ordered(|_ecx, fraction, value, order_by| {
+                Ok((percentile_input(fraction, value)?, AggregateFunc::PercentileCont { order_by }))
+            }), 3974;
+        },
+        "percentile_disc" => Aggregate {
+            params!(Float64, Any) => Operation::binary_ordered(|_ecx, fraction, value, order_by| {
+                Ok((percentile_input(fraction, value)?, AggregateFunc::PercentileDisc { order_by }))
+            }) => Any, 3972;
+        },
         "json_agg" => Aggregate {
             params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("json_agg")) => Jsonb, 3175;
         },
@@ -2585,9 +2722,24 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "row_number" => ScalarWindow {
             params!() => ScalarWindowFunc::RowNumber, 3100;
         },
+        "rank" => ScalarWindow {
+            params!() => ScalarWindowFunc::Rank, 3101;
+        },
         "dense_rank" => ScalarWindow {
             params!() => ScalarWindowFunc::DenseRank, 3102;
         },
+        "percent_rank" => ScalarWindow {
+            params!() => ScalarWindowFunc::PercentRank, 3103;
+        },
+        "cume_dist" => ScalarWindow {
+            params!() => ScalarWindowFunc::CumeDist, 3104;
+        },
+        "ntile" => ValueWindow {
+            params!(Int32) => Operation::unary(|_ecx, num_buckets| {
+                let num_buckets = error_if_not_positive(num_buckets, "argument of ntile must be greater than zero");
+                Ok((num_buckets, ValueWindowFunc::Ntile))
+            }) => Int32, 3105;
+        },
         "lag" => ValueWindow {
             // All args are encoded into a single record to be handled later
             params!(Any) => Operation::unary(|ecx, e| {
@@ -2658,6 +2810,19 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "last_value" => ValueWindow {
             params!(Any) => ValueWindowFunc::LastValue => Any, 3113;
         },
+        "nth_value" => ValueWindow {
+            // All args are encoded into a single record to be handled later
+            params!(Any, Int32) => Operation::binary(|_ecx, e, n| {
+                let n = error_if_not_positive(n, "argument of nth_value must be greater than zero");
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("expr"), ColumnName::from("n")]
+                    },
+                    exprs: vec![e, n],
+                };
+                Ok((e, ValueWindowFunc::NthValue))
+            }) => Any, 3114;
+        },
 
         // Table functions.
         "generate_series" => Table {
@@ -2786,6 +2951,33 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 })
             }), 3931;
         },
+        "jsonb_path_query" => Table {
+            params!(Jsonb, JsonPath) => Operation::variadic(move |_ecx, exprs| {
+                plan_jsonb_path_query(exprs)
+            }) => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_2_OID;
+            params!(Jsonb, JsonPath, Jsonb) => Operation::variadic(move |_ecx, exprs| {
+                plan_jsonb_path_query(exprs)
+            }) => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_3_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => Operation::variadic(move |_ecx, exprs| {
+                plan_jsonb_path_query(exprs)
+            }) => ReturnType::set_of(Jsonb.into()), 4006;
+        },
+        "regexp_matches" => Table {
+            params!(String, String) => Operation::variadic(move |_ecx, exprs| {
+                plan_regexp_matches(exprs)
+            }) => ReturnType::set_of(ScalarType::Array(Box::new(ScalarType::String)).into()), 2763;
+            params!(String, String, String) => Operation::variadic(move |_ecx, exprs| {
+                plan_regexp_matches(exprs)
+            }) => ReturnType::set_of(ScalarType::Array(Box::new(ScalarType::String)).into()), 2764;
+        },
+        "regexp_split_to_table" => Table {
+            params!(String, String) => Operation::variadic(move |_ecx, exprs| {
+                plan_regexp_split_to_table(exprs)
+            }) => ReturnType::set_of(String.into()), 2765;
+            params!(String, String, String) => Operation::variadic(move |_ecx, exprs| {
+                plan_regexp_split_to_table(exprs)
+            }) => ReturnType::set_of(String.into()), 2766;
+        },
         // Note that these implementations' input to `generate_series` is
         // contrived to match Flink's expected values. There are other,
         // equally valid windows we could generate.
@@ -2850,6 +3042,9 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
     use ParamType::*;
     use ScalarType::*;
     builtins! {
+        "approx_count_distinct" => Aggregate {
+            params!(Any) => AggregateFunc::ApproxCountDistinct => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+        },
         "csv_extract" => Table {
             params!(Int64, String) => Operation::binary(move |_ecx, ncols, input| {
                 let ncols = match ncols.into_literal_int64() {
@@ -3075,6 +3270,78 @@ fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     })
 }
 
+/// Wraps the `Int32` expression `n` so that it raises an error with the given
+/// message when evaluated to zero or a negative number.
+fn error_if_not_positive(n: HirScalarExpr, message: &str) -> HirScalarExpr {
+    let error = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::ErrorIfNull,
+        exprs: vec![
+            HirScalarExpr::literal_null(ScalarType::Int32),
+            HirScalarExpr::literal(Datum::String(message), ScalarType::String),
+        ],
+    };
+    HirScalarExpr::If {
+        cond: Box::new(n.clone().call_binary(
+            HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32),
+            BinaryFunc::Lte,
+        )),
+        then: Box::new(error),
+        els: Box::new(n),
+    }
+}
+
+/// Builds the `(value, fraction)` record aggregated by the percentile
+/// functions, raising an error if the fraction is not between 0 and 1.
+fn percentile_input(
+    fraction: HirScalarExpr,
+    value: HirScalarExpr,
+) -> Result<HirScalarExpr, PlanError> {
+    // PostgreSQL evaluates the fraction once per group, while we evaluate it
+    // for every input row and assume that it doesn't vary within a group.
+    let mut refers_to_input = false;
+    #[allow(deprecated)]
+    fraction.visit_columns(0, &mut |depth, col| {
+        if col.level == depth {
+            refers_to_input = true;
+        }
+    });
+    if refers_to_input {
+        bail_unsupported!("percentile fractions that refer to input columns");
+    }
+
+    let error = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::ErrorIfNull,
+        exprs: vec![
+            HirScalarExpr::literal_null(ScalarType::Float64),
+            HirScalarExpr::literal(
+                Datum::String("percentile value is not between 0 and 1"),
+                ScalarType::String,
+            ),
+        ],
+    };
+    let out_of_range = fraction
+        .clone()
+        .call_binary(
+            HirScalarExpr::literal(Datum::Float64(0.0.into()), ScalarType::Float64),
+            BinaryFunc::Lt,
+        )
+        .or(fraction.clone().call_binary(
+            HirScalarExpr::literal(Datum::Float64(1.0.into()), ScalarType::Float64),
+            BinaryFunc::Gt,
+        ));
+    let fraction = HirScalarExpr::If {
+        cond: Box::new(out_of_range),
+        then: Box::new(error),
+        els: Box::new(fraction),
+    };
+    Ok(HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec![ColumnName::from("value"), ColumnName::from("fraction")],
+        },
+        exprs: vec![value, fraction],
+    })
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
@@ -3089,6 +3356,116 @@ fn array_to_string(
     })
 }
 
+/// Plans `jsonb_path_query`, parsing the path up front if it is a literal
+/// rather than once per row. Invalid literal paths are left to fail during
+/// evaluation, as they would if the path were not a literal.
+fn plan_jsonb_path_query(mut exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, PlanError> {
+    let path = exprs[1]
+        .clone()
+        .into_literal_string()
+        .and_then(|path| strconv::parse_jsonpath(&path).ok());
+    let func = match path {
+        Some(path) => {
+            exprs.remove(1);
+            TableFunc::JsonbPathQueryCompiled { path }
+        }
+        None => TableFunc::JsonbPathQuery,
+    };
+    Ok(TableFuncPlan {
+        expr: HirRelationExpr::CallTable { func, exprs },
+        column_names: vec!["jsonb_path_query".into()],
+    })
+}
+
+/// Plans the `@?` and `@@` operators, which behave like `jsonb_path_exists`
+/// and `jsonb_path_match` with no variables and with errors suppressed.
+fn plan_jsonb_path_operator(
+    func: VariadicFunc,
+    jsonb: HirScalarExpr,
+    path: HirScalarExpr,
+) -> HirScalarExpr {
+    let vars = HirScalarExpr::literal(Datum::Map(DatumMap::empty()), ScalarType::Jsonb);
+    HirScalarExpr::CallVariadic {
+        func,
+        exprs: vec![jsonb, path, vars, HirScalarExpr::literal_true()],
+    }
+}
+
+/// Plans a regex table function, whose pattern and optional flags arguments
+/// must be string literals.
+///
+/// Returns the haystack expression, the compiled regex, and whether the `g`
+/// flag was specified, or `None` if the pattern or flags are a literal `NULL`,
+/// in which case the function, being strict, produces no rows.
+fn plan_regex_table_func_args(
+    name: &str,
+    mut exprs: Vec<HirScalarExpr>,
+) -> Result<Option<(HirScalarExpr, Regex, bool)>, PlanError> {
+    let flags = match exprs.len() {
+        3 => match exprs.pop().unwrap().into_literal_nullable_string() {
+            None => sql_bail!("{} requires a string literal as its flags argument", name),
+            Some(None) => return Ok(None),
+            Some(Some(flags)) => flags,
+        },
+        _ => String::new(),
+    };
+    let needle = match exprs.pop().unwrap().into_literal_nullable_string() {
+        None => sql_bail!("{} requires a string literal as its pattern argument", name),
+        Some(None) => return Ok(None),
+        Some(Some(needle)) => needle,
+    };
+    let haystack = exprs.pop().unwrap();
+    let (flags, global) = func::split_global_regex_flag(&flags);
+    let regex = func::build_regex(&needle, &flags).map_err(|e| sql_err!("{}", e))?;
+    Ok(Some((haystack, Regex(regex), global)))
+}
+
+fn plan_regexp_matches(exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, PlanError> {
+    let column_names = vec!["regexp_matches".into()];
+    let (haystack, regex, global) = match plan_regex_table_func_args("regexp_matches", exprs)? {
+        Some(args) => args,
+        None => {
+            let typ = ScalarType::Array(Box::new(ScalarType::String)).nullable(false);
+            return Ok(TableFuncPlan {
+                expr: HirRelationExpr::constant(vec![], RelationType::new(vec![typ])),
+                column_names,
+            });
+        }
+    };
+    Ok(TableFuncPlan {
+        expr: HirRelationExpr::CallTable {
+            func: TableFunc::RegexpMatches { regex, global },
+            exprs: vec![haystack],
+        },
+        column_names,
+    })
+}
+
+fn plan_regexp_split_to_table(exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, PlanError> {
+    let column_names = vec!["regexp_split_to_table".into()];
+    let (haystack, regex, global) =
+        match plan_regex_table_func_args("regexp_split_to_table", exprs)? {
+            Some(args) => args,
+            None => {
+                let typ = ScalarType::String.nullable(false);
+                return Ok(TableFuncPlan {
+                    expr: HirRelationExpr::constant(vec![], RelationType::new(vec![typ])),
+                    column_names,
+                });
+            }
+        };
+    if global {
+        sql_bail!("regexp_split_to_table does not support the \"global\" option");
+    }
+    Ok(TableFuncPlan {
+        expr: HirRelationExpr::CallTable {
+            func: TableFunc::RegexpSplitToTable(regex),
+            exprs: vec![haystack],
+        },
+        column_names,
+    })
+}
+
 /// Correlates an operator with all of its implementations.
 static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
     use BinaryFunc::*;
@@ -3438,6 +3815,8 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
                       .call_binary(rhs, JsonbContainsJsonb))
             }), oid::OP_CONTAINS_STRING_JSONB_OID;
             params!(MapAnyCompatible, MapAnyCompatible) => MapContainsMap => Bool, oid::OP_CONTAINS_MAP_MAP_OID;
+            params!(RangeAny, RangeElementAny) => RangeContainsElem { rev: false } => Bool, 3858;
+            params!(RangeAny, RangeAny) => RangeContainsRange { rev: false } => Bool, 3859;
         },
         "<@" => Scalar {
             params!(Jsonb, Jsonb) => Operation::binary(|_ecx, lhs, rhs| {
@@ -3459,6 +3838,24 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(MapAnyCompatible, MapAnyCompatible) => Operation::binary(|_ecx, lhs, rhs| {
                 Ok(rhs.call_binary(lhs, MapContainsMap))
             }) => Bool, oid::OP_CONTAINED_MAP_MAP_OID;
+            params!(RangeElementAny, RangeAny) => RangeContainsElem { rev: true } => Bool, 3860;
+            params!(RangeAny, RangeAny) => RangeContainsRange { rev: true } => Bool, 3861;
+        },
+        "&&" => Scalar {
+            params!(RangeAny, RangeAny) => RangeOverlaps => Bool, 3857;
+        },
+        "-|-" => Scalar {
+            params!(RangeAny, RangeAny) => RangeAdjacent => Bool, 3862;
+        },
+        "@?" => Scalar {
+            params!(Jsonb, JsonPath) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(plan_jsonb_path_operator(VariadicFunc::JsonbPathExists, lhs, rhs))
+            }) => Bool, 4012;
+        },
+        "@@" => Scalar {
+            params!(Jsonb, JsonPath) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(plan_jsonb_path_operator(VariadicFunc::JsonbPathMatch, lhs, rhs))
+            }) => Bool, 4013;
         },
         "?" => Scalar {
             params!(Jsonb, String) => JsonbContainsString, 3247;
@@ -3496,6 +3893,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Jsonb, Jsonb) => BinaryFunc::Lt, 3242;
             params!(ArrayAny, ArrayAny) => BinaryFunc::Lt => Bool, 1072;
             params!(RecordAny, RecordAny) => BinaryFunc::Lt => Bool, 2990;
+            params!(RangeAny, RangeAny) => BinaryFunc::Lt => Bool, 3871;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lt =>Bool, oid::FUNC_MZ_TIMESTAMP_LT_MZ_TIMESTAMP_OID;
         },
         "<=" => Scalar {
@@ -3523,6 +3921,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Jsonb, Jsonb) => BinaryFunc::Lte, 3244;
             params!(ArrayAny, ArrayAny) => BinaryFunc::Lte => Bool, 1074;
             params!(RecordAny, RecordAny) => BinaryFunc::Lte => Bool, 2992;
+            params!(RangeAny, RangeAny) => BinaryFunc::Lte => Bool, 3872;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lte =>Bool, oid::FUNC_MZ_TIMESTAMP_LTE_MZ_TIMESTAMP_OID;
         },
         ">" => Scalar {
@@ -3550,6 +3949,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Jsonb, Jsonb) => BinaryFunc::Gt, 3243;
             params!(ArrayAny, ArrayAny) => BinaryFunc::Gt => Bool, 1073;
             params!(RecordAny, RecordAny) => BinaryFunc::Gt => Bool, 2991;
+            params!(RangeAny, RangeAny) => BinaryFunc::Gt => Bool, 3874;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gt =>Bool, oid::FUNC_MZ_TIMESTAMP_GT_MZ_TIMESTAMP_OID;
         },
         ">=" => Scalar {
@@ -3577,6 +3977,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Jsonb, Jsonb) => BinaryFunc::Gte, 3245;
             params!(ArrayAny, ArrayAny) => BinaryFunc::Gte => Bool, 1075;
             params!(RecordAny, RecordAny) => BinaryFunc::Gte => Bool, 2993;
+            params!(RangeAny, RangeAny) => BinaryFunc::Gte => Bool, 3873;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gte =>Bool, oid::FUNC_MZ_TIMESTAMP_GTE_MZ_TIMESTAMP_OID;
         },
         // Warning!
@@ -3614,6 +4015,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(ListAny, ListAny) => BinaryFunc::Eq => Bool, oid::FUNC_LIST_EQ_OID;
             params!(ArrayAny, ArrayAny) => BinaryFunc::Eq => Bool, 1070;
             params!(RecordAny, RecordAny) => BinaryFunc::Eq => Bool, 2988;
+            params!(RangeAny, RangeAny) => BinaryFunc::Eq => Bool, 3855;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::Eq => Bool, oid::FUNC_MZ_TIMESTAMP_EQ_MZ_TIMESTAMP_OID;
         },
         "<>" => Scalar {
@@ -3641,6 +4043,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Jsonb, Jsonb) => BinaryFunc::NotEq, 3241;
             params!(ArrayAny, ArrayAny) => BinaryFunc::NotEq => Bool, 1071;
             params!(RecordAny, RecordAny) => BinaryFunc::NotEq => Bool, 2989;
+            params!(RangeAny, RangeAny) => BinaryFunc::NotEq => Bool, 3856;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::NotEq=>Bool, oid::FUNC_MZ_TIMESTAMP_NOT_EQ_MZ_TIMESTAMP_OID;
         }
     }
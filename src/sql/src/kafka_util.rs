@@ -53,6 +53,7 @@ pub fn validate_options_for_context<T: AstInfo>(
             IsolationLevel => None,
             Topic => None,
             TopicMetadataRefreshIntervalMs => None,
+            TopicPattern => Some(Source),
             TransactionTimeoutMs => None,
             StartTimestamp => Some(Source),
             StartOffset => Some(Source),
@@ -90,6 +91,7 @@ generate_extracted_config!(
     ),
     (Topic, String),
     (TopicMetadataRefreshIntervalMs, i32),
+    (TopicPattern, String),
     (TransactionTimeoutMs, i32),
     (StartTimestamp, i64),
     (StartOffset, Vec<i64>),
@@ -210,7 +212,7 @@ impl TryFrom<&KafkaConfigOptionExtracted> for Option<KafkaStartOffsetType> {
 pub async fn create_consumer(
     connection_context: &ConnectionContext,
     kafka_connection: &KafkaConnection,
-    topic: &str,
+    topic: Option<&str>,
 ) -> Result<Arc<BaseConsumer<BrokerRewritingClientContext<KafkaErrCheckContext>>>, PlanError> {
     let consumer: BaseConsumer<_> = kafka_connection
         .create_with_context(
@@ -223,18 +225,22 @@ pub async fn create_consumer(
     let consumer = Arc::new(consumer);
 
     let context = Arc::clone(consumer.context());
-    let owned_topic = String::from(topic);
+    let owned_topic = topic.map(String::from);
     // Wait for a metadata request for up to one second. This greatly
     // increases the probability that we'll see a connection error if
     // e.g. the hostname was mistyped. librdkafka doesn't expose a
     // better API for asking whether a connection succeeded or failed,
-    // unfortunately.
-    task::spawn_blocking(move || format!("kafka_get_metadata:{topic}"), {
-        let consumer = Arc::clone(&consumer);
-        move || {
-            let _ = consumer.fetch_metadata(Some(&owned_topic), Duration::from_secs(1));
-        }
-    })
+    // unfortunately. Without a topic, the metadata of all topics is
+    // requested.
+    task::spawn_blocking(
+        move || format!("kafka_get_metadata:{}", topic.unwrap_or("*")),
+        {
+            let consumer = Arc::clone(&consumer);
+            move || {
+                let _ = consumer.fetch_metadata(owned_topic.as_deref(), Duration::from_secs(1));
+            }
+        },
+    )
     .await
     .map_err(|e| sql_err!("{}", e))?;
     let error = context.inner().error.lock().expect("lock poisoned");
@@ -107,3 +107,4 @@ pub mod parse;
 pub mod plan;
 pub mod pure;
 pub mod query_model;
+pub mod s3_util;
@@ -659,6 +659,8 @@ impl FromStr for DatabaseId {
 pub enum RoleId {
     System(u64),
     User(u64),
+    /// The pseudo-role `PUBLIC`, of which every role is implicitly a member.
+    Public,
 }
 
 impl RoleId {
@@ -675,6 +677,9 @@ impl FromStr for RoleId {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "p" {
+            return Ok(Self::Public);
+        }
         if s.len() < 2 {
             return Err(anyhow!("couldn't parse role id {}", s));
         }
@@ -692,6 +697,7 @@ impl fmt::Display for RoleId {
         match self {
             Self::System(id) => write!(f, "s{}", id),
             Self::User(id) => write!(f, "u{}", id),
+            Self::Public => write!(f, "p"),
         }
     }
 }
@@ -822,15 +828,24 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             used_names.insert(cte_name.clone());
 
             let id = LocalId::new(self.ctes.len() as u64);
+            // In a `WITH RECURSIVE` block, each CTE is in scope within its
+            // own definition.
+            if q.ctes_recursive {
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name.clone(), old_val));
+            }
             ctes.push(Cte {
                 alias: cte.alias,
                 id,
                 query: self.fold_query(cte.query),
             });
-            let old_val = self.ctes.insert(cte_name.clone(), id);
-            old_cte_values.push((cte_name, old_val));
+            if !q.ctes_recursive {
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name, old_val));
+            }
         }
         let result = Query {
+            ctes_recursive: q.ctes_recursive,
             ctes,
             body: self.fold_set_expr(q.body),
             limit: q.limit.map(|l| self.fold_limit(l)),
@@ -31,6 +31,7 @@
 #![allow(clippy::extra_unused_lifetimes)]
 
 use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
@@ -42,6 +43,7 @@ use mz_compute_client::controller::ComputeInstanceId;
 use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
+use mz_repr::adt::datetime::Timezone;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
 use mz_storage_client::types::sinks::{SinkEnvelope, StorageSinkConnectionBuilder};
@@ -51,9 +53,10 @@ use crate::ast::{
     ExplainStage, Expr, FetchDirection, IndexOptionName, NoticeSeverity, ObjectType, Raw,
     SetVariableValue, Statement, StatementKind, TransactionAccessMode,
 };
-use crate::catalog::{CatalogType, IdReference};
+use crate::catalog::{CatalogType, IdReference, Privilege, PrivilegeObject};
 use crate::names::{
-    Aug, DatabaseId, FullObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier, SchemaId,
+    Aug, DatabaseId, FullObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId,
+    SchemaId,
 };
 
 pub(crate) mod error;
@@ -140,6 +143,10 @@ pub enum Plan {
     Deallocate(DeallocatePlan),
     Raise(RaisePlan),
     RotateKeys(RotateKeysPlan),
+    GrantPrivileges(GrantPrivilegesPlan),
+    RevokePrivileges(RevokePrivilegesPlan),
+    GrantRole(GrantRolePlan),
+    RevokeRole(RevokeRolePlan),
 }
 
 impl Plan {
@@ -203,10 +210,14 @@ impl Plan {
             StatementKind::Execute => vec![PlanKind::Execute],
             StatementKind::Explain => vec![PlanKind::Explain],
             StatementKind::Fetch => vec![PlanKind::Fetch],
+            StatementKind::GrantPrivileges => vec![PlanKind::GrantPrivileges],
+            StatementKind::GrantRole => vec![PlanKind::GrantRole],
             StatementKind::Insert => vec![PlanKind::Insert],
             StatementKind::Prepare => vec![PlanKind::Prepare],
             StatementKind::Raise => vec![PlanKind::Raise],
             StatementKind::ResetVariable => vec![PlanKind::ResetVariable],
+            StatementKind::RevokePrivileges => vec![PlanKind::RevokePrivileges],
+            StatementKind::RevokeRole => vec![PlanKind::RevokeRole],
             StatementKind::Rollback => vec![PlanKind::AbortTransaction],
             StatementKind::Select => vec![PlanKind::Peek],
             StatementKind::SetTransaction => vec![],
@@ -243,9 +254,27 @@ pub struct CreateSchemaPlan {
     pub if_not_exists: bool,
 }
 
-#[derive(Debug)]
 pub struct CreateRolePlan {
     pub name: String,
+    /// The role's password, if any. Only a verifier for the password is
+    /// stored in the catalog.
+    pub password: Option<String>,
+    /// Whether the role bypasses all privilege checks.
+    pub super_user: bool,
+    /// Whether the role may be used to log in.
+    pub login: bool,
+}
+
+impl fmt::Debug for CreateRolePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Plans are logged, so never print the plaintext password.
+        f.debug_struct("CreateRolePlan")
+            .field("name", &self.name)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .field("super_user", &self.super_user)
+            .field("login", &self.login)
+            .finish()
+    }
 }
 
 #[derive(Debug)]
@@ -410,6 +439,32 @@ pub struct DropRolesPlan {
     pub names: Vec<String>,
 }
 
+#[derive(Debug)]
+pub struct GrantPrivilegesPlan {
+    pub privileges: Vec<Privilege>,
+    pub objects: Vec<PrivilegeObject>,
+    pub grantees: Vec<RoleId>,
+}
+
+#[derive(Debug)]
+pub struct RevokePrivilegesPlan {
+    pub privileges: Vec<Privilege>,
+    pub objects: Vec<PrivilegeObject>,
+    pub grantees: Vec<RoleId>,
+}
+
+#[derive(Debug)]
+pub struct GrantRolePlan {
+    pub role_ids: Vec<RoleId>,
+    pub member_ids: Vec<RoleId>,
+}
+
+#[derive(Debug)]
+pub struct RevokeRolePlan {
+    pub role_ids: Vec<RoleId>,
+    pub member_ids: Vec<RoleId>,
+}
+
 #[derive(Debug)]
 pub struct DropComputeInstancesPlan {
     pub names: Vec<String>,
@@ -669,7 +724,7 @@ pub struct Sink {
     pub create_sql: String,
     pub from: GlobalId,
     pub connection_builder: StorageSinkConnectionBuilder,
-    pub envelope: SinkEnvelope,
+    pub envelope: Option<SinkEnvelope>,
 }
 
 #[derive(Clone, Debug)]
@@ -795,17 +850,21 @@ impl Params {
 }
 
 /// Controls planning of a SQL query.
-#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, Copy)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, Copy)]
 pub struct PlanContext {
     pub wall_time: DateTime<Utc>,
     pub qgm_optimizations: bool,
+    /// The session's time zone, in which conversions between timestamps with
+    /// and without time zones take place.
+    pub timezone: Timezone,
 }
 
 impl PlanContext {
-    pub fn new(wall_time: DateTime<Utc>, qgm_optimizations: bool) -> Self {
+    pub fn new(wall_time: DateTime<Utc>, qgm_optimizations: bool, timezone: Timezone) -> Self {
         Self {
             wall_time,
             qgm_optimizations,
+            timezone,
         }
     }
 
@@ -816,6 +875,7 @@ impl PlanContext {
         PlanContext {
             wall_time: now::to_datetime(NOW_ZERO()),
             qgm_optimizations: false,
+            timezone: Timezone::default(),
         }
     }
 }
@@ -171,6 +171,25 @@ impl<'a> Explanation<'a> {
                         .chain_local_ids
                         .insert(value_chain, (name.clone(), *id));
 
+                    walk(body, explanation, id_gen);
+                }
+                LetRec { bindings, body } => {
+                    // Each recursive definition goes in its own chain. References
+                    // to bindings that are not yet walked are printed without
+                    // their chain ID.
+                    for (name, id, value, _) in bindings {
+                        walk(value, explanation, id_gen);
+                        explanation.chain = id_gen.allocate_id();
+
+                        let value_chain = explanation.expr_chain(value);
+                        explanation
+                            .local_id_chains
+                            .insert(*id, (name.clone(), value_chain));
+                        explanation
+                            .chain_local_ids
+                            .insert(value_chain, (name.clone(), *id));
+                    }
+
                     walk(body, explanation, id_gen);
                 }
             }
@@ -181,6 +200,7 @@ impl<'a> Explanation<'a> {
                 Constant { .. }
                 | Get { .. }
                 | Let { .. }
+                | LetRec { .. }
                 | Project { .. }
                 | Distinct { .. }
                 | Negate { .. }
@@ -237,14 +257,16 @@ impl<'a> Explanation<'a> {
         {
             for expr in exprs {
                 // Elide chains that would consist only a of single Get node.
-                if let HirRelationExpr::Get {
-                    id: Id::Local(id), ..
-                } = expr
-                {
-                    explanation.expr_chains.insert(
-                        expr as *const HirRelationExpr,
-                        explanation.local_id_chains[id].1,
-                    );
+                let local_chain = match expr {
+                    HirRelationExpr::Get {
+                        id: Id::Local(id), ..
+                    } => explanation.local_id_chains.get(id).map(|(_, chain)| *chain),
+                    _ => None,
+                };
+                if let Some(chain) = local_chain {
+                    explanation
+                        .expr_chains
+                        .insert(expr as *const HirRelationExpr, chain);
                 } else {
                     walk(expr, explanation, id_gen);
                     explanation.chain = id_gen.allocate_id();
@@ -297,7 +319,7 @@ impl<'a> Explanation<'a> {
 
         match node.expr {
             // Lets are annotated on the chain ID that they correspond to.
-            Let { .. } => (),
+            Let { .. } | LetRec { .. } => (),
             Constant { rows, .. } => {
                 write!(f, "| Constant")?;
                 for row in rows {
@@ -545,6 +567,9 @@ impl<'a> Explanation<'a> {
                         self.fmt_scalar_expr(f, &scalar.expr)?;
                         write!(f, ")")?
                     }
+                    WindowExprType::Aggregate(aggregate) => {
+                        self.fmt_aggregate_expr(f, &aggregate.aggregate_expr)?
+                    }
                 }
                 write!(f, " over (")?;
                 for (i, e) in expr.partition.iter().enumerate() {
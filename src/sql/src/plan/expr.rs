@@ -114,6 +114,15 @@ pub enum HirRelationExpr {
         /// The result of the `Let`, evaluated with `name` bound to `value`.
         body: Box<HirRelationExpr>,
     },
+    /// Recursive CTEs
+    LetRec {
+        /// Bindings of names and identifiers to collections and their types.
+        /// Each collection may refer to any of the identifiers bound here,
+        /// including its own.
+        bindings: Vec<(String, mz_expr::LocalId, HirRelationExpr, RelationType)>,
+        /// The result of the `LetRec`, evaluated with all `bindings` in scope.
+        body: Box<HirRelationExpr>,
+    },
     Project {
         input: Box<HirRelationExpr>,
         outputs: Vec<usize>,
@@ -336,6 +345,7 @@ impl VisitChildren<HirScalarExpr> for WindowExpr {
 pub enum WindowExprType {
     Scalar(ScalarWindowExpr),
     Value(ValueWindowExpr),
+    Aggregate(AggregateWindowExpr),
 }
 
 impl WindowExprType {
@@ -348,6 +358,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions(f),
             Self::Value(expr) => expr.visit_expressions(f),
+            Self::Aggregate(expr) => expr.visit_expressions(f),
         }
     }
 
@@ -360,6 +371,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions_mut(f),
             Self::Value(expr) => expr.visit_expressions_mut(f),
+            Self::Aggregate(expr) => expr.visit_expressions_mut(f),
         }
     }
 
@@ -372,6 +384,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.typ(outers, inner, params),
             Self::Value(expr) => expr.typ(outers, inner, params),
+            Self::Aggregate(expr) => expr.typ(outers, inner, params),
         }
     }
 }
@@ -384,6 +397,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => (),
             Self::Value(expr) => expr.visit_children(f),
+            Self::Aggregate(expr) => expr.visit_children(f),
         }
     }
 
@@ -394,6 +408,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => (),
             Self::Value(expr) => expr.visit_mut_children(f),
+            Self::Aggregate(expr) => expr.visit_mut_children(f),
         }
     }
 
@@ -405,6 +420,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => Ok(()),
             Self::Value(expr) => expr.try_visit_children(f),
+            Self::Aggregate(expr) => expr.try_visit_children(f),
         }
     }
 
@@ -416,6 +432,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => Ok(()),
             Self::Value(expr) => expr.try_visit_mut_children(f),
+            Self::Aggregate(expr) => expr.try_visit_mut_children(f),
         }
     }
 }
@@ -434,7 +451,10 @@ impl ScalarWindowExpr {
     {
         match self.func {
             ScalarWindowFunc::RowNumber => {}
+            ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -446,7 +466,10 @@ impl ScalarWindowExpr {
     {
         match self.func {
             ScalarWindowFunc::RowNumber => {}
+            ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -465,9 +488,18 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::RowNumber => mz_expr::AggregateFunc::RowNumber {
                 order_by: self.order_by,
             },
+            ScalarWindowFunc::Rank => mz_expr::AggregateFunc::Rank {
+                order_by: self.order_by,
+            },
             ScalarWindowFunc::DenseRank => mz_expr::AggregateFunc::DenseRank {
                 order_by: self.order_by,
             },
+            ScalarWindowFunc::PercentRank => mz_expr::AggregateFunc::PercentRank {
+                order_by: self.order_by,
+            },
+            ScalarWindowFunc::CumeDist => mz_expr::AggregateFunc::CumeDist {
+                order_by: self.order_by,
+            },
         }
     }
 }
@@ -476,14 +508,20 @@ impl ScalarWindowExpr {
 /// Scalar Window functions
 pub enum ScalarWindowFunc {
     RowNumber,
+    Rank,
     DenseRank,
+    PercentRank,
+    CumeDist,
 }
 
 impl ScalarWindowFunc {
     pub fn output_type(&self) -> ColumnType {
         match self {
             ScalarWindowFunc::RowNumber => ScalarType::Int64.nullable(false),
+            ScalarWindowFunc::Rank => ScalarType::Int64.nullable(false),
             ScalarWindowFunc::DenseRank => ScalarType::Int64.nullable(false),
+            ScalarWindowFunc::PercentRank => ScalarType::Float64.nullable(false),
+            ScalarWindowFunc::CumeDist => ScalarType::Float64.nullable(false),
         }
     }
 }
@@ -541,6 +579,13 @@ impl ValueWindowExpr {
                 order_by: self.order_by,
                 window_frame: self.window_frame,
             },
+            ValueWindowFunc::NthValue => mz_expr::AggregateFunc::NthValue {
+                order_by: self.order_by,
+                window_frame: self.window_frame,
+            },
+            ValueWindowFunc::Ntile => mz_expr::AggregateFunc::Ntile {
+                order_by: self.order_by,
+            },
         }
     }
 }
@@ -584,6 +629,8 @@ pub enum ValueWindowFunc {
     Lead,
     FirstValue,
     LastValue,
+    NthValue,
+    Ntile,
 }
 
 impl ValueWindowFunc {
@@ -598,10 +645,93 @@ impl ValueWindowFunc {
             ValueWindowFunc::FirstValue | ValueWindowFunc::LastValue => {
                 input_type.scalar_type.nullable(true)
             }
+            ValueWindowFunc::NthValue => {
+                // The input is a (value, n) record, so extract the type of the first arg
+                input_type.scalar_type.unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(true)
+            }
+            // The input is the number of buckets, which determines the output type
+            ValueWindowFunc::Ntile => input_type.scalar_type.nullable(true),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// An aggregate function evaluated over the window frame of each row.
+pub struct AggregateWindowExpr {
+    pub aggregate_expr: AggregateExpr,
+    pub order_by: Vec<ColumnOrder>,
+    pub window_frame: WindowFrame,
+}
+
+impl AggregateWindowExpr {
+    #[deprecated = "Use `VisitChildren<HirScalarExpr>::visit_children` instead."]
+    pub fn visit_expressions<'a, F, E>(&'a self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a HirScalarExpr) -> Result<(), E>,
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    #[deprecated = "Use `VisitChildren<HirScalarExpr>::visit_mut_children` instead."]
+    pub fn visit_expressions_mut<'a, F, E>(&'a mut self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a mut HirScalarExpr) -> Result<(), E>,
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+
+    fn typ(
+        &self,
+        outers: &[RelationType],
+        inner: &RelationType,
+        params: &BTreeMap<usize, ScalarType>,
+    ) -> ColumnType {
+        self.aggregate_expr.typ(outers, inner, params)
+    }
+
+    pub fn into_expr(self) -> mz_expr::AggregateFunc {
+        mz_expr::AggregateFunc::WindowAggregate {
+            wrapped_aggregate: Box::new(self.aggregate_expr.func.into_expr()),
+            order_by: self.order_by,
+            window_frame: self.window_frame,
+        }
+    }
+}
+
+impl VisitChildren<HirScalarExpr> for AggregateWindowExpr {
+    fn visit_children<F>(&self, mut f: F)
+    where
+        F: FnMut(&HirScalarExpr),
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    fn visit_mut_children<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut HirScalarExpr),
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+
+    fn try_visit_children<F, E>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&HirScalarExpr) -> Result<(), E>,
+        E: From<RecursionLimitError>,
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    fn try_visit_mut_children<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut HirScalarExpr) -> Result<(), E>,
+        E: From<RecursionLimitError>,
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+}
+
 /// A `CoercibleScalarExpr` is a [`HirScalarExpr`] whose type is not fully
 /// determined. Several SQL expressions can be freely coerced based upon where
 /// in the expression tree they appear. For example, the string literal '42'
@@ -856,6 +986,7 @@ pub enum AggregateFunc {
     SumFloat64,
     SumNumeric,
     Count,
+    ApproxCountDistinct,
     Any,
     All,
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
@@ -885,6 +1016,15 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    Mode {
+        order_by: Vec<ColumnOrder>,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -936,6 +1076,7 @@ impl AggregateFunc {
             AggregateFunc::SumFloat64 => mz_expr::AggregateFunc::SumFloat64,
             AggregateFunc::SumNumeric => mz_expr::AggregateFunc::SumNumeric,
             AggregateFunc::Count => mz_expr::AggregateFunc::Count,
+            AggregateFunc::ApproxCountDistinct => mz_expr::AggregateFunc::ApproxCountDistinct,
             AggregateFunc::Any => mz_expr::AggregateFunc::Any,
             AggregateFunc::All => mz_expr::AggregateFunc::All,
             AggregateFunc::JsonbAgg { order_by } => mz_expr::AggregateFunc::JsonbAgg { order_by },
@@ -949,6 +1090,13 @@ impl AggregateFunc {
                 mz_expr::AggregateFunc::ListConcat { order_by }
             }
             AggregateFunc::StringAgg { order_by } => mz_expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::PercentileCont { order_by } => {
+                mz_expr::AggregateFunc::PercentileCont { order_by }
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                mz_expr::AggregateFunc::PercentileDisc { order_by }
+            }
+            AggregateFunc::Mode { order_by } => mz_expr::AggregateFunc::Mode { order_by },
             AggregateFunc::Dummy => mz_expr::AggregateFunc::Dummy,
         }
     }
@@ -973,12 +1121,27 @@ impl AggregateFunc {
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } => ScalarType::Float64,
+            AggregateFunc::PercentileDisc { .. } => match input_type.scalar_type {
+                // The input is wrapped in a Record with the ORDER BY, and the value is the first
+                // field of the inner (value, fraction) Record.
+                ScalarType::Record { fields, .. } => match &fields[0].1.scalar_type {
+                    ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            AggregateFunc::Mode { .. } => match input_type.scalar_type {
+                // The input is wrapped in a Record with the ORDER BY, so extract it out.
+                ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                _ => unreachable!(),
+            },
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
@@ -997,7 +1160,10 @@ impl AggregateFunc {
             _ => input_type.scalar_type,
         };
         // max/min/sum return null on empty sets
-        let nullable = !matches!(self, AggregateFunc::Count);
+        let nullable = !matches!(
+            self,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct
+        );
         scalar_type.nullable(nullable)
     }
 
@@ -1010,6 +1176,19 @@ impl AggregateFunc {
                 | ArrayConcat { .. }
                 | ListConcat { .. }
                 | StringAgg { .. }
+                | PercentileCont { .. }
+                | PercentileDisc { .. }
+                | Mode { .. }
+        )
+    }
+
+    /// Reports whether this is an ordered-set aggregate, which must be called
+    /// with a `WITHIN GROUP (ORDER BY ...)` clause.
+    pub fn is_ordered_set(&self) -> bool {
+        use AggregateFunc::*;
+        matches!(
+            self,
+            PercentileCont { .. } | PercentileDisc { .. } | Mode { .. }
         )
     }
 }
@@ -1024,6 +1203,7 @@ impl HirRelationExpr {
             HirRelationExpr::Constant { typ, .. } => typ.clone(),
             HirRelationExpr::Get { typ, .. } => typ.clone(),
             HirRelationExpr::Let { body, .. } => body.typ(outers, params),
+            HirRelationExpr::LetRec { body, .. } => body.typ(outers, params),
             HirRelationExpr::Project { input, outputs } => {
                 let input_typ = input.typ(outers, params);
                 RelationType::new(
@@ -1107,6 +1287,7 @@ impl HirRelationExpr {
             HirRelationExpr::Constant { typ, .. } => typ.column_types.len(),
             HirRelationExpr::Get { typ, .. } => typ.column_types.len(),
             HirRelationExpr::Let { body, .. } => body.arity(),
+            HirRelationExpr::LetRec { body, .. } => body.arity(),
             HirRelationExpr::Project { outputs, .. } => outputs.len(),
             HirRelationExpr::Map { input, scalars } => input.arity() + scalars.len(),
             HirRelationExpr::CallTable { func, .. } => func.output_arity(),
@@ -1332,6 +1513,12 @@ impl HirRelationExpr {
                 f(value, depth)?;
                 f(body, depth)?;
             }
+            HirRelationExpr::LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value, depth)?;
+                }
+                f(body, depth)?;
+            }
             HirRelationExpr::Project { input, .. } => {
                 f(input, depth)?;
             }
@@ -1409,6 +1596,12 @@ impl HirRelationExpr {
                 f(value, depth)?;
                 f(body, depth)?;
             }
+            HirRelationExpr::LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value, depth)?;
+                }
+                f(body, depth)?;
+            }
             HirRelationExpr::Project { input, .. } => {
                 f(input, depth)?;
             }
@@ -1487,6 +1680,7 @@ impl HirRelationExpr {
                 }
                 HirRelationExpr::Union { .. }
                 | HirRelationExpr::Let { .. }
+                | HirRelationExpr::LetRec { .. }
                 | HirRelationExpr::Project { .. }
                 | HirRelationExpr::Distinct { .. }
                 | HirRelationExpr::TopK { .. }
@@ -1535,6 +1729,7 @@ impl HirRelationExpr {
                 }
                 HirRelationExpr::Union { .. }
                 | HirRelationExpr::Let { .. }
+                | HirRelationExpr::LetRec { .. }
                 | HirRelationExpr::Project { .. }
                 | HirRelationExpr::Distinct { .. }
                 | HirRelationExpr::TopK { .. }
@@ -1660,6 +1855,12 @@ impl VisitChildren<Self> for HirRelationExpr {
                 f(value);
                 f(body);
             }
+            LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value);
+                }
+                f(body);
+            }
             Project { input, outputs: _ } => f(input),
             Map { input, scalars: _ } => {
                 f(input);
@@ -1736,6 +1937,12 @@ impl VisitChildren<Self> for HirRelationExpr {
                 f(value);
                 f(body);
             }
+            LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value);
+                }
+                f(body);
+            }
             Project { input, outputs: _ } => f(input),
             Map { input, scalars: _ } => {
                 f(input);
@@ -1812,6 +2019,12 @@ impl VisitChildren<Self> for HirRelationExpr {
                 f(value)?;
                 f(body)?;
             }
+            LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value)?;
+                }
+                f(body)?;
+            }
             Project { input, outputs: _ } => f(input)?,
             Map { input, scalars: _ } => {
                 f(input)?;
@@ -1889,6 +2102,12 @@ impl VisitChildren<Self> for HirRelationExpr {
                 f(value)?;
                 f(body)?;
             }
+            LetRec { bindings, body } => {
+                for (_, _, value, _) in bindings {
+                    f(value)?;
+                }
+                f(body)?;
+            }
             Project { input, outputs: _ } => f(input)?,
             Map { input, scalars: _ } => {
                 f(input)?;
@@ -1955,6 +2174,10 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 value: _,
                 body: _,
             }
+            | LetRec {
+                bindings: _,
+                body: _,
+            }
             | Project {
                 input: _,
                 outputs: _,
@@ -2021,6 +2244,10 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 value: _,
                 body: _,
             }
+            | LetRec {
+                bindings: _,
+                body: _,
+            }
             | Project {
                 input: _,
                 outputs: _,
@@ -2088,6 +2315,10 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 value: _,
                 body: _,
             }
+            | LetRec {
+                bindings: _,
+                body: _,
+            }
             | Project {
                 input: _,
                 outputs: _,
@@ -2156,6 +2387,10 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 value: _,
                 body: _,
             }
+            | LetRec {
+                bindings: _,
+                body: _,
+            }
             | Project {
                 input: _,
                 outputs: _,
@@ -2664,7 +2899,21 @@ impl HirScalarExpr {
     ///
     /// Panics if this expression does not have type [`ScalarType::String`].
     pub fn into_literal_string(self) -> Option<String> {
-        self.simplify_to_literal().and_then(|row| {
+        self.into_literal_nullable_string().flatten()
+    }
+
+    /// Attempts to simplify this expression to a literal string that may be
+    /// `NULL`.
+    ///
+    /// Returns `None` if this expression cannot be simplified, e.g. because it
+    /// contains non-literal values, and `Some(None)` if it simplifies to a
+    /// literal `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this expression does not have type [`ScalarType::String`].
+    pub fn into_literal_nullable_string(self) -> Option<Option<String>> {
+        self.simplify_to_literal().map(|row| {
             let datum = row.unpack_first();
             if datum.is_null() {
                 None
@@ -298,6 +298,52 @@ impl HirRelationExpr {
                         body
                     })
                 }
+                LetRec { bindings, body } => {
+                    // All bindings are in scope within every binding, so we must
+                    // register all of them before lowering any of their values.
+                    let outer_column_types = get_outer.typ().column_types;
+                    let mut ids = Vec::with_capacity(bindings.len());
+                    let mut old_values = Vec::with_capacity(bindings.len());
+                    for (_, id, _, typ) in bindings.iter() {
+                        let new_id = mz_expr::LocalId::new(id_gen.allocate_id());
+                        let relation_type = RelationType::new(
+                            outer_column_types
+                                .iter()
+                                .cloned()
+                                .chain(typ.column_types.iter().cloned())
+                                .collect(),
+                        );
+                        let old_value = cte_map.insert(
+                            id.clone(),
+                            CteDesc {
+                                new_id,
+                                relation_type,
+                                outer_relation: get_outer.clone(),
+                            },
+                        );
+                        ids.push(new_id);
+                        old_values.push((id.clone(), old_value));
+                    }
+                    let values = bindings
+                        .into_iter()
+                        .map(|(_, _, value, _)| {
+                            value.applied_to(id_gen, get_outer.clone(), col_map, cte_map)
+                        })
+                        .collect();
+                    let body = body.applied_to(id_gen, get_outer, col_map, cte_map);
+                    for (id, old_value) in old_values.into_iter().rev() {
+                        if let Some(old_value) = old_value {
+                            cte_map.insert(id, old_value);
+                        } else {
+                            cte_map.remove(&id);
+                        }
+                    }
+                    SR::LetRec {
+                        ids,
+                        values,
+                        body: Box::new(body),
+                    }
+                }
                 Project { input, outputs } => {
                     // Projections should be applied to the decorrelated `inner`, and to its columns,
                     // which means rebasing `outputs` to start `get_outer.arity()` columns later.
@@ -1050,8 +1096,18 @@ impl HirScalarExpr {
                                     });
                             SS::Column(inner.arity() - 1)
                         }
-                        WindowExprType::Value(func) => {
-                            let hir_scalar_input = func.expr.clone();
+                        window_func @ (WindowExprType::Value(_) | WindowExprType::Aggregate(_)) => {
+                            // Value window functions and aggregate window functions are both
+                            // computed from the encoded args of every row in the partition
+                            let (hir_scalar_input, func) = match window_func {
+                                WindowExprType::Value(func) => {
+                                    (func.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Aggregate(func) => {
+                                    (func.aggregate_expr.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Scalar(_) => unreachable!(),
+                            };
                             *inner =
                                 inner
                                     .take_dangerous()
@@ -1194,7 +1250,6 @@ impl HirScalarExpr {
                                             }
                                             .nullable(false);
 
-                                            let func = func.into_expr();
                                             let aggregate = mz_expr::AggregateExpr {
                                                 func,
                                                 expr: agg_input,
@@ -1565,6 +1620,11 @@ where
             // traversing the definition of the new CTE under the same ID.
             assert!(!cte_map.contains_key(id));
         }
+        HirRelationExpr::LetRec { bindings, .. } => {
+            for (_, id, _, _) in bindings {
+                assert!(!cte_map.contains_key(id));
+            }
+        }
         _ => {}
     });
     let mut new_col_map = HashMap::new();
@@ -43,12 +43,14 @@ use itertools::Itertools;
 use uuid::Uuid;
 
 use mz_expr::virtual_syntax::AlgExcept;
+use mz_expr::visit::Visit;
 use mz_expr::{func as expr_func, Id, LocalId, MirScalarExpr, RowSetFinishing};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_ore::str::StrExt;
 use mz_repr::adt::char::CharLength;
+use mz_repr::adt::datetime::Timezone;
 use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::adt::varchar::VarCharMaxLength;
 use mz_repr::{
@@ -58,7 +60,7 @@ use mz_repr::{
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    AsOf, Assignment, AstInfo, DeleteStatement, Distinct, Expr, Function, FunctionArgs,
+    AsOf, Assignment, AstInfo, Cte, DeleteStatement, Distinct, Expr, Function, FunctionArgs,
     HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint, JoinOperator,
     Limit, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr,
     SetOperator, ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableFunction,
@@ -72,10 +74,10 @@ use crate::names::{Aug, PartialObjectName, ResolvedDataType, ResolvedObjectName}
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
-    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
-    CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr, JoinKind,
-    ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc, WindowExpr,
-    WindowExprType,
+    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, AggregateWindowExpr,
+    BinaryFunc, CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr,
+    JoinKind, ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc,
+    WindowExpr, WindowExprType,
 };
 use crate::plan::plan_utils::{self, JoinSide};
 use crate::plan::scope::{Scope, ScopeItem};
@@ -244,6 +246,7 @@ pub fn plan_insert_query(
                 // Special-case simple VALUES clauses as PostgreSQL does.
                 Query {
                     body: SetExpr::Values(Values(values)),
+                    ctes_recursive: _,
                     ctes,
                     order_by,
                     limit: None,
@@ -1023,14 +1026,19 @@ fn plan_query_inner(
         used_names.insert(cte_name.clone());
 
         // Plan CTE.
-        let (val, scope) = plan_nested_query(qcx, &cte.query)?;
-        let typ = qcx.relation_type(&val);
-        let mut val_desc = RelationDesc::new(typ, scope.column_names());
-        plan_utils::maybe_rename_columns(
-            format!("CTE {}", cte.alias.name),
-            &mut val_desc,
-            &cte.alias.columns,
-        )?;
+        let (val, val_desc, recursive) = if q.ctes_recursive {
+            plan_recursive_cte(qcx, cte, &cte_name)?
+        } else {
+            let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+            let typ = qcx.relation_type(&val);
+            let mut val_desc = RelationDesc::new(typ, scope.column_names());
+            plan_utils::maybe_rename_columns(
+                format!("CTE {}", cte.alias.name),
+                &mut val_desc,
+                &cte.alias.columns,
+            )?;
+            (val, val_desc, false)
+        };
 
         let old_val = qcx.ctes.insert(
             cte.id,
@@ -1040,7 +1048,7 @@ fn plan_query_inner(
                 val_desc,
             },
         );
-        old_cte_values.push((cte.id, old_val));
+        old_cte_values.push((cte.id, old_val, recursive));
     }
     let limit = match &q.limit {
         None => None,
@@ -1097,13 +1105,20 @@ fn plan_query_inner(
         }
     }?;
 
-    for (id, old_val) in old_cte_values.into_iter().rev() {
+    for (id, old_val, recursive) in old_cte_values.into_iter().rev() {
         if let Some(cte) = qcx.ctes.remove(&id) {
-            result = HirRelationExpr::Let {
-                name: cte.name,
-                id: id.clone(),
-                value: Box::new(cte.val),
-                body: Box::new(result),
+            result = if recursive {
+                HirRelationExpr::LetRec {
+                    bindings: vec![(cte.name, id, cte.val, cte.val_desc.typ().clone())],
+                    body: Box::new(result),
+                }
+            } else {
+                HirRelationExpr::Let {
+                    name: cte.name,
+                    id: id.clone(),
+                    value: Box::new(cte.val),
+                    body: Box::new(result),
+                }
             };
         }
         if let Some(old_val) = old_val {
@@ -1114,6 +1129,128 @@ fn plan_query_inner(
     Ok((result, scope, finishing))
 }
 
+/// Plans the definition of a CTE in a `WITH RECURSIVE` block.
+///
+/// A CTE that refers to itself must have the form `non_recursive_term UNION
+/// [ALL] recursive_term`, where only `recursive_term` refers to the CTE. The
+/// columns of the CTE take their names and types from `non_recursive_term`.
+/// CTEs of any other form are planned as if they were not recursive.
+///
+/// Returns the definition of the CTE, its description, and whether the
+/// definition refers to the CTE itself.
+fn plan_recursive_cte(
+    qcx: &mut QueryContext,
+    cte: &Cte<Aug>,
+    cte_name: &str,
+) -> Result<(HirRelationExpr, RelationDesc, bool), PlanError> {
+    let (all, left, right) = match &cte.query {
+        Query {
+            ctes_recursive: _,
+            ctes,
+            body:
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    all,
+                    left,
+                    right,
+                },
+            order_by,
+            limit: None,
+            offset: None,
+        } if ctes.is_empty() && order_by.is_empty() => (*all, left, right),
+        _ => {
+            let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+            let typ = qcx.relation_type(&val);
+            let mut val_desc = RelationDesc::new(typ, scope.column_names());
+            plan_utils::maybe_rename_columns(
+                format!("CTE {}", cte.alias.name),
+                &mut val_desc,
+                &cte.alias.columns,
+            )?;
+            return Ok((val, val_desc, false));
+        }
+    };
+
+    // The non-recursive term determines the type of the CTE. The CTE is not
+    // yet in scope, so any reference to it fails to plan.
+    let (left_expr, left_scope) = plan_set_expr(qcx, left)?;
+    let left_type = qcx.relation_type(&left_expr);
+    // The recursive term may introduce nulls into any column, and no key of
+    // the non-recursive term is guaranteed to survive the iteration.
+    let typ = RelationType::new(
+        left_type
+            .column_types
+            .iter()
+            .map(|typ| typ.scalar_type.clone().nullable(true))
+            .collect(),
+    );
+    let mut val_desc = RelationDesc::new(typ, left_scope.column_names());
+    plan_utils::maybe_rename_columns(
+        format!("CTE {}", cte.alias.name),
+        &mut val_desc,
+        &cte.alias.columns,
+    )?;
+
+    // Plan the recursive term with the CTE in scope. The definition recorded
+    // here is a placeholder, as only the description is used while planning.
+    let old_val = qcx.ctes.insert(
+        cte.id,
+        CteDesc {
+            val: HirRelationExpr::constant(vec![], val_desc.typ().clone()),
+            name: cte_name.to_string(),
+            val_desc: val_desc.clone(),
+        },
+    );
+    let right = plan_set_expr(qcx, right);
+    match old_val {
+        Some(old_val) => qcx.ctes.insert(cte.id, old_val),
+        None => qcx.ctes.remove(&cte.id),
+    };
+    let (right_expr, _right_scope) = right?;
+
+    let right_type = qcx.relation_type(&right_expr);
+    if left_type.arity() != right_type.arity() {
+        sql_bail!(
+            "each UNION query must have the same number of columns: {} vs {}",
+            left_type.arity(),
+            right_type.arity(),
+        );
+    }
+    for (i, (left_type, right_type)) in left_type
+        .column_types
+        .iter()
+        .zip(right_type.column_types.iter())
+        .enumerate()
+    {
+        if !left_type.scalar_type.base_eq(&right_type.scalar_type) {
+            sql_bail!(
+                "recursive query {} column {} has type {} in non-recursive term but type {} overall",
+                cte_name.quoted(),
+                i + 1,
+                qcx.humanize_scalar_type(&left_type.scalar_type),
+                qcx.humanize_scalar_type(&right_type.scalar_type),
+            );
+        }
+    }
+
+    let mut recursive = false;
+    right_expr.visit_post(&mut |expr| {
+        if let HirRelationExpr::Get {
+            id: Id::Local(id), ..
+        } = expr
+        {
+            recursive |= *id == cte.id;
+        }
+    })?;
+
+    let val = if all {
+        left_expr.union(right_expr)
+    } else {
+        left_expr.union(right_expr).distinct()
+    };
+    Ok((val, val_desc, recursive))
+}
+
 pub fn plan_nested_query(
     qcx: &mut QueryContext,
     q: &Query<Aug>,
@@ -3935,8 +4072,9 @@ fn plan_aggregate(
         name,
         args,
         filter,
-        over,
+        over: _,
         distinct,
+        within_group,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
     // Normal aggregate functions, like `sum`, expect as input a single expression
@@ -3957,10 +4095,6 @@ fn plan_aggregate(
         _ => unreachable!("plan_aggregate called on non-aggregate function,"),
     };
 
-    if over.is_some() {
-        bail_unsupported!("aggregate window functions");
-    }
-
     let name = normalize::unresolved_object_name(name.clone())?;
 
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
@@ -3971,8 +4105,19 @@ fn plan_aggregate(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
+    //
+    // Ordered-set aggregates, like `percentile_cont(0.5) WITHIN GROUP (ORDER BY
+    // x)`, are called with the expressions in their `WITHIN GROUP` clause
+    // appended to their direct arguments, as in PostgreSQL. The direct
+    // arguments may be empty, as in `mode() WITHIN GROUP (ORDER BY x)`.
     let (args, order_by) = match &args {
         FunctionArgs::Star => (vec![], vec![]),
+        FunctionArgs::Args { args, order_by } if *within_group => {
+            let mut args = args.clone();
+            args.extend(order_by.iter().map(|obe| obe.expr.clone()));
+            let args = plan_exprs(ecx, &args)?;
+            (args, order_by.clone())
+        }
         FunctionArgs::Args { args, order_by } => {
             if args.is_empty() {
                 sql_bail!(
@@ -3988,6 +4133,17 @@ fn plan_aggregate(
     let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
 
     let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(&name), impls, args, col_orders)?;
+    match (func.is_ordered_set(), *within_group) {
+        (true, false) => sql_bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            name
+        ),
+        (false, true) => sql_bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            name
+        ),
+        _ => (),
+    }
     if let Some(filter) = &filter {
         // If a filter is present, as in
         //
@@ -4150,11 +4306,44 @@ fn plan_function<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
     let unresolved_name = normalize::unresolved_object_name(name.clone())?;
 
     let impls = match resolve_func(ecx, name, args)? {
+        Func::Aggregate(_) if over.is_some() => {
+            let (window_spec, window_frame, partition) = plan_window_spec(ecx, f)?;
+
+            if *within_group {
+                sql_bail!("OVER is not supported for ordered-set aggregate {}", name);
+            }
+            if *distinct {
+                bail_unsupported!("DISTINCT in aggregate window functions");
+            }
+            if let FunctionArgs::Args { order_by, .. } = args {
+                if !order_by.is_empty() {
+                    bail_unsupported!("ORDER BY in aggregate window functions");
+                }
+            }
+
+            // Window function calls cannot be nested.
+            let mut agg_ecx = ecx.with_name("window function arguments");
+            agg_ecx.allow_windows = false;
+            let aggregate_expr = plan_aggregate(&agg_ecx, f)?;
+
+            let (order_by, col_orders) = plan_function_order_by(ecx, &window_spec.order_by)?;
+
+            return Ok(HirScalarExpr::Windowing(WindowExpr {
+                func: WindowExprType::Aggregate(AggregateWindowExpr {
+                    aggregate_expr,
+                    order_by: col_orders,
+                    window_frame,
+                }),
+                partition,
+                order_by,
+            }));
+        }
         Func::Aggregate(_) if ecx.allow_aggregates => {
             // should already have been caught by `scope.resolve_expr` in `plan_expr`
             sql_bail!(
@@ -4234,6 +4423,12 @@ fn plan_function<'a>(
             name
         );
     }
+    if *within_group {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
+    }
 
     let scalar_args = match &args {
         FunctionArgs::Star => {
@@ -4421,14 +4616,48 @@ fn plan_literal<'a>(l: &'a Value) -> Result<CoercibleScalarExpr, PlanError> {
     Ok(expr.into())
 }
 
+/// Plans the window frame and the PARTITION BY expressions of the OVER clause
+/// of a window function call.
+fn plan_window_spec<'a>(
+    ecx: &ExprContext,
+    Function { name, over, .. }: &'a Function<Aug>,
+) -> Result<
+    (
+        &'a WindowSpec<Aug>,
+        mz_expr::WindowFrame,
+        Vec<HirScalarExpr>,
+    ),
+    PlanError,
+> {
+    if !ecx.allow_windows {
+        sql_bail!("window functions are not allowed in {}", ecx.name);
+    }
+
+    let window_spec = match over.as_ref() {
+        Some(over) => over,
+        None => sql_bail!("window function {} requires an OVER clause", name),
+    };
+    let window_frame = match window_spec.window_frame.as_ref() {
+        Some(frame) => plan_window_frame(frame)?,
+        None => mz_expr::WindowFrame::default(),
+    };
+    let mut partition = Vec::new();
+    for expr in &window_spec.partition_by {
+        partition.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
+    }
+
+    Ok((window_spec, window_frame, partition))
+}
+
 fn validate_window_function_plan<'a>(
     ecx: &ExprContext,
-    Function {
+    f @ Function {
         name,
         args,
         filter,
-        over,
+        over: _,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<
     (
@@ -4439,10 +4668,6 @@ fn validate_window_function_plan<'a>(
     ),
     PlanError,
 > {
-    if !ecx.allow_windows {
-        sql_bail!("window functions are not allowed in {}", ecx.name);
-    }
-
     // Various things are duplicated here and in `plan_function` to improve error messages.
 
     if *distinct {
@@ -4456,19 +4681,15 @@ fn validate_window_function_plan<'a>(
         bail_unsupported!("FILTER in non-aggregate window functions");
     }
 
-    let window_spec = match over.as_ref() {
-        Some(over) => over,
-        None => sql_bail!("window function {} requires an OVER clause", name),
-    };
-    let window_frame = match window_spec.window_frame.as_ref() {
-        Some(frame) => plan_window_frame(frame)?,
-        None => mz_expr::WindowFrame::default(),
-    };
-    let mut partition = Vec::new();
-    for expr in &window_spec.partition_by {
-        partition.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
+    if *within_group {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
     }
 
+    let (window_spec, window_frame, partition) = plan_window_spec(ecx, f)?;
+
     let scalar_args = match &args {
         FunctionArgs::Star => {
             sql_bail!("* argument is invalid with non-aggregate function {}", name)
@@ -4523,11 +4744,12 @@ fn plan_window_frame(
         (_, _) => (),
     }
 
-    // RANGE is only supported in the default frame
+    // RANGE is only supported with UNBOUNDED and CURRENT ROW bounds
     if units == mz_expr::WindowFrameUnits::Range
-        && (start_bound != UnboundedPreceding || end_bound != CurrentRow)
+        && (matches!(start_bound, OffsetPreceding(_) | OffsetFollowing(_))
+            || matches!(end_bound, OffsetPreceding(_) | OffsetFollowing(_)))
     {
-        bail_unsupported!("RANGE in non-default window frames")
+        bail_unsupported!("RANGE with offset PRECEDING/FOLLOWING")
     }
 
     let frame = mz_expr::WindowFrame {
@@ -4720,6 +4942,11 @@ fn scalar_type_from_catalog(
                     value_type: Box::new(scalar_type_from_catalog(scx, *value_id, &[])?),
                     custom_id: Some(id),
                 }),
+                CatalogType::Range {
+                    element_reference: element_id,
+                } => Ok(ScalarType::Range {
+                    element_type: Box::new(scalar_type_from_catalog(scx, *element_id, &[])?),
+                }),
                 CatalogType::Record { fields } => {
                     let scalars: Vec<(ColumnName, ColumnType)> = fields
                         .iter()
@@ -4753,6 +4980,7 @@ fn scalar_type_from_catalog(
                 CatalogType::MzTimestamp => Ok(ScalarType::MzTimestamp),
                 CatalogType::Interval => Ok(ScalarType::Interval),
                 CatalogType::Jsonb => Ok(ScalarType::Jsonb),
+                CatalogType::JsonPath => Ok(ScalarType::JsonPath),
                 CatalogType::Oid => Ok(ScalarType::Oid),
                 CatalogType::PgLegacyChar => Ok(ScalarType::PgLegacyChar),
                 CatalogType::Pseudo => {
@@ -4832,7 +5060,9 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
         };
 
         match item.func() {
-            Ok(Func::Aggregate { .. }) => {
+            // Aggregate window functions are planned like other window
+            // functions, rather than as part of the reduction.
+            Ok(Func::Aggregate { .. }) if func.over.is_none() => {
                 if self.within_aggregate {
                     self.err = Some(sql_err!("nested aggregate functions are not allowed",));
                     return;
@@ -4844,6 +5074,7 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     filter,
                     over: _,
                     distinct: _,
+                    within_group: _,
                 } = func;
                 if let Some(filter) = filter {
                     self.visit_expr_mut(filter);
@@ -4908,6 +5139,7 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group: false,
             } = func
             {
                 let func = TableFunction { name, args };
@@ -5045,8 +5277,16 @@ impl<'a> QueryContext<'a> {
                 Ok((expr, scope))
             }
             ResolvedObjectName::Cte { id, name } => {
+                let cte = match self.ctes.get(&id) {
+                    Some(cte) => cte,
+                    // Only the definition of a recursive CTE can refer to a
+                    // CTE that is not yet planned.
+                    None => sql_bail!(
+                        "recursive reference to query {} must not appear within its non-recursive term",
+                        name.quoted()
+                    ),
+                };
                 let name = name.into();
-                let cte = self.ctes.get(&id).unwrap();
                 let expr = HirRelationExpr::Get {
                     id: Id::Local(id),
                     typ: cte.val_desc.typ().clone(),
@@ -5096,6 +5336,29 @@ impl<'a> ExprContext<'a> {
         self.qcx.scx.catalog
     }
 
+    /// Returns the name of the session's time zone as a string literal, if
+    /// conversions between timestamps with and without time zones must take
+    /// place in a time zone other than UTC.
+    ///
+    /// Only one-shot queries observe the session's time zone. The results of
+    /// static queries, like those that back views and indexes, must not depend
+    /// upon the session that queries them, so they always use UTC.
+    pub fn session_timezone(&self) -> Option<HirScalarExpr> {
+        self.session_timezone_value()
+            .map(|tz| HirScalarExpr::literal(Datum::String(&tz.to_string()), ScalarType::String))
+    }
+
+    /// Like [`ExprContext::session_timezone`], but returns the time zone
+    /// itself rather than its name.
+    pub fn session_timezone_value(&self) -> Option<Timezone> {
+        match self.qcx.lifetime {
+            QueryLifetime::OneShot(pcx) if pcx.timezone != Timezone::default() => {
+                Some(pcx.timezone)
+            }
+            _ => None,
+        }
+    }
+
     pub fn with_name(&self, name: &'a str) -> ExprContext<'a> {
         let mut ecx = self.clone();
         ecx.name = name;
@@ -140,6 +140,10 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::DropRoles(stmt) => ddl::describe_drop_role(&scx, stmt)?,
         Statement::DropSchema(stmt) => ddl::describe_drop_schema(&scx, stmt)?,
+        Statement::GrantPrivileges(stmt) => ddl::describe_grant_privileges(&scx, stmt)?,
+        Statement::GrantRole(stmt) => ddl::describe_grant_role(&scx, stmt)?,
+        Statement::RevokePrivileges(stmt) => ddl::describe_revoke_privileges(&scx, stmt)?,
+        Statement::RevokeRole(stmt) => ddl::describe_revoke_role(&scx, stmt)?,
 
         // `SHOW` statements.
         Statement::Show(ShowStatement::ShowColumns(stmt)) => {
@@ -219,9 +223,10 @@ pub fn describe(
 /// Produces a [`Plan`] from the purified statement `stmt`.
 ///
 /// Planning is a pure, synchronous function and so requires that the provided
-/// `stmt` does does not depend on any external state. Only `CREATE SOURCE`
-/// statements can depend on external state; remove that state prior to calling
-/// this function via [`crate::pure::purify_create_source`].
+/// `stmt` does does not depend on any external state. Only `CREATE SOURCE` and
+/// `CREATE SINK` statements can depend on external state; remove that state
+/// prior to calling this function via [`crate::pure::purify_create_source`] or
+/// [`crate::pure::purify_create_sink`].
 ///
 /// The returned plan is tied to the state of the provided catalog. If the state
 /// of the catalog changes after planning, the validity of the plan is not
@@ -281,6 +286,10 @@ pub fn plan(
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::DropRoles(stmt) => ddl::plan_drop_role(scx, stmt),
         Statement::DropSchema(stmt) => ddl::plan_drop_schema(scx, stmt),
+        Statement::GrantPrivileges(stmt) => ddl::plan_grant_privileges(scx, stmt),
+        Statement::GrantRole(stmt) => ddl::plan_grant_role(scx, stmt),
+        Statement::RevokePrivileges(stmt) => ddl::plan_revoke_privileges(scx, stmt),
+        Statement::RevokeRole(stmt) => ddl::plan_revoke_role(scx, stmt),
 
         // DML statements.
         Statement::Copy(stmt) => dml::plan_copy(scx, stmt),
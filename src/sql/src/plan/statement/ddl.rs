@@ -27,6 +27,7 @@ use tracing::warn;
 
 use mz_expr::CollectionPlan;
 use mz_interchange::avro::AvroSchemaGenerator;
+use mz_interchange::protobuf::{DecodedDescriptors, Encoder as ProtobufEncoder};
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_proto::RustType;
@@ -50,18 +51,20 @@ use mz_storage_client::types::connections::{
 };
 use mz_storage_client::types::sinks::{
     KafkaConsistencyConfig, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention,
-    KafkaSinkFormat, SinkEnvelope, StorageSinkConnectionBuilder,
+    KafkaSinkFormat, ProtobufSinkFormat, ProtobufSinkMessage, PublishedSchemaInfo,
+    S3SinkConnection, S3SinkFormat, SinkEnvelope, StorageSinkConnectionBuilder,
 };
 use mz_storage_client::types::sources::encoding::{
     included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, DataEncodingInner,
-    ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
+    JsonEncoding, ParquetEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
+    SourceDataEncodingInner,
 };
 use mz_storage_client::types::sources::{
     GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KeyEnvelope,
     KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
     PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails, S3SourceConnection, SourceDesc, SourceEnvelope,
-    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStyle,
+    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStateBackend, UpsertStyle,
 };
 
 use crate::ast::display::AstDisplay;
@@ -78,22 +81,27 @@ use crate::ast::{
     CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
     CreateTypeStatement, CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
     CsrConnectionAvro, CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf,
-    CsrSeedProtobuf, CsvColumns, DbzMode, DropClusterReplicasStatement, DropClustersStatement,
-    DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement, Envelope,
-    Expr, Format, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaBroker,
+    CsrSeedProtobuf, CsrSeedProtobufSchema, CsvColumns, DbzMode, DropClusterReplicasStatement,
+    DropClustersStatement, DropDatabaseStatement, DropObjectsStatement, DropRolesStatement,
+    DropSchemaStatement, Envelope, Expr, Format, GrantObjectType, GrantPrivilegesStatement,
+    GrantRoleStatement, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaBroker,
     KafkaBrokerAwsPrivatelinkOption, KafkaBrokerAwsPrivatelinkOptionName, KafkaBrokerTunnel,
     KafkaConfigOptionName, KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint,
     LoadGeneratorOption, LoadGeneratorOptionName, ObjectType, PgConfigOption, PgConfigOptionName,
-    PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
-    ReplicaDefinition, ReplicaOption, ReplicaOptionName, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, Value, ViewDefinition,
+    PostgresConnectionOption, PostgresConnectionOptionName, Privilege as AstPrivilege,
+    PrivilegeSpecification, ProtobufSchema, QualifiedReplica, ReplicaDefinition, ReplicaOption,
+    ReplicaOptionName, RevokePrivilegesStatement, RevokeRoleStatement, S3SinkOption,
+    S3SinkOptionName, SourceIncludeMetadata, SourceIncludeMetadataType, SshConnectionOptionName,
+    Statement, TableConstraint, UnresolvedDatabaseName, UnresolvedSchemaName, Value,
+    ViewDefinition,
+};
+use crate::catalog::{
+    CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails, Privilege, PrivilegeObject,
 };
-use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails};
 use crate::kafka_util::{self, KafkaConfigOptionExtracted, KafkaStartOffsetType};
 use crate::names::{
     Aug, FullSchemaName, QualifiedObjectName, RawDatabaseSpecifier, ResolvedClusterName,
-    ResolvedDataType, ResolvedDatabaseSpecifier, ResolvedObjectName, SchemaSpecifier,
+    ResolvedDataType, ResolvedDatabaseSpecifier, ResolvedObjectName, RoleId, SchemaSpecifier,
 };
 use crate::normalize::{self, ident};
 use crate::plan::error::PlanError;
@@ -112,7 +120,8 @@ use crate::plan::{
     CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
     CreateTablePlan, CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan,
     DropComputeReplicasPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
-    FullObjectName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext,
+    FullObjectName, GrantPrivilegesPlan, GrantRolePlan, HirScalarExpr, Index, Ingestion,
+    MaterializedView, Params, Plan, QueryContext, RevokePrivilegesPlan, RevokeRolePlan,
     RotateKeysPlan, Secret, Sink, Source, StorageHostConfig, Table, Type, View,
 };
 
@@ -323,7 +332,8 @@ generate_extracted_config!(
     (Remote, String),
     (Size, String),
     (Timeline, String),
-    (TimestampInterval, Interval)
+    (TimestampInterval, Interval),
+    (UpsertStateBackend, String)
 );
 
 generate_extracted_config!(
@@ -352,7 +362,10 @@ pub fn plan_create_source(
 
     let envelope = envelope.clone().unwrap_or(Envelope::None);
 
-    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[CreateSourceOptionName::Size];
+    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[
+        CreateSourceOptionName::Size,
+        CreateSourceOptionName::UpsertStateBackend,
+    ];
 
     if with_options
         .iter()
@@ -372,10 +385,25 @@ pub fn plan_create_source(
         // TODO(guswynn): should this be `bail_unsupported!`?
         sql_bail!("INCLUDE HEADERS with non-Kafka sources not supported");
     }
-    if !matches!(connection, CreateSourceConnection::Kafka { .. }) && !include_metadata.is_empty() {
-        bail_unsupported!("INCLUDE metadata with non-Kafka sources");
+    if !matches!(
+        connection,
+        CreateSourceConnection::Kafka { .. }
+            | CreateSourceConnection::Kinesis { .. }
+            | CreateSourceConnection::S3 { .. }
+    ) && !include_metadata.is_empty()
+    {
+        bail_unsupported!("INCLUDE metadata with non-Kafka, non-Kinesis, or non-S3 sources");
     }
 
+    let unwrap_name = |alias: Option<Ident>, default, pos| {
+        Some(IncludedColumnPos {
+            name: alias
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| String::from(default)),
+            pos,
+        })
+    };
+
     let (mut external_connection, encoding, available_subsources) = match connection {
         CreateSourceConnection::Kafka(mz_sql_parser::ast::KafkaSourceConnection {
             connection:
@@ -397,6 +425,7 @@ pub fn plan_create_source(
                 opt.name != KafkaConfigOptionName::StartOffset
                     && opt.name != KafkaConfigOptionName::StartTimestamp
                     && opt.name != KafkaConfigOptionName::Topic
+                    && opt.name != KafkaConfigOptionName::TopicPattern
             }) {
                 scx.require_unsafe_mode(&format!("KAFKA CONNECTION option {}", opt.name))?;
             }
@@ -415,9 +444,16 @@ pub fn plan_create_source(
                 kafka_connection.options.insert(k, v);
             }
 
-            let topic = extracted_options
-                .topic
-                .expect("validated exists during purification");
+            let (topic, topic_pattern) =
+                match (extracted_options.topic, extracted_options.topic_pattern) {
+                    (Some(topic), None) => (topic, false),
+                    (None, Some(pattern)) => {
+                        KafkaSourceConnection::compile_topic_pattern(&pattern)
+                            .map_err(|e| sql_err!("invalid TOPIC PATTERN: {}", e))?;
+                        (pattern, true)
+                    }
+                    _ => unreachable!("validated during purification"),
+                };
             let group_id_prefix = extracted_options.group_id_prefix;
 
             let mut start_offsets = HashMap::new();
@@ -436,6 +472,10 @@ pub fn plan_create_source(
                 }
             }
 
+            if !start_offsets.is_empty() && topic_pattern {
+                sql_bail!("START OFFSET is not supported with TOPIC PATTERN");
+            }
+
             if !start_offsets.is_empty() && envelope.requires_all_input() {
                 sql_bail!("START OFFSET is not supported with ENVELOPE {}", envelope)
             }
@@ -446,6 +486,7 @@ pub fn plan_create_source(
                 connection: kafka_connection,
                 connection_id: connection_item.id(),
                 topic,
+                topic_pattern,
                 start_offsets,
                 group_id_prefix,
                 environment_id: scx.catalog.config().environment_id.clone(),
@@ -456,15 +497,6 @@ pub fn plan_create_source(
                 include_headers: None,
             };
 
-            let unwrap_name = |alias: Option<Ident>, default, pos| {
-                Some(IncludedColumnPos {
-                    name: alias
-                        .map(|a| a.to_string())
-                        .unwrap_or_else(|| String::from(default)),
-                    pos,
-                })
-            };
-
             if !matches!(envelope, Envelope::Upsert | Envelope::None)
                 && include_metadata
                     .iter()
@@ -483,12 +515,7 @@ pub fn plan_create_source(
                         connection.include_partition = unwrap_name(item.alias, "partition", pos);
                     }
                     SourceIncludeMetadataType::Topic => {
-                        // TODO(bwm): This requires deeper thought, the current structure of the
-                        // code requires us to clone the topic name around all over the place
-                        // whether or not anyone ever uses it. Considering we expect the
-                        // overwhelming majority of people will *not* want topics in dataflows that
-                        // is an unnacceptable cost.
-                        bail_unsupported!("INCLUDE TOPIC");
+                        connection.include_topic = unwrap_name(item.alias, "topic", pos);
                     }
                     SourceIncludeMetadataType::Offset => {
                         connection.include_offset = unwrap_name(item.alias, "offset", pos);
@@ -527,10 +554,30 @@ pub fn plan_create_source(
             };
 
             let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+
+            // Kinesis records are identified by the shard they were read from
+            // and their sequence number within that shard, which are exposed
+            // as `INCLUDE PARTITION` and `INCLUDE OFFSET` respectively.
+            let mut include_shard_id = None;
+            let mut include_sequence_number = None;
+            for (pos, item) in include_metadata.iter().cloned().enumerate() {
+                match item.ty {
+                    SourceIncludeMetadataType::Partition => {
+                        include_shard_id = unwrap_name(item.alias, "partition", pos);
+                    }
+                    SourceIncludeMetadataType::Offset => {
+                        include_sequence_number = unwrap_name(item.alias, "offset", pos);
+                    }
+                    ty => sql_bail!("INCLUDE {} with Kinesis sources not supported", ty),
+                }
+            }
+
             let connection = GenericSourceConnection::Kinesis(KinesisSourceConnection {
                 connection_id: connection_item.id(),
                 stream_name,
                 aws,
+                include_shard_id,
+                include_sequence_number,
             });
             (connection, encoding, None)
         }
@@ -568,6 +615,23 @@ pub fn plan_create_source(
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 sql_bail!("S3 sources do not support key decoding");
             }
+
+            // `INCLUDE KEY` exposes the key of the object that a record was
+            // read from, and `INCLUDE TIMESTAMP` the time it was last modified.
+            let mut include_key = None;
+            let mut include_last_modified = None;
+            for (pos, item) in include_metadata.iter().cloned().enumerate() {
+                match item.ty {
+                    SourceIncludeMetadataType::Key => {
+                        include_key = unwrap_name(item.alias, "key", pos);
+                    }
+                    SourceIncludeMetadataType::Timestamp => {
+                        include_last_modified = unwrap_name(item.alias, "timestamp", pos);
+                    }
+                    ty => sql_bail!("INCLUDE {} with S3 sources not supported", ty),
+                }
+            }
+
             let connection = GenericSourceConnection::S3(S3SourceConnection {
                 connection_id: connection_item.id(),
                 key_sources: converted_sources,
@@ -584,8 +648,13 @@ pub fn plan_create_source(
                 aws,
                 compression: match compression {
                     Compression::Gzip => mz_storage_client::types::sources::Compression::Gzip,
+                    Compression::Zstd => mz_storage_client::types::sources::Compression::Zstd,
+                    Compression::Bzip2 => mz_storage_client::types::sources::Compression::Bzip2,
+                    Compression::Snappy => mz_storage_client::types::sources::Compression::Snappy,
                     Compression::None => mz_storage_client::types::sources::Compression::None,
                 },
+                include_key,
+                include_last_modified,
             });
             (connection, encoding, None)
         }
@@ -913,7 +982,13 @@ pub fn plan_create_source(
 
     let (key_desc, value_desc) = encoding.desc()?;
 
-    let mut key_envelope = get_key_envelope(include_metadata, &envelope, &encoding)?;
+    // Only Kafka sources have keys to decode; for S3 sources `INCLUDE KEY`
+    // refers to the object key and was handled above.
+    let mut key_envelope = if matches!(connection, CreateSourceConnection::Kafka { .. }) {
+        get_key_envelope(include_metadata, &envelope, &encoding)?
+    } else {
+        get_key_envelope(&[], &envelope, &encoding)?
+    };
 
     // Not all source envelopes are compatible with all source connections.
     // Whoever constructs the source ingestion pipeline is responsible for
@@ -964,7 +1039,7 @@ pub fn plan_create_source(
     let metadata_columns = external_connection.metadata_columns();
     let metadata_column_types = external_connection.metadata_column_types();
     let metadata_desc = included_column_desc(metadata_columns.clone());
-    let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+    let (mut envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
 
     let CreateSourceOptionExtracted {
         remote,
@@ -972,9 +1047,27 @@ pub fn plan_create_source(
         timeline,
         timestamp_interval,
         ignore_keys,
+        upsert_state_backend,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
+    if let Some(backend) = upsert_state_backend {
+        let backend = match backend.to_lowercase().as_str() {
+            "memory" => UpsertStateBackend::Memory,
+            "disk" => UpsertStateBackend::Disk,
+            _ => sql_bail!(
+                "invalid UPSERT STATE BACKEND {}: must be 'memory' or 'disk'",
+                backend.quoted()
+            ),
+        };
+        match &mut envelope {
+            SourceEnvelope::Upsert(upsert) => upsert.state_backend = backend,
+            _ => sql_bail!(
+                "UPSERT STATE BACKEND is only valid for sources with ENVELOPE UPSERT or ENVELOPE DEBEZIUM"
+            ),
+        }
+    }
+
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
     }
@@ -1513,11 +1606,94 @@ fn get_encoding_inner(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json { columns } => {
+            let mut names = HashSet::new();
+            let mut json_columns = vec![];
+            for column in columns {
+                let name = normalize::column_name(column.name.clone());
+                if !names.insert(name.clone()) {
+                    sql_bail!("column {} specified more than once", name.as_str().quoted());
+                }
+                let typ = query::scalar_type_from_sql(scx, &column.data_type)?;
+                if !is_json_projectable_type(&typ) {
+                    bail_unsupported!(format!(
+                        "projecting JSON fields as type {}",
+                        scx.humanize_scalar_type(&typ)
+                    ));
+                }
+                json_columns.push((name.as_str().to_string(), typ));
+            }
+            DataEncodingInner::Json(JsonEncoding {
+                columns: json_columns,
+            })
+        }
+        Format::Parquet { columns } => {
+            let mut names = HashSet::new();
+            let mut parquet_columns = vec![];
+            for column in columns {
+                let name = normalize::column_name(column.name.clone());
+                if !names.insert(name.clone()) {
+                    sql_bail!("column {} specified more than once", name.as_str().quoted());
+                }
+                let typ = query::scalar_type_from_sql(scx, &column.data_type)?;
+                if !is_parquet_projectable_type(&typ) {
+                    bail_unsupported!(format!(
+                        "reading Parquet columns as type {}",
+                        scx.humanize_scalar_type(&typ)
+                    ));
+                }
+                parquet_columns.push((name.as_str().to_string(), typ));
+            }
+            DataEncodingInner::Parquet(ParquetEncoding {
+                columns: parquet_columns,
+            })
+        }
         Format::Text => DataEncodingInner::Text,
     }))
 }
 
+/// Reports whether JSON values can be decoded directly into a column of type
+/// `typ`.
+fn is_json_projectable_type(typ: &ScalarType) -> bool {
+    matches!(
+        typ,
+        ScalarType::Bool
+            | ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::Float32
+            | ScalarType::Float64
+            | ScalarType::Numeric { max_scale: None }
+            | ScalarType::String
+            | ScalarType::Date
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz
+            | ScalarType::Interval
+            | ScalarType::Uuid
+            | ScalarType::Jsonb
+    )
+}
+
+/// Reports whether Parquet columns can be decoded directly into a column of
+/// type `typ`.
+fn is_parquet_projectable_type(typ: &ScalarType) -> bool {
+    matches!(
+        typ,
+        ScalarType::Bool
+            | ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::Float32
+            | ScalarType::Float64
+            | ScalarType::Numeric { max_scale: None }
+            | ScalarType::String
+            | ScalarType::Bytes
+            | ScalarType::Date
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz
+    )
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
     included_items: &[SourceIncludeMetadata],
@@ -1564,8 +1740,10 @@ fn get_unnamed_key_envelope(key: &DataEncoding) -> Result<KeyEnvelope, PlanError
             sql_bail!("{} sources cannot use INCLUDE KEY", key.op_name())
         }
         DataEncodingInner::Bytes | DataEncodingInner::Text => false,
+        DataEncodingInner::Json(JsonEncoding { ref columns }) => !columns.is_empty(),
         DataEncodingInner::Avro(_)
         | DataEncodingInner::Csv(_)
+        | DataEncodingInner::Parquet(_)
         | DataEncodingInner::Protobuf(_)
         | DataEncodingInner::Regex { .. } => true,
     };
@@ -1797,12 +1975,20 @@ pub fn plan_create_sink(
         ))?;
     }
 
-    let envelope = match envelope {
-        None => sql_bail!("ENVELOPE clause is required"),
-        Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
-        Some(Envelope::Upsert) => SinkEnvelope::Upsert,
-        Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+    let envelope = match (&connection, envelope) {
+        // S3 sinks record the diff of every update alongside its timestamp,
+        // so there is no envelope to apply.
+        (CreateSinkConnection::S3 { .. }, None) => None,
+        (CreateSinkConnection::S3 { .. }, Some(_)) => {
+            sql_bail!("S3 sinks do not support the ENVELOPE clause")
+        }
+        (_, None) => sql_bail!("ENVELOPE clause is required"),
+        (_, Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain))) => {
+            Some(SinkEnvelope::Debezium)
+        }
+        (_, Some(Envelope::Upsert)) => Some(SinkEnvelope::Upsert),
+        (_, Some(Envelope::CdcV2)) => bail_unsupported!("CDCv2 sinks"),
+        (_, Some(Envelope::None)) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
     };
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let from = scx.get_item_by_resolved_name(&from)?;
@@ -1839,14 +2025,14 @@ pub fn plan_create_sink(
                     desc.typ().keys.iter().any(|key_columns| {
                         key_columns.iter().all(|column| indices.contains(column))
                     });
-                if key.not_enforced && envelope == SinkEnvelope::Upsert {
+                if key.not_enforced && envelope == Some(SinkEnvelope::Upsert) {
                     // TODO: We should report a warning notice back to the user via the pgwire
                     // protocol. See https://github.com/MaterializeInc/materialize/issues/9333.
                     warn!(
                         "Verification of upsert key disabled for sink '{}' via 'NOT ENFORCED'. This is potentially dangerous and can lead to crashing materialize when the specified key is not in fact a unique key of the sinked view.",
                         name
                     );
-                } else if !is_valid_key && envelope == SinkEnvelope::Upsert {
+                } else if !is_valid_key && envelope == Some(SinkEnvelope::Upsert) {
                     return Err(invalid_upsert_key_err(&desc, &key_columns));
                 }
                 Some(indices)
@@ -1854,6 +2040,7 @@ pub fn plan_create_sink(
                 None
             }
         }
+        CreateSinkConnection::S3 { .. } => None,
     };
 
     // pick the first valid natural relation key, if any
@@ -1870,7 +2057,7 @@ pub fn plan_create_sink(
         (RelationDesc::new(typ, names), key_indices)
     });
 
-    if key_desc_and_indices.is_none() && envelope == SinkEnvelope::Upsert {
+    if key_desc_and_indices.is_none() && envelope == Some(SinkEnvelope::Upsert) {
         return Err(PlanError::UpsertSinkWithoutKey);
     }
 
@@ -1882,8 +2069,12 @@ pub fn plan_create_sink(
             relation_key_indices,
             key_desc_and_indices,
             desc.into_owned(),
-            envelope,
+            envelope.expect("Kafka sinks require an envelope"),
         )?,
+        CreateSinkConnection::S3 {
+            connection,
+            options,
+        } => s3_sink_builder(scx, connection, options, format, desc.into_owned())?,
     };
 
     let CreateSinkOptionExtracted {
@@ -2084,7 +2275,89 @@ fn kafka_sink_builder(
                 csr_connection,
             }
         }
-        Some(Format::Json) => KafkaSinkFormat::Json,
+        Some(Format::Json { columns }) if columns.is_empty() => KafkaSinkFormat::Json,
+        Some(Format::Json { .. }) => sql_bail!("JSON sinks do not support declaring columns"),
+        Some(Format::Protobuf(ProtobufSchema::Csr {
+            csr_connection:
+                CsrConnectionProtobuf {
+                    connection:
+                        CsrConnection {
+                            connection,
+                            options,
+                        },
+                    seed,
+                },
+        })) => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            if !matches!(item.connection()?, Connection::Csr(_)) {
+                sql_bail!("{} is not a schema registry connection", item.name());
+            }
+            if !options.is_empty() {
+                sql_bail!("Protobuf CSR connections do not support any options");
+            }
+            let CsrSeedProtobuf { key, value } =
+                seed.expect("CSR seed resolution should already have been called: Proto");
+            let schema_id = |schema: &CsrSeedProtobufSchema| {
+                schema.schema_id.ok_or_else(|| {
+                    sql_err!(
+                        "SEED for Protobuf sinks must specify the ID of schema {}",
+                        schema.message_name.quoted()
+                    )
+                })
+            };
+            let value_schema_id = schema_id(&value)?;
+            let (key, key_schema_id) = match (&key_desc_and_indices, key) {
+                (Some(_), Some(key)) => {
+                    let key_schema_id = schema_id(&key)?;
+                    let key = ProtobufSinkMessage {
+                        descriptors: strconv::parse_bytes(&key.schema)?,
+                        message_name: key.message_name,
+                    };
+                    (Some(key), Some(key_schema_id))
+                }
+                (Some(_), None) => {
+                    sql_bail!(
+                        "Protobuf sinks with a KEY require a key schema in the schema registry"
+                    )
+                }
+                (None, _) => (None, None),
+            };
+            let format = ProtobufSinkFormat {
+                key,
+                value: ProtobufSinkMessage {
+                    descriptors: strconv::parse_bytes(&value.schema)?,
+                    message_name: value.message_name,
+                },
+            };
+            validate_protobuf_sink_format(&format, &key_desc_and_indices, &value_desc, envelope)?;
+            KafkaSinkFormat::Protobuf {
+                format,
+                published_schema_info: Some(PublishedSchemaInfo {
+                    key_schema_id,
+                    value_schema_id,
+                }),
+            }
+        }
+        Some(Format::Protobuf(ProtobufSchema::InlineSchema {
+            message_name,
+            schema: mz_sql_parser::ast::Schema { schema },
+        })) => {
+            if key_desc_and_indices.is_some() {
+                sql_bail!("Protobuf sinks with a KEY require a schema registry connection");
+            }
+            let format = ProtobufSinkFormat {
+                key: None,
+                value: ProtobufSinkMessage {
+                    descriptors: strconv::parse_bytes(&schema)?,
+                    message_name,
+                },
+            };
+            validate_protobuf_sink_format(&format, &key_desc_and_indices, &value_desc, envelope)?;
+            KafkaSinkFormat::Protobuf {
+                format,
+                published_schema_info: None,
+            }
+        }
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
@@ -2140,6 +2413,103 @@ fn kafka_sink_builder(
     ))
 }
 
+generate_extracted_config!(S3SinkOption, (Bucket, String), (Prefix, String));
+
+fn s3_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedObjectName,
+    options: Vec<S3SinkOption<Aug>>,
+    format: Option<Format<Aug>>,
+    value_desc: RelationDesc,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO S3")?;
+
+    let item = scx.get_item_by_resolved_name(&connection)?;
+    let aws = match item.connection()? {
+        Connection::Aws(aws) => aws.clone(),
+        _ => sql_bail!("{} is not an AWS connection", item.name()),
+    };
+
+    let S3SinkOptionExtracted { bucket, prefix, .. } = options.try_into()?;
+    let bucket = bucket.ok_or_else(|| sql_err!("S3 CONNECTION must specify BUCKET"))?;
+    let prefix = prefix.unwrap_or_default().trim_matches('/').to_string();
+
+    let format = match format {
+        Some(Format::Parquet { columns }) if columns.is_empty() => S3SinkFormat::Parquet,
+        Some(Format::Parquet { .. }) => sql_bail!("Parquet sinks do not support declaring columns"),
+        Some(Format::Csv { columns, delimiter }) => {
+            let header = match columns {
+                CsvColumns::Header { names } if names.is_empty() => true,
+                CsvColumns::Header { .. } => {
+                    sql_bail!("CSV sinks name their header columns after the sinked relation")
+                }
+                CsvColumns::Count(n) if n == value_desc.arity() => false,
+                CsvColumns::Count(n) => sql_bail!(
+                    "CSV WITH {} COLUMNS does not match the {} columns of the sinked relation",
+                    n,
+                    value_desc.arity()
+                ),
+            };
+            S3SinkFormat::Csv {
+                header,
+                delimiter: match delimiter as u32 {
+                    0..=127 => delimiter as u8,
+                    _ => sql_bail!("CSV delimiter must be an ASCII character"),
+                },
+            }
+        }
+        Some(_) => sql_bail!("S3 sinks only support FORMAT PARQUET and FORMAT CSV"),
+        None => sql_bail!("S3 sinks require a FORMAT clause"),
+    };
+
+    // Every data file carries the timestamp and diff of each update in two
+    // extra columns, whose names must not collide with the relation's.
+    for name in ["mz_timestamp", "mz_diff"] {
+        if value_desc.get_by_name(&ColumnName::from(name)).is_some() {
+            sql_bail!(
+                "S3 sinks write a column named {}, which the sinked relation already has",
+                name.quoted()
+            );
+        }
+    }
+
+    Ok(StorageSinkConnectionBuilder::S3(S3SinkConnection {
+        connection_id: item.id(),
+        aws,
+        bucket,
+        prefix,
+        format,
+        value_desc,
+    }))
+}
+
+/// Ensures that the columns of a Protobuf sink can be encoded as the declared
+/// Protobuf messages.
+fn validate_protobuf_sink_format(
+    format: &ProtobufSinkFormat,
+    key_desc_and_indices: &Option<(RelationDesc, Vec<usize>)>,
+    value_desc: &RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<(), PlanError> {
+    let decode = |message: &ProtobufSinkMessage| {
+        DecodedDescriptors::from_bytes(&message.descriptors, message.message_name.clone())
+            .map_err(|e| sql_err!("{:#}", e))
+    };
+    let key = match (key_desc_and_indices, &format.key) {
+        (Some((key_desc, _indices)), Some(key)) => Some((key_desc.clone(), decode(key)?, None)),
+        _ => None,
+    };
+    ProtobufEncoder::new(
+        key,
+        value_desc.clone(),
+        decode(&format.value)?,
+        None,
+        matches!(envelope, SinkEnvelope::Debezium),
+    )
+    .map_err(|e| sql_err!("{:#}", e))?;
+    Ok(())
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -2399,6 +2769,7 @@ pub fn plan_create_role(
 ) -> Result<Plan, PlanError> {
     let mut login = None;
     let mut super_user = None;
+    let mut password = None;
     for option in options {
         match option {
             CreateRoleOption::Login | CreateRoleOption::NoLogin if login.is_some() => {
@@ -2407,23 +2778,33 @@ pub fn plan_create_role(
             CreateRoleOption::SuperUser | CreateRoleOption::NoSuperUser if super_user.is_some() => {
                 sql_bail!("conflicting or redundant options");
             }
+            CreateRoleOption::Password(_) if password.is_some() => {
+                sql_bail!("conflicting or redundant options");
+            }
             CreateRoleOption::Login => login = Some(true),
             CreateRoleOption::NoLogin => login = Some(false),
             CreateRoleOption::SuperUser => super_user = Some(true),
             CreateRoleOption::NoSuperUser => super_user = Some(false),
+            CreateRoleOption::Password(p) => password = Some(p),
         }
     }
     if is_user && login.is_none() {
         login = Some(true);
     }
-    if login != Some(true) {
-        bail_unsupported!("non-login users");
+    if let Some(Some(password)) = &password {
+        if password.is_empty() {
+            sql_bail!("password must not be empty");
+        }
     }
-    if super_user != Some(true) {
-        bail_unsupported!("non-superusers");
+    let name = normalize::ident(name);
+    if name == PUBLIC_ROLE_NAME {
+        sql_bail!("role name {} is reserved", name.quoted());
     }
     Ok(Plan::CreateRole(CreateRolePlan {
-        name: normalize::ident(name),
+        name,
+        password: password.flatten(),
+        super_user: super_user.unwrap_or(false),
+        login: login.unwrap_or(false),
     }))
 }
 
@@ -3278,6 +3659,248 @@ pub fn plan_drop_role(
     Ok(Plan::DropRoles(DropRolesPlan { names: out }))
 }
 
+/// The name of the pseudo-role of which every role is a member.
+const PUBLIC_ROLE_NAME: &str = "public";
+
+fn plan_role_id(scx: &StatementContext, name: Ident) -> Result<RoleId, PlanError> {
+    let name = normalize::ident(name);
+    if name == PUBLIC_ROLE_NAME {
+        Ok(RoleId::Public)
+    } else {
+        Ok(scx.catalog.resolve_role(&name)?.id())
+    }
+}
+
+fn plan_member_role_ids(
+    scx: &StatementContext,
+    role_names: Vec<Ident>,
+    member_names: Vec<Ident>,
+) -> Result<(Vec<RoleId>, Vec<RoleId>), PlanError> {
+    let mut role_ids = vec![];
+    for name in role_names {
+        match plan_role_id(scx, name)? {
+            RoleId::Public => {
+                sql_bail!("role \"{}\" cannot have explicit members", PUBLIC_ROLE_NAME)
+            }
+            id => role_ids.push(id),
+        }
+    }
+    let mut member_ids = vec![];
+    for name in member_names {
+        match plan_role_id(scx, name)? {
+            RoleId::Public => sql_bail!(
+                "role \"{}\" cannot be a member of other roles",
+                PUBLIC_ROLE_NAME
+            ),
+            id => member_ids.push(id),
+        }
+    }
+    Ok((role_ids, member_ids))
+}
+
+fn plan_privilege_objects(
+    scx: &StatementContext,
+    object_type: GrantObjectType,
+    names: Vec<UnresolvedObjectName>,
+) -> Result<Vec<PrivilegeObject>, PlanError> {
+    let mut objects = vec![];
+    for name in names {
+        let object = match object_type {
+            GrantObjectType::Table => {
+                let item = scx
+                    .catalog
+                    .resolve_item(&normalize::unresolved_object_name(name)?)?;
+                let full_name = scx.catalog.resolve_full_name(item.name());
+                if item.id().is_system() {
+                    sql_bail!(
+                        "cannot change privileges on {} because it is required by the database system",
+                        full_name
+                    );
+                }
+                match item.item_type() {
+                    CatalogItemType::Table
+                    | CatalogItemType::Source
+                    | CatalogItemType::View
+                    | CatalogItemType::MaterializedView => (),
+                    typ => sql_bail!("\"{}\" is a {} not a table", full_name, typ),
+                }
+                PrivilegeObject::Item(item.id())
+            }
+            GrantObjectType::Database => {
+                let name = match name.0.len() {
+                    1 => UnresolvedDatabaseName(name.0.into_element()),
+                    _ => sql_bail!("invalid database name {}", name.to_string().quoted()),
+                };
+                PrivilegeObject::Database(scx.resolve_database(&name)?.id())
+            }
+            GrantObjectType::Schema => {
+                let schema = scx.resolve_schema(UnresolvedSchemaName(name.0))?;
+                match (schema.database(), schema.id()) {
+                    (ResolvedDatabaseSpecifier::Id(_), SchemaSpecifier::Id(id)) => {
+                        PrivilegeObject::Schema(*id)
+                    }
+                    _ => sql_bail!(
+                        "cannot change privileges on schema {} because it is required by the database system",
+                        schema.name().schema
+                    ),
+                }
+            }
+            GrantObjectType::Cluster => {
+                let name = match name.0.len() {
+                    1 => name.0.into_element(),
+                    _ => sql_bail!("invalid cluster name {}", name.to_string().quoted()),
+                };
+                let instance = scx
+                    .catalog
+                    .resolve_compute_instance(Some(normalize::ident_ref(&name)))?;
+                PrivilegeObject::ComputeInstance(instance.id())
+            }
+        };
+        objects.push(object);
+    }
+    Ok(objects)
+}
+
+fn plan_privileges(
+    privileges: PrivilegeSpecification,
+    object_type: GrantObjectType,
+    objects: &[PrivilegeObject],
+) -> Result<Vec<Privilege>, PlanError> {
+    // All objects are of the same type, so any object will do to determine
+    // which privileges are valid.
+    let valid = match objects.first() {
+        Some(object) => Privilege::all_for(object),
+        None => return Ok(vec![]),
+    };
+    match privileges {
+        PrivilegeSpecification::All => Ok(valid.to_vec()),
+        PrivilegeSpecification::Privileges(privileges) => {
+            let mut out = vec![];
+            for privilege in privileges {
+                let privilege = match privilege {
+                    AstPrivilege::Select => Privilege::Select,
+                    AstPrivilege::Insert => Privilege::Insert,
+                    AstPrivilege::Update => Privilege::Update,
+                    AstPrivilege::Delete => Privilege::Delete,
+                    AstPrivilege::Usage => Privilege::Usage,
+                    AstPrivilege::Create => Privilege::Create,
+                };
+                if !valid.contains(&privilege) {
+                    sql_bail!(
+                        "invalid privilege type {} for {}",
+                        privilege,
+                        object_type.to_ast_string().to_lowercase()
+                    );
+                }
+                if !out.contains(&privilege) {
+                    out.push(privilege);
+                }
+            }
+            Ok(out)
+        }
+    }
+}
+
+pub fn describe_grant_privileges(
+    _: &StatementContext,
+    _: GrantPrivilegesStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_grant_privileges(
+    scx: &StatementContext,
+    GrantPrivilegesStatement {
+        privileges,
+        object_type,
+        names,
+        roles,
+    }: GrantPrivilegesStatement,
+) -> Result<Plan, PlanError> {
+    let objects = plan_privilege_objects(scx, object_type, names)?;
+    let privileges = plan_privileges(privileges, object_type, &objects)?;
+    let grantees = roles
+        .into_iter()
+        .map(|role| plan_role_id(scx, role))
+        .collect::<Result<_, _>>()?;
+    Ok(Plan::GrantPrivileges(GrantPrivilegesPlan {
+        privileges,
+        objects,
+        grantees,
+    }))
+}
+
+pub fn describe_revoke_privileges(
+    _: &StatementContext,
+    _: RevokePrivilegesStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_revoke_privileges(
+    scx: &StatementContext,
+    RevokePrivilegesStatement {
+        privileges,
+        object_type,
+        names,
+        roles,
+    }: RevokePrivilegesStatement,
+) -> Result<Plan, PlanError> {
+    let objects = plan_privilege_objects(scx, object_type, names)?;
+    let privileges = plan_privileges(privileges, object_type, &objects)?;
+    let grantees = roles
+        .into_iter()
+        .map(|role| plan_role_id(scx, role))
+        .collect::<Result<_, _>>()?;
+    Ok(Plan::RevokePrivileges(RevokePrivilegesPlan {
+        privileges,
+        objects,
+        grantees,
+    }))
+}
+
+pub fn describe_grant_role(
+    _: &StatementContext,
+    _: GrantRoleStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_grant_role(
+    scx: &StatementContext,
+    GrantRoleStatement {
+        role_names,
+        member_names,
+    }: GrantRoleStatement,
+) -> Result<Plan, PlanError> {
+    let (role_ids, member_ids) = plan_member_role_ids(scx, role_names, member_names)?;
+    Ok(Plan::GrantRole(GrantRolePlan {
+        role_ids,
+        member_ids,
+    }))
+}
+
+pub fn describe_revoke_role(
+    _: &StatementContext,
+    _: RevokeRoleStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_revoke_role(
+    scx: &StatementContext,
+    RevokeRoleStatement {
+        role_names,
+        member_names,
+    }: RevokeRoleStatement,
+) -> Result<Plan, PlanError> {
+    let (role_ids, member_ids) = plan_member_role_ids(scx, role_names, member_names)?;
+    Ok(Plan::RevokeRole(RevokeRolePlan {
+        role_ids,
+        member_ids,
+    }))
+}
+
 pub fn describe_drop_cluster(
     _: &StatementContext,
     _: DropClustersStatement,
@@ -3791,6 +4414,7 @@ pub fn plan_alter_source(
                 timeline: timeline_opt,
                 timestamp_interval: timestamp_interval_opt,
                 ignore_keys: ignore_keys_opt,
+                upsert_state_backend: upsert_state_backend_opt,
             } = CreateSourceOptionExtracted::try_from(options)?;
 
             if let Some(value) = remote_opt {
@@ -3808,6 +4432,9 @@ pub fn plan_alter_source(
             if let Some(_) = ignore_keys_opt {
                 sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
             }
+            if let Some(_) = upsert_state_backend_opt {
+                sql_bail!("Cannot modify the UPSERT STATE BACKEND of a SOURCE.");
+            }
         }
         AlterSourceAction::ResetOptions(reset) => {
             for name in reset {
@@ -3827,6 +4454,9 @@ pub fn plan_alter_source(
                     CreateSourceOptionName::IgnoreKeys => {
                         sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
                     }
+                    CreateSourceOptionName::UpsertStateBackend => {
+                        sql_bail!("Cannot modify the UPSERT STATE BACKEND of a SOURCE.");
+                    }
                 }
             }
         }
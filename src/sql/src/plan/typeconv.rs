@@ -22,7 +22,7 @@ use mz_expr::VariadicFunc;
 use mz_repr::{ColumnName, ColumnType, Datum, RelationType, ScalarBaseType, ScalarType};
 
 use super::error::PlanError;
-use super::expr::{CoercibleScalarExpr, ColumnRef, HirScalarExpr, UnaryFunc};
+use super::expr::{BinaryFunc, CoercibleScalarExpr, ColumnRef, HirScalarExpr, UnaryFunc};
 use super::query::{ExprContext, QueryContext};
 use super::scope::Scope;
 use crate::catalog::TypeCategory;
@@ -39,6 +39,27 @@ fn sql_impl_cast(expr: &'static str) -> CastTemplate {
     })
 }
 
+/// Returns a template for a cast between a timestamp with time zone and a type
+/// without a time zone.
+///
+/// Such casts take place in the session's time zone, as reported by
+/// [`ExprContext::session_timezone`]. If that is UTC, the cast applies `utc`.
+/// Otherwise, the cast is planned by `in_timezone`, which receives the input
+/// and the name of the session's time zone.
+fn timestamptz_cast<F>(utc: UnaryFunc, in_timezone: F) -> CastTemplate
+where
+    F: Fn(HirScalarExpr, HirScalarExpr) -> HirScalarExpr + Copy + Send + Sync + 'static,
+{
+    CastTemplate::new(move |ecx, _ccx, _from_type, _to_type| {
+        let utc = utc.clone();
+        let timezone = ecx.session_timezone();
+        Some(move |e: HirScalarExpr| match timezone {
+            None => e.call_unary(utc),
+            Some(timezone) => in_timezone(e, timezone),
+        })
+    })
+}
+
 /// A cast is a function that takes a `ScalarExpr` to another `ScalarExpr`.
 type Cast = Box<dyn FnOnce(HirScalarExpr) -> HirScalarExpr>;
 
@@ -354,7 +375,9 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
 
         // DATE
         (Date, Timestamp) => Implicit: CastDateToTimestamp(func::CastDateToTimestamp),
-        (Date, TimestampTz) => Implicit: CastDateToTimestampTz(func::CastDateToTimestampTz),
+        (Date, TimestampTz) => Implicit: timestamptz_cast(CastDateToTimestampTz(func::CastDateToTimestampTz), |e, tz| {
+            tz.call_binary(e.call_unary(CastDateToTimestamp(func::CastDateToTimestamp)), BinaryFunc::TimezoneTimestamp)
+        }),
         (Date, String) => Assignment: CastDateToString(func::CastDateToString),
 
         // TIME
@@ -363,15 +386,31 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
 
         // TIMESTAMP
         (Timestamp, Date) => Assignment: CastTimestampToDate(func::CastTimestampToDate),
-        (Timestamp, TimestampTz) => Implicit: CastTimestampToTimestampTz(func::CastTimestampToTimestampTz),
+        (Timestamp, TimestampTz) => Implicit: timestamptz_cast(CastTimestampToTimestampTz(func::CastTimestampToTimestampTz), |e, tz| {
+            tz.call_binary(e, BinaryFunc::TimezoneTimestamp)
+        }),
         (Timestamp, Time) => Assignment: CastTimestampToTime(func::CastTimestampToTime),
         (Timestamp, String) => Assignment: CastTimestampToString(func::CastTimestampToString),
 
         // TIMESTAMPTZ
-        (TimestampTz, Date) => Assignment: CastTimestampTzToDate(func::CastTimestampTzToDate),
-        (TimestampTz, Timestamp) => Assignment: CastTimestampTzToTimestamp(func::CastTimestampTzToTimestamp),
-        (TimestampTz, Time) => Assignment: CastTimestampTzToTime(func::CastTimestampTzToTime),
-        (TimestampTz, String) => Assignment: CastTimestampTzToString(func::CastTimestampTzToString),
+        (TimestampTz, Date) => Assignment: timestamptz_cast(CastTimestampTzToDate(func::CastTimestampTzToDate), |e, tz| {
+            tz.call_binary(e, BinaryFunc::TimezoneTimestampTz).call_unary(CastTimestampToDate(func::CastTimestampToDate))
+        }),
+        (TimestampTz, Timestamp) => Assignment: timestamptz_cast(CastTimestampTzToTimestamp(func::CastTimestampTzToTimestamp), |e, tz| {
+            tz.call_binary(e, BinaryFunc::TimezoneTimestampTz)
+        }),
+        (TimestampTz, Time) => Assignment: timestamptz_cast(CastTimestampTzToTime(func::CastTimestampTzToTime), |e, tz| {
+            tz.call_binary(e, BinaryFunc::TimezoneTimestampTz).call_unary(CastTimestampToTime(func::CastTimestampToTime))
+        }),
+        (TimestampTz, String) => Assignment: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+            // Render the timestamp in the session's time zone, like the
+            // text encoding of timestamps sent to clients.
+            let func = match ecx.session_timezone_value() {
+                None => CastTimestampTzToString(func::CastTimestampTzToString),
+                Some(tz) => CastTimestampTzToStringInTimezone(func::CastTimestampTzToStringInTimezone(tz)),
+            };
+            Some(move |e: HirScalarExpr| e.call_unary(func))
+        }),
 
         // INTERVAL
         (Interval, Time) => Assignment: CastIntervalToTime(func::CastIntervalToTime),
@@ -455,6 +494,7 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         (String, Interval) => Explicit: CastStringToInterval(func::CastStringToInterval),
         (String, Bytes) => Explicit: CastStringToBytes(func::CastStringToBytes),
         (String, Jsonb) => Explicit: CastStringToJsonb(func::CastStringToJsonb),
+        (String, JsonPath) => Explicit: CastStringToJsonPath(func::CastStringToJsonPath),
         (String, Uuid) => Explicit: CastStringToUuid(func::CastStringToUuid),
         (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
             let return_ty = to_type.clone();
@@ -483,6 +523,15 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
                 cast_expr: Box::new(cast_expr),
             })))
         }),
+        (String, Range) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
+            let return_ty = to_type.clone();
+            let to_el_type = to_type.unwrap_range_element_type();
+            let cast_expr = plan_hypothetical_cast(ecx, ccx, from_type, to_el_type)?;
+            Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastStringToRange(func::CastStringToRange {
+                return_ty,
+                cast_expr: Box::new(cast_expr),
+            })))
+        }),
         (String, Int2Vector) => Explicit: CastStringToInt2Vector(func::CastStringToInt2Vector),
         (String, Char) => Implicit: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
             let length = to_type.unwrap_char_length();
@@ -562,6 +611,12 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         }),
         (Array, List) => Explicit: CastArrayToListOneDim(func::CastArrayToListOneDim),
 
+        // RANGE
+        (Range, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+            let ty = from_type.clone();
+            Some(|e: HirScalarExpr| e.call_unary(CastRangeToString(func::CastRangeToString { ty })))
+        }),
+
         // INT2VECTOR
         (Int2Vector, Array) => Implicit: CastTemplate::new(|_ecx, _ccx, _from_type, _to_type| {
             Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastInt2VectorToArray(func::CastInt2VectorToArray)))
@@ -611,6 +666,9 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         }),
         (Jsonb, String) => Assignment: CastJsonbToString(func::CastJsonbToString),
 
+        // JSONPATH
+        (JsonPath, String) => Assignment: CastJsonPathToString(func::CastJsonPathToString),
+
         // UUID
         (Uuid, String) => Assignment: CastUuidToString(func::CastUuidToString),
 
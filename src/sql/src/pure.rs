@@ -17,6 +17,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
+use globset::GlobBuilder;
 use mz_repr::adt::system::Oid;
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
@@ -34,19 +35,21 @@ use mz_repr::{strconv, GlobalId};
 use mz_secrets::SecretsReader;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    ColumnDef, ColumnOption, ColumnOptionDef, CsrConnection, CsrSeedAvro, CsrSeedProtobuf,
-    CsrSeedProtobufSchema, DbzMode, DeferredObjectName, Envelope, Ident, KafkaConfigOption,
-    KafkaConfigOptionName, KafkaConnection, KafkaSourceConnection, PgConfigOption,
-    PgConfigOptionName, ReaderSchemaSelectionStrategy, TableConstraint, UnresolvedObjectName,
+    ColumnDef, ColumnOption, ColumnOptionDef, Compression, CsrConnection, CsrSeedAvro,
+    CsrSeedProtobuf, CsrSeedProtobufSchema, DbzMode, DeferredObjectName, Envelope, Ident,
+    KafkaConfigOption, KafkaConfigOptionName, KafkaConnection, KafkaSourceConnection,
+    PgConfigOption, PgConfigOptionName, ReaderSchemaSelectionStrategy, S3KeySource,
+    TableConstraint, UnresolvedObjectName,
 };
 use mz_storage_client::types::connections::aws::AwsConfig;
 use mz_storage_client::types::connections::{Connection, ConnectionContext};
 use mz_storage_client::types::sources::PostgresSourcePublicationDetails;
 
 use crate::ast::{
-    AvroSchema, CreateReferencedSubsources, CreateSourceConnection, CreateSourceFormat,
-    CreateSourceStatement, CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro,
-    CsrConnectionProtobuf, CsvColumns, Format, ProtobufSchema, Value, WithOptionValue,
+    AvroSchema, CreateReferencedSubsources, CreateSinkConnection, CreateSinkStatement,
+    CreateSourceConnection, CreateSourceFormat, CreateSourceStatement, CreateSourceSubsource,
+    CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf, CsvColumns, Format,
+    ProtobufSchema, Value, WithOptionValue,
 };
 use crate::catalog::{ErsatzCatalog, SessionCatalog};
 use crate::kafka_util;
@@ -56,6 +59,7 @@ use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::statement::ddl::load_generator_ast_to_generator;
 use crate::plan::StatementContext;
+use crate::s3_util;
 
 fn subsource_gen<'a, T>(
     selected_subsources: &mut Vec<CreateSourceSubsource<Aug>>,
@@ -158,15 +162,26 @@ pub async fn purify_create_source(
                 connection.options.insert(k, v);
             }
 
-            let topic = extracted_options
-                .topic
-                .ok_or_else(|| sql_err!("KAFKA CONNECTION without TOPIC"))?;
+            // Sources read either from a single topic or from every topic that
+            // matches a pattern, which may not exist yet.
+            let topic = match (extracted_options.topic, extracted_options.topic_pattern) {
+                (Some(topic), None) => Some(topic),
+                (None, Some(_)) => None,
+                (Some(_), Some(_)) => sql_bail!("cannot specify both TOPIC and TOPIC PATTERN"),
+                (None, None) => sql_bail!("KAFKA CONNECTION without TOPIC or TOPIC PATTERN"),
+            };
 
-            let consumer = kafka_util::create_consumer(&connection_context, &connection, &topic)
-                .await
-                .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
+            let consumer =
+                kafka_util::create_consumer(&connection_context, &connection, topic.as_deref())
+                    .await
+                    .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
 
             if let Some(offset_type) = offset_type {
+                let Some(topic) = topic else {
+                    sql_bail!(
+                        "START OFFSET and START TIMESTAMP are not supported with TOPIC PATTERN"
+                    );
+                };
                 // Translate `START TIMESTAMP` to a start offset
                 match kafka_util::lookup_start_offsets(
                     Arc::clone(&consumer),
@@ -628,6 +643,70 @@ pub async fn purify_create_source(
     Ok((subsources, stmt))
 }
 
+/// Purifies a statement, removing any dependencies on external state.
+///
+/// For sinks, this fetches the Protobuf schemas of sinks that read their
+/// schemas from a Confluent Schema Registry.
+pub async fn purify_create_sink(
+    catalog: Box<dyn SessionCatalog>,
+    mut stmt: CreateSinkStatement<Aug>,
+    connection_context: ConnectionContext,
+) -> Result<CreateSinkStatement<Aug>, PlanError> {
+    let CreateSinkStatement {
+        connection, format, ..
+    } = &mut stmt;
+
+    let (options, key) = match connection {
+        CreateSinkConnection::Kafka {
+            connection: KafkaConnection { options, .. },
+            key,
+        } => (options, key),
+        // Sinks into S3 do not reference any external schemas.
+        CreateSinkConnection::S3 { .. } => return Ok(stmt),
+    };
+
+    if let Some(Format::Protobuf(ProtobufSchema::Csr {
+        csr_connection:
+            CsrConnectionProtobuf {
+                seed: seed @ None,
+                connection: CsrConnection { connection, .. },
+            },
+    })) = format
+    {
+        let KafkaConfigOptionExtracted { topic, .. } = options.clone().try_into()?;
+        let topic = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
+
+        let scx = StatementContext::new(None, &*catalog);
+        let ccsr_connection = match scx.get_item_by_resolved_name(connection)?.connection()? {
+            Connection::Csr(connection) => connection.clone(),
+            _ => sql_bail!("{} is not a schema registry connection", connection),
+        };
+        let ccsr_client = ccsr_connection
+            .connect(&*connection_context.secrets_reader)
+            .await?;
+
+        // Record the IDs of the schemas, so that the sink tags its messages
+        // with the schemas it was planned against, even if newer versions of
+        // them are registered later.
+        let (mut value, value_id) =
+            compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
+        value.schema_id = Some(value_id);
+        let key = match key {
+            Some(_) => {
+                let (mut key, key_id) =
+                    compile_proto(&format!("{}-key", topic), &ccsr_client).await?;
+                key.schema_id = Some(key_id);
+                Some(key)
+            }
+            None => None,
+        };
+
+        *seed = Some(CsrSeedProtobuf { value, key });
+    }
+
+    Ok(stmt)
+}
+
 async fn purify_source_format(
     catalog: &dyn SessionCatalog,
     format: &mut CreateSourceFormat<Aug>,
@@ -711,11 +790,99 @@ async fn purify_source_format_single(
                 }
             }
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Parquet { columns } => match connection {
+            CreateSourceConnection::S3 {
+                connection,
+                key_sources,
+                pattern,
+                compression,
+            } => {
+                if columns.is_empty() {
+                    *columns = purify_parquet_columns(
+                        catalog,
+                        connection,
+                        key_sources,
+                        pattern.as_deref(),
+                        compression,
+                        connection_context,
+                    )
+                    .await?;
+                }
+            }
+            _ => sql_bail!("FORMAT PARQUET is only supported for S3 sources"),
+        },
+        Format::Bytes | Format::Regex(_) | Format::Json { .. } | Format::Text => (),
     }
     Ok(())
 }
 
+/// Infers the columns of a `FORMAT PARQUET` S3 source from the footer of one
+/// of the objects that the source will read.
+async fn purify_parquet_columns(
+    catalog: &dyn SessionCatalog,
+    connection: &ResolvedObjectName,
+    key_sources: &[S3KeySource],
+    pattern: Option<&str>,
+    compression: &Compression,
+    connection_context: &ConnectionContext,
+) -> Result<Vec<ColumnDef<Aug>>, PlanError> {
+    if *compression != Compression::None {
+        sql_bail!(
+            "cannot infer the columns of FORMAT PARQUET for compressed objects; \
+             specify the columns explicitly"
+        );
+    }
+    let Some(bucket) = key_sources.iter().find_map(|key_source| match key_source {
+        S3KeySource::Scan { bucket } => Some(bucket),
+        S3KeySource::SqsNotifications { .. } => None,
+    }) else {
+        sql_bail!(
+            "cannot infer the columns of FORMAT PARQUET without a BUCKET SCAN; \
+             specify the columns explicitly"
+        );
+    };
+    let glob = pattern
+        .map(|p| {
+            GlobBuilder::new(p)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()
+                .map(|glob| glob.compile_matcher())
+        })
+        .transpose()
+        .map_err(|e| sql_err!("parsing glob: {e}"))?;
+
+    let scx = StatementContext::new(None, &*catalog);
+    let aws = {
+        let item = scx.get_item_by_resolved_name(connection)?;
+        match item.connection()? {
+            Connection::Aws(aws) => aws.clone(),
+            _ => sql_bail!("{} is not an AWS connection", item.name()),
+        }
+    };
+    let config = aws
+        .load(
+            connection_context.aws_external_id_prefix.as_ref(),
+            None,
+            &*connection_context.secrets_reader,
+        )
+        .await;
+    let client = aws_sdk_s3::Client::new(&config);
+
+    let columns = s3_util::infer_parquet_columns(&client, bucket, glob.as_ref()).await?;
+    columns
+        .into_iter()
+        .map(|(name, typ)| {
+            Ok(ColumnDef {
+                name: Ident::new(name),
+                data_type: scx.resolve_type(typ)?,
+                collation: None,
+                options: vec![],
+            })
+        })
+        .collect()
+}
+
 async fn purify_csr_connection_proto(
     catalog: &dyn SessionCatalog,
     connection: &mut CreateSourceConnection<Aug>,
@@ -732,7 +899,10 @@ async fn purify_csr_connection_proto(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        match topic {
+            Some(topic) => topic,
+            None => sql_bail!("CONFLUENT SCHEMA REGISTRY is not supported with TOPIC PATTERN"),
+        }
     } else {
         sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
@@ -757,10 +927,11 @@ async fn purify_csr_connection_proto(
                 .connect(&*connection_context.secrets_reader)
                 .await?;
 
-            let value = compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
+            let (value, _) = compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
             let key = compile_proto(&format!("{}-key", topic), &ccsr_client)
                 .await
-                .ok();
+                .ok()
+                .map(|(key, _)| key);
 
             if matches!(envelope, Some(Envelope::Debezium(DbzMode::Plain))) && key.is_none() {
                 sql_bail!("Key schema is required for ENVELOPE DEBEZIUM");
@@ -790,7 +961,10 @@ async fn purify_csr_connection_avro(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        match topic {
+            Some(topic) => topic,
+            None => sql_bail!("CONFLUENT SCHEMA REGISTRY is not supported with TOPIC PATTERN"),
+        }
     } else {
         sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
@@ -890,7 +1064,7 @@ async fn get_remote_csr_schema(
 async fn compile_proto(
     subject_name: &String,
     ccsr_client: &Client,
-) -> Result<CsrSeedProtobufSchema, PlanError> {
+) -> Result<(CsrSeedProtobufSchema, i32), PlanError> {
     let (primary_subject, dependency_subjects) = ccsr_client
         .get_subject_and_references(subject_name)
         .await
@@ -928,10 +1102,12 @@ async fn compile_proto(
     let mut schema = String::new();
     strconv::format_bytes(&mut schema, bytes);
 
-    Ok(CsrSeedProtobufSchema {
+    let seed = CsrSeedProtobufSchema {
         schema,
         message_name,
-    })
+        schema_id: None,
+    };
+    Ok((seed, primary_subject.schema.id))
 }
 
 /// Makes an always-valid AWS API call to perform a basic sanity check of
@@ -0,0 +1,151 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Provides convenience functions for working with S3 from the `sql` package.
+
+use std::io::Cursor;
+
+use anyhow::Context;
+use arrow2::datatypes::DataType;
+use arrow2::io::parquet::read::{infer_schema, read_metadata};
+use globset::GlobMatcher;
+
+use mz_ore::str::StrExt;
+
+use crate::plan::PlanError;
+
+/// The magic bytes that begin and end every Parquet file.
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Infers the columns of a `FORMAT PARQUET` source from the schema of the
+/// first object in `bucket` that matches `glob`.
+///
+/// Only the footer of the object, which holds its schema, is downloaded.
+pub async fn infer_parquet_columns(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    glob: Option<&GlobMatcher>,
+) -> Result<Vec<(String, mz_pgrepr::Type)>, PlanError> {
+    let key = find_object(client, bucket, glob).await?;
+
+    // A Parquet file ends with its footer, followed by the length of the
+    // footer as a four-byte little-endian integer and the magic bytes.
+    let tail = get_object_suffix(client, bucket, &key, 8).await?;
+    if tail.len() != 8 || &tail[4..] != PARQUET_MAGIC {
+        sql_bail!("S3 object {} is not a Parquet file", key.quoted());
+    }
+    let footer_len = u32::from_le_bytes(tail[..4].try_into().expect("slice has length 4"));
+    let footer = get_object_suffix(client, bucket, &key, u64::from(footer_len) + 8).await?;
+
+    // Present the footer to the Parquet reader as a file of its own by
+    // prefixing it with the magic bytes that begin every Parquet file. The
+    // reader only consults the end of the file to read its metadata.
+    let mut file = PARQUET_MAGIC.to_vec();
+    file.extend(footer);
+    let metadata = read_metadata(&mut Cursor::new(file))
+        .map_err(|e| sql_err!("reading schema of Parquet object {}: {}", key.quoted(), e))?;
+    let schema = infer_schema(&metadata)
+        .map_err(|e| sql_err!("reading schema of Parquet object {}: {}", key.quoted(), e))?;
+
+    schema
+        .fields
+        .into_iter()
+        .map(|field| {
+            let Some(typ) = parquet_column_type(field.data_type.to_logical_type()) else {
+                sql_bail!(
+                    "cannot infer the type of column {} of Parquet object {}: \
+                     Parquet type {:?} is not supported; specify the columns of \
+                     FORMAT PARQUET explicitly",
+                    field.name.quoted(),
+                    key.quoted(),
+                    field.data_type
+                );
+            };
+            Ok((field.name, typ))
+        })
+        .collect()
+}
+
+/// Returns the SQL type that a Parquet column of type `data_type` is read as
+/// when the columns of a source are inferred.
+fn parquet_column_type(data_type: &DataType) -> Option<mz_pgrepr::Type> {
+    Some(match data_type {
+        DataType::Boolean => mz_pgrepr::Type::Bool,
+        DataType::Int8 | DataType::UInt8 | DataType::Int16 => mz_pgrepr::Type::Int2,
+        DataType::UInt16 | DataType::Int32 => mz_pgrepr::Type::Int4,
+        DataType::UInt32 | DataType::Int64 => mz_pgrepr::Type::Int8,
+        DataType::Float32 => mz_pgrepr::Type::Float4,
+        DataType::Float64 => mz_pgrepr::Type::Float8,
+        DataType::Decimal(_, _) => mz_pgrepr::Type::Numeric { constraints: None },
+        DataType::Utf8 | DataType::LargeUtf8 => mz_pgrepr::Type::Text,
+        DataType::Binary | DataType::LargeBinary => mz_pgrepr::Type::Bytea,
+        DataType::Date32 => mz_pgrepr::Type::Date,
+        DataType::Timestamp(_, None) => mz_pgrepr::Type::Timestamp { precision: None },
+        DataType::Timestamp(_, Some(_)) => mz_pgrepr::Type::TimestampTz { precision: None },
+        _ => return None,
+    })
+}
+
+/// Returns the key of the first object in `bucket` that matches `glob`.
+async fn find_object(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    glob: Option<&GlobMatcher>,
+) -> Result<String, PlanError> {
+    let mut continuation_token = None;
+    loop {
+        let response = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .with_context(|| format!("listing objects in S3 bucket {}", bucket.quoted()))?;
+        let key = response
+            .contents
+            .into_iter()
+            .flatten()
+            .filter_map(|object| object.key)
+            .find(|key| glob.map(|g| g.is_match(key)).unwrap_or(true));
+        if let Some(key) = key {
+            return Ok(key);
+        }
+        continuation_token = match response.next_continuation_token {
+            Some(token) => Some(token),
+            None => sql_bail!(
+                "cannot infer the columns of FORMAT PARQUET: no objects in S3 bucket {} \
+                 match the source",
+                bucket.quoted()
+            ),
+        };
+    }
+}
+
+/// Downloads the last `len` bytes of the object `key` in `bucket`.
+async fn get_object_suffix(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    len: u64,
+) -> Result<Vec<u8>, PlanError> {
+    let object = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .range(format!("bytes=-{}", len))
+        .send()
+        .await
+        .with_context(|| format!("downloading S3 object {}", key.quoted()))?;
+    let body = object
+        .body
+        .collect()
+        .await
+        .with_context(|| format!("downloading S3 object {}", key.quoted()))?;
+    Ok(body.into_bytes().to_vec())
+}
@@ -344,6 +344,7 @@ impl<'a> FromSql<'a> for Slt {
             PgType::INT8 => Self(Value::Int8(types::int8_from_sql(raw)?)),
             PgType::INTERVAL => Self(Value::Interval(Interval::from_sql(ty, raw)?)),
             PgType::JSONB => Self(Value::Jsonb(Jsonb::from_sql(ty, raw)?)),
+            PgType::JSONPATH => Self(Value::decode_binary(&mz_pgrepr::Type::JsonPath, raw)?),
             PgType::NUMERIC => Self(Value::Numeric(Numeric::from_sql(ty, raw)?)),
             PgType::OID => Self(Value::Oid(types::oid_from_sql(raw)?)),
             PgType::REGCLASS => Self(Value::Oid(types::oid_from_sql(raw)?)),
@@ -402,6 +403,10 @@ impl<'a> FromSql<'a> for Slt {
                         elements,
                     })
                 }
+                PgKind::Range(_) => {
+                    let typ = mz_pgrepr::Type::from_oid(ty.oid())?;
+                    Self(Value::decode_binary(&typ, raw)?)
+                }
                 _ => match ty.oid() {
                     oid::TYPE_UINT2_OID => {
                         let v = raw.get_u16();
@@ -436,7 +441,7 @@ impl<'a> FromSql<'a> for Slt {
     }
     fn accepts(ty: &PgType) -> bool {
         match ty.kind() {
-            PgKind::Array(_) | PgKind::Composite(_) => return true,
+            PgKind::Array(_) | PgKind::Composite(_) | PgKind::Range(_) => return true,
             _ => {}
         }
         match ty.oid() {
@@ -459,6 +464,7 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::INT8
                 | PgType::INTERVAL
                 | PgType::JSONB
+                | PgType::JSONPATH
                 | PgType::NUMERIC
                 | PgType::OID
                 | PgType::REGCLASS
@@ -649,6 +655,8 @@ impl Runner {
                 suppress_output: false,
                 data_dir: temp_dir.path().to_path_buf(),
                 command_wrapper: vec![],
+                cgroup_root: None,
+                secrets_master_key: None,
             })
             .await?,
         );
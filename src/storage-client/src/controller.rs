@@ -402,7 +402,7 @@ impl<T: Timestamp> ReadPolicy<T> {
 }
 
 /// Metadata required by a storage instance to read a storage collection
-#[derive(Arbitrary, Clone, Debug, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize)]
+#[derive(Arbitrary, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CollectionMetadata {
     /// The persist location where the shards are located
     pub persist_location: PersistLocation,
@@ -412,6 +412,9 @@ pub struct CollectionMetadata {
     pub data_shard: ShardId,
     /// The persist shard containing the status updates for this storage collection
     pub status_shard: Option<ShardId>,
+    /// The schema of the contents of this storage collection, used to write
+    /// them to persist with structured keys
+    pub relation_desc: RelationDesc,
 }
 
 impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
@@ -422,6 +425,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             data_shard: self.data_shard.to_string(),
             remap_shard: self.remap_shard.to_string(),
             status_shard: self.status_shard.map(|s| s.to_string()),
+            relation_desc: Some(self.relation_desc.into_proto()),
         }
     }
 
@@ -443,6 +447,9 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
                 .status_shard
                 .map(|s| s.parse().map_err(TryFromProtoError::InvalidShardId))
                 .transpose()?,
+            relation_desc: value
+                .relation_desc
+                .into_rust_if_some("ProtoCollectionMetadata::relation_desc")?,
         })
     }
 }
@@ -872,6 +879,7 @@ where
                 remap_shard: collection_shards.remap_shard,
                 data_shard: collection_shards.data_shard,
                 status_shard,
+                relation_desc: description.desc.clone(),
             };
 
             // should be replaced with real introspection (https://github.com/MaterializeInc/materialize/issues/14266)
@@ -890,10 +898,11 @@ where
                 .unwrap();
 
             let purpose = format!("controller data {}", id);
-            let write = persist_client
-                .open_writer(metadata.data_shard, &purpose)
+            let mut write = persist_client
+                .open_writer::<SourceData, (), T, Diff>(metadata.data_shard, &purpose)
                 .await
                 .expect("invalid persist usage");
+            write.set_key_schema(Arc::new(metadata.relation_desc.clone()));
 
             // Construct the handle in a separate block to ensure all error paths are diverging
             let since_handle = {
@@ -1479,6 +1488,7 @@ where
         orchestrator: Arc<dyn NamespacedOrchestrator>,
         storaged_image: String,
         init_container_image: Option<String>,
+        persist_write_structured_keys: bool,
         now: NowFn,
         postgres_factory: &PostgresFactory,
         envd_epoch: NonZeroI64,
@@ -1494,6 +1504,7 @@ where
                     orchestrator,
                     storaged_image,
                     init_container_image,
+                    persist_write_structured_keys,
                 },
                 Arc::clone(&persist_clients),
             ),
@@ -55,6 +55,8 @@ pub struct StorageHostsConfig {
     pub storaged_image: String,
     /// The init container image to use for storaged.
     pub init_container_image: Option<String>,
+    /// Whether storage hosts write structured keys to persist.
+    pub persist_write_structured_keys: bool,
 }
 
 /// Manages provisioning of storage hosts and assignment of storage objects
@@ -71,6 +73,8 @@ pub struct StorageHosts<T> {
     storaged_image: String,
     /// The init container image to use for storaged.
     init_container_image: Option<String>,
+    /// Whether storage hosts write structured keys to persist.
+    persist_write_structured_keys: bool,
     /// The known storage hosts, identified by network address.
     hosts: HashMap<StorageHostAddr, StorageHost<T>>,
     /// The assignment of storage objects to storage hosts.
@@ -107,6 +111,7 @@ where
             orchestrator: config.orchestrator,
             storaged_image: config.storaged_image,
             init_container_image: config.init_container_image,
+            persist_write_structured_keys: config.persist_write_structured_keys,
             objects: HashMap::new(),
             hosts: HashMap::new(),
             initialized: false,
@@ -241,7 +246,7 @@ where
                     image: self.storaged_image.clone(),
                     init_container_image: self.init_container_image.clone(),
                     args: &|assigned| {
-                        vec![
+                        let mut storage_opts = vec![
                             format!("--workers={}", allocation.workers),
                             format!(
                                 "--controller-listen-addr={}:{}",
@@ -252,7 +257,11 @@ where
                                 assigned.listen_host, assigned.ports["internal-http"]
                             ),
                             format!("--opentelemetry-resource=storage_id={}", id),
-                        ]
+                        ];
+                        if self.persist_write_structured_keys {
+                            storage_opts.push("--persist-write-structured-keys".into());
+                        }
+                        storage_opts
                     },
                     ports: vec![
                         ServicePort {
@@ -33,6 +33,7 @@ pub async fn build_sink_connection(
 ) -> Result<StorageSinkConnection, anyhow::Error> {
     match builder {
         StorageSinkConnectionBuilder::Kafka(k) => build_kafka(k, connection_context).await,
+        StorageSinkConnectionBuilder::S3(s3) => Ok(StorageSinkConnection::S3(s3)),
     }
 }
 
@@ -211,7 +212,7 @@ async fn build_kafka(
     .await
     .context("error registering kafka topic for sink")?;
 
-    let published_schema_info = match builder.format {
+    let (published_schema_info, protobuf_format) = match builder.format {
         KafkaSinkFormat::Avro {
             key_schema,
             value_schema,
@@ -231,12 +232,20 @@ async fn build_kafka(
             )
             .await
             .context("error publishing kafka schemas for sink")?;
-            Some(PublishedSchemaInfo {
+            let published_schema_info = PublishedSchemaInfo {
                 key_schema_id,
                 value_schema_id,
-            })
+            };
+            (Some(published_schema_info), None)
         }
-        KafkaSinkFormat::Json => None,
+        KafkaSinkFormat::Json => (None, None),
+        // Protobuf schemas are not published by the sink. Purification
+        // recorded the IDs of the registered schemas the sink was planned
+        // against.
+        KafkaSinkFormat::Protobuf {
+            format,
+            published_schema_info,
+        } => (published_schema_info, Some(format)),
     };
 
     let progress = match builder.consistency_config {
@@ -263,6 +272,7 @@ async fn build_kafka(
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
         published_schema_info,
+        protobuf_format,
         progress,
         fuel: builder.fuel,
     }))
@@ -10,11 +10,15 @@
 //! A source that reads from an a persist shard.
 
 use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::mem::discriminant;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Instant;
 
+use arrow2::bitmap::Bitmap;
 use differential_dataflow::Hashable;
 use futures::Stream as FuturesStream;
 use timely::dataflow::channels::pact::Exchange;
@@ -26,18 +30,20 @@ use timely::PartialOrder;
 use tokio::sync::{mpsc, Mutex};
 use tracing::trace;
 
-use mz_expr::MfpPlan;
+use mz_expr::{BinaryFunc, MfpPlan, MirScalarExpr, UnaryFunc};
 use mz_ore::cast::CastFrom;
 use mz_ore::vec::VecExt;
 use mz_persist::location::ExternalError;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::fetch::SerdeLeasedBatchPart;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_persist_types::columnar::{Part, PartDecoder, PartStats};
+use mz_persist_types::Codec;
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 
 use crate::controller::CollectionMetadata;
 use crate::types::errors::DataflowError;
-use crate::types::sources::SourceData;
+use crate::types::sources::{SourceData, SourceDataColumnarDecoder, SOURCE_DATA_ERROR_COLUMN};
 
 /// Creates a new source that reads from a persist shard, distributing the work
 /// of reading data to all timely workers.
@@ -100,6 +106,238 @@ where
     (ok_stream, err_stream, token)
 }
 
+/// The parts of an [MfpPlan] that can be applied to the structured columnar
+/// encoding of [SourceData], before any rows are decoded.
+#[derive(Debug)]
+struct StructuredPushdown {
+    /// The number of columns in the rows the MFP is applied to.
+    arity: usize,
+    /// The columns used by the MFP. All others are decoded as nulls.
+    demand: HashSet<usize>,
+    /// Predicates of the form `column <op> literal`, with `op` expressed as a
+    /// test of the [Ordering] of the column's value relative to the literal.
+    filters: Vec<(usize, Row, fn(Ordering) -> bool)>,
+    /// The MFP's temporal lower bounds, if its other expressions can't error.
+    lower_bounds: Vec<MirScalarExpr>,
+    /// The MFP's temporal upper bounds, if its other expressions can't error.
+    upper_bounds: Vec<MirScalarExpr>,
+}
+
+impl StructuredPushdown {
+    fn new(mfp: &MfpPlan) -> Self {
+        let nontemporal = mfp.nontemporal();
+        let mut filters = Vec::new();
+        let mut lower_bounds = Vec::new();
+        let mut upper_bounds = Vec::new();
+        // Skipping an update the MFP would filter out is only correct if the
+        // MFP couldn't have produced an error for it instead. The temporal
+        // bounds are only evaluated for updates that pass the predicates, so
+        // they don't matter here.
+        if !nontemporal.could_error() {
+            for (_, predicate) in nontemporal.predicates.iter() {
+                if let Some(filter) = Self::filter(predicate, nontemporal.input_arity) {
+                    filters.push(filter);
+                }
+            }
+            lower_bounds = mfp.lower_bounds().to_vec();
+            upper_bounds = mfp.upper_bounds().to_vec();
+        }
+        StructuredPushdown {
+            arity: nontemporal.input_arity,
+            demand: mfp.demand(),
+            filters,
+            lower_bounds,
+            upper_bounds,
+        }
+    }
+
+    /// Returns the input column that `bound` is a non-decreasing function of,
+    /// if it is one we recognize.
+    ///
+    /// The recognized functions only error on values below or above some
+    /// threshold, so if they don't error on the smallest and largest values
+    /// of the column, they don't error on any of them.
+    fn monotone_column(bound: &MirScalarExpr, input_arity: usize) -> Option<usize> {
+        match bound {
+            MirScalarExpr::Column(column) if *column < input_arity => Some(*column),
+            MirScalarExpr::CallUnary {
+                func:
+                    UnaryFunc::CastUint64ToMzTimestamp(_)
+                    | UnaryFunc::CastUint32ToMzTimestamp(_)
+                    | UnaryFunc::CastInt64ToMzTimestamp(_)
+                    | UnaryFunc::CastInt32ToMzTimestamp(_)
+                    | UnaryFunc::CastTimestampToMzTimestamp(_)
+                    | UnaryFunc::CastTimestampTzToMzTimestamp(_)
+                    | UnaryFunc::StepMzTimestamp(_),
+                expr,
+            } => Self::monotone_column(expr, input_arity),
+            _ => None,
+        }
+    }
+
+    /// Returns the column, literal, and comparison for `predicate`, if it
+    /// compares an input column to a literal.
+    fn filter(
+        predicate: &MirScalarExpr,
+        input_arity: usize,
+    ) -> Option<(usize, Row, fn(Ordering) -> bool)> {
+        let (func, expr1, expr2) = match predicate {
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => (func, expr1, expr2),
+            _ => return None,
+        };
+        let (column, literal, flipped) = match (&**expr1, &**expr2) {
+            (MirScalarExpr::Column(column), MirScalarExpr::Literal(Ok(literal), _)) => {
+                (*column, literal, false)
+            }
+            (MirScalarExpr::Literal(Ok(literal), _), MirScalarExpr::Column(column)) => {
+                (*column, literal, true)
+            }
+            _ => return None,
+        };
+        // Columns past the input arity are computed by the MFP's map.
+        if column >= input_arity {
+            return None;
+        }
+        let cmp: fn(Ordering) -> bool = match (func, flipped) {
+            (BinaryFunc::Eq, _) => Ordering::is_eq,
+            (BinaryFunc::NotEq, _) => Ordering::is_ne,
+            (BinaryFunc::Lt, false) | (BinaryFunc::Gt, true) => Ordering::is_lt,
+            (BinaryFunc::Lte, false) | (BinaryFunc::Gte, true) => Ordering::is_le,
+            (BinaryFunc::Gt, false) | (BinaryFunc::Lt, true) => Ordering::is_gt,
+            (BinaryFunc::Gte, false) | (BinaryFunc::Lte, true) => Ordering::is_ge,
+            _ => return None,
+        };
+        Some((column, literal.clone(), cmp))
+    }
+
+    /// Returns a decoder for the demanded columns of `part`, along with a mask
+    /// of the updates that pass all of the filters that could be evaluated on
+    /// the columns.
+    fn decoder(
+        &self,
+        part: &Part,
+    ) -> Result<(Box<dyn PartDecoder<SourceData>>, Option<Bitmap>), String> {
+        let part = part.project(|field| match field.name.parse::<usize>() {
+            Ok(column) => self.demand.contains(&column),
+            // Keep the error column.
+            Err(_) => true,
+        });
+        let decoder = SourceDataColumnarDecoder::new(self.arity, &part)?;
+        let mut mask: Option<Bitmap> = None;
+        for (column, literal, cmp) in self.filters.iter() {
+            if let Some(filtered) = decoder.filter(*column, literal.unpack_first(), *cmp) {
+                mask = Some(match mask {
+                    Some(mask) => &mask & &filtered,
+                    None => filtered,
+                });
+            }
+        }
+        Ok((Box::new(decoder), mask))
+    }
+
+    /// Returns whether the [PartStats] of a part show that the MFP, applied to
+    /// the part's updates with times advanced to `as_of` and beyond `until`
+    /// dropped, produces nothing at all.
+    fn can_skip(
+        &self,
+        stats: &PartStats,
+        as_of: &Antichain<Timestamp>,
+        until: &Antichain<Timestamp>,
+    ) -> bool {
+        // Errors are always passed through, so only parts without any can be
+        // skipped.
+        match stats.columns.get(SOURCE_DATA_ERROR_COLUMN) {
+            Some(err) if err.null_count == stats.len => {}
+            _ => return false,
+        }
+        let column_bounds = |column: usize| {
+            let column_stats = stats.columns.get(&column.to_string())?;
+            let lower = Row::decode(column_stats.lower.as_ref()?).ok()?;
+            let upper = Row::decode(column_stats.upper.as_ref()?).ok()?;
+            Some((lower, upper))
+        };
+
+        for (column, literal, cmp) in self.filters.iter() {
+            // A comparison with null is never true.
+            if stats
+                .columns
+                .get(&column.to_string())
+                .map_or(false, |x| x.null_count == stats.len)
+            {
+                return true;
+            }
+            let Some((lower, upper)) = column_bounds(*column) else {
+                continue;
+            };
+            let (lower, upper, literal) = (
+                lower.unpack_first(),
+                upper.unpack_first(),
+                literal.unpack_first(),
+            );
+            if discriminant(&lower) != discriminant(&literal)
+                || discriminant(&upper) != discriminant(&literal)
+            {
+                continue;
+            }
+            // Every value in the column is between `lower` and `upper`, which
+            // limits how it can compare to the literal.
+            let possible = match (lower.cmp(&literal), upper.cmp(&literal)) {
+                (Ordering::Greater, _) => vec![Ordering::Greater],
+                (_, Ordering::Less) => vec![Ordering::Less],
+                (Ordering::Equal, Ordering::Equal) => vec![Ordering::Equal],
+                (Ordering::Equal, _) => vec![Ordering::Equal, Ordering::Greater],
+                (_, Ordering::Equal) => vec![Ordering::Less, Ordering::Equal],
+                _ => vec![Ordering::Less, Ordering::Equal, Ordering::Greater],
+            };
+            if !possible.into_iter().any(*cmp) {
+                return true;
+            }
+        }
+
+        // Evaluates a bound at the smallest and largest values of the column
+        // it is monotone in, if it is.
+        let eval_bound = |bound: &MirScalarExpr| {
+            let column = Self::monotone_column(bound, self.arity)?;
+            let (lower, upper) = column_bounds(column)?;
+            let arena = RowArena::new();
+            let eval = |row: &Row| {
+                let mut datums = vec![Datum::Null; self.arity];
+                datums[column] = row.unpack_first();
+                match bound.eval(&datums, &arena) {
+                    Ok(Datum::MzTimestamp(t)) => Some(t),
+                    _ => None,
+                }
+            };
+            Some((eval(&lower)?, eval(&upper)?))
+        };
+        let mut lower_ranges = Vec::new();
+        let mut upper_ranges = Vec::new();
+        for (bounds, ranges) in [
+            (&self.lower_bounds, &mut lower_ranges),
+            (&self.upper_bounds, &mut upper_ranges),
+        ] {
+            for bound in bounds.iter() {
+                let range = eval_bound(bound);
+                // The bound might error on some update in the part.
+                if range.is_none() && bound.could_error() {
+                    return false;
+                }
+                ranges.extend(range);
+            }
+        }
+        // An update is retracted at the least of its upper bounds, so if that
+        // is not beyond the `as_of` it nets out to nothing.
+        if let Some(as_of) = as_of.as_option() {
+            if upper_ranges.iter().any(|(_, max)| max <= as_of) {
+                return true;
+            }
+        }
+        // An update is inserted at the greatest of its lower bounds, so if that
+        // is beyond `until` it is never emitted.
+        lower_ranges.iter().any(|(min, _)| until.less_equal(min))
+    }
+}
+
 /// Informs a `persist_source` to skip flow control on its output
 pub const NO_FLOW_CONTROL: usize = usize::MAX;
 
@@ -152,10 +390,16 @@ where
 
     // Extract the MFP if it exists; leave behind an identity MFP in that case.
     let mut map_filter_project = map_filter_project.as_mut().map(|mfp| mfp.take());
+    // Use the MFP to decode only the columns it needs from parts with
+    // structured keys, and to skip updates that it would filter out.
+    let structured_pushdown = map_filter_project
+        .as_ref()
+        .map(|mfp| Arc::new(StructuredPushdown::new(mfp)));
 
     // All of these need to be cloned out here because they're moved into the
     // `try_stream!` generator.
     let persist_clients_stream = Arc::<Mutex<PersistClientCache>>::clone(&persist_clients);
+    let structured_pushdown_stream = structured_pushdown.clone();
     let persist_location_stream = metadata.persist_location.clone();
     let data_shard = metadata.data_shard.clone();
     let as_of_stream = as_of;
@@ -268,7 +512,7 @@ where
                     .return_leased_part(subscription.leased_part_from_exchangeable(leased_part));
             }
 
-            let (parts, progress) = subscription.next().await;
+            let (mut parts, progress) = subscription.next().await;
             // If `until.less_equal(progress)`, it means that all subsequent batches will
             // contain only times greater or equal to `until`, which means they can be dropped
             // in their entirety. The current batch must be emitted, but we can stop afterwards.
@@ -276,6 +520,20 @@ where
                 done = true;
             }
 
+            // Skip any parts whose stats show that the MFP would filter out
+            // everything in them, without distributing them to be fetched.
+            if let Some(pushdown) = &structured_pushdown_stream {
+                let (skipped, kept) = parts.into_iter().partition::<Vec<_>, _>(|part| {
+                    part.stats().map_or(false, |stats| {
+                        pushdown.can_skip(stats, &as_of_stream, &until_clone)
+                    })
+                });
+                for part in skipped {
+                    subscription.return_leased_part(part);
+                }
+                parts = kept;
+            }
+
             let parts_size_bytes = parts.iter().map(|x| x.encoded_size_bytes()).sum();
             yield (parts, progress, parts_size_bytes);
         }
@@ -422,8 +680,14 @@ where
                     let (consumed_part, fetched_part) = fetcher
                         .fetch_leased_part(fetcher.leased_part_from_exchangeable(part))
                         .await;
-                    let fetched_part = fetched_part
+                    let mut fetched_part = fetched_part
                         .expect("shard_id generated for sources must match across all workers");
+                    if let Some(pushdown) = &structured_pushdown {
+                        let pushdown = Arc::clone(pushdown);
+                        fetched_part.set_structured_key_decoder(Box::new(move |part| {
+                            pushdown.decoder(part)
+                        }));
+                    }
                     // SUBTLE: This operator yields back to timely whenever an await returns a
                     // Pending result from the overall async/await state machine `poll`. Since
                     // this is fetching from remote storage, it will yield and thus we can reset
@@ -576,3 +840,96 @@ where
 
     (update_output_stream, token)
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::{func, MapFilterProject, UnmaterializableFunc};
+    use mz_persist_types::columnar::Schema;
+    use mz_repr::{RelationDesc, ScalarType};
+
+    use super::*;
+
+    fn stats(rows: &[SourceData]) -> PartStats {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(true))
+            .with_column("b", ScalarType::UInt64.nullable(true));
+        let schema: &dyn Schema<SourceData> = &desc;
+        let mut encoder = schema.encoder();
+        for row in rows {
+            encoder.encode(row);
+        }
+        let part = encoder.finish().expect("valid data");
+        schema.stats(&part).expect("valid part")
+    }
+
+    fn pushdown(predicate: MirScalarExpr) -> StructuredPushdown {
+        let mfp = MapFilterProject::new(2)
+            .filter(vec![predicate])
+            .into_plan()
+            .expect("valid plan");
+        StructuredPushdown::new(&mfp)
+    }
+
+    #[test]
+    fn structured_pushdown_can_skip() {
+        let ok =
+            |a: i64, b: u64| SourceData(Ok(Row::pack_slice(&[Datum::Int64(a), Datum::UInt64(b)])));
+        let part = stats(&[ok(1, 10), ok(5, 20)]);
+        let as_of = Antichain::from_elem(Timestamp::from(15u64));
+        let until = Antichain::from_elem(Timestamp::from(25u64));
+
+        let a_cmp = |func: BinaryFunc, x: i64| {
+            pushdown(MirScalarExpr::Column(0).call_binary(
+                MirScalarExpr::literal_ok(Datum::Int64(x), ScalarType::Int64),
+                func,
+            ))
+        };
+        assert!(a_cmp(BinaryFunc::Eq, 7).can_skip(&part, &as_of, &until));
+        assert!(!a_cmp(BinaryFunc::Eq, 3).can_skip(&part, &as_of, &until));
+        assert!(a_cmp(BinaryFunc::Lt, 1).can_skip(&part, &as_of, &until));
+        assert!(!a_cmp(BinaryFunc::Lte, 1).can_skip(&part, &as_of, &until));
+        assert!(a_cmp(BinaryFunc::Gt, 5).can_skip(&part, &as_of, &until));
+        assert!(!a_cmp(BinaryFunc::Gte, 5).can_skip(&part, &as_of, &until));
+
+        // Errors are never skipped.
+        let with_err = stats(&[
+            ok(1, 10),
+            SourceData(Err(DataflowError::from(mz_expr::EvalError::DivisionByZero))),
+        ]);
+        assert!(!a_cmp(BinaryFunc::Eq, 7).can_skip(&with_err, &as_of, &until));
+
+        // Nothing compares equal to null.
+        let nulls = stats(&[SourceData(Ok(Row::pack_slice(&[Datum::Null, Datum::Null])))]);
+        assert!(a_cmp(BinaryFunc::Eq, 7).can_skip(&nulls, &as_of, &until));
+
+        let now = || MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow);
+        let b_ts = || {
+            MirScalarExpr::Column(1).call_unary(UnaryFunc::CastUint64ToMzTimestamp(
+                func::CastUint64ToMzTimestamp,
+            ))
+        };
+        // Every update is retracted by 21, which is after the as_of.
+        assert!(
+            !pushdown(now().call_binary(b_ts(), BinaryFunc::Lte)).can_skip(&part, &as_of, &until)
+        );
+        // ... but not after a later as_of.
+        assert!(
+            pushdown(now().call_binary(b_ts(), BinaryFunc::Lte)).can_skip(
+                &part,
+                &Antichain::from_elem(Timestamp::from(21u64)),
+                &until
+            )
+        );
+        // Every update is inserted at 11 or later, which is after the until.
+        assert!(
+            pushdown(now().call_binary(b_ts(), BinaryFunc::Gt)).can_skip(
+                &part,
+                &as_of,
+                &Antichain::from_elem(Timestamp::from(11u64))
+            )
+        );
+        assert!(
+            !pushdown(now().call_binary(b_ts(), BinaryFunc::Gt)).can_skip(&part, &as_of, &until)
+        );
+    }
+}
@@ -22,6 +22,7 @@ use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{GlobalId, RelationDesc};
 
 use crate::controller::CollectionMetadata;
+use crate::types::connections::aws::AwsConfig;
 use crate::types::connections::{CsrConnection, KafkaConnection};
 
 include!(concat!(
@@ -220,6 +221,7 @@ impl RustType<ProtoSinkAsOf> for SinkAsOf<mz_repr::Timestamp> {
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum StorageSinkConnection {
     Kafka(KafkaSinkConnection),
+    S3(S3SinkConnection),
 }
 
 impl StorageSinkConnection {
@@ -228,6 +230,7 @@ impl StorageSinkConnection {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            S3(S3SinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 }
@@ -238,6 +241,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 StorageSinkConnection::Kafka(kafka) => Kind::Kafka(kafka.into_proto()),
+                StorageSinkConnection::S3(s3) => Kind::S3(s3.into_proto()),
             }),
         }
     }
@@ -249,6 +253,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoStorageSinkConnection::kind"))?;
         Ok(match kind {
             Kind::Kafka(kafka) => StorageSinkConnection::Kafka(kafka.into_rust()?),
+            Kind::S3(s3) => StorageSinkConnection::S3(s3.into_rust()?),
         })
     }
 }
@@ -279,6 +284,9 @@ pub struct KafkaSinkConnection {
     pub relation_key_indices: Option<Vec<usize>>,
     pub value_desc: RelationDesc,
     pub published_schema_info: Option<PublishedSchemaInfo>,
+    /// The Protobuf messages to encode keys and values as, if the sink
+    /// produces Protobuf rather than Avro or JSON.
+    pub protobuf_format: Option<ProtobufSinkFormat>,
     pub progress: KafkaSinkProgressConnection,
     // Maximum number of records the sink will attempt to send each time it is
     // invoked
@@ -294,6 +302,7 @@ proptest::prop_compose! {
         relation_key_indices in any::<Option<Vec<usize>>>(),
         value_desc in any::<RelationDesc>(),
         published_schema_info in any::<Option<PublishedSchemaInfo>>(),
+        protobuf_format in any::<Option<ProtobufSinkFormat>>(),
         progress in any::<KafkaSinkProgressConnection>(),
         fuel in any::<usize>(),
     ) -> KafkaSinkConnection {
@@ -305,6 +314,7 @@ proptest::prop_compose! {
             relation_key_indices,
             value_desc,
             published_schema_info,
+            protobuf_format,
             progress,
             fuel,
         }
@@ -364,6 +374,7 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
             relation_key_indices: self.relation_key_indices.into_proto(),
             value_desc: Some(self.value_desc.into_proto()),
             published_schema_info: self.published_schema_info.into_proto(),
+            protobuf_format: self.protobuf_format.into_proto(),
             progress: Some(self.progress.into_proto()),
             fuel: self.fuel.into_proto(),
         }
@@ -384,6 +395,7 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
                 .value_desc
                 .into_rust_if_some("ProtoKafkaSinkConnection::addrs")?,
             published_schema_info: proto.published_schema_info.into_rust()?,
+            protobuf_format: proto.protobuf_format.into_rust()?,
             progress: proto
                 .progress
                 .into_rust_if_some("ProtoKafkaSinkConnection::progress")?,
@@ -392,6 +404,142 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
     }
 }
 
+/// A sink that writes the changes to a collection as files in an S3 bucket.
+///
+/// Each file holds the updates for a `[lower, upper)` range of timestamps.
+/// A manifest object under `prefix` lists the files that have been completely
+/// written, along with the frontier up to which they are complete.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnection {
+    pub connection_id: GlobalId,
+    pub aws: AwsConfig,
+    pub bucket: String,
+    /// The key prefix under which data files and the manifest are written.
+    pub prefix: String,
+    pub format: S3SinkFormat,
+    pub value_desc: RelationDesc,
+}
+
+impl RustType<ProtoS3SinkConnection> for S3SinkConnection {
+    fn into_proto(&self) -> ProtoS3SinkConnection {
+        ProtoS3SinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            aws: Some(self.aws.into_proto()),
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            format: Some(self.format.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(S3SinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoS3SinkConnection::connection_id")?,
+            aws: proto.aws.into_rust_if_some("ProtoS3SinkConnection::aws")?,
+            bucket: proto.bucket,
+            prefix: proto.prefix,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoS3SinkConnection::format")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoS3SinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// The file format written by an S3 sink.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3SinkFormat {
+    Parquet,
+    Csv {
+        /// Whether each file starts with a header row naming the columns.
+        header: bool,
+        delimiter: u8,
+    },
+}
+
+impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
+    fn into_proto(&self) -> ProtoS3SinkFormat {
+        use proto_s3_sink_format::{Kind, ProtoCsv};
+        ProtoS3SinkFormat {
+            kind: Some(match self {
+                S3SinkFormat::Parquet => Kind::Parquet(()),
+                S3SinkFormat::Csv { header, delimiter } => Kind::Csv(ProtoCsv {
+                    header: *header,
+                    delimiter: delimiter.into_proto(),
+                }),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkFormat) -> Result<Self, TryFromProtoError> {
+        use proto_s3_sink_format::{Kind, ProtoCsv};
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3SinkFormat::kind"))?;
+        Ok(match kind {
+            Kind::Parquet(()) => S3SinkFormat::Parquet,
+            Kind::Csv(ProtoCsv { header, delimiter }) => S3SinkFormat::Csv {
+                header,
+                delimiter: delimiter.into_rust()?,
+            },
+        })
+    }
+}
+
+/// A Protobuf message type that a Kafka sink encodes rows as.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufSinkMessage {
+    /// An encoded `FileDescriptorSet` that contains the message type.
+    pub descriptors: Vec<u8>,
+    /// The fully qualified name of the message type.
+    pub message_name: String,
+}
+
+impl RustType<ProtoProtobufSinkMessage> for ProtobufSinkMessage {
+    fn into_proto(&self) -> ProtoProtobufSinkMessage {
+        ProtoProtobufSinkMessage {
+            descriptors: self.descriptors.clone(),
+            message_name: self.message_name.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoProtobufSinkMessage) -> Result<Self, TryFromProtoError> {
+        Ok(ProtobufSinkMessage {
+            descriptors: proto.descriptors,
+            message_name: proto.message_name,
+        })
+    }
+}
+
+/// The Protobuf message types that a Kafka sink encodes keys and values as.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufSinkFormat {
+    pub key: Option<ProtobufSinkMessage>,
+    pub value: ProtobufSinkMessage,
+}
+
+impl RustType<ProtoProtobufSinkFormat> for ProtobufSinkFormat {
+    fn into_proto(&self) -> ProtoProtobufSinkFormat {
+        ProtoProtobufSinkFormat {
+            key: self.key.into_proto(),
+            value: Some(self.value.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoProtobufSinkFormat) -> Result<Self, TryFromProtoError> {
+        Ok(ProtobufSinkFormat {
+            key: proto.key.into_rust()?,
+            value: proto
+                .value
+                .into_rust_if_some("ProtoProtobufSinkFormat::value")?,
+        })
+    }
+}
+
 /// TODO(JLDLaughlin): Documentation.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PublishedSchemaInfo {
@@ -420,6 +568,7 @@ impl StorageSinkConnection {
     pub fn name(&self) -> &'static str {
         match self {
             StorageSinkConnection::Kafka(_) => "kafka",
+            StorageSinkConnection::S3(_) => "s3",
         }
     }
 }
@@ -427,6 +576,9 @@ impl StorageSinkConnection {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnectionBuilder {
     Kafka(KafkaSinkConnectionBuilder),
+    /// S3 sinks need no external resources to be set up before they run, so
+    /// they are fully described at planning time.
+    S3(S3SinkConnection),
 }
 
 impl StorageSinkConnectionBuilder {
@@ -435,6 +587,7 @@ impl StorageSinkConnectionBuilder {
         use StorageSinkConnectionBuilder::*;
         match self {
             Kafka(KafkaSinkConnectionBuilder { connection_id, .. }) => Some(*connection_id),
+            S3(S3SinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 }
@@ -476,4 +629,11 @@ pub enum KafkaSinkFormat {
         csr_connection: CsrConnection,
     },
     Json,
+    Protobuf {
+        format: ProtobufSinkFormat,
+        /// The IDs of the schemas of the messages in the schema registry, if
+        /// they were taken from a schema registry. Messages are written in
+        /// the Confluent wire format if present.
+        published_schema_info: Option<PublishedSchemaInfo>,
+    },
 }
@@ -9,6 +9,7 @@
 
 //! Types and traits related to the introduction of changing collections into `dataflow`.
 
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::{Add, AddAssign, Deref, DerefMut};
 use std::rc::Rc;
@@ -16,6 +17,9 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail};
+use arrow2::array::{Array, BinaryArray, MutableArray, MutableBinaryArray};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::datatypes::{DataType, Field};
 use async_trait::async_trait;
 use bytes::BufMut;
 use differential_dataflow::lattice::Lattice;
@@ -34,10 +38,14 @@ use mz_expr::MirScalarExpr;
 use mz_ore::now::NowFn;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::write::WriteHandle;
+use mz_persist_types::columnar::{ColumnStats, Part, PartDecoder, PartEncoder, PartStats, Schema};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::numeric::NumericMaxScale;
-use mz_repr::{ColumnType, Diff, GlobalId, RelationDesc, RelationType, Row, ScalarType};
+use mz_repr::{
+    ColumnType, Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowColumnarDecoder,
+    RowColumnarEncoder, ScalarType,
+};
 
 use crate::controller::{CollectionMetadata, ResumptionFrontierCalculator};
 use crate::types::connections::aws::AwsConfig;
@@ -97,6 +105,7 @@ impl<T: Timestamp + Lattice + Codec64> ResumptionFrontierCalculator<T>
                 data_shard,
                 // The status shard only contains non-definite status updates
                 status_shard: _,
+                relation_desc: _,
             } = &export.storage_metadata;
             let handle = client_cache
                 .open(persist_location.clone())
@@ -117,6 +126,7 @@ impl<T: Timestamp + Lattice + Codec64> ResumptionFrontierCalculator<T>
             data_shard: _,
             // The status shard only contains non-definite status updates
             status_shard: _,
+            relation_desc: _,
         } = &self.ingestion_metadata;
         let remap_handle = client_cache
             .open(persist_location.clone())
@@ -431,6 +441,14 @@ pub enum IncludedColumnSource {
     Timestamp,
     Topic,
     Headers,
+    /// The key of the S3 object a record was read from.
+    ObjectKey,
+    /// The time at which the S3 object a record was read from was last modified.
+    LastModified,
+    /// The ID of the Kinesis shard a record was read from.
+    ShardId,
+    /// The sequence number of a Kinesis record within its shard.
+    SequenceNumber,
 }
 
 impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
@@ -443,6 +461,10 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
                 IncludedColumnSource::Timestamp => Kind::Timestamp(()),
                 IncludedColumnSource::Topic => Kind::Topic(()),
                 IncludedColumnSource::Headers => Kind::Headers(()),
+                IncludedColumnSource::ObjectKey => Kind::ObjectKey(()),
+                IncludedColumnSource::LastModified => Kind::LastModified(()),
+                IncludedColumnSource::ShardId => Kind::ShardId(()),
+                IncludedColumnSource::SequenceNumber => Kind::SequenceNumber(()),
             }),
         }
     }
@@ -458,6 +480,10 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
             Kind::Timestamp(()) => IncludedColumnSource::Timestamp,
             Kind::Topic(()) => IncludedColumnSource::Topic,
             Kind::Headers(()) => IncludedColumnSource::Headers,
+            Kind::ObjectKey(()) => IncludedColumnSource::ObjectKey,
+            Kind::LastModified(()) => IncludedColumnSource::LastModified,
+            Kind::ShardId(()) => IncludedColumnSource::ShardId,
+            Kind::SequenceNumber(()) => IncludedColumnSource::SequenceNumber,
         })
     }
 }
@@ -723,6 +749,8 @@ pub struct UpsertEnvelope {
     /// The indices of the keys in the full value row, used
     /// to deduplicate data in `upsert_core`
     pub key_indices: Vec<usize>,
+    /// Where the upsert operator keeps the current value of each key
+    pub state_backend: UpsertStateBackend,
 }
 
 impl Arbitrary for UpsertEnvelope {
@@ -734,11 +762,13 @@ impl Arbitrary for UpsertEnvelope {
             any::<usize>(),
             any::<UpsertStyle>(),
             proptest::collection::vec(any::<usize>(), 1..4),
+            any::<UpsertStateBackend>(),
         )
-            .prop_map(|(source_arity, style, key_indices)| Self {
+            .prop_map(|(source_arity, style, key_indices, state_backend)| Self {
                 source_arity,
                 style,
                 key_indices,
+                state_backend,
             })
             .boxed()
     }
@@ -750,6 +780,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
             source_arity: self.source_arity.into_proto(),
             style: Some(self.style.into_proto()),
             key_indices: self.key_indices.into_proto(),
+            state_backend: Some(self.state_backend.into_proto()),
         }
     }
 
@@ -760,6 +791,44 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
                 .style
                 .into_rust_if_some("ProtoUpsertEnvelope::style")?,
             key_indices: proto.key_indices.into_rust()?,
+            state_backend: proto
+                .state_backend
+                .into_rust_if_some("ProtoUpsertEnvelope::state_backend")?,
+        })
+    }
+}
+
+/// The data structure in which an upsert operator keeps the current value of
+/// each key.
+#[derive(Arbitrary, Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum UpsertStateBackend {
+    /// Keep keys and values in memory.
+    #[default]
+    Memory,
+    /// Keep keys and values in a file on the local disk of the storage
+    /// process. Only a hash of each key is kept in memory.
+    Disk,
+}
+
+impl RustType<ProtoUpsertStateBackend> for UpsertStateBackend {
+    fn into_proto(&self) -> ProtoUpsertStateBackend {
+        use proto_upsert_state_backend::Kind;
+        ProtoUpsertStateBackend {
+            kind: Some(match self {
+                UpsertStateBackend::Memory => Kind::Memory(()),
+                UpsertStateBackend::Disk => Kind::Disk(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoUpsertStateBackend) -> Result<Self, TryFromProtoError> {
+        use proto_upsert_state_backend::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoUpsertStateBackend::kind"))?;
+        Ok(match kind {
+            Kind::Memory(()) => UpsertStateBackend::Memory,
+            Kind::Disk(()) => UpsertStateBackend::Disk,
         })
     }
 }
@@ -1042,6 +1111,7 @@ impl UnplannedSourceEnvelope {
                     style: upsert_style,
                     key_indices: key.expect("into_source_envelope to be passed correct parameters for UnplannedSourceEnvelope::Upsert"),
                     source_arity: source_arity.expect("into_source_envelope to be passed correct parameters for UnplannedSourceEnvelope::Upsert"),
+                    state_backend: UpsertStateBackend::default(),
                 })
             },
             UnplannedSourceEnvelope::Debezium(inner) => {
@@ -1179,6 +1249,10 @@ pub struct KafkaSourceConnection {
     pub connection: KafkaConnection,
     pub connection_id: GlobalId,
     pub topic: String,
+    /// If true, `topic` is a regular expression, and the source reads from
+    /// every topic whose entire name matches it, including topics that are
+    /// created after the source.
+    pub topic_pattern: bool,
     // Map from partition -> starting offset
     pub start_offsets: HashMap<i32, i64>,
     pub group_id_prefix: Option<String>,
@@ -1194,6 +1268,14 @@ pub struct KafkaSourceConnection {
     pub include_headers: Option<IncludedColumnPos>,
 }
 
+impl KafkaSourceConnection {
+    /// Compiles a topic pattern into a regular expression that only matches
+    /// entire topic names.
+    pub fn compile_topic_pattern(pattern: &str) -> Result<regex::Regex, regex::Error> {
+        regex::Regex::new(&format!("^(?:{})$", pattern))
+    }
+}
+
 impl SourceConnection for KafkaSourceConnection {
     fn name(&self) -> &'static str {
         "kafka"
@@ -1209,6 +1291,7 @@ impl Arbitrary for KafkaSourceConnection {
             any::<KafkaConnection>(),
             any::<GlobalId>(),
             any::<String>(),
+            any::<bool>(),
             proptest::collection::hash_map(any::<i32>(), any::<i64>(), 1..4),
             any::<Option<String>>(),
             any::<String>(),
@@ -1223,6 +1306,7 @@ impl Arbitrary for KafkaSourceConnection {
                     connection,
                     connection_id,
                     topic,
+                    topic_pattern,
                     start_offsets,
                     group_id_prefix,
                     environment_id,
@@ -1235,6 +1319,7 @@ impl Arbitrary for KafkaSourceConnection {
                     connection,
                     connection_id,
                     topic,
+                    topic_pattern,
                     start_offsets,
                     group_id_prefix,
                     environment_id,
@@ -1255,6 +1340,7 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             connection: Some(self.connection.into_proto()),
             connection_id: Some(self.connection_id.into_proto()),
             topic: self.topic.clone(),
+            topic_pattern: self.topic_pattern,
             start_offsets: self.start_offsets.clone(),
             group_id_prefix: self.group_id_prefix.clone(),
             environment_id: None,
@@ -1276,6 +1362,7 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
                 .connection_id
                 .into_rust_if_some("ProtoKafkaSourceConnection::connection_id")?,
             topic: proto.topic,
+            topic_pattern: proto.topic_pattern,
             start_offsets: proto.start_offsets,
             group_id_prefix: proto.group_id_prefix,
             environment_id: match (proto.environment_id, proto.environment_name) {
@@ -1298,6 +1385,9 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
 #[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
+    Snappy,
     None,
 }
 
@@ -1307,6 +1397,9 @@ impl RustType<ProtoCompression> for Compression {
         ProtoCompression {
             kind: Some(match self {
                 Compression::Gzip => Kind::Gzip(()),
+                Compression::Zstd => Kind::Zstd(()),
+                Compression::Bzip2 => Kind::Bzip2(()),
+                Compression::Snappy => Kind::Snappy(()),
                 Compression::None => Kind::None(()),
             }),
         }
@@ -1316,6 +1409,9 @@ impl RustType<ProtoCompression> for Compression {
         use proto_compression::Kind;
         Ok(match proto.kind {
             Some(Kind::Gzip(())) => Compression::Gzip,
+            Some(Kind::Zstd(())) => Compression::Zstd,
+            Some(Kind::Bzip2(())) => Compression::Bzip2,
+            Some(Kind::Snappy(())) => Compression::Snappy,
             Some(Kind::None(())) => Compression::None,
             None => {
                 return Err(TryFromProtoError::MissingField(
@@ -1573,8 +1669,37 @@ impl GenericSourceConnection {
 
                 items.into_values().collect()
             }
-            Self::Kinesis(_) => vec![],
-            Self::S3(_) => vec![],
+            Self::Kinesis(KinesisSourceConnection {
+                include_shard_id: shard_id,
+                include_sequence_number: sequence_number,
+                ..
+            }) => {
+                let mut items = BTreeMap::new();
+                for include in [shard_id, sequence_number].into_iter().flatten() {
+                    items.insert(
+                        include.pos + 1,
+                        (&*include.name, ScalarType::String.nullable(false)),
+                    );
+                }
+                items.into_values().collect()
+            }
+            Self::S3(S3SourceConnection {
+                include_key: key,
+                include_last_modified: last_modified,
+                ..
+            }) => {
+                // S3 does not guarantee that it reports when an object was last modified.
+                let mut items = BTreeMap::new();
+                for (include, ty) in [
+                    (key, ScalarType::String.nullable(false)),
+                    (last_modified, ScalarType::Timestamp.nullable(true)),
+                ] {
+                    if let Some(include) = include {
+                        items.insert(include.pos + 1, (&*include.name, ty));
+                    }
+                }
+                items.into_values().collect()
+            }
             Self::Postgres(_) => vec![],
             Self::LoadGenerator(_) => vec![],
             Self::TestScript(_) => vec![],
@@ -1609,10 +1734,40 @@ impl GenericSourceConnection {
 
                 items.into_values().collect()
             }
+            GenericSourceConnection::Kinesis(KinesisSourceConnection {
+                include_shard_id: shard_id,
+                include_sequence_number: sequence_number,
+                ..
+            }) => {
+                let mut items = BTreeMap::new();
+                for (include, ty) in [
+                    (shard_id, IncludedColumnSource::ShardId),
+                    (sequence_number, IncludedColumnSource::SequenceNumber),
+                ] {
+                    if let Some(include) = include {
+                        items.insert(include.pos, ty);
+                    }
+                }
+                items.into_values().collect()
+            }
+            GenericSourceConnection::S3(S3SourceConnection {
+                include_key: key,
+                include_last_modified: last_modified,
+                ..
+            }) => {
+                let mut items = BTreeMap::new();
+                for (include, ty) in [
+                    (key, IncludedColumnSource::ObjectKey),
+                    (last_modified, IncludedColumnSource::LastModified),
+                ] {
+                    if let Some(include) = include {
+                        items.insert(include.pos, ty);
+                    }
+                }
+                items.into_values().collect()
+            }
 
-            GenericSourceConnection::Kinesis(_)
-            | GenericSourceConnection::S3(_)
-            | GenericSourceConnection::Postgres(_)
+            GenericSourceConnection::Postgres(_)
             | GenericSourceConnection::LoadGenerator(_)
             | GenericSourceConnection::TestScript(_) => Vec::new(),
         }
@@ -1654,6 +1809,11 @@ pub struct KinesisSourceConnection {
     pub connection_id: GlobalId,
     pub stream_name: String,
     pub aws: AwsConfig,
+    /// If present, include the shard ID as an output column of the source with the given name.
+    pub include_shard_id: Option<IncludedColumnPos>,
+    /// If present, include the sequence number as an output column of the source with the given
+    /// name.
+    pub include_sequence_number: Option<IncludedColumnPos>,
 }
 
 impl SourceConnection for KinesisSourceConnection {
@@ -1668,6 +1828,8 @@ impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
             stream_name: self.stream_name.clone(),
             aws: Some(self.aws.into_proto()),
             connection_id: Some(self.connection_id.into_proto()),
+            include_shard_id: self.include_shard_id.into_proto(),
+            include_sequence_number: self.include_sequence_number.into_proto(),
         }
     }
 
@@ -1680,6 +1842,8 @@ impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
             connection_id: proto
                 .connection_id
                 .into_rust_if_some("ProtoKinesisSourceConnection::connection_id")?,
+            include_shard_id: proto.include_shard_id.into_rust()?,
+            include_sequence_number: proto.include_sequence_number.into_rust()?,
         })
     }
 }
@@ -2149,6 +2313,11 @@ pub struct S3SourceConnection {
     pub pattern: Option<Glob>,
     pub aws: AwsConfig,
     pub compression: Compression,
+    /// If present, include the object key as an output column of the source with the given name.
+    pub include_key: Option<IncludedColumnPos>,
+    /// If present, include the object's last modification time as an output column of the
+    /// source with the given name.
+    pub include_last_modified: Option<IncludedColumnPos>,
 }
 
 impl SourceConnection for S3SourceConnection {
@@ -2178,16 +2347,28 @@ impl Arbitrary for S3SourceConnection {
             proptest::option::of(any_glob()),
             any::<AwsConfig>(),
             any::<Compression>(),
+            any::<Option<IncludedColumnPos>>(),
+            any::<Option<IncludedColumnPos>>(),
         )
-            .prop_map(|(connection_id, key_sources, pattern, aws, compression)| {
-                S3SourceConnection {
+            .prop_map(
+                |(
                     connection_id,
                     key_sources,
                     pattern,
                     aws,
                     compression,
-                }
-            })
+                    include_key,
+                    include_last_modified,
+                )| S3SourceConnection {
+                    connection_id,
+                    key_sources,
+                    pattern,
+                    aws,
+                    compression,
+                    include_key,
+                    include_last_modified,
+                },
+            )
             .boxed()
     }
 }
@@ -2200,6 +2381,8 @@ impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
             pattern: self.pattern.as_ref().map(|g| g.glob().into()),
             aws: Some(self.aws.into_proto()),
             compression: Some(self.compression.into_proto()),
+            include_key: self.include_key.into_proto(),
+            include_last_modified: self.include_last_modified.into_proto(),
         }
     }
 
@@ -2224,6 +2407,8 @@ impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
             compression: proto
                 .compression
                 .into_rust_if_some("ProtoS3SourceConnection::compression")?,
+            include_key: proto.include_key.into_rust()?,
+            include_last_modified: proto.include_last_modified.into_rust()?,
         })
     }
 }
@@ -2320,6 +2505,170 @@ impl Codec for SourceData {
         let proto = ProtoSourceData::decode(buf).map_err(|err| err.to_string())?;
         proto.into_rust().map_err(|err| err.to_string())
     }
+
+    fn structured_decoder(part: &Part) -> Result<Box<dyn PartDecoder<Self>>, String> {
+        // Every column but the error column belongs to the row.
+        let arity = part
+            .fields()
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| format!("missing {} column", SOURCE_DATA_ERROR_COLUMN))?;
+        Ok(Box::new(SourceDataColumnarDecoder::new(arity, part)?))
+    }
+}
+
+/// The name of the column storing the errors of [SourceData].
+const SOURCE_DATA_ERROR_COLUMN: &str = "err";
+
+/// Returns a copy of `desc` with every column nullable, as required to store
+/// error rows, which have no datums, in the same columns as the ok rows.
+fn source_data_row_desc(desc: &RelationDesc) -> RelationDesc {
+    let typ = RelationType::new(
+        desc.typ()
+            .column_types
+            .iter()
+            .map(|typ| typ.clone().nullable(true))
+            .collect(),
+    );
+    RelationDesc::new(typ, desc.iter_names().cloned())
+}
+
+impl Schema<SourceData> for RelationDesc {
+    fn columns(&self) -> Vec<Field> {
+        let mut columns = Schema::<Row>::columns(&source_data_row_desc(self));
+        columns.push(Field::new(SOURCE_DATA_ERROR_COLUMN, DataType::Binary, true));
+        columns
+    }
+
+    fn encoder(&self) -> Box<dyn PartEncoder<SourceData>> {
+        let row_desc = source_data_row_desc(self);
+        Box::new(SourceDataColumnarEncoder {
+            row: RowColumnarEncoder::new(&row_desc),
+            err: MutableBinaryArray::new(),
+            null_row: Row::pack(std::iter::repeat(Datum::Null).take(row_desc.arity())),
+            buf: Vec::new(),
+        })
+    }
+
+    fn decoder(&self, part: &Part) -> Result<Box<dyn PartDecoder<SourceData>>, String> {
+        let row = RowColumnarDecoder::new(&source_data_row_desc(self), part)?;
+        Ok(Box::new(SourceDataColumnarDecoder::with_row_decoder(
+            row, part,
+        )?))
+    }
+
+    fn stats(&self, part: &Part) -> Result<PartStats, String> {
+        let row = RowColumnarDecoder::new(&source_data_row_desc(self), part)?;
+        let decoder = SourceDataColumnarDecoder::with_row_decoder(row, part)?;
+        let mut stats = decoder.row.stats()?;
+        stats.columns.insert(
+            SOURCE_DATA_ERROR_COLUMN.to_owned(),
+            ColumnStats {
+                null_count: decoder.err.null_count(),
+                lower: None,
+                upper: None,
+            },
+        );
+        Ok(stats)
+    }
+}
+
+/// Encodes [SourceData] into a [Part] with the columns of a [RelationDesc],
+/// all made nullable, and an additional column for errors.
+///
+/// Errors are stored as a row of nulls along with the [Codec] encoding of the
+/// error.
+struct SourceDataColumnarEncoder {
+    row: RowColumnarEncoder,
+    err: MutableBinaryArray<i32>,
+    null_row: Row,
+    buf: Vec<u8>,
+}
+
+impl PartEncoder<SourceData> for SourceDataColumnarEncoder {
+    fn encode(&mut self, val: &SourceData) {
+        match &val.0 {
+            Ok(row) => {
+                self.row.append(row);
+                self.err.push::<&[u8]>(None);
+            }
+            Err(err) => {
+                self.row.append(&self.null_row);
+                self.buf.clear();
+                err.encode(&mut self.buf);
+                self.err.push(Some(self.buf.as_slice()));
+            }
+        }
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Part, String> {
+        let row = self.row.finish()?;
+        let mut fields = row.fields().to_vec();
+        fields.push(Field::new(SOURCE_DATA_ERROR_COLUMN, DataType::Binary, true));
+        let mut columns = row.columns().to_vec();
+        columns.push(self.err.as_box());
+        Part::new(row.len(), fields, columns)
+    }
+}
+
+/// Decodes [SourceData] from a [Part] written by the [Schema] impl for
+/// [RelationDesc].
+#[derive(Debug)]
+pub struct SourceDataColumnarDecoder {
+    row: RowColumnarDecoder,
+    err: BinaryArray<i32>,
+}
+
+impl SourceDataColumnarDecoder {
+    /// Returns a decoder for [SourceData] with rows of `arity` columns stored
+    /// in `part`.
+    ///
+    /// Row columns missing from `part` are decoded as nulls, but the error
+    /// column must be present.
+    pub fn new(arity: usize, part: &Part) -> Result<Self, String> {
+        let row = RowColumnarDecoder::from_part(arity, part)?;
+        Self::with_row_decoder(row, part)
+    }
+
+    fn with_row_decoder(row: RowColumnarDecoder, part: &Part) -> Result<Self, String> {
+        let err = part
+            .column(SOURCE_DATA_ERROR_COLUMN)
+            .and_then(|x| x.as_any().downcast_ref::<BinaryArray<i32>>())
+            .ok_or_else(|| format!("missing {} column", SOURCE_DATA_ERROR_COLUMN))?
+            .clone();
+        Ok(SourceDataColumnarDecoder { row, err })
+    }
+
+    /// Like [RowColumnarDecoder::filter], but errors always pass the filter,
+    /// so that they are never dropped from the output.
+    pub fn filter<F: Fn(Ordering) -> bool>(
+        &self,
+        column: usize,
+        literal: Datum,
+        pred: F,
+    ) -> Option<Bitmap> {
+        let mask = self.row.filter(column, literal, pred)?;
+        let errs = (0..self.err.len())
+            .map(|idx| self.err.is_valid(idx))
+            .collect::<MutableBitmap>()
+            .into();
+        Some(&mask | &errs)
+    }
+}
+
+impl PartDecoder<SourceData> for SourceDataColumnarDecoder {
+    fn decode(&self, idx: usize) -> SourceData {
+        if self.err.is_valid(idx) {
+            let err = DataflowError::decode(self.err.value(idx))
+                .expect("structured errors were validated when written");
+            return SourceData(Err(err));
+        }
+        let mut row = Row::default();
+        self.row
+            .decode_into(idx, &mut row.packer())
+            .expect("structured rows were validated when written");
+        SourceData(Ok(row))
+    }
 }
 
 /// A `SourceToken` manages interest in a source.
@@ -2351,3 +2700,54 @@ fn test_timeline_parsing() {
     assert!("Dance".parse::<Timeline>().is_err());
     assert!("".parse::<Timeline>().is_err());
 }
+
+#[test]
+fn test_source_data_columnar() {
+    use mz_expr::EvalError;
+
+    let desc = RelationDesc::empty()
+        .with_column("a", ScalarType::Int64.nullable(false))
+        .with_column("b", ScalarType::String.nullable(true));
+    let data = vec![
+        SourceData(Ok(Row::pack_slice(&[
+            Datum::Int64(1),
+            Datum::String("one"),
+        ]))),
+        SourceData(Err(DataflowError::from(EvalError::DivisionByZero))),
+        SourceData(Ok(Row::pack_slice(&[Datum::Int64(3), Datum::Null]))),
+    ];
+
+    // RelationDesc is also a Schema<Row>, so be explicit.
+    let schema: &dyn Schema<SourceData> = &desc;
+    let mut encoder = schema.encoder();
+    for x in data.iter() {
+        encoder.encode(x);
+    }
+    let part = encoder.finish().expect("valid data");
+    assert_eq!(part.fields(), schema.columns().as_slice());
+    let decoder = schema.decoder(&part).expect("valid part");
+    let decoded = (0..part.len())
+        .map(|idx| decoder.decode(idx))
+        .collect::<Vec<_>>();
+    assert_eq!(decoded, data);
+
+    // Projected away columns decode as nulls and errors always pass filters.
+    let projected = part.project(|field| field.name != "1");
+    let decoder = SourceDataColumnarDecoder::new(2, &projected).expect("valid part");
+    assert_eq!(
+        decoder.decode(0),
+        SourceData(Ok(Row::pack_slice(&[Datum::Int64(1), Datum::Null])))
+    );
+    let mask = decoder
+        .filter(0, Datum::Int64(3), Ordering::is_eq)
+        .expect("filter on int column");
+    assert_eq!(mask.iter().collect::<Vec<_>>(), vec![false, true, true]);
+    assert!(SourceDataColumnarDecoder::new(2, &part.project(|field| field.name != "err")).is_err());
+
+    // Errors count as nulls in the row columns.
+    let stats = schema.stats(&part).expect("valid part");
+    assert_eq!(stats.len, 3);
+    assert_eq!(stats.columns["0"].null_count, 1);
+    assert_eq!(stats.columns["1"].null_count, 2);
+    assert_eq!(stats.columns["err"].null_count, 2);
+}
@@ -136,6 +136,8 @@ pub enum DataEncodingInner {
     Protobuf(ProtobufEncoding),
     Csv(CsvEncoding),
     Regex(RegexEncoding),
+    Json(JsonEncoding),
+    Parquet(ParquetEncoding),
     Bytes,
     Text,
     RowCodec(RelationDesc),
@@ -150,6 +152,8 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
                 DataEncodingInner::Protobuf(e) => Kind::Protobuf(e.into_proto()),
                 DataEncodingInner::Csv(e) => Kind::Csv(e.into_proto()),
                 DataEncodingInner::Regex(e) => Kind::Regex(e.into_proto()),
+                DataEncodingInner::Json(e) => Kind::Json(e.into_proto()),
+                DataEncodingInner::Parquet(e) => Kind::Parquet(e.into_proto()),
                 DataEncodingInner::Bytes => Kind::Bytes(()),
                 DataEncodingInner::Text => Kind::Text(()),
                 DataEncodingInner::RowCodec(e) => Kind::RowCodec(e.into_proto()),
@@ -167,6 +171,8 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
             Kind::Protobuf(e) => DataEncodingInner::Protobuf(e.into_rust()?),
             Kind::Csv(e) => DataEncodingInner::Csv(e.into_rust()?),
             Kind::Regex(e) => DataEncodingInner::Regex(e.into_rust()?),
+            Kind::Json(e) => DataEncodingInner::Json(e.into_rust()?),
+            Kind::Parquet(e) => DataEncodingInner::Parquet(e.into_rust()?),
             Kind::Bytes(()) => DataEncodingInner::Bytes,
             Kind::Text(()) => DataEncodingInner::Text,
             Kind::RowCodec(e) => DataEncodingInner::RowCodec(e.into_rust()?),
@@ -267,6 +273,24 @@ impl DataEncoding {
                         desc.with_column(name, ScalarType::String.nullable(false))
                     }),
             },
+            DataEncodingInner::Json(JsonEncoding { columns }) => {
+                if columns.is_empty() {
+                    RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+                } else {
+                    // Fields may be absent from any given document, so all
+                    // projected columns are nullable.
+                    columns
+                        .iter()
+                        .fold(RelationDesc::empty(), |desc, (name, typ)| {
+                            desc.with_column(name, typ.clone().nullable(true))
+                        })
+                }
+            }
+            DataEncodingInner::Parquet(ParquetEncoding { columns }) => columns
+                .iter()
+                .fold(RelationDesc::empty(), |desc, (name, typ)| {
+                    desc.with_column(name, typ.clone().nullable(true))
+                }),
             DataEncodingInner::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
@@ -290,6 +314,8 @@ impl DataEncoding {
             DataEncodingInner::Protobuf(_) => "Protobuf",
             DataEncodingInner::Regex { .. } => "Regex",
             DataEncodingInner::Csv(_) => "Csv",
+            DataEncodingInner::Json(_) => "Json",
+            DataEncodingInner::Parquet(_) => "Parquet",
             DataEncodingInner::Text => "Text",
             DataEncodingInner::RowCodec(_) => "RowCodec",
         }
@@ -426,6 +452,88 @@ impl ColumnSpec {
     }
 }
 
+/// Arguments necessary to define how to decode from JSON format
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonEncoding {
+    /// The top-level fields to project out of each document, along with the
+    /// type to decode each of them as.
+    ///
+    /// If empty, each document is decoded in its entirety into a single
+    /// `jsonb` column named `data`.
+    pub columns: Vec<(String, ScalarType)>,
+}
+
+impl RustType<ProtoJsonEncoding> for JsonEncoding {
+    fn into_proto(&self) -> ProtoJsonEncoding {
+        use proto_json_encoding::ProtoJsonColumn;
+        ProtoJsonEncoding {
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, typ)| ProtoJsonColumn {
+                    name: name.clone(),
+                    typ: Some(typ.into_proto()),
+                })
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonEncoding {
+            columns: proto
+                .columns
+                .into_iter()
+                .map(|column| {
+                    Ok((
+                        column.name,
+                        column.typ.into_rust_if_some("ProtoJsonColumn::typ")?,
+                    ))
+                })
+                .collect::<Result<_, TryFromProtoError>>()?,
+        })
+    }
+}
+
+/// Arguments necessary to define how to decode from Parquet format
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ParquetEncoding {
+    /// The columns to read out of each Parquet object, along with the type to
+    /// decode each of them as. Columns are matched against the object's schema
+    /// by name.
+    pub columns: Vec<(String, ScalarType)>,
+}
+
+impl RustType<ProtoParquetEncoding> for ParquetEncoding {
+    fn into_proto(&self) -> ProtoParquetEncoding {
+        use proto_parquet_encoding::ProtoParquetColumn;
+        ProtoParquetEncoding {
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, typ)| ProtoParquetColumn {
+                    name: name.clone(),
+                    typ: Some(typ.into_proto()),
+                })
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoParquetEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(ParquetEncoding {
+            columns: proto
+                .columns
+                .into_iter()
+                .map(|column| {
+                    Ok((
+                        column.name,
+                        column.typ.into_rust_if_some("ProtoParquetColumn::typ")?,
+                    ))
+                })
+                .collect::<Result<_, TryFromProtoError>>()?,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct RegexEncoding {
     pub regex: mz_repr::adt::regex::Regex,
@@ -74,6 +74,17 @@ struct Args {
     /// Number of dataflow worker threads.
     #[clap(long, env = "WORKERS", value_name = "N", default_value = "1")]
     workers: usize,
+    /// A directory in which dataflows can keep ephemeral state, like the
+    /// state of upsert sources with a disk state backend.
+    ///
+    /// Defaults to a directory in the system's temporary directory.
+    #[clap(long, env = "SCRATCH_DIRECTORY", value_name = "PATH")]
+    scratch_directory: Option<PathBuf>,
+
+    // === Storage options. ===
+    /// Whether to write a structured columnar encoding of keys to persist.
+    #[clap(long, env = "PERSIST_WRITE_STRUCTURED_KEYS")]
+    persist_write_structured_keys: bool,
 
     // === Cloud options. ===
     /// An external ID to be supplied to all AWS AssumeRole operations.
@@ -214,6 +225,10 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             args.aws_external_id,
             secrets_reader,
         ),
+        scratch_directory: args
+            .scratch_directory
+            .unwrap_or_else(|| env::temp_dir().join("storaged")),
+        persist_write_structured_keys: args.persist_write_structured_keys,
     };
 
     // Initialize fail crate for failpoint support
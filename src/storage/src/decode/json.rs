@@ -0,0 +1,157 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde_json::Value;
+
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{strconv, Datum, Row, RowPacker, ScalarType};
+use mz_storage_client::types::errors::DecodeErrorKind;
+use mz_storage_client::types::sources::encoding::JsonEncoding;
+
+#[derive(Debug)]
+pub struct JsonDecoderState {
+    columns: Vec<(String, ScalarType)>,
+    row_buf: Row,
+}
+
+impl JsonDecoderState {
+    pub fn new(JsonEncoding { columns }: JsonEncoding) -> Self {
+        JsonDecoderState {
+            columns,
+            row_buf: Row::default(),
+        }
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
+        let mut packer = self.row_buf.packer();
+        if self.columns.is_empty() {
+            JsonbPacker::new(&mut packer)
+                .pack_slice(bytes)
+                .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?;
+        } else {
+            let document: Value = serde_json::from_slice(bytes)
+                .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?;
+            let object = match document {
+                Value::Object(object) => object,
+                _ => {
+                    return Err(DecodeErrorKind::Text(
+                        "Failed to decode JSON: expected an object".to_string(),
+                    ))
+                }
+            };
+            for (name, typ) in &self.columns {
+                match object.get(name) {
+                    None | Some(Value::Null) => packer.push(Datum::Null),
+                    Some(value) => pack_field(&mut packer, value, typ).map_err(|e| {
+                        DecodeErrorKind::Text(format!(
+                            "Failed to decode JSON field {}: {}",
+                            name, e
+                        ))
+                    })?,
+                }
+            }
+        }
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+/// Packs the JSON `value` as a datum of type `typ`.
+///
+/// Scalar JSON values are interpreted using their textual representation, so
+/// that e.g. both `1` and `"1"` decode to the same `int4`.
+fn pack_field(packer: &mut RowPacker, value: &Value, typ: &ScalarType) -> Result<(), String> {
+    if let ScalarType::Jsonb = typ {
+        return JsonbPacker::new(packer)
+            .pack_serde_json(value.clone())
+            .map_err(|e| e.to_string());
+    }
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(_) | Value::Object(_) | Value::Null => {
+            return Err(format!("cannot decode {} as a scalar value", value))
+        }
+    };
+    let datum = match typ {
+        ScalarType::Bool => Datum::from(strconv::parse_bool(&text).map_err(|e| e.to_string())?),
+        ScalarType::Int16 => Datum::from(strconv::parse_int16(&text).map_err(|e| e.to_string())?),
+        ScalarType::Int32 => Datum::from(strconv::parse_int32(&text).map_err(|e| e.to_string())?),
+        ScalarType::Int64 => Datum::from(strconv::parse_int64(&text).map_err(|e| e.to_string())?),
+        ScalarType::Float32 => {
+            Datum::from(strconv::parse_float32(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::Float64 => {
+            Datum::from(strconv::parse_float64(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::Numeric { max_scale: None } => {
+            Datum::Numeric(strconv::parse_numeric(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::String => Datum::String(&text),
+        ScalarType::Date => Datum::Date(strconv::parse_date(&text).map_err(|e| e.to_string())?),
+        ScalarType::Timestamp => {
+            Datum::Timestamp(strconv::parse_timestamp(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::TimestampTz => {
+            Datum::TimestampTz(strconv::parse_timestamptz(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::Interval => {
+            Datum::Interval(strconv::parse_interval(&text).map_err(|e| e.to_string())?)
+        }
+        ScalarType::Uuid => Datum::Uuid(strconv::parse_uuid(&text).map_err(|e| e.to_string())?),
+        _ => unreachable!(
+            "JSON columns of type {:?} are rejected during planning",
+            typ
+        ),
+    };
+    packer.push(datum);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use mz_repr::adt::jsonb::Jsonb;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_document() {
+        let mut state = JsonDecoderState::new(JsonEncoding { columns: vec![] });
+        let row = state.decode(br#"{"a": 1, "b": [true]}"#).unwrap().unwrap();
+        let expected = Jsonb::from_str(r#"{"a": 1, "b": [true]}"#).unwrap();
+        assert_eq!(row, expected.into_row());
+
+        assert!(state.decode(b"{").is_err());
+    }
+
+    #[test]
+    fn test_decode_columns() {
+        let mut state = JsonDecoderState::new(JsonEncoding {
+            columns: vec![
+                ("a".into(), ScalarType::Int32),
+                ("b".into(), ScalarType::String),
+                ("c".into(), ScalarType::Bool),
+            ],
+        });
+        let row = state
+            .decode(br#"{"a": "7", "b": 2.5, "d": "ignored"}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            row,
+            Row::pack_slice(&[Datum::Int32(7), Datum::String("2.5"), Datum::Null])
+        );
+
+        assert!(state.decode(br#"{"a": "seven"}"#).is_err());
+        assert!(state.decode(br#"{"a": [1]}"#).is_err());
+        assert!(state.decode(br#"[1, 2]"#).is_err());
+    }
+}
@@ -45,13 +45,17 @@ use mz_storage_client::types::sources::{IncludedColumnSource, MzOffset};
 
 use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
+use self::json::JsonDecoderState;
 use self::metrics::DecodeMetrics;
+use self::parquet::ParquetDecoderState;
 use self::protobuf::ProtobufDecoderState;
-use crate::source::types::{DecodeResult, SourceOutput};
+use crate::source::types::{DecodeResult, MessageOrigin, SourceOutput};
 
 mod avro;
 mod csv;
+mod json;
 pub mod metrics;
+mod parquet;
 mod protobuf;
 
 /// Decode delimited CDCv2 messages.
@@ -138,6 +142,7 @@ pub(crate) enum PreDelimitedFormat {
     Text,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
+    Json(JsonDecoderState),
 }
 
 impl PreDelimitedFormat {
@@ -165,6 +170,7 @@ impl PreDelimitedFormat {
                 Ok(Some(row_buf.clone()))
             }
             PreDelimitedFormat::Protobuf(pb) => pb.get_value(bytes).transpose(),
+            PreDelimitedFormat::Json(json) => json.decode(bytes),
         }
     }
 }
@@ -177,6 +183,7 @@ pub(crate) enum DataDecoderInner {
         format: PreDelimitedFormat,
     },
     Csv(CsvDecoderState),
+    Parquet(ParquetDecoderState),
 
     PreDelimited(PreDelimitedFormat),
 }
@@ -202,6 +209,7 @@ impl DataDecoder {
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes),
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
+            DataDecoderInner::Parquet(parquet) => parquet.decode(bytes),
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
                 *bytes = &[];
@@ -213,9 +221,11 @@ impl DataDecoder {
     /// Get the next record if it exists, assuming an EOF has occurred.
     ///
     /// This is distinct from `next` because, for example, a CSV record should be returned even if it
-    /// does not end in a newline.
+    /// does not end in a newline. Some formats, like Parquet, can only be decoded once the whole
+    /// object has been seen, so callers should keep calling `eof` until it returns `Ok(None)`.
     pub fn eof(&mut self, bytes: &mut &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
         match &mut self.inner {
+            DataDecoderInner::Parquet(parquet) => parquet.eof(),
             DataDecoderInner::Csv(csv) => {
                 let result = csv.decode(bytes);
                 csv.reset_for_new_object();
@@ -283,7 +293,8 @@ fn get_decoder(
         DataEncodingInner::Text
         | DataEncodingInner::Bytes
         | DataEncodingInner::Protobuf(_)
-        | DataEncodingInner::Regex(_) => {
+        | DataEncodingInner::Regex(_)
+        | DataEncodingInner::Json(_) => {
             let after_delimiting = match encoding.inner {
                 DataEncodingInner::Regex(RegexEncoding { regex }) => {
                     PreDelimitedFormat::Regex(regex.0, Default::default())
@@ -294,6 +305,9 @@ fn get_decoder(
                                     client creation in purification.",
                     ))
                 }
+                DataEncodingInner::Json(encoding) => {
+                    PreDelimitedFormat::Json(JsonDecoderState::new(encoding))
+                }
                 DataEncodingInner::Bytes => PreDelimitedFormat::Bytes,
                 DataEncodingInner::Text => PreDelimitedFormat::Text,
                 _ => unreachable!(),
@@ -315,6 +329,13 @@ fn get_decoder(
                 metrics,
             }
         }
+        DataEncodingInner::Parquet(enc) => {
+            let state = ParquetDecoderState::new(enc);
+            DataDecoder {
+                inner: DataDecoderInner::Parquet(state),
+                metrics,
+            }
+        }
         DataEncodingInner::RowCodec(_) => {
             unreachable!("RowCodec sources should not go through the general decoding path.")
         }
@@ -404,6 +425,7 @@ where
                     upstream_time_millis,
                     partition,
                     headers,
+                    origin,
                     diff: (),
                 } in data.iter()
                 {
@@ -442,6 +464,7 @@ where
                             *position,
                             *upstream_time_millis,
                             headers.as_deref(),
+                            origin.as_ref(),
                         ),
                     });
                 }
@@ -506,8 +529,6 @@ where
             let mut n_errors = 0;
             let mut n_successes = 0;
             input.for_each(|cap, data| {
-                // Currently Kafka is the only kind of source that can have metadata, and it is
-                // always delimited, so we will never have metadata in `render_decode`
                 let mut session = output.session(&cap);
                 for SourceOutput {
                     key: _,
@@ -516,6 +537,7 @@ where
                     upstream_time_millis,
                     partition,
                     headers,
+                    origin,
                     diff: (),
                 } in data.iter()
                 {
@@ -532,38 +554,43 @@ where
                             }
                             value_buf.clear();
 
-                            match result.transpose() {
-                                None => continue,
-                                Some(value) => {
-                                    if value.is_err() {
-                                        n_errors += 1;
-                                    } else if matches!(&value, Ok(_)) {
-                                        n_successes += 1;
-                                    }
-                                    // `RangeFrom` `Iterator`'s never end
-                                    let position = n_seen.next().unwrap();
-                                    let metadata = to_metadata_row(
-                                        &metadata_items,
-                                        partition.clone(),
-                                        position.into(),
-                                        *upstream_time_millis,
-                                        headers.as_deref(),
-                                    );
-
-                                    session.give(DecodeResult {
-                                        key: None,
-                                        value: Some(value.map(|r| (r, 1)).map_err(|inner| {
-                                            DecodeError {
-                                                kind: inner,
-                                                raw: None,
-                                            }
-                                        })),
-                                        position: position.into(),
-                                        upstream_time_millis: *upstream_time_millis,
-                                        partition: partition.clone(),
-                                        metadata,
-                                    });
+                            // Formats that can only be decoded once the whole object has been
+                            // seen produce all of their records at EOF, so drain the decoder.
+                            while let Some(value) = result.transpose() {
+                                let is_err = value.is_err();
+                                if is_err {
+                                    n_errors += 1;
+                                } else {
+                                    n_successes += 1;
+                                }
+                                // `RangeFrom` `Iterator`'s never end
+                                let position = n_seen.next().unwrap();
+                                let metadata = to_metadata_row(
+                                    &metadata_items,
+                                    partition.clone(),
+                                    position.into(),
+                                    *upstream_time_millis,
+                                    headers.as_deref(),
+                                    origin.as_ref(),
+                                );
+
+                                session.give(DecodeResult {
+                                    key: None,
+                                    value: Some(value.map(|r| (r, 1)).map_err(|inner| {
+                                        DecodeError {
+                                            kind: inner,
+                                            raw: None,
+                                        }
+                                    })),
+                                    position: position.into(),
+                                    upstream_time_millis: *upstream_time_millis,
+                                    partition: partition.clone(),
+                                    metadata,
+                                });
+                                if is_err {
+                                    break;
                                 }
+                                result = value_decoder.eof(&mut &[][..]);
                             }
                             continue;
                         }
@@ -614,6 +641,7 @@ where
                             position.into(),
                             *upstream_time_millis,
                             headers.as_deref(),
+                            origin.as_ref(),
                         );
 
                         if value_bytes_remaining.is_empty() {
@@ -669,65 +697,90 @@ fn to_metadata_row(
     position: MzOffset,
     upstream_time_millis: Option<i64>,
     headers: Option<&[(String, Option<Vec<u8>>)]>,
+    origin: Option<&MessageOrigin>,
 ) -> Row {
     let position = position.offset;
     let mut row = Row::default();
     let mut packer = row.packer();
-    match partition {
-        PartitionId::Kafka(partition) => {
-            for item in metadata_items.iter() {
-                match item {
-                    IncludedColumnSource::Partition => packer.push(Datum::from(partition)),
-                    IncludedColumnSource::Offset => packer.push(Datum::UInt64(position)),
-                    IncludedColumnSource::Timestamp => {
-                        let ts =
-                            upstream_time_millis.expect("kafka sources always have upstream_time");
-                        let (secs, mut millis) = (ts / 1000, (ts.abs() % 1000) as u32);
-                        if secs < 0 {
-                            millis = 1000 - millis;
-                        }
-
-                        let d: Datum = NaiveDateTime::from_timestamp_opt(secs, millis * 1_000_000)
-                            .and_then(|dt| {
-                                let ct: Option<CheckedTimestamp<NaiveDateTime>> =
-                                    dt.try_into().ok();
-                                ct
-                            })
-                            .into();
-                        packer.push(d)
-                    }
-                    IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
-                    IncludedColumnSource::Headers => {
-                        packer.push_list_with(|r| {
-                            // If the source asked for headers, but we didn't get any, we still
-                            // want to run the `push_dict_with`, to produce an empty map value
-                            //
-                            // This is a `BTreeMap`, so the `push_dict_with` ordering invariant is
-                            // upheld
-                            if let Some(headers) = headers {
-                                for (k, v) in headers {
-                                    match v {
-                                        Some(v) => r.push_list_with(|record_row| {
-                                            record_row.push(Datum::String(k));
-                                            record_row.push(Datum::Bytes(v));
-                                        }),
-                                        None => r.push_list_with(|record_row| {
-                                            record_row.push(Datum::String(k));
-                                            record_row.push(Datum::Null);
-                                        }),
-                                    }
-                                }
-                            }
-                        });
-                    }
+    for item in metadata_items.iter() {
+        match item {
+            IncludedColumnSource::Partition => match partition {
+                PartitionId::Kafka(partition) | PartitionId::KafkaTopic(_, partition) => {
+                    packer.push(Datum::from(partition))
                 }
+                PartitionId::None => unreachable!("only Kafka sources include partitions"),
+            },
+            IncludedColumnSource::Offset => packer.push(Datum::UInt64(position)),
+            IncludedColumnSource::Timestamp => {
+                let ts = upstream_time_millis.expect("kafka sources always have upstream_time");
+                packer.push(millis_to_datum(ts))
             }
-        }
-        PartitionId::None => {
-            if !metadata_items.is_empty() {
-                unreachable!("Only Kafka supports metadata items");
+            IncludedColumnSource::LastModified => {
+                // S3 does not guarantee that it reports the modification time of an object.
+                packer.push(upstream_time_millis.map_or(Datum::Null, millis_to_datum))
+            }
+            IncludedColumnSource::Headers => {
+                packer.push_list_with(|r| {
+                    // If the source asked for headers, but we didn't get any, we still
+                    // want to run the `push_dict_with`, to produce an empty map value
+                    //
+                    // This is a `BTreeMap`, so the `push_dict_with` ordering invariant is
+                    // upheld
+                    if let Some(headers) = headers {
+                        for (k, v) in headers {
+                            match v {
+                                Some(v) => r.push_list_with(|record_row| {
+                                    record_row.push(Datum::String(k));
+                                    record_row.push(Datum::Bytes(v));
+                                }),
+                                None => r.push_list_with(|record_row| {
+                                    record_row.push(Datum::String(k));
+                                    record_row.push(Datum::Null);
+                                }),
+                            }
+                        }
+                    }
+                });
             }
+            IncludedColumnSource::Topic => match origin {
+                Some(MessageOrigin::Kafka { topic }) => packer.push(Datum::String(topic)),
+                _ => unreachable!("kafka sources that include the topic always pass it along"),
+            },
+            IncludedColumnSource::ObjectKey => match origin {
+                Some(MessageOrigin::S3 { key }) => packer.push(Datum::String(key)),
+                _ => unreachable!("s3 sources that include the key always pass it along"),
+            },
+            IncludedColumnSource::ShardId => match origin {
+                Some(MessageOrigin::Kinesis { shard_id, .. }) => {
+                    packer.push(Datum::String(shard_id))
+                }
+                _ => unreachable!("kinesis sources that include the shard always pass it along"),
+            },
+            IncludedColumnSource::SequenceNumber => match origin {
+                Some(MessageOrigin::Kinesis {
+                    sequence_number, ..
+                }) => packer.push(Datum::String(sequence_number)),
+                _ => unreachable!(
+                    "kinesis sources that include the sequence number always pass it along"
+                ),
+            },
         }
     }
     row
 }
+
+/// Converts milliseconds since the unix epoch to a timestamp datum, or `NULL` if the timestamp is
+/// out of range.
+fn millis_to_datum(ts: i64) -> Datum<'static> {
+    let (secs, mut millis) = (ts / 1000, (ts.abs() % 1000) as u32);
+    if secs < 0 {
+        millis = 1000 - millis;
+    }
+
+    NaiveDateTime::from_timestamp_opt(secs, millis * 1_000_000)
+        .and_then(|dt| {
+            let ct: Option<CheckedTimestamp<NaiveDateTime>> = dt.try_into().ok();
+            ct
+        })
+        .into()
+}
@@ -0,0 +1,489 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::Cursor;
+
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Schema, TimeUnit};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use arrow2::types::NativeType;
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use mz_ore::str::StrExt;
+use mz_repr::adt::date::Date;
+use mz_repr::adt::numeric::{self, Numeric};
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{Datum, Row, ScalarType};
+use mz_storage_client::types::errors::DecodeErrorKind;
+use mz_storage_client::types::sources::encoding::ParquetEncoding;
+
+/// Decodes Parquet objects.
+///
+/// A Parquet file describes its schema and the location of its row groups in a
+/// footer at the end of the file, so nothing can be decoded until the entire
+/// object has been seen. Bytes are buffered until the end of the object is
+/// signaled via [`ParquetDecoderState::eof`]. The object is then decoded one
+/// row group at a time, so that only the rows of a single row group are held
+/// in memory at once.
+pub struct ParquetDecoderState {
+    columns: Vec<(String, ScalarType)>,
+    /// The bytes of the current object seen so far.
+    buf: Vec<u8>,
+    /// The reader over the row groups of the last complete object, if any of
+    /// them remain to be decoded.
+    reader: Option<FileReader<Cursor<Vec<u8>>>>,
+    /// The decoded rows of the current row group that have not yet been
+    /// returned.
+    rows: VecDeque<Row>,
+}
+
+impl fmt::Debug for ParquetDecoderState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetDecoderState")
+            .field("columns", &self.columns)
+            .field("buf_len", &self.buf.len())
+            .field("reading", &self.reader.is_some())
+            .field("rows", &self.rows.len())
+            .finish()
+    }
+}
+
+impl ParquetDecoderState {
+    pub fn new(ParquetEncoding { columns }: ParquetEncoding) -> Self {
+        ParquetDecoderState {
+            columns,
+            buf: vec![],
+            reader: None,
+            rows: VecDeque::new(),
+        }
+    }
+
+    /// Buffers the bytes in `chunk`, consuming all of them.
+    pub fn decode(&mut self, chunk: &mut &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
+        self.buf.extend_from_slice(chunk);
+        *chunk = &[];
+        Ok(None)
+    }
+
+    /// Returns the next row of the object whose end was most recently
+    /// reached, decoding the next row group of the object if necessary.
+    ///
+    /// Returns `None` once all rows of the object have been returned. After an
+    /// error, the remainder of the object is skipped.
+    pub fn eof(&mut self) -> Result<Option<Row>, DecodeErrorKind> {
+        if !self.buf.is_empty() {
+            let buf = std::mem::take(&mut self.buf);
+            self.rows.clear();
+            self.reader = Some(self.open_object(buf).map_err(decode_error)?);
+        }
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Ok(Some(row));
+            }
+            let Some(reader) = &mut self.reader else {
+                return Ok(None);
+            };
+            match reader.next() {
+                Some(Ok(chunk)) => match self.decode_row_group(&chunk) {
+                    Ok(rows) => self.rows = rows,
+                    Err(e) => {
+                        self.reader = None;
+                        return Err(decode_error(e));
+                    }
+                },
+                Some(Err(e)) => {
+                    self.reader = None;
+                    return Err(decode_error(e.to_string()));
+                }
+                None => self.reader = None,
+            }
+        }
+    }
+
+    /// Reads the footer of the object in `buf` and returns a reader over its
+    /// row groups that reads only the requested columns.
+    fn open_object(&self, buf: Vec<u8>) -> Result<FileReader<Cursor<Vec<u8>>>, String> {
+        let mut reader = Cursor::new(buf);
+        let metadata = read_metadata(&mut reader).map_err(|e| e.to_string())?;
+        let schema = infer_schema(&metadata).map_err(|e| e.to_string())?;
+
+        // Project the object's schema down to the requested columns, in the
+        // order in which they were requested, so that only those columns are
+        // read.
+        let fields = self
+            .columns
+            .iter()
+            .map(|(name, _)| {
+                schema
+                    .fields
+                    .iter()
+                    .find(|field| &field.name == name)
+                    .cloned()
+                    .ok_or_else(|| format!("column {} does not exist", name.quoted()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(FileReader::new(
+            reader,
+            metadata.row_groups,
+            Schema::from(fields),
+            None,
+            None,
+            None,
+        ))
+    }
+
+    /// Converts the columns of a single row group into rows.
+    fn decode_row_group(&self, chunk: &Chunk<Box<dyn Array>>) -> Result<VecDeque<Row>, String> {
+        let columns = chunk
+            .columns()
+            .iter()
+            .zip(&self.columns)
+            .map(|(array, (name, typ))| {
+                array_to_datums(&**array, typ)
+                    .map_err(|e| format!("column {}: {}", name.quoted(), e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut rows = VecDeque::with_capacity(chunk.len());
+        let mut row_buf = Row::default();
+        for i in 0..chunk.len() {
+            row_buf
+                .packer()
+                .extend(columns.iter().map(|column| column[i]));
+            rows.push_back(row_buf.clone());
+        }
+        Ok(rows)
+    }
+}
+
+fn decode_error(e: String) -> DecodeErrorKind {
+    DecodeErrorKind::Text(format!("Failed to decode Parquet object: {}", e))
+}
+
+/// Converts each value in `array` to a datum of type `typ`.
+fn array_to_datums<'a>(array: &'a dyn Array, typ: &ScalarType) -> Result<Vec<Datum<'a>>, String> {
+    if let ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64 | ScalarType::Numeric { .. } =
+        typ
+    {
+        if let Some(values) = integer_values(array) {
+            return values
+                .into_iter()
+                .map(|v| match (v, typ) {
+                    (None, _) => Ok(Datum::Null),
+                    (Some(v), ScalarType::Int16) => i16::try_from(v)
+                        .map(Datum::Int16)
+                        .map_err(|_| "smallint out of range".to_string()),
+                    (Some(v), ScalarType::Int32) => i32::try_from(v)
+                        .map(Datum::Int32)
+                        .map_err(|_| "integer out of range".to_string()),
+                    (Some(v), ScalarType::Int64) => Ok(Datum::Int64(v)),
+                    (Some(v), _) => Ok(Datum::from(Numeric::from(v))),
+                })
+                .collect();
+        }
+    }
+
+    let data_type = array.data_type().to_logical_type();
+    match (typ, data_type) {
+        (ScalarType::Bool, DataType::Boolean) => Ok(downcast::<BooleanArray>(array)
+            .iter()
+            .map(Datum::from)
+            .collect()),
+        (ScalarType::Float32, DataType::Float32) => {
+            primitive_to_datums(array, |v: f32| Ok(Datum::from(v)))
+        }
+        (ScalarType::Float64, DataType::Float32) => {
+            primitive_to_datums(array, |v: f32| Ok(Datum::from(f64::from(v))))
+        }
+        (ScalarType::Float64, DataType::Float64) => {
+            primitive_to_datums(array, |v: f64| Ok(Datum::from(v)))
+        }
+        (ScalarType::Numeric { .. }, DataType::Decimal(_, scale)) => {
+            let scale =
+                u8::try_from(*scale).map_err(|_| "decimal scale out of range".to_string())?;
+            primitive_to_datums(array, |v: i128| {
+                numeric::twos_complement_be_to_numeric(&mut v.to_be_bytes(), scale)
+                    .map(Datum::from)
+                    .map_err(|e| e.to_string())
+            })
+        }
+        (ScalarType::String, DataType::Utf8) => Ok(downcast::<Utf8Array<i32>>(array)
+            .iter()
+            .map(Datum::from)
+            .collect()),
+        (ScalarType::String, DataType::LargeUtf8) => Ok(downcast::<Utf8Array<i64>>(array)
+            .iter()
+            .map(Datum::from)
+            .collect()),
+        (ScalarType::Bytes, DataType::Binary) => Ok(downcast::<BinaryArray<i32>>(array)
+            .iter()
+            .map(Datum::from)
+            .collect()),
+        (ScalarType::Bytes, DataType::LargeBinary) => Ok(downcast::<BinaryArray<i64>>(array)
+            .iter()
+            .map(Datum::from)
+            .collect()),
+        (ScalarType::Date, DataType::Date32) => primitive_to_datums(array, |v: i32| {
+            Date::from_unix_epoch(v)
+                .map(Datum::Date)
+                .map_err(|e| e.to_string())
+        }),
+        (ScalarType::Timestamp, DataType::Timestamp(unit, _)) => {
+            primitive_to_datums(array, |v: i64| {
+                let ts = to_naive_datetime(v, unit)?;
+                CheckedTimestamp::from_timestamplike(ts)
+                    .map(Datum::Timestamp)
+                    .map_err(|e| e.to_string())
+            })
+        }
+        (ScalarType::TimestampTz, DataType::Timestamp(unit, _)) => {
+            primitive_to_datums(array, |v: i64| {
+                let ts = DateTime::<Utc>::from_utc(to_naive_datetime(v, unit)?, Utc);
+                CheckedTimestamp::from_timestamplike(ts)
+                    .map(Datum::TimestampTz)
+                    .map_err(|e| e.to_string())
+            })
+        }
+        _ => Err(format!(
+            "cannot read Parquet type {:?} as {:?}",
+            data_type, typ
+        )),
+    }
+}
+
+fn downcast<A: 'static>(array: &dyn Array) -> &A {
+    array
+        .as_any()
+        .downcast_ref::<A>()
+        .expect("array matches its data type")
+}
+
+fn primitive_to_datums<'a, T, F>(array: &'a dyn Array, f: F) -> Result<Vec<Datum<'a>>, String>
+where
+    T: NativeType,
+    F: Fn(T) -> Result<Datum<'a>, String>,
+{
+    downcast::<PrimitiveArray<T>>(array)
+        .iter()
+        .map(|v| match v {
+            None => Ok(Datum::Null),
+            Some(v) => f(*v),
+        })
+        .collect()
+}
+
+/// Returns the values of `array` widened to `i64`, if `array` is an array of
+/// integers that all fit in an `i64`.
+fn integer_values(array: &dyn Array) -> Option<Vec<Option<i64>>> {
+    fn widen<T: NativeType + Into<i64>>(array: &dyn Array) -> Vec<Option<i64>> {
+        downcast::<PrimitiveArray<T>>(array)
+            .iter()
+            .map(|v| v.map(|v| (*v).into()))
+            .collect()
+    }
+
+    Some(match array.data_type().to_logical_type() {
+        DataType::Int8 => widen::<i8>(array),
+        DataType::Int16 => widen::<i16>(array),
+        DataType::Int32 => widen::<i32>(array),
+        DataType::Int64 => widen::<i64>(array),
+        DataType::UInt8 => widen::<u8>(array),
+        DataType::UInt16 => widen::<u16>(array),
+        DataType::UInt32 => widen::<u32>(array),
+        _ => return None,
+    })
+}
+
+fn to_naive_datetime(v: i64, unit: &TimeUnit) -> Result<NaiveDateTime, String> {
+    let (secs, nanos) = match unit {
+        TimeUnit::Second => (v, 0),
+        TimeUnit::Millisecond => (v.div_euclid(1_000), v.rem_euclid(1_000) * 1_000_000),
+        TimeUnit::Microsecond => (v.div_euclid(1_000_000), v.rem_euclid(1_000_000) * 1_000),
+        TimeUnit::Nanosecond => (v.div_euclid(1_000_000_000), v.rem_euclid(1_000_000_000)),
+    };
+    u32::try_from(nanos)
+        .ok()
+        .and_then(|nanos| NaiveDateTime::from_timestamp_opt(secs, nanos))
+        .ok_or_else(|| "timestamp out of range".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow2::datatypes::Field;
+    use arrow2::io::parquet::write::{
+        CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+    };
+
+    use super::*;
+
+    fn write_parquet(fields: Vec<Field>, arrays: Vec<Box<dyn Array>>) -> Vec<u8> {
+        write_parquet_row_groups(fields, vec![arrays])
+    }
+
+    /// Writes a Parquet file with one row group per entry of `row_groups`.
+    fn write_parquet_row_groups(
+        fields: Vec<Field>,
+        row_groups: Vec<Vec<Box<dyn Array>>>,
+    ) -> Vec<u8> {
+        let schema = Schema::from(fields);
+        let options = WriteOptions {
+            write_statistics: true,
+            compression: CompressionOptions::Snappy,
+            version: Version::V2,
+        };
+        let encodings = schema
+            .fields
+            .iter()
+            .map(|_| vec![Encoding::Plain])
+            .collect();
+        let row_groups = RowGroupIterator::try_new(
+            row_groups.into_iter().map(|arrays| Ok(Chunk::new(arrays))),
+            &schema,
+            options,
+            encodings,
+        )
+        .unwrap();
+        let mut buf = vec![];
+        {
+            let mut writer = FileWriter::try_new(&mut buf, schema, options).unwrap();
+            for group in row_groups {
+                writer.write(group.unwrap()).unwrap();
+            }
+            writer.end(None).unwrap();
+        }
+        buf
+    }
+
+    fn decode_all(state: &mut ParquetDecoderState, object: &[u8]) -> Vec<Row> {
+        // Feed the object through in two chunks, as the S3 source would.
+        let (first, second) = object.split_at(object.len() / 2);
+        for mut chunk in [first, second] {
+            assert_eq!(state.decode(&mut chunk), Ok(None));
+            assert!(chunk.is_empty());
+        }
+        let mut rows = vec![];
+        while let Some(row) = state.eof().unwrap() {
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn test_decode_columns() {
+        let object = write_parquet(
+            vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+                Field::new("c", DataType::Decimal(10, 2), false),
+                Field::new("d", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+            ],
+            vec![
+                Box::new(PrimitiveArray::<i32>::from([Some(1), None])),
+                Box::new(Utf8Array::<i32>::from([Some("x"), Some("y")])),
+                Box::new(
+                    PrimitiveArray::<i128>::from_vec(vec![12345, -5]).to(DataType::Decimal(10, 2)),
+                ),
+                Box::new(
+                    PrimitiveArray::<i64>::from_vec(vec![0, 1_500])
+                        .to(DataType::Timestamp(TimeUnit::Millisecond, None)),
+                ),
+            ],
+        );
+
+        // Columns may be requested in any order and widened to larger types.
+        let mut state = ParquetDecoderState::new(ParquetEncoding {
+            columns: vec![
+                ("d".into(), ScalarType::Timestamp),
+                ("a".into(), ScalarType::Int64),
+                ("c".into(), ScalarType::Numeric { max_scale: None }),
+            ],
+        });
+        let rows = decode_all(&mut state, &object);
+        let ts = |secs, nanos| {
+            Datum::Timestamp(
+                CheckedTimestamp::from_timestamplike(
+                    NaiveDateTime::from_timestamp_opt(secs, nanos).unwrap(),
+                )
+                .unwrap(),
+            )
+        };
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack_slice(&[
+                    ts(0, 0),
+                    Datum::Int64(1),
+                    Datum::from("123.45".parse::<Numeric>().unwrap()),
+                ]),
+                Row::pack_slice(&[
+                    ts(1, 500_000_000),
+                    Datum::Null,
+                    Datum::from("-0.05".parse::<Numeric>().unwrap()),
+                ]),
+            ]
+        );
+
+        // The state is ready to decode the next object.
+        assert_eq!(decode_all(&mut state, &object).len(), 2);
+    }
+
+    #[test]
+    fn test_decode_row_groups() {
+        let object = write_parquet_row_groups(
+            vec![Field::new("a", DataType::Int64, true)],
+            vec![
+                vec![Box::new(PrimitiveArray::<i64>::from_vec(vec![1, 2, 3]))],
+                vec![Box::new(PrimitiveArray::<i64>::from_vec(vec![4, 5]))],
+            ],
+        );
+        let mut state = ParquetDecoderState::new(ParquetEncoding {
+            columns: vec![("a".into(), ScalarType::Int64)],
+        });
+        state.decode(&mut &object[..]).unwrap();
+
+        // Only the first row group is decoded to produce the first row.
+        assert_eq!(state.eof(), Ok(Some(Row::pack_slice(&[Datum::Int64(1)]))));
+        assert_eq!(state.rows.len(), 2);
+
+        let mut values = vec![];
+        while let Some(row) = state.eof().unwrap() {
+            values.push(row.unpack_first().unwrap_int64());
+        }
+        assert_eq!(values, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let object = write_parquet(
+            vec![Field::new("a", DataType::Int64, true)],
+            vec![Box::new(PrimitiveArray::<i64>::from([Some(1 << 40)]))],
+        );
+
+        for (column, typ) in [
+            ("b", ScalarType::Int64),
+            ("a", ScalarType::Int32),
+            ("a", ScalarType::String),
+        ] {
+            let mut state = ParquetDecoderState::new(ParquetEncoding {
+                columns: vec![(column.into(), typ)],
+            });
+            state.decode(&mut &object[..]).unwrap();
+            assert!(state.eof().is_err());
+            assert_eq!(state.eof(), Ok(None));
+        }
+
+        let mut state = ParquetDecoderState::new(ParquetEncoding {
+            columns: vec![("a".into(), ScalarType::Int64)],
+        });
+        state.decode(&mut &b"not a parquet file"[..]).unwrap();
+        assert!(state.eof().is_err());
+    }
+}
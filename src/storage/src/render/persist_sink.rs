@@ -121,6 +121,7 @@ pub fn render<G>(
                 )
                 .await
                 .expect("could not open persist shard");
+            write.set_key_schema(Arc::new(metadata.relation_desc.clone()));
 
             // Initialize this sink's `upper` to the `upper` of the persist shard we are writing
             // to. Data from the source not beyond this time will be dropped, as it has already
@@ -414,10 +414,12 @@ where
                         };
                     let (upsert_ok, upsert_err) = super::upsert::upsert(
                         &transformed_results,
+                        id,
                         resume_upper,
                         upsert_envelope.clone(),
                         previous_stream,
                         previous_token,
+                        storage_state.scratch_directory.clone(),
                     );
 
                     (upsert_ok.as_collection(), Some(upsert_err.as_collection()))
@@ -8,8 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use std::any::Any;
-use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use differential_dataflow::hashable::Hashable;
@@ -21,20 +22,26 @@ use timely::dataflow::operators::{Capability, CapabilityRef, Concat, OkErr, Oper
 use timely::dataflow::{Scope, Stream};
 use timely::order::PartialOrder;
 use timely::progress::frontier::AntichainRef;
-use timely::progress::{Antichain, ChangeBatch};
+use timely::progress::Antichain;
 use tracing::{error, info};
 
 use mz_expr::{EvalError, MirScalarExpr};
+use mz_ore::halt;
 use mz_ore::permutations::inverse_argsort;
-use mz_repr::{Datum, DatumVec, DatumVecBorrow, Diff, Row, RowArena, Timestamp};
+use mz_repr::{Datum, DatumVec, DatumVecBorrow, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::types::errors::{
     DataflowError, DecodeError, EnvelopeError, UpsertError, UpsertValueError,
 };
-use mz_storage_client::types::sources::{MzOffset, UpsertEnvelope, UpsertStyle};
+use mz_storage_client::types::sources::{
+    MzOffset, UpsertEnvelope, UpsertStateBackend, UpsertStyle,
+};
 use mz_timely_util::operator::StreamExt;
 
+use crate::render::upsert::state::{DiskUpsertState, MemoryUpsertState, UpsertState};
 use crate::source::types::DecodeResult;
 
+mod state;
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 struct UpsertSourceData {
     /// The actual value
@@ -54,12 +61,17 @@ struct UpsertSourceData {
 /// the rendering pipeline in that their input is a stream
 /// with two components instead of one, and the second component
 /// can be null or empty.
+///
+/// The operator keeps the current value of each key in the backend selected by
+/// `upsert_envelope`. Disk-backed state is kept in files in `scratch_directory`.
 pub(crate) fn upsert<G>(
     stream: &Stream<G, DecodeResult>,
+    source_id: GlobalId,
     as_of_frontier: Antichain<Timestamp>,
     upsert_envelope: UpsertEnvelope,
     previous: Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>,
     previous_token: Option<Rc<dyn Any>>,
+    scratch_directory: PathBuf,
 ) -> (
     Stream<G, (Row, Timestamp, Diff)>,
     Stream<G, (DataflowError, Timestamp, Diff)>,
@@ -133,12 +145,14 @@ where
 
     let upsert_output = upsert_core(
         stream,
+        source_id,
         predicates,
         position_or,
         as_of_frontier,
         upsert_envelope,
         previous.as_collection(),
         previous_token,
+        scratch_directory,
     );
     let (mut oks, errs2) = upsert_output.ok_err(|(data, time, diff)| match data {
         Ok(data) => Ok((data, time, diff)),
@@ -274,16 +288,49 @@ fn extract_kv<G: Scope>(
 /// Internal core upsert logic.
 fn upsert_core<G>(
     stream: &Stream<G, DecodeResult>,
+    source_id: GlobalId,
     predicates: Vec<MirScalarExpr>,
     position_or: Vec<Option<usize>>,
     as_of_frontier: Antichain<Timestamp>,
     upsert_envelope: UpsertEnvelope,
     previous: Collection<G, Result<Row, UpsertError>, Diff>,
     mut previous_token: Option<Rc<dyn Any>>,
+    scratch_directory: PathBuf,
 ) -> Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>
 where
     G: Scope<Timestamp = Timestamp>,
 {
+    let worker_index = stream.scope().index();
+    let state_backend = upsert_envelope.state_backend;
+    let mut current_values: Box<dyn UpsertState> = match state_backend {
+        UpsertStateBackend::Memory => Box::new(MemoryUpsertState::default()),
+        UpsertStateBackend::Disk => {
+            // The file name must be unique even if this source is rendered
+            // again before the previous rendering's state is dropped.
+            let name = format!(
+                "upsert-{}-{}-{}",
+                source_id,
+                worker_index,
+                uuid::Uuid::new_v4()
+            );
+            let state = DiskUpsertState::new(&scratch_directory, &name).unwrap_or_else(|e| {
+                halt!(
+                    "creating upsert state for source {} in {}: {}",
+                    source_id,
+                    scratch_directory.display(),
+                    e
+                )
+            });
+            Box::new(state)
+        }
+    };
+    // The state holds no previous output to rehydrate from if there is none.
+    if previous_token.is_none() {
+        current_values
+            .finish_rehydration()
+            .unwrap_or_else(|e| halt!("upsert state for source {}: {}", source_id, e));
+    }
+
     // Prepare sorted and structured `key_indices` required
     // by the upsert operator, and a `DatumVec` used to avoid
     // an allocation.
@@ -345,45 +392,36 @@ where
                 .map(|(idx, value_idx)| (*value_idx, idx))
                 .collect();
             let mut kdv = DatumVec::new();
-            // this is a map of (decoded key) -> (decoded_value). We store the
-            // latest value for a given key that way we know what to retract if
-            // a new value with the same key comes along.
+            // `current_values` is a map of (decoded key) -> (decoded_value).
+            // We store the latest value for a given key that way we know what
+            // to retract if a new value with the same key comes along.
             //
-            // If `previous_token` is true, we need to rehydrate this from the last good input,
-            // so set it to `None` for now.
-            let mut current_values = if previous_token.is_some() {
-                None
-            } else {
-                Some(HashMap::default())
-            };
-
-            let mut initial_values_multiset = ChangeBatch::default();
+            // If `previous_token` is set, we need to rehydrate it from the
+            // last good input first.
             move |data_input, previous_input, output| {
                 if previous_token.is_some() {
-                    assert!(current_values.is_none());
-                    // Hydrate the `current_values` map from the previous state of the collection.
-                    // We can't just insert things into the `current_values` map directly, since
-                    // we might in general have non-one multiplicities due to Persist being behind on compaction.
-                    // Thus, we use `initial_values_multiset` to keep track of how many of each record we've seen.
+                    // Hydrate `current_values` from the previous state of the collection.
+                    // The previous state might in general have non-one multiplicities due
+                    // to Persist being behind on compaction, so the state consolidates the
+                    // updates as they stream in, in the same backend it keeps its values in.
                     //
-                    // At the end of reading the entire previous input, `initial_values_multiset` must have exactly one of each record,
-                    // and furthermore, each key must be unique. We validate this property for sanity's sake, and build `current_values`.
-                    //
-                    // TODO[btv] This algorithm has the potential to use unbounded space if we can't make any assumptions
-                    // about Persist's level of compaction or the order in which it returns updates.
-                    // See the detailed discussion [here](https://materializeinc.slack.com/archives/C01CFKM1QRF/p1659063332027139).
-                    // This is thought to be fine for our current purposes, but we will need to rethink it once the Persist team has
-                    // thought harder about what guarantees/APIs they want to offer relating to bounded-memory consolidation.
-                    // Tracked here: https://github.com/MaterializeInc/materialize/issues/14086
+                    // Once we have read the entire previous input, each record must have a
+                    // multiplicity of exactly one, and furthermore, each key must be unique.
+                    // The state validates this property for sanity's sake.
                     previous_input.for_each(|_cap, data| {
                         data.swap(&mut repop_scratch_vector);
-                        initial_values_multiset.extend(
-                            repop_scratch_vector
-                                .drain(..)
-                                // filter out records at or past when we are resuming this operator from
-                                .filter(|(_d, t, _r)| !as_of_frontier.less_equal(t))
-                                .map(|(d, _t, r)| (d, r)),
-                        );
+                        for ((k, v), t, r) in repop_scratch_vector.drain(..) {
+                            // filter out records at or past when we are resuming this operator from
+                            if !as_of_frontier.less_equal(&t) {
+                                current_values.rehydrate(k, v, r).unwrap_or_else(|e| {
+                                    halt!(
+                                        "rehydrating upsert state for source {}: {}",
+                                        source_id,
+                                        e
+                                    )
+                                });
+                            }
+                        }
                     });
                     if PartialOrder::less_equal(
                         &AntichainRef::new(&as_of_frontier),
@@ -393,23 +431,9 @@ where
                         // Without this, we will re-download everything we upload, wasting tons of bandwidth.
                         previous_token = None;
 
-                        let mut new_current_values =
-                            HashMap::with_capacity(initial_values_multiset.len());
-                        for ((k, v), r) in initial_values_multiset.drain() {
-                            assert!(
-                                r == 1,
-                                "The upsert state should have exactly one value per key"
-                            );
-                            match new_current_values.entry(k) {
-                                Entry::Occupied(_oe) => {
-                                    panic!("The upsert state should have exactly one value per key")
-                                }
-                                Entry::Vacant(ve) => {
-                                    ve.insert(v);
-                                }
-                            }
+                        if let Err(e) = current_values.finish_rehydration() {
+                            halt!("rehydrating upsert state for source {}: {}", source_id, e);
                         }
-                        current_values = Some(new_current_values);
                     }
                 }
 
@@ -427,10 +451,9 @@ where
                 // Don't try to do anything if we aren't done building the `current_values` map.
                 // Any new data that comes in as we rehydrate `current_values` is just stored in
                 // memory in `pending_values` until we are ready to merge it into `current_values`.
-                let current_values = match &mut current_values {
-                    None => return,
-                    Some(x) => x,
-                };
+                if previous_token.is_some() {
+                    return;
+                }
 
                 let mut removed_times = Vec::new();
                 for (time, (cap, map)) in pending_values.iter_mut() {
@@ -441,11 +464,11 @@ where
                         // it will be less than the times in the rest of the map
                         break;
                     }
-                    process_pending_values_batch(
+                    let result = process_pending_values_batch(
                         time,
                         cap,
                         map,
-                        current_values,
+                        &mut *current_values,
                         &mut row_packer,
                         &mut dv,
                         &upsert_envelope,
@@ -456,7 +479,12 @@ where
                         &position_or,
                         &mut removed_times,
                         output,
-                    )
+                    );
+                    if let Err(e) = result {
+                        // The state is rebuilt from the operator's output when
+                        // the source restarts.
+                        halt!("updating upsert state for source {}: {}", source_id, e);
+                    }
                 }
                 // Discard entries, capabilities for complete times.
                 for time in removed_times {
@@ -549,7 +577,7 @@ fn process_pending_values_batch(
     cap: &mut Capability<Timestamp>,
     map: &mut HashMap<Option<Result<Row, DecodeError>>, UpsertSourceData>,
     // The current map of values we use to perform the upsert comparision
-    current_values: &mut HashMap<Result<Row, DecodeError>, Result<Row, DataflowError>>,
+    current_values: &mut dyn UpsertState,
     // A shared row used to pack new rows for evaluation and output
     row_packer: &mut Row,
     // A shared row used to build a Vec<Datum<'_>> for evaluation
@@ -580,7 +608,7 @@ fn process_pending_values_batch(
             (Result<Row, DataflowError>, Timestamp, Diff),
         >,
     >,
-) {
+) -> Result<(), io::Error> {
     let mut session = output.session(cap);
     removed_times.push(time.clone());
     for (key, data) in map.drain() {
@@ -636,7 +664,7 @@ fn process_pending_values_batch(
                     .map(|full_row| thin(key_indices_sorted, full_row, row_packer))
                     .map_err(|e| e.clone());
                 current_values
-                    .insert(decoded_key.clone(), thinned_value)
+                    .insert(decoded_key.clone(), thinned_value)?
                     .map(|res| {
                         res.map(|v| {
                             rehydrate(
@@ -651,7 +679,7 @@ fn process_pending_values_batch(
                         })
                     })
             } else {
-                current_values.remove(&decoded_key).map(|res| {
+                current_values.remove(&decoded_key)?.map(|res| {
                     res.map(|v| {
                         rehydrate(
                             key_indices_map,
@@ -676,6 +704,7 @@ fn process_pending_values_batch(
             }
         }
     }
+    Ok(())
 }
 
 fn build_datum_vec_for_evaluation<'row>(
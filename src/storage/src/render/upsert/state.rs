@@ -0,0 +1,632 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Backends for the state that the upsert operator keeps for each key.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+
+use differential_dataflow::hashable::Hashable;
+use timely::progress::ChangeBatch;
+
+use mz_persist_types::Codec;
+use mz_repr::{Diff, Row};
+use mz_storage_client::types::errors::{DataflowError, DecodeError};
+
+pub(super) type UpsertKey = Result<Row, DecodeError>;
+pub(super) type UpsertValue = Result<Row, DataflowError>;
+
+/// The current value of each key of an upsert source.
+///
+/// A state starts out rehydrating: it receives the updates of the upsert
+/// operator's previous output through [`UpsertState::rehydrate`], until
+/// [`UpsertState::finish_rehydration`] is called. Only then may keys be
+/// inserted and removed.
+pub(super) trait UpsertState {
+    /// Sets the value of `key` to `value`, returning the previous value of
+    /// `key`, if any.
+    fn insert(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+    ) -> Result<Option<UpsertValue>, io::Error>;
+
+    /// Removes `key`, returning its previous value, if any.
+    fn remove(&mut self, key: &UpsertKey) -> Result<Option<UpsertValue>, io::Error>;
+
+    /// Adds `diff` to the multiplicity of `(key, value)` in the previous
+    /// output of the upsert operator.
+    ///
+    /// The previous output need not be consolidated, so updates for the same
+    /// key may arrive in any order and with any multiplicity.
+    fn rehydrate(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+        diff: Diff,
+    ) -> Result<(), io::Error>;
+
+    /// Sets the value of each key to its value in the previous output of the
+    /// upsert operator.
+    ///
+    /// Panics if the consolidated previous output does not hold exactly one
+    /// value, with multiplicity one, for each of its keys.
+    fn finish_rehydration(&mut self) -> Result<(), io::Error>;
+}
+
+/// An [`UpsertState`] that keeps keys and values in memory.
+#[derive(Default)]
+pub(super) struct MemoryUpsertState {
+    values: HashMap<UpsertKey, UpsertValue>,
+    rehydration: ChangeBatch<(UpsertKey, UpsertValue)>,
+}
+
+impl UpsertState for MemoryUpsertState {
+    fn insert(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+    ) -> Result<Option<UpsertValue>, io::Error> {
+        Ok(self.values.insert(key, value))
+    }
+
+    fn remove(&mut self, key: &UpsertKey) -> Result<Option<UpsertValue>, io::Error> {
+        Ok(self.values.remove(key))
+    }
+
+    fn rehydrate(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+        diff: Diff,
+    ) -> Result<(), io::Error> {
+        self.rehydration.update((key, value), diff);
+        Ok(())
+    }
+
+    fn finish_rehydration(&mut self) -> Result<(), io::Error> {
+        for ((key, value), diff) in self.rehydration.drain() {
+            assert_rehydrated(diff, self.values.insert(key, value).is_some());
+        }
+        Ok(())
+    }
+}
+
+/// An [`UpsertState`] that keeps keys and values in an append-only file on
+/// local disk.
+///
+/// The state is not durable. Its files are deleted when the state is dropped,
+/// and after a restart the upsert operator rehydrates it from its output
+/// collection, exactly as it does for in-memory state.
+pub(super) struct DiskUpsertState {
+    /// The value of each key.
+    values: RecordFile,
+    /// While rehydrating, the multiplicity of each `(key, value)` pair in the
+    /// previous output of the upsert operator.
+    ///
+    /// The record for a pair is keyed by the length of the encoded key, the
+    /// encoded key, and the encoded value, and holds the multiplicity.
+    rehydration: Option<RecordFile>,
+    key_buf: Vec<u8>,
+    value_buf: Vec<u8>,
+}
+
+impl DiskUpsertState {
+    /// Creates an empty state backed by new files whose names start with
+    /// `name` in `directory`.
+    pub(super) fn new(directory: &Path, name: &str) -> Result<Self, io::Error> {
+        fs::create_dir_all(directory)?;
+        Ok(DiskUpsertState {
+            values: RecordFile::new(directory.join(name))?,
+            rehydration: Some(RecordFile::new(
+                directory.join(format!("{name}.rehydration")),
+            )?),
+            key_buf: Vec::new(),
+            value_buf: Vec::new(),
+        })
+    }
+}
+
+impl UpsertState for DiskUpsertState {
+    fn insert(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+    ) -> Result<Option<UpsertValue>, io::Error> {
+        encode_key(&key, &mut self.key_buf);
+        encode_value(&value, &mut self.value_buf);
+        match self.values.insert(&self.key_buf, &self.value_buf)? {
+            Some(old_value) => Ok(Some(decode_value(old_value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn remove(&mut self, key: &UpsertKey) -> Result<Option<UpsertValue>, io::Error> {
+        encode_key(key, &mut self.key_buf);
+        match self.values.remove(&self.key_buf)? {
+            Some(old_value) => Ok(Some(decode_value(old_value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn rehydrate(
+        &mut self,
+        key: UpsertKey,
+        value: UpsertValue,
+        diff: Diff,
+    ) -> Result<(), io::Error> {
+        let rehydration = self
+            .rehydration
+            .as_mut()
+            .expect("rehydrate called after finish_rehydration");
+        encode_key(&key, &mut self.value_buf);
+        self.key_buf.clear();
+        let key_len = u32::try_from(self.value_buf.len()).expect("upsert key too large");
+        self.key_buf.extend(key_len.to_le_bytes());
+        self.key_buf.extend(&self.value_buf);
+        encode_value(&value, &mut self.value_buf);
+        self.key_buf.extend(&self.value_buf);
+
+        let diff = match rehydration.get(&self.key_buf)? {
+            Some(old_diff) => decode_diff(old_diff)? + diff,
+            None => diff,
+        };
+        if diff == 0 {
+            rehydration.remove(&self.key_buf)?;
+        } else {
+            rehydration.insert(&self.key_buf, &diff.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn finish_rehydration(&mut self) -> Result<(), io::Error> {
+        let mut rehydration = self
+            .rehydration
+            .take()
+            .expect("finish_rehydration called twice");
+        let values = &mut self.values;
+        rehydration.for_each(|pair, diff| {
+            let (key_len, pair) = pair.split_at(4);
+            let key_len = u32::from_le_bytes(key_len.try_into().expect("split at 4"));
+            let (key, value) = pair.split_at(usize::try_from(key_len).expect("u32 fits in usize"));
+            let existed = values.insert(key, value)?.is_some();
+            assert_rehydrated(decode_diff(diff)?, existed);
+            Ok(())
+        })
+    }
+}
+
+/// Verifies that the consolidated previous output of the upsert operator
+/// holds a single copy of a `(key, value)` pair, and no other value for the
+/// key.
+fn assert_rehydrated(diff: Diff, key_seen_before: bool) {
+    assert!(
+        diff == 1,
+        "The upsert state should have exactly one value per key"
+    );
+    if key_seen_before {
+        panic!("The upsert state should have exactly one value per key")
+    }
+}
+
+/// The amount of garbage, in bytes, that a [`RecordFile`] tolerates before it
+/// considers rewriting its file.
+const COMPACTION_THRESHOLD: u64 = 64 << 20;
+
+/// A map from byte strings to byte strings, kept in an append-only file on
+/// local disk.
+///
+/// Only a hash of each key and the location of its record in the file are
+/// kept in memory. Every insert appends a new record to the file. Overwritten
+/// and removed records are reclaimed by rewriting the file once it holds more
+/// garbage than live data.
+///
+/// The file is deleted when the map is dropped.
+struct RecordFile {
+    path: PathBuf,
+    file: BufWriter<File>,
+    /// The number of bytes written to `file`, including buffered bytes.
+    len: u64,
+    /// The number of bytes in `file` that belong to live records.
+    live_len: u64,
+    /// The records in `file`, by the hash of their key.
+    index: HashMap<u64, Slot>,
+    read_buf: Vec<u8>,
+}
+
+/// The location of a record in the file of a [`RecordFile`].
+///
+/// A record is a key immediately followed by a value.
+#[derive(Clone, Copy, Debug)]
+struct RecordPos {
+    offset: u64,
+    key_len: u32,
+    value_len: u32,
+}
+
+impl RecordPos {
+    fn len(&self) -> u64 {
+        u64::from(self.key_len) + u64::from(self.value_len)
+    }
+
+    fn key_len(&self) -> usize {
+        usize::try_from(self.key_len).expect("u32 fits in usize")
+    }
+}
+
+/// The records whose keys share a hash.
+#[derive(Debug)]
+enum Slot {
+    One(RecordPos),
+    Many(Vec<RecordPos>),
+}
+
+impl Slot {
+    fn positions(&self) -> &[RecordPos] {
+        match self {
+            Slot::One(pos) => std::slice::from_ref(pos),
+            Slot::Many(positions) => positions,
+        }
+    }
+
+    fn positions_mut(&mut self) -> &mut [RecordPos] {
+        match self {
+            Slot::One(pos) => std::slice::from_mut(pos),
+            Slot::Many(positions) => positions,
+        }
+    }
+}
+
+impl RecordFile {
+    /// Creates an empty map backed by a new file at `path`.
+    fn new(path: PathBuf) -> Result<Self, io::Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        Ok(RecordFile {
+            path,
+            file: BufWriter::new(file),
+            len: 0,
+            live_len: 0,
+            index: HashMap::new(),
+            read_buf: Vec::new(),
+        })
+    }
+
+    /// Reads the record at `pos` into `read_buf`.
+    fn read_record(&mut self, pos: RecordPos) -> Result<(), io::Error> {
+        // Records may still be sitting in the write buffer.
+        self.file.flush()?;
+        self.read_buf.resize(
+            usize::try_from(pos.len()).expect("record fits in memory"),
+            0,
+        );
+        self.file
+            .get_ref()
+            .read_exact_at(&mut self.read_buf, pos.offset)
+    }
+
+    /// Finds the record for `key`, returning the record's hash and its
+    /// position among the records with that hash.
+    ///
+    /// If found, the record is left in `read_buf`.
+    fn find(&mut self, key: &[u8]) -> Result<Option<(u64, usize)>, io::Error> {
+        let hash = key.hashed();
+        let positions = match self.index.get(&hash) {
+            Some(slot) => slot.positions().to_vec(),
+            None => return Ok(None),
+        };
+        for (i, pos) in positions.into_iter().enumerate() {
+            if pos.key_len() != key.len() {
+                continue;
+            }
+            self.read_record(pos)?;
+            if self.read_buf[..key.len()] == *key {
+                return Ok(Some((hash, i)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Appends a record made of `key` and `value` to the file.
+    fn append(&mut self, key: &[u8], value: &[u8]) -> Result<RecordPos, io::Error> {
+        let pos = RecordPos {
+            offset: self.len,
+            key_len: u32::try_from(key.len()).expect("upsert key too large"),
+            value_len: u32::try_from(value.len()).expect("upsert value too large"),
+        };
+        self.file.write_all(key)?;
+        self.file.write_all(value)?;
+        self.len += pos.len();
+        self.live_len += pos.len();
+        Ok(pos)
+    }
+
+    /// Returns the value of `key`, if any.
+    fn get(&mut self, key: &[u8]) -> Result<Option<&[u8]>, io::Error> {
+        match self.find(key)? {
+            Some(_) => Ok(Some(&self.read_buf[key.len()..])),
+            None => Ok(None),
+        }
+    }
+
+    /// Sets the value of `key` to `value`, returning the previous value of
+    /// `key`, if any.
+    fn insert(&mut self, key: &[u8], value: &[u8]) -> Result<Option<&[u8]>, io::Error> {
+        let existing = self.find(key)?;
+        let new_pos = self.append(key, value)?;
+        let old_pos = match existing {
+            Some((hash, i)) => {
+                let slot = self.index.get_mut(&hash).expect("found above");
+                let old_pos = std::mem::replace(&mut slot.positions_mut()[i], new_pos);
+                self.live_len -= old_pos.len();
+                Some(old_pos)
+            }
+            None => {
+                let hash = key.hashed();
+                let slot = match self.index.remove(&hash) {
+                    None => Slot::One(new_pos),
+                    Some(Slot::One(pos)) => Slot::Many(vec![pos, new_pos]),
+                    Some(Slot::Many(mut positions)) => {
+                        positions.push(new_pos);
+                        Slot::Many(positions)
+                    }
+                };
+                self.index.insert(hash, slot);
+                None
+            }
+        };
+        self.finish_update(old_pos)
+    }
+
+    /// Removes `key`, returning its previous value, if any.
+    fn remove(&mut self, key: &[u8]) -> Result<Option<&[u8]>, io::Error> {
+        let (hash, i) = match self.find(key)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let old_pos = match self.index.remove(&hash).expect("found above") {
+            Slot::One(pos) => pos,
+            Slot::Many(mut positions) => {
+                let pos = positions.swap_remove(i);
+                let slot = match positions.as_slice() {
+                    [pos] => Slot::One(*pos),
+                    _ => Slot::Many(positions),
+                };
+                self.index.insert(hash, slot);
+                pos
+            }
+        };
+        self.live_len -= old_pos.len();
+        self.finish_update(Some(old_pos))
+    }
+
+    /// Compacts the file if necessary after an update, and returns the value
+    /// of the record at `old_pos`, which the update replaced or removed.
+    fn finish_update(&mut self, old_pos: Option<RecordPos>) -> Result<Option<&[u8]>, io::Error> {
+        // Compaction does not preserve dead records, so read the old record
+        // first.
+        if let Some(old_pos) = old_pos {
+            self.read_record(old_pos)?;
+        }
+        if self.needs_compaction() {
+            let old_record = std::mem::take(&mut self.read_buf);
+            self.compact()?;
+            self.read_buf = old_record;
+        }
+        Ok(old_pos.map(|old_pos| &self.read_buf[old_pos.key_len()..]))
+    }
+
+    /// Calls `f` with the key and value of each record.
+    fn for_each<F>(&mut self, mut f: F) -> Result<(), io::Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), io::Error>,
+    {
+        let positions: Vec<_> = self
+            .index
+            .values()
+            .flat_map(|slot| slot.positions())
+            .copied()
+            .collect();
+        for pos in positions {
+            self.read_record(pos)?;
+            let (key, value) = self.read_buf.split_at(pos.key_len());
+            f(key, value)?;
+        }
+        Ok(())
+    }
+
+    fn needs_compaction(&self) -> bool {
+        let garbage = self.len - self.live_len;
+        garbage >= COMPACTION_THRESHOLD && garbage >= self.live_len
+    }
+
+    /// Rewrites the file to contain only live records.
+    fn compact(&mut self) -> Result<(), io::Error> {
+        self.file.flush()?;
+
+        let mut compacted_path = self.path.clone().into_os_string();
+        compacted_path.push(".compacted");
+        let compacted_path = PathBuf::from(compacted_path);
+        let compacted = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&compacted_path)?;
+        let mut writer = BufWriter::new(compacted);
+        let mut len = 0;
+        for slot in self.index.values_mut() {
+            for pos in slot.positions_mut() {
+                self.read_buf.resize(
+                    usize::try_from(pos.len()).expect("record fits in memory"),
+                    0,
+                );
+                self.file
+                    .get_ref()
+                    .read_exact_at(&mut self.read_buf, pos.offset)?;
+                writer.write_all(&self.read_buf)?;
+                pos.offset = len;
+                len += pos.len();
+            }
+        }
+        writer.flush()?;
+        fs::rename(&compacted_path, &self.path)?;
+        self.file = writer;
+        self.len = len;
+        self.live_len = len;
+        Ok(())
+    }
+}
+
+impl Drop for RecordFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn encode_key(key: &UpsertKey, buf: &mut Vec<u8>) {
+    buf.clear();
+    match key {
+        Ok(row) => {
+            buf.push(0);
+            row.encode(buf);
+        }
+        Err(err) => {
+            buf.push(1);
+            err.encode(buf);
+        }
+    }
+}
+
+fn encode_value(value: &UpsertValue, buf: &mut Vec<u8>) {
+    buf.clear();
+    match value {
+        Ok(row) => {
+            buf.push(0);
+            row.encode(buf);
+        }
+        Err(err) => {
+            buf.push(1);
+            err.encode(buf);
+        }
+    }
+}
+
+fn decode_value(buf: &[u8]) -> Result<UpsertValue, io::Error> {
+    let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+    match buf.split_first() {
+        Some((0, row)) => Ok(Ok(Row::decode(row).map_err(invalid)?)),
+        Some((1, err)) => Ok(Err(DataflowError::decode(err).map_err(invalid)?)),
+        _ => Err(invalid("corrupt upsert state record".into())),
+    }
+}
+
+fn decode_diff(buf: &[u8]) -> Result<Diff, io::Error> {
+    let buf = buf.try_into().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "corrupt upsert rehydration record",
+        )
+    })?;
+    Ok(Diff::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    fn row(s: &str) -> Row {
+        Row::pack([Datum::String(s)])
+    }
+
+    fn new_disk_state() -> (PathBuf, DiskUpsertState) {
+        let dir = std::env::temp_dir().join(format!("upsert-state-test-{}", uuid::Uuid::new_v4()));
+        let state = DiskUpsertState::new(&dir, "state").unwrap();
+        (dir, state)
+    }
+
+    #[test]
+    fn test_disk_upsert_state() {
+        let (dir, mut state) = new_disk_state();
+        state.finish_rehydration().unwrap();
+
+        assert_eq!(state.insert(Ok(row("a")), Ok(row("1"))).unwrap(), None);
+        assert_eq!(state.insert(Ok(row("b")), Ok(row("2"))).unwrap(), None);
+        assert_eq!(
+            state.insert(Ok(row("a")), Ok(row("3"))).unwrap(),
+            Some(Ok(row("1")))
+        );
+        assert_eq!(state.remove(&Ok(row("b"))).unwrap(), Some(Ok(row("2"))));
+        assert_eq!(state.remove(&Ok(row("b"))).unwrap(), None);
+        assert_eq!(state.remove(&Ok(row("c"))).unwrap(), None);
+        assert_eq!(state.insert(Ok(row("b")), Ok(row("4"))).unwrap(), None);
+        assert_eq!(state.remove(&Ok(row("a"))).unwrap(), Some(Ok(row("3"))));
+        assert_eq!(state.remove(&Ok(row("b"))).unwrap(), Some(Ok(row("4"))));
+
+        let path = state.values.path.clone();
+        assert!(path.exists());
+        drop(state);
+        assert!(!path.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_upsert_state_compaction() {
+        let (dir, mut state) = new_disk_state();
+        state.finish_rehydration().unwrap();
+
+        let big = "x".repeat(1 << 20);
+        for i in 0..100 {
+            state
+                .insert(Ok(row("key")), Ok(row(&format!("{big}{i}"))))
+                .unwrap();
+        }
+        assert!(state.values.len < 2 * COMPACTION_THRESHOLD);
+        assert_eq!(
+            state.remove(&Ok(row("key"))).unwrap(),
+            Some(Ok(row(&format!("{big}99"))))
+        );
+        drop(state);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_upsert_state_rehydration() {
+        let (dir, mut state) = new_disk_state();
+
+        // An unconsolidated previous output, in which "a" moved from "1" to
+        // "2", and "b" was retracted before it was inserted.
+        let mut rehydrate = |k, v, diff| state.rehydrate(Ok(row(k)), Ok(row(v)), diff).unwrap();
+        rehydrate("a", "1", 1);
+        rehydrate("b", "3", -1);
+        rehydrate("a", "2", 1);
+        rehydrate("b", "3", 1);
+        rehydrate("a", "1", -1);
+        rehydrate("b", "3", 1);
+        let rehydration_path = state.rehydration.as_ref().unwrap().path.clone();
+        assert!(rehydration_path.exists());
+        state.finish_rehydration().unwrap();
+        assert!(!rehydration_path.exists());
+
+        assert_eq!(state.remove(&Ok(row("a"))).unwrap(), Some(Ok(row("2"))));
+        assert_eq!(state.remove(&Ok(row("b"))).unwrap(), Some(Ok(row("3"))));
+        assert_eq!(state.remove(&Ok(row("c"))).unwrap(), None);
+        drop(state);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -10,6 +10,7 @@
 //! An interactive dataflow server.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
@@ -44,6 +45,10 @@ pub struct Config {
     pub metrics_registry: MetricsRegistry,
     /// Configuration for source and sink connection.
     pub connection_context: ConnectionContext,
+    /// A directory in which dataflows can keep ephemeral state on local disk.
+    pub scratch_directory: PathBuf,
+    /// Whether to write a structured columnar encoding of keys to persist.
+    pub persist_write_structured_keys: bool,
 }
 
 /// A handle to a running dataflow server.
@@ -73,10 +78,9 @@ pub fn serve(
 
     let tokio_executor = tokio::runtime::Handle::current();
     let now = config.now;
-    let persist_clients = PersistClientCache::new(
-        PersistConfig::new(config.build_info, now.clone()),
-        &config.metrics_registry,
-    );
+    let mut persist_config = PersistConfig::new(config.build_info, now.clone());
+    persist_config.write_structured_keys = config.persist_write_structured_keys;
+    let persist_clients = PersistClientCache::new(persist_config, &config.metrics_registry);
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
 
     let worker_guards = timely::execute::execute(config.timely_config, move |timely_worker| {
@@ -112,6 +116,7 @@ pub fn serve(
                 sink_write_frontiers: HashMap::new(),
                 sink_handles: HashMap::new(),
                 dropped_ids: Vec::new(),
+                scratch_directory: config.scratch_directory.clone(),
             },
         }
         .run()
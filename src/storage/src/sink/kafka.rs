@@ -45,6 +45,7 @@ use tracing::{debug, error, info, warn};
 use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator};
 use mz_interchange::encode::Encode;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{DecodedDescriptors, Encoder as ProtobufEncoder};
 use mz_kafka_util::client::{BrokerRewritingClientContext, MzClientContext};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
@@ -55,8 +56,8 @@ use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::errors::DataflowError;
 use mz_storage_client::types::sinks::{
-    KafkaSinkConnection, MetadataFilled, PublishedSchemaInfo, SinkAsOf, SinkEnvelope,
-    StorageSinkDesc,
+    KafkaSinkConnection, MetadataFilled, ProtobufSinkMessage, PublishedSchemaInfo, SinkAsOf,
+    SinkEnvelope, StorageSinkDesc,
 };
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 
@@ -901,11 +902,49 @@ where
         .map(|(desc, _indices)| desc.clone());
     let value_desc = connection.value_desc.clone();
 
-    let encoded_stream = match connection.published_schema_info {
-        Some(PublishedSchemaInfo {
-            key_schema_id,
-            value_schema_id,
-        }) => {
+    let encoded_stream = match (connection.protobuf_format, connection.published_schema_info) {
+        (Some(format), published_schema_info) => {
+            let (key_schema_id, value_schema_id) = match published_schema_info {
+                Some(PublishedSchemaInfo {
+                    key_schema_id,
+                    value_schema_id,
+                }) => (key_schema_id, Some(value_schema_id)),
+                None => (None, None),
+            };
+            let decode = |message: ProtobufSinkMessage| {
+                DecodedDescriptors::from_bytes(&message.descriptors, message.message_name)
+                    .expect("protobuf descriptors validated during planning")
+            };
+            let key = match (key_desc, format.key) {
+                (Some(key_desc), Some(key_message)) => {
+                    Some((key_desc, decode(key_message), key_schema_id))
+                }
+                _ => None,
+            };
+            let encoder = ProtobufEncoder::new(
+                key,
+                value_desc,
+                decode(format.value),
+                value_schema_id,
+                matches!(envelope, Some(SinkEnvelope::Debezium)),
+            )
+            .expect("protobuf encoder validated during planning");
+            encode_stream(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                encoder,
+                connection.fuel,
+                name.clone(),
+            )
+        }
+        (
+            None,
+            Some(PublishedSchemaInfo {
+                key_schema_id,
+                value_schema_id,
+            }),
+        ) => {
             let schema_generator = AvroSchemaGenerator::new(
                 None,
                 None,
@@ -923,7 +962,7 @@ where
                 name.clone(),
             )
         }
-        None => {
+        (None, None) => {
             let encoder = JsonEncoder::new(
                 key_desc,
                 value_desc,
@@ -966,7 +1005,14 @@ where
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnection`] will be discarded without producing them.
 pub fn produce_to_kafka<G>(
-    stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
+    stream: Stream<
+        G,
+        (
+            Result<(Option<Vec<u8>>, Option<Vec<u8>>), String>,
+            Timestamp,
+            Diff,
+        ),
+    >,
     id: GlobalId,
     name: String,
     connection: KafkaSinkConnection,
@@ -1107,7 +1153,7 @@ where
                             // Queue all pending rows waiting to be sent to kafka
                             assert!(is_active_worker);
                             rows.swap(&mut vector);
-                            for ((key, value), time, diff) in vector.drain(..) {
+                            for (encoded, time, diff) in vector.drain(..) {
                                 let should_emit = if as_of.strict {
                                     as_of.frontier.less_than(&time)
                                 } else {
@@ -1128,6 +1174,15 @@ where
                                 };
                                 let diff = diff as usize;
 
+                                let (key, value) = match encoded {
+                                    Ok(encoded) => encoded,
+                                    Err(e) => Healthchecker::report_stall_and_halt(
+                                        s.healthchecker.lock().await.as_mut(),
+                                        format!("unable to encode message in {}: {e}", s.name),
+                                    )
+                                    .await,
+                                };
+
                                 let rows = s.pending_rows.entry(time).or_default();
                                 rows.push(EncodedRow {
                                     key,
@@ -1273,7 +1328,8 @@ where
 /// This operator will only encode `fuel` number of updates per invocation. If necessary, it will
 /// stash updates and use an [`timely::scheduling::Activator`] to re-schedule future invocations.
 ///
-/// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
+/// Input [`Row`] updates must me compatible with the given implementor of [`Encode`]. Updates
+/// that hold values the encoder cannot represent are emitted as errors, which stall the sink.
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` will be discarded
 /// without encoding them.
@@ -1292,7 +1348,14 @@ fn encode_stream<G>(
     encoder: impl Encode + 'static,
     fuel: usize,
     name_prefix: String,
-) -> Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>
+) -> Stream<
+    G,
+    (
+        Result<(Option<Vec<u8>>, Option<Vec<u8>>), String>,
+        Timestamp,
+        Diff,
+    ),
+>
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -1316,7 +1379,11 @@ where
     >,
                                  output: &mut OutputHandle<
         _,
-        ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff),
+        (
+            Result<(Option<Vec<u8>>, Option<Vec<u8>>), String>,
+            Timestamp,
+            Diff,
+        ),
         _,
     >| {
         let mut fuel_remaining = fuel;
@@ -1358,9 +1425,17 @@ where
             records
                 .drain(..num_records_to_drain)
                 .for_each(|((key, value), time, diff)| {
-                    let key = key.map(|key| encoder.encode_key_unchecked(key));
-                    let value = value.map(|value| encoder.encode_value_unchecked(value));
-                    session.give(((key, value), time, diff));
+                    let encoded = key
+                        .map(|key| encoder.encode_key_unchecked(key))
+                        .transpose()
+                        .and_then(|key| {
+                            let value = value
+                                .map(|value| encoder.encode_value_unchecked(value))
+                                .transpose()?;
+                            Ok((key, value))
+                        })
+                        .map_err(|e| format!("{e:#}"));
+                    session.give((encoded, time, diff));
                 });
 
             fuel_remaining -= num_records_to_drain;
@@ -12,6 +12,7 @@
 mod healthcheck;
 mod kafka;
 mod metrics;
+mod s3;
 
 pub use healthcheck::{Healthchecker, SinkStatus};
 pub(crate) use metrics::KafkaBaseMetrics;
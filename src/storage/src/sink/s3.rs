@@ -0,0 +1,1261 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that exports the changes to a collection as files in S3.
+//!
+//! The sink's active worker divides time into contiguous ranges as the input
+//! frontier advances, and broadcasts a description of each range to all
+//! workers. Updates are distributed across workers, and once the input
+//! frontier passes the end of a range, each worker writes its updates at
+//! times in that range to data files named after the range, splitting them
+//! into several files if they are large. The active worker then lists the
+//! files of all workers in the sink's manifest along with the new `upper`.
+//! Readers that only consult the manifest therefore always see a consistent
+//! snapshot: summing the diffs of every file listed in the manifest yields the
+//! contents of the collection as of the manifest's `upper`.
+//!
+//! To keep the manifest small, the files it lists are periodically rolled into
+//! an immutable manifest segment that the manifest points to, which in turn
+//! points to the previous segment.
+//!
+//! On restart the sink resumes from the `upper` recorded in the manifest. If
+//! the sink is asked to start from a later time, it cannot recover the
+//! individual changes in between, so it writes a new snapshot instead, and
+//! the manifest stops listing the files written before it.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+use arrow2::array::{
+    Array, MutableArray, MutableBinaryArray, MutableBooleanArray, MutablePrimitiveArray,
+    MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::parquet::write::{
+    CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use aws_sdk_s3::types::{ByteStream, SdkError};
+use aws_sdk_s3::Client as S3Client;
+use bytes::BytesMut;
+use differential_dataflow::consolidation::consolidate_updates;
+use differential_dataflow::{Collection, Hashable};
+use serde::{Deserialize, Serialize};
+use timely::dataflow::channels::pact::{Exchange, Pipeline};
+use timely::dataflow::operators::{Broadcast, CapabilitySet};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{debug, info};
+
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    MetadataFilled, S3SinkConnection, S3SinkFormat, SinkAsOf, StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatus};
+use crate::storage_state::StorageState;
+
+/// The name of the manifest object, relative to the sink's prefix.
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// How long the sink lets its frontier advance without data before it
+/// records the new frontier in the manifest anyway.
+const IDLE_MANIFEST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long to retry S3 requests before giving up and restarting the sink.
+const S3_RETRY_DURATION: Duration = Duration::from_secs(60);
+
+/// The approximate size of the rows in a single data file, above which the
+/// updates for a range of times are split across several files.
+const PART_TARGET_BYTES: usize = 64 << 20;
+
+/// The number of data files the manifest lists before they are rolled into a
+/// manifest segment.
+const MANIFEST_MAX_PARTS: usize = 1000;
+
+impl<G> SinkRender<G> for S3SinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        false
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // All workers write data files, but a single worker maintains the
+        // manifest and reports the sink's frontier, and the other workers
+        // report an empty frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = s3(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            Rc::clone(&shared_frontier),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// The manifest of an S3 sink, stored as JSON next to its data files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Manifest {
+    /// The frontier up to which the manifest is complete. All updates at times
+    /// before `upper` are contained in `parts` and the parts of the segments
+    /// reachable through `previous`.
+    upper: Timestamp,
+    /// The data files written since the last segment was rolled, in the order
+    /// they were written.
+    parts: Vec<ManifestPart>,
+    /// The key of the segment that lists the data files written before `parts`,
+    /// if any.
+    #[serde(default)]
+    previous: Option<String>,
+}
+
+/// A segment of a [`Manifest`], rolled out of the manifest once it lists
+/// [`MANIFEST_MAX_PARTS`] data files. Segments are never modified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestSegment {
+    /// The data files listed by the segment, in the order they were written.
+    parts: Vec<ManifestPart>,
+    /// The key of the segment that lists the data files written before
+    /// `parts`, if any.
+    previous: Option<String>,
+}
+
+/// An entry in a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestPart {
+    /// The key of the data file.
+    key: String,
+    /// The inclusive lower bound of the timestamps of the updates in the file.
+    lower: Timestamp,
+    /// The exclusive upper bound of the timestamps of the updates in the file.
+    upper: Timestamp,
+    /// The number of updates in the file.
+    records: usize,
+    /// Whether the file is part of a full snapshot of the collection as of
+    /// `lower`. A manifest never lists files written before its most recent
+    /// snapshot.
+    snapshot: bool,
+}
+
+impl Manifest {
+    /// Records that `parts`, which together hold every update at times in
+    /// `[description.lower, description.upper)`, have been written.
+    fn append(&mut self, description: &PartDescription, parts: Vec<ManifestPart>) {
+        assert_eq!(
+            self.upper, description.lower,
+            "part descriptions are contiguous"
+        );
+        if description.snapshot {
+            // Readers must disregard everything written before a snapshot, so
+            // stop listing it.
+            self.parts.clear();
+            self.previous = None;
+        }
+        self.parts.extend(parts);
+        self.upper = description.upper;
+    }
+
+    /// Moves the parts listed by the manifest into a new segment that will be
+    /// stored at `segment_key`, and returns that segment.
+    fn roll(&mut self, segment_key: String) -> ManifestSegment {
+        ManifestSegment {
+            parts: std::mem::take(&mut self.parts),
+            previous: self.previous.replace(segment_key),
+        }
+    }
+}
+
+/// A range of times whose updates are to be written as data files, decided by
+/// the sink's active worker and broadcast to all workers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PartDescription {
+    /// The inclusive lower bound of the range.
+    lower: Timestamp,
+    /// The exclusive upper bound of the range.
+    upper: Timestamp,
+    /// Whether the data files for the range form a snapshot of the collection.
+    snapshot: bool,
+}
+
+/// Writes data files and the manifest of a single S3 sink.
+struct S3SinkWriter {
+    name: String,
+    client: S3Client,
+    connection: S3SinkConnection,
+}
+
+impl S3SinkWriter {
+    async fn new(
+        name: String,
+        id: GlobalId,
+        connection: S3SinkConnection,
+        connection_context: &ConnectionContext,
+    ) -> Self {
+        let config = connection
+            .aws
+            .load(
+                connection_context.aws_external_id_prefix.as_ref(),
+                Some(&id),
+                &*connection_context.secrets_reader,
+            )
+            .await;
+        S3SinkWriter {
+            name,
+            client: S3Client::new(&config),
+            connection,
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        if self.connection.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.connection.prefix, name)
+        }
+    }
+
+    /// Reads the sink's manifest, if one has been written.
+    async fn read_manifest(&self) -> Result<Option<Manifest>, anyhow::Error> {
+        let key = self.key(MANIFEST_NAME);
+        let result = Retry::default()
+            .max_duration(S3_RETRY_DURATION)
+            .retry_async(|_| async {
+                let object = match self
+                    .client
+                    .get_object()
+                    .bucket(&self.connection.bucket)
+                    .key(&key)
+                    .send()
+                    .await
+                {
+                    Ok(object) => object,
+                    Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => {
+                        return Ok(None)
+                    }
+                    Err(e) => return Err(anyhow!(e)),
+                };
+                let bytes = object.body.collect().await?.into_bytes();
+                Ok(Some(bytes))
+            })
+            .await
+            .with_context(|| format!("reading manifest {}/{}", self.connection.bucket, key))?;
+        match result {
+            None => Ok(None),
+            Some(bytes) => {
+                let manifest = serde_json::from_slice(&bytes).with_context(|| {
+                    format!("decoding manifest {}/{}", self.connection.bucket, key)
+                })?;
+                Ok(Some(manifest))
+            }
+        }
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        contents: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), anyhow::Error> {
+        Retry::default()
+            .max_duration(S3_RETRY_DURATION)
+            .retry_async(|_| async {
+                self.client
+                    .put_object()
+                    .bucket(&self.connection.bucket)
+                    .key(key)
+                    .body(ByteStream::from(contents.clone()))
+                    .content_type(content_type)
+                    .send()
+                    .await
+            })
+            .await
+            .with_context(|| format!("writing {}/{}", self.connection.bucket, key))?;
+        Ok(())
+    }
+
+    /// Writes `updates`, which must all be at times in the range of
+    /// `description`, as new data files of at most about [`PART_TARGET_BYTES`]
+    /// each and returns their manifest entries.
+    ///
+    /// `worker` distinguishes the files of different workers that write
+    /// updates for the same range.
+    async fn write_parts(
+        &self,
+        description: &PartDescription,
+        worker: usize,
+        updates: &[(Row, Timestamp, Diff)],
+    ) -> Result<Vec<ManifestPart>, anyhow::Error> {
+        let mut parts = vec![];
+        for (index, updates) in split_by_size(updates, PART_TARGET_BYTES).enumerate() {
+            parts.push(self.write_part(description, worker, index, updates).await?);
+        }
+        Ok(parts)
+    }
+
+    async fn write_part(
+        &self,
+        description: &PartDescription,
+        worker: usize,
+        index: usize,
+        updates: &[(Row, Timestamp, Diff)],
+    ) -> Result<ManifestPart, anyhow::Error> {
+        let (extension, content_type, contents) = match &self.connection.format {
+            S3SinkFormat::Parquet => (
+                "parquet",
+                "application/vnd.apache.parquet",
+                encode_parquet(&self.connection.value_desc, updates)?,
+            ),
+            S3SinkFormat::Csv { header, delimiter } => (
+                "csv",
+                "text/csv",
+                encode_csv(&self.connection.value_desc, updates, *header, *delimiter),
+            ),
+        };
+        let PartDescription {
+            lower,
+            upper,
+            snapshot,
+        } = *description;
+        // Zero-pad the timestamps so that listing the prefix returns the files
+        // in the order they were written.
+        let key = self.key(&format!(
+            "{:020}-{:020}-{}-{}.{}",
+            lower, upper, worker, index, extension
+        ));
+        info!(
+            "{}: writing {} updates in [{}, {}) to {}",
+            self.name,
+            updates.len(),
+            lower,
+            upper,
+            key
+        );
+        self.put_object(&key, contents, content_type).await?;
+        Ok(ManifestPart {
+            key,
+            lower,
+            upper,
+            records: updates.len(),
+            snapshot,
+        })
+    }
+
+    /// Writes `manifest`, first rolling its parts into a new segment if it
+    /// lists too many of them.
+    async fn write_manifest(&self, manifest: &mut Manifest) -> Result<(), anyhow::Error> {
+        if manifest.parts.len() >= MANIFEST_MAX_PARTS {
+            // The upper of the manifest only ever advances, so it makes for a
+            // unique segment name.
+            let segment_key = self.key(&format!("manifest-{:020}.json", manifest.upper));
+            let segment = manifest.roll(segment_key.clone());
+            info!(
+                "{}: rolling {} parts into manifest segment {}",
+                self.name,
+                segment.parts.len(),
+                segment_key
+            );
+            let contents = serde_json::to_vec(&segment).expect("manifest segment serializes");
+            self.put_object(&segment_key, contents, "application/json")
+                .await?;
+        }
+        let contents = serde_json::to_vec(manifest).expect("manifest serializes");
+        self.put_object(&self.key(MANIFEST_NAME), contents, "application/json")
+            .await
+    }
+}
+
+/// Splits `updates` into consecutive runs whose rows take up at most about
+/// `target_bytes` bytes. Every run contains at least one update.
+fn split_by_size(
+    updates: &[(Row, Timestamp, Diff)],
+    target_bytes: usize,
+) -> impl Iterator<Item = &[(Row, Timestamp, Diff)]> {
+    let mut rest = updates;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut bytes = 0;
+        let mut len = 0;
+        for (row, _, _) in rest {
+            bytes += row.byte_len();
+            if len > 0 && bytes > target_bytes {
+                break;
+            }
+            len += 1;
+        }
+        let (run, tail) = rest.split_at(len);
+        rest = tail;
+        Some(run)
+    })
+}
+
+fn s3<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: S3SinkConnection,
+    as_of: SinkAsOf,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("s3-{}", id);
+
+    // The active worker reads the manifest to decide where the sink resumes,
+    // and tells the operator that describes the data files to write.
+    let (resume_tx, resume_rx) = tokio::sync::oneshot::channel();
+
+    let (descriptions, mint_token) = mint_part_descriptions(&name, id, &collection, resume_rx);
+    let (parts, write_token) = write_parts(
+        &name,
+        id,
+        &collection,
+        &descriptions,
+        connection.clone(),
+        as_of.clone(),
+        connection_context,
+    );
+    let append_token = append_parts(
+        &name,
+        id,
+        &descriptions,
+        &parts,
+        connection,
+        as_of,
+        resume_tx,
+        write_frontier,
+        connection_context,
+        healthchecker_args,
+    );
+
+    Rc::new((mint_token, write_token, append_token))
+}
+
+/// Describes the ranges of times whose updates are to be written as data
+/// files, starting from where the sink resumes, which it learns via `resume`.
+///
+/// Only the active worker describes ranges, and it emits each description at
+/// the lower bound of its range. Downstream operators can therefore be sure
+/// that they have seen every description whose range ends at or before their
+/// input frontier.
+fn mint_part_descriptions<G>(
+    name: &str,
+    id: GlobalId,
+    collection: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    resume: tokio::sync::oneshot::Receiver<(Timestamp, bool)>,
+) -> (Stream<G, PartDescription>, Rc<dyn Any>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = collection.scope();
+    let is_active_worker = usize::cast_from(id.hashed()) % scope.peers() == scope.index();
+
+    let mut builder = AsyncOperatorBuilder::new(format!("{} mint_part_descriptions", name), scope);
+    let (mut output, descriptions) = builder.new_output();
+    let mut input = builder.new_input(&collection.inner, Pipeline);
+
+    let name = name.to_string();
+    let button = builder.build(move |mut capabilities| async move {
+        let cap = capabilities.pop().expect("missing capability");
+        if !is_active_worker {
+            return;
+        }
+        let mut cap_set = CapabilitySet::from_elem(cap);
+
+        let Ok((mut lower, mut snapshot)) = resume.await else {
+            // The operator that writes the manifest shut down first.
+            return;
+        };
+        cap_set.downgrade(&[lower]);
+
+        while let Some(event) = input.next().await {
+            let Event::Progress(frontier) = event else {
+                continue;
+            };
+            let upper = frontier.as_option().copied().unwrap_or(Timestamp::MAX);
+            if upper <= lower {
+                continue;
+            }
+            let description = PartDescription {
+                lower,
+                upper,
+                snapshot,
+            };
+            debug!("{}: minted part description {:?}", name, description);
+            output
+                .activate()
+                .session(&cap_set.delayed(&lower))
+                .give(description);
+            lower = upper;
+            snapshot = false;
+            cap_set.downgrade(frontier.iter());
+        }
+    });
+
+    (descriptions, Rc::new(button.press_on_drop()))
+}
+
+/// Writes this worker's share of the updates in `collection` as data files,
+/// one range of `descriptions` at a time, and emits their manifest entries at
+/// the lower bound of their range.
+///
+/// Updates are distributed across workers by row, so that every worker writes
+/// data files and buffers only a share of the not yet complete updates.
+fn write_parts<G>(
+    name: &str,
+    id: GlobalId,
+    collection: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    descriptions: &Stream<G, PartDescription>,
+    connection: S3SinkConnection,
+    as_of: SinkAsOf,
+    connection_context: &ConnectionContext,
+) -> (Stream<G, ManifestPart>, Rc<dyn Any>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = collection.scope();
+    let worker_index = scope.index();
+
+    let mut builder = AsyncOperatorBuilder::new(format!("{} write_parts", name), scope);
+    let (mut output, parts) = builder.new_output();
+    let mut descriptions_input = builder.new_input(&descriptions.broadcast(), Pipeline);
+    let mut updates_input = builder.new_input(
+        &collection.inner,
+        Exchange::new(
+            |((_key, value), _time, _diff): &((Option<Row>, Option<Row>), Timestamp, Diff)| {
+                value.hashed()
+            },
+        ),
+    );
+
+    let name = name.to_string();
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        let writer = S3SinkWriter::new(name, id, connection, &connection_context).await;
+
+        // The updates that have not yet been written, and the length of
+        // `pending` when it was last consolidated.
+        let mut pending: Vec<(Row, Timestamp, Diff)> = Vec::new();
+        let mut consolidated_len = 0;
+        // The descriptions of ranges that have not yet been written, in the
+        // order they were minted, with capabilities at their lower bounds.
+        let mut in_flight = VecDeque::new();
+        let mut updates_frontier = Antichain::from_elem(Timestamp::minimum());
+        let mut buffer = Vec::new();
+
+        loop {
+            tokio::select! {
+                Some(event) = descriptions_input.next() => {
+                    if let Event::Data(cap, data) = event {
+                        for description in data.iter() {
+                            let cap = cap.delayed(&description.lower);
+                            in_flight.push_back((description.clone(), cap));
+                        }
+                    }
+                }
+                Some(event) = updates_input.next() => {
+                    match event {
+                        Event::Data(_, data) => {
+                            data.swap(&mut buffer);
+                            for ((_key, value), time, diff) in buffer.drain(..) {
+                                let should_emit = if as_of.strict {
+                                    as_of.frontier.less_than(&time)
+                                } else {
+                                    as_of.frontier.less_equal(&time)
+                                };
+                                if !should_emit {
+                                    continue;
+                                }
+                                let row = value.expect("S3 sinks have no envelope");
+                                pending.push((row, time, diff));
+                            }
+                            // Consolidate whenever the buffer has doubled in
+                            // size, so that updates that cancel out do not pile
+                            // up while the frontier is held back.
+                            if pending.len() >= 2 * std::cmp::max(consolidated_len, 1024) {
+                                consolidate_updates(&mut pending);
+                                consolidated_len = pending.len();
+                            }
+                        }
+                        Event::Progress(frontier) => updates_frontier = frontier,
+                    }
+                }
+                else => return,
+            }
+
+            // Write out every range whose updates are all present.
+            while let Some((description, _)) = in_flight.front() {
+                if updates_frontier.less_than(&description.upper) {
+                    break;
+                }
+                let (description, cap) = in_flight.pop_front().expect("known to exist");
+                let (mut ready, not_ready) = pending
+                    .drain(..)
+                    .partition::<Vec<_>, _>(|(_, time, _)| *time < description.upper);
+                pending = not_ready;
+                consolidated_len = std::cmp::min(consolidated_len, pending.len());
+                // Updates before the range were written by a previous
+                // incarnation of the sink.
+                ready.retain(|(_, time, _)| *time >= description.lower);
+                consolidate_updates(&mut ready);
+                ready.sort_by_key(|(_, time, _)| *time);
+
+                let parts = writer.write_parts(&description, worker_index, &ready).await;
+                let mut parts = match parts {
+                    Ok(parts) => parts,
+                    Err(e) => Healthchecker::report_stall_and_halt(None, format!("{e:#}")).await,
+                };
+                output.activate().session(&cap).give_vec(&mut parts);
+            }
+        }
+    });
+
+    (parts, Rc::new(button.press_on_drop()))
+}
+
+/// Appends the data files written for each range of `descriptions` to the
+/// manifest, once the files of all workers have arrived, and reports the
+/// manifest's upper as the sink's write frontier.
+///
+/// Only the active worker maintains the manifest. It also decides where the
+/// sink resumes, which it sends to `resume`.
+#[allow(clippy::too_many_arguments)]
+fn append_parts<G>(
+    name: &str,
+    id: GlobalId,
+    descriptions: &Stream<G, PartDescription>,
+    parts: &Stream<G, ManifestPart>,
+    connection: S3SinkConnection,
+    as_of: SinkAsOf,
+    resume: tokio::sync::oneshot::Sender<(Timestamp, bool)>,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = descriptions.scope();
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut builder = AsyncOperatorBuilder::new(format!("{} append_parts", name), scope);
+    // Descriptions are only minted on the active worker, so they need not be
+    // exchanged.
+    let mut descriptions_input = builder.new_input(descriptions, Pipeline);
+    let mut parts_input = builder.new_input(parts, Exchange::new(move |_| hashed_id));
+
+    let name = name.to_string();
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let mut healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => {
+                let mut hc = Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker");
+                hc.update_status(SinkStatus::Starting).await;
+                Some(hc)
+            }
+            None => None,
+        };
+
+        let writer = S3SinkWriter::new(name, id, connection, &connection_context).await;
+
+        let existing = match writer.read_manifest().await {
+            Ok(existing) => existing,
+            Err(e) => {
+                Healthchecker::report_stall_and_halt(healthchecker.as_mut(), format!("{e:#}")).await
+            }
+        };
+
+        let as_of_ts = as_of
+            .frontier
+            .as_option()
+            .copied()
+            .unwrap_or_else(Timestamp::minimum);
+        // The manifest we will extend, and whether the first range we write
+        // must be a snapshot.
+        let (mut manifest, snapshot) = match existing {
+            // We can pick up exactly where the last incarnation left off,
+            // skipping the updates it already wrote.
+            Some(manifest) if as_of_ts < manifest.upper => (manifest, false),
+            // The changes between the manifest's upper and our as_of are
+            // unavailable, so readers must start over from a new snapshot.
+            Some(mut manifest) => {
+                manifest.upper = as_of_ts;
+                (manifest, !as_of.strict)
+            }
+            None => (
+                Manifest {
+                    upper: as_of_ts,
+                    parts: vec![],
+                    previous: None,
+                },
+                !as_of.strict,
+            ),
+        };
+        info!(
+            "{}: initial as_of: {:?}, resuming at {}",
+            writer.name, as_of.frontier, manifest.upper
+        );
+        *write_frontier.borrow_mut() = manifest_frontier(&manifest);
+        if resume.send((manifest.upper, snapshot)).is_err() {
+            return;
+        }
+
+        if let Some(healthchecker) = healthchecker.as_mut() {
+            healthchecker.update_status(SinkStatus::Running).await;
+        }
+
+        let mut descriptions = VecDeque::new();
+        let mut received: Vec<ManifestPart> = Vec::new();
+        let mut parts_frontier = Antichain::from_elem(Timestamp::minimum());
+        let mut buffer = Vec::new();
+        let mut last_manifest_write = Instant::now();
+
+        loop {
+            tokio::select! {
+                Some(event) = descriptions_input.next() => {
+                    if let Event::Data(_, data) = event {
+                        data.swap(&mut buffer);
+                        descriptions.extend(buffer.drain(..));
+                    }
+                }
+                Some(event) = parts_input.next() => {
+                    match event {
+                        Event::Data(_, data) => received.extend(data.iter().cloned()),
+                        Event::Progress(frontier) => parts_frontier = frontier,
+                    }
+                }
+                else => return,
+            }
+
+            // Every worker emits the data files for a range at its lower
+            // bound, so once the frontier passes the end of a range, all of
+            // its data files have arrived.
+            let mut changed = false;
+            while let Some(description) = descriptions.front() {
+                if parts_frontier.less_than(&description.upper) {
+                    break;
+                }
+                let description = descriptions.pop_front().expect("known to exist");
+                let (mut ready, not_ready) = received
+                    .drain(..)
+                    .partition::<Vec<_>, _>(|part| part.lower == description.lower);
+                received = not_ready;
+                ready.sort_by(|a, b| a.key.cmp(&b.key));
+                changed |= description.snapshot || !ready.is_empty();
+                manifest.append(&description, ready);
+            }
+
+            if *write_frontier.borrow() == manifest_frontier(&manifest)
+                || (!changed && last_manifest_write.elapsed() < IDLE_MANIFEST_INTERVAL)
+            {
+                continue;
+            }
+
+            if let Err(e) = writer.write_manifest(&mut manifest).await {
+                Healthchecker::report_stall_and_halt(healthchecker.as_mut(), format!("{e:#}")).await
+            }
+            last_manifest_write = Instant::now();
+
+            *write_frontier.borrow_mut() = manifest_frontier(&manifest);
+        }
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+/// Returns the write frontier that `manifest` reports for the sink.
+fn manifest_frontier(manifest: &Manifest) -> Antichain<Timestamp> {
+    if manifest.upper == Timestamp::MAX {
+        // The input is complete.
+        Antichain::new()
+    } else {
+        Antichain::from_elem(manifest.upper)
+    }
+}
+
+/// Encodes `updates` as a Parquet file with one column per column of `desc`,
+/// followed by `mz_timestamp` and `mz_diff` columns.
+///
+/// Columns whose type has no natural Parquet equivalent are written as their
+/// PostgreSQL text representation.
+fn encode_parquet(
+    desc: &RelationDesc,
+    updates: &[(Row, Timestamp, Diff)],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut fields = vec![];
+    let mut columns = vec![];
+    for (name, typ) in desc.iter() {
+        let column = ParquetColumn::new(&typ.scalar_type, updates.len());
+        fields.push(Field::new(name.as_str(), column.data_type(), typ.nullable));
+        columns.push(column);
+    }
+    // Like persist, we write timestamps as signed integers: the bytes are the
+    // same over the range of realistic timestamps, and more readers handle
+    // them.
+    fields.push(Field::new("mz_timestamp", DataType::Int64, false));
+    fields.push(Field::new("mz_diff", DataType::Int64, false));
+    let mut timestamps = MutablePrimitiveArray::<i64>::with_capacity(updates.len());
+    let mut diffs = MutablePrimitiveArray::<i64>::with_capacity(updates.len());
+
+    for (row, time, diff) in updates {
+        for (column, datum) in columns.iter_mut().zip(row.iter()) {
+            column.push(datum);
+        }
+        timestamps.push(Some(
+            i64::try_from(*time).context("timestamp out of range")?,
+        ));
+        diffs.push(Some(*diff));
+    }
+
+    let mut arrays: Vec<Box<dyn Array>> = columns.into_iter().map(|c| c.finish()).collect();
+    arrays.push(timestamps.as_box());
+    arrays.push(diffs.as_box());
+
+    let schema = Schema::from(fields);
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Snappy,
+        version: Version::V2,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|_| vec![Encoding::Plain])
+        .collect();
+    let row_groups = RowGroupIterator::try_new(
+        vec![Ok(Chunk::new(arrays))].into_iter(),
+        &schema,
+        options,
+        encodings,
+    )?;
+    let mut buf = vec![];
+    let mut writer = FileWriter::try_new(&mut buf, schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(buf)
+}
+
+/// A Parquet column under construction.
+enum ParquetColumn {
+    Bool(MutableBooleanArray),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Date(MutablePrimitiveArray<i32>),
+    Timestamp(MutablePrimitiveArray<i64>),
+    TimestampTz(MutablePrimitiveArray<i64>),
+    Bytes(MutableBinaryArray<i32>),
+    String(MutableUtf8Array<i32>),
+    /// Any other type, written in its text representation.
+    Text {
+        typ: ScalarType,
+        array: MutableUtf8Array<i32>,
+        buf: BytesMut,
+    },
+}
+
+impl ParquetColumn {
+    fn new(typ: &ScalarType, capacity: usize) -> Self {
+        match typ {
+            ScalarType::Bool => Self::Bool(MutableBooleanArray::with_capacity(capacity)),
+            ScalarType::Int16 => Self::Int16(MutablePrimitiveArray::with_capacity(capacity)),
+            ScalarType::Int32 => Self::Int32(MutablePrimitiveArray::with_capacity(capacity)),
+            ScalarType::Int64 => Self::Int64(MutablePrimitiveArray::with_capacity(capacity)),
+            ScalarType::Float32 => Self::Float32(MutablePrimitiveArray::with_capacity(capacity)),
+            ScalarType::Float64 => Self::Float64(MutablePrimitiveArray::with_capacity(capacity)),
+            ScalarType::Date => {
+                Self::Date(MutablePrimitiveArray::with_capacity(capacity).to(DataType::Date32))
+            }
+            ScalarType::Timestamp => Self::Timestamp(
+                MutablePrimitiveArray::with_capacity(capacity)
+                    .to(DataType::Timestamp(TimeUnit::Microsecond, None)),
+            ),
+            ScalarType::TimestampTz => {
+                Self::TimestampTz(MutablePrimitiveArray::with_capacity(capacity).to(
+                    DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+                ))
+            }
+            ScalarType::Bytes => Self::Bytes(MutableBinaryArray::with_capacity(capacity)),
+            ScalarType::String => Self::String(MutableUtf8Array::with_capacity(capacity)),
+            typ => Self::Text {
+                typ: typ.clone(),
+                array: MutableUtf8Array::with_capacity(capacity),
+                buf: BytesMut::new(),
+            },
+        }
+    }
+
+    fn data_type(&self) -> DataType {
+        match self {
+            Self::Bool(array) => array.data_type().clone(),
+            Self::Int16(array) => array.data_type().clone(),
+            Self::Int32(array) | Self::Date(array) => array.data_type().clone(),
+            Self::Int64(array) | Self::Timestamp(array) | Self::TimestampTz(array) => {
+                array.data_type().clone()
+            }
+            Self::Float32(array) => array.data_type().clone(),
+            Self::Float64(array) => array.data_type().clone(),
+            Self::Bytes(array) => array.data_type().clone(),
+            Self::String(array) | Self::Text { array, .. } => array.data_type().clone(),
+        }
+    }
+
+    fn push(&mut self, datum: Datum) {
+        if datum.is_null() {
+            match self {
+                Self::Bool(array) => array.push_null(),
+                Self::Int16(array) => array.push_null(),
+                Self::Int32(array) | Self::Date(array) => array.push_null(),
+                Self::Int64(array) | Self::Timestamp(array) | Self::TimestampTz(array) => {
+                    array.push_null()
+                }
+                Self::Float32(array) => array.push_null(),
+                Self::Float64(array) => array.push_null(),
+                Self::Bytes(array) => array.push_null(),
+                Self::String(array) | Self::Text { array, .. } => array.push_null(),
+            }
+            return;
+        }
+        match self {
+            Self::Bool(array) => array.push(Some(datum.unwrap_bool())),
+            Self::Int16(array) => array.push(Some(datum.unwrap_int16())),
+            Self::Int32(array) => array.push(Some(datum.unwrap_int32())),
+            Self::Int64(array) => array.push(Some(datum.unwrap_int64())),
+            Self::Float32(array) => array.push(Some(datum.unwrap_float32())),
+            Self::Float64(array) => array.push(Some(datum.unwrap_float64())),
+            Self::Date(array) => array.push(Some(datum.unwrap_date().unix_epoch_days())),
+            Self::Timestamp(array) => {
+                let ts = datum.unwrap_timestamp();
+                array.push(Some(
+                    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros()),
+                ))
+            }
+            Self::TimestampTz(array) => {
+                let ts = datum.unwrap_timestamptz();
+                array.push(Some(
+                    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros()),
+                ))
+            }
+            Self::Bytes(array) => array.push(Some(datum.unwrap_bytes())),
+            Self::String(array) => array.push(Some(datum.unwrap_str())),
+            Self::Text { typ, array, buf } => {
+                buf.clear();
+                if let Some(value) = mz_pgrepr::Value::from_datum(datum, typ) {
+                    value.encode_text(buf);
+                }
+                array.push(Some(
+                    std::str::from_utf8(&buf[..]).expect("text encoding produces valid UTF-8"),
+                ));
+            }
+        }
+    }
+
+    fn finish(self) -> Box<dyn Array> {
+        match self {
+            Self::Bool(mut array) => array.as_box(),
+            Self::Int16(mut array) => array.as_box(),
+            Self::Int32(mut array) | Self::Date(mut array) => array.as_box(),
+            Self::Int64(mut array) | Self::Timestamp(mut array) | Self::TimestampTz(mut array) => {
+                array.as_box()
+            }
+            Self::Float32(mut array) => array.as_box(),
+            Self::Float64(mut array) => array.as_box(),
+            Self::Bytes(mut array) => array.as_box(),
+            Self::String(mut array) | Self::Text { mut array, .. } => array.as_box(),
+        }
+    }
+}
+
+/// Encodes `updates` as CSV with one column per column of `desc`, followed by
+/// `mz_timestamp` and `mz_diff` columns.
+///
+/// Values are written in their PostgreSQL text representation and quoted as
+/// PostgreSQL's `COPY ... (FORMAT CSV)` would: `NULL` is an empty unquoted
+/// field, and the empty string is `""`.
+fn encode_csv(
+    desc: &RelationDesc,
+    updates: &[(Row, Timestamp, Diff)],
+    header: bool,
+    delimiter: u8,
+) -> Vec<u8> {
+    let mut out = vec![];
+    if header {
+        let names = desc
+            .iter_names()
+            .map(|name| name.as_str())
+            .chain(["mz_timestamp", "mz_diff"]);
+        for (i, name) in names.enumerate() {
+            if i > 0 {
+                out.push(delimiter);
+            }
+            write_csv_field(&mut out, Some(name.as_bytes()), delimiter);
+        }
+        out.push(b'\n');
+    }
+
+    let mut buf = BytesMut::new();
+    for (row, time, diff) in updates {
+        for (datum, typ) in row.iter().zip(desc.typ().column_types.iter()) {
+            buf.clear();
+            match mz_pgrepr::Value::from_datum(datum, &typ.scalar_type) {
+                None => write_csv_field(&mut out, None, delimiter),
+                Some(value) => {
+                    value.encode_text(&mut buf);
+                    write_csv_field(&mut out, Some(&buf[..]), delimiter);
+                }
+            }
+            out.push(delimiter);
+        }
+        out.extend(time.to_string().as_bytes());
+        out.push(delimiter);
+        out.extend(diff.to_string().as_bytes());
+        out.push(b'\n');
+    }
+    out
+}
+
+fn write_csv_field(out: &mut Vec<u8>, field: Option<&[u8]>, delimiter: u8) {
+    match field {
+        None => (),
+        Some(field)
+            if field.is_empty()
+                || field
+                    .iter()
+                    .any(|b| *b == delimiter || matches!(b, b'"' | b'\n' | b'\r')) =>
+        {
+            out.push(b'"');
+            for b in field {
+                if *b == b'"' {
+                    out.push(b'"');
+                }
+                out.push(*b);
+            }
+            out.push(b'"');
+        }
+        Some(field) => out.extend(field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow2::array::{PrimitiveArray, Utf8Array};
+    use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+
+    use super::*;
+
+    fn desc() -> RelationDesc {
+        RelationDesc::empty()
+            .with_column("a", ScalarType::Int32.nullable(true))
+            .with_column("b", ScalarType::String.nullable(false))
+            .with_column("c", ScalarType::Jsonb.nullable(true))
+    }
+
+    fn updates() -> Vec<(Row, Timestamp, Diff)> {
+        vec![
+            (
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("x,y"), Datum::Null]),
+                Timestamp::new(5),
+                1,
+            ),
+            (
+                Row::pack_slice(&[Datum::Null, Datum::String(""), Datum::True]),
+                Timestamp::new(6),
+                -2,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let csv = encode_csv(&desc(), &updates(), true, b',');
+        assert_eq!(
+            std::str::from_utf8(&csv).unwrap(),
+            "a,b,c,mz_timestamp,mz_diff\n1,\"x,y\",,5,1\n,\"\",true,6,-2\n"
+        );
+
+        let csv = encode_csv(&desc(), &updates(), false, b'|');
+        assert_eq!(
+            std::str::from_utf8(&csv).unwrap(),
+            "1|x,y||5|1\n|\"\"|true|6|-2\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_parquet() {
+        let parquet = encode_parquet(&desc(), &updates()).unwrap();
+
+        let mut reader = Cursor::new(parquet);
+        let metadata = read_metadata(&mut reader).unwrap();
+        let schema = infer_schema(&metadata).unwrap();
+        let names: Vec<_> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c", "mz_timestamp", "mz_diff"]);
+        assert!(!schema.fields[1].is_nullable);
+
+        let chunks = FileReader::new(reader, metadata.row_groups, schema, None, None, None)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        let arrays = chunks[0].arrays();
+        let a = arrays[0]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i32>>()
+            .unwrap();
+        assert_eq!(
+            a.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(1), None]
+        );
+        let c = arrays[2].as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(c.iter().collect::<Vec<_>>(), [None, Some("true")]);
+        let diffs = arrays[4]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .unwrap();
+        assert_eq!(diffs.values().as_slice(), [1, -2]);
+    }
+
+    #[test]
+    fn test_split_by_size() {
+        let updates: Vec<_> = (0..5)
+            .map(|i| (Row::pack_slice(&[Datum::Int64(i)]), Timestamp::new(1), 1))
+            .collect();
+        let row_bytes = updates[0].0.byte_len();
+
+        let runs: Vec<_> = split_by_size(&updates, 2 * row_bytes)
+            .map(|run| run.len())
+            .collect();
+        assert_eq!(runs, [2, 2, 1]);
+
+        // Every run holds at least one update, even if it is too large.
+        let runs: Vec<_> = split_by_size(&updates, 0).map(|run| run.len()).collect();
+        assert_eq!(runs, [1, 1, 1, 1, 1]);
+
+        assert_eq!(split_by_size(&[], 0).count(), 0);
+    }
+
+    #[test]
+    fn test_manifest() {
+        fn part(key: &str, lower: u64, upper: u64, snapshot: bool) -> ManifestPart {
+            ManifestPart {
+                key: key.into(),
+                lower: Timestamp::new(lower),
+                upper: Timestamp::new(upper),
+                records: 1,
+                snapshot,
+            }
+        }
+        fn description(lower: u64, upper: u64, snapshot: bool) -> PartDescription {
+            PartDescription {
+                lower: Timestamp::new(lower),
+                upper: Timestamp::new(upper),
+                snapshot,
+            }
+        }
+
+        // Manifests written before segments existed have no `previous`.
+        let mut manifest: Manifest = serde_json::from_str(
+            r#"{"upper":5,"parts":[{"key":"a","lower":0,"upper":5,"records":1,"snapshot":true}]}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.previous, None);
+
+        manifest.append(
+            &description(5, 7, false),
+            vec![part("b-0", 5, 7, false), part("b-1", 5, 7, false)],
+        );
+        manifest.append(&description(7, 8, false), vec![]);
+        assert_eq!(manifest.upper, Timestamp::new(8));
+        assert_eq!(manifest.parts.len(), 3);
+
+        let segment = manifest.roll("segment".into());
+        assert_eq!(segment.parts.len(), 3);
+        assert_eq!(segment.previous, None);
+        assert!(manifest.parts.is_empty());
+        assert_eq!(manifest.previous.as_deref(), Some("segment"));
+
+        manifest.append(&description(8, 9, false), vec![part("c", 8, 9, false)]);
+        assert_eq!(manifest.previous.as_deref(), Some("segment"));
+
+        // A snapshot supersedes everything listed before it.
+        manifest.append(&description(9, 15, true), vec![part("d", 9, 15, true)]);
+        assert_eq!(manifest.upper, Timestamp::new(15));
+        assert_eq!(manifest.parts, [part("d", 9, 15, true)]);
+        assert_eq!(manifest.previous, None);
+    }
+}
@@ -89,6 +89,7 @@ where
                     value,
                     upstream_time_millis,
                     headers,
+                    origin,
                 }),
                 ts,
                 diff,
@@ -99,6 +100,7 @@ where
                     value,
                     upstream_time_millis,
                     headers,
+                    origin,
                 }),
                 ts,
                 diff,
@@ -110,6 +112,7 @@ where
                     value,
                     upstream_time_millis,
                     headers,
+                    origin,
                 }),
                 ts,
                 diff,
@@ -120,6 +123,7 @@ where
                     value,
                     upstream_time_millis,
                     headers,
+                    origin,
                 }),
                 ts,
                 diff,
@@ -164,6 +164,7 @@ impl SourceReader for LoadGeneratorSourceReader {
             key: (),
             value,
             headers: None,
+            origin: None,
         };
         let ts = (PartitionId::None, self.offset);
         let message = match typ {
@@ -389,6 +389,7 @@ mod tests {
 
     use mz_ore::metrics::MetricsRegistry;
     use mz_persist_client::{PersistConfig, PersistLocation, ShardId};
+    use mz_repr::RelationDesc;
 
     // Test suite
     #[tokio::test(start_paused = true)]
@@ -682,6 +683,7 @@ mod tests {
             remap_shard: ShardId::new(),
             data_shard: ShardId::new(),
             status_shard: Some(status_shard_id),
+            relation_desc: RelationDesc::empty(),
         };
 
         Healthchecker::new(
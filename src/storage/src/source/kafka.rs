@@ -22,6 +22,7 @@ use rdkafka::statistics::Statistics;
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::types::RDKafkaRespErr;
 use rdkafka::{ClientContext, Message, TopicPartitionList};
+use regex::Regex;
 use timely::scheduling::activate::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
 use tracing::{error, info, warn};
@@ -37,7 +38,7 @@ use mz_storage_client::types::sources::{KafkaSourceConnection, MzOffset};
 use crate::source::commit::LogCommitter;
 
 use crate::source::source_reader_pipeline::HealthStatus;
-use crate::source::types::{OffsetCommitter, SourceConnectionBuilder};
+use crate::source::types::{MessageOrigin, OffsetCommitter, SourceConnectionBuilder};
 use crate::source::{
     NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
 };
@@ -48,8 +49,12 @@ mod metrics;
 
 /// Contains all information necessary to ingest data from Kafka
 pub struct KafkaSourceReader {
-    /// Name of the topic on which this source is backed on
+    /// Name of the topic on which this source is backed on, or the topic
+    /// pattern if the source reads from several topics
     topic_name: String,
+    /// If present, the source reads from every topic that matches this pattern
+    /// rather than from `topic_name`
+    topic_pattern: Option<Regex>,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
     /// Source global ID
@@ -65,13 +70,13 @@ pub struct KafkaSourceReader {
     /// The most recently read offset for each partition known to this source
     /// reader. An offset of -1 indicates that no prior message has been read
     /// for the given partition.
-    last_offsets: HashMap<i32, i64>,
+    last_offsets: HashMap<PartitionId, i64>,
     /// The offset to start reading from for each partition.
-    start_offsets: HashMap<i32, i64>,
+    start_offsets: HashMap<PartitionId, i64>,
     /// Channel to receive Kafka statistics JSON blobs from the stats callback.
     stats_rx: crossbeam_channel::Receiver<Jsonb>,
     /// The last partition we received
-    partition_info: Arc<Mutex<Option<Vec<i32>>>>,
+    partition_info: Arc<Mutex<Option<Vec<PartitionId>>>>,
     /// A handle to the spawned metadata thread
     // Drop order is important here, we want the thread to be unparked after the `partition_info`
     // Arc has been dropped, so that the unpacked thread notices it and exits immediately
@@ -80,6 +85,8 @@ pub struct KafkaSourceReader {
     partition_metrics: KafkaPartitionMetrics,
     /// Whether or not to unpack and allocate headers and pass them through in the `SourceMessage`
     include_headers: bool,
+    /// Whether or not to pass the topic through in the `SourceMessage`
+    include_topic: bool,
     /// The latest status detected by the metadata refresh thread.
     health_status: Arc<Mutex<Option<HealthStatus>>>,
 }
@@ -111,10 +118,16 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
             connection,
             connection_id,
             topic,
+            topic_pattern,
             group_id_prefix,
             environment_id,
             ..
         } = self;
+        let topic_pattern = if topic_pattern {
+            Some(KafkaSourceConnection::compile_topic_pattern(&topic)?)
+        } else {
+            None
+        };
         let (stats_tx, stats_rx) = crossbeam_channel::unbounded();
         let health_status = Arc::new(Mutex::new(None));
         let consumer: BaseConsumer<_> =
@@ -175,18 +188,17 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
         let mut start_offsets: HashMap<_, i64> = self
             .start_offsets
             .into_iter()
+            .map(|(pid, offset)| (PartitionId::Kafka(pid), offset))
             .filter(|(pid, _offset)| {
-                let pid = PartitionId::Kafka(*pid);
-                crate::source::responsible_for(&source_id, worker_id, worker_count, &pid)
+                crate::source::responsible_for(&source_id, worker_id, worker_count, pid)
             })
-            .map(|(k, v)| (k, v))
             .collect();
 
         for (pid, restored_offset) in restored_offsets {
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
+            match (&pid, &topic_pattern) {
+                (PartitionId::Kafka(_), None) | (PartitionId::KafkaTopic(_, _), Some(_)) => {}
                 _ => panic!("unexpected partition id type"),
-            };
+            }
             if let Some(restored_offset) = restored_offset {
                 let restored_offset = i64::try_from(restored_offset.offset)
                     .expect("restored kafka offsets must fit into i64");
@@ -204,6 +216,7 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
         let metadata_thread_handle = {
             let partition_info = Arc::downgrade(&partition_info);
             let topic = topic.clone();
+            let topic_pattern = topic_pattern.clone();
             let consumer = Arc::clone(&consumer);
             let metadata_refresh_interval = connection
                 .options
@@ -232,8 +245,20 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                         "starting kafka metadata refresh thread"
                     );
                     while let Some(partition_info) = partition_info.upgrade() {
-                        let result =
-                            get_kafka_partitions(&consumer, &topic, Duration::from_secs(30));
+                        let timeout = Duration::from_secs(30);
+                        let result = match &topic_pattern {
+                            None => get_kafka_partitions(&consumer, &topic, timeout).map(|pids| {
+                                pids.into_iter().map(PartitionId::Kafka).collect::<Vec<_>>()
+                            }),
+                            Some(pattern) => get_kafka_topic_partitions(
+                                &consumer, pattern, timeout,
+                            )
+                            .map(|pids| {
+                                pids.into_iter()
+                                    .map(|(topic, pid)| PartitionId::KafkaTopic(topic, pid))
+                                    .collect()
+                            }),
+                        };
                         match result {
                             Ok(info) => {
                                 *partition_info.lock().unwrap() = Some(info);
@@ -252,10 +277,17 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                 .unwrap()
                 .unpark_on_drop()
         };
-        let partition_ids = start_offsets.keys().copied().collect();
+        let partition_ids = start_offsets
+            .keys()
+            .map(|pid| {
+                let (topic, pid) = kafka_topic_partition(&topic, pid);
+                (topic.to_string(), pid)
+            })
+            .collect();
         Ok((
             KafkaSourceReader {
                 topic_name: topic.clone(),
+                topic_pattern,
                 source_name,
                 id: source_id,
                 partition_consumers: VecDeque::new(),
@@ -267,13 +299,9 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                 stats_rx,
                 partition_info,
                 include_headers: self.include_headers.is_some(),
+                include_topic: self.include_topic.is_some(),
                 _metadata_thread_handle: metadata_thread_handle,
-                partition_metrics: KafkaPartitionMetrics::new(
-                    metrics,
-                    partition_ids,
-                    topic.clone(),
-                    source_id,
-                ),
+                partition_metrics: KafkaPartitionMetrics::new(metrics, partition_ids, source_id),
                 health_status,
             },
             KafkaOffsetCommiter {
@@ -309,7 +337,6 @@ impl SourceReader for KafkaSourceReader {
             // new partitions.
             let mut unconsumed_partitions = Vec::new();
             for pid in partitions {
-                let pid = PartitionId::Kafka(pid);
                 if crate::source::responsible_for(&self.id, self.worker_id, self.worker_count, &pid)
                 {
                     self.ensure_partition(pid);
@@ -344,7 +371,12 @@ impl SourceReader for KafkaSourceReader {
                     ))
                 }
                 Ok(message) => {
-                    let (message, ts) = construct_source_message(&message, self.include_headers);
+                    let (message, ts) = construct_source_message(
+                        &message,
+                        self.include_headers,
+                        self.include_topic,
+                        self.topic_pattern.is_some(),
+                    );
                     next_message = self.handle_message(Ok(message), ts);
                 }
             }
@@ -412,10 +444,7 @@ impl OffsetCommitter for KafkaOffsetCommiter {
             // Note that we expect the above layers to pre-filter
             // by partition for us. This is part of the
             // `OffsetCommitter` contract.
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
+            let (topic, pid) = kafka_topic_partition(&self.topic_name, &pid);
 
             // This matches the behavior of auto-commit, where we commit a
             // pseudo-_frontier_. Additionally, overflow will be caught
@@ -427,7 +456,7 @@ impl OffsetCommitter for KafkaOffsetCommiter {
                     .try_into()
                     .expect("offset to be vald i64"),
             );
-            tpl.add_partition_offset(&self.topic_name, pid, offset_to_commit)
+            tpl.add_partition_offset(topic, pid, offset_to_commit)
                 .expect("offset known to be valid");
         }
 
@@ -446,16 +475,12 @@ impl OffsetCommitter for KafkaOffsetCommiter {
 impl KafkaSourceReader {
     /// Ensures that a partition queue for `pid` exists.
     fn ensure_partition(&mut self, pid: PartitionId) {
-        let pid = match pid {
-            PartitionId::Kafka(p) => p,
-            _ => unreachable!(),
-        };
         if self.last_offsets.contains_key(&pid) {
             return;
         }
 
         let start_offset = self.start_offsets.get(&pid).copied().unwrap_or(0);
-        self.create_partition_queue(pid, Offset::Offset(start_offset));
+        self.create_partition_queue(pid.clone(), Offset::Offset(start_offset));
 
         let prev = self.last_offsets.insert(pid, start_offset - 1);
 
@@ -469,25 +494,26 @@ impl KafkaSourceReader {
         self.partition_consumers.len().try_into().unwrap()
     }
 
-    /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: i32, initial_offset: Offset) {
+    /// Creates a new partition queue for `pid`.
+    fn create_partition_queue(&mut self, pid: PartitionId, initial_offset: Offset) {
+        let (topic, partition_id) = kafka_topic_partition(&self.topic_name, &pid);
         info!(
             "Activating Kafka queue for {} [{}] (source {}) on worker {}",
-            self.topic_name, partition_id, self.id, self.worker_id
+            topic, partition_id, self.id, self.worker_id
         );
 
         // Collect old partition assignments
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, initial_offset)
+            .add_partition_offset(topic, partition_id, initial_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -497,9 +523,10 @@ impl KafkaSourceReader {
         // after every call to `self.consumer.assign`.
         let context = Arc::clone(self.consumer.context());
         for pc in &mut self.partition_consumers {
+            let (topic, partition_id) = kafka_topic_partition(&self.topic_name, &pc.pid);
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(topic, partition_id)
                 .expect("partition known to be valid");
             pc.partition_queue.set_nonempty_callback({
                 let context = Arc::clone(&context);
@@ -509,20 +536,18 @@ impl KafkaSourceReader {
 
         let mut partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(topic, partition_id)
             .expect("partition known to be valid");
         partition_queue.set_nonempty_callback(move || context.inner().activate());
         self.partition_consumers.push_front(PartitionConsumer::new(
-            partition_id,
+            pid,
             partition_queue,
             self.include_headers,
+            self.include_topic,
+            self.topic_pattern.is_some(),
         ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().count(),
             self.partition_consumers.len()
         );
     }
@@ -530,9 +555,10 @@ impl KafkaSourceReader {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: i32, next_offset: i64) {
+    fn fast_forward_consumer(&self, pid: &PartitionId, next_offset: i64) {
+        let (topic, pid) = kafka_topic_partition(&self.topic_name, pid);
         let res = self.consumer.seek(
-            &self.topic_name,
+            topic,
             pid,
             Offset::Offset(next_offset),
             Duration::from_secs(1),
@@ -540,12 +566,10 @@ impl KafkaSourceReader {
         match res {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
-                let position = res
-                    .get(&(self.topic_name.clone(), pid))
-                    .and_then(|p| match p {
-                        Offset::Offset(o) => Some(o),
-                        _ => None,
-                    });
+                let position = res.get(&(topic.to_string(), pid)).and_then(|p| match p {
+                    Offset::Offset(o) => Some(o),
+                    _ => None,
+                });
                 if let Some(position) = position {
                     if *position != next_offset {
                         warn!("Did not fast-forward consumer on partition PID: {} to the correct Kafka offset. Currently at offset: {} Expected offset: {}",
@@ -569,18 +593,34 @@ impl KafkaSourceReader {
     fn update_stats(&mut self) {
         while let Ok(stats) = self.stats_rx.try_recv() {
             match serde_json::from_str::<Statistics>(&stats.to_string()) {
-                Ok(statistics) => {
-                    let topic = statistics.topics.get(&self.topic_name);
-                    match topic {
+                Ok(statistics) => match &self.topic_pattern {
+                    None => match statistics.topics.get(&self.topic_name) {
                         Some(topic) => {
                             for (id, partition) in &topic.partitions {
-                                self.partition_metrics
-                                    .set_offset_max(*id, partition.hi_offset);
+                                self.partition_metrics.set_offset_max(
+                                    &self.topic_name,
+                                    *id,
+                                    partition.hi_offset,
+                                );
                             }
                         }
                         None => error!("No stats found for topic: {}", &self.topic_name),
+                    },
+                    Some(pattern) => {
+                        for (name, topic) in &statistics.topics {
+                            if !pattern.is_match(name) {
+                                continue;
+                            }
+                            for (id, partition) in &topic.partitions {
+                                self.partition_metrics.set_offset_max(
+                                    name,
+                                    *id,
+                                    partition.hi_offset,
+                                );
+                            }
+                        }
                     }
-                }
+                },
                 Err(e) => {
                     error!("failed decoding librdkafka statistics JSON: {}", e);
                 }
@@ -610,7 +650,7 @@ impl KafkaSourceReader {
                 let pid = partition_queue.pid();
                 let last_offset = self
                     .last_offsets
-                    .get(&pid)
+                    .get(pid)
                     .expect("partition known to be installed");
                 format!(
                     "kafka error consuming from source: {} topic: {}: partition: {} last processed offset: {} : {}",
@@ -634,10 +674,7 @@ impl KafkaSourceReader {
         message: Result<SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>>, SourceReaderError>,
         time: (PartitionId, MzOffset),
     ) -> NextMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
-        let partition = match time.0 {
-            PartitionId::Kafka(pid) => pid,
-            _ => unreachable!(),
-        };
+        let partition = &time.0;
 
         // Offsets are guaranteed to be 1) monotonically increasing *unless* there is
         // a network issue or a new partition added, at which point the consumer may
@@ -650,11 +687,11 @@ impl KafkaSourceReader {
 
         // Given the explicit consumer to partition assignment, we should never receive a message
         // for a partition for which we have no metadata
-        assert!(self.last_offsets.contains_key(&partition));
+        assert!(self.last_offsets.contains_key(partition));
 
         let last_offset_ref = self
             .last_offsets
-            .get_mut(&partition)
+            .get_mut(partition)
             .expect("partition known to be installed");
 
         let last_offset = *last_offset_ref;
@@ -686,6 +723,8 @@ impl KafkaSourceReader {
 fn construct_source_message(
     msg: &BorrowedMessage<'_>,
     include_headers: bool,
+    include_topic: bool,
+    multi_topic: bool,
 ) -> (
     SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>>,
     (PartitionId, MzOffset),
@@ -699,9 +738,23 @@ fn construct_source_message(
         ),
         _ => None,
     };
-    let pid = PartitionId::Kafka(msg.partition());
+    let origin = if include_topic {
+        Some(MessageOrigin::Kafka {
+            topic: msg.topic().to_string(),
+        })
+    } else {
+        None
+    };
+    let pid = if multi_topic {
+        PartitionId::KafkaTopic(msg.topic().to_string(), msg.partition())
+    } else {
+        PartitionId::Kafka(msg.partition())
+    };
     let Ok(offset) = u64::try_from(msg.offset()) else {
-        panic!("got negative offset ({}) from otherwise non-error'd kafka message", msg.offset());
+        panic!(
+            "got negative offset ({}) from otherwise non-error'd kafka message",
+            msg.offset()
+        );
     };
     let msg = SourceMessage {
         output: 0,
@@ -709,6 +762,7 @@ fn construct_source_message(
         key: msg.key().map(|k| k.to_vec()),
         value: msg.payload().map(|p| p.to_vec()),
         headers,
+        origin,
     };
     (msg, (pid, offset.into()))
 }
@@ -716,24 +770,33 @@ fn construct_source_message(
 /// Wrapper around a partition containing the underlying consumer
 struct PartitionConsumer {
     /// the partition id with which this consumer is associated
-    pid: i32,
+    pid: PartitionId,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<BrokerRewritingClientContext<GlueConsumerContext>>,
     /// Whether or not to unpack and allocate headers and pass them through in the `SourceMessage`
     include_headers: bool,
+    /// Whether or not to pass the topic through in the `SourceMessage`
+    include_topic: bool,
+    /// Whether the source reads from several topics, in which case partition
+    /// ids include the topic
+    multi_topic: bool,
 }
 
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
     fn new(
-        pid: i32,
+        pid: PartitionId,
         partition_queue: PartitionQueue<BrokerRewritingClientContext<GlueConsumerContext>>,
         include_headers: bool,
+        include_topic: bool,
+        multi_topic: bool,
     ) -> Self {
         PartitionConsumer {
             pid,
             partition_queue,
             include_headers,
+            include_topic,
+            multi_topic,
         }
     }
 
@@ -754,8 +817,13 @@ impl PartitionConsumer {
     > {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let (msg, ts) = construct_source_message(&msg, self.include_headers);
-                assert_eq!(ts.0, PartitionId::Kafka(self.pid));
+                let (msg, ts) = construct_source_message(
+                    &msg,
+                    self.include_headers,
+                    self.include_topic,
+                    self.multi_topic,
+                );
+                assert_eq!(ts.0, self.pid);
                 Ok(Some((msg, ts)))
             }
             Some(Err(err)) => Err(err),
@@ -764,8 +832,8 @@ impl PartitionConsumer {
     }
 
     /// Return the partition id for this PartitionConsumer
-    fn pid(&self) -> i32 {
-        self.pid
+    fn pid(&self) -> &PartitionId {
+        &self.pid
     }
 }
 
@@ -809,6 +877,17 @@ impl GlueConsumerContext {
 
 impl ConsumerContext for GlueConsumerContext {}
 
+/// Returns the topic and partition of a Kafka partition id. Partition ids of
+/// sources that read from a single topic do not include the topic, which is
+/// `topic_name` instead.
+fn kafka_topic_partition<'a>(topic_name: &'a str, pid: &'a PartitionId) -> (&'a str, i32) {
+    match pid {
+        PartitionId::Kafka(pid) => (topic_name, *pid),
+        PartitionId::KafkaTopic(topic, pid) => (topic, *pid),
+        PartitionId::None => panic!("unexpected partition id type"),
+    }
+}
+
 /// Return the list of partition ids associated with a specific topic
 fn get_kafka_partitions<C>(
     consumer: &BaseConsumer<C>,
@@ -824,13 +903,6 @@ where
         .get(0)
         .context("expected a topic in the metadata result")?;
 
-    fn check_err(err: Option<RDKafkaRespErr>) -> anyhow::Result<()> {
-        if let Some(err) = err {
-            Err(RDKafkaErrorCode::from(err))?
-        }
-        Ok(())
-    }
-
     check_err(topic_meta.error())?;
 
     let mut partition_ids = Vec::with_capacity(topic_meta.partitions().len());
@@ -842,6 +914,57 @@ where
     Ok(partition_ids)
 }
 
+/// Return the list of topics and partition ids of all topics whose names
+/// match `pattern`
+///
+/// Topics whose metadata reports an error are logged and skipped, so that a
+/// single broken topic does not prevent discovering partitions of the others.
+/// They will be picked up again on a later refresh once their metadata is
+/// healthy.
+fn get_kafka_topic_partitions<C>(
+    consumer: &BaseConsumer<C>,
+    pattern: &Regex,
+    timeout: Duration,
+) -> Result<Vec<(String, i32)>, anyhow::Error>
+where
+    C: ConsumerContext,
+{
+    let metadata = consumer.fetch_metadata(None, timeout)?;
+
+    let mut partition_ids = Vec::new();
+    for topic_meta in metadata.topics() {
+        if !pattern.is_match(topic_meta.name()) {
+            continue;
+        }
+        let topic_partitions = check_err(topic_meta.error()).and_then(|()| {
+            topic_meta
+                .partitions()
+                .iter()
+                .map(|partition_meta| {
+                    check_err(partition_meta.error())?;
+                    Ok((topic_meta.name().to_string(), partition_meta.id()))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()
+        });
+        match topic_partitions {
+            Ok(topic_partitions) => partition_ids.extend(topic_partitions),
+            Err(e) => warn!(
+                "skipping topic {} while fetching kafka metadata: {}",
+                topic_meta.name(),
+                e
+            ),
+        }
+    }
+    Ok(partition_ids)
+}
+
+fn check_err(err: Option<RDKafkaRespErr>) -> anyhow::Result<()> {
+    if let Some(err) = err {
+        Err(RDKafkaErrorCode::from(err))?
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -33,7 +33,7 @@ use mz_storage_client::types::sources::{KinesisSourceConnection, MzOffset};
 
 use crate::source::commit::LogCommitter;
 use crate::source::metrics::KinesisMetrics;
-use crate::source::types::SourceConnectionBuilder;
+use crate::source::types::{MessageOrigin, SourceConnectionBuilder};
 use crate::source::{
     NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
 };
@@ -66,6 +66,9 @@ pub struct KinesisSourceReader {
     processed_message_count: u64,
     /// Metrics from which per-shard metrics get created.
     base_metrics: KinesisMetrics,
+    /// Whether or not to pass the shard ID and sequence number of each record through in the
+    /// `SourceMessage`
+    include_origin: bool,
     // Kinesis sources support single-threaded ingestion only, so only one of
     // the `KinesisSourceReader`s will actually produce data.
     active_read_worker: bool,
@@ -150,6 +153,9 @@ impl SourceConnectionBuilder for KinesisSourceConnection {
         let active_read_worker =
             crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
 
+        let include_origin =
+            self.include_shard_id.is_some() || self.include_sequence_number.is_some();
+
         // TODO: This creates all the machinery, even for the non-active workers.
         // We could change that to only spin up Kinesis when needed.
         let state = TokioHandle::current().block_on(create_state(
@@ -171,6 +177,7 @@ impl SourceConnectionBuilder for KinesisSourceConnection {
                     stream_name,
                     processed_message_count: 0,
                     base_metrics: metrics.kinesis,
+                    include_origin,
                     active_read_worker,
                     reported_unconsumed_partitions: false,
                 },
@@ -313,12 +320,21 @@ impl SourceReader for KinesisSourceReader {
                             PartitionId::None,
                             MzOffset::from(self.processed_message_count),
                         );
+                        let origin = if self.include_origin {
+                            Some(MessageOrigin::Kinesis {
+                                shard_id: shard_id.clone(),
+                                sequence_number: record.sequence_number.unwrap_or_default(),
+                            })
+                        } else {
+                            None
+                        };
                         let source_message = SourceMessage {
                             output: 0,
                             upstream_time_millis: None,
                             key: (),
                             value: Some(data),
                             headers: None,
+                            origin,
                         };
                         self.buffered_messages.push_back((source_message, ts));
                     }
@@ -349,6 +349,7 @@ impl SourceReader for PostgresSourceReader {
                         key: (),
                         value,
                         headers: None,
+                        origin: None,
                     };
                     let ts = (PartitionId::None, lsn.into());
                     NextMessage::Ready(SourceMessageType::Finalized(Ok(msg), ts, diff))
@@ -359,6 +360,7 @@ impl SourceReader for PostgresSourceReader {
                         key: (),
                         value,
                         headers: None,
+                        origin: None,
                     };
                     let ts = (PartitionId::None, lsn.into());
                     NextMessage::Ready(SourceMessageType::InProgress(Ok(msg), ts, diff))
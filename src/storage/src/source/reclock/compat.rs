@@ -191,12 +191,19 @@ impl PersistHandle {
 /// A binding of None partition is encoded as a single datum containing the offset.
 ///
 /// A binding of a Kafka partition is encoded as the partition datum followed by the offset datum.
+///
+/// A binding of a partition of one of several Kafka topics is encoded as the topic datum, followed
+/// by the partition datum and the offset datum.
 fn pack_binding(pid: PartitionId, offset: MzOffset) -> SourceData {
-    let mut row = Row::with_capacity(2);
+    let mut row = Row::with_capacity(3);
     let mut packer = row.packer();
     match pid {
         PartitionId::None => {}
         PartitionId::Kafka(pid) => packer.push(Datum::Int32(pid)),
+        PartitionId::KafkaTopic(topic, pid) => {
+            packer.push(Datum::String(&topic));
+            packer.push(Datum::Int32(pid));
+        }
     }
     packer.push(Datum::UInt64(offset.offset));
     SourceData(Ok(row))
@@ -207,9 +214,14 @@ fn pack_binding(pid: PartitionId, offset: MzOffset) -> SourceData {
 fn unpack_binding(data: SourceData) -> (PartitionId, MzOffset) {
     let row = data.0.expect("invalid binding");
     let mut datums = row.iter();
-    let (pid, offset) = match (datums.next(), datums.next()) {
-        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => (PartitionId::Kafka(p), offset),
-        (Some(Datum::UInt64(offset)), None) => (PartitionId::None, offset),
+    let (pid, offset) = match (datums.next(), datums.next(), datums.next()) {
+        (Some(Datum::String(topic)), Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => {
+            (PartitionId::KafkaTopic(topic.to_string(), p), offset)
+        }
+        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::Kafka(p), offset)
+        }
+        (Some(Datum::UInt64(offset)), None, None) => (PartitionId::None, offset),
         _ => panic!("invalid binding"),
     };
 
@@ -33,7 +33,7 @@ use std::default::Default;
 use std::ops::AddAssign;
 use std::sync::Arc;
 
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
 use aws_sdk_s3::error::{GetObjectError, ListObjectsV2Error};
 use aws_sdk_s3::types::SdkError;
 use aws_sdk_s3::Client as S3Client;
@@ -42,7 +42,7 @@ use aws_sdk_sqs::Client as SqsClient;
 use futures::{FutureExt, StreamExt, TryStreamExt};
 use globset::GlobMatcher;
 use timely::scheduling::SyncActivator;
-use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::{self, Duration};
 use tokio_util::io::{ReaderStream, StreamReader};
@@ -62,7 +62,7 @@ use mz_storage_client::types::sources::{Compression, MzOffset, S3KeySource, S3So
 use self::metrics::{BucketMetrics, ScanBucketMetrics};
 use self::notifications::{Event, EventType, TestEvent};
 use crate::source::commit::LogCommitter;
-use crate::source::types::SourceConnectionBuilder;
+use crate::source::types::{MessageOrigin, SourceConnectionBuilder};
 use crate::source::{
     NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
 };
@@ -74,6 +74,15 @@ mod notifications;
 
 struct InternalMessage {
     record: Option<Vec<u8>>,
+    /// The object the record was read from
+    object: Arc<ObjectInfo>,
+}
+
+/// Information about an S3 object that is attached to every record read from it
+struct ObjectInfo {
+    key: String,
+    /// When the object was last modified, in milliseconds since the unix epoch
+    last_modified_millis: Option<i64>,
 }
 /// Size of data chunks we send to dataflow
 const CHUNK_SIZE: usize = 4096;
@@ -92,6 +101,9 @@ pub struct S3SourceReader {
     /// Total number of records that this source has read
     offset: S3Offset,
 
+    /// Whether or not to pass the object key of each record through in the `SourceMessage`
+    include_key: bool,
+
     // S3 sources support single-threaded ingestion only, so only one of the
     // `S3SourceReader`s will actually produce data.
     active_read_worker: bool,
@@ -684,6 +696,8 @@ async fn download_object(
     compression: Compression,
     source_id: &str,
 ) -> Result<DownloadMetricUpdate, DownloadError> {
+    let last_modified_millis = std::sync::Mutex::new(None);
+    let last_modified_millis_ref = &last_modified_millis;
     let retry_reader: RetryReader<_, _, _> = RetryReader::new(|state, offset| async move {
         let range = if offset == 0 {
             None
@@ -715,14 +729,19 @@ async fn download_object(
         if let Some(s) = obj.content_encoding.as_deref() {
             match (s, compression) {
                 ("gzip", Compression::Gzip) => (),
+                ("zstd", Compression::Zstd) => (),
                 ("identity", Compression::None) => (),
-                ("identity" | "gzip", _) => {
+                ("identity" | "gzip" | "zstd", _) => {
                     debug!("object {} has mismatched Content-Encoding: {}", key, s)
                 }
                 _ => debug!("object {} has unrecognized Content-Encoding: {}", key, s),
             }
         }
 
+        if let Some(last_modified) = obj.last_modified.and_then(|t| t.to_millis().ok()) {
+            *last_modified_millis_ref.lock().expect("lock poisoned") = Some(last_modified);
+        }
+
         Ok(StreamReader::new(obj.body.map_err(|e| {
             std::io::Error::new(std::io::ErrorKind::Other, e)
         })))
@@ -741,12 +760,32 @@ async fn download_object(
         Err(err) => return Err(DownloadError::Failed { err }),
     };
 
+    // The first request for the object has completed, so its modification time is known.
+    let object = Arc::new(ObjectInfo {
+        key: key.to_string(),
+        last_modified_millis: *last_modified_millis.lock().expect("lock poisoned"),
+    });
+
     let mut download_result = match compression {
-        Compression::None => read_object_chunked(source_id, reader, tx).await,
+        Compression::None => read_object_chunked(source_id, reader, &object, tx).await,
         Compression::Gzip => {
             let decoder = GzipDecoder::new(reader);
-            read_object_chunked(source_id, decoder, tx).await
+            read_object_chunked(source_id, decoder, &object, tx).await
+        }
+        Compression::Zstd => {
+            let decoder = ZstdDecoder::new(reader);
+            read_object_chunked(source_id, decoder, &object, tx).await
+        }
+        Compression::Bzip2 => {
+            let decoder = BzDecoder::new(reader);
+            read_object_chunked(source_id, decoder, &object, tx).await
         }
+        Compression::Snappy => match read_object_snappy(reader).await {
+            Ok(decompressed) => {
+                read_object_chunked(source_id, &decompressed[..], &object, tx).await
+            }
+            Err(err) => Err(DownloadError::Failed { err }),
+        },
     };
 
     debug!(
@@ -755,7 +794,10 @@ async fn download_object(
     );
 
     if download_result.is_ok() {
-        let sent = tx.send(Ok(InternalMessage { record: None }));
+        let sent = tx.send(Ok(InternalMessage {
+            record: None,
+            object,
+        }));
         if sent.await.is_err() {
             download_result = Err(DownloadError::SendFailed);
         }
@@ -764,9 +806,30 @@ async fn download_object(
     download_result
 }
 
+/// Reads an entire object compressed with the Snappy framing format and
+/// returns its decompressed contents.
+///
+/// There is no streaming asynchronous Snappy decoder, so unlike the other
+/// compression formats the object is buffered in memory before it is
+/// decompressed.
+async fn read_object_snappy<R>(mut reader: R) -> Result<Vec<u8>, std::io::Error>
+where
+    R: Unpin + AsyncRead,
+{
+    let mut compressed = vec![];
+    reader.read_to_end(&mut compressed).await?;
+    let mut decompressed = vec![];
+    std::io::copy(
+        &mut snap::read::FrameDecoder::new(&compressed[..]),
+        &mut decompressed,
+    )?;
+    Ok(decompressed)
+}
+
 async fn read_object_chunked<R>(
     source_id: &str,
     reader: R,
+    object: &Arc<ObjectInfo>,
     tx: &Sender<Result<InternalMessage, S3Error>>,
 ) -> Result<DownloadMetricUpdate, DownloadError>
 where
@@ -787,6 +850,7 @@ where
                         // gives out 0 bytes, so the chunk is always !empty.
                         // See https://github.com/tokio-rs/tokio/blob/e8f19e771f501408427f7f9ee6ba4f54b2d4094c/tokio-util/src/io/reader_stream.rs#L102-L108
                         record: Some(chunk.to_vec()),
+                        object: Arc::clone(object),
                     }))
                     .await
                     .is_err()
@@ -828,6 +892,7 @@ impl SourceConnectionBuilder for S3SourceConnection {
     ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
         let active_read_worker =
             crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+        let include_key = self.include_key.is_some();
 
         // a single arbitrary worker is responsible for scanning the bucket
         let (receiver, shutdowner) = if active_read_worker {
@@ -911,6 +976,7 @@ impl SourceConnectionBuilder for S3SourceConnection {
                 receiver_stream: receiver,
                 dataflow_status: shutdowner,
                 offset: S3Offset(0),
+                include_key,
                 active_read_worker,
                 reported_unconsumed_partitions: false,
             },
@@ -940,14 +1006,22 @@ impl SourceReader for S3SourceReader {
         }
 
         match self.receiver_stream.recv().now_or_never() {
-            Some(Some(Ok(InternalMessage { record }))) => {
+            Some(Some(Ok(InternalMessage { record, object }))) => {
                 self.offset += 1;
+                let origin = if self.include_key {
+                    Some(MessageOrigin::S3 {
+                        key: object.key.clone(),
+                    })
+                } else {
+                    None
+                };
                 let msg = SourceMessage {
                     output: 0,
-                    upstream_time_millis: None,
+                    upstream_time_millis: object.last_modified_millis,
                     key: (),
                     value: record,
                     headers: None,
+                    origin,
                 };
                 let ts = (PartitionId::None, self.offset.into());
                 NextMessage::Ready(SourceMessageType::Finalized(Ok(msg), ts, ()))
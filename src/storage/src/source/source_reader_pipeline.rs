@@ -1595,6 +1595,7 @@ fn handle_message<K, V, D>(
                     message.upstream_time_millis,
                     time.0,
                     message.headers,
+                    message.origin,
                     diff,
                 )),
             )
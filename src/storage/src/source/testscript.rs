@@ -104,6 +104,7 @@ impl SourceReader for TestScriptSourceReader {
                         key: key.map(|k| k.into_bytes()),
                         value: Some(value.into_bytes()),
                         headers: None,
+                        origin: None,
                     });
                     let ts = (PartitionId::None, MzOffset::from(offset));
                     return Some(SourceMessageType::Finalized(msg, ts, ()));
@@ -214,6 +214,26 @@ pub struct SourceMessage<Key, Value> {
     /// Headers, if the source is configured to pass them along. If it is, but there are none, it
     /// passes `Some([])`
     pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+    /// Where the message came from in the upstream system, if the source is configured to pass
+    /// it along
+    pub origin: Option<MessageOrigin>,
+}
+
+/// Identifies where a message came from in the upstream system, beyond its partition and offset.
+///
+/// Sources only attach this to messages if it was requested with an `INCLUDE` clause, as it is
+/// expensive to clone these strings for every message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessageOrigin {
+    /// The Kafka topic the message was read from.
+    Kafka { topic: String },
+    /// The S3 object the message was read from.
+    S3 { key: String },
+    /// The Kinesis shard the record was read from, and its sequence number within that shard.
+    Kinesis {
+        shard_id: String,
+        sequence_number: String,
+    },
 }
 
 /// A record produced by a source
@@ -238,6 +258,9 @@ where
     /// Headers, if the source is configured to pass them along. If it is, but there are none, it
     /// passes `Some([])`
     pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+    /// Where the record came from in the upstream system, if the source is configured to pass
+    /// it along
+    pub origin: Option<MessageOrigin>,
 
     /// Indicator for what the differential `diff` value
     /// for this decoded message should be
@@ -257,6 +280,7 @@ where
         upstream_time_millis: Option<i64>,
         partition: PartitionId,
         headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+        origin: Option<MessageOrigin>,
         diff: D,
     ) -> SourceOutput<K, V, D> {
         SourceOutput {
@@ -266,6 +290,7 @@ where
             upstream_time_millis,
             partition,
             headers,
+            origin,
             diff,
         }
     }
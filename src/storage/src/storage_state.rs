@@ -8,6 +8,7 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -99,6 +100,8 @@ pub struct StorageState {
     pub sink_handles: HashMap<GlobalId, SinkHandle>,
     /// Collection ids that have been dropped but not yet reported as dropped
     pub dropped_ids: Vec<GlobalId>,
+    /// A directory in which dataflows can keep ephemeral state on local disk.
+    pub scratch_directory: PathBuf,
 }
 
 /// This maintains an additional read hold on the source data for a sink, alongside
@@ -164,6 +164,7 @@ where
                 sink_write_frontiers: HashMap::new(),
                 sink_handles: HashMap::new(),
                 dropped_ids: Vec::new(),
+                scratch_directory: std::env::temp_dir(),
             };
 
             let (_fake_tx, fake_rx) = crossbeam_channel::bounded(1);
@@ -177,6 +178,7 @@ where
                 remap_shard: mz_persist_client::ShardId::new(),
                 data_shard: mz_persist_client::ShardId::new(),
                 status_shard: None,
+                relation_desc: mz_repr::RelationDesc::empty(),
             };
             let data_shard = collection_metadata.data_shard.clone();
             let id = GlobalId::User(1);
@@ -707,6 +707,7 @@ impl Run for PosCommand {
                     "s3-put-object" => s3::run_put_object(builtin, state).await,
                     "s3-delete-objects" => s3::run_delete_object(builtin, state).await,
                     "s3-add-notifications" => s3::run_add_notifications(builtin, state).await,
+                    "s3-verify-data" => s3::run_verify_data(builtin, state).await,
                     "set-regex" => set::run_regex_set(builtin, state),
                     "unset-regex" => set::run_regex_unset(builtin, state),
                     "set-sql-timeout" => set::run_sql_timeout(builtin, state),
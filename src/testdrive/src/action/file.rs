@@ -11,7 +11,7 @@ use std::path;
 use std::str::FromStr;
 
 use anyhow::bail;
-use async_compression::tokio::write::GzipEncoder;
+use async_compression::tokio::write::{BzEncoder, GzipEncoder, ZstdEncoder};
 use tokio::fs::{self, OpenOptions};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
@@ -21,6 +21,9 @@ use crate::parser::BuiltinCommand;
 
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
+    Snappy,
     None,
 }
 
@@ -30,6 +33,9 @@ impl FromStr for Compression {
     fn from_str(s: &str) -> Result<Self, anyhow::Error> {
         match s {
             "gzip" => Ok(Compression::Gzip),
+            "zstd" => Ok(Compression::Zstd),
+            "bzip2" => Ok(Compression::Bzip2),
+            "snappy" => Ok(Compression::Snappy),
             "none" => Ok(Compression::None),
             f => bail!("unknown compression format: {}", f),
         }
@@ -80,6 +86,9 @@ pub async fn run_append(
 
     let mut file: Box<dyn AsyncWrite + Unpin + Send> = match compression {
         Compression::Gzip => Box::new(GzipEncoder::new(file)),
+        Compression::Zstd => Box::new(ZstdEncoder::new(file)),
+        Compression::Bzip2 => Box::new(BzEncoder::new(file)),
+        Compression::Snappy => bail!("snappy compression is not supported for files"),
         Compression::None => Box::new(file),
     };
 
@@ -12,15 +12,20 @@ use std::io::Write;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
+use async_compression::tokio::write::{BzEncoder, ZstdEncoder};
 use aws_sdk_s3::error::{CreateBucketError, CreateBucketErrorKind};
 use aws_sdk_s3::model::{
     BucketLocationConstraint, CreateBucketConfiguration, Delete, NotificationConfiguration,
     ObjectIdentifier, QueueConfiguration,
 };
 use aws_sdk_s3::types::{ByteStream, SdkError};
+use aws_sdk_s3::Client as S3Client;
 use aws_sdk_sqs::model::{DeleteMessageBatchRequestEntry, QueueAttributeName};
 use flate2::write::GzEncoder;
 use flate2::Compression as Flate2Compression;
+use mz_ore::retry::Retry;
+use regex::Regex;
+use tokio::io::AsyncWriteExt;
 
 use crate::action::file::{build_compression, Compression};
 use crate::action::{ControlFlow, State};
@@ -98,6 +103,39 @@ pub async fn run_put_object(
                 .context("writing to gzip encoder")?;
             encoder.finish().context("writing to gzip encoder")
         }
+        Compression::Zstd => {
+            let mut encoder = ZstdEncoder::new(Vec::new());
+            encoder
+                .write_all(buffer.as_ref())
+                .await
+                .context("writing to zstd encoder")?;
+            encoder
+                .shutdown()
+                .await
+                .context("writing to zstd encoder")?;
+            Ok(encoder.into_inner())
+        }
+        Compression::Bzip2 => {
+            let mut encoder = BzEncoder::new(Vec::new());
+            encoder
+                .write_all(buffer.as_ref())
+                .await
+                .context("writing to bzip2 encoder")?;
+            encoder
+                .shutdown()
+                .await
+                .context("writing to bzip2 encoder")?;
+            Ok(encoder.into_inner())
+        }
+        Compression::Snappy => {
+            let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+            encoder
+                .write_all(buffer.as_ref())
+                .context("writing to snappy encoder")?;
+            encoder
+                .into_inner()
+                .map_err(|e| anyhow!("writing to snappy encoder: {}", e.error()))
+        }
     }?;
 
     state
@@ -107,8 +145,9 @@ pub async fn run_put_object(
         .body(ByteStream::from(contents))
         .content_type("application/octet-stream")
         .set_content_encoding(match compression {
-            Compression::None => None,
             Compression::Gzip => Some("gzip".to_string()),
+            Compression::Zstd => Some("zstd".to_string()),
+            Compression::Bzip2 | Compression::Snappy | Compression::None => None,
         })
         .key(&key)
         .send()
@@ -148,6 +187,135 @@ pub async fn run_delete_object(
     Ok(ControlFlow::Continue)
 }
 
+/// Verifies the data written by an S3 sink with `FORMAT CSV`.
+///
+/// Reads the sink's manifest, concatenates the data lines of every file it
+/// lists, and compares them against the command's input. Header lines are
+/// skipped.
+pub async fn run_verify_data(
+    mut cmd: BuiltinCommand,
+    state: &mut State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let bucket_prefix = format!("testdrive-{}", cmd.args.string("bucket")?);
+    let prefix = cmd.args.string("prefix")?;
+    let header = cmd.args.opt_bool("header")?.unwrap_or(false);
+    let sort_rows = cmd.args.opt_bool("sort-rows")?.unwrap_or(false);
+    cmd.args.done()?;
+
+    let bucket = format!("{}-{}", bucket_prefix, state.seed);
+    println!("Verifying S3 sink data in {}/{}", bucket, prefix);
+
+    let client = state.s3_client.clone();
+    let regex = state.regex.clone();
+    let regex_replacement = state.regex_replacement.clone();
+    let mut expected = cmd.input;
+    if sort_rows {
+        expected.sort();
+    }
+
+    Retry::default()
+        .max_duration(state.default_timeout)
+        .retry_async_canceling(|_| {
+            let client = client.clone();
+            let bucket = bucket.clone();
+            let prefix = prefix.clone();
+            let regex = regex.clone();
+            let regex_replacement = regex_replacement.clone();
+            let expected = expected.clone();
+            async move {
+                let mut actual =
+                    read_sink_lines(&client, &bucket, &prefix, header, regex, &regex_replacement)
+                        .await?;
+                if sort_rows {
+                    actual.sort();
+                }
+                if actual != expected {
+                    bail!(
+                        "S3 sink contents did not match\nexpected:\n{}\nactual:\n{}",
+                        expected.join("\n"),
+                        actual.join("\n")
+                    );
+                }
+                Ok(())
+            }
+        })
+        .await?;
+    Ok(ControlFlow::Continue)
+}
+
+async fn read_sink_lines(
+    client: &S3Client,
+    bucket: &str,
+    prefix: &str,
+    header: bool,
+    regex: Option<Regex>,
+    regex_replacement: &str,
+) -> Result<Vec<String>, anyhow::Error> {
+    let manifest = get_json_object(client, bucket, &format!("{}/manifest.json", prefix))
+        .await
+        .context("fetching S3 sink manifest")?;
+
+    // The manifest lists the most recent parts, and points to a chain of
+    // segments that list the parts written before them.
+    let mut segments = vec![manifest];
+    while let Some(previous) = segments.last().unwrap()["previous"].as_str() {
+        let previous = previous.to_string();
+        let segment = get_json_object(client, bucket, &previous)
+            .await
+            .context("fetching S3 sink manifest segment")?;
+        segments.push(segment);
+    }
+    let mut parts = vec![];
+    for segment in segments.iter().rev() {
+        parts.extend(
+            segment["parts"]
+                .as_array()
+                .ok_or_else(|| anyhow!("S3 sink manifest is missing parts"))?,
+        );
+    }
+
+    let mut lines = vec![];
+    for part in parts {
+        let key = part["key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("S3 sink manifest part is missing key"))?;
+        let object = client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("fetching S3 sink file {}", key))?;
+        let contents = object.body.collect().await?.into_bytes();
+        let contents = std::str::from_utf8(&contents).context("decoding S3 sink file")?;
+        let skip = if header { 1 } else { 0 };
+        for line in contents.lines().skip(skip) {
+            let line = match &regex {
+                Some(regex) => regex.replace_all(line, regex_replacement).to_string(),
+                None => line.to_string(),
+            };
+            lines.push(line);
+        }
+    }
+    Ok(lines)
+}
+
+async fn get_json_object(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let object = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .with_context(|| format!("fetching S3 object {}", key))?;
+    let contents = object.body.collect().await?.into_bytes();
+    serde_json::from_slice(&contents).with_context(|| format!("decoding S3 object {}", key))
+}
+
 pub struct AddBucketNotifications {
     bucket_prefix: String,
     queue_prefix: String,
@@ -70,6 +70,12 @@ impl Attribute for NonNegative {
                 let body = self.results[n - 1];
                 self.results.push(body);
             }
+            LetRec { .. } => {
+                // Recursive bindings are not tracked in the environment,
+                // so their uses are conservatively considered negative.
+                let body = self.results[n - 1];
+                self.results.push(body);
+            }
             Project { input: _, .. } => {
                 let input = self.results[n - 1];
                 self.results.push(input);
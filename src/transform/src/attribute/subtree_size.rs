@@ -42,6 +42,14 @@ impl Attribute for SubtreeSize {
                 let value = self.results[n - 1 - body];
                 self.results.push(body + value + 1);
             }
+            LetRec { values, .. } => {
+                // The body and each value, in reverse order.
+                let mut offset = 1;
+                for _ in 0..values.len() + 1 {
+                    offset += &self.results[n - offset];
+                }
+                self.results.push(offset);
+            }
             Project { input: _, .. } => {
                 let input = self.results[n - 1];
                 self.results.push(input + 1);
@@ -110,6 +110,24 @@ impl ColumnKnowledge {
                     }
                     Ok(body_knowledge)
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Recursive bindings may be used before their values are harvested,
+                    // so we assume nothing about them beyond what their types tell us.
+                    let prior_knowledge = ids
+                        .iter()
+                        .map(|id| knowledge.remove(&mz_expr::Id::Local(*id)))
+                        .collect::<Vec<_>>();
+                    for value in values.iter_mut() {
+                        self.harvest(value, knowledge, knowledge_stack)?;
+                    }
+                    let body_knowledge = self.harvest(body, knowledge, knowledge_stack)?;
+                    for (id, prior_knowledge) in ids.iter().zip(prior_knowledge) {
+                        if let Some(prior_knowledge) = prior_knowledge {
+                            knowledge.insert(mz_expr::Id::Local(*id), prior_knowledge);
+                        }
+                    }
+                    Ok(body_knowledge)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     let input_knowledge = self.harvest(input, knowledge, knowledge_stack)?;
                     Ok(outputs
@@ -57,9 +57,13 @@ impl crate::Transform for RelationCSE {
         relation: &mut MirRelationExpr,
         args: TransformArgs,
     ) -> Result<(), crate::TransformError> {
-        let mut bindings = Bindings::default();
-        bindings.intern_expression(relation)?;
-        bindings.populate_expression(relation);
+        // Interning flattens all bindings into a single sequence, which would
+        // move expressions out of the scope of recursive bindings they use.
+        if !relation.is_recursive() {
+            let mut bindings = Bindings::default();
+            bindings.intern_expression(relation)?;
+            bindings.populate_expression(relation);
+        }
         self.inline_let.transform_without_trace(relation)?;
         self.update_let.transform_without_trace(relation, args)?;
         mz_repr::explain_new::trace_plan(&*relation);
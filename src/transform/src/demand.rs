@@ -112,6 +112,25 @@ impl Demand {
 
                     self.action(value, needs, gets)
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Recursive bindings may be used before they are bound, and so
+                    // we conservatively demand all columns of each of their values.
+                    let prior = ids
+                        .iter()
+                        .map(|id| gets.insert(Id::Local(*id), HashSet::new()))
+                        .collect::<Vec<_>>();
+                    self.action(body, columns, gets)?;
+                    for value in values.iter_mut() {
+                        self.action(value, (0..value.arity()).collect(), gets)?;
+                    }
+                    for (id, prior) in ids.iter().zip(prior) {
+                        gets.remove(&Id::Local(*id));
+                        if let Some(prior) = prior {
+                            gets.insert(Id::Local(*id), prior);
+                        }
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Project { input, outputs } => self.action(
                     input,
                     columns.into_iter().map(|c| outputs[c]).collect(),
@@ -81,6 +81,7 @@ impl FoldConstants {
             MirRelationExpr::Constant { .. } => { /* handled after match */ }
             MirRelationExpr::Get { .. } => {}
             MirRelationExpr::Let { .. } => { /* constant prop done in InlineLet */ }
+            MirRelationExpr::LetRec { .. } => { /* recursive bindings are not constant */ }
             MirRelationExpr::Reduce {
                 input,
                 group_key,
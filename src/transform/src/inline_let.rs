@@ -147,6 +147,13 @@ impl InlineLet {
                 *relation = body.take_dangerous();
                 // might be another Let in the body so have to recur here
                 self.action(relation, lets)
+            } else if let MirRelationExpr::LetRec { values, body, .. } = relation {
+                // Bindings within a `LetRec` may reference its recursive bindings,
+                // and so must not be lifted out of it.
+                for value in values.iter_mut() {
+                    self.transform_without_trace(value)?;
+                }
+                self.transform_without_trace(body)
             } else {
                 relation.try_visit_mut_children(|child| self.action(child, lets))
             }
@@ -263,6 +263,17 @@ impl LiteralLifting {
                     gets.remove(&id);
                     result
                 }
+                MirRelationExpr::LetRec { values, body, .. } => {
+                    // Recursive bindings are used before their literals are known, so
+                    // any literals lifted from their values are re-installed in place.
+                    for value in values.iter_mut() {
+                        let literals = self.action(value, gets)?;
+                        if !literals.is_empty() {
+                            *value = value.take_dangerous().map(literals);
+                        }
+                    }
+                    self.action(body, gets)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     // We do not want to lift literals around projections.
                     // Projections are the highest lifted operator and lifting
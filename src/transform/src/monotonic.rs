@@ -116,6 +116,20 @@ impl MonotonicFlag {
                     }
                     result
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Recursive bindings are not known to be monotonic.
+                    let prior = ids.iter().map(|id| locals.remove(id)).collect::<Vec<_>>();
+                    for value in values.iter_mut() {
+                        self.apply(value, mon_ids, locals)?;
+                    }
+                    let result = self.apply(body, mon_ids, locals)?;
+                    for (id, prior) in ids.iter().zip(prior) {
+                        if prior {
+                            locals.insert(*id);
+                        }
+                    }
+                    result
+                }
                 // The default behavior.
                 // TODO: check that this is the behavior we want.
                 MirRelationExpr::Negate { .. } | MirRelationExpr::Constant { rows: Err(_), .. } => {
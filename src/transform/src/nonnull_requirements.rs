@@ -110,6 +110,25 @@ impl NonNullRequirements {
                     }
                     Ok(())
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Recursive bindings are used before their requirements are
+                    // known, so we push no non-null requirements at their values.
+                    let prior = ids
+                        .iter()
+                        .map(|id| gets.insert(Id::Local(*id), Vec::new()))
+                        .collect::<Vec<_>>();
+                    self.action(body, columns, gets)?;
+                    for value in values.iter_mut() {
+                        self.action(value, HashSet::new(), gets)?;
+                    }
+                    for (id, prior) in ids.iter().zip(prior) {
+                        gets.remove(&Id::Local(*id));
+                        if let Some(prior) = prior {
+                            gets.insert(Id::Local(*id), prior);
+                        }
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Project { input, outputs } => self.action(
                     input,
                     columns.into_iter().map(|c| outputs[c]).collect(),
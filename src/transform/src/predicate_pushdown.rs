@@ -467,6 +467,20 @@ impl PredicatePushdown {
                     // Continue recursively on the value.
                     self.action(value, get_predicates)
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Predicates cannot be pushed into recursive bindings, as their
+                    // values also contain uses of the bindings. We push predicates
+                    // within each value and the body, and discard what we learn
+                    // about uses of the bindings.
+                    for value in values.iter_mut() {
+                        self.action(value, get_predicates)?;
+                    }
+                    self.action(body, get_predicates)?;
+                    for id in ids.iter() {
+                        get_predicates.remove(&Id::Local(*id));
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Join {
                     inputs,
                     equivalences,
@@ -91,6 +91,14 @@ impl ProjectionLifting {
                     gets.remove(&id);
                     Ok(())
                 }
+                MirRelationExpr::LetRec { values, body, .. } => {
+                    // Projections are not lifted out of recursive bindings, as
+                    // their uses within the values would need to change too.
+                    for value in values.iter_mut() {
+                        self.action(value, gets)?;
+                    }
+                    self.action(body, gets)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     self.action(input, gets)?;
                     if let MirRelationExpr::Project {
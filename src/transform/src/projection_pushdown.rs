@@ -118,6 +118,26 @@ impl ProjectionPushdown {
                 )?;
                 desired_projection.clone()
             }
+            MirRelationExpr::LetRec { ids, values, body } => {
+                // Recursive bindings are used within their own values, so we do not
+                // narrow them, and instead push all of their columns at each value.
+                let prior = ids
+                    .iter()
+                    .map(|id| gets.insert(Id::Local(*id), BTreeSet::new()))
+                    .collect::<Vec<_>>();
+                self.action(body, desired_projection, gets)?;
+                for value in values.iter_mut() {
+                    let all_columns = (0..value.arity()).collect::<Vec<_>>();
+                    self.action(value, &all_columns, gets)?;
+                }
+                for (id, prior) in ids.iter().zip(prior) {
+                    gets.remove(&Id::Local(*id));
+                    if let Some(prior) = prior {
+                        gets.insert(Id::Local(*id), prior);
+                    }
+                }
+                desired_projection.clone()
+            }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
@@ -102,6 +102,24 @@ impl RedundantJoin {
                     }
                     Ok(result)
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // Recursive bindings are used before their values are known, so
+                    // we only record that their uses are exactly the bindings.
+                    let old = ids
+                        .iter()
+                        .map(|id| lets.remove(&Id::Local(*id)))
+                        .collect::<Vec<_>>();
+                    for value in values.iter_mut() {
+                        self.action(value, lets)?;
+                    }
+                    let result = self.action(body, lets)?;
+                    for (id, old) in ids.iter().zip(old) {
+                        if let Some(old) = old {
+                            lets.insert(Id::Local(*id), old);
+                        }
+                    }
+                    Ok(result)
+                }
                 MirRelationExpr::Get { id, typ } => {
                     // Extract the value provenance, or an empty list if unavailable.
                     let mut val_info = lets.get(id).cloned().unwrap_or_default();
@@ -13,7 +13,7 @@
 use std::collections::HashMap;
 
 use crate::TransformArgs;
-use mz_expr::visit::VisitChildren;
+use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::{Id, LocalId, MirRelationExpr, RECURSION_LIMIT};
 use mz_ore::id_gen::IdGen;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
@@ -95,6 +95,42 @@ impl UpdateLet {
                     *id = new_id;
                     Ok(())
                 }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    // The values of recursive bindings are not available at their uses,
+                    // so we retain the types already recorded on their `Get`s.
+                    let mut types = HashMap::new();
+                    for expr in values.iter().chain(std::iter::once(&**body)) {
+                        expr.visit_post(&mut |e| {
+                            if let MirRelationExpr::Get {
+                                id: Id::Local(get_id),
+                                typ,
+                            } = e
+                            {
+                                if ids.contains(get_id) {
+                                    types.entry(*get_id).or_insert_with(|| typ.clone());
+                                }
+                            }
+                        })?;
+                    }
+                    let mut prevs = Vec::with_capacity(ids.len());
+                    for (id, value) in ids.iter_mut().zip(values.iter()) {
+                        let new_id = LocalId::new(id_gen.allocate_id());
+                        let typ = types.remove(&*id).unwrap_or_else(|| value.typ());
+                        prevs.push((*id, remap.insert(*id, (new_id, typ))));
+                        *id = new_id;
+                    }
+                    for value in values.iter_mut() {
+                        self.action(value, remap, id_gen)?;
+                    }
+                    self.action(body, remap, id_gen)?;
+                    for (id, prev) in prevs {
+                        remap.remove(&id);
+                        if let Some(prev_stuff) = prev {
+                            remap.insert(id, prev_stuff);
+                        }
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Get { id, typ } => {
                     if let Id::Local(local_id) = id {
                         if let Some((new_id, new_type)) = remap.get(local_id) {